@@ -21,39 +21,36 @@ use std::sync::Arc;
 
 use datafusion::arrow::datatypes::Schema;
 
-use arrow_flight::flight_descriptor;
 use arrow_flight::flight_service_client::FlightServiceClient;
 use arrow_flight::utils::flight_data_to_arrow_batch;
-use arrow_flight::{FlightDescriptor, Ticket};
+use arrow_flight::FlightDescriptor;
 use datafusion::arrow::util::pretty;
 
-/// This example shows how to wrap DataFusion with `FlightService` to support looking up schema information for
-/// Parquet files and executing SQL queries against them on a remote server.
+/// This example shows how to look up the schema of a table served by DataFusion's
+/// `FlightService` implementation, and how to execute SQL queries against it.
 /// This example is run along-side the example `flight_server`.
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let testdata = datafusion::test_util::parquet_test_data();
-
     // Create Flight client
     let mut client = FlightServiceClient::connect("http://localhost:50051").await?;
 
-    // Call get_schema to get the schema of a Parquet file
-    let request = tonic::Request::new(FlightDescriptor {
-        r#type: flight_descriptor::DescriptorType::Path as i32,
-        cmd: vec![],
-        path: vec![format!("{}/alltypes_plain.parquet", testdata)],
-    });
+    // Call get_schema to get the schema of a registered table
+    let request = tonic::Request::new(FlightDescriptor::new_path(vec![
+        "alltypes_plain".to_string()
+    ]));
 
     let schema_result = client.get_schema(request).await?.into_inner();
     let schema = Schema::try_from(&schema_result)?;
     println!("Schema: {:?}", schema);
 
-    // Call do_get to execute a SQL query and receive results
-    let request = tonic::Request::new(Ticket {
-        ticket: "SELECT id FROM alltypes_plain".into(),
-    });
+    // Call get_flight_info to plan a SQL query, and do_get to receive its results
+    let request = tonic::Request::new(FlightDescriptor::new_cmd(
+        "SELECT id FROM alltypes_plain".as_bytes().to_vec(),
+    ));
+    let flight_info = client.get_flight_info(request).await?.into_inner();
+    let ticket = flight_info.endpoint[0].ticket.clone().unwrap();
 
-    let mut stream = client.do_get(request).await?.into_inner();
+    let mut stream = client.do_get(ticket).await?.into_inner();
 
     // the schema should be the first message returned, else client should error
     let flight_data = stream.message().await?.unwrap();
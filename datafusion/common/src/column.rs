@@ -17,7 +17,7 @@
 
 //! Column
 
-use crate::{DFSchema, DataFusionError, Result, SchemaError};
+use crate::{ambiguous_column, DFSchema, DataFusionError, Result, SchemaError};
 use std::collections::HashSet;
 use std::convert::Infallible;
 use std::fmt;
@@ -123,6 +123,14 @@ impl Column {
                             return Ok(fields[0].qualified_column());
                         }
                     }
+
+                    // Otherwise the fields belong to different relations, such
+                    // as the `id` columns of both sides of `t1 JOIN t2 ON
+                    // t1.id = t2.id`, which can only be told apart by qualifier
+                    return Err(ambiguous_column(
+                        &self.name,
+                        fields.iter().map(|f| f.qualified_column()).collect(),
+                    ));
                 }
             }
         }
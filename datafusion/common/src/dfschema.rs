@@ -23,7 +23,7 @@ use std::convert::TryFrom;
 use std::sync::Arc;
 
 use crate::error::{DataFusionError, Result, SchemaError};
-use crate::{field_not_found, Column, TableReference};
+use crate::{ambiguous_column, field_not_found, Column, TableReference};
 
 use arrow::compute::can_cast_types;
 use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
@@ -229,6 +229,10 @@ impl DFSchema {
             )),
             Some(idx) => match matches.next() {
                 None => Ok(idx),
+                // found more than one matches of a name without qualifier
+                Some(_) if qualifier.is_none() => {
+                    Err(self.ambiguous_unqualified_name(name))
+                }
                 // found more than one matches
                 Some(_) => Err(DataFusionError::Internal(format!(
                     "Ambiguous reference to qualified field named '{}.{}'",
@@ -279,15 +283,21 @@ impl DFSchema {
         match matches.len() {
             0 => Err(field_not_found(None, name, self)),
             1 => Ok(matches[0]),
-            _ => Err(DataFusionError::SchemaError(
-                SchemaError::AmbiguousReference {
-                    qualifier: None,
-                    name: name.to_string(),
-                },
-            )),
+            _ => Err(self.ambiguous_unqualified_name(name)),
         }
     }
 
+    /// The error of a reference to the unqualified `name` of several
+    /// fields, listing their qualified names
+    fn ambiguous_unqualified_name(&self, name: &str) -> DataFusionError {
+        let candidates = self
+            .fields_with_unqualified_name(name)
+            .into_iter()
+            .map(|field| field.qualified_column())
+            .collect();
+        ambiguous_column(name, candidates)
+    }
+
     /// Find the field with the given qualified name
     pub fn field_with_qualified_name(
         &self,
@@ -561,6 +571,12 @@ impl DFField {
         self.field.is_nullable()
     }
 
+    /// Returns this `DFField` marked as supporting null values or not
+    pub fn with_nullable(mut self, nullable: bool) -> Self {
+        self.field = self.field.with_nullable(nullable);
+        self
+    }
+
     /// Returns a string to the `DFField`'s qualified name
     pub fn qualified_name(&self) -> String {
         if let Some(qualifier) = &self.qualifier {
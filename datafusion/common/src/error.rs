@@ -126,6 +126,11 @@ pub enum SchemaError {
         field: Column,
         valid_fields: Option<Vec<Column>>,
     },
+    /// An unqualified name refers to several fields, of different relations
+    AmbiguousColumn {
+        name: String,
+        candidates: Vec<Column>,
+    },
 }
 
 /// Create a "field not found" DataFusion::SchemaError
@@ -146,6 +151,23 @@ pub fn field_not_found(
     })
 }
 
+/// Create an "ambiguous column" DataFusion::SchemaError for the unqualified
+/// `name` matching all of `candidates`
+pub fn ambiguous_column(name: &str, candidates: Vec<Column>) -> DataFusionError {
+    DataFusionError::SchemaError(SchemaError::AmbiguousColumn {
+        name: name.to_string(),
+        candidates,
+    })
+}
+
+/// Formats `column` as `'relation'.'name'`, or `'name'` if unqualified
+fn quote_column(column: &Column) -> String {
+    match &column.relation {
+        Some(q) => format!("'{}'.'{}'", q, column.name),
+        None => format!("'{}'", column.name),
+    }
+}
+
 impl Display for SchemaError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -153,25 +175,14 @@ impl Display for SchemaError {
                 field,
                 valid_fields,
             } => {
-                write!(f, "No field named ")?;
-                if let Some(q) = &field.relation {
-                    write!(f, "'{}'.'{}'", q, field.name)?;
-                } else {
-                    write!(f, "'{}'", field.name)?;
-                }
+                write!(f, "No field named {}", quote_column(field))?;
                 if let Some(fields) = valid_fields {
                     write!(
                         f,
                         ". Valid fields are {}",
                         fields
                             .iter()
-                            .map(|field| {
-                                if let Some(q) = &field.relation {
-                                    format!("'{}'.'{}'", q, field.name)
-                                } else {
-                                    format!("'{}'", field.name)
-                                }
-                            })
+                            .map(quote_column)
                             .collect::<Vec<String>>()
                             .join(", ")
                     )?;
@@ -199,6 +210,18 @@ impl Display for SchemaError {
                     write!(f, "Ambiguous reference to unqualified field '{}'", name)
                 }
             }
+            Self::AmbiguousColumn { name, candidates } => {
+                write!(
+                    f,
+                    "Ambiguous reference to unqualified field '{}', which could be any of {}",
+                    name,
+                    candidates
+                        .iter()
+                        .map(quote_column)
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                )
+            }
         }
     }
 }
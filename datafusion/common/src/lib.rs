@@ -25,6 +25,7 @@ pub mod from_slice;
 pub mod parsers;
 #[cfg(feature = "pyarrow")]
 mod pyarrow;
+pub mod rows;
 pub mod scalar;
 pub mod stats;
 mod table_reference;
@@ -32,7 +33,9 @@ pub mod test_util;
 
 pub use column::Column;
 pub use dfschema::{DFField, DFSchema, DFSchemaRef, ExprSchema, ToDFSchema};
-pub use error::{field_not_found, DataFusionError, Result, SchemaError};
+pub use error::{
+    ambiguous_column, field_not_found, DataFusionError, Result, SchemaError,
+};
 pub use parsers::parse_interval;
 pub use scalar::{ScalarType, ScalarValue};
 pub use stats::{ColumnStatistics, Statistics};
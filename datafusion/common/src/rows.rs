@@ -0,0 +1,543 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Row by row access to the values of a [`RecordBatch`], with typed getters
+//! that check the column types instead of requiring downcasts.
+//!
+//! ```
+//! # use std::sync::Arc;
+//! # use arrow::array::{ArrayRef, Int64Array, StringArray};
+//! # use arrow::record_batch::RecordBatch;
+//! # use datafusion_common::rows::rows;
+//! let batch = RecordBatch::try_from_iter(vec![
+//!     ("id", Arc::new(Int64Array::from(vec![Some(1), None])) as ArrayRef),
+//!     ("name", Arc::new(StringArray::from(vec!["a", "b"])) as ArrayRef),
+//! ])
+//! .unwrap();
+//!
+//! let values = rows(&batch)
+//!     .map(|row| Ok((row.get_i64(0)?, row.get_str(1)?)))
+//!     .collect::<datafusion_common::Result<Vec<_>>>()
+//!     .unwrap();
+//! assert_eq!(values, vec![(Some(1), Some("a")), (None, Some("b"))]);
+//! ```
+
+use arrow::array::*;
+use arrow::datatypes::DataType;
+use arrow::record_batch::RecordBatch;
+
+use crate::{DataFusionError, Result, ScalarValue};
+
+/// Returns an iterator over the rows of `batch`
+pub fn rows(batch: &RecordBatch) -> Rows<'_> {
+    Rows {
+        batch,
+        next: 0,
+        end: batch.num_rows(),
+    }
+}
+
+/// Iterator over the rows of a [`RecordBatch`], see [`rows`]
+#[derive(Debug, Clone)]
+pub struct Rows<'a> {
+    batch: &'a RecordBatch,
+    next: usize,
+    end: usize,
+}
+
+impl<'a> Iterator for Rows<'a> {
+    type Item = RowAccessor<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next == self.end {
+            return None;
+        }
+        let row = RowAccessor::new(self.batch, self.next);
+        self.next += 1;
+        Some(row)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.end - self.next;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a> DoubleEndedIterator for Rows<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.next == self.end {
+            return None;
+        }
+        self.end -= 1;
+        Some(RowAccessor::new(self.batch, self.end))
+    }
+}
+
+impl<'a> ExactSizeIterator for Rows<'a> {}
+
+/// Generates a getter for the values of a primitive column
+macro_rules! primitive_getter {
+    ($(#[$doc:meta])* $NAME:ident, $ARRAY:ty, $NATIVE:ty, $DATA_TYPE:expr) => {
+        $(#[$doc])*
+        pub fn $NAME(&self, idx: usize) -> Result<Option<$NATIVE>> {
+            let array = self.typed_column::<$ARRAY>(idx, &$DATA_TYPE)?;
+            Ok(array.is_valid(self.row).then(|| array.value(self.row)))
+        }
+    };
+}
+
+/// A view of one row of a [`RecordBatch`].
+///
+/// The typed getters return `Ok(None)` for null values and an error naming
+/// the expected and actual [`DataType`] if the column has another type.
+/// [`Self::get_scalar`] reads values of any type.
+///
+/// Not to be confused with the accessor of the `datafusion-row` crate, which
+/// reads rows encoded in the row format.
+#[derive(Debug, Clone, Copy)]
+pub struct RowAccessor<'a> {
+    batch: &'a RecordBatch,
+    row: usize,
+}
+
+impl<'a> RowAccessor<'a> {
+    /// Creates a view of the row at index `row` of `batch`, panics if it is
+    /// out of bounds
+    pub fn new(batch: &'a RecordBatch, row: usize) -> Self {
+        assert!(
+            row < batch.num_rows(),
+            "row index {} out of bounds for a batch of {} rows",
+            row,
+            batch.num_rows()
+        );
+        Self { batch, row }
+    }
+
+    /// Returns the index of this row in its batch
+    pub fn row_index(&self) -> usize {
+        self.row
+    }
+
+    /// Returns the number of columns of the row
+    pub fn len(&self) -> usize {
+        self.batch.num_columns()
+    }
+
+    /// Returns true if the row has no columns
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the index of the column named `name`
+    pub fn index_of(&self, name: &str) -> Result<usize> {
+        Ok(self.batch.schema().index_of(name)?)
+    }
+
+    /// Returns true if the value of column `idx` is null
+    pub fn is_null(&self, idx: usize) -> Result<bool> {
+        Ok(self.column(idx)?.is_null(self.row))
+    }
+
+    /// Returns the value of column `idx`, of any type
+    pub fn get_scalar(&self, idx: usize) -> Result<ScalarValue> {
+        ScalarValue::try_from_array(self.column(idx)?, self.row)
+    }
+
+    primitive_getter!(
+        /// Returns the value of `Boolean` column `idx`
+        get_bool, BooleanArray, bool, DataType::Boolean
+    );
+    primitive_getter!(
+        /// Returns the value of `Int8` column `idx`
+        get_i8, Int8Array, i8, DataType::Int8
+    );
+    primitive_getter!(
+        /// Returns the value of `Int16` column `idx`
+        get_i16, Int16Array, i16, DataType::Int16
+    );
+    primitive_getter!(
+        /// Returns the value of `Int32` column `idx`
+        get_i32, Int32Array, i32, DataType::Int32
+    );
+    primitive_getter!(
+        /// Returns the value of `Int64` column `idx`
+        get_i64, Int64Array, i64, DataType::Int64
+    );
+    primitive_getter!(
+        /// Returns the value of `UInt8` column `idx`
+        get_u8, UInt8Array, u8, DataType::UInt8
+    );
+    primitive_getter!(
+        /// Returns the value of `UInt16` column `idx`
+        get_u16, UInt16Array, u16, DataType::UInt16
+    );
+    primitive_getter!(
+        /// Returns the value of `UInt32` column `idx`
+        get_u32, UInt32Array, u32, DataType::UInt32
+    );
+    primitive_getter!(
+        /// Returns the value of `UInt64` column `idx`
+        get_u64, UInt64Array, u64, DataType::UInt64
+    );
+    primitive_getter!(
+        /// Returns the value of `Float32` column `idx`
+        get_f32, Float32Array, f32, DataType::Float32
+    );
+    primitive_getter!(
+        /// Returns the value of `Float64` column `idx`
+        get_f64, Float64Array, f64, DataType::Float64
+    );
+    primitive_getter!(
+        /// Returns the value of `Date32` column `idx`, in days since the epoch
+        get_date32, Date32Array, i32, DataType::Date32
+    );
+    primitive_getter!(
+        /// Returns the value of `Date64` column `idx`, in milliseconds since the epoch
+        get_date64, Date64Array, i64, DataType::Date64
+    );
+
+    /// Returns the value of `Utf8` or `LargeUtf8` column `idx`
+    pub fn get_str(&self, idx: usize) -> Result<Option<&'a str>> {
+        Ok(match self.column(idx)?.data_type() {
+            DataType::LargeUtf8 => {
+                let array =
+                    self.typed_column::<LargeStringArray>(idx, &DataType::LargeUtf8)?;
+                array.is_valid(self.row).then(|| array.value(self.row))
+            }
+            _ => {
+                let array = self.typed_column::<StringArray>(idx, &DataType::Utf8)?;
+                array.is_valid(self.row).then(|| array.value(self.row))
+            }
+        })
+    }
+
+    /// Returns the value of `Binary` or `LargeBinary` column `idx`
+    pub fn get_binary(&self, idx: usize) -> Result<Option<&'a [u8]>> {
+        Ok(match self.column(idx)?.data_type() {
+            DataType::LargeBinary => {
+                let array =
+                    self.typed_column::<LargeBinaryArray>(idx, &DataType::LargeBinary)?;
+                array.is_valid(self.row).then(|| array.value(self.row))
+            }
+            _ => {
+                let array = self.typed_column::<BinaryArray>(idx, &DataType::Binary)?;
+                array.is_valid(self.row).then(|| array.value(self.row))
+            }
+        })
+    }
+
+    /// Returns the value of any `Timestamp` column `idx` in the unit of the
+    /// column, without its time zone
+    pub fn get_timestamp(&self, idx: usize) -> Result<Option<i64>> {
+        let column = self.column(idx)?;
+        if !matches!(column.data_type(), DataType::Timestamp(_, _)) {
+            return Err(DataFusionError::Execution(format!(
+                "Expected column {} to be a Timestamp, got {}",
+                idx,
+                column.data_type()
+            )));
+        }
+        Ok(match self.get_scalar(idx)? {
+            ScalarValue::TimestampSecond(v, _)
+            | ScalarValue::TimestampMillisecond(v, _)
+            | ScalarValue::TimestampMicrosecond(v, _)
+            | ScalarValue::TimestampNanosecond(v, _) => v,
+            other => {
+                return Err(DataFusionError::Internal(format!(
+                    "Unexpected timestamp value {:?}",
+                    other
+                )))
+            }
+        })
+    }
+
+    fn column(&self, idx: usize) -> Result<&'a ArrayRef> {
+        let batch: &'a RecordBatch = self.batch;
+        if idx >= batch.num_columns() {
+            return Err(DataFusionError::Execution(format!(
+                "Column index {} out of bounds for a row of {} columns",
+                idx,
+                batch.num_columns()
+            )));
+        }
+        Ok(batch.column(idx))
+    }
+
+    /// Downcasts column `idx`, which must have the `expected` type
+    fn typed_column<T: Array + 'static>(
+        &self,
+        idx: usize,
+        expected: &DataType,
+    ) -> Result<&'a T> {
+        let column = self.column(idx)?;
+        if column.data_type() != expected {
+            let name = self.batch.schema().field(idx).name().clone();
+            return Err(DataFusionError::Execution(format!(
+                "Expected column {} ('{}') to be {}, got {}",
+                idx,
+                name,
+                expected,
+                column.data_type()
+            )));
+        }
+        column.as_any().downcast_ref::<T>().ok_or_else(|| {
+            DataFusionError::Internal(format!(
+                "could not cast column {} to {}",
+                idx,
+                std::any::type_name::<T>()
+            ))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow::datatypes::{Field, Int32Type};
+
+    use super::*;
+
+    fn all_types_batch() -> RecordBatch {
+        let struct_array = StructArray::from(vec![
+            (
+                Field::new("x", DataType::Int32, true),
+                Arc::new(Int32Array::from(vec![Some(1), None])) as ArrayRef,
+            ),
+            (
+                Field::new("y", DataType::Utf8, true),
+                Arc::new(StringArray::from(vec![Some("s"), Some("t")])) as ArrayRef,
+            ),
+        ]);
+        let list_array = ListArray::from_iter_primitive::<Int32Type, _, _>(vec![
+            Some(vec![Some(1), None, Some(3)]),
+            None,
+        ]);
+
+        RecordBatch::try_from_iter(vec![
+            (
+                "bool",
+                Arc::new(BooleanArray::from(vec![Some(true), None])) as ArrayRef,
+            ),
+            ("i8", Arc::new(Int8Array::from(vec![Some(-8), None])) as _),
+            (
+                "i16",
+                Arc::new(Int16Array::from(vec![Some(-16), None])) as _,
+            ),
+            (
+                "i32",
+                Arc::new(Int32Array::from(vec![Some(-32), None])) as _,
+            ),
+            (
+                "i64",
+                Arc::new(Int64Array::from(vec![Some(-64), None])) as _,
+            ),
+            ("u8", Arc::new(UInt8Array::from(vec![Some(8), None])) as _),
+            (
+                "u16",
+                Arc::new(UInt16Array::from(vec![Some(16), None])) as _,
+            ),
+            (
+                "u32",
+                Arc::new(UInt32Array::from(vec![Some(32), None])) as _,
+            ),
+            (
+                "u64",
+                Arc::new(UInt64Array::from(vec![Some(64), None])) as _,
+            ),
+            (
+                "f32",
+                Arc::new(Float32Array::from(vec![Some(1.5), None])) as _,
+            ),
+            (
+                "f64",
+                Arc::new(Float64Array::from(vec![Some(2.5), None])) as _,
+            ),
+            (
+                "date32",
+                Arc::new(Date32Array::from(vec![Some(1), None])) as _,
+            ),
+            (
+                "date64",
+                Arc::new(Date64Array::from(vec![Some(2), None])) as _,
+            ),
+            (
+                "utf8",
+                Arc::new(StringArray::from(vec![Some("a"), None])) as _,
+            ),
+            (
+                "large_utf8",
+                Arc::new(LargeStringArray::from(vec![Some("b"), None])) as _,
+            ),
+            (
+                "binary",
+                Arc::new(BinaryArray::from(vec![Some(b"c".as_ref()), None])) as _,
+            ),
+            (
+                "large_binary",
+                Arc::new(LargeBinaryArray::from(vec![Some(b"d".as_ref()), None])) as _,
+            ),
+            (
+                "ts",
+                Arc::new(TimestampMillisecondArray::from(vec![Some(3), None])) as _,
+            ),
+            ("struct", Arc::new(struct_array) as _),
+            ("list", Arc::new(list_array) as _),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn typed_getters() -> Result<()> {
+        let batch = all_types_batch();
+        let mut rows = rows(&batch);
+        assert_eq!(rows.len(), 2);
+
+        let row = rows.next().unwrap();
+        assert_eq!(row.row_index(), 0);
+        assert_eq!(row.len(), 20);
+        assert_eq!(row.get_bool(0)?, Some(true));
+        assert_eq!(row.get_i8(1)?, Some(-8));
+        assert_eq!(row.get_i16(2)?, Some(-16));
+        assert_eq!(row.get_i32(3)?, Some(-32));
+        assert_eq!(row.get_i64(4)?, Some(-64));
+        assert_eq!(row.get_u8(5)?, Some(8));
+        assert_eq!(row.get_u16(6)?, Some(16));
+        assert_eq!(row.get_u32(7)?, Some(32));
+        assert_eq!(row.get_u64(8)?, Some(64));
+        assert_eq!(row.get_f32(9)?, Some(1.5));
+        assert_eq!(row.get_f64(10)?, Some(2.5));
+        assert_eq!(row.get_date32(11)?, Some(1));
+        assert_eq!(row.get_date64(12)?, Some(2));
+        assert_eq!(row.get_str(13)?, Some("a"));
+        assert_eq!(row.get_str(row.index_of("large_utf8")?)?, Some("b"));
+        assert_eq!(row.get_binary(15)?, Some(b"c".as_ref()));
+        assert_eq!(row.get_binary(16)?, Some(b"d".as_ref()));
+        assert_eq!(row.get_timestamp(17)?, Some(3));
+
+        // the second row is all nulls, except for the struct
+        let row = rows.next().unwrap();
+        assert!(rows.next().is_none());
+        assert_eq!(row.get_bool(0)?, None);
+        assert_eq!(row.get_i64(4)?, None);
+        assert_eq!(row.get_u64(8)?, None);
+        assert_eq!(row.get_f64(10)?, None);
+        assert_eq!(row.get_str(13)?, None);
+        assert_eq!(row.get_str(14)?, None);
+        assert_eq!(row.get_binary(16)?, None);
+        assert_eq!(row.get_timestamp(17)?, None);
+        assert!(!row.is_null(18)?);
+        assert!(row.is_null(19)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn scalar_getter() -> Result<()> {
+        let batch = all_types_batch();
+        let struct_fields = vec![
+            Field::new("x", DataType::Int32, true),
+            Field::new("y", DataType::Utf8, true),
+        ];
+
+        let scalars = rows(&batch)
+            .map(|row| {
+                (0..row.len())
+                    .map(|idx| row.get_scalar(idx))
+                    .collect::<Result<Vec<_>>>()
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        assert_eq!(scalars[0][4], ScalarValue::Int64(Some(-64)));
+        assert_eq!(
+            scalars[0][14],
+            ScalarValue::LargeUtf8(Some("b".to_string()))
+        );
+        assert_eq!(
+            scalars[0][17],
+            ScalarValue::TimestampMillisecond(Some(3), None)
+        );
+        assert_eq!(
+            scalars[0][18],
+            ScalarValue::Struct(
+                Some(vec![
+                    ScalarValue::Int32(Some(1)),
+                    ScalarValue::Utf8(Some("s".to_string())),
+                ]),
+                Box::new(struct_fields.clone()),
+            )
+        );
+        assert_eq!(
+            scalars[0][19],
+            ScalarValue::new_list(
+                Some(vec![
+                    ScalarValue::Int32(Some(1)),
+                    ScalarValue::Int32(None),
+                    ScalarValue::Int32(Some(3)),
+                ]),
+                DataType::Int32,
+            )
+        );
+
+        // nulls are typed
+        assert_eq!(scalars[1][4], ScalarValue::Int64(None));
+        assert_eq!(
+            scalars[1][18],
+            ScalarValue::Struct(
+                Some(vec![
+                    ScalarValue::Int32(None),
+                    ScalarValue::Utf8(Some("t".to_string())),
+                ]),
+                Box::new(struct_fields),
+            )
+        );
+        assert_eq!(scalars[1][19], ScalarValue::new_list(None, DataType::Int32));
+
+        Ok(())
+    }
+
+    #[test]
+    fn getter_errors() {
+        let batch = all_types_batch();
+        let row = rows(&batch).next_back().unwrap();
+        assert_eq!(row.row_index(), 1);
+
+        // the type is checked even if the value is null
+        assert!(row.is_null(3).unwrap());
+        let err = row.get_i64(3).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Execution error: Expected column 3 ('i32') to be Int64, got Int32"
+        );
+        let err = row.get_str(15).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Execution error: Expected column 15 ('binary') to be Utf8, got Binary"
+        );
+        let err = row.get_timestamp(4).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Execution error: Expected column 4 to be a Timestamp, got Int64"
+        );
+        let err = row.get_scalar(20).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Execution error: Column index 20 out of bounds for a row of 20 columns"
+        );
+        assert!(row.index_of("missing").is_err());
+    }
+}
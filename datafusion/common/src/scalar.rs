@@ -35,6 +35,7 @@ use arrow::{
         TimestampSecondType, UInt16Type, UInt32Type, UInt64Type, UInt8Type,
         DECIMAL128_MAX_PRECISION,
     },
+    error::ArrowError,
 };
 use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime};
 use ordered_float::OrderedFloat;
@@ -945,6 +946,21 @@ macro_rules! eq_array_primitive {
     }};
 }
 
+// Like eq_array_primitive, but with the float semantics of `ScalarValue::eq`,
+// under which all NaNs are equal and -0.0 equals 0.0
+macro_rules! eq_array_float {
+    ($array:expr, $index:expr, $ARRAYTYPE:ident, $VALUE:expr) => {{
+        let array = $array.as_any().downcast_ref::<$ARRAYTYPE>().unwrap();
+        let is_valid = array.is_valid($index);
+        match $VALUE {
+            Some(val) => {
+                is_valid && OrderedFloat(array.value($index)) == OrderedFloat(*val)
+            }
+            None => !is_valid,
+        }
+    }};
+}
+
 impl ScalarValue {
     /// Create a decimal Scalar from value/precision and scale.
     pub fn try_new_decimal128(value: i128, precision: u8, scale: u8) -> Result<Self> {
@@ -1440,6 +1456,7 @@ impl ScalarValue {
                 // Initialize a Vector to store the ScalarValues for each column
                 let mut columns: Vec<Vec<ScalarValue>> =
                     (0..fields.len()).map(|_| Vec::new()).collect();
+                let mut valid = BooleanBufferBuilder::new(0);
 
                 // Iterate over scalars to populate the column scalars for each row
                 for scalar in scalars {
@@ -1450,6 +1467,7 @@ impl ScalarValue {
                                 for (column, value) in columns.iter_mut().zip(values) {
                                     column.push(value.clone());
                                 }
+                                valid.append(true);
                             }
                             None => {
                                 // Push NULL of the appropriate type for each field
@@ -1459,6 +1477,7 @@ impl ScalarValue {
                                     column
                                         .push(ScalarValue::try_from(field.data_type())?);
                                 }
+                                valid.append(false);
                             }
                         };
                     } else {
@@ -1478,7 +1497,7 @@ impl ScalarValue {
                     })
                     .collect::<Result<Vec<_>>>()?;
 
-                Arc::new(StructArray::from(field_values))
+                Arc::new(StructArray::from((field_values, valid.finish())))
             }
             DataType::Dictionary(key_type, value_type) => {
                 // create the values array
@@ -1742,19 +1761,15 @@ impl ScalarValue {
                     Arc::new(repeat(None::<&str>).take(size).collect::<BinaryArray>())
                 }
             },
-            ScalarValue::FixedSizeBinary(_, e) => match e {
+            ScalarValue::FixedSizeBinary(s, e) => match e {
                 Some(value) => Arc::new(
                     FixedSizeBinaryArray::try_from_sparse_iter(
                         repeat(Some(value.as_slice())).take(size),
                     )
                     .unwrap(),
                 ),
-                None => Arc::new(
-                    FixedSizeBinaryArray::try_from_sparse_iter(
-                        repeat(None::<&[u8]>).take(size),
-                    )
-                    .unwrap(),
-                ),
+                // the value size can't be inferred from nulls alone
+                None => new_null_array(&DataType::FixedSizeBinary(*s), size),
             },
             ScalarValue::LargeBinary(e) => match e {
                 Some(value) => Arc::new(
@@ -1845,18 +1860,8 @@ impl ScalarValue {
 
                     Arc::new(StructArray::from(field_values))
                 }
-                None => {
-                    let field_values: Vec<_> = fields
-                        .iter()
-                        .map(|field| {
-                            let none_field = Self::try_from(field.data_type())
-                                .expect("Failed to construct null ScalarValue from Struct field type");
-                            (field.clone(), none_field.to_array_of_size(size))
-                        })
-                        .collect();
-
-                    Arc::new(StructArray::from(field_values))
-                }
+                // a null struct rather than a struct of null fields
+                None => new_null_array(&self.get_datatype(), size),
             },
             ScalarValue::Dictionary(key_type, v) => {
                 // values array is one element long (the value)
@@ -1985,6 +1990,20 @@ impl ScalarValue {
                     tz_opt
                 )
             }
+            DataType::Interval(IntervalUnit::YearMonth) => {
+                typed_cast!(array, index, IntervalYearMonthArray, IntervalYearMonth)
+            }
+            DataType::Interval(IntervalUnit::DayTime) => {
+                typed_cast!(array, index, IntervalDayTimeArray, IntervalDayTime)
+            }
+            DataType::Interval(IntervalUnit::MonthDayNano) => {
+                typed_cast!(
+                    array,
+                    index,
+                    IntervalMonthDayNanoArray,
+                    IntervalMonthDayNano
+                )
+            }
             DataType::Dictionary(key_type, _) => {
                 let (values_array, values_index) = match key_type.as_ref() {
                     DataType::Int8 => get_dict_value::<Int8Type>(array, index),
@@ -2010,6 +2029,9 @@ impl ScalarValue {
             }
             DataType::Struct(fields) => {
                 let array = as_struct_array(array)?;
+                if array.is_null(index) {
+                    return Ok(Self::Struct(None, Box::new(fields.clone())));
+                }
                 let mut field_values: Vec<ScalarValue> = Vec::new();
                 for col_index in 0..array.num_columns() {
                     let col_array = array.column(col_index);
@@ -2061,6 +2083,10 @@ impl ScalarValue {
 
     /// Try to parse `value` into a ScalarValue of type `target_type`
     pub fn try_from_string(value: String, target_type: &DataType) -> Result<Self> {
+        if let DataType::Decimal128(precision, scale) = target_type {
+            let value = parse_decimal128(&value, *precision, *scale)?;
+            return ScalarValue::try_new_decimal128(value, *precision, *scale);
+        }
         let value = ScalarValue::Utf8(Some(value));
         let cast_options = CastOptions { safe: false };
         let cast_arr = cast_with_options(&value.to_array(), target_type, &cast_options)?;
@@ -2110,10 +2136,10 @@ impl ScalarValue {
                 eq_array_primitive!(array, index, BooleanArray, val)
             }
             ScalarValue::Float32(val) => {
-                eq_array_primitive!(array, index, Float32Array, val)
+                eq_array_float!(array, index, Float32Array, val)
             }
             ScalarValue::Float64(val) => {
-                eq_array_primitive!(array, index, Float64Array, val)
+                eq_array_float!(array, index, Float64Array, val)
             }
             ScalarValue::Int8(val) => eq_array_primitive!(array, index, Int8Array, val),
             ScalarValue::Int16(val) => eq_array_primitive!(array, index, Int16Array, val),
@@ -2142,7 +2168,30 @@ impl ScalarValue {
             ScalarValue::LargeBinary(val) => {
                 eq_array_primitive!(array, index, LargeBinaryArray, val)
             }
-            ScalarValue::List(_, _) => unimplemented!(),
+            ScalarValue::List(values, _) => {
+                let nested_array = match array.data_type() {
+                    DataType::FixedSizeList(_, _) => {
+                        let array =
+                            array.as_any().downcast_ref::<FixedSizeListArray>().unwrap();
+                        array.is_valid(index).then(|| array.value(index))
+                    }
+                    _ => {
+                        let array = array.as_any().downcast_ref::<ListArray>().unwrap();
+                        array.is_valid(index).then(|| array.value(index))
+                    }
+                };
+                match (values, nested_array) {
+                    (Some(values), Some(nested_array)) => {
+                        values.len() == nested_array.len()
+                            && values
+                                .iter()
+                                .enumerate()
+                                .all(|(i, value)| value.eq_array(&nested_array, i))
+                    }
+                    (None, None) => true,
+                    _ => false,
+                }
+            }
             ScalarValue::Date32(val) => {
                 eq_array_primitive!(array, index, Date32Array, val)
             }
@@ -2173,7 +2222,20 @@ impl ScalarValue {
             ScalarValue::IntervalMonthDayNano(val) => {
                 eq_array_primitive!(array, index, IntervalMonthDayNanoArray, val)
             }
-            ScalarValue::Struct(_, _) => unimplemented!(),
+            ScalarValue::Struct(values, _) => {
+                let array = as_struct_array(array).unwrap();
+                match values {
+                    Some(values) => {
+                        array.is_valid(index)
+                            && values.len() == array.num_columns()
+                            && values
+                                .iter()
+                                .enumerate()
+                                .all(|(i, value)| value.eq_array(array.column(i), index))
+                    }
+                    None => !array.is_valid(index),
+                }
+            }
             ScalarValue::Dictionary(key_type, v) => {
                 let (values_array, values_index) = match key_type.as_ref() {
                     DataType::Int8 => get_dict_value::<Int8Type>(array, index),
@@ -2238,6 +2300,48 @@ impl From<Option<&str>> for ScalarValue {
     }
 }
 
+/// Parses `value`, such as `-12.3`, as the unscaled value of a decimal of
+/// `precision` and `scale`, failing if it has more fractional digits than
+/// `scale` or more digits in total than `precision`
+fn parse_decimal128(value: &str, precision: u8, scale: u8) -> Result<i128> {
+    let error = || {
+        DataFusionError::ArrowError(ArrowError::CastError(format!(
+            "Cannot cast string '{}' to value of {:?} type",
+            value,
+            DataType::Decimal128(precision, scale)
+        )))
+    };
+
+    let trimmed = value.trim();
+    let (negative, digits) = match trimmed.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, trimmed.strip_prefix('+').unwrap_or(trimmed)),
+    };
+    let (integer, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+    if (integer.is_empty() && fraction.is_empty())
+        || fraction.len() > scale as usize
+        || !integer
+            .bytes()
+            .chain(fraction.bytes())
+            .all(|b| b.is_ascii_digit())
+    {
+        return Err(error());
+    }
+
+    let padding = repeat(b'0').take(scale as usize - fraction.len());
+    let mut unscaled: i128 = 0;
+    for digit in integer.bytes().chain(fraction.bytes()).chain(padding) {
+        unscaled = unscaled
+            .checked_mul(10)
+            .and_then(|unscaled| unscaled.checked_add((digit - b'0') as i128))
+            .ok_or_else(error)?;
+    }
+    if precision > DECIMAL128_MAX_PRECISION || unscaled >= 10_i128.pow(precision as u32) {
+        return Err(error());
+    }
+    Ok(if negative { -unscaled } else { unscaled })
+}
+
 impl FromStr for ScalarValue {
     type Err = Infallible;
 
@@ -2406,7 +2510,8 @@ impl TryFrom<&DataType> for ScalarValue {
                 index_type.clone(),
                 Box::new(value_type.as_ref().try_into()?),
             ),
-            DataType::List(ref nested_type) => {
+            DataType::List(ref nested_type)
+            | DataType::FixedSizeList(ref nested_type, _) => {
                 ScalarValue::new_list(None, nested_type.data_type().clone())
             }
             DataType::Struct(fields) => {
@@ -2677,6 +2782,35 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn scalar_decimal_from_string() {
+        let decimal = |value: &str| {
+            ScalarValue::try_from_string(value.to_string(), &DataType::Decimal128(5, 2))
+        };
+        assert_eq!(
+            decimal("12.3").unwrap(),
+            ScalarValue::Decimal128(Some(1230), 5, 2)
+        );
+        assert_eq!(
+            decimal(" -0.05").unwrap(),
+            ScalarValue::Decimal128(Some(-5), 5, 2)
+        );
+        assert_eq!(
+            decimal("+999.99").unwrap(),
+            ScalarValue::Decimal128(Some(99999), 5, 2)
+        );
+        assert_eq!(
+            decimal(".5").unwrap(),
+            ScalarValue::Decimal128(Some(50), 5, 2)
+        );
+
+        // more fractional digits than the scale, more digits than the
+        // precision, or not a number at all
+        for value in ["1.234", "1000", "", ".", "1e3", "1.2.3", "abc"] {
+            assert!(decimal(value).is_err(), "{} parsed", value);
+        }
+    }
+
     #[test]
     fn scalar_decimal_test() -> Result<()> {
         let decimal_value = ScalarValue::Decimal128(Some(123), 10, 1);
@@ -3048,6 +3182,104 @@ mod tests {
         );
     }
 
+    #[test]
+    fn scalar_try_from_array_round_trip() {
+        let struct_fields = vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Utf8, true),
+        ];
+        let scalars = vec![
+            ScalarValue::Boolean(Some(true)),
+            ScalarValue::Float32(Some(1.5)),
+            ScalarValue::Float64(Some(-2.5)),
+            ScalarValue::Decimal128(Some(12345), 10, 2),
+            ScalarValue::Int8(Some(-8)),
+            ScalarValue::Int16(Some(-16)),
+            ScalarValue::Int32(Some(-32)),
+            ScalarValue::Int64(Some(-64)),
+            ScalarValue::UInt8(Some(8)),
+            ScalarValue::UInt16(Some(16)),
+            ScalarValue::UInt32(Some(32)),
+            ScalarValue::UInt64(Some(64)),
+            ScalarValue::Utf8(Some("foo".to_string())),
+            ScalarValue::LargeUtf8(Some("bar".to_string())),
+            ScalarValue::Binary(Some(b"foo".to_vec())),
+            ScalarValue::LargeBinary(Some(b"bar".to_vec())),
+            ScalarValue::FixedSizeBinary(3, Some(b"baz".to_vec())),
+            ScalarValue::Date32(Some(18000)),
+            ScalarValue::Date64(Some(1555200000000)),
+            ScalarValue::Time64(Some(3600000000000)),
+            ScalarValue::TimestampSecond(Some(1), None),
+            ScalarValue::TimestampMillisecond(Some(2), Some("UTC".to_string())),
+            ScalarValue::TimestampMicrosecond(Some(3), None),
+            ScalarValue::TimestampNanosecond(Some(4), Some("+08:00".to_string())),
+            ScalarValue::IntervalYearMonth(Some(13)),
+            ScalarValue::IntervalDayTime(Some(IntervalDayTimeType::make_value(1, 2))),
+            ScalarValue::IntervalMonthDayNano(Some(
+                IntervalMonthDayNanoType::make_value(1, 2, 3),
+            )),
+            ScalarValue::new_list(
+                Some(vec![ScalarValue::Int32(Some(1)), ScalarValue::Int32(None)]),
+                DataType::Int32,
+            ),
+            ScalarValue::new_list(
+                Some(vec![ScalarValue::new_list(
+                    Some(vec![ScalarValue::Utf8(Some("nested".to_string()))]),
+                    DataType::Utf8,
+                )]),
+                DataType::List(Box::new(Field::new("item", DataType::Utf8, true))),
+            ),
+            ScalarValue::Struct(
+                Some(vec![
+                    ScalarValue::Int32(Some(1)),
+                    ScalarValue::Utf8(Some("x".to_string())),
+                ]),
+                Box::new(struct_fields.clone()),
+            ),
+            ScalarValue::Struct(
+                Some(vec![
+                    ScalarValue::Struct(
+                        Some(vec![ScalarValue::Int32(None), ScalarValue::Utf8(None)]),
+                        Box::new(struct_fields.clone()),
+                    ),
+                    ScalarValue::new_list(
+                        Some(vec![ScalarValue::Int64(Some(7))]),
+                        DataType::Int64,
+                    ),
+                ]),
+                Box::new(vec![
+                    Field::new("inner", DataType::Struct(struct_fields.clone()), true),
+                    Field::new(
+                        "list",
+                        DataType::List(Box::new(Field::new(
+                            "item",
+                            DataType::Int64,
+                            true,
+                        ))),
+                        true,
+                    ),
+                ]),
+            ),
+        ];
+
+        for scalar in scalars {
+            let array = scalar.to_array();
+            assert_eq!(
+                ScalarValue::try_from_array(&array, 0).unwrap(),
+                scalar,
+                "{:?}",
+                scalar.get_datatype()
+            );
+
+            // a null of the same type reads back as a typed null
+            let null = ScalarValue::try_from(&scalar.get_datatype()).unwrap();
+            let array = null.to_array();
+            let value = ScalarValue::try_from_array(&array, 0).unwrap();
+            assert!(value.is_null(), "{:?}", value);
+            assert_eq!(value.get_datatype(), scalar.get_datatype());
+        }
+    }
+
     #[test]
     fn scalar_try_from_dict_datatype() {
         let data_type =
@@ -3100,6 +3332,40 @@ mod tests {
 
         let str_vals = vec![Some("foo"), None, Some("bar")];
 
+        let list_vals = vec![
+            ScalarValue::new_list(
+                Some(vec![ScalarValue::Int64(Some(1)), ScalarValue::Int64(None)]),
+                DataType::Int64,
+            ),
+            ScalarValue::new_list(None, DataType::Int64),
+            ScalarValue::new_list(
+                Some(vec![ScalarValue::Int64(Some(1))]),
+                DataType::Int64,
+            ),
+        ];
+
+        let struct_fields = vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Utf8, true),
+        ];
+        let struct_vals = vec![
+            ScalarValue::Struct(
+                Some(vec![
+                    ScalarValue::Int32(Some(1)),
+                    ScalarValue::Utf8(Some("x".to_string())),
+                ]),
+                Box::new(struct_fields.clone()),
+            ),
+            ScalarValue::Struct(None, Box::new(struct_fields.clone())),
+            ScalarValue::Struct(
+                Some(vec![
+                    ScalarValue::Int32(None),
+                    ScalarValue::Utf8(Some("x".to_string())),
+                ]),
+                Box::new(struct_fields),
+            ),
+        ];
+
         /// Test each value in `scalar` with the corresponding element
         /// at `array`. Assumes each element is unique (aka not equal
         /// with all other indexes)
@@ -3251,6 +3517,14 @@ mod tests {
             make_str_dict_test_case!(str_vals, UInt16Type),
             make_str_dict_test_case!(str_vals, UInt32Type),
             make_str_dict_test_case!(str_vals, UInt64Type),
+            TestCase {
+                array: ScalarValue::iter_to_array(list_vals.clone()).unwrap(),
+                scalars: list_vals,
+            },
+            TestCase {
+                array: ScalarValue::iter_to_array(struct_vals.clone()).unwrap(),
+                scalars: struct_vals,
+            },
         ];
 
         for case in cases {
@@ -3285,6 +3559,22 @@ mod tests {
         }
     }
 
+    #[test]
+    fn scalar_eq_array_float_keys() {
+        let array: ArrayRef = Arc::new(Float64Array::from(vec![
+            Some(-0.0),
+            Some(f64::from_bits(f64::NAN.to_bits() | 1)),
+            None,
+        ]));
+
+        // same as ScalarValue::eq, -0.0 equals 0.0 and all NaNs are equal
+        assert!(ScalarValue::Float64(Some(0.0)).eq_array(&array, 0));
+        assert!(ScalarValue::Float64(Some(f64::NAN)).eq_array(&array, 1));
+        assert!(!ScalarValue::Float64(Some(f64::NAN)).eq_array(&array, 0));
+        assert!(!ScalarValue::Float64(Some(f64::NAN)).eq_array(&array, 2));
+        assert!(ScalarValue::Float64(None).eq_array(&array, 2));
+    }
+
     #[test]
     fn scalar_partial_ordering() {
         use ScalarValue::*;
@@ -0,0 +1,159 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Benchmarks of hash aggregations grouped by a single column, over 10M
+//! rows in 1k groups.
+//!
+//! The signed integer and date keys are grouped by their values, while
+//! the `UInt64` keys, which have the same values, go through the generic
+//! path, as a baseline.
+
+#[macro_use]
+extern crate criterion;
+extern crate arrow;
+extern crate datafusion;
+
+use std::sync::Arc;
+
+use arrow::array::{ArrayRef, Date32Array, Int32Array, Int64Array, UInt64Array};
+use arrow::datatypes::DataType;
+use arrow::record_batch::RecordBatch;
+use criterion::Criterion;
+use datafusion::physical_plan::aggregates::{
+    AggregateExec, AggregateMode, PhysicalGroupBy,
+};
+use datafusion::physical_plan::expressions::{col, BitXor, Count};
+use datafusion::physical_plan::memory::MemoryExec;
+use datafusion::physical_plan::{collect, AggregateExpr, ExecutionPlan};
+use datafusion::prelude::SessionContext;
+use tokio::runtime::Runtime;
+
+const NUM_ROWS: usize = 10_000_000;
+const NUM_GROUPS: usize = 1_000;
+const BATCH_SIZE: usize = 8192;
+
+/// Creates the batches of a group column of `keys` and an `Int64` value
+/// column
+fn create_batches(keys: impl Fn(usize, usize) -> ArrayRef) -> Vec<RecordBatch> {
+    (0..NUM_ROWS)
+        .step_by(BATCH_SIZE)
+        .map(|start| {
+            let end = (start + BATCH_SIZE).min(NUM_ROWS);
+            let values = Int64Array::from_iter_values((start..end).map(|i| i as i64));
+            RecordBatch::try_from_iter(vec![
+                ("a", keys(start, end)),
+                ("b", Arc::new(values) as ArrayRef),
+            ])
+            .unwrap()
+        })
+        .collect()
+}
+
+/// The group of the row at `i`, scattered so that every batch has rows of
+/// most groups
+fn group(i: usize) -> usize {
+    (i * 7919) % NUM_GROUPS
+}
+
+/// Aggregates `batches` by `COUNT` and `BIT_XOR`, which the row format does
+/// not support, so that the grouping is done by the primitive group path,
+/// or by the generic one for `uint64` keys
+fn aggregate(rt: &Runtime, batches: &[RecordBatch]) {
+    let schema = batches[0].schema();
+    let input =
+        Arc::new(MemoryExec::try_new(&[batches.to_vec()], schema.clone(), None).unwrap());
+    let group_by =
+        PhysicalGroupBy::new_single(vec![(col("a", &schema).unwrap(), "a".to_string())]);
+    let aggregates: Vec<Arc<dyn AggregateExpr>> = vec![
+        Arc::new(Count::new(
+            col("b", &schema).unwrap(),
+            "COUNT(b)".to_string(),
+            DataType::Int64,
+        )),
+        Arc::new(BitXor::new(
+            col("b", &schema).unwrap(),
+            "BIT_XOR(b)",
+            DataType::Int64,
+        )),
+    ];
+    let exec: Arc<dyn ExecutionPlan> = Arc::new(
+        AggregateExec::try_new(
+            AggregateMode::Partial,
+            group_by,
+            aggregates,
+            input,
+            schema,
+        )
+        .unwrap(),
+    );
+
+    let task_ctx = SessionContext::new().task_ctx();
+    let batches = rt.block_on(collect(exec, task_ctx)).unwrap();
+    assert_eq!(
+        batches.iter().map(|batch| batch.num_rows()).sum::<usize>(),
+        NUM_GROUPS
+    );
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    let rt = Runtime::new().unwrap();
+
+    let cases: Vec<(&str, Vec<RecordBatch>)> = vec![
+        (
+            "int64",
+            create_batches(|start, end| {
+                Arc::new(Int64Array::from_iter_values(
+                    (start..end).map(|i| group(i) as i64),
+                ))
+            }),
+        ),
+        (
+            "int32",
+            create_batches(|start, end| {
+                Arc::new(Int32Array::from_iter_values(
+                    (start..end).map(|i| group(i) as i32),
+                ))
+            }),
+        ),
+        (
+            "date32",
+            create_batches(|start, end| {
+                Arc::new(Date32Array::from_iter_values(
+                    (start..end).map(|i| group(i) as i32),
+                ))
+            }),
+        ),
+        (
+            "uint64",
+            create_batches(|start, end| {
+                Arc::new(UInt64Array::from_iter_values(
+                    (start..end).map(|i| group(i) as u64),
+                ))
+            }),
+        ),
+    ];
+
+    let mut group = c.benchmark_group("aggregate_primitive_group_by");
+    group.sample_size(10);
+    for (name, batches) in &cases {
+        group.bench_function(*name, |b| b.iter(|| aggregate(&rt, batches)));
+    }
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
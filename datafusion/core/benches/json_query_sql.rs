@@ -0,0 +1,278 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Benchmarks of scanning newline delimited JSON with [`NdJsonExec`]
+//!
+//! Each benchmark generates a file in a temporary directory and then
+//! times a full scan of it (`SELECT * FROM t`) through
+//! [`SessionContext::read_json`], so whichever reader `NdJsonExec`
+//! uses is the one being measured. The inputs are:
+//!
+//! * `wide`: flat objects with [`WIDE_COLUMNS`] numeric and string fields
+//! * `deep`: objects nested [`DEEP_LEVELS`] levels deep
+//! * `low_cardinality`: a handful of distinct string values per column
+//! * `wide_gzip`: the `wide` input, gzip compressed
+//!
+//! and each is scanned with several session batch sizes.
+//!
+//! Interpreting results: every input contains [`NUM_ROWS`] rows, so
+//! the reported time is directly comparable across inputs of the same
+//! shape, and criterion's throughput figure is bytes of *uncompressed*
+//! JSON per second. To compare two reader implementations, record a
+//! baseline on the first (`cargo bench --bench json_query_sql --
+//! --save-baseline before`) and compare against it on the second
+//! (`-- --baseline before`). Differences smaller than the noise
+//! threshold criterion reports are not meaningful.
+//!
+//! Running `cargo test --bench json_query_sql` executes every benchmark
+//! exactly once, which is a cheap way to check they still work.
+//!
+//! [`NdJsonExec`]: datafusion::physical_plan::file_format::NdJsonExec
+//! [`SessionContext::read_json`]: datafusion::prelude::SessionContext::read_json
+
+use arrow::datatypes::DataType;
+use arrow::record_batch::RecordBatch;
+use arrow::util::display::array_value_to_string;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use datafusion::datasource::file_format::file_type::FileCompressionType;
+use datafusion::prelude::{NdJsonReadOptions, SessionConfig, SessionContext};
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+use tokio::runtime::Runtime;
+
+/// The number of rows written to every generated file
+const NUM_ROWS: usize = 16 * 1024;
+/// The number of fields of the `wide` input
+const WIDE_COLUMNS: usize = 64;
+/// The nesting depth of the `deep` input
+const DEEP_LEVELS: usize = 8;
+/// The session batch sizes each input is scanned with
+const BATCH_SIZES: [usize; 3] = [1024, 4096, 8192];
+
+fn wide_row(row: usize) -> String {
+    let mut line = String::from("{");
+    for col in 0..WIDE_COLUMNS {
+        if col > 0 {
+            line.push(',');
+        }
+        match col % 4 {
+            0 => write!(line, "\"c{}\":{}", col, row * col),
+            1 => write!(line, "\"c{}\":{}.5", col, row),
+            2 => write!(line, "\"c{}\":\"value-{}-{}\"", col, row, col),
+            _ => write!(line, "\"c{}\":{}", col, row % 2 == 0),
+        }
+        .unwrap();
+    }
+    line.push('}');
+    line
+}
+
+fn deep_row(row: usize) -> String {
+    let mut line = String::new();
+    for level in 0..DEEP_LEVELS {
+        write!(line, "{{\"id{}\":{},\"n{}\":", level, row, level).unwrap();
+    }
+    write!(line, "{{\"leaf\":\"{}\"}}", row).unwrap();
+    line.push_str(&"}".repeat(DEEP_LEVELS));
+    line
+}
+
+fn low_cardinality_row(row: usize) -> String {
+    const VALUES: [&str; 4] = ["alpha", "beta", "gamma", "delta"];
+    format!(
+        "{{\"s0\":\"{}\",\"s1\":\"{}\",\"s2\":\"{}\",\"i\":{}}}",
+        VALUES[row % 4],
+        VALUES[row % 3],
+        VALUES[row % 2],
+        row
+    )
+}
+
+/// Writes [`NUM_ROWS`] rows produced by `row_fn` to `path`, returning
+/// the number of uncompressed bytes written
+fn generate_file(
+    path: &Path,
+    compression: FileCompressionType,
+    row_fn: fn(usize) -> String,
+) -> u64 {
+    let file = File::create(path).unwrap();
+    let mut writer: Box<dyn Write> = match compression {
+        FileCompressionType::GZIP => {
+            Box::new(GzEncoder::new(file, Compression::default()))
+        }
+        FileCompressionType::UNCOMPRESSED => Box::new(file),
+        other => panic!("unsupported compression in benchmark: {:?}", other),
+    };
+
+    let mut bytes = 0;
+    for row in 0..NUM_ROWS {
+        let line = row_fn(row);
+        bytes += line.len() as u64 + 1;
+        writeln!(writer, "{}", line).unwrap();
+    }
+    writer.flush().unwrap();
+    bytes
+}
+
+struct Input {
+    name: &'static str,
+    path: PathBuf,
+    compression: FileCompressionType,
+    bytes: u64,
+    /// The number of top level columns of the input
+    columns: usize,
+}
+
+fn generate_inputs(dir: &TempDir) -> Vec<Input> {
+    type Spec = (
+        &'static str,
+        &'static str,
+        FileCompressionType,
+        fn(usize) -> String,
+        usize,
+    );
+    let specs: [Spec; 4] = [
+        (
+            "wide",
+            "wide.json",
+            FileCompressionType::UNCOMPRESSED,
+            wide_row,
+            WIDE_COLUMNS,
+        ),
+        (
+            "deep",
+            "deep.json",
+            FileCompressionType::UNCOMPRESSED,
+            deep_row,
+            2,
+        ),
+        (
+            "low_cardinality",
+            "low_cardinality.json",
+            FileCompressionType::UNCOMPRESSED,
+            low_cardinality_row,
+            4,
+        ),
+        (
+            "wide_gzip",
+            "wide.json.gz",
+            FileCompressionType::GZIP,
+            wide_row,
+            WIDE_COLUMNS,
+        ),
+    ];
+
+    specs
+        .into_iter()
+        .map(|(name, file_name, compression, row_fn, columns)| {
+            let path = dir.path().join(file_name);
+            let bytes = generate_file(&path, compression.clone(), row_fn);
+            Input {
+                name,
+                path,
+                compression,
+                bytes,
+                columns,
+            }
+        })
+        .collect()
+}
+
+/// Scans the whole input, returning the batches read
+async fn scan(ctx: &SessionContext, input: &Input) -> Vec<RecordBatch> {
+    let extension = input.path.file_name().unwrap().to_str().unwrap();
+    let extension = &extension[extension.find('.').unwrap()..];
+    let options = NdJsonReadOptions::default()
+        .file_extension(extension)
+        .file_compression_type(input.compression.clone());
+
+    let df = ctx
+        .read_json(input.path.to_str().unwrap(), options)
+        .await
+        .unwrap();
+    df.collect().await.unwrap()
+}
+
+/// Checks that scanning `input` reads all of its rows and columns, with the
+/// values of its first row
+fn check_scan(rt: &Runtime, ctx: &SessionContext, input: &Input) {
+    let batches = rt.block_on(scan(ctx, input));
+    let rows: usize = batches.iter().map(|batch| batch.num_rows()).sum();
+    assert_eq!(rows, NUM_ROWS, "{}", input.name);
+    let batch = batches.iter().find(|batch| batch.num_rows() > 0).unwrap();
+    assert_eq!(batch.num_columns(), input.columns, "{}", input.name);
+
+    let mut first_row = String::from("{");
+    for (i, field) in batch.schema().fields().iter().enumerate() {
+        if i > 0 {
+            first_row.push(',');
+        }
+        let value = array_value_to_string(batch.column(i), 0).unwrap();
+        match field.data_type() {
+            DataType::Utf8 => write!(first_row, "\"{}\":\"{}\"", field.name(), value),
+            _ => write!(first_row, "\"{}\":{}", field.name(), value),
+        }
+        .unwrap();
+    }
+    first_row.push('}');
+    // the deep input has nested values, which are only counted
+    if input.name != "deep" {
+        let expected = match input.name {
+            "low_cardinality" => low_cardinality_row(0),
+            _ => wide_row(0),
+        };
+        let parse = |s: &str| serde_json::from_str::<serde_json::Value>(s).unwrap();
+        assert_eq!(parse(&first_row), parse(&expected), "{}", input.name);
+    }
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    let dir = TempDir::new().unwrap();
+    let inputs = generate_inputs(&dir);
+    let rt = Runtime::new().unwrap();
+
+    for input in &inputs {
+        let mut group = c.benchmark_group(format!("json_scan_{}", input.name));
+        group.throughput(Throughput::Bytes(input.bytes));
+
+        for batch_size in BATCH_SIZES {
+            let config = SessionConfig::new()
+                .with_batch_size(batch_size)
+                .with_target_partitions(1);
+            let ctx = SessionContext::with_config(config);
+
+            // make sure the input is read correctly before timing anything,
+            // which `cargo test --bench json_query_sql` also checks in CI
+            check_scan(&rt, &ctx, input);
+
+            group.bench_with_input(
+                BenchmarkId::new("batch_size", batch_size),
+                input,
+                |b, input| b.iter(|| rt.block_on(scan(&ctx, input))),
+            );
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
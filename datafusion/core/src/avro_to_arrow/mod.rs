@@ -38,6 +38,13 @@ pub fn read_avro_schema_from_reader<R: Read>(reader: &mut R) -> Result<Schema> {
     schema::to_arrow_schema(schema)
 }
 
+#[cfg(feature = "avro")]
+/// Read Avro schema given its JSON definition, as found in `.avsc` files
+pub fn read_avro_schema_from_json(json: &str) -> Result<Schema> {
+    let schema = apache_avro::Schema::parse_str(json)?;
+    schema::to_arrow_schema(&schema)
+}
+
 #[cfg(not(feature = "avro"))]
 /// Read Avro schema given a reader (requires the avro feature)
 pub fn read_avro_schema_from_reader<R: Read>(_: &mut R) -> Result<Schema> {
@@ -45,3 +52,11 @@ pub fn read_avro_schema_from_reader<R: Read>(_: &mut R) -> Result<Schema> {
         "cannot read avro schema without the 'avro' feature enabled".to_string(),
     ))
 }
+
+#[cfg(not(feature = "avro"))]
+/// Read Avro schema given its JSON definition (requires the avro feature)
+pub fn read_avro_schema_from_json(_: &str) -> Result<Schema> {
+    Err(crate::error::DataFusionError::NotImplemented(
+        "cannot read avro schema without the 'avro' feature enabled".to_string(),
+    ))
+}
@@ -46,6 +46,17 @@ pub const OPT_COALESCE_BATCHES: &str = "datafusion.execution.coalesce_batches";
 pub const OPT_COALESCE_TARGET_BATCH_SIZE: &str =
     "datafusion.execution.coalesce_target_batch_size";
 
+/// Configuration option "datafusion.execution.skip_partial_aggregation_probe_rows"
+pub const OPT_SKIP_PARTIAL_AGGREGATION_PROBE_ROWS: &str =
+    "datafusion.execution.skip_partial_aggregation_probe_rows";
+
+/// Configuration option "datafusion.execution.skip_partial_aggregation_probe_ratio_threshold"
+pub const OPT_SKIP_PARTIAL_AGGREGATION_PROBE_RATIO_THRESHOLD: &str =
+    "datafusion.execution.skip_partial_aggregation_probe_ratio_threshold";
+
+/// Configuration option "datafusion.execution.query_timeout_ms"
+pub const OPT_QUERY_TIMEOUT_MS: &str = "datafusion.execution.query_timeout_ms";
+
 /// Configuration option "datafusion.execution.time_zone"
 pub const OPT_TIME_ZONE: &str = "datafusion.execution.time_zone";
 
@@ -68,6 +79,26 @@ pub const OPT_OPTIMIZER_SKIP_FAILED_RULES: &str =
 /// Configuration option "datafusion.optimizer.max_passes"
 pub const OPT_OPTIMIZER_MAX_PASSES: &str = "datafusion.optimizer.max_passes";
 
+/// Configuration option "datafusion.optimizer.repartition_file_scans"
+pub const OPT_REPARTITION_FILE_SCANS: &str =
+    "datafusion.optimizer.repartition_file_scans";
+
+/// Configuration option "datafusion.optimizer.repartition_file_min_size"
+pub const OPT_REPARTITION_FILE_MIN_SIZE: &str =
+    "datafusion.optimizer.repartition_file_min_size";
+
+/// Configuration option "datafusion.sql.lenient_arithmetic"
+pub const OPT_LENIENT_ARITHMETIC: &str = "datafusion.sql.lenient_arithmetic";
+
+/// Configuration option "datafusion.sql.wrapping_arithmetic"
+pub const OPT_WRAPPING_ARITHMETIC: &str = "datafusion.sql.wrapping_arithmetic";
+
+/// Configuration option "datafusion.sql.checked_division"
+pub const OPT_CHECKED_DIVISION: &str = "datafusion.sql.checked_division";
+
+/// Configuration option "datafusion.format.max_column_width"
+pub const OPT_FORMAT_MAX_COLUMN_WIDTH: &str = "datafusion.format.max_column_width";
+
 /// Location scanned to load tables for `default` schema
 pub const OPT_CATALOG_LOCATION: &str = "datafusion.catalog.location";
 
@@ -146,6 +177,20 @@ impl ConfigDefinition {
         )
     }
 
+    /// Create a configuration option definition with a f64 value
+    pub fn new_f64(
+        key: impl Into<String>,
+        description: impl Into<String>,
+        default_value: f64,
+    ) -> Self {
+        Self::new(
+            key,
+            description,
+            DataType::Float64,
+            ScalarValue::Float64(Some(default_value)),
+        )
+    }
+
     /// Create a configuration option definition with a string value
     pub fn new_string(
         key: impl Into<String>,
@@ -212,11 +257,20 @@ impl BuiltInConfigs {
                 '{}'.", OPT_COALESCE_TARGET_BATCH_SIZE),
                 true,
             ),
-             ConfigDefinition::new_u64(
-                 OPT_COALESCE_TARGET_BATCH_SIZE,
-                 format!("Target batch size when coalescing batches. Uses in conjunction with the \
-                          configuration setting '{}'.", OPT_COALESCE_BATCHES),
-                 4096,
+            ConfigDefinition::new(
+                OPT_COALESCE_TARGET_BATCH_SIZE,
+                format!("Target batch size when coalescing batches. Uses in conjunction with the \
+                         configuration setting '{}'. Unset by default, using the configuration \
+                         setting '{}'.", OPT_COALESCE_BATCHES, OPT_BATCH_SIZE),
+                DataType::UInt64,
+                ScalarValue::UInt64(None),
+            ),
+            ConfigDefinition::new_u64(
+                OPT_QUERY_TIMEOUT_MS,
+                "Number of milliseconds after which the scans of a query fail rather than \
+                 read more data, counted from the creation of its task context. 0 means no \
+                 timeout.",
+                0,
             ),
             ConfigDefinition::new_string(
                 OPT_TIME_ZONE,
@@ -256,6 +310,62 @@ impl BuiltInConfigs {
                 "Number of times that the optimizer will attempt to optimize the plan",
                 3
             ),
+            ConfigDefinition::new_bool(
+                OPT_REPARTITION_FILE_SCANS,
+                format!("When set to true, the physical optimizer will split the uncompressed \
+                newline delimited JSON files of a scan into byte ranges read in parallel, rather \
+                than repartitioning the batches of the scan. Files smaller than \
+                '{}' are never split.", OPT_REPARTITION_FILE_MIN_SIZE),
+                false,
+            ),
+            ConfigDefinition::new_u64(
+                OPT_REPARTITION_FILE_MIN_SIZE,
+                format!("Minimum size in bytes of the files split into byte ranges when \
+                '{}' is enabled. Smaller files are always read whole.", OPT_REPARTITION_FILE_SCANS),
+                10 * 1024 * 1024,
+            ),
+            ConfigDefinition::new_u64(
+                OPT_SKIP_PARTIAL_AGGREGATION_PROBE_ROWS,
+                format!("Number of input rows a partial hash aggregation groups before \
+                deciding whether to keep grouping them, see '{}'.",
+                OPT_SKIP_PARTIAL_AGGREGATION_PROBE_RATIO_THRESHOLD),
+                100_000,
+            ),
+            ConfigDefinition::new_f64(
+                OPT_SKIP_PARTIAL_AGGREGATION_PROBE_RATIO_THRESHOLD,
+                format!("When the ratio of groups to input rows of a partial hash aggregation \
+                reaches this threshold after '{}' rows, it stops grouping and passes its \
+                remaining rows on to the final aggregation. Values greater than 1 disable it.",
+                OPT_SKIP_PARTIAL_AGGREGATION_PROBE_ROWS),
+                0.8,
+            ),
+            ConfigDefinition::new_u64(
+                OPT_FORMAT_MAX_COLUMN_WIDTH,
+                "Maximum width of the values printed by `DataFrame::show`, longer values \
+                 being truncated with an ellipsis. 0 means no limit.",
+                0,
+            ),
+            ConfigDefinition::new_bool(
+                OPT_LENIENT_ARITHMETIC,
+                "When set to true, integer and floating point arithmetic that overflows or \
+                divides by zero yields null rather than failing the query.",
+                false,
+            ),
+            ConfigDefinition::new_bool(
+                OPT_WRAPPING_ARITHMETIC,
+                "When set to true, integer addition, subtraction, multiplication and SUM \
+                wrap around on overflow rather than failing the query, silently \
+                yielding wrong results.",
+                false,
+            ),
+            ConfigDefinition::new_bool(
+                OPT_CHECKED_DIVISION,
+                "When set to true, integer and floating point division by a column with \
+                zeros fails the query rather than yielding null for those rows, as \
+                division by a literal zero does. Ignored if \
+                `datafusion.sql.lenient_arithmetic` is enabled.",
+                false,
+            ),
             ConfigDefinition::new_string(
                 OPT_CATALOG_LOCATION,
                 "Location scanned to load tables for `default` schema, defaults to None",
@@ -377,6 +487,11 @@ impl ConfigOptions {
         self.set(key, ScalarValue::UInt64(Some(value)))
     }
 
+    /// set a `f64` configuration option
+    pub fn set_f64(&mut self, key: &str, value: f64) {
+        self.set(key, ScalarValue::Float64(Some(value)))
+    }
+
     /// set a `String` configuration option
     pub fn set_string(&mut self, key: &str, value: impl Into<String>) {
         self.set(key, ScalarValue::Utf8(Some(value.into())))
@@ -397,6 +512,11 @@ impl ConfigOptions {
         get_conf_value!(self, UInt64, key, "u64")
     }
 
+    /// get a f64 configuration option
+    pub fn get_f64(&self, key: &str) -> Option<f64> {
+        get_conf_value!(self, Float64, key, "f64")
+    }
+
     /// get a string configuration option
     pub fn get_string(&self, key: &str) -> Option<String> {
         get_conf_value!(self, Utf8, key, "string")
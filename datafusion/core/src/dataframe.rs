@@ -17,21 +17,29 @@
 
 //! DataFrame API for building and executing query plans.
 
-use crate::arrow::datatypes::Schema;
+use crate::arrow::array::{Array, ArrayRef, StringArray};
 use crate::arrow::datatypes::SchemaRef;
+use crate::arrow::datatypes::{DataType, Field, Schema};
+use crate::arrow::error::Result as ArrowResult;
 use crate::arrow::record_batch::RecordBatch;
+use crate::arrow::util::display::array_value_to_string;
 use crate::arrow::util::pretty;
+use crate::config::OPT_FORMAT_MAX_COLUMN_WIDTH;
+use crate::datasource::file_format::file_type::FileCompressionType;
 use crate::datasource::{MemTable, TableProvider};
-use crate::error::Result;
+use crate::error::{DataFusionError, Result};
 use crate::execution::{
     context::{SessionState, TaskContext},
     FunctionRegistry,
 };
 use crate::logical_expr::{
-    col, utils::find_window_exprs, Expr, JoinType, LogicalPlan, LogicalPlanBuilder,
+    utils::find_window_exprs, Expr, JoinType, LogicalPlan, LogicalPlanBuilder,
     Partitioning, TableType,
 };
-use crate::physical_plan::file_format::{plan_to_csv, plan_to_json, plan_to_parquet};
+use crate::physical_plan::file_format::{
+    plan_to_csv, plan_to_ipc, plan_to_ipc_stream, plan_to_json, plan_to_parquet,
+    IpcWriteMode,
+};
 use crate::physical_plan::SendableRecordBatchStream;
 use crate::physical_plan::{collect, collect_partitioned};
 use crate::physical_plan::{execute_stream, execute_stream_partitioned, ExecutionPlan};
@@ -42,6 +50,7 @@ use datafusion_expr::TableProviderFilterPushDown;
 use parking_lot::RwLock;
 use parquet::file::properties::WriterProperties;
 use std::any::Any;
+use std::collections::HashSet;
 use std::sync::Arc;
 
 /// DataFrame represents a logical set of rows with the same named columns.
@@ -84,13 +93,39 @@ impl DataFrame {
         }
     }
 
+    /// Create a DataFrame of `plan` that is executed in the session of `ctx`,
+    /// such as a plan built with [`LogicalPlanBuilder`] or taken from
+    /// another DataFrame with [`Self::into_unoptimized_plan`]
+    ///
+    /// ```
+    /// # use datafusion::prelude::*;
+    /// # use datafusion::error::Result;
+    /// # use datafusion::logical_expr::LogicalPlanBuilder;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// let ctx = SessionContext::new();
+    /// let df = ctx.read_csv("tests/example.csv", CsvReadOptions::new()).await?;
+    /// let plan = LogicalPlanBuilder::from(df.into_unoptimized_plan())
+    ///     .limit(0, Some(1))?
+    ///     .build()?;
+    /// let df = DataFrame::from_logical_plan(&ctx, plan);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_logical_plan(ctx: &SessionContext, plan: LogicalPlan) -> Arc<Self> {
+        Arc::new(Self {
+            session_state: ctx.state.clone(),
+            plan,
+        })
+    }
+
     /// Create a physical plan
     pub async fn create_physical_plan(&self) -> Result<Arc<dyn ExecutionPlan>> {
         // this function is copied from SessionContext function of the
         // same name
         let state_cloned = {
             let mut state = self.session_state.write();
-            state.execution_props.start_execution();
+            state.start_execution();
 
             // We need to clone `state` to release the lock that is not `Send`. We could
             // make the lock `Send` by using `tokio::sync::Mutex`, but that would require to
@@ -234,7 +269,9 @@ impl DataFrame {
     }
 
     /// Calculate the union of two [`DataFrame`]s, preserving duplicate rows.The
-    /// two [`DataFrame`]s must have exactly the same schema
+    /// two [`DataFrame`]s must have the same number of columns, of compatible
+    /// types, which are matched by position and named after the columns of
+    /// this [`DataFrame`]
     ///
     /// ```
     /// # use datafusion::prelude::*;
@@ -255,7 +292,8 @@ impl DataFrame {
     }
 
     /// Calculate the distinct union of two [`DataFrame`]s.  The
-    /// two [`DataFrame`]s must have exactly the same schema
+    /// two [`DataFrame`]s must have the same number of columns, of
+    /// compatible types
     ///
     /// ```
     /// # use datafusion::prelude::*;
@@ -299,6 +337,37 @@ impl DataFrame {
         )))
     }
 
+    /// Keep only the first row of each group of rows with the same `on_expr`
+    /// values, rows of a group being ordered by `sort_expr`, which has to start
+    /// with the `on_expr` expressions when not empty.
+    ///
+    /// ```
+    /// # use datafusion::prelude::*;
+    /// # use datafusion::error::Result;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// let ctx = SessionContext::new();
+    /// let df = ctx.read_csv("tests/example.csv", CsvReadOptions::new()).await?;
+    /// let df = df.distinct_on(
+    ///     vec![col("a")],
+    ///     vec![col("a").sort(true, false), col("b").sort(false, true)],
+    /// )?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn distinct_on(
+        &self,
+        on_expr: Vec<Expr>,
+        sort_expr: Vec<Expr>,
+    ) -> Result<Arc<DataFrame>> {
+        Ok(Arc::new(DataFrame::new(
+            self.session_state.clone(),
+            &LogicalPlanBuilder::from(self.plan.clone())
+                .distinct_on(on_expr, sort_expr)?
+                .build()?,
+        )))
+    }
+
     /// Sort the DataFrame by the specified sorting expressions. Any expression can be turned into
     /// a sort expression by calling its [sort](../logical_plan/enum.Expr.html#method.sort) method.
     ///
@@ -362,6 +431,47 @@ impl DataFrame {
         Ok(Arc::new(DataFrame::new(self.session_state.clone(), &plan)))
     }
 
+    /// Join this DataFrame with another DataFrame on the conjunction of
+    /// arbitrary boolean expressions.
+    ///
+    /// Equality predicates between a column of each DataFrame are used as
+    /// join keys, and the other predicates only filter the matched rows.
+    /// Joins without any such equality predicate are only supported for
+    /// [`JoinType::Inner`].
+    ///
+    /// ```
+    /// # use datafusion::prelude::*;
+    /// # use datafusion::error::Result;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// let ctx = SessionContext::new();
+    /// let left = ctx.read_csv("tests/example.csv", CsvReadOptions::new()).await?;
+    /// let right = ctx.read_csv("tests/example.csv", CsvReadOptions::new()).await?
+    ///   .select(vec![
+    ///     col("a").alias("a2"),
+    ///     col("b").alias("b2"),
+    ///     col("c").alias("c2")])?;
+    /// let join = left.join_on(
+    ///     right,
+    ///     JoinType::Inner,
+    ///     vec![col("a").eq(col("a2")), col("b").gt(col("b2"))],
+    /// )?;
+    /// let batches = join.collect().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn join_on(
+        &self,
+        right: Arc<DataFrame>,
+        join_type: JoinType,
+        on_exprs: impl IntoIterator<Item = Expr>,
+    ) -> Result<Arc<DataFrame>> {
+        let plan = LogicalPlanBuilder::from(self.plan.clone())
+            .join_on(&right.plan, join_type, on_exprs)?
+            .build()?;
+        Ok(Arc::new(DataFrame::new(self.session_state.clone(), &plan)))
+    }
+
     /// Repartition a DataFrame based on a logical partitioning scheme.
     ///
     /// ```
@@ -407,6 +517,9 @@ impl DataFrame {
 
     /// Print results.
     ///
+    /// Values wider than the `datafusion.format.max_column_width`
+    /// configuration option are truncated.
+    ///
     /// ```
     /// # use datafusion::prelude::*;
     /// # use datafusion::error::Result;
@@ -420,7 +533,8 @@ impl DataFrame {
     /// ```
     pub async fn show(&self) -> Result<()> {
         let results = self.collect().await?;
-        Ok(pretty::print_batches(&results)?)
+        println!("{}", self.format_batches(&results)?);
+        Ok(())
     }
 
     /// Print results and limit rows.
@@ -437,12 +551,56 @@ impl DataFrame {
     /// # }
     /// ```
     pub async fn show_limit(&self, num: usize) -> Result<()> {
+        println!("{}", self.to_string_limit(num).await?);
+        Ok(())
+    }
+
+    /// Format at most `num` rows of results as the table printed by
+    /// [`DataFrame::show_limit`].
+    ///
+    /// ```
+    /// # use datafusion::prelude::*;
+    /// # use datafusion::error::Result;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// let ctx = SessionContext::new();
+    /// let df = ctx.read_csv("tests/example.csv", CsvReadOptions::new()).await?;
+    /// let table = df.to_string_limit(10).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn to_string_limit(&self, num: usize) -> Result<String> {
         let results = self.limit(0, Some(num))?.collect().await?;
-        Ok(pretty::print_batches(&results)?)
+        self.format_batches(&results)
+    }
+
+    /// Format `batches` as a table, truncating the values wider than the
+    /// `datafusion.format.max_column_width` configuration option
+    fn format_batches(&self, batches: &[RecordBatch]) -> Result<String> {
+        let max_width = self
+            .session_state
+            .read()
+            .config
+            .config_options()
+            .read()
+            .get_u64(OPT_FORMAT_MAX_COLUMN_WIDTH)
+            .unwrap_or_default() as usize;
+        if max_width == 0 {
+            return Ok(pretty::pretty_format_batches(batches)?.to_string());
+        }
+
+        let batches = batches
+            .iter()
+            .map(|batch| truncate_values(batch, max_width))
+            .collect::<ArrowResult<Vec<_>>>()?;
+        Ok(pretty::pretty_format_batches(&batches)?.to_string())
     }
 
     /// Executes this DataFrame and returns a stream over a single partition
     ///
+    /// The output partitions are merged as they are produced, so unlike
+    /// [`DataFrame::collect`] the results are not buffered in memory.
+    ///
     /// ```
     /// # use datafusion::prelude::*;
     /// # use datafusion::error::Result;
@@ -531,6 +689,21 @@ impl DataFrame {
         state.optimize(&self.plan)
     }
 
+    /// Consume the DataFrame and return its unoptimized logical plan, which
+    /// is only cloned if the DataFrame is shared
+    pub fn into_unoptimized_plan(self: Arc<Self>) -> LogicalPlan {
+        match Arc::try_unwrap(self) {
+            Ok(df) => df.plan,
+            Err(df) => df.plan.clone(),
+        }
+    }
+
+    /// Consume the DataFrame and return its optimized logical plan
+    pub fn into_optimized_plan(self: Arc<Self>) -> Result<LogicalPlan> {
+        let state = self.session_state.read().clone();
+        state.optimize(&self.into_unoptimized_plan())
+    }
+
     /// Return a DataFrame with the explanation of its plan so far.
     ///
     /// if `analyze` is specified, runs the plan and reports metrics
@@ -617,6 +790,57 @@ impl DataFrame {
         )))
     }
 
+    /// Calculate the distinct intersection of two [`DataFrame`]s, as SQL
+    /// `INTERSECT` does.  The two [`DataFrame`]s must have the same number
+    /// of columns
+    ///
+    /// ```
+    /// # use datafusion::prelude::*;
+    /// # use datafusion::error::Result;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// let ctx = SessionContext::new();
+    /// let df = ctx.read_csv("tests/example.csv", CsvReadOptions::new()).await?;
+    /// let df = df.intersect_distinct(df.clone())?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn intersect_distinct(
+        &self,
+        dataframe: Arc<DataFrame>,
+    ) -> Result<Arc<DataFrame>> {
+        let left_plan = self.plan.clone();
+        let right_plan = dataframe.plan.clone();
+        Ok(Arc::new(DataFrame::new(
+            self.session_state.clone(),
+            &LogicalPlanBuilder::intersect(left_plan, right_plan, false)?,
+        )))
+    }
+
+    /// Calculate the distinct exception of two [`DataFrame`]s, as SQL
+    /// `EXCEPT` does.  The two [`DataFrame`]s must have the same number of
+    /// columns
+    ///
+    /// ```
+    /// # use datafusion::prelude::*;
+    /// # use datafusion::error::Result;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// let ctx = SessionContext::new();
+    /// let df = ctx.read_csv("tests/example.csv", CsvReadOptions::new()).await?;
+    /// let df = df.except_distinct(df.clone())?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn except_distinct(&self, dataframe: Arc<DataFrame>) -> Result<Arc<DataFrame>> {
+        let left_plan = self.plan.clone();
+        let right_plan = dataframe.plan.clone();
+        Ok(Arc::new(DataFrame::new(
+            self.session_state.clone(),
+            &LogicalPlanBuilder::except(left_plan, right_plan, false)?,
+        )))
+    }
+
     /// Write a `DataFrame` to a CSV file.
     pub async fn write_csv(&self, path: &str) -> Result<()> {
         let plan = self.create_physical_plan().await?;
@@ -637,9 +861,40 @@ impl DataFrame {
 
     /// Executes a query and writes the results to a partitioned JSON file.
     pub async fn write_json(&self, path: impl AsRef<str>) -> Result<()> {
+        self.write_json_with_compression(path, FileCompressionType::UNCOMPRESSED)
+            .await
+    }
+
+    /// Executes a query and writes the results to a partitioned JSON file,
+    /// compressing every partition with `file_compression_type`.
+    pub async fn write_json_with_compression(
+        &self,
+        path: impl AsRef<str>,
+        file_compression_type: FileCompressionType,
+    ) -> Result<()> {
+        let plan = self.create_physical_plan().await?;
+        let state = self.session_state.read().clone();
+        plan_to_json(&state, plan, path, file_compression_type).await
+    }
+
+    /// Executes a query and writes the results to Arrow IPC files, laid out
+    /// according to `mode`.
+    pub async fn write_ipc(
+        &self,
+        path: impl AsRef<str>,
+        mode: IpcWriteMode,
+    ) -> Result<()> {
         let plan = self.create_physical_plan().await?;
         let state = self.session_state.read().clone();
-        plan_to_json(&state, plan, path).await
+        plan_to_ipc(&state, plan, path, mode).await
+    }
+
+    /// Executes a query and writes the results to `writer` in the Arrow IPC
+    /// streaming format, as they are produced.
+    pub async fn write_ipc_stream<W: std::io::Write>(&self, writer: W) -> Result<()> {
+        let plan = self.create_physical_plan().await?;
+        let state = self.session_state.read().clone();
+        plan_to_ipc_stream(&state, plan, writer).await
     }
 
     /// Add an additional column to the DataFrame.
@@ -694,8 +949,9 @@ impl DataFrame {
         )))
     }
 
-    /// Rename one column by applying a new projection. This is a no-op if the column to be
-    /// renamed does not exist.
+    /// Rename one column by applying a new projection. The column can be
+    /// referred to by its qualified name, or by its name alone if that is
+    /// unambiguous, and it is an error if there is no such column.
     ///
     /// ```
     /// # use datafusion::prelude::*;
@@ -704,7 +960,7 @@ impl DataFrame {
     /// # async fn main() -> Result<()> {
     /// let ctx = SessionContext::new();
     /// let df = ctx.read_csv("tests/example.csv", CsvReadOptions::new()).await?;
-    /// let df = df.with_column_renamed("ab_sum", "total")?;
+    /// let df = df.with_column_renamed("a", "total")?;
     /// # Ok(())
     /// # }
     /// ```
@@ -713,35 +969,95 @@ impl DataFrame {
         old_name: &str,
         new_name: &str,
     ) -> Result<Arc<DataFrame>> {
-        let mut projection = vec![];
-        let mut rename_applied = false;
-        for field in self.plan.schema().fields() {
-            let field_name = field.qualified_name();
-            if old_name == field_name {
-                projection.push(col(&field_name).alias(new_name));
-                rename_applied = true;
-            } else {
-                projection.push(col(&field_name));
-            }
-        }
-        if rename_applied {
-            let project_plan = LogicalPlanBuilder::from(self.plan.clone())
-                .project(projection)?
-                .build()?;
-            Ok(Arc::new(DataFrame::new(
-                self.session_state.clone(),
-                &project_plan,
-            )))
-        } else {
-            Ok(Arc::new(DataFrame::new(
-                self.session_state.clone(),
-                &self.plan,
-            )))
+        let idx = self.index_of_column(old_name)?;
+        let projection = self
+            .plan
+            .schema()
+            .fields()
+            .iter()
+            .enumerate()
+            .map(|(i, field)| {
+                let expr = Expr::Column(field.qualified_column());
+                if i == idx {
+                    expr.alias(new_name)
+                } else {
+                    expr
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let project_plan = LogicalPlanBuilder::from(self.plan.clone())
+            .project(projection)?
+            .build()?;
+        Ok(Arc::new(DataFrame::new(
+            self.session_state.clone(),
+            &project_plan,
+        )))
+    }
+
+    /// Remove columns from the DataFrame by applying a new projection. As for
+    /// [`DataFrame::with_column_renamed`], columns can be referred to by
+    /// their qualified name or their name alone, and it is an error if any
+    /// of them does not exist.
+    ///
+    /// ```
+    /// # use datafusion::prelude::*;
+    /// # use datafusion::error::Result;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// let ctx = SessionContext::new();
+    /// let df = ctx.read_csv("tests/example.csv", CsvReadOptions::new()).await?;
+    /// let df = df.drop_columns(&["b", "c"])?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn drop_columns(&self, columns: &[&str]) -> Result<Arc<DataFrame>> {
+        let dropped = columns
+            .iter()
+            .map(|name| self.index_of_column(name))
+            .collect::<Result<HashSet<_>>>()?;
+        let projection = self
+            .plan
+            .schema()
+            .fields()
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !dropped.contains(i))
+            .map(|(_, field)| Expr::Column(field.qualified_column()))
+            .collect::<Vec<_>>();
+
+        let project_plan = LogicalPlanBuilder::from(self.plan.clone())
+            .project(projection)?
+            .build()?;
+        Ok(Arc::new(DataFrame::new(
+            self.session_state.clone(),
+            &project_plan,
+        )))
+    }
+
+    /// Returns the index of the column named `name`, either its qualified
+    /// name or, if unambiguous, its name alone
+    fn index_of_column(&self, name: &str) -> Result<usize> {
+        let schema = self.plan.schema();
+        if let Some(idx) = schema
+            .fields()
+            .iter()
+            .position(|field| field.qualified_name() == name)
+        {
+            return Ok(idx);
         }
+
+        schema.index_of_column_by_name(None, name)
     }
 
     /// Cache DataFrame as a memory table.
     ///
+    /// The DataFrame is executed once and its results are kept in memory, so
+    /// that DataFrames derived from the returned one do not evaluate it
+    /// again. The memory held by the results is registered as tracked usage
+    /// with the memory manager of the session until the returned DataFrame,
+    /// and every plan built from it, are dropped.
+    ///
     /// ```
     /// # use datafusion::prelude::*;
     /// # use datafusion::error::Result;
@@ -754,16 +1070,47 @@ impl DataFrame {
     /// # }
     /// ```
     pub async fn cache(&self) -> Result<Arc<DataFrame>> {
+        let session_state = self.session_state.read().clone();
         let mem_table = MemTable::try_new(
             SchemaRef::from(self.schema().clone()),
             self.collect_partitioned().await?,
-        )?;
+        )?
+        .with_tracked_memory(session_state.runtime_env.clone());
 
-        SessionContext::with_state(self.session_state.read().clone())
-            .read_table(Arc::new(mem_table))
+        SessionContext::with_state(session_state).read_table(Arc::new(mem_table))
     }
 }
 
+/// Renders the values of `batch` as strings of at most `max_width`
+/// characters, longer ones being truncated with an ellipsis
+fn truncate_values(batch: &RecordBatch, max_width: usize) -> ArrowResult<RecordBatch> {
+    const ELLIPSIS: &str = "...";
+
+    let schema = batch.schema();
+    let mut fields = Vec::with_capacity(batch.num_columns());
+    let mut columns: Vec<ArrayRef> = Vec::with_capacity(batch.num_columns());
+    for (field, column) in schema.fields().iter().zip(batch.columns()) {
+        let values = (0..column.len())
+            .map(|row| {
+                if column.is_null(row) {
+                    return Ok(None);
+                }
+                let value = array_value_to_string(column, row)?;
+                if value.chars().count() <= max_width {
+                    return Ok(Some(value));
+                }
+                let keep = max_width.saturating_sub(ELLIPSIS.len());
+                Ok(Some(
+                    value.chars().take(keep).chain(ELLIPSIS.chars()).collect(),
+                ))
+            })
+            .collect::<ArrowResult<StringArray>>()?;
+        fields.push(Field::new(field.name(), DataType::Utf8, true));
+        columns.push(Arc::new(values));
+    }
+    RecordBatch::try_new(Arc::new(Schema::new(fields)), columns)
+}
+
 // TODO: This will introduce a ref cycle (#2659)
 #[async_trait]
 impl TableProvider for DataFrame {
@@ -837,23 +1184,26 @@ impl TableProvider for DataFrame {
 
 #[cfg(test)]
 mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
     use std::vec;
 
     use super::*;
+    use crate::assert_batches_sorted_eq;
+    use crate::execution::context::{SessionConfig, SessionContext};
     use crate::execution::options::{CsvReadOptions, ParquetReadOptions};
+    use crate::physical_plan::common;
     use crate::physical_plan::ColumnarValue;
     use crate::physical_plan::Partitioning;
     use crate::physical_plan::PhysicalExpr;
     use crate::test_util;
     use crate::test_util::parquet_test_data;
-    use crate::{assert_batches_sorted_eq, execution::context::SessionContext};
-    use arrow::array::Int32Array;
-    use arrow::datatypes::DataType;
+    use arrow::array::{Int32Array, Int64Array};
     use datafusion_expr::{
-        avg, cast, count, count_distinct, create_udf, lit, max, min, sum,
+        avg, cast, col, count, count_distinct, create_udf, lit, max, min, sum,
         BuiltInWindowFunction, ScalarFunctionImplementation, Volatility, WindowFunction,
     };
     use datafusion_physical_expr::expressions::Column;
+    use futures::StreamExt;
 
     #[tokio::test]
     async fn select_columns() -> Result<()> {
@@ -1096,6 +1446,276 @@ mod tests {
         Ok(())
     }
 
+    /// A context with the tables `t1 (a, b)` and `t2 (c, d)`
+    fn set_op_context() -> Result<SessionContext> {
+        let ctx = SessionContext::new();
+        ctx.register_batch(
+            "t1",
+            RecordBatch::try_from_iter(vec![
+                ("a", Arc::new(Int32Array::from(vec![1, 2, 3, 3])) as _),
+                (
+                    "b",
+                    Arc::new(arrow::array::StringArray::from(vec!["x", "y", "z", "z"]))
+                        as _,
+                ),
+            ])?,
+        )?;
+        ctx.register_batch(
+            "t2",
+            RecordBatch::try_from_iter(vec![
+                ("c", Arc::new(Int32Array::from(vec![1, 2, 3])) as _),
+                ("d", Arc::new(Int32Array::from(vec![2, 1, 5])) as _),
+            ])?,
+        )?;
+        Ok(ctx)
+    }
+
+    #[tokio::test]
+    async fn join_on() -> Result<()> {
+        let ctx = set_op_context()?;
+        let t1 = ctx.table("t1")?;
+        let t2 = ctx.table("t2")?;
+
+        // no equality between the inputs
+        let df = t1.join_on(t2.clone(), JoinType::Inner, vec![col("a").lt(col("d"))])?;
+        assert_eq!(
+            format!("{:?}", df.to_unoptimized_plan()),
+            "Filter: t1.a < t2.d\
+            \n  CrossJoin:\
+            \n    TableScan: t1\
+            \n    TableScan: t2"
+        );
+        assert_batches_sorted_eq!(
+            vec![
+                "+---+---+---+---+",
+                "| a | b | c | d |",
+                "+---+---+---+---+",
+                "| 1 | x | 1 | 2 |",
+                "| 1 | x | 3 | 5 |",
+                "| 2 | y | 3 | 5 |",
+                "| 3 | z | 3 | 5 |",
+                "| 3 | z | 3 | 5 |",
+                "+---+---+---+---+",
+            ],
+            &df.collect().await?
+        );
+
+        // an equality key and a filter of the matched rows
+        let df = t1.join_on(
+            t2.clone(),
+            JoinType::Left,
+            vec![col("c").eq(col("a")), col("d").gt(lit(1))],
+        )?;
+        assert_eq!(
+            format!("{:?}", df.to_unoptimized_plan()),
+            "Left Join: t1.a = t2.c Filter: t2.d > Int64(1)\
+            \n  TableScan: t1\
+            \n  TableScan: t2"
+        );
+        assert_batches_sorted_eq!(
+            vec![
+                "+---+---+---+---+",
+                "| a | b | c | d |",
+                "+---+---+---+---+",
+                "| 1 | x | 1 | 2 |",
+                "| 2 | y |   |   |",
+                "| 3 | z | 3 | 5 |",
+                "| 3 | z | 3 | 5 |",
+                "+---+---+---+---+",
+            ],
+            &df.collect().await?
+        );
+
+        let err = t1
+            .join_on(t2, JoinType::Inner, vec![col("a").eq(col("e"))])
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Schema error: No field named 'e'. Valid fields are 't1'.'a', 't1'.'b', 't2'.'c', 't2'.'d'."
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn read_table_join_sql_round_trip() -> Result<()> {
+        let ctx = set_op_context()?;
+        let batch = RecordBatch::try_from_iter(vec![
+            ("a", Arc::new(Int32Array::from(vec![1, 2, 3])) as _),
+            ("b", Arc::new(StringArray::from(vec!["x", "y", "z"])) as _),
+        ])?;
+        let provider = MemTable::try_new(batch.schema(), vec![vec![batch]])?;
+
+        // an unregistered provider, filtered with the builder API and joined
+        // with the result of a query
+        let left = ctx
+            .read_table(Arc::new(provider))?
+            .filter(col("a").gt(lit(1)))?;
+        let right = ctx.sql("SELECT c, d * 10 AS d10 FROM t2").await?;
+        let df = left.join(right, JoinType::Inner, &["a"], &["c"], None)?;
+
+        // rebuilt from its plan, extended and run again
+        let plan = LogicalPlanBuilder::from(df.into_unoptimized_plan())
+            .project(vec![col("b"), col("d10")])?
+            .build()?;
+        let df = DataFrame::from_logical_plan(&ctx, plan);
+        assert_batches_sorted_eq!(
+            vec![
+                "+---+-----+",
+                "| b | d10 |",
+                "+---+-----+",
+                "| y | 10  |",
+                "| z | 50  |",
+                "+---+-----+",
+            ],
+            &df.collect().await?
+        );
+
+        // only the used columns are scanned once optimized
+        let optimized = format!("{:?}", df.into_optimized_plan()?);
+        assert!(
+            optimized.contains("TableScan: ?table? projection=[a, b]"),
+            "{}",
+            optimized
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn intersect_and_except_distinct() -> Result<()> {
+        let ctx = set_op_context()?;
+        let t1 = ctx.table("t1")?.select_columns(&["a"])?;
+        let t2 = ctx.table("t2")?.select_columns(&["c"])?;
+
+        let df = t1.intersect_distinct(t2.clone())?;
+        assert_eq!(df.schema().field(0).name(), "a");
+        assert_batches_sorted_eq!(
+            vec!["+---+", "| a |", "+---+", "| 1 |", "| 2 |", "| 3 |", "+---+"],
+            &df.collect().await?
+        );
+
+        // unlike INTERSECT ALL, duplicates of the left input are removed
+        let df = t1.intersect(t2.clone())?;
+        assert_eq!(
+            df.collect()
+                .await?
+                .iter()
+                .map(|b| b.num_rows())
+                .sum::<usize>(),
+            4
+        );
+
+        let df = t2.except_distinct(ctx.table("t2")?.select_columns(&["d"])?)?;
+        assert_batches_sorted_eq!(
+            vec!["+---+", "| c |", "+---+", "| 3 |", "+---+"],
+            &df.collect().await?
+        );
+
+        let err = t1.except_distinct(ctx.table("t2")?).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Error during planning: INTERSECT/EXCEPT query must have the same number of columns. Left is 1 and right is 2."
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn with_column_replacing_type() -> Result<()> {
+        let ctx = set_op_context()?;
+        let df = ctx
+            .table("t1")?
+            .with_column("b", col("a") * lit(10))?
+            .with_column("a", cast(col("a"), DataType::Utf8))?;
+
+        let fields = df
+            .schema()
+            .fields()
+            .iter()
+            .map(|f| (f.name().as_str(), f.data_type().clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(fields, vec![("a", DataType::Utf8), ("b", DataType::Int64)]);
+        assert_batches_sorted_eq!(
+            vec![
+                "+---+----+",
+                "| a | b  |",
+                "+---+----+",
+                "| 1 | 10 |",
+                "| 2 | 20 |",
+                "| 3 | 30 |",
+                "| 3 | 30 |",
+                "+---+----+",
+            ],
+            &df.collect().await?
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn rename_and_drop_columns() -> Result<()> {
+        let ctx = set_op_context()?;
+        let df = ctx.table("t1")?.join(
+            ctx.table("t2")?,
+            JoinType::Inner,
+            &["a"],
+            &["c"],
+            None,
+        )?;
+
+        // columns can be referred to by their name alone if unambiguous
+        let renamed = df
+            .with_column_renamed("b", "bb")?
+            .drop_columns(&["t2.c", "a"])?;
+        let names = renamed
+            .schema()
+            .fields()
+            .iter()
+            .map(|f| f.name().as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["bb", "d"]);
+        assert_batches_sorted_eq!(
+            vec![
+                "+----+---+",
+                "| bb | d |",
+                "+----+---+",
+                "| x  | 2 |",
+                "| y  | 1 |",
+                "| z  | 5 |",
+                "| z  | 5 |",
+                "+----+---+",
+            ],
+            &renamed.collect().await?
+        );
+
+        let err = df.with_column_renamed("e", "ee").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Schema error: No field named 'e'. Valid fields are 't1'.'a', 't1'.'b', 't2'.'c', 't2'.'d'."
+        );
+
+        ctx.register_table("t3", ctx.table("t1")?)?;
+        let df = ctx.table("t1")?.join(
+            ctx.table("t3")?,
+            JoinType::Inner,
+            &["a"],
+            &["a"],
+            None,
+        )?;
+        let err = df.drop_columns(&["a"]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Schema error: Ambiguous reference to unqualified field 'a', which could be any of 't1'.'a', 't3'.'a'"
+        );
+        assert_eq!(
+            df.drop_columns(&["t3.a", "t3.b"])?.schema().fields().len(),
+            2
+        );
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn register_table() -> Result<()> {
         let df = test_table().await?.select_columns(&["c1", "c12"])?;
@@ -1519,6 +2139,244 @@ mod tests {
         Ok(())
     }
 
+    /// A table of `partitions` partitions of `batches` batches of `rows` rows,
+    /// with the partition index in column `p` and a running count in `v`
+    fn partitioned_table(
+        ctx: &SessionContext,
+        partitions: usize,
+        batches: usize,
+        rows: usize,
+    ) -> Result<Arc<DataFrame>> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("p", DataType::Int32, false),
+            Field::new("v", DataType::Int64, false),
+        ]));
+        let data = (0..partitions)
+            .map(|partition| {
+                (0..batches)
+                    .map(|batch| {
+                        let start = ((partition * batches + batch) * rows) as i64;
+                        RecordBatch::try_new(
+                            schema.clone(),
+                            vec![
+                                Arc::new(Int32Array::from(vec![partition as i32; rows])),
+                                Arc::new(Int64Array::from_iter_values(
+                                    start..start + rows as i64,
+                                )),
+                            ],
+                        )
+                    })
+                    .collect::<ArrowResult<Vec<_>>>()
+            })
+            .collect::<ArrowResult<Vec<_>>>()?;
+        ctx.read_table(Arc::new(MemTable::try_new(schema, data)?))
+    }
+
+    #[tokio::test]
+    async fn execute_stream_is_incremental() -> Result<()> {
+        let config = SessionConfig::new()
+            .with_target_partitions(4)
+            .with_batch_size(1000);
+        let ctx = SessionContext::with_config(config);
+        let df = partitioned_table(&ctx, 4, 25, 1000)?
+            .select(vec![col("p"), (col("v") * lit(2_i64)).alias("w")])?;
+
+        // the partitions are merged into a single stream of batches that is
+        // consumed one batch at a time
+        let mut stream = df.execute_stream().await?;
+        let (mut batches, mut rows, mut max_rows) = (0, 0, 0);
+        while let Some(batch) = stream.next().await {
+            let batch = batch?;
+            batches += 1;
+            rows += batch.num_rows();
+            max_rows = max_rows.max(batch.num_rows());
+        }
+        assert_eq!(batches, 100);
+        assert_eq!(rows, 100_000);
+        assert_eq!(max_rows, 1000);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn partitioned_results_keep_partition_boundaries() -> Result<()> {
+        let config = SessionConfig::new().with_target_partitions(3);
+        let ctx = SessionContext::with_config(config);
+        let df = partitioned_table(&ctx, 3, 2, 10)?.filter(col("v").gt(lit(5_i64)))?;
+
+        let partition_values = |batches: &[RecordBatch]| -> (HashSet<i32>, usize) {
+            let mut values = HashSet::new();
+            let mut rows = 0;
+            for batch in batches {
+                let p = batch
+                    .column(0)
+                    .as_any()
+                    .downcast_ref::<Int32Array>()
+                    .unwrap();
+                values.extend(p.iter().flatten());
+                rows += batch.num_rows();
+            }
+            (values, rows)
+        };
+
+        let partitions = df.collect_partitioned().await?;
+        assert_eq!(partitions.len(), 3);
+        // the first 6 rows of partition 0 are filtered out
+        let expected = vec![
+            (HashSet::from([0]), 14),
+            (HashSet::from([1]), 20),
+            (HashSet::from([2]), 20),
+        ];
+        let actual: Vec<_> = partitions.iter().map(|b| partition_values(b)).collect();
+        assert_eq!(actual, expected);
+
+        let streams = df.execute_stream_partitioned().await?;
+        assert_eq!(streams.len(), 3);
+        let mut actual = vec![];
+        for stream in streams {
+            let batches = common::collect(stream).await?;
+            actual.push(partition_values(&batches));
+        }
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    fn show_table(ctx: &SessionContext) -> Result<Arc<DataFrame>> {
+        use arrow::array::{StructArray, TimestampSecondArray};
+
+        let s = StructArray::from(vec![
+            (
+                Field::new("a", DataType::Int32, true),
+                Arc::new(Int32Array::from(vec![Some(1), Some(2), None])) as ArrayRef,
+            ),
+            (
+                Field::new("b", DataType::Utf8, true),
+                Arc::new(StringArray::from(vec![Some("x"), None, Some("z")])) as ArrayRef,
+            ),
+        ]);
+        let batch = RecordBatch::try_from_iter(vec![
+            (
+                "id",
+                Arc::new(Int32Array::from(vec![Some(1), None, Some(3)])) as ArrayRef,
+            ),
+            (
+                "ts",
+                Arc::new(TimestampSecondArray::from(vec![
+                    Some(1_614_618_000),
+                    None,
+                    Some(0),
+                ])) as ArrayRef,
+            ),
+            (
+                "name",
+                Arc::new(StringArray::from(vec![
+                    Some("short"),
+                    Some("a rather long value"),
+                    None,
+                ])) as ArrayRef,
+            ),
+            ("s", Arc::new(s) as ArrayRef),
+        ])?;
+        ctx.read_batch(batch)
+    }
+
+    #[tokio::test]
+    async fn to_string_limit() -> Result<()> {
+        let df = show_table(&SessionContext::new())?;
+
+        let expected = vec![
+            "+----+---------------------+---------------------+-----------------------+",
+            "| id | ts                  | name                | s                     |",
+            "+----+---------------------+---------------------+-----------------------+",
+            "| 1  | 2021-03-01T17:00:00 | short               | {\"a\": 1, \"b\": \"x\"}    |",
+            "|    |                     | a rather long value | {\"a\": 2, \"b\": null}   |",
+            "| 3  | 1970-01-01T00:00:00 |                     | {\"a\": null, \"b\": \"z\"} |",
+            "+----+---------------------+---------------------+-----------------------+",
+        ];
+        assert_eq!(df.to_string_limit(10).await?, expected.join("\n"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn to_string_limit_truncates_wide_values() -> Result<()> {
+        let config = SessionConfig::new().set_u64(OPT_FORMAT_MAX_COLUMN_WIDTH, 12);
+        let df = show_table(&SessionContext::with_config(config))?;
+
+        let expected = vec![
+            "+----+--------------+--------------+--------------+",
+            "| id | ts           | name         | s            |",
+            "+----+--------------+--------------+--------------+",
+            "| 1  | 2021-03-0... | short        | {\"a\": 1, ... |",
+            "|    |              | a rather ... | {\"a\": 2, ... |",
+            "+----+--------------+--------------+--------------+",
+        ];
+        assert_eq!(df.to_string_limit(2).await?, expected.join("\n"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn cache_evaluates_source_once() -> Result<()> {
+        // counts the values the UDF is invoked on
+        let evaluated = Arc::new(AtomicUsize::new(0));
+        let counter = evaluated.clone();
+        let identity: ScalarFunctionImplementation =
+            Arc::new(move |args: &[ColumnarValue]| {
+                let rows = match &args[0] {
+                    ColumnarValue::Array(array) => array.len(),
+                    ColumnarValue::Scalar(_) => 1,
+                };
+                counter.fetch_add(rows, Ordering::SeqCst);
+                Ok(args[0].clone())
+            });
+        let identity = create_udf(
+            "identity",
+            vec![DataType::Float64],
+            Arc::new(DataType::Float64),
+            Volatility::Immutable,
+            identity,
+        );
+
+        let df = test_table()
+            .await?
+            .select_columns(&["c2", "c12"])?
+            .with_column("v", identity.call(vec![col("c12")]))?;
+        let runtime = df.session_state.read().runtime_env.clone();
+        let tracked_before = runtime.memory_manager.get_tracker_total();
+
+        let cached_df = df.cache().await?;
+        assert_eq!(evaluated.load(Ordering::SeqCst), 100);
+        let tracked = runtime.memory_manager.get_tracker_total() - tracked_before;
+        assert!(tracked > 0);
+
+        let total = cached_df
+            .aggregate(vec![], vec![count(col("v"))])?
+            .collect()
+            .await?;
+        let per_group = cached_df
+            .aggregate(vec![col("c2")], vec![count(col("v"))])?
+            .collect()
+            .await?;
+        let total = total[0]
+            .column(0)
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .unwrap()
+            .value(0);
+        assert_eq!(total, 100);
+        assert_eq!(per_group.iter().map(|b| b.num_rows()).sum::<usize>(), 5);
+        // the source was not evaluated again by either aggregation
+        assert_eq!(evaluated.load(Ordering::SeqCst), 100);
+
+        // dropping the cached DataFrame releases its memory
+        drop(cached_df);
+        assert_eq!(runtime.memory_manager.get_tracker_total(), tracked_before);
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn partition_aware_union() -> Result<()> {
         let left = test_table().await?.select_columns(&["c1", "c2"])?;
@@ -58,6 +58,12 @@ pub trait TableProvider: Sync + Send {
     /// The table provider will be usually responsible of grouping
     /// the source data into partitions that can be efficiently
     /// parallelized or distributed.
+    ///
+    /// `filters` are the filters for which [`Self::supports_filter_pushdown`]
+    /// returned [`TableProviderFilterPushDown::Exact`] or
+    /// [`TableProviderFilterPushDown::Inexact`], which are combined with AND.
+    /// The provider must apply the exact ones itself, as they are not
+    /// evaluated again on the rows it returns.
     async fn scan(
         &self,
         ctx: &SessionState,
@@ -71,7 +77,9 @@ pub trait TableProvider: Sync + Send {
     ) -> Result<Arc<dyn ExecutionPlan>>;
 
     /// Tests whether the table provider can make use of a filter expression
-    /// to optimise data retrieval.
+    /// to optimise data retrieval. This is called for every conjunct of a
+    /// filter applied to the table, so a provider may handle some of them
+    /// exactly and leave the others to be evaluated after the scan.
     fn supports_filter_pushdown(
         &self,
         _filter: &Expr,
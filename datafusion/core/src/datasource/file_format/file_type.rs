@@ -20,20 +20,34 @@
 use crate::error::{DataFusionError, Result};
 use std::io::Error;
 
+#[cfg(feature = "compression_bzip2")]
+use async_compression::tokio::bufread::BzDecoder as AsyncBzDecoder;
+#[cfg(feature = "compression_xz")]
+use async_compression::tokio::bufread::XzDecoder as AsyncXzDecoder;
 use async_compression::tokio::bufread::{
-    BzDecoder as AsyncBzDecoder, GzipDecoder as AsyncGzDecoder,
+    GzipDecoder as AsyncGzDecoder, ZstdDecoder as AsyncZstdDecoder,
 };
+#[cfg(feature = "compression_bzip2")]
 use bzip2::read::BzDecoder;
+#[cfg(feature = "compression_bzip2")]
+use bzip2::write::BzEncoder;
 
 use crate::datasource::file_format::avro::DEFAULT_AVRO_EXTENSION;
 use crate::datasource::file_format::csv::DEFAULT_CSV_EXTENSION;
+use crate::datasource::file_format::ipc::DEFAULT_IPC_EXTENSION;
 use crate::datasource::file_format::json::DEFAULT_JSON_EXTENSION;
 use crate::datasource::file_format::parquet::DEFAULT_PARQUET_EXTENSION;
 use bytes::Bytes;
 use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
 use futures::{Stream, TryStreamExt};
 use std::str::FromStr;
 use tokio_util::io::{ReaderStream, StreamReader};
+#[cfg(feature = "compression_xz")]
+use xz2::read::XzDecoder;
+#[cfg(feature = "compression_xz")]
+use xz2::write::XzEncoder;
+use zstd::{Decoder as ZstdDecoder, Encoder as ZstdEncoder};
 
 /// Define each `FileType`/`FileCompressionType`'s extension
 pub trait GetExt {
@@ -48,6 +62,10 @@ pub enum FileCompressionType {
     GZIP,
     /// Bzip2-ed file
     BZIP2,
+    /// Zstandard-ed file
+    ZSTD,
+    /// Xz-ed file
+    XZ,
     /// Uncompressed file
     UNCOMPRESSED,
 }
@@ -57,6 +75,8 @@ impl GetExt for FileCompressionType {
         match self {
             FileCompressionType::GZIP => ".gz".to_owned(),
             FileCompressionType::BZIP2 => ".bz2".to_owned(),
+            FileCompressionType::ZSTD => ".zst".to_owned(),
+            FileCompressionType::XZ => ".xz".to_owned(),
             FileCompressionType::UNCOMPRESSED => "".to_owned(),
         }
     }
@@ -70,6 +90,8 @@ impl FromStr for FileCompressionType {
         match s.as_str() {
             "GZIP" | "GZ" => Ok(FileCompressionType::GZIP),
             "BZIP2" | "BZ2" => Ok(FileCompressionType::BZIP2),
+            "ZSTD" | "ZST" => Ok(FileCompressionType::ZSTD),
+            "XZ" => Ok(FileCompressionType::XZ),
             "" => Ok(FileCompressionType::UNCOMPRESSED),
             _ => Err(DataFusionError::NotImplemented(format!(
                 "Unknown FileCompressionType: {}",
@@ -81,11 +103,26 @@ impl FromStr for FileCompressionType {
 
 /// `FileCompressionType` implementation
 impl FileCompressionType {
+    /// Detect the compression of a file from the extension of its `path`,
+    /// such as `.gz` for `GZIP`, or `UNCOMPRESSED` if it has no known
+    /// compression extension.
+    pub fn from_path(path: &str) -> Self {
+        [
+            FileCompressionType::GZIP,
+            FileCompressionType::BZIP2,
+            FileCompressionType::ZSTD,
+            FileCompressionType::XZ,
+        ]
+        .into_iter()
+        .find(|compression| path.ends_with(&compression.get_ext()))
+        .unwrap_or(FileCompressionType::UNCOMPRESSED)
+    }
+
     /// Given a `Stream`, create a `Stream` which data are decompressed with `FileCompressionType`.
     pub fn convert_stream<T: Stream<Item = Result<Bytes>> + Unpin + Send + 'static>(
         &self,
         s: T,
-    ) -> Box<dyn Stream<Item = Result<Bytes>> + Send + Unpin> {
+    ) -> Result<Box<dyn Stream<Item = Result<Bytes>> + Send + Unpin>> {
         let err_converter = |e: Error| match e
             .get_ref()
             .and_then(|e| e.downcast_ref::<DataFusionError>())
@@ -99,35 +136,160 @@ impl FileCompressionType {
             None => Into::<DataFusionError>::into(e),
         };
 
-        match self {
+        Ok(match self {
             FileCompressionType::GZIP => Box::new(
                 ReaderStream::new(AsyncGzDecoder::new(StreamReader::new(s)))
                     .map_err(err_converter),
             ),
+            #[cfg(feature = "compression_bzip2")]
             FileCompressionType::BZIP2 => Box::new(
                 ReaderStream::new(AsyncBzDecoder::new(StreamReader::new(s)))
                     .map_err(err_converter),
             ),
+            FileCompressionType::ZSTD => Box::new(
+                ReaderStream::new(AsyncZstdDecoder::new(StreamReader::new(s)))
+                    .map_err(err_converter),
+            ),
+            #[cfg(feature = "compression_xz")]
+            FileCompressionType::XZ => Box::new(
+                ReaderStream::new(AsyncXzDecoder::new(StreamReader::new(s)))
+                    .map_err(err_converter),
+            ),
+            #[cfg(not(feature = "compression_bzip2"))]
+            FileCompressionType::BZIP2 => {
+                return Err(feature_disabled("compression_bzip2"))
+            }
+            #[cfg(not(feature = "compression_xz"))]
+            FileCompressionType::XZ => return Err(feature_disabled("compression_xz")),
             FileCompressionType::UNCOMPRESSED => Box::new(s),
-        }
+        })
     }
 
     /// Given a `Read`, create a `Read` which data are decompressed with `FileCompressionType`.
     pub fn convert_read<T: std::io::Read + Send + 'static>(
         &self,
         r: T,
-    ) -> Box<dyn std::io::Read + Send> {
-        match self {
+    ) -> Result<Box<dyn std::io::Read + Send>> {
+        Ok(match self {
             FileCompressionType::GZIP => Box::new(GzDecoder::new(r)),
+            #[cfg(feature = "compression_bzip2")]
             FileCompressionType::BZIP2 => Box::new(BzDecoder::new(r)),
+            FileCompressionType::ZSTD => Box::new(ZstdDecoder::new(r)?),
+            #[cfg(feature = "compression_xz")]
+            FileCompressionType::XZ => Box::new(XzDecoder::new(r)),
+            #[cfg(not(feature = "compression_bzip2"))]
+            FileCompressionType::BZIP2 => {
+                return Err(feature_disabled("compression_bzip2"))
+            }
+            #[cfg(not(feature = "compression_xz"))]
+            FileCompressionType::XZ => return Err(feature_disabled("compression_xz")),
             FileCompressionType::UNCOMPRESSED => Box::new(r),
+        })
+    }
+
+    /// Given a `Write`, create a [`FileEncoder`] which data are compressed with `FileCompressionType`.
+    ///
+    /// The compressed data is only complete once [`FileEncoder::finish`] is
+    /// called.
+    pub fn convert_write<T: std::io::Write + Send + 'static>(
+        &self,
+        w: T,
+    ) -> Result<FileEncoder<T>> {
+        let encoder = match self {
+            FileCompressionType::GZIP => {
+                Encoder::Gzip(GzEncoder::new(w, flate2::Compression::default()))
+            }
+            #[cfg(feature = "compression_bzip2")]
+            FileCompressionType::BZIP2 => {
+                Encoder::Bzip2(BzEncoder::new(w, bzip2::Compression::default()))
+            }
+            FileCompressionType::ZSTD => Encoder::Zstd(ZstdEncoder::new(w, 0)?),
+            #[cfg(feature = "compression_xz")]
+            FileCompressionType::XZ => Encoder::Xz(XzEncoder::new(w, 6)),
+            #[cfg(not(feature = "compression_bzip2"))]
+            FileCompressionType::BZIP2 => {
+                return Err(feature_disabled("compression_bzip2"))
+            }
+            #[cfg(not(feature = "compression_xz"))]
+            FileCompressionType::XZ => return Err(feature_disabled("compression_xz")),
+            FileCompressionType::UNCOMPRESSED => Encoder::Uncompressed(w),
+        };
+        Ok(FileEncoder { encoder })
+    }
+}
+
+/// A `Write` compressing the data written to it, see
+/// [`FileCompressionType::convert_write`]
+pub struct FileEncoder<W: std::io::Write> {
+    encoder: Encoder<W>,
+}
+
+enum Encoder<W: std::io::Write> {
+    Gzip(GzEncoder<W>),
+    #[cfg(feature = "compression_bzip2")]
+    Bzip2(BzEncoder<W>),
+    Zstd(ZstdEncoder<'static, W>),
+    #[cfg(feature = "compression_xz")]
+    Xz(XzEncoder<W>),
+    Uncompressed(W),
+}
+
+impl<W: std::io::Write> FileEncoder<W> {
+    /// Completes the compressed data, such as by writing the trailer of
+    /// its format, returning the underlying `Write`. Dropping the encoder
+    /// instead may leave the data incomplete.
+    pub fn finish(self) -> std::io::Result<W> {
+        match self.encoder {
+            Encoder::Gzip(encoder) => encoder.finish(),
+            #[cfg(feature = "compression_bzip2")]
+            Encoder::Bzip2(encoder) => encoder.finish(),
+            Encoder::Zstd(encoder) => encoder.finish(),
+            #[cfg(feature = "compression_xz")]
+            Encoder::Xz(encoder) => encoder.finish(),
+            Encoder::Uncompressed(mut w) => {
+                w.flush()?;
+                Ok(w)
+            }
+        }
+    }
+
+    fn inner(&mut self) -> &mut dyn std::io::Write {
+        match &mut self.encoder {
+            Encoder::Gzip(encoder) => encoder,
+            #[cfg(feature = "compression_bzip2")]
+            Encoder::Bzip2(encoder) => encoder,
+            Encoder::Zstd(encoder) => encoder,
+            #[cfg(feature = "compression_xz")]
+            Encoder::Xz(encoder) => encoder,
+            Encoder::Uncompressed(w) => w,
         }
     }
 }
 
+impl<W: std::io::Write> std::io::Write for FileEncoder<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.inner().write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner().flush()
+    }
+}
+
+/// Returns the error for a compression whose `feature` of DataFusion is disabled
+#[cfg(any(not(feature = "compression_bzip2"), not(feature = "compression_xz")))]
+fn feature_disabled(feature: &str) -> DataFusionError {
+    DataFusionError::NotImplemented(format!(
+        "The compression requires the '{}' feature of DataFusion",
+        feature
+    ))
+}
+
 /// Readable file type
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum FileType {
+    /// Apache Arrow IPC file
+    ARROW,
     /// Apache Avro file
     AVRO,
     /// Apache Parquet file
@@ -141,6 +303,7 @@ pub enum FileType {
 impl GetExt for FileType {
     fn get_ext(&self) -> String {
         match self {
+            FileType::ARROW => DEFAULT_IPC_EXTENSION.to_owned(),
             FileType::AVRO => DEFAULT_AVRO_EXTENSION.to_owned(),
             FileType::PARQUET => DEFAULT_PARQUET_EXTENSION.to_owned(),
             FileType::CSV => DEFAULT_CSV_EXTENSION.to_owned(),
@@ -155,6 +318,7 @@ impl FromStr for FileType {
     fn from_str(s: &str) -> Result<Self> {
         let s = s.to_uppercase();
         match s.as_str() {
+            "ARROW" | "IPC" => Ok(FileType::ARROW),
             "AVRO" => Ok(FileType::AVRO),
             "PARQUET" => Ok(FileType::PARQUET),
             "CSV" => Ok(FileType::CSV),
@@ -174,7 +338,7 @@ impl FileType {
 
         match self {
             FileType::JSON | FileType::CSV => Ok(format!("{}{}", ext, c.get_ext())),
-            FileType::PARQUET | FileType::AVRO => match c {
+            FileType::PARQUET | FileType::AVRO | FileType::ARROW => match c {
                 FileCompressionType::UNCOMPRESSED => Ok(ext),
                 _ => Err(DataFusionError::Internal(
                     "FileCompressionType can be specified for CSV/JSON FileType.".into(),
@@ -190,6 +354,21 @@ mod tests {
     use crate::error::DataFusionError;
     use std::str::FromStr;
 
+    #[test]
+    fn from_path() {
+        let cases = [
+            ("data.json", FileCompressionType::UNCOMPRESSED),
+            ("dir.gz/data.json", FileCompressionType::UNCOMPRESSED),
+            ("data.json.gz", FileCompressionType::GZIP),
+            ("data.json.bz2", FileCompressionType::BZIP2),
+            ("data.json.zst", FileCompressionType::ZSTD),
+            ("data.json.xz", FileCompressionType::XZ),
+        ];
+        for (path, expected) in cases {
+            assert_eq!(FileCompressionType::from_path(path), expected, "{}", path);
+        }
+    }
+
     #[test]
     fn get_ext_with_compression() {
         let file_type = FileType::CSV;
@@ -211,6 +390,18 @@ mod tests {
                 .unwrap(),
             ".csv.bz2"
         );
+        assert_eq!(
+            file_type
+                .get_ext_with_compression(FileCompressionType::ZSTD)
+                .unwrap(),
+            ".csv.zst"
+        );
+        assert_eq!(
+            file_type
+                .get_ext_with_compression(FileCompressionType::XZ)
+                .unwrap(),
+            ".csv.xz"
+        );
 
         let file_type = FileType::JSON;
         assert_eq!(
@@ -231,6 +422,18 @@ mod tests {
                 .unwrap(),
             ".json.bz2"
         );
+        assert_eq!(
+            file_type
+                .get_ext_with_compression(FileCompressionType::ZSTD)
+                .unwrap(),
+            ".json.zst"
+        );
+        assert_eq!(
+            file_type
+                .get_ext_with_compression(FileCompressionType::XZ)
+                .unwrap(),
+            ".json.xz"
+        );
 
         let file_type = FileType::AVRO;
         assert_eq!(
@@ -273,6 +476,8 @@ mod tests {
         assert_eq!(FileType::from_str("json").unwrap(), FileType::JSON);
         assert_eq!(FileType::from_str("JSON").unwrap(), FileType::JSON);
 
+        assert_eq!(FileType::from_str("arrow").unwrap(), FileType::ARROW);
+        assert_eq!(FileType::from_str("IPC").unwrap(), FileType::ARROW);
         assert_eq!(FileType::from_str("avro").unwrap(), FileType::AVRO);
         assert_eq!(FileType::from_str("AVRO").unwrap(), FileType::AVRO);
 
@@ -318,6 +523,19 @@ mod tests {
             FileCompressionType::BZIP2
         );
 
+        assert_eq!(
+            FileCompressionType::from_str("zst").unwrap(),
+            FileCompressionType::ZSTD
+        );
+        assert_eq!(
+            FileCompressionType::from_str("ZSTD").unwrap(),
+            FileCompressionType::ZSTD
+        );
+        assert_eq!(
+            FileCompressionType::from_str("xz").unwrap(),
+            FileCompressionType::XZ
+        );
+
         assert_eq!(
             FileCompressionType::from_str("").unwrap(),
             FileCompressionType::UNCOMPRESSED
@@ -0,0 +1,399 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Arrow IPC (Feather v2 / streaming) format abstractions
+
+use std::any::Any;
+use std::io::{Read, Seek, SeekFrom};
+use std::ops::Range;
+use std::sync::Arc;
+
+use arrow::datatypes::{Schema, SchemaRef};
+use arrow::ipc;
+use arrow::ipc::reader::{FileReader, StreamReader};
+use arrow::record_batch::RecordBatch;
+use async_trait::async_trait;
+use bytes::Bytes;
+use object_store::{ObjectMeta, ObjectStore};
+
+use super::FileFormat;
+use crate::error::{DataFusionError, Result};
+use crate::logical_expr::Expr;
+use crate::physical_plan::file_format::{FileScanConfig, IpcExec};
+use crate::physical_plan::ExecutionPlan;
+use crate::physical_plan::Statistics;
+
+/// The default file extension of Arrow IPC files
+pub const DEFAULT_IPC_EXTENSION: &str = ".arrow";
+
+/// The magic bytes at the start (and end) of an Arrow IPC file
+const ARROW_MAGIC: [u8; 6] = *b"ARROW1";
+
+/// Arrow IPC `FileFormat` implementation.
+///
+/// Both the IPC file format (Feather v2) and the IPC streaming format
+/// are supported; which one a given file uses is detected from its
+/// leading magic bytes.
+#[derive(Default, Debug)]
+pub struct IpcFormat;
+
+#[async_trait]
+impl FileFormat for IpcFormat {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    async fn infer_schema(
+        &self,
+        store: &Arc<dyn ObjectStore>,
+        objects: &[ObjectMeta],
+    ) -> Result<SchemaRef> {
+        let mut schemas = vec![];
+        for object in objects {
+            schemas.push(IpcMetadata::new(store, object).schema().await?);
+        }
+        let merged_schema = Schema::try_merge(schemas)?;
+        Ok(Arc::new(merged_schema))
+    }
+
+    async fn infer_stats(
+        &self,
+        store: &Arc<dyn ObjectStore>,
+        _table_schema: SchemaRef,
+        object: &ObjectMeta,
+    ) -> Result<Statistics> {
+        let num_rows = IpcMetadata::new(store, object).num_rows().await?;
+        Ok(Statistics {
+            num_rows: Some(num_rows),
+            total_byte_size: Some(object.size),
+            column_statistics: None,
+            is_exact: true,
+        })
+    }
+
+    async fn create_physical_plan(
+        &self,
+        conf: FileScanConfig,
+        _filters: &[Expr],
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let exec = IpcExec::new(conf);
+        Ok(Arc::new(exec))
+    }
+}
+
+/// The marker preceding the length of the metadata of an IPC message
+const CONTINUATION_MARKER: [u8; 4] = [0xff; 4];
+
+/// Reads the schema and the row counts of an Arrow IPC object from its
+/// metadata only, without fetching the record batches: the footer of the
+/// file format, or the message headers of the streaming format.
+struct IpcMetadata<'a> {
+    store: &'a Arc<dyn ObjectStore>,
+    object: &'a ObjectMeta,
+}
+
+impl<'a> IpcMetadata<'a> {
+    fn new(store: &'a Arc<dyn ObjectStore>, object: &'a ObjectMeta) -> Self {
+        Self { store, object }
+    }
+
+    fn error(&self, message: impl std::fmt::Display) -> DataFusionError {
+        DataFusionError::Execution(format!(
+            "Invalid Arrow IPC file {}: {}",
+            self.object.location, message
+        ))
+    }
+
+    async fn read(&self, range: Range<usize>) -> Result<Bytes> {
+        if range.end > self.object.size {
+            return Err(self.error("unexpected end of file"));
+        }
+        Ok(self.store.get_range(&self.object.location, range).await?)
+    }
+
+    /// Whether the object uses the file format, which starts with `ARROW1`
+    async fn is_file(&self) -> Result<bool> {
+        // the file format ends with the magic bytes too, after the footer
+        let min_size = 2 * ARROW_MAGIC.len() + 4;
+        if self.object.size < min_size {
+            return Ok(false);
+        }
+        Ok(self.read(0..ARROW_MAGIC.len()).await?.as_ref() == ARROW_MAGIC)
+    }
+
+    /// The bytes of the footer of the file format, which is followed by its
+    /// length and the magic bytes
+    async fn footer(&self) -> Result<Bytes> {
+        let size = self.object.size;
+        let trailer_start = size - ARROW_MAGIC.len() - 4;
+        let trailer = self.read(trailer_start..size).await?;
+        if trailer[4..] != ARROW_MAGIC {
+            return Err(self.error("missing trailing magic bytes"));
+        }
+        let footer_len =
+            i32::from_le_bytes([trailer[0], trailer[1], trailer[2], trailer[3]]);
+        let footer_len = usize::try_from(footer_len)
+            .ok()
+            .filter(|len| *len <= trailer_start)
+            .ok_or_else(|| self.error("invalid footer length"))?;
+        self.read(trailer_start - footer_len..trailer_start).await
+    }
+
+    /// The metadata of the message at `offset` of the object, and the offset
+    /// of its body, `None` at the end of a stream
+    async fn message(&self, offset: usize) -> Result<Option<(Bytes, usize)>> {
+        if offset + 4 > self.object.size {
+            return Ok(None);
+        }
+        let prefix = self.read(offset..offset + 4).await?;
+        let (prefix, prefix_len) = if prefix.as_ref() == CONTINUATION_MARKER {
+            (self.read(offset + 4..offset + 8).await?, 8)
+        } else {
+            // the legacy format without continuation marker
+            (prefix, 4)
+        };
+        let len = i32::from_le_bytes([prefix[0], prefix[1], prefix[2], prefix[3]]);
+        let len =
+            usize::try_from(len).map_err(|_| self.error("invalid message length"))?;
+        if len == 0 {
+            return Ok(None);
+        }
+        let start = offset + prefix_len;
+        Ok(Some((self.read(start..start + len).await?, start + len)))
+    }
+
+    async fn schema(&self) -> Result<Schema> {
+        if self.is_file().await? {
+            let footer = self.footer().await?;
+            let footer = ipc::root_as_footer(&footer).map_err(|e| self.error(e))?;
+            let schema = footer
+                .schema()
+                .ok_or_else(|| self.error("missing schema"))?;
+            return Ok(ipc::convert::fb_to_schema(schema));
+        }
+        // a stream starts with its schema
+        let (metadata, _) = self
+            .message(0)
+            .await?
+            .ok_or_else(|| self.error("missing schema"))?;
+        let message = ipc::root_as_message(&metadata).map_err(|e| self.error(e))?;
+        let schema = message
+            .header_as_schema()
+            .ok_or_else(|| self.error("the first message is not a schema"))?;
+        Ok(ipc::convert::fb_to_schema(schema))
+    }
+
+    async fn num_rows(&self) -> Result<usize> {
+        let mut num_rows = 0;
+        if self.is_file().await? {
+            let footer = self.footer().await?;
+            let footer = ipc::root_as_footer(&footer).map_err(|e| self.error(e))?;
+            // the blocks locate the messages of the record batches
+            let offsets: Vec<usize> = footer
+                .recordBatches()
+                .map(|blocks| {
+                    blocks.iter().map(|block| block.offset() as usize).collect()
+                })
+                .unwrap_or_default();
+            for offset in offsets {
+                num_rows += self.record_batch_rows(offset).await?.unwrap_or(0);
+            }
+            return Ok(num_rows);
+        }
+        // the headers of a stream are read one after the other, skipping
+        // the bodies of the messages
+        let mut offset = 0;
+        while let Some((metadata, body_start)) = self.message(offset).await? {
+            let message = ipc::root_as_message(&metadata).map_err(|e| self.error(e))?;
+            if let Some(batch) = message.header_as_record_batch() {
+                num_rows += batch.length() as usize;
+            }
+            offset = body_start + message.bodyLength() as usize;
+        }
+        Ok(num_rows)
+    }
+
+    /// The number of rows of the message at `offset`, if a record batch
+    async fn record_batch_rows(&self, offset: usize) -> Result<Option<usize>> {
+        let (metadata, _) = match self.message(offset).await? {
+            Some(message) => message,
+            None => return Ok(None),
+        };
+        let message = ipc::root_as_message(&metadata).map_err(|e| self.error(e))?;
+        Ok(message
+            .header_as_record_batch()
+            .map(|batch| batch.length() as usize))
+    }
+}
+
+/// A reader of record batches from either the Arrow IPC file format or
+/// the Arrow IPC streaming format, detected from the leading magic bytes
+pub(crate) enum IpcBatchReader<R: Read + Seek> {
+    /// The random access file format, which starts with `ARROW1`
+    File(FileReader<R>),
+    /// The streaming format
+    Stream(StreamReader<R>),
+}
+
+impl<R: Read + Seek> IpcBatchReader<R> {
+    /// Create a reader over `reader`, only decoding the columns at the
+    /// indices in `projection` if provided
+    pub(crate) fn try_new(mut reader: R, projection: Option<Vec<usize>>) -> Result<Self> {
+        let mut magic = [0; ARROW_MAGIC.len()];
+        let is_file = match reader.read_exact(&mut magic) {
+            Ok(()) => magic == ARROW_MAGIC,
+            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => false,
+            Err(e) => return Err(e.into()),
+        };
+        reader.seek(SeekFrom::Start(0))?;
+
+        Ok(if is_file {
+            Self::File(FileReader::try_new(reader, projection)?)
+        } else {
+            Self::Stream(StreamReader::try_new(reader, projection)?)
+        })
+    }
+
+    /// The schema of the batches produced by this reader
+    pub(crate) fn schema(&self) -> SchemaRef {
+        match self {
+            Self::File(reader) => reader.schema(),
+            Self::Stream(reader) => reader.schema(),
+        }
+    }
+}
+
+impl<R: Read + Seek> Iterator for IpcBatchReader<R> {
+    type Item = arrow::error::Result<RecordBatch>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Self::File(reader) => reader.next(),
+            Self::Stream(reader) => reader.next(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::datasource::file_format::test_util::scan_format;
+    use crate::physical_plan::collect;
+    use crate::prelude::SessionContext;
+    use arrow::array::{Int32Array, StringArray};
+    use arrow::datatypes::{DataType, Field};
+    use arrow::ipc::writer::{FileWriter, StreamWriter};
+    use object_store::local::LocalFileSystem;
+    use std::fs::File;
+    use tempfile::TempDir;
+
+    use crate::test::object_store::local_unpartitioned_file;
+
+    fn test_batch() -> RecordBatch {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("name", DataType::Utf8, true),
+        ]));
+        RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(Int32Array::from(vec![1, 2, 3])),
+                Arc::new(StringArray::from(vec![Some("a"), None, Some("c")])),
+            ],
+        )
+        .unwrap()
+    }
+
+    fn write_files(dir: &TempDir) -> (String, String) {
+        let batch = test_batch();
+
+        let file_path = dir.path().join("file.arrow");
+        let mut writer =
+            FileWriter::try_new(File::create(&file_path).unwrap(), &batch.schema())
+                .unwrap();
+        writer.write(&batch).unwrap();
+        writer.write(&batch).unwrap();
+        writer.finish().unwrap();
+
+        let stream_path = dir.path().join("stream.arrow");
+        let mut writer =
+            StreamWriter::try_new(File::create(&stream_path).unwrap(), &batch.schema())
+                .unwrap();
+        writer.write(&batch).unwrap();
+        writer.finish().unwrap();
+
+        (
+            file_path.to_str().unwrap().to_string(),
+            stream_path.to_str().unwrap().to_string(),
+        )
+    }
+
+    #[tokio::test]
+    async fn infer_schema_and_stats() -> Result<()> {
+        let dir = TempDir::new()?;
+        let (file_path, stream_path) = write_files(&dir);
+        let store = Arc::new(LocalFileSystem::new()) as _;
+        let format = IpcFormat::default();
+
+        for (path, expected_rows) in [(file_path, 6), (stream_path, 3)] {
+            let meta = local_unpartitioned_file(&path);
+            let schema = format.infer_schema(&store, &[meta.clone()]).await?;
+            assert_eq!(schema.as_ref(), test_batch().schema().as_ref());
+
+            let stats = format.infer_stats(&store, schema, &meta).await?;
+            assert_eq!(stats.num_rows, Some(expected_rows));
+            assert!(stats.is_exact);
+
+            // only ranges of the metadata are read from other stores
+            let memory = object_store::memory::InMemory::new();
+            let location = object_store::path::Path::from("data.arrow");
+            memory.put(&location, std::fs::read(&path)?.into()).await?;
+            let memory: Arc<dyn ObjectStore> = Arc::new(memory);
+            let meta = memory.head(&location).await?;
+            let schema = format.infer_schema(&memory, &[meta.clone()]).await?;
+            assert_eq!(schema.as_ref(), test_batch().schema().as_ref());
+            let stats = format.infer_stats(&memory, schema, &meta).await?;
+            assert_eq!(stats.num_rows, Some(expected_rows));
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn read_with_projection_and_limit() -> Result<()> {
+        let dir = TempDir::new()?;
+        write_files(&dir);
+        let ctx = SessionContext::new();
+
+        let exec = scan_format(
+            &IpcFormat::default(),
+            dir.path().to_str().unwrap(),
+            "file.arrow",
+            Some(vec![1]),
+            Some(4),
+        )
+        .await?;
+        assert_eq!(exec.schema().fields().len(), 1);
+        assert_eq!(exec.schema().field(0).name(), "name");
+
+        let batches = collect(exec, ctx.task_ctx()).await?;
+        let rows: usize = batches.iter().map(|b| b.num_rows()).sum();
+        assert_eq!(rows, 4);
+
+        Ok(())
+    }
+}
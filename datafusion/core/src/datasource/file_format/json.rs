@@ -18,64 +18,226 @@
 //! Line delimited JSON format abstractions
 
 use std::any::Any;
+use std::collections::HashMap;
 
-use std::io::BufReader;
+use std::io::{BufRead, BufReader};
 use std::sync::Arc;
 
+use arrow::compute::SortOptions;
 use arrow::datatypes::Schema;
 use arrow::datatypes::SchemaRef;
+use arrow::error::Result as ArrowResult;
 use arrow::json::reader::infer_json_schema_from_iterator;
-use arrow::json::reader::ValueIter;
 use async_trait::async_trait;
 use bytes::Buf;
+use serde_json::Value;
+use tracing::Instrument;
 
 use object_store::{GetResult, ObjectMeta, ObjectStore};
 
 use super::FileFormat;
 use super::FileScanConfig;
 use crate::datasource::file_format::file_type::FileCompressionType;
+use crate::datasource::file_format::nested_stats::NestedStatsAccumulator;
 use crate::datasource::file_format::DEFAULT_SCHEMA_INFER_MAX_RECORD;
-use crate::error::Result;
+use crate::error::{DataFusionError, Result};
 use crate::logical_expr::Expr;
-use crate::physical_plan::file_format::NdJsonExec;
+use crate::physical_plan::expressions::{Column, PhysicalSortExpr};
+use crate::physical_plan::file_format::{
+    DurationDetector, FixedSizeListDetector, JsonBatchReader, JsonScanOptions,
+    JsonValueIter, LargeOffsetDetector, NdJsonExec, NullFieldDetector,
+    TypeConflictDetector,
+};
 use crate::physical_plan::ExecutionPlan;
-use crate::physical_plan::Statistics;
+use crate::physical_plan::{ColumnStatistics, Statistics};
 
 /// The default file extension of json files
 pub const DEFAULT_JSON_EXTENSION: &str = ".json";
+/// The number of records decoded at once to collect nested statistics
+const NESTED_STATS_BATCH_SIZE: usize = 8192;
 /// New line delimited JSON `FileFormat` implementation.
 #[derive(Debug)]
 pub struct JsonFormat {
     schema_infer_max_rec: Option<usize>,
-    file_compression_type: FileCompressionType,
+    schema_infer_null_max_rec: Option<usize>,
+    file_compression_type: Option<FileCompressionType>,
+    scan_options: Arc<JsonScanOptions>,
+    schema: Option<SchemaRef>,
+    nested_stats_paths: Vec<String>,
+    file_sort_order: Vec<Expr>,
 }
 
 impl Default for JsonFormat {
     fn default() -> Self {
         Self {
             schema_infer_max_rec: Some(DEFAULT_SCHEMA_INFER_MAX_RECORD),
-            file_compression_type: FileCompressionType::UNCOMPRESSED,
+            schema_infer_null_max_rec: Some(DEFAULT_SCHEMA_INFER_MAX_RECORD),
+            file_compression_type: None,
+            scan_options: Arc::new(JsonScanOptions::default()),
+            schema: None,
+            nested_stats_paths: vec![],
+            file_sort_order: vec![],
         }
     }
 }
 
 impl JsonFormat {
-    /// Set a limit in terms of records to scan to infer the schema
+    /// Set a limit in terms of records to scan to infer the schema, past
+    /// which the records are still scanned while a field has only been
+    /// observed with null values, see [`Self::with_schema_infer_null_max_rec`]
     /// - defaults to `DEFAULT_SCHEMA_INFER_MAX_RECORD`
     pub fn with_schema_infer_max_rec(mut self, max_rec: Option<usize>) -> Self {
         self.schema_infer_max_rec = max_rec;
         self
     }
 
-    /// Set a `FileCompressionType` of JSON
-    /// - defaults to `FileCompressionType::UNCOMPRESSED`
+    /// Set a limit in terms of records to scan past the limit of
+    /// [`Self::with_schema_infer_max_rec`] while a field has only been
+    /// observed with null values. The fields still only null when it is
+    /// reached are inferred as `Utf8`
+    /// - defaults to `DEFAULT_SCHEMA_INFER_MAX_RECORD`
+    pub fn with_schema_infer_null_max_rec(mut self, max_rec: Option<usize>) -> Self {
+        self.schema_infer_null_max_rec = max_rec;
+        self
+    }
+
+    /// Set a `FileCompressionType` of JSON, used for every file
+    /// - defaults to detecting the compression of each file from its
+    ///   extension, see [`FileCompressionType::from_path`]
     pub fn with_file_compression_type(
         mut self,
         file_compression_type: FileCompressionType,
     ) -> Self {
-        self.file_compression_type = file_compression_type;
+        self.file_compression_type = Some(file_compression_type);
+        self
+    }
+
+    /// Set the [`JsonScanOptions`] used both to infer the schema and to
+    /// read the files
+    /// - defaults to `JsonScanOptions::default()`
+    pub fn with_scan_options(mut self, scan_options: JsonScanOptions) -> Self {
+        self.scan_options = Arc::new(scan_options);
+        self
+    }
+
+    /// Rename the top level keys of the records, from their name in the
+    /// files to the name of the column they are read into, such as
+    /// `"created-at"` to `created_at`. This sets
+    /// [`JsonScanOptions::field_renames`], so must follow any call to
+    /// [`Self::with_scan_options`]
+    /// - defaults to no renames
+    pub fn with_field_renames(mut self, field_renames: HashMap<String, String>) -> Self {
+        Arc::make_mut(&mut self.scan_options).field_renames = field_renames;
+        self
+    }
+
+    /// Use `schema` as the schema of the files instead of inferring it,
+    /// for instance one loaded with [`read_schema_file`]
+    /// - defaults to `None`, inferring the schema from the files
+    ///
+    /// [`read_schema_file`]: super::schema_file::read_schema_file
+    pub fn with_schema(mut self, schema: SchemaRef) -> Self {
+        self.schema = Some(schema);
+        self
+    }
+
+    /// Collect the minimum, maximum and null count of the nested leaf fields
+    /// at the dot separated `paths`, such as `payload.ts`, when collecting
+    /// the statistics of the files, so that filters on them such as
+    /// `payload['ts'] > 10` skip the files they rule out
+    /// - defaults to no paths
+    pub fn with_nested_statistics(mut self, paths: Vec<String>) -> Self {
+        self.nested_stats_paths = paths;
         self
     }
+
+    /// Declare that the rows of every file are sorted by `file_sort_order`,
+    /// sort expressions over columns such as `col("ts").sort(true, false)`,
+    /// the files not being sorted relative to each other. Scans merge the
+    /// files of each of their partitions in this order, so that sorting
+    /// their rows by a prefix of it only merges the partitions. Only the
+    /// prefix of the order over projected columns is kept by a scan.
+    /// - defaults to no order
+    pub fn with_file_sort_order(mut self, file_sort_order: Vec<Expr>) -> Self {
+        self.file_sort_order = file_sort_order;
+        self
+    }
+
+    /// The order of the rows of every file, see [`Self::with_file_sort_order`]
+    pub fn file_sort_order(&self) -> &[Expr] {
+        &self.file_sort_order
+    }
+
+    /// The order of the rows of every file over `schema`, the projected
+    /// schema of a scan, see [`Self::with_file_sort_order`]
+    fn output_ordering(&self, schema: &Schema) -> Result<Vec<PhysicalSortExpr>> {
+        let mut ordering = vec![];
+        for expr in &self.file_sort_order {
+            let (name, options) = match expr {
+                Expr::Sort {
+                    expr,
+                    asc,
+                    nulls_first,
+                } => match expr.as_ref() {
+                    Expr::Column(column) => (
+                        &column.name,
+                        SortOptions {
+                            descending: !asc,
+                            nulls_first: *nulls_first,
+                        },
+                    ),
+                    _ => {
+                        return Err(DataFusionError::Plan(format!(
+                        "The sort order of JSON files can only be over columns, got {:?}",
+                        expr
+                    )))
+                    }
+                },
+                _ => {
+                    return Err(DataFusionError::Plan(format!(
+                    "The sort order of JSON files requires sort expressions, got {:?}",
+                    expr
+                )))
+                }
+            };
+            match schema.index_of(name) {
+                Ok(index) => ordering.push(PhysicalSortExpr {
+                    expr: Arc::new(Column::new(name, index)),
+                    options,
+                }),
+                // the rest of the order is meaningless without this column
+                Err(_) => break,
+            }
+        }
+        Ok(ordering)
+    }
+
+    /// Returns `schema` if it has all the
+    /// [`JsonScanOptions::required_columns`], or an error listing the
+    /// missing ones
+    fn check_required_columns(&self, schema: SchemaRef) -> Result<SchemaRef> {
+        let missing: Vec<String> = self
+            .scan_options
+            .required_columns
+            .iter()
+            .filter(|name| schema.field_with_name(name).is_err())
+            .map(|name| format!("'{}'", name))
+            .collect();
+        if missing.is_empty() {
+            return Ok(schema);
+        }
+
+        let columns: Vec<String> = schema
+            .fields()
+            .iter()
+            .map(|field| format!("'{}'", field.name()))
+            .collect();
+        Err(DataFusionError::Plan(format!(
+            "The JSON files are missing the required columns {}, their columns are {}",
+            missing.join(", "),
+            columns.join(", ")
+        )))
+    }
 }
 
 #[async_trait]
@@ -89,42 +251,136 @@ impl FileFormat for JsonFormat {
         store: &Arc<dyn ObjectStore>,
         objects: &[ObjectMeta],
     ) -> Result<SchemaRef> {
+        self.scan_options.check_comment_prefix()?;
+        if let Some(schema) = &self.schema {
+            let schema = self.scan_options.apply_to_schema(schema.clone())?;
+            return self.check_required_columns(schema);
+        }
+
         let mut schemas = Vec::new();
         let mut records_to_read = self.schema_infer_max_rec.unwrap_or(usize::MAX);
-        let file_compression_type = self.file_compression_type.to_owned();
+        let mut null_records_to_read =
+            self.schema_infer_null_max_rec.unwrap_or(usize::MAX);
+        let mut detector = self
+            .scan_options
+            .infer_durations
+            .then(DurationDetector::default);
+        let mut large_offsets = self
+            .scan_options
+            .large_offsets_threshold
+            .map(LargeOffsetDetector::new);
+        let mut fixed_size_lists = self
+            .scan_options
+            .infer_fixed_size_lists
+            .then(FixedSizeListDetector::default);
+        let mut conflicts = TypeConflictDetector::default();
+        // a field only observed as null so far, such as an object whose
+        // first values are null, would be left out of the schema
+        let mut nulls = NullFieldDetector::default();
         for object in objects {
-            let mut take_while = || {
-                let should_take = records_to_read > 0;
+            // an empty file has no records to infer the schema from
+            if object.size == 0 {
+                continue;
+            }
+            let file_compression_type = match &self.file_compression_type {
+                Some(file_compression_type) => file_compression_type.clone(),
+                None => FileCompressionType::from_path(object.location.as_ref()),
+            };
+            let mut take_while = |value: &ArrowResult<Value>| {
+                // keep reading past the limit until the type of every field
+                // is known, or the null records limit is reached
+                let should_take = records_to_read > 0
+                    || (!nulls.is_resolved() && null_records_to_read > 0);
                 if should_take {
-                    records_to_read -= 1;
+                    if records_to_read > 0 {
+                        records_to_read -= 1;
+                    } else {
+                        null_records_to_read -= 1;
+                    }
+                    if let Ok(value) = value {
+                        nulls.observe(value);
+                    }
                 }
                 should_take
             };
-
-            let schema = match store.get(&object.location).await? {
-                GetResult::File(file, _) => {
-                    let decoder = file_compression_type.convert_read(file);
-                    let mut reader = BufReader::new(decoder);
-                    let iter = ValueIter::new(&mut reader, None);
-                    infer_json_schema_from_iterator(iter.take_while(|_| take_while()))?
-                }
-                r @ GetResult::Stream(_) => {
-                    let data = r.bytes().await?;
-                    let decoder = file_compression_type.convert_read(data.reader());
-                    let mut reader = BufReader::new(decoder);
-                    let iter = ValueIter::new(&mut reader, None);
-                    infer_json_schema_from_iterator(iter.take_while(|_| take_while()))?
+            let mut observe = |value: &ArrowResult<Value>| {
+                if let Ok(value) = value {
+                    conflicts.observe(value);
+                    if let Some(detector) = detector.as_mut() {
+                        detector.observe(value)
+                    }
+                    if let Some(large_offsets) = large_offsets.as_mut() {
+                        large_offsets.observe(value)
+                    }
+                    if let Some(fixed_size_lists) = fixed_size_lists.as_mut() {
+                        fixed_size_lists.observe(value)
+                    }
                 }
             };
 
+            let span = tracing::debug_span!("json_infer_schema", path = %object.location);
+            let schema = async {
+                let schema = match store.get(&object.location).await? {
+                    GetResult::File(file, _) => {
+                        let decoder = file_compression_type.convert_read(file)?;
+                        let reader = BufReader::new(
+                            self.scan_options.encoding.convert_read(decoder),
+                        );
+                        let iter =
+                            JsonValueIter::new(reader, self.scan_options.clone(), None);
+                        infer_json_schema_from_iterator(
+                            iter.take_while(&mut take_while).inspect(&mut observe),
+                        )?
+                    }
+                    r @ GetResult::Stream(_) => {
+                        let data = r.bytes().await?;
+                        let decoder =
+                            file_compression_type.convert_read(data.reader())?;
+                        let reader = BufReader::new(
+                            self.scan_options.encoding.convert_read(decoder),
+                        );
+                        let iter =
+                            JsonValueIter::new(reader, self.scan_options.clone(), None);
+                        infer_json_schema_from_iterator(
+                            iter.take_while(&mut take_while).inspect(&mut observe),
+                        )?
+                    }
+                };
+                Ok::<_, DataFusionError>(schema)
+            }
+            .instrument(span)
+            .await?;
+
             schemas.push(schema);
-            if records_to_read == 0 {
+            if records_to_read == 0 && (nulls.is_resolved() || null_records_to_read == 0)
+            {
                 break;
             }
         }
 
         let schema = Schema::try_merge(schemas)?;
-        Ok(Arc::new(schema))
+        // the fields only null in all the records are left out, unlike the
+        // ones whose type the null records limit cut the inference of
+        let schema = match null_records_to_read {
+            0 => nulls.apply(schema),
+            _ => schema,
+        };
+        conflicts.report(&schema, &self.scan_options);
+        let schema = match detector {
+            Some(detector) => detector.apply(schema),
+            None => schema,
+        };
+        // lists of a fixed size have no offsets to widen
+        let schema = match fixed_size_lists {
+            Some(fixed_size_lists) => fixed_size_lists.apply(schema),
+            None => schema,
+        };
+        let schema = match large_offsets {
+            Some(large_offsets) => large_offsets.apply(schema),
+            None => schema,
+        };
+        let schema = self.scan_options.apply_to_schema(Arc::new(schema))?;
+        self.check_required_columns(schema)
     }
 
     async fn infer_stats(
@@ -136,26 +392,99 @@ impl FileFormat for JsonFormat {
         Ok(Statistics::default())
     }
 
+    fn nested_stats_paths(&self) -> &[String] {
+        &self.nested_stats_paths
+    }
+
+    async fn infer_nested_stats(
+        &self,
+        store: &Arc<dyn ObjectStore>,
+        table_schema: SchemaRef,
+        object: &ObjectMeta,
+    ) -> Result<Vec<ColumnStatistics>> {
+        let mut accumulator =
+            NestedStatsAccumulator::new(&table_schema, &self.nested_stats_paths);
+        let projection = accumulator.projection();
+        // an empty file has no records, and may lack the header its
+        // decompression would expect
+        if projection.is_empty() || object.size == 0 {
+            return accumulator.finish();
+        }
+
+        let file_compression_type = match &self.file_compression_type {
+            Some(file_compression_type) => file_compression_type.clone(),
+            None => FileCompressionType::from_path(object.location.as_ref()),
+        };
+        let decoder = match store.get(&object.location).await? {
+            GetResult::File(file, _) => file_compression_type.convert_read(file)?,
+            r @ GetResult::Stream(_) => {
+                let data = r.bytes().await?;
+                file_compression_type.convert_read(data.reader())?
+            }
+        };
+        let reader: Box<dyn BufRead + Send> = Box::new(BufReader::new(
+            self.scan_options.encoding.convert_read(decoder),
+        ));
+        let batches = JsonBatchReader::new(
+            reader,
+            table_schema,
+            NESTED_STATS_BATCH_SIZE,
+            Some(projection),
+            self.scan_options.clone(),
+        );
+        for batch in batches {
+            accumulator.update(&batch?)?;
+        }
+        accumulator.finish()
+    }
+
     async fn create_physical_plan(
         &self,
         conf: FileScanConfig,
         _filters: &[Expr],
     ) -> Result<Arc<dyn ExecutionPlan>> {
-        let exec = NdJsonExec::new(conf, self.file_compression_type.to_owned());
+        let scan_options = self
+            .scan_options
+            .as_ref()
+            .clone()
+            .with_detect_compression(self.file_compression_type.is_none());
+        let (projected_schema, _) = conf.project();
+        let ordering = self.output_ordering(&projected_schema)?;
+        let exec = NdJsonExec::new(
+            conf,
+            self.file_compression_type
+                .clone()
+                .unwrap_or(FileCompressionType::UNCOMPRESSED),
+        )
+        .with_scan_options(scan_options);
+        let exec = if ordering.is_empty() {
+            exec
+        } else {
+            exec.with_output_ordering(ordering)
+        };
         Ok(Arc::new(exec))
     }
+
+    fn detects_compression(&self) -> bool {
+        self.file_compression_type.is_none()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::super::test_util::scan_format;
+    use arrow::array::Array;
     use datafusion_common::cast::as_int64_array;
     use futures::StreamExt;
     use object_store::local::LocalFileSystem;
 
     use super::*;
-    use crate::physical_plan::collect;
-    use crate::prelude::{SessionConfig, SessionContext};
+    use crate::arrow::datatypes::{DataType, Field, TimeUnit};
+    use crate::datasource::listing::ListingOptions;
+    use crate::physical_plan::file_format::JsonDiagnostics;
+    use crate::physical_plan::{collect, displayable};
+    use crate::prelude::{NdJsonReadOptions, SessionConfig, SessionContext};
+    use crate::scalar::ScalarValue;
     use crate::test::object_store::local_unpartitioned_file;
 
     #[tokio::test]
@@ -242,6 +571,33 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn infer_schema_required_columns() -> Result<()> {
+        let mut ctx = SessionContext::new();
+        let filename = "tests/jsons/2.json";
+        let options = |required_columns: &[&str]| NdJsonReadOptions {
+            scan_options: JsonScanOptions::new().with_required_columns(
+                required_columns.iter().map(|c| c.to_string()).collect(),
+            ),
+            ..Default::default()
+        };
+
+        // the table is not created, without scanning the file
+        let err = ctx
+            .read_json(filename, options(&["a", "e", "f"]))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Error during planning: The JSON files are missing the required columns \
+            'e', 'f', their columns are 'a', 'b', 'c', 'd'"
+        );
+
+        let df = ctx.read_json(filename, options(&["d", "a"])).await?;
+        assert_eq!(df.schema().fields().len(), 4);
+        Ok(())
+    }
+
     async fn get_exec(
         projection: Option<Vec<usize>>,
         limit: Option<usize>,
@@ -251,6 +607,39 @@ mod tests {
         scan_format(&format, ".", filename, projection, limit).await
     }
 
+    #[tokio::test]
+    async fn infer_schema_type_conflicts() -> Result<()> {
+        let store = Arc::new(LocalFileSystem::new()) as _;
+        let filename = "tests/jsons/mixed_types.json";
+        let diagnostics = Arc::new(JsonDiagnostics::default());
+        let format = JsonFormat::default().with_scan_options(
+            JsonScanOptions::new().with_diagnostics(diagnostics.clone()),
+        );
+
+        let file_schema = format
+            .infer_schema(&store, &[local_unpartitioned_file(filename)])
+            .await?;
+
+        let fields = file_schema
+            .fields()
+            .iter()
+            .map(|f| format!("{}: {:?}", f.name(), f.data_type()))
+            .take(3)
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["a: Utf8", "b: Utf8", "c: Float64"], fields);
+        assert!(matches!(
+            file_schema.field_with_name("d")?.data_type(),
+            DataType::List(_)
+        ));
+        // only `a` fell back to Utf8, `c` and `d` were coerced
+        assert_eq!(
+            diagnostics.messages(),
+            vec!["JSON schema inference read column 'a' as Utf8 because its values have conflicting types: Boolean, Int64, Utf8"]
+        );
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn infer_schema_with_limit() {
         let store = Arc::new(LocalFileSystem::new()) as _;
@@ -269,4 +658,454 @@ mod tests {
             .collect::<Vec<_>>();
         assert_eq!(vec!["a: Int64", "b: Float64", "c: Boolean"], fields);
     }
+
+    #[tokio::test]
+    async fn infer_schema_null_first_struct() -> Result<()> {
+        let session_ctx = SessionContext::new();
+        let task_ctx = session_ctx.task_ctx();
+        let filename = "tests/jsons/null_first_struct.json";
+
+        // `meta` is null in the records within the limit, the scan goes on
+        // until its first object
+        let format = JsonFormat::default().with_schema_infer_max_rec(Some(1));
+        let exec = scan_format(&format, ".", filename, None, None).await?;
+        let meta = exec.schema().field(1).clone();
+        assert_eq!(meta.name(), "meta");
+        assert!(meta.is_nullable());
+        let fields = match meta.data_type() {
+            DataType::Struct(fields) => fields
+                .iter()
+                .map(|f| format!("{}: {:?}", f.name(), f.data_type()))
+                .collect::<Vec<_>>(),
+            other => panic!("expected a struct, got {:?}", other),
+        };
+        assert_eq!(fields, vec!["name: Utf8", "score: Float64"]);
+
+        let batches = collect(exec, task_ctx).await?;
+        // the null objects are null structs
+        let metas = batches[0]
+            .column(1)
+            .as_any()
+            .downcast_ref::<arrow::array::StructArray>()
+            .unwrap();
+        assert_eq!(metas.null_count(), 3);
+        assert!(metas.is_valid(2));
+        let names = metas
+            .column_by_name("name")
+            .unwrap()
+            .as_any()
+            .downcast_ref::<arrow::array::StringArray>()
+            .unwrap();
+        assert_eq!(names.value(2), "x");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn infer_schema_null_first_struct_with_limit() -> Result<()> {
+        let store = Arc::new(LocalFileSystem::new()) as _;
+        let filename = "tests/jsons/null_first_struct.json";
+
+        // `meta` is still only null when the records past the limit run out
+        let format = JsonFormat::default()
+            .with_schema_infer_max_rec(Some(1))
+            .with_schema_infer_null_max_rec(Some(1));
+        let file_schema = format
+            .infer_schema(&store, &[local_unpartitioned_file(filename)])
+            .await?;
+
+        let fields = file_schema
+            .fields()
+            .iter()
+            .map(|f| format!("{}: {:?}", f.name(), f.data_type()))
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["id: Int64", "meta: Utf8"], fields);
+        assert!(file_schema.field_with_name("meta")?.is_nullable());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn infer_schema_large_offsets() -> Result<()> {
+        let session_ctx = SessionContext::new();
+        let task_ctx = session_ctx.task_ctx();
+        let filename = "tests/jsons/2.json";
+
+        // stands in for i32::MAX, the strings of `d` total 24 bytes
+        let format = JsonFormat::default()
+            .with_scan_options(JsonScanOptions::new().with_large_offsets_threshold(20));
+        let exec = scan_format(&format, ".", filename, Some(vec![3]), None).await?;
+        assert_eq!(exec.schema().field(0).data_type(), &DataType::LargeUtf8);
+
+        let batches = collect(exec, task_ctx).await?;
+        let column = batches[0].column(0);
+        assert_eq!(column.data_type(), &DataType::LargeUtf8);
+        let values = column
+            .as_any()
+            .downcast_ref::<arrow::array::LargeStringArray>()
+            .unwrap();
+        assert_eq!(values.value(2), "text");
+
+        // the strings of `d` do not exceed this threshold
+        let format = JsonFormat::default()
+            .with_scan_options(JsonScanOptions::new().with_large_offsets_threshold(24));
+        let exec = scan_format(&format, ".", filename, Some(vec![3]), None).await?;
+        assert_eq!(exec.schema().field(0).data_type(), &DataType::Utf8);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn infer_schema_fixed_size_lists() -> Result<()> {
+        let session_ctx = SessionContext::new();
+        let task_ctx = session_ctx.task_ctx();
+        let filename = "tests/jsons/fixed_size_lists.json";
+
+        let format = JsonFormat::default()
+            .with_scan_options(JsonScanOptions::new().with_infer_fixed_size_lists(true));
+        let exec = scan_format(&format, ".", filename, None, None).await?;
+        let item = Box::new(Field::new("item", DataType::Float64, true));
+        assert_eq!(
+            exec.schema().field(1).data_type(),
+            &DataType::FixedSizeList(item, 3)
+        );
+        // the lists of `tags` have different lengths
+        assert!(matches!(
+            exec.schema().field(2).data_type(),
+            DataType::List(_)
+        ));
+
+        let batches = collect(exec, task_ctx).await?;
+        let vectors = batches[0]
+            .column(1)
+            .as_any()
+            .downcast_ref::<arrow::array::FixedSizeListArray>()
+            .unwrap();
+        assert_eq!(vectors.len(), 4);
+        assert!(vectors.is_null(2));
+        let values = vectors.value(3);
+        let values = values
+            .as_any()
+            .downcast_ref::<arrow::array::Float64Array>()
+            .unwrap();
+        assert_eq!(values.values(), &[4.0, 4.5, 5.0]);
+
+        // without the option the vectors are read as lists
+        let exec = scan_format(&JsonFormat::default(), ".", filename, None, None).await?;
+        assert!(matches!(
+            exec.schema().field(1).data_type(),
+            DataType::List(_)
+        ));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn read_epoch_timestamps() -> Result<()> {
+        let session_ctx = SessionContext::new();
+        let task_ctx = session_ctx.task_ctx();
+        let filename = "tests/jsons/epoch_timestamps.json";
+
+        let scan_options = JsonScanOptions::new()
+            .with_epoch_timestamp_column("created_ms", TimeUnit::Millisecond);
+        let format = JsonFormat::default().with_scan_options(scan_options);
+        let exec = scan_format(&format, ".", filename, None, None).await?;
+        assert_eq!(exec.schema().field(0).data_type(), &DataType::Int64);
+        assert_eq!(
+            exec.schema().field(1).data_type(),
+            &DataType::Timestamp(TimeUnit::Millisecond, None)
+        );
+
+        let batches = collect(exec, task_ctx).await?;
+        let timestamps = batches[0]
+            .column(1)
+            .as_any()
+            .downcast_ref::<arrow::array::TimestampMillisecondArray>()
+            .unwrap();
+        assert_eq!(timestamps.len(), 4);
+        assert!(timestamps.is_null(2));
+        assert_eq!(timestamps.value(0), 1_609_459_200_000);
+        assert_eq!(timestamps.value(1), 1_640_995_200_123);
+        assert_eq!(timestamps.value(3), -1500);
+        assert_eq!(
+            timestamps.value_as_datetime(1).unwrap().to_string(),
+            "2022-01-01 00:00:00.123"
+        );
+
+        // without the option the timestamps are read as integers
+        let exec = scan_format(&JsonFormat::default(), ".", filename, None, None).await?;
+        assert_eq!(exec.schema().field(1).data_type(), &DataType::Int64);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn read_nonfinite_floats() -> Result<()> {
+        let session_ctx = SessionContext::new();
+        let task_ctx = session_ctx.task_ctx();
+        let filename = "tests/jsons/nonfinite_floats.json";
+
+        let err = scan_format(&JsonFormat::default(), ".", filename, None, None)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Not valid JSON"), "{}", err);
+
+        let format = JsonFormat::default()
+            .with_scan_options(JsonScanOptions::new().with_allow_nonfinite_floats(true));
+        let exec = scan_format(&format, ".", filename, None, None).await?;
+        let fields = exec
+            .schema()
+            .fields()
+            .iter()
+            .map(|f| format!("{}: {:?}", f.name(), f.data_type()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                "a: Float64",
+                "b: Utf8",
+                "c: List(Field { name: \"item\", data_type: Float64, nullable: true, dict_id: 0, dict_is_ordered: false, metadata: None })"
+            ],
+            fields
+        );
+
+        let batches = collect(exec, task_ctx).await?;
+        let batch = &batches[0];
+        assert_eq!(batch.num_rows(), 6);
+
+        let a = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<arrow::array::Float64Array>()
+            .unwrap();
+        assert_eq!(a.value(0), 1.5);
+        assert!(a.value(1).is_nan());
+        assert_eq!(a.value(2), f64::INFINITY);
+        assert_eq!(a.value(3), f64::NEG_INFINITY);
+        assert!(a.is_null(4));
+        assert_eq!(a.value(5), 2.0);
+
+        // strings are not parsed
+        let b = batch
+            .column(1)
+            .as_any()
+            .downcast_ref::<arrow::array::StringArray>()
+            .unwrap();
+        assert_eq!(b.value(1), "NaN");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn read_nested_nonfinite_floats() -> Result<()> {
+        let session_ctx = SessionContext::new();
+        let tmp_dir = tempfile::TempDir::new()?;
+        std::fs::write(
+            tmp_dir.path().join("a.json"),
+            "{\"a\": NaN, \"c\": [1.0]}\n{\"a\": 1.0, \"c\": [NaN]}\n",
+        )?;
+        let path = format!("{}/", tmp_dir.path().to_str().unwrap());
+
+        let options = NdJsonReadOptions::default()
+            .scan_options(JsonScanOptions::new().with_allow_nonfinite_floats(true));
+        let err = session_ctx
+            .register_json("t", &path, options)
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("NaN nested in an object or list"),
+            "{}",
+            err
+        );
+
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Float64, true),
+            Field::new(
+                "c",
+                DataType::List(Box::new(Field::new("item", DataType::Float64, true))),
+                true,
+            ),
+        ]));
+        let options = NdJsonReadOptions::default()
+            .schema(schema)
+            .scan_options(JsonScanOptions::new().with_allow_nonfinite_floats(true));
+        session_ctx.register_json("t", &path, options).await?;
+        let err = session_ctx
+            .sql("SELECT * FROM t")
+            .await?
+            .collect()
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("NaN nested in an object or list"),
+            "{}",
+            err
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn read_renamed_fields() -> Result<()> {
+        let tmp_dir = tempfile::TempDir::new()?;
+        std::fs::write(
+            tmp_dir.path().join("a.json"),
+            "{\"id\": 1, \"created-at\": \"2022-01-01\"}\n\
+             {\"id\": 2, \"created-at\": \"2022-02-01\"}\n",
+        )?;
+        let path = format!("{}/", tmp_dir.path().to_str().unwrap());
+
+        let format = JsonFormat::default().with_field_renames(HashMap::from([(
+            "created-at".to_string(),
+            "created_at".to_string(),
+        )]));
+        let ctx = SessionContext::new();
+        let options = ListingOptions::new(Arc::new(format))
+            .with_file_extension(DEFAULT_JSON_EXTENSION);
+        ctx.register_listing_table("t", &path, options, None, None)
+            .await?;
+
+        let batches = ctx
+            .sql("SELECT created_at FROM t WHERE id = 2")
+            .await?
+            .collect()
+            .await?;
+        let expected = vec![
+            "+------------+",
+            "| created_at |",
+            "+------------+",
+            "| 2022-02-01 |",
+            "+------------+",
+        ];
+        crate::assert_batches_eq!(expected, &batches);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn file_sort_order_merges_partitions() -> Result<()> {
+        let tmp_dir = tempfile::TempDir::new()?;
+        for (name, ids) in [
+            ("a.json", [1, 4, 7]),
+            ("b.json", [2, 5, 8]),
+            ("c.json", [3, 6, 9]),
+        ] {
+            let lines = ids
+                .iter()
+                .map(|id| format!("{{\"id\": {}}}\n", id))
+                .collect::<String>();
+            std::fs::write(tmp_dir.path().join(name), lines)?;
+        }
+        let path = format!("{}/", tmp_dir.path().to_str().unwrap());
+
+        let ctx = SessionContext::new();
+        let sorted = JsonFormat::default()
+            .with_file_sort_order(vec![crate::prelude::col("id").sort(true, false)]);
+        for (name, format) in [("t", sorted), ("unsorted", JsonFormat::default())] {
+            let options = ListingOptions::new(Arc::new(format))
+                .with_file_extension(DEFAULT_JSON_EXTENSION)
+                .with_target_partitions(2);
+            ctx.register_listing_table(name, &path, options, None, None)
+                .await?;
+        }
+
+        // the files are only sorted individually, so their partitions are
+        // merged rather than sorted
+        let plan = ctx
+            .sql("SELECT id FROM t ORDER BY id")
+            .await?
+            .create_physical_plan()
+            .await?;
+        let displayed = format!("{}", displayable(plan.as_ref()).indent());
+        assert!(
+            displayed.contains("SortPreservingMergeExec"),
+            "{}",
+            displayed
+        );
+        assert!(!displayed.contains("SortExec"), "{}", displayed);
+        let batches = collect(plan, ctx.task_ctx()).await?;
+        let ids = batches
+            .iter()
+            .flat_map(|batch| as_int64_array(batch.column(0)).unwrap().values().to_vec())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, (1..=9).collect::<Vec<_>>());
+
+        let plan = ctx
+            .sql("SELECT id FROM unsorted ORDER BY id")
+            .await?
+            .create_physical_plan()
+            .await?;
+        let displayed = format!("{}", displayable(plan.as_ref()).indent());
+        assert!(displayed.contains("SortExec"), "{}", displayed);
+
+        Ok(())
+    }
+
+    /// Returns the number of files scanned by the JSON scans of `plan`
+    fn scanned_files(plan: &Arc<dyn ExecutionPlan>) -> usize {
+        let files = match plan.as_any().downcast_ref::<NdJsonExec>() {
+            Some(exec) => exec.base_config().file_groups.iter().flatten().count(),
+            None => 0,
+        };
+        files + plan.children().iter().map(scanned_files).sum::<usize>()
+    }
+
+    #[tokio::test]
+    async fn nested_statistics_prune_files() -> Result<()> {
+        let tmp_dir = tempfile::TempDir::new()?;
+        std::fs::write(
+            tmp_dir.path().join("a.json"),
+            "{\"id\": 1, \"payload\": {\"ts\": 1}}\n\
+             {\"id\": 2, \"payload\": {\"ts\": 10}}\n\
+             {\"id\": 3, \"payload\": null}\n",
+        )?;
+        std::fs::write(
+            tmp_dir.path().join("b.json"),
+            "{\"id\": 4, \"payload\": {\"ts\": 100}}\n\
+             {\"id\": 5, \"payload\": {\"ts\": 200}}\n",
+        )?;
+        let path = format!("{}/", tmp_dir.path().to_str().unwrap());
+
+        // the statistics of the nested leaf are collected per file
+        let format =
+            JsonFormat::default().with_nested_statistics(vec!["payload.ts".into()]);
+        let store = Arc::new(LocalFileSystem::new()) as _;
+        let file = local_unpartitioned_file(format!("{}a.json", path));
+        let schema = format.infer_schema(&store, &[file.clone()]).await?;
+        let statistics = format.infer_nested_stats(&store, schema, &file).await?;
+        assert_eq!(
+            statistics,
+            vec![ColumnStatistics {
+                null_count: Some(1),
+                max_value: Some(ScalarValue::Int64(Some(10))),
+                min_value: Some(ScalarValue::Int64(Some(1))),
+                distinct_count: None,
+            }]
+        );
+
+        let ctx = SessionContext::new();
+        for (name, format) in [("t", format), ("no_nested_stats", JsonFormat::default())]
+        {
+            let options = ListingOptions::new(Arc::new(format))
+                .with_file_extension(DEFAULT_JSON_EXTENSION)
+                .with_target_partitions(2);
+            ctx.register_listing_table(name, &path, options, None, None)
+                .await?;
+        }
+
+        // the file whose timestamps are all lower is skipped
+        let df = ctx
+            .sql("SELECT id FROM t WHERE payload['ts'] > 50 ORDER BY id")
+            .await?;
+        let plan = df.create_physical_plan().await?;
+        assert_eq!(scanned_files(&plan), 1);
+        let batches = collect(plan, ctx.task_ctx()).await?;
+        let expected = vec!["+----+", "| id |", "+----+", "| 4  |", "| 5  |", "+----+"];
+        crate::assert_batches_eq!(expected, &batches);
+
+        let df = ctx
+            .sql("SELECT id FROM no_nested_stats WHERE payload['ts'] > 50")
+            .await?;
+        assert_eq!(scanned_files(&df.create_physical_plan().await?), 2);
+
+        Ok(())
+    }
 }
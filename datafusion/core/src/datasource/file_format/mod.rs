@@ -23,8 +23,11 @@ pub const DEFAULT_SCHEMA_INFER_MAX_RECORD: usize = 1000;
 pub mod avro;
 pub mod csv;
 pub mod file_type;
+pub mod ipc;
 pub mod json;
+pub(crate) mod nested_stats;
 pub mod parquet;
+pub mod schema_file;
 
 use std::any::Any;
 use std::fmt;
@@ -34,7 +37,7 @@ use crate::arrow::datatypes::SchemaRef;
 use crate::error::Result;
 use crate::logical_expr::Expr;
 use crate::physical_plan::file_format::FileScanConfig;
-use crate::physical_plan::{ExecutionPlan, Statistics};
+use crate::physical_plan::{ColumnStatistics, ExecutionPlan, Statistics};
 
 use async_trait::async_trait;
 use object_store::{ObjectMeta, ObjectStore};
@@ -79,6 +82,39 @@ pub trait FileFormat: Send + Sync + fmt::Debug {
         conf: FileScanConfig,
         filters: &[Expr],
     ) -> Result<Arc<dyn ExecutionPlan>>;
+
+    /// Returns true if the compression of each file is detected from its
+    /// extension, in which case the files whose listing extension is
+    /// followed by the extension of a compression, such as `.json.gz`, are
+    /// listed as well. Defaults to false.
+    fn detects_compression(&self) -> bool {
+        false
+    }
+
+    /// Returns the paths of the nested leaf fields, such as `payload.ts`
+    /// for the field `ts` of the struct column `payload`, whose statistics
+    /// are inferred by [`Self::infer_nested_stats`]. Defaults to none.
+    fn nested_stats_paths(&self) -> &[String] {
+        &[]
+    }
+
+    /// Infer the statistics of the nested leaf fields of
+    /// [`Self::nested_stats_paths`] for the provided object, in the same
+    /// order. They let filters on nested fields prune the files of a scan.
+    ///
+    /// `table_schema` is the (combined) schema of the overall table, against
+    /// which the paths are resolved.
+    async fn infer_nested_stats(
+        &self,
+        _store: &Arc<dyn ObjectStore>,
+        _table_schema: SchemaRef,
+        _object: &ObjectMeta,
+    ) -> Result<Vec<ColumnStatistics>> {
+        Ok(vec![
+            ColumnStatistics::default();
+            self.nested_stats_paths().len()
+        ])
+    }
 }
 
 #[cfg(test)]
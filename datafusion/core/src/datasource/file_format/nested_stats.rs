@@ -0,0 +1,427 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Statistics of nested leaf fields, such as `payload.ts` for the field `ts`
+//! of the struct column `payload`, and the pruning of files with them
+
+use std::sync::Arc;
+
+use arrow::array::{Array, ArrayRef, BooleanArray, StructArray, UInt64Array};
+use arrow::compute::{and, filter, is_not_null};
+use arrow::datatypes::{DataType, Field, Schema};
+use arrow::record_batch::RecordBatch;
+use datafusion_common::Column;
+use datafusion_expr::expr::GetIndexedField;
+use datafusion_expr::expr_rewriter::{ExprRewritable, ExprRewriter, RewriteRecursion};
+use datafusion_optimizer::utils::conjunction;
+
+use crate::error::{DataFusionError, Result};
+use crate::logical_expr::Expr;
+use crate::physical_optimizer::pruning::{PruningPredicate, PruningStatistics};
+use crate::physical_plan::expressions::{MaxAccumulator, MinAccumulator};
+use crate::physical_plan::{Accumulator, ColumnStatistics};
+use crate::scalar::ScalarValue;
+
+/// Returns the field of `schema` at the dot separated `path`, descending
+/// into the fields of struct columns
+pub(crate) fn nested_field<'a>(schema: &'a Schema, path: &str) -> Option<&'a Field> {
+    let mut segments = path.split('.');
+    let mut field = schema.field_with_name(segments.next()?).ok()?;
+    for segment in segments {
+        field = match field.data_type() {
+            DataType::Struct(fields) => fields.iter().find(|f| f.name() == segment)?,
+            _ => return None,
+        };
+    }
+    Some(field)
+}
+
+/// Accumulates the statistics of nested leaf fields over record batches
+pub(crate) struct NestedStatsAccumulator {
+    /// The accumulators of the paths, `None` for paths not in the schema
+    paths: Vec<Option<PathAccumulator>>,
+}
+
+struct PathAccumulator {
+    segments: Vec<String>,
+    /// `None` for types without a minimum and maximum
+    min_max: Option<(MinAccumulator, MaxAccumulator)>,
+    null_count: usize,
+}
+
+impl NestedStatsAccumulator {
+    /// Create an accumulator of the statistics of the fields of `schema`
+    /// at `paths`
+    pub(crate) fn new(schema: &Schema, paths: &[String]) -> Self {
+        let paths = paths
+            .iter()
+            .map(|path| {
+                let field = nested_field(schema, path)?;
+                let min_max = match (
+                    MinAccumulator::try_new(field.data_type()),
+                    MaxAccumulator::try_new(field.data_type()),
+                ) {
+                    (Ok(min), Ok(max)) => Some((min, max)),
+                    _ => None,
+                };
+                Some(PathAccumulator {
+                    segments: path.split('.').map(str::to_owned).collect(),
+                    min_max,
+                    null_count: 0,
+                })
+            })
+            .collect();
+        Self { paths }
+    }
+
+    /// The top level columns that the batches must contain
+    pub(crate) fn projection(&self) -> Vec<String> {
+        let mut projection: Vec<String> = self
+            .paths
+            .iter()
+            .flatten()
+            .map(|path| path.segments[0].clone())
+            .collect();
+        projection.sort();
+        projection.dedup();
+        projection
+    }
+
+    /// Accumulate the values of the paths in `batch`
+    pub(crate) fn update(&mut self, batch: &RecordBatch) -> Result<()> {
+        for path in self.paths.iter_mut().flatten() {
+            let column = batch
+                .schema()
+                .index_of(&path.segments[0])
+                .map(|i| batch.column(i).clone())?;
+            let values = leaf_values(column, &path.segments[1..])?;
+            // the rows of a null struct have a null leaf, and are filtered out
+            // of its values
+            path.null_count += batch.num_rows() - values.len() + values.null_count();
+            if let Some((min, max)) = path.min_max.as_mut() {
+                min.update_batch(&[values.clone()])?;
+                max.update_batch(&[values])?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the statistics of the paths, in their order
+    pub(crate) fn finish(self) -> Result<Vec<ColumnStatistics>> {
+        self.paths
+            .into_iter()
+            .map(|path| {
+                let path = match path {
+                    Some(path) => path,
+                    None => return Ok(ColumnStatistics::default()),
+                };
+                let (min_value, max_value) = match path.min_max {
+                    Some((min, max)) => {
+                        let (min, max) = (min.evaluate()?, max.evaluate()?);
+                        // all null values have no minimum or maximum
+                        if min.is_null() || max.is_null() {
+                            (None, None)
+                        } else {
+                            (Some(min), Some(max))
+                        }
+                    }
+                    None => (None, None),
+                };
+                Ok(ColumnStatistics {
+                    null_count: Some(path.null_count),
+                    max_value,
+                    min_value,
+                    distinct_count: None,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Returns the values of the field at `segments` of the struct `column`,
+/// without those of the rows where one of its ancestors is null
+fn leaf_values(column: ArrayRef, segments: &[String]) -> Result<ArrayRef> {
+    let mut array = column;
+    let mut valid: Option<BooleanArray> = None;
+    for segment in segments {
+        let struct_array =
+            array
+                .as_any()
+                .downcast_ref::<StructArray>()
+                .ok_or_else(|| {
+                    DataFusionError::Internal(format!(
+                        "Expected a struct array to take the field {} from, got {:?}",
+                        segment,
+                        array.data_type()
+                    ))
+                })?;
+        if struct_array.null_count() > 0 {
+            let not_null = is_not_null(struct_array)?;
+            valid = Some(match valid {
+                Some(valid) => and(&valid, &not_null)?,
+                None => not_null,
+            });
+        }
+        array = struct_array
+            .column_by_name(segment)
+            .ok_or_else(|| {
+                DataFusionError::Internal(format!(
+                    "Struct array has no field {}",
+                    segment
+                ))
+            })?
+            .clone();
+    }
+    match valid {
+        Some(valid) => Ok(filter(array.as_ref(), &valid)?),
+        None => Ok(array),
+    }
+}
+
+/// Prunes files with the statistics of their nested leaf fields, using the
+/// filters on those fields
+pub(crate) struct NestedStatsPruning {
+    predicate: PruningPredicate,
+    /// The leaf fields, named by their path, in the order of their statistics
+    fields: Vec<Field>,
+}
+
+impl NestedStatsPruning {
+    /// Create the pruning of the files of `schema` with the statistics of
+    /// the fields at `paths`, or `None` if no filter is on those fields
+    pub(crate) fn try_new(
+        schema: &Schema,
+        paths: &[String],
+        filters: &[Expr],
+    ) -> Result<Option<Self>> {
+        let fields: Vec<Field> = paths
+            .iter()
+            .map(|path| {
+                let data_type = nested_field(schema, path)
+                    .map(|field| field.data_type().clone())
+                    .unwrap_or(DataType::Null);
+                Field::new(path, data_type, true)
+            })
+            .collect();
+
+        // the fields are referred to as columns named by their path
+        let mut rewriter = NestedColumnRewriter {
+            paths,
+            rewritten: false,
+        };
+        let filters = filters
+            .iter()
+            .map(|expr| expr.clone().rewrite(&mut rewriter))
+            .collect::<Result<Vec<_>>>()?;
+        let expr = match conjunction(filters) {
+            Some(expr) if rewriter.rewritten => expr,
+            _ => return Ok(None),
+        };
+
+        let predicate =
+            PruningPredicate::try_new(expr, Arc::new(Schema::new(fields.clone())))?;
+        Ok(Some(Self { predicate, fields }))
+    }
+
+    /// Returns false if no row of a file with the nested `statistics`, in
+    /// the order of the paths, can match the filters
+    pub(crate) fn prune(&self, statistics: &[ColumnStatistics]) -> Result<bool> {
+        let statistics = FileNestedStatistics {
+            fields: &self.fields,
+            statistics,
+        };
+        Ok(self.predicate.prune(&statistics)?[0])
+    }
+}
+
+/// Rewrites the fields of struct columns at the paths to columns named by
+/// their path
+struct NestedColumnRewriter<'a> {
+    paths: &'a [String],
+    rewritten: bool,
+}
+
+impl NestedColumnRewriter<'_> {
+    fn nested_path(&self, expr: &Expr) -> Option<String> {
+        let path = expr_path(expr)?;
+        if self.paths.contains(&path) {
+            Some(path)
+        } else {
+            None
+        }
+    }
+}
+
+impl ExprRewriter for NestedColumnRewriter<'_> {
+    fn pre_visit(&mut self, expr: &Expr) -> Result<RewriteRecursion> {
+        match expr {
+            Expr::GetIndexedField(_) if self.nested_path(expr).is_some() => {
+                Ok(RewriteRecursion::Mutate)
+            }
+            _ => Ok(RewriteRecursion::Continue),
+        }
+    }
+
+    fn mutate(&mut self, expr: Expr) -> Result<Expr> {
+        match self.nested_path(&expr) {
+            Some(path) if matches!(expr, Expr::GetIndexedField(_)) => {
+                self.rewritten = true;
+                Ok(Expr::Column(Column {
+                    relation: None,
+                    name: path,
+                }))
+            }
+            _ => Ok(expr),
+        }
+    }
+}
+
+/// Returns the dot separated path of a column or of a chain of fields taken
+/// from a column, such as `payload.ts` for `payload['ts']`
+fn expr_path(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Column(column) => Some(column.name.clone()),
+        Expr::GetIndexedField(GetIndexedField {
+            expr,
+            key: ScalarValue::Utf8(Some(key)),
+        }) => expr_path(expr).map(|path| format!("{}.{}", path, key)),
+        _ => None,
+    }
+}
+
+/// The nested statistics of a single file
+struct FileNestedStatistics<'a> {
+    fields: &'a [Field],
+    statistics: &'a [ColumnStatistics],
+}
+
+impl FileNestedStatistics<'_> {
+    fn column_statistics(&self, column: &Column) -> Option<&ColumnStatistics> {
+        let i = self.fields.iter().position(|f| f.name() == &column.name)?;
+        self.statistics.get(i)
+    }
+}
+
+impl PruningStatistics for FileNestedStatistics<'_> {
+    fn min_values(&self, column: &Column) -> Option<ArrayRef> {
+        let value = self.column_statistics(column)?.min_value.as_ref()?;
+        Some(value.to_array())
+    }
+
+    fn max_values(&self, column: &Column) -> Option<ArrayRef> {
+        let value = self.column_statistics(column)?.max_value.as_ref()?;
+        Some(value.to_array())
+    }
+
+    fn num_containers(&self) -> usize {
+        1
+    }
+
+    fn null_counts(&self, column: &Column) -> Option<ArrayRef> {
+        let null_count = self.column_statistics(column)?.null_count?;
+        Some(Arc::new(UInt64Array::from(vec![null_count as u64])))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logical_expr::{col, lit};
+    use arrow::array::Int64Array;
+
+    fn schema() -> Schema {
+        Schema::new(vec![
+            Field::new("id", DataType::Int64, true),
+            Field::new(
+                "payload",
+                DataType::Struct(vec![Field::new("ts", DataType::Int64, true)]),
+                true,
+            ),
+        ])
+    }
+
+    fn get_field(expr: Expr, key: &str) -> Expr {
+        Expr::GetIndexedField(GetIndexedField::new(
+            Box::new(expr),
+            ScalarValue::Utf8(Some(key.to_string())),
+        ))
+    }
+
+    #[test]
+    fn accumulate_nested_leaf() -> Result<()> {
+        let schema = Arc::new(schema());
+        let paths = vec!["payload.ts".to_string(), "payload.missing".to_string()];
+        let ts: ArrayRef =
+            Arc::new(Int64Array::from(vec![Some(3), Some(-1), None, Some(99)]));
+        // the last row has a null payload, whose value is not accounted for
+        let payload = StructArray::from((
+            vec![(Field::new("ts", DataType::Int64, true), ts)],
+            arrow::buffer::Buffer::from([0b0111u8]),
+        ));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int64Array::from(vec![1, 2, 3, 4])),
+                Arc::new(payload),
+            ],
+        )?;
+
+        let mut accumulator = NestedStatsAccumulator::new(&schema, &paths);
+        assert_eq!(accumulator.projection(), vec!["payload".to_string()]);
+        accumulator.update(&batch)?;
+        let statistics = accumulator.finish()?;
+        assert_eq!(
+            statistics,
+            vec![
+                ColumnStatistics {
+                    null_count: Some(2),
+                    max_value: Some(ScalarValue::Int64(Some(3))),
+                    min_value: Some(ScalarValue::Int64(Some(-1))),
+                    distinct_count: None,
+                },
+                ColumnStatistics::default(),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn prune_with_nested_filters() -> Result<()> {
+        let schema = schema();
+        let paths = vec!["payload.ts".to_string()];
+        let statistics = vec![ColumnStatistics {
+            null_count: Some(0),
+            max_value: Some(ScalarValue::Int64(Some(10))),
+            min_value: Some(ScalarValue::Int64(Some(1))),
+            distinct_count: None,
+        }];
+
+        let filters = [get_field(col("payload"), "ts").gt(lit(50i64))];
+        let pruning = NestedStatsPruning::try_new(&schema, &paths, &filters)?.unwrap();
+        assert!(!pruning.prune(&statistics)?);
+
+        let filters = [get_field(col("payload"), "ts").lt(lit(5i64))];
+        let pruning = NestedStatsPruning::try_new(&schema, &paths, &filters)?.unwrap();
+        assert!(pruning.prune(&statistics)?);
+
+        // filters not on the nested fields can't prune
+        let filters = [col("id").gt(lit(50i64))];
+        assert!(NestedStatsPruning::try_new(&schema, &paths, &filters)?.is_none());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,401 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Loading of schemas declared in standalone schema files, so that they
+//! can be reused as the explicit schema of a [`FileFormat`](super::FileFormat)
+//! such as [`JsonFormat`](super::json::JsonFormat)
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::io::Cursor;
+use std::path::Path;
+
+use arrow::datatypes::{DataType, Field, IntervalUnit, Schema, TimeUnit};
+use serde_json::Value;
+
+use super::ipc::IpcBatchReader;
+use crate::avro_to_arrow::{read_avro_schema_from_json, read_avro_schema_from_reader};
+use crate::error::{DataFusionError, Result};
+
+/// The magic bytes at the start of an Avro object container file
+const AVRO_MAGIC: [u8; 4] = *b"Obj\x01";
+
+/// Loads the schema declared in the file at `path`, which may be one of:
+///
+/// * an Arrow IPC stream, such as a lone schema message, or an Arrow IPC
+///   file; only the schema is read, any record batches are ignored
+/// * an Avro schema definition (`.avsc`) or an Avro object container file,
+///   both of which require the `avro` feature
+/// * a JSON schema spec listing the fields of the schema, each with a
+///   `name`, a `type` named after the [`DataType`] variant and an optional
+///   `nullable` flag (defaulting to `true`):
+///
+/// ```json
+/// {
+///   "fields": [
+///     {"name": "id", "type": "Int64", "nullable": false},
+///     {"name": "ts", "type": "Timestamp", "unit": "Millisecond", "timezone": "+00:00"},
+///     {"name": "price", "type": "Decimal128", "precision": 10, "scale": 2},
+///     {"name": "tags", "type": "List", "children": [{"name": "item", "type": "Utf8"}]}
+///   ],
+///   "metadata": {"source": "orders"}
+/// }
+/// ```
+///
+/// Types with parameters take them as extra properties: `unit` for
+/// `Timestamp`, `Time32`, `Time64`, `Duration` and `Interval`, an optional
+/// `timezone` for `Timestamp`, `precision` and `scale` for `Decimal128`,
+/// `size` for `FixedSizeBinary`, and `children` for `List`, `LargeList`
+/// (a single child) and `Struct`.
+pub fn read_schema_file(path: impl AsRef<Path>) -> Result<Schema> {
+    let bytes = std::fs::read(path)?;
+    if bytes.starts_with(&AVRO_MAGIC) {
+        return read_avro_schema_from_reader(&mut bytes.as_slice());
+    }
+
+    if bytes.iter().find(|b| !b.is_ascii_whitespace()) == Some(&b'{') {
+        let value: Value = serde_json::from_slice(&bytes)
+            .map_err(|e| invalid(format!("not valid JSON: {}", e)))?;
+        return match value.get("type") {
+            Some(Value::String(t)) if t == "record" => {
+                read_avro_schema_from_json(&value.to_string())
+            }
+            _ => parse_schema(&value),
+        };
+    }
+
+    let reader = IpcBatchReader::try_new(Cursor::new(bytes), None)?;
+    Ok(reader.schema().as_ref().clone())
+}
+
+fn invalid(message: impl Display) -> DataFusionError {
+    DataFusionError::Plan(format!("Invalid schema file: {}", message))
+}
+
+fn parse_schema(value: &Value) -> Result<Schema> {
+    let fields = value
+        .get("fields")
+        .and_then(Value::as_array)
+        .ok_or_else(|| invalid("the schema must have a 'fields' array"))?
+        .iter()
+        .map(parse_field)
+        .collect::<Result<Vec<_>>>()?;
+
+    let metadata = match value.get("metadata") {
+        None => HashMap::new(),
+        Some(Value::Object(metadata)) => metadata
+            .iter()
+            .map(|(key, value)| match value.as_str() {
+                Some(value) => Ok((key.clone(), value.to_string())),
+                None => Err(invalid(format!("metadata '{}' must be a string", key))),
+            })
+            .collect::<Result<_>>()?,
+        Some(_) => return Err(invalid("the schema metadata must be an object")),
+    };
+
+    Ok(Schema::new_with_metadata(fields, metadata))
+}
+
+fn parse_field(value: &Value) -> Result<Field> {
+    let name = str_property(value, "name", "a field")?;
+    let nullable = match value.get("nullable") {
+        None => true,
+        Some(nullable) => nullable
+            .as_bool()
+            .ok_or_else(|| invalid(format!("'nullable' of '{}' must be a bool", name)))?,
+    };
+    Ok(Field::new(name, parse_data_type(value, name)?, nullable))
+}
+
+fn parse_data_type(value: &Value, name: &str) -> Result<DataType> {
+    Ok(match str_property(value, "type", name)? {
+        "Null" => DataType::Null,
+        "Boolean" => DataType::Boolean,
+        "Int8" => DataType::Int8,
+        "Int16" => DataType::Int16,
+        "Int32" => DataType::Int32,
+        "Int64" => DataType::Int64,
+        "UInt8" => DataType::UInt8,
+        "UInt16" => DataType::UInt16,
+        "UInt32" => DataType::UInt32,
+        "UInt64" => DataType::UInt64,
+        "Float16" => DataType::Float16,
+        "Float32" => DataType::Float32,
+        "Float64" => DataType::Float64,
+        "Utf8" => DataType::Utf8,
+        "LargeUtf8" => DataType::LargeUtf8,
+        "Binary" => DataType::Binary,
+        "LargeBinary" => DataType::LargeBinary,
+        "Date32" => DataType::Date32,
+        "Date64" => DataType::Date64,
+        "Timestamp" => {
+            let timezone = match value.get("timezone") {
+                None | Some(Value::Null) => None,
+                Some(Value::String(timezone)) => Some(timezone.clone()),
+                Some(_) => {
+                    return Err(invalid(format!(
+                        "'timezone' of '{}' must be a string",
+                        name
+                    )))
+                }
+            };
+            DataType::Timestamp(time_unit(value, name)?, timezone)
+        }
+        "Time32" => DataType::Time32(time_unit(value, name)?),
+        "Time64" => DataType::Time64(time_unit(value, name)?),
+        "Duration" => DataType::Duration(time_unit(value, name)?),
+        "Interval" => DataType::Interval(match str_property(value, "unit", name)? {
+            "YearMonth" => IntervalUnit::YearMonth,
+            "DayTime" => IntervalUnit::DayTime,
+            "MonthDayNano" => IntervalUnit::MonthDayNano,
+            unit => {
+                return Err(invalid(format!(
+                    "unknown interval unit '{}' of '{}'",
+                    unit, name
+                )))
+            }
+        }),
+        "Decimal128" => DataType::Decimal128(
+            int_property(value, "precision", name)?,
+            int_property(value, "scale", name)?,
+        ),
+        "FixedSizeBinary" => {
+            DataType::FixedSizeBinary(int_property(value, "size", name)?)
+        }
+        "List" => DataType::List(Box::new(single_child(value, name)?)),
+        "LargeList" => DataType::LargeList(Box::new(single_child(value, name)?)),
+        "Struct" => DataType::Struct(children(value, name)?),
+        data_type => {
+            return Err(invalid(format!(
+                "unsupported type '{}' of '{}'",
+                data_type, name
+            )))
+        }
+    })
+}
+
+fn time_unit(value: &Value, name: &str) -> Result<TimeUnit> {
+    match str_property(value, "unit", name)? {
+        "Second" => Ok(TimeUnit::Second),
+        "Millisecond" => Ok(TimeUnit::Millisecond),
+        "Microsecond" => Ok(TimeUnit::Microsecond),
+        "Nanosecond" => Ok(TimeUnit::Nanosecond),
+        unit => Err(invalid(format!(
+            "unknown time unit '{}' of '{}'",
+            unit, name
+        ))),
+    }
+}
+
+fn children(value: &Value, name: &str) -> Result<Vec<Field>> {
+    value
+        .get("children")
+        .and_then(Value::as_array)
+        .ok_or_else(|| invalid(format!("'{}' must have a 'children' array", name)))?
+        .iter()
+        .map(parse_field)
+        .collect()
+}
+
+fn single_child(value: &Value, name: &str) -> Result<Field> {
+    let mut children = children(value, name)?;
+    match children.len() {
+        1 => Ok(children.remove(0)),
+        _ => Err(invalid(format!("'{}' must have exactly one child", name))),
+    }
+}
+
+fn str_property<'a>(value: &'a Value, property: &str, name: &str) -> Result<&'a str> {
+    value
+        .get(property)
+        .and_then(Value::as_str)
+        .ok_or_else(|| invalid(format!("{} must have a string '{}'", name, property)))
+}
+
+fn int_property<T: TryFrom<u64>>(value: &Value, property: &str, name: &str) -> Result<T> {
+    value
+        .get(property)
+        .and_then(Value::as_u64)
+        .and_then(|v| T::try_from(v).ok())
+        .ok_or_else(|| invalid(format!("'{}' of '{}' is not valid", property, name)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_batches_eq;
+    use crate::datasource::file_format::json::JsonFormat;
+    use crate::datasource::file_format::test_util::scan_format;
+    use crate::physical_plan::collect;
+    use crate::prelude::SessionContext;
+    use arrow::ipc::writer::StreamWriter;
+    use std::fs::File;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn scan_json_with_ipc_schema_file() -> Result<()> {
+        let dir = TempDir::new()?;
+        let path = dir.path().join("schema.arrows");
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int64, false),
+            Field::new("d", DataType::Utf8, true),
+        ]);
+        let mut writer = StreamWriter::try_new(File::create(&path)?, &schema)?;
+        writer.finish()?;
+
+        let loaded = read_schema_file(&path)?;
+        assert_eq!(loaded, schema);
+
+        let format = JsonFormat::default().with_schema(Arc::new(loaded));
+        let exec = scan_format(&format, ".", "tests/jsons/2.json", None, Some(3)).await?;
+        let batches = collect(exec, SessionContext::new().task_ctx()).await?;
+        let expected = vec![
+            "+-----+------+",
+            "| a   | d    |",
+            "+-----+------+",
+            "| 1   | 4    |",
+            "| -10 | 4    |",
+            "| 2   | text |",
+            "+-----+------+",
+        ];
+        assert_batches_eq!(expected, &batches);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn scan_json_with_json_schema_spec() -> Result<()> {
+        let dir = TempDir::new()?;
+        let path = dir.path().join("schema.json");
+        std::fs::write(
+            &path,
+            r#"{
+                "fields": [
+                    {"name": "b", "type": "Float32", "nullable": false},
+                    {"name": "c", "type": "Boolean"},
+                    {"name": "d", "type": "LargeUtf8"}
+                ],
+                "metadata": {"source": "2.json"}
+            }"#,
+        )?;
+
+        let schema = read_schema_file(&path)?;
+        let expected_schema = Schema::new_with_metadata(
+            vec![
+                Field::new("b", DataType::Float32, false),
+                Field::new("c", DataType::Boolean, true),
+                Field::new("d", DataType::LargeUtf8, true),
+            ],
+            HashMap::from([("source".to_string(), "2.json".to_string())]),
+        );
+        assert_eq!(schema, expected_schema);
+
+        let format = JsonFormat::default().with_schema(Arc::new(schema));
+        let exec = scan_format(&format, ".", "tests/jsons/2.json", None, Some(3)).await?;
+        assert_eq!(exec.schema().field(0).data_type(), &DataType::Float32);
+        let batches = collect(exec, SessionContext::new().task_ctx()).await?;
+        let expected = vec![
+            "+------+-------+------+",
+            "| b    | c     | d    |",
+            "+------+-------+------+",
+            "| 2    | false | 4    |",
+            "| -3.5 | true  | 4    |",
+            "| 0.6  | false | text |",
+            "+------+-------+------+",
+        ];
+        assert_batches_eq!(expected, &batches);
+
+        Ok(())
+    }
+
+    #[test]
+    fn json_schema_spec_types() -> Result<()> {
+        let spec = serde_json::json!({
+            "fields": [
+                {"name": "ts", "type": "Timestamp", "unit": "Millisecond", "timezone": "+00:00"},
+                {"name": "span", "type": "Interval", "unit": "MonthDayNano"},
+                {"name": "price", "type": "Decimal128", "precision": 10, "scale": 2},
+                {"name": "tags", "type": "List", "children": [{"name": "item", "type": "Utf8"}]},
+                {"name": "point", "type": "Struct", "children": [
+                    {"name": "x", "type": "Float64"},
+                    {"name": "y", "type": "Float64"}
+                ]}
+            ]
+        });
+        let data_types: Vec<_> = parse_schema(&spec)?
+            .fields()
+            .iter()
+            .map(|f| f.data_type().clone())
+            .collect();
+        assert_eq!(
+            data_types,
+            vec![
+                DataType::Timestamp(TimeUnit::Millisecond, Some("+00:00".to_string())),
+                DataType::Interval(IntervalUnit::MonthDayNano),
+                DataType::Decimal128(10, 2),
+                DataType::List(Box::new(Field::new("item", DataType::Utf8, true))),
+                DataType::Struct(vec![
+                    Field::new("x", DataType::Float64, true),
+                    Field::new("y", DataType::Float64, true),
+                ]),
+            ]
+        );
+
+        let err = parse_schema(&serde_json::json!({
+            "fields": [{"name": "a", "type": "Decimal256"}]
+        }))
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Error during planning: Invalid schema file: unsupported type 'Decimal256' of 'a'"
+        );
+
+        Ok(())
+    }
+
+    #[cfg(feature = "avro")]
+    #[test]
+    fn read_avro_schema_definition() -> Result<()> {
+        let dir = TempDir::new()?;
+        let path = dir.path().join("schema.avsc");
+        std::fs::write(
+            &path,
+            r#"{
+                "type": "record",
+                "name": "test",
+                "fields": [
+                    {"name": "a", "type": ["null", "long"]},
+                    {"name": "d", "type": "string"}
+                ]
+            }"#,
+        )?;
+
+        let schema = read_schema_file(&path)?;
+        let fields: Vec<_> = schema
+            .fields()
+            .iter()
+            .map(|f| (f.name().as_str(), f.data_type().clone(), f.is_nullable()))
+            .collect();
+        assert_eq!(
+            fields,
+            vec![("a", DataType::Int64, true), ("d", DataType::Utf8, false)]
+        );
+
+        Ok(())
+    }
+}
@@ -45,6 +45,7 @@ use datafusion_expr::{
 };
 use object_store::path::Path;
 use object_store::{ObjectMeta, ObjectStore};
+use regex::Regex;
 
 const FILE_SIZE_COLUMN_NAME: &str = "_df_part_file_size_";
 const FILE_PATH_COLUMN_NAME: &str = "_df_part_file_path_";
@@ -163,7 +164,14 @@ pub fn split_files(
 /// Discover the partitions on the given path and prune out files
 /// that belong to irrelevant partitions using `filters` expressions.
 /// `filters` might contain expressions that can be resolved only at the
-/// file level (e.g. Parquet row group pruning).
+/// file level (e.g. Parquet row group pruning). If `detect_compression` is
+/// true, files whose `file_extension` is followed by the extension of a
+/// compression are listed as well.
+///
+/// The values of the partitions are parsed from the path of the files, see
+/// [`ListingOptions::partition_regex`].
+///
+/// [`ListingOptions::partition_regex`]: super::ListingOptions::partition_regex
 ///
 /// TODO for tables with many files (10k+), it will usually more efficient
 /// to first list the folders relative to the first partition dimension,
@@ -173,9 +181,11 @@ pub async fn pruned_partition_list<'a>(
     table_path: &'a ListingTableUrl,
     filters: &'a [Expr],
     file_extension: &'a str,
+    detect_compression: bool,
     table_partition_cols: &'a [String],
+    partition_regex: Option<&'a Regex>,
 ) -> Result<BoxStream<'a, Result<PartitionedFile>>> {
-    let list = table_path.list_all_files(store, file_extension);
+    let list = table_path.list_all_files(store, file_extension, detect_compression);
 
     // if no partition col => simply list all the files
     if table_partition_cols.is_empty() {
@@ -194,10 +204,11 @@ pub async fn pruned_partition_list<'a>(
         // the object store.
         Ok(Box::pin(list.try_filter_map(
             move |object_meta| async move {
-                let parsed_path = parse_partitions_for_path(
+                let parsed_path = partition_values_for_path(
                     table_path,
                     &object_meta.location,
                     table_partition_cols,
+                    partition_regex,
                 )
                 .map(|p| {
                     p.iter()
@@ -216,7 +227,8 @@ pub async fn pruned_partition_list<'a>(
     } else {
         // parse the partition values and serde them as a RecordBatch to filter them
         let metas: Vec<_> = list.try_collect().await?;
-        let batch = paths_to_batch(table_partition_cols, table_path, &metas)?;
+        let batch =
+            paths_to_batch(table_partition_cols, partition_regex, table_path, &metas)?;
         let mem_table = MemTable::try_new(batch.schema(), vec![vec![batch]])?;
 
         // Filter the partitions using a local datafusion context
@@ -243,6 +255,7 @@ pub async fn pruned_partition_list<'a>(
 /// Note: For the last modified date, this looses precisions higher than millisecond.
 fn paths_to_batch(
     table_partition_cols: &[String],
+    partition_regex: Option<&Regex>,
     table_path: &ListingTableUrl,
     metas: &[ObjectMeta],
 ) -> Result<RecordBatch> {
@@ -254,10 +267,11 @@ fn paths_to_batch(
         .map(|_| StringBuilder::with_capacity(metas.len(), 1024))
         .collect::<Vec<_>>();
     for file_meta in metas {
-        if let Some(partition_values) = parse_partitions_for_path(
+        if let Some(partition_values) = partition_values_for_path(
             table_path,
             &file_meta.location,
             table_partition_cols,
+            partition_regex,
         ) {
             key_builder.append_value(file_meta.location.as_ref());
             length_builder.append_value(file_meta.size as u64);
@@ -336,6 +350,42 @@ fn batches_to_paths(batches: &[RecordBatch]) -> Result<Vec<PartitionedFile>> {
         .collect()
 }
 
+/// Extract the partition values for the given `file_path` (in the given `table_path`)
+/// associated to the partitions defined by `table_partition_cols`, from the named
+/// capture groups of `partition_regex` if set, or from hive style directories otherwise
+fn partition_values_for_path<'a>(
+    table_path: &ListingTableUrl,
+    file_path: &'a Path,
+    table_partition_cols: &[String],
+    partition_regex: Option<&Regex>,
+) -> Option<Vec<&'a str>> {
+    match partition_regex {
+        Some(regex) => parse_partitions_with_regex(
+            table_path,
+            file_path,
+            table_partition_cols,
+            regex,
+        ),
+        None => parse_partitions_for_path(table_path, file_path, table_partition_cols),
+    }
+}
+
+/// Extract the partition values for the given `file_path` (in the given `table_path`)
+/// from the capture groups of `regex` named after the `table_partition_cols`, matched
+/// against the path of the file relative to the table
+fn parse_partitions_with_regex<'a>(
+    table_path: &ListingTableUrl,
+    file_path: &'a Path,
+    table_partition_cols: &[String],
+    regex: &Regex,
+) -> Option<Vec<&'a str>> {
+    let captures = regex.captures(table_path.relative_path(file_path)?)?;
+    table_partition_cols
+        .iter()
+        .map(|pn| captures.name(pn).map(|value| value.as_str()))
+        .collect()
+}
+
 /// Extract the partition values for the given `file_path` (in the given `table_path`)
 /// associated to the partitions defined by `table_partition_cols`
 fn parse_partitions_for_path<'a>(
@@ -415,7 +465,9 @@ mod tests {
             &ListingTableUrl::parse("file:///tablepath/").unwrap(),
             &[filter],
             ".parquet",
+            false,
             &[String::from("mypartition")],
+            None,
         )
         .await
         .expect("partition pruning failed")
@@ -438,7 +490,9 @@ mod tests {
             &ListingTableUrl::parse("file:///tablepath/").unwrap(),
             &[filter],
             ".parquet",
+            false,
             &[String::from("mypartition")],
+            None,
         )
         .await
         .expect("partition pruning failed")
@@ -485,7 +539,9 @@ mod tests {
             &ListingTableUrl::parse("file:///tablepath/").unwrap(),
             &[filter1, filter2, filter3],
             ".parquet",
+            false,
             &[String::from("part1"), String::from("part2")],
+            None,
         )
         .await
         .expect("partition pruning failed")
@@ -589,6 +645,51 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_partitions_with_regex() {
+        let table_path = ListingTableUrl::parse("file:///bucket/mytable").unwrap();
+        let regex = Regex::new(r"(?P<year>\d{4})/events_(?P<day>\d{2})\.json$").unwrap();
+        let cols = [String::from("day"), String::from("year")];
+        assert_eq!(
+            Some(vec!["01", "2021"]),
+            partition_values_for_path(
+                &table_path,
+                &Path::from("bucket/mytable/2021/events_01.json"),
+                &cols,
+                Some(&regex)
+            )
+        );
+        assert_eq!(
+            None,
+            partition_values_for_path(
+                &table_path,
+                &Path::from("bucket/mytable/2021/other.json"),
+                &cols,
+                Some(&regex)
+            )
+        );
+        // the regex is matched against the path relative to the table
+        assert_eq!(
+            None,
+            partition_values_for_path(
+                &table_path,
+                &Path::from("bucket/othertable/2021/events_01.json"),
+                &cols,
+                Some(&regex)
+            )
+        );
+        // a partition column that is not a capture group is not extracted
+        assert_eq!(
+            None,
+            partition_values_for_path(
+                &table_path,
+                &Path::from("bucket/mytable/2021/events_01.json"),
+                &[String::from("month")],
+                Some(&regex)
+            )
+        );
+    }
+
     #[test]
     fn test_path_batch_roundtrip_no_partiton() {
         let files = vec![
@@ -605,7 +706,7 @@ mod tests {
         ];
 
         let table_path = ListingTableUrl::parse("file:///mybucket/tablepath").unwrap();
-        let batches = paths_to_batch(&[], &table_path, &files)
+        let batches = paths_to_batch(&[], None, &table_path, &files)
             .expect("Serialization of file list to batch failed");
 
         let parsed_files = batches_to_paths(&[batches]).unwrap();
@@ -637,6 +738,7 @@ mod tests {
 
         let batches = paths_to_batch(
             &[String::from("part1")],
+            None,
             &ListingTableUrl::parse("file:///mybucket/tablepath").unwrap(),
             &files,
         )
@@ -26,12 +26,14 @@ use dashmap::DashMap;
 use futures::{future, stream, StreamExt, TryStreamExt};
 use object_store::path::Path;
 use object_store::ObjectMeta;
+use regex::Regex;
 
 use crate::datasource::file_format::file_type::{FileCompressionType, FileType};
+use crate::datasource::file_format::nested_stats::NestedStatsPruning;
 use crate::datasource::{
     file_format::{
-        avro::AvroFormat, csv::CsvFormat, json::JsonFormat, parquet::ParquetFormat,
-        FileFormat,
+        avro::AvroFormat, csv::CsvFormat, ipc::IpcFormat, json::JsonFormat,
+        parquet::ParquetFormat, FileFormat,
     },
     get_statistics_with_limit,
     listing::ListingTableUrl,
@@ -45,7 +47,7 @@ use crate::{
     physical_plan::{
         empty::EmptyExec,
         file_format::{FileScanConfig, DEFAULT_PARTITION_COLUMN_DATATYPE},
-        project_schema, ExecutionPlan, Statistics,
+        project_schema, ColumnStatistics, ExecutionPlan, Statistics,
     },
 };
 
@@ -125,6 +127,7 @@ impl ListingTableConfig {
             .map_err(|_| DataFusionError::Internal(err_msg))?;
 
         let file_format: Arc<dyn FileFormat> = match file_type {
+            FileType::ARROW => Arc::new(IpcFormat::default()),
             FileType::AVRO => Arc::new(AvroFormat::default()),
             FileType::CSV => Arc::new(
                 CsvFormat::default().with_file_compression_type(file_compression_type),
@@ -148,7 +151,7 @@ impl ListingTableConfig {
             .table_paths
             .get(0)
             .unwrap()
-            .list_all_files(store.as_ref(), "")
+            .list_all_files(store.as_ref(), "", false)
             .next()
             .await
             .ok_or_else(|| DataFusionError::Internal("No files for table".into()))??;
@@ -162,6 +165,7 @@ impl ListingTableConfig {
             file_extension,
             target_partitions: ctx.config.target_partitions,
             table_partition_cols: vec![],
+            partition_regex: None,
         };
 
         Ok(Self {
@@ -220,6 +224,8 @@ pub struct ListingOptions {
     /// Group files to avoid that the number of partitions exceeds
     /// this limit
     pub target_partitions: usize,
+    /// See [`Self::with_partition_regex`]
+    partition_regex: Option<Regex>,
 }
 
 impl ListingOptions {
@@ -236,9 +242,56 @@ impl ListingOptions {
             table_partition_cols: vec![],
             collect_stat: true,
             target_partitions: 1,
+            partition_regex: None,
         }
     }
 
+    /// Set the suffix on which files should be filtered
+    pub fn with_file_extension(mut self, file_extension: impl Into<String>) -> Self {
+        self.file_extension = file_extension.into();
+        self
+    }
+
+    /// Set the expected partition column names, see
+    /// [`Self::table_partition_cols`]
+    pub fn with_table_partition_cols(
+        mut self,
+        table_partition_cols: Vec<String>,
+    ) -> Self {
+        self.table_partition_cols = table_partition_cols;
+        self
+    }
+
+    /// Set whether to guess statistics from the files
+    pub fn with_collect_stat(mut self, collect_stat: bool) -> Self {
+        self.collect_stat = collect_stat;
+        self
+    }
+
+    /// Set the number of partitions that the files are grouped into
+    pub fn with_target_partitions(mut self, target_partitions: usize) -> Self {
+        self.target_partitions = target_partitions;
+        self
+    }
+
+    /// Set a regex whose named capture groups extract the values of the
+    /// `table_partition_cols` from the path of every file, relative to the
+    /// table path, instead of hive style `col=value` folders. For example
+    /// `events_(?P<day>\d{4}-\d{2}-\d{2})\.json` extracts the `day`
+    /// partition of `events_2021-03-01.json`:
+    /// - Every partition column must be a named capture group of the regex.
+    /// - Files whose path does not match the regex will be ignored.
+    pub fn with_partition_regex(mut self, partition_regex: Option<Regex>) -> Self {
+        self.partition_regex = partition_regex;
+        self
+    }
+
+    /// The regex extracting the partition columns from the path of the
+    /// files, see [`Self::with_partition_regex`]
+    pub fn partition_regex(&self) -> Option<&Regex> {
+        self.partition_regex.as_ref()
+    }
+
     /// Infer the schema of the files at the given path on the provided object store.
     /// The inferred schema does not include the partitioning columns.
     ///
@@ -253,7 +306,11 @@ impl ListingOptions {
         let store = ctx.runtime_env.object_store(table_path)?;
 
         let files: Vec<_> = table_path
-            .list_all_files(store.as_ref(), &self.file_extension)
+            .list_all_files(
+                store.as_ref(),
+                &self.file_extension,
+                self.format.detects_compression(),
+            )
             .try_collect()
             .await?;
 
@@ -264,13 +321,13 @@ impl ListingOptions {
 /// Collected statistics for files
 /// Cache is invalided when file size or last modification has changed
 #[derive(Default)]
-struct StatisticsCache {
-    statistics: DashMap<Path, (ObjectMeta, Statistics)>,
+struct StatisticsCache<T = Statistics> {
+    statistics: DashMap<Path, (ObjectMeta, T)>,
 }
 
-impl StatisticsCache {
+impl<T: Clone> StatisticsCache<T> {
     /// Get `Statistics` for file location. Returns None if file has changed or not found.
-    fn get(&self, meta: &ObjectMeta) -> Option<Statistics> {
+    fn get(&self, meta: &ObjectMeta) -> Option<T> {
         self.statistics
             .get(&meta.location)
             .map(|s| {
@@ -288,7 +345,7 @@ impl StatisticsCache {
     }
 
     /// Save collected file statistics
-    fn save(&self, meta: ObjectMeta, statistics: Statistics) {
+    fn save(&self, meta: ObjectMeta, statistics: T) {
         self.statistics
             .insert(meta.location.clone(), (meta, statistics));
     }
@@ -305,6 +362,9 @@ pub struct ListingTable {
     options: ListingOptions,
     definition: Option<String>,
     collected_statistics: StatisticsCache,
+    /// The statistics of the nested fields of the format's
+    /// [`FileFormat::nested_stats_paths`]
+    collected_nested_statistics: StatisticsCache<Vec<ColumnStatistics>>,
 }
 
 impl ListingTable {
@@ -324,6 +384,20 @@ impl ListingTable {
             DataFusionError::Internal("No ListingOptions provided".into())
         })?;
 
+        if let Some(regex) = &options.partition_regex {
+            for part in &options.table_partition_cols {
+                if !regex
+                    .capture_names()
+                    .any(|name| name == Some(part.as_str()))
+                {
+                    return Err(DataFusionError::Plan(format!(
+                        "Partition column {} is not a named capture group of the partition regex {}",
+                        part, regex
+                    )));
+                }
+            }
+        }
+
         // Add the partition columns to the file schema
         let mut table_fields = file_schema.fields().clone();
         for part in &options.table_partition_cols {
@@ -341,6 +415,7 @@ impl ListingTable {
             options,
             definition: None,
             collected_statistics: Default::default(),
+            collected_nested_statistics: Default::default(),
         };
 
         Ok(table)
@@ -452,13 +527,24 @@ impl ListingTable {
                 table_path,
                 filters,
                 &self.options.file_extension,
+                self.options.format.detects_compression(),
                 &self.options.table_partition_cols,
+                self.options.partition_regex.as_ref(),
             )
         }))
         .await?;
 
         let file_list = stream::iter(file_list).flatten();
 
+        // the filters on nested fields with statistics prune the files whose
+        // statistics rule them out
+        let nested_paths = self.options.format.nested_stats_paths();
+        let nested_pruning = if self.options.collect_stat && !nested_paths.is_empty() {
+            NestedStatsPruning::try_new(&self.file_schema, nested_paths, filters)?
+        } else {
+            None
+        };
+
         // collect the statistics if required by the config
         let files = file_list.then(|part_file| async {
             let part_file = part_file?;
@@ -483,8 +569,30 @@ impl ListingTable {
             } else {
                 Statistics::default()
             };
-            Ok((part_file, statistics)) as Result<(PartitionedFile, Statistics)>
+
+            if let Some(nested_pruning) = &nested_pruning {
+                let meta = &part_file.object_meta;
+                let nested_statistics = match self.collected_nested_statistics.get(meta) {
+                    Some(nested_statistics) => nested_statistics,
+                    None => {
+                        let nested_statistics = self
+                            .options
+                            .format
+                            .infer_nested_stats(&store, self.file_schema.clone(), meta)
+                            .await?;
+                        self.collected_nested_statistics
+                            .save(meta.clone(), nested_statistics.clone());
+                        nested_statistics
+                    }
+                };
+                if !nested_pruning.prune(&nested_statistics)? {
+                    return Ok(None);
+                }
+            }
+            Ok(Some((part_file, statistics)))
+                as Result<Option<(PartitionedFile, Statistics)>>
         });
+        let files = files.try_filter_map(|file| future::ready(Ok(file)));
 
         let (files, statistics) =
             get_statistics_with_limit(files, self.schema(), limit).await?;
@@ -566,6 +674,7 @@ mod tests {
             table_partition_cols: vec![String::from("p1")],
             target_partitions: 4,
             collect_stat: true,
+            partition_regex: None,
         };
 
         let table_path = ListingTableUrl::parse("test:///table/").unwrap();
@@ -768,6 +877,7 @@ mod tests {
             table_partition_cols: vec![],
             target_partitions,
             collect_stat: true,
+            partition_regex: None,
         };
 
         let schema = Schema::new(vec![Field::new("a", DataType::Boolean, false)]);
@@ -805,6 +915,7 @@ mod tests {
             table_partition_cols: vec![],
             target_partitions,
             collect_stat: true,
+            partition_regex: None,
         };
 
         let schema = Schema::new(vec![Field::new("a", DataType::Boolean, false)]);
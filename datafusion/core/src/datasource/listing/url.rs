@@ -15,6 +15,7 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use crate::datasource::file_format::file_type::{FileCompressionType, GetExt};
 use crate::datasource::object_store::ObjectStoreUrl;
 use datafusion_common::{DataFusionError, Result};
 use futures::stream::BoxStream;
@@ -126,20 +127,28 @@ impl ListingTableUrl {
         &'a self,
         path: &'b Path,
     ) -> Option<impl Iterator<Item = &'b str> + 'a> {
+        use object_store::path::DELIMITER;
+        Some(self.relative_path(path)?.split(DELIMITER))
+    }
+
+    /// Strips the prefix of this [`ListingTableUrl`] from the provided path, returning
+    /// the remaining path
+    pub(crate) fn relative_path<'b>(&self, path: &'b Path) -> Option<&'b str> {
         use object_store::path::DELIMITER;
         let path: &str = path.as_ref();
-        let stripped = match self.prefix.as_ref() {
-            "" => path,
-            p => path.strip_prefix(p)?.strip_prefix(DELIMITER)?,
-        };
-        Some(stripped.split(DELIMITER))
+        match self.prefix.as_ref() {
+            "" => Some(path),
+            p => path.strip_prefix(p)?.strip_prefix(DELIMITER),
+        }
     }
 
-    /// List all files identified by this [`ListingTableUrl`] for the provided `file_extension`
+    /// List all files identified by this [`ListingTableUrl`] for the provided `file_extension`,
+    /// ignoring the extension of their compression if `detect_compression` is true
     pub(crate) fn list_all_files<'a>(
         &'a self,
         store: &'a dyn ObjectStore,
         file_extension: &'a str,
+        detect_compression: bool,
     ) -> BoxStream<'a, Result<ObjectMeta>> {
         // If the prefix is a file, use a head request, otherwise list
         let is_dir = self.url.as_str().ends_with('/');
@@ -153,7 +162,12 @@ impl ListingTableUrl {
         list.map_err(Into::into)
             .try_filter(move |meta| {
                 let path = &meta.location;
-                let extension_match = path.as_ref().ends_with(file_extension);
+                let mut name = path.as_ref();
+                if detect_compression {
+                    let compression = FileCompressionType::from_path(name).get_ext();
+                    name = &name[..name.len() - compression.len()];
+                }
+                let extension_match = name.ends_with(file_extension);
                 let glob_match = match &self.glob {
                     Some(glob) => match self.strip_prefix(path) {
                         Some(mut segments) => {
@@ -21,6 +21,7 @@ use crate::datasource::datasource::TableProviderFactory;
 use crate::datasource::file_format::avro::AvroFormat;
 use crate::datasource::file_format::csv::CsvFormat;
 use crate::datasource::file_format::file_type::{FileType, GetExt};
+use crate::datasource::file_format::ipc::IpcFormat;
 use crate::datasource::file_format::json::JsonFormat;
 use crate::datasource::file_format::parquet::ParquetFormat;
 use crate::datasource::file_format::FileFormat;
@@ -58,16 +59,14 @@ impl TableProviderFactory for ListingTableFactory {
             FileType::CSV => Arc::new(CsvFormat::default()),
             FileType::PARQUET => Arc::new(ParquetFormat::default()),
             FileType::AVRO => Arc::new(AvroFormat::default()),
+            FileType::ARROW => Arc::new(IpcFormat::default()),
             FileType::JSON => Arc::new(JsonFormat::default()),
         };
 
-        let options = ListingOptions {
-            format: file_format,
-            collect_stat: true,
-            file_extension: file_extension.to_owned(),
-            target_partitions: 1,
-            table_partition_cols: vec![],
-        };
+        let options = ListingOptions::new(file_format)
+            .with_file_extension(file_extension)
+            .with_collect_stat(true)
+            .with_target_partitions(1);
 
         let table_path = ListingTableUrl::parse(&cmd.location)?;
         let resolved_schema = options.infer_schema(state, &table_path).await?;
@@ -19,49 +19,63 @@
 //! queried by DataFusion. This allows data to be pre-loaded into memory and then
 //! repeatedly queried without incurring additional file I/O overhead.
 
-use futures::StreamExt;
 use std::any::Any;
 use std::sync::Arc;
 
-use arrow::datatypes::SchemaRef;
+use arrow::datatypes::{Schema, SchemaRef};
 use arrow::record_batch::RecordBatch;
 use async_trait::async_trait;
 
 use crate::datasource::{TableProvider, TableType};
 use crate::error::{DataFusionError, Result};
 use crate::execution::context::{SessionState, TaskContext};
+use crate::execution::runtime_env::RuntimeEnv;
 use crate::logical_expr::Expr;
-use crate::physical_plan::common;
+use crate::physical_plan::common::{self, batch_byte_size};
+use crate::physical_plan::expressions::{MaxAccumulator, MinAccumulator};
 use crate::physical_plan::memory::MemoryExec;
-use crate::physical_plan::ExecutionPlan;
-use crate::physical_plan::{repartition::RepartitionExec, Partitioning};
+use crate::physical_plan::{Accumulator, ExecutionPlan, Statistics};
 
 /// In-memory table
 pub struct MemTable {
     schema: SchemaRef,
     batches: Vec<Vec<RecordBatch>>,
+    /// Exact statistics of the batches, computed once on creation
+    statistics: Statistics,
+    /// Memory of the batches registered with the memory manager of a
+    /// runtime, released when the table is dropped
+    tracked: Option<(Arc<RuntimeEnv>, usize)>,
 }
 
 impl MemTable {
-    /// Create a new in-memory table from the provided schema and record batches
+    /// Create a new in-memory table from the provided schema and record batches.
+    ///
+    /// The batches must have the columns of `schema`, with the same names and
+    /// types. Batches whose schema only differs in nullability or metadata
+    /// are given `schema`, provided they have no nulls in the columns it
+    /// declares as not nullable.
     pub fn try_new(schema: SchemaRef, partitions: Vec<Vec<RecordBatch>>) -> Result<Self> {
-        if partitions
-            .iter()
-            .flatten()
-            .all(|batches| schema.contains(&batches.schema()))
-        {
-            Ok(Self {
-                schema,
-                batches: partitions,
+        let batches = partitions
+            .into_iter()
+            .map(|partition| {
+                partition
+                    .into_iter()
+                    .map(|batch| coerce_batch(&schema, batch))
+                    .collect::<Result<Vec<_>>>()
             })
-        } else {
-            Err(DataFusionError::Plan(
-                "Mismatch between schema and batches".to_string(),
-            ))
-        }
+            .collect::<Result<Vec<_>>>()?;
+        let statistics = compute_statistics(&schema, &batches);
+        Ok(Self {
+            schema,
+            batches,
+            statistics,
+            tracked: None,
+        })
     }
 
-    /// Create a mem table by reading from another data source
+    /// Create a mem table by reading from another data source, with its
+    /// rows spread evenly over `output_partitions` partitions if set, or
+    /// keeping the partitions of the source otherwise
     pub async fn load(
         t: Arc<dyn TableProvider>,
         output_partitions: Option<usize>,
@@ -91,30 +105,43 @@ impl MemTable {
             data.push(result);
         }
 
-        let exec = MemoryExec::try_new(&data, schema.clone(), None)?;
-
         if let Some(num_partitions) = output_partitions {
-            let exec = RepartitionExec::try_new(
-                Arc::new(exec),
-                Partitioning::RoundRobinBatch(num_partitions),
-            )?;
-
-            // execute and collect results
-            let mut output_partitions = vec![];
-            for i in 0..exec.output_partitioning().partition_count() {
-                // execute this *output* partition and collect all batches
-                let task_ctx = Arc::new(TaskContext::from(ctx));
-                let mut stream = exec.execute(i, task_ctx)?;
-                let mut batches = vec![];
-                while let Some(result) = stream.next().await {
-                    batches.push(result?);
-                }
-                output_partitions.push(batches);
-            }
+            data = repartition_evenly(data.into_iter().flatten(), num_partitions);
+        }
+        MemTable::try_new(schema, data)
+    }
+
+    /// Returns the exact statistics of the batches of this table
+    pub fn statistics(&self) -> &Statistics {
+        &self.statistics
+    }
+
+    /// Register the memory held by the batches of this table as tracked
+    /// usage of the memory manager of `runtime`, until the table is dropped
+    pub fn with_tracked_memory(mut self, runtime: Arc<RuntimeEnv>) -> Self {
+        self.release_tracked_memory();
+        let size = self.batches.iter().flatten().map(batch_byte_size).sum();
+        runtime.grow_tracker_usage(size);
+        self.tracked = Some((runtime, size));
+        self
+    }
 
-            return MemTable::try_new(schema.clone(), output_partitions);
+    /// Returns the memory of the batches of this table that is tracked by
+    /// a memory manager, if any
+    pub fn tracked_memory(&self) -> Option<usize> {
+        self.tracked.as_ref().map(|(_, size)| *size)
+    }
+
+    fn release_tracked_memory(&mut self) {
+        if let Some((runtime, size)) = self.tracked.take() {
+            runtime.shrink_tracker_usage(size);
         }
-        MemTable::try_new(schema.clone(), data)
+    }
+}
+
+impl Drop for MemTable {
+    fn drop(&mut self) {
+        self.release_tracked_memory();
     }
 }
 
@@ -139,22 +166,133 @@ impl TableProvider for MemTable {
         _filters: &[Expr],
         _limit: Option<usize>,
     ) -> Result<Arc<dyn ExecutionPlan>> {
-        Ok(Arc::new(MemoryExec::try_new(
-            &self.batches.clone(),
-            self.schema(),
-            projection.clone(),
-        )?))
+        Ok(Arc::new(
+            MemoryExec::try_new(
+                &self.batches.clone(),
+                self.schema(),
+                projection.clone(),
+            )?
+            .with_statistics(self.statistics.clone()),
+        ))
+    }
+}
+
+/// Returns `batch` with the schema `schema`, if its columns have the same
+/// names and types and no nulls in the columns `schema` declares as not
+/// nullable
+fn coerce_batch(schema: &SchemaRef, batch: RecordBatch) -> Result<RecordBatch> {
+    if &batch.schema() == schema {
+        return Ok(batch);
+    }
+
+    let batch_schema = batch.schema();
+    if batch_schema.fields().len() != schema.fields().len()
+        || batch_schema
+            .fields()
+            .iter()
+            .zip(schema.fields())
+            .any(|(a, b)| a.name() != b.name() || a.data_type() != b.data_type())
+    {
+        return Err(DataFusionError::Plan(
+            "Mismatch between schema and batches".to_string(),
+        ));
+    }
+    for (field, column) in schema.fields().iter().zip(batch.columns()) {
+        if !field.is_nullable() && column.null_count() > 0 {
+            return Err(DataFusionError::Plan(format!(
+                "Mismatch between schema and batches: column '{}' is not nullable but has {} nulls",
+                field.name(),
+                column.null_count()
+            )));
+        }
     }
+    Ok(RecordBatch::try_new(
+        schema.clone(),
+        batch.columns().to_vec(),
+    )?)
+}
+
+/// Computes the exact number of rows and null counts of `partitions`, and the
+/// minimum and maximum values of the columns of types supported by the
+/// `MIN` and `MAX` aggregates
+fn compute_statistics(schema: &Schema, partitions: &[Vec<RecordBatch>]) -> Statistics {
+    let mut statistics =
+        common::compute_record_batch_statistics(partitions, schema, None);
+    if let Some(column_statistics) = statistics.column_statistics.as_mut() {
+        for (i, (field, column_statistics)) in
+            schema.fields().iter().zip(column_statistics).enumerate()
+        {
+            let (mut max, mut min) = match (
+                MaxAccumulator::try_new(field.data_type()),
+                MinAccumulator::try_new(field.data_type()),
+            ) {
+                (Ok(max), Ok(min)) => (max, min),
+                _ => continue,
+            };
+            let evaluated = partitions
+                .iter()
+                .flatten()
+                .try_for_each(|batch| {
+                    let values = [batch.column(i).clone()];
+                    max.update_batch(&values)?;
+                    min.update_batch(&values)
+                })
+                .and_then(|_| Ok((max.evaluate()?, min.evaluate()?)));
+            // all null columns have no minimum or maximum
+            if let Ok((max_value, min_value)) = evaluated {
+                if !max_value.is_null() && !min_value.is_null() {
+                    column_statistics.max_value = Some(max_value);
+                    column_statistics.min_value = Some(min_value);
+                }
+            }
+        }
+    }
+    statistics
+}
+
+/// Spreads the rows of `batches` over `num_partitions` partitions, the
+/// number of rows of any two partitions differing by at most one, slicing
+/// batches where needed
+fn repartition_evenly(
+    batches: impl IntoIterator<Item = RecordBatch>,
+    num_partitions: usize,
+) -> Vec<Vec<RecordBatch>> {
+    let batches = batches.into_iter().collect::<Vec<_>>();
+    let num_partitions = num_partitions.max(1);
+    let num_rows: usize = batches.iter().map(|batch| batch.num_rows()).sum();
+    let partition_rows = |partition: usize| {
+        num_rows / num_partitions + usize::from(partition < num_rows % num_partitions)
+    };
+    let mut partitions = vec![vec![]; num_partitions];
+
+    let mut partition = 0;
+    let mut remaining = partition_rows(partition);
+    for batch in batches {
+        let mut offset = 0;
+        while offset < batch.num_rows() {
+            if remaining == 0 {
+                partition += 1;
+                remaining = partition_rows(partition);
+            }
+            let len = remaining.min(batch.num_rows() - offset);
+            partitions[partition].push(batch.slice(offset, len));
+            offset += len;
+            remaining -= len;
+        }
+    }
+    partitions
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::from_slice::FromSlice;
+    use crate::physical_plan::ColumnStatistics;
     use crate::prelude::SessionContext;
-    use arrow::array::Int32Array;
+    use arrow::array::{Int32Array, StringArray};
     use arrow::datatypes::{DataType, Field, Schema};
-    use arrow::error::ArrowError;
+    use arrow::error::{ArrowError, Result as ArrowResult};
+    use datafusion_common::ScalarValue;
     use futures::StreamExt;
     use std::collections::HashMap;
 
@@ -390,4 +528,159 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_nullability_coercion() -> Result<()> {
+        let session_ctx = SessionContext::new();
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("b", DataType::Int32, true),
+        ]));
+        let mut metadata = HashMap::new();
+        metadata.insert("foo".to_string(), "bar".to_string());
+        let batch_schema = Arc::new(Schema::new_with_metadata(
+            vec![
+                Field::new("a", DataType::Int32, true),
+                Field::new("b", DataType::Int32, true),
+            ],
+            metadata,
+        ));
+
+        // the batch has no nulls in the column the table declares as not nullable
+        let batch = RecordBatch::try_new(
+            batch_schema.clone(),
+            vec![
+                Arc::new(Int32Array::from_slice([1, 2])),
+                Arc::new(Int32Array::from(vec![Some(3), None])),
+            ],
+        )?;
+        let provider = MemTable::try_new(schema.clone(), vec![vec![batch]])?;
+        let exec = provider
+            .scan(&session_ctx.state(), &None, &[], None)
+            .await?;
+        let batches = common::collect(exec.execute(0, session_ctx.task_ctx())?).await?;
+        assert_eq!(batches[0].schema(), schema);
+
+        let batch = RecordBatch::try_new(
+            batch_schema,
+            vec![
+                Arc::new(Int32Array::from(vec![Some(1), None])),
+                Arc::new(Int32Array::from(vec![Some(3), None])),
+            ],
+        )?;
+        let err = MemTable::try_new(schema, vec![vec![batch]]).err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "Error during planning: Mismatch between schema and batches: column 'a' is not nullable but has 1 nulls"
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_load_repartitions_evenly() -> Result<()> {
+        let session_ctx = SessionContext::new();
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, false)]));
+        let batches = [0..5, 5..6, 6..10]
+            .into_iter()
+            .map(|range| {
+                let values = Int32Array::from_iter_values(range);
+                RecordBatch::try_new(schema.clone(), vec![Arc::new(values)])
+            })
+            .collect::<ArrowResult<Vec<_>>>()?;
+        let source = MemTable::try_new(schema, vec![batches])?;
+
+        let provider =
+            MemTable::load(Arc::new(source), Some(3), &session_ctx.state()).await?;
+        let exec = provider
+            .scan(&session_ctx.state(), &None, &[], None)
+            .await?;
+        assert_eq!(exec.output_partitioning().partition_count(), 3);
+
+        let mut partition_rows = vec![];
+        let mut values = vec![];
+        for partition in 0..3 {
+            let batches =
+                common::collect(exec.execute(partition, session_ctx.task_ctx())?).await?;
+            partition_rows.push(batches.iter().map(|b| b.num_rows()).sum::<usize>());
+            for batch in batches {
+                let array = batch
+                    .column(0)
+                    .as_any()
+                    .downcast_ref::<Int32Array>()
+                    .unwrap();
+                values.extend(array.values().iter().copied());
+            }
+        }
+        assert_eq!(partition_rows, vec![4, 3, 3]);
+        assert_eq!(values, (0..10).collect::<Vec<_>>());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_statistics() -> Result<()> {
+        let session_ctx = SessionContext::new();
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("b", DataType::Utf8, true),
+            Field::new("c", DataType::Int32, true),
+        ]));
+        let batch1 = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int32Array::from_slice([3, 1])),
+                Arc::new(StringArray::from(vec![Some("x"), None])),
+                Arc::new(Int32Array::from(vec![None, None])),
+            ],
+        )?;
+        let batch2 = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int32Array::from_slice([7])),
+                Arc::new(StringArray::from(vec![Some("b")])),
+                Arc::new(Int32Array::from(vec![None])),
+            ],
+        )?;
+        let provider = MemTable::try_new(schema, vec![vec![batch1], vec![batch2]])?;
+
+        let statistics = provider.statistics();
+        assert!(statistics.is_exact);
+        assert_eq!(statistics.num_rows, Some(3));
+        let expected = vec![
+            ColumnStatistics {
+                null_count: Some(0),
+                max_value: Some(ScalarValue::Int32(Some(7))),
+                min_value: Some(ScalarValue::Int32(Some(1))),
+                distinct_count: None,
+            },
+            ColumnStatistics {
+                null_count: Some(1),
+                max_value: Some(ScalarValue::Utf8(Some("x".to_string()))),
+                min_value: Some(ScalarValue::Utf8(Some("b".to_string()))),
+                distinct_count: None,
+            },
+            // a column of nulls only has no minimum or maximum
+            ColumnStatistics {
+                null_count: Some(3),
+                max_value: None,
+                min_value: None,
+                distinct_count: None,
+            },
+        ];
+        assert_eq!(statistics.column_statistics, Some(expected.clone()));
+
+        // the statistics of a scan are projected
+        let exec = provider
+            .scan(&session_ctx.state(), &Some(vec![1, 0]), &[], None)
+            .await?;
+        let exec_statistics = exec.statistics();
+        assert_eq!(exec_statistics.num_rows, Some(3));
+        assert_eq!(
+            exec_statistics.column_statistics,
+            Some(vec![expected[1].clone(), expected[0].clone()])
+        );
+
+        Ok(())
+    }
 }
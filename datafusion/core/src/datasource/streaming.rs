@@ -0,0 +1,274 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Data source for querying streams of record batches as they arrive, without
+//! buffering them in memory first
+
+use std::any::Any;
+use std::sync::Arc;
+
+use arrow::datatypes::SchemaRef;
+use async_trait::async_trait;
+
+use crate::datasource::{TableProvider, TableType};
+use crate::error::Result;
+use crate::execution::context::SessionState;
+use crate::logical_expr::Expr;
+use crate::physical_plan::expressions::Column;
+use crate::physical_plan::projection::ProjectionExec;
+use crate::physical_plan::streaming::{PartitionStream, StreamingTableExec};
+use crate::physical_plan::{ExecutionPlan, PhysicalExpr};
+
+/// A table whose partitions are read from [`PartitionStream`]s.
+///
+/// Each scan of the table executes every partition again. Partitions created
+/// with [`OneShotPartitionStream`] can only be executed once, so queries after
+/// the first fail when executed; provide a [`PartitionStream`] that creates a
+/// new stream on each execution to query the table repeatedly.
+///
+/// [`OneShotPartitionStream`]: crate::physical_plan::streaming::OneShotPartitionStream
+pub struct StreamingTable {
+    schema: SchemaRef,
+    partitions: Vec<Arc<dyn PartitionStream>>,
+}
+
+impl StreamingTable {
+    /// Create a new table from the provided schema and partition streams
+    pub fn try_new(
+        schema: SchemaRef,
+        partitions: Vec<Arc<dyn PartitionStream>>,
+    ) -> Result<Self> {
+        // validates the schemas of the partitions
+        StreamingTableExec::try_new(schema.clone(), partitions.clone())?;
+        Ok(Self { schema, partitions })
+    }
+}
+
+#[async_trait]
+impl TableProvider for StreamingTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn table_type(&self) -> TableType {
+        TableType::Base
+    }
+
+    async fn scan(
+        &self,
+        _ctx: &SessionState,
+        projection: &Option<Vec<usize>>,
+        _filters: &[Expr],
+        _limit: Option<usize>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let exec = Arc::new(StreamingTableExec::try_new(
+            self.schema.clone(),
+            self.partitions.clone(),
+        )?);
+
+        match projection {
+            Some(projection)
+                if !projection.iter().copied().eq(0..self.schema.fields().len()) =>
+            {
+                let exprs = projection
+                    .iter()
+                    .map(|i| {
+                        let name = self.schema.field(*i).name();
+                        let expr: Arc<dyn PhysicalExpr> = Arc::new(Column::new(name, *i));
+                        (expr, name.clone())
+                    })
+                    .collect();
+                Ok(Arc::new(ProjectionExec::try_new(exprs, exec)?))
+            }
+            _ => Ok(exec),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_batches_eq;
+    use crate::execution::context::TaskContext;
+    use crate::physical_plan::memory::MemoryStream;
+    use crate::physical_plan::stream::RecordBatchStreamAdapter;
+    use crate::physical_plan::streaming::OneShotPartitionStream;
+    use crate::physical_plan::{displayable, SendableRecordBatchStream};
+    use crate::prelude::SessionContext;
+    use arrow::array::{ArrayRef, Int64Array, StringArray};
+    use arrow::datatypes::{DataType, Field, Schema};
+    use arrow::error::Result as ArrowResult;
+    use arrow::record_batch::RecordBatch;
+    use tokio::sync::mpsc;
+    use tokio_stream::wrappers::ReceiverStream;
+
+    fn schema() -> SchemaRef {
+        Arc::new(Schema::new(vec![
+            Field::new("k", DataType::Utf8, false),
+            Field::new("v", DataType::Int64, false),
+        ]))
+    }
+
+    fn batch(keys: Vec<&str>, values: Vec<i64>) -> RecordBatch {
+        RecordBatch::try_new(
+            schema(),
+            vec![
+                Arc::new(StringArray::from(keys)) as ArrayRef,
+                Arc::new(Int64Array::from(values)),
+            ],
+        )
+        .unwrap()
+    }
+
+    /// Returns a partition reading the batches sent to the returned channel
+    fn channel_partition() -> (
+        mpsc::Sender<ArrowResult<RecordBatch>>,
+        Arc<dyn PartitionStream>,
+    ) {
+        let (tx, rx) = mpsc::channel(1);
+        let stream: SendableRecordBatchStream = Box::pin(RecordBatchStreamAdapter::new(
+            schema(),
+            ReceiverStream::new(rx),
+        ));
+        (tx, Arc::new(OneShotPartitionStream::new(stream)))
+    }
+
+    /// A partition that can be replayed, returning the same batches on each execution
+    struct ReplayablePartition {
+        schema: SchemaRef,
+        batches: Vec<RecordBatch>,
+    }
+
+    impl PartitionStream for ReplayablePartition {
+        fn schema(&self) -> &SchemaRef {
+            &self.schema
+        }
+
+        fn execute(&self, _ctx: Arc<TaskContext>) -> Result<SendableRecordBatchStream> {
+            Ok(Box::pin(MemoryStream::try_new(
+                self.batches.clone(),
+                self.schema.clone(),
+                None,
+            )?))
+        }
+    }
+
+    #[tokio::test]
+    async fn aggregate_channel_streams() -> Result<()> {
+        let ctx = SessionContext::new();
+        let (tx1, partition1) = channel_partition();
+        let (tx2, partition2) = channel_partition();
+        let table = StreamingTable::try_new(schema(), vec![partition1, partition2])?;
+        ctx.register_table("t", Arc::new(table))?;
+
+        // the query runs while the batches arrive
+        let df = ctx
+            .sql("SELECT k, SUM(v) AS total FROM t GROUP BY k ORDER BY k")
+            .await?;
+        let query = tokio::spawn(async move { df.collect().await });
+
+        tx1.send(Ok(batch(vec!["a", "b"], vec![1, 2])))
+            .await
+            .unwrap();
+        tx2.send(Ok(batch(vec!["b", "c"], vec![3, 4])))
+            .await
+            .unwrap();
+        tx1.send(Ok(batch(vec!["a"], vec![5]))).await.unwrap();
+
+        // the results are complete once the channels are closed
+        drop(tx1);
+        drop(tx2);
+        let results = query.await.unwrap()?;
+        let expected = vec![
+            "+---+-------+",
+            "| k | total |",
+            "+---+-------+",
+            "| a | 6     |",
+            "| b | 5     |",
+            "| c | 4     |",
+            "+---+-------+",
+        ];
+        assert_batches_eq!(expected, &results);
+
+        // the batches of the channels can't be read again
+        let err = ctx
+            .sql("SELECT * FROM t")
+            .await?
+            .collect()
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("The stream of this partition has already been consumed"),
+            "{}",
+            err
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn project_replayable_partitions() -> Result<()> {
+        let ctx = SessionContext::new();
+        let partition = Arc::new(ReplayablePartition {
+            schema: schema(),
+            batches: vec![batch(vec!["a", "b"], vec![1, 2])],
+        });
+        let table = StreamingTable::try_new(schema(), vec![partition])?;
+        ctx.register_table("t", Arc::new(table))?;
+
+        let df = ctx.sql("SELECT v FROM t").await?;
+        let plan = df.create_physical_plan().await?;
+        let plan = displayable(plan.as_ref()).indent().to_string();
+        assert!(plan.contains("ProjectionExec: expr=[v@1 as v]"), "{}", plan);
+        assert!(
+            plan.contains("StreamingTableExec: partitions=1"),
+            "{}",
+            plan
+        );
+
+        let expected = vec!["+---+", "| v |", "+---+", "| 1 |", "| 2 |", "+---+"];
+        for _ in 0..2 {
+            let results = ctx.sql("SELECT v FROM t").await?.collect().await?;
+            assert_batches_eq!(expected, &results);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn schema_mismatch() {
+        let other_schema =
+            Arc::new(Schema::new(vec![Field::new("k", DataType::Utf8, false)]));
+        let partition = Arc::new(ReplayablePartition {
+            schema: other_schema,
+            batches: vec![],
+        });
+        let err = StreamingTable::try_new(schema(), vec![partition])
+            .err()
+            .unwrap();
+        assert!(
+            err.to_string().contains("Mismatch between the schema"),
+            "{}",
+            err
+        );
+    }
+}
@@ -24,8 +24,8 @@ use crate::{
     datasource::listing::{ListingOptions, ListingTable},
     datasource::{
         file_format::{
-            avro::AvroFormat, csv::CsvFormat, json::JsonFormat, parquet::ParquetFormat,
-            FileFormat,
+            avro::AvroFormat, csv::CsvFormat, ipc::IpcFormat, json::JsonFormat,
+            parquet::ParquetFormat, FileFormat,
         },
         MemTable, ViewTable,
     },
@@ -33,7 +33,9 @@ use crate::{
     optimizer::optimizer::Optimizer,
     physical_optimizer::{
         aggregate_statistics::AggregateStatistics,
-        hash_build_probe_order::HashBuildProbeOrder, optimizer::PhysicalOptimizerRule,
+        hash_build_probe_order::HashBuildProbeOrder, json_count_only::JsonCountOnly,
+        json_partition_values_only::JsonPartitionValuesOnly,
+        optimizer::PhysicalOptimizerRule,
     },
 };
 pub use datafusion_physical_expr::execution_props::ExecutionProps;
@@ -41,6 +43,7 @@ use datafusion_physical_expr::var_provider::is_system_variables;
 use parking_lot::RwLock;
 use std::str::FromStr;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use std::{
     any::{Any, TypeId},
     hash::{BuildHasherDefault, Hasher},
@@ -51,7 +54,8 @@ use std::{
     fmt::Debug,
 };
 
-use arrow::datatypes::{DataType, SchemaRef};
+use arrow::array::StringArray;
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
 use arrow::record_batch::RecordBatch;
 
 use crate::catalog::{
@@ -76,13 +80,20 @@ use crate::physical_optimizer::coalesce_batches::CoalesceBatches;
 use crate::physical_optimizer::repartition::Repartition;
 
 use crate::config::{
-    ConfigOptions, OPT_BATCH_SIZE, OPT_COALESCE_BATCHES, OPT_COALESCE_TARGET_BATCH_SIZE,
-    OPT_FILTER_NULL_JOIN_KEYS, OPT_OPTIMIZER_MAX_PASSES, OPT_OPTIMIZER_SKIP_FAILED_RULES,
+    ConfigOptions, OPT_BATCH_SIZE, OPT_CHECKED_DIVISION, OPT_COALESCE_BATCHES,
+    OPT_COALESCE_TARGET_BATCH_SIZE, OPT_FILTER_NULL_JOIN_KEYS, OPT_LENIENT_ARITHMETIC,
+    OPT_OPTIMIZER_MAX_PASSES, OPT_OPTIMIZER_SKIP_FAILED_RULES, OPT_QUERY_TIMEOUT_MS,
+    OPT_REPARTITION_FILE_MIN_SIZE, OPT_REPARTITION_FILE_SCANS,
+    OPT_SKIP_PARTIAL_AGGREGATION_PROBE_RATIO_THRESHOLD,
+    OPT_SKIP_PARTIAL_AGGREGATION_PROBE_ROWS, OPT_WRAPPING_ARITHMETIC,
 };
 use crate::datasource::file_format::file_type::{FileCompressionType, FileType};
 use crate::execution::{runtime_env::RuntimeEnv, FunctionRegistry};
 use crate::physical_optimizer::enforcement::BasicEnforcement;
-use crate::physical_plan::file_format::{plan_to_csv, plan_to_json, plan_to_parquet};
+use crate::physical_plan::file_format::{
+    plan_to_csv, plan_to_ipc, plan_to_ipc_stream, plan_to_json, plan_to_parquet,
+    IpcWriteMode,
+};
 use crate::physical_plan::planner::DefaultPhysicalPlanner;
 use crate::physical_plan::udaf::AggregateUDF;
 use crate::physical_plan::udf::ScalarUDF;
@@ -104,7 +115,8 @@ use crate::datasource::object_store::ObjectStoreUrl;
 use uuid::Uuid;
 
 use super::options::{
-    AvroReadOptions, CsvReadOptions, NdJsonReadOptions, ParquetReadOptions,
+    AvroReadOptions, CsvReadOptions, IpcReadOptions, NdJsonReadOptions,
+    ParquetReadOptions,
 };
 
 /// The default catalog name - this impacts what SQL queries use if not specified
@@ -253,7 +265,7 @@ impl SessionContext {
         let plan = self.create_logical_plan(sql)?;
         match plan {
             LogicalPlan::CreateExternalTable(cmd) => match cmd.file_type.as_str() {
-                "PARQUET" | "CSV" | "JSON" | "AVRO" => {
+                "PARQUET" | "CSV" | "JSON" | "AVRO" | "ARROW" => {
                     self.create_listing_table(&cmd).await
                 }
                 _ => self.create_custom_table(&cmd).await,
@@ -264,29 +276,30 @@ impl SessionContext {
                 input,
                 if_not_exists,
                 or_replace,
+                temporary,
             }) => {
-                let table = self.table(name.as_str());
-
-                match (if_not_exists, or_replace, table) {
-                    (true, false, Ok(_)) => self.return_empty_dataframe(),
-                    (false, true, Ok(_)) => {
-                        self.deregister_table(name.as_str())?;
-                        let physical =
-                            Arc::new(DataFrame::new(self.state.clone(), &input));
-
-                        let batches: Vec<_> = physical.collect_partitioned().await?;
-                        let table = Arc::new(MemTable::try_new(
-                            Arc::new(input.schema().as_ref().into()),
-                            batches,
-                        )?);
+                // a temporary table may share the name of a table of the catalogs
+                let table_exists = if temporary {
+                    self.state.read().temp_tables.table_exist(&name)
+                } else {
+                    self.table_exist(name.as_str()).unwrap_or(false)
+                };
 
-                        self.register_table(name.as_str(), table)?;
-                        self.return_empty_dataframe()
-                    }
-                    (true, true, Ok(_)) => Err(DataFusionError::Internal(
+                match (if_not_exists, or_replace, table_exists) {
+                    (true, false, true) => self.return_empty_dataframe(),
+                    (true, true, true) => Err(DataFusionError::Internal(
                         "'IF NOT EXISTS' cannot coexist with 'REPLACE'".to_string(),
                     )),
-                    (_, _, Err(_)) => {
+                    (false, false, true) => Err(DataFusionError::Execution(format!(
+                        "Table '{:?}' already exists",
+                        name
+                    ))),
+                    (_, _, table_exists) => {
+                        if table_exists && temporary {
+                            self.deregister_temp_table(name.as_str())?;
+                        } else if table_exists {
+                            self.deregister_table(name.as_str())?;
+                        }
                         let physical =
                             Arc::new(DataFrame::new(self.state.clone(), &input));
 
@@ -296,13 +309,13 @@ impl SessionContext {
                             batches,
                         )?);
 
-                        self.register_table(name.as_str(), table)?;
+                        if temporary {
+                            self.register_temp_table(name.as_str(), table)?;
+                        } else {
+                            self.register_table(name.as_str(), table)?;
+                        }
                         self.return_empty_dataframe()
                     }
-                    (false, false, Ok(_)) => Err(DataFusionError::Execution(format!(
-                        "Table '{:?}' already exists",
-                        name
-                    ))),
                 }
             }
 
@@ -340,14 +353,59 @@ impl SessionContext {
             LogicalPlan::DropTable(DropTable {
                 name, if_exists, ..
             }) => {
-                let result = self.find_and_deregister(name.as_str(), TableType::Base);
+                // an unqualified name refers to the temporary table it shadows
+                // the tables of the catalogs with
+                let table_ref = TableReference::from(name.as_str());
+                if self.state.read().temp_table(table_ref).is_some() {
+                    self.deregister_temp_table(name.as_str())?;
+                    // the table it shadowed is visible again, which is
+                    // noticed rather than silently switched to
+                    let shadowed = self
+                        .state
+                        .read()
+                        .schema_for_ref(table_ref)
+                        .map(|schema| schema.table_exist(table_ref.table()))
+                        .unwrap_or(false);
+                    if !shadowed {
+                        return self.return_empty_dataframe();
+                    }
+                    let notice = format!(
+                        "Dropped the temporary table '{}'. Hint: the table '{}' it \
+                         shadowed still exists",
+                        name, name
+                    );
+                    let schema =
+                        Schema::new(vec![Field::new("notice", DataType::Utf8, false)]);
+                    let batch = RecordBatch::try_new(
+                        Arc::new(schema),
+                        vec![Arc::new(StringArray::from(vec![notice]))],
+                    )?;
+                    return self.read_batch(batch);
+                }
+
+                let result = self.find_and_deregister(table_ref, TableType::Base);
                 match (result, if_exists) {
                     (Ok(true), _) => self.return_empty_dataframe(),
                     (_, true) => self.return_empty_dataframe(),
-                    (_, _) => Err(DataFusionError::Execution(format!(
-                        "Table {:?} doesn't exist.",
-                        name
-                    ))),
+                    (_, _) => {
+                        let hint = if self
+                            .state
+                            .read()
+                            .temp_tables
+                            .table_exist(table_ref.table())
+                        {
+                            format!(
+                                " Hint: the temporary table '{}' is dropped with its unqualified name.",
+                                table_ref.table()
+                            )
+                        } else {
+                            String::new()
+                        };
+                        Err(DataFusionError::Execution(format!(
+                            "Table {:?} doesn't exist.{}",
+                            name, hint
+                        )))
+                    }
                 }
             }
 
@@ -399,6 +457,16 @@ impl SessionContext {
                         config_options.write().set_u64(&variable, new_value);
                     }
 
+                    ScalarValue::Float64(_) => {
+                        let new_value = value.parse::<f64>().map_err(|_| {
+                            DataFusionError::Execution(format!(
+                                "Failed to parse {} as f64",
+                                value,
+                            ))
+                        })?;
+                        config_options.write().set_f64(&variable, new_value);
+                    }
+
                     ScalarValue::Utf8(_) => {
                         let new_value = value.parse::<String>().map_err(|_| {
                             DataFusionError::Execution(format!(
@@ -423,21 +491,21 @@ impl SessionContext {
                 if_not_exists,
                 ..
             }) => {
-                // sqlparser doesnt accept database / catalog as parameter to CREATE SCHEMA
-                // so for now, we default to default catalog
+                // a schema name without a catalog is created in the default catalog
+                let default_catalog = self.copied_config().default_catalog;
                 let tokens: Vec<&str> = schema_name.split('.').collect();
-                let (catalog, schema_name) = match tokens.len() {
-                    1 => Ok((DEFAULT_CATALOG, schema_name.as_str())),
+                let (catalog_name, schema_name) = match tokens.len() {
+                    1 => Ok((default_catalog.as_str(), schema_name.as_str())),
                     2 => Ok((tokens[0], tokens[1])),
                     _ => Err(DataFusionError::Execution(format!(
                         "Unable to parse catalog from {}",
                         schema_name
                     ))),
                 }?;
-                let catalog = self.catalog(catalog).ok_or_else(|| {
+                let catalog = self.catalog(catalog_name).ok_or_else(|| {
                     DataFusionError::Execution(format!(
                         "Missing '{}' catalog",
-                        DEFAULT_CATALOG
+                        catalog_name
                     ))
                 })?;
 
@@ -543,6 +611,7 @@ impl SessionContext {
             ),
             FileType::PARQUET => Arc::new(ParquetFormat::default()),
             FileType::AVRO => Arc::new(AvroFormat::default()),
+            FileType::ARROW => Arc::new(IpcFormat::default()),
             FileType::JSON => Arc::new(
                 JsonFormat::default().with_file_compression_type(file_compression_type),
             ),
@@ -558,13 +627,11 @@ impl SessionContext {
                 } else {
                     Some(Arc::new(cmd.schema.as_ref().to_owned().into()))
                 };
-                let options = ListingOptions {
-                    format: file_format,
-                    collect_stat: self.copied_config().collect_statistics,
-                    file_extension: file_extension.to_owned(),
-                    target_partitions: self.copied_config().target_partitions,
-                    table_partition_cols: cmd.table_partition_cols.clone(),
-                };
+                let options = ListingOptions::new(file_format)
+                    .with_file_extension(file_extension)
+                    .with_table_partition_cols(cmd.table_partition_cols.clone())
+                    .with_collect_stat(self.copied_config().collect_statistics)
+                    .with_target_partitions(self.copied_config().target_partitions);
                 self.register_listing_table(
                     cmd.name.as_str(),
                     cmd.location.clone(),
@@ -632,6 +699,16 @@ impl SessionContext {
             .add_var_provider(variable_type, provider);
     }
 
+    /// Fixes the time that `now()`, `current_date()` and `current_time()`
+    /// are evaluated at for every query executed from now on, or lets it be
+    /// the time each query starts again if `None`
+    pub fn set_query_execution_start_time(&self, start_time: Option<DateTime<Utc>>) {
+        self.state
+            .write()
+            .execution_props
+            .fix_query_execution_start_time(start_time);
+    }
+
     /// Registers a scalar UDF within this context.
     ///
     /// Note in SQL queries, function names are looked up using
@@ -698,6 +775,33 @@ impl SessionContext {
 
         let listing_options = options.to_listing_options(target_partitions);
 
+        let resolved_schema = match options.schema {
+            Some(s) => options.scan_options.apply_to_schema(s)?,
+            None => {
+                listing_options
+                    .infer_schema(&self.state(), &table_path)
+                    .await?
+            }
+        };
+        let config = ListingTableConfig::new(table_path)
+            .with_listing_options(listing_options)
+            .with_schema(resolved_schema);
+        let provider = ListingTable::try_new(config)?;
+
+        self.read_table(Arc::new(provider))
+    }
+
+    /// Creates a [`DataFrame`] for reading an Arrow IPC data source.
+    pub async fn read_ipc(
+        &self,
+        table_path: impl AsRef<str>,
+        options: IpcReadOptions<'_>,
+    ) -> Result<Arc<DataFrame>> {
+        let table_path = ListingTableUrl::parse(table_path)?;
+        let target_partitions = self.copied_config().target_partitions;
+
+        let listing_options = options.to_listing_options(target_partitions);
+
         let resolved_schema = match options.schema {
             Some(s) => s,
             None => {
@@ -847,6 +951,11 @@ impl SessionContext {
 
     /// Registers a Json file as a table that it can be referenced
     /// from SQL statements executed against this context.
+    ///
+    /// `table_path` may also be a directory, whose files with the extension
+    /// of `options` are read, including compressed ones such as `.json.gz`
+    /// unless a compression type is set. The schema is inferred from the
+    /// files unless provided in `options`.
     pub async fn register_json(
         &self,
         name: &str,
@@ -855,15 +964,13 @@ impl SessionContext {
     ) -> Result<()> {
         let listing_options =
             options.to_listing_options(self.copied_config().target_partitions);
+        let schema = options
+            .schema
+            .map(|schema| options.scan_options.apply_to_schema(schema))
+            .transpose()?;
 
-        self.register_listing_table(
-            name,
-            table_path,
-            listing_options,
-            options.schema,
-            None,
-        )
-        .await?;
+        self.register_listing_table(name, table_path, listing_options, schema, None)
+            .await?;
         Ok(())
     }
 
@@ -910,6 +1017,28 @@ impl SessionContext {
         Ok(())
     }
 
+    /// Registers an Arrow IPC file as a table that can be referenced from
+    /// SQL statements executed against this context.
+    pub async fn register_ipc(
+        &self,
+        name: &str,
+        table_path: &str,
+        options: IpcReadOptions<'_>,
+    ) -> Result<()> {
+        let listing_options =
+            options.to_listing_options(self.copied_config().target_partitions);
+
+        self.register_listing_table(
+            name,
+            table_path,
+            listing_options,
+            options.schema,
+            None,
+        )
+        .await?;
+        Ok(())
+    }
+
     /// Registers a named catalog using a custom `CatalogProvider` so that
     /// it can be referenced from SQL statements executed against this
     /// context.
@@ -978,6 +1107,43 @@ impl SessionContext {
             .deregister_table(table_ref.table())
     }
 
+    /// Registers a temporary [`TableProvider`] named `name`, only visible to
+    /// this session, which shadows the table of the same name in the default
+    /// catalog and schema when referred to by its unqualified name.
+    ///
+    /// Temporary tables are dropped with the session, and are not listed in
+    /// `information_schema`.
+    ///
+    /// Returns the temporary table previously registered under `name`, if any
+    pub fn register_temp_table(
+        &self,
+        name: &str,
+        provider: Arc<dyn TableProvider>,
+    ) -> Result<Option<Arc<dyn TableProvider>>> {
+        let temp_tables = self.state.read().temp_tables.clone();
+        match TableReference::from(name) {
+            TableReference::Bare { table } => {
+                let previous = temp_tables.deregister_table(table)?;
+                temp_tables.register_table(table.to_owned(), provider)?;
+                Ok(previous)
+            }
+            _ => Err(DataFusionError::Plan(format!(
+                "The name of a temporary table can't be qualified by a schema or catalog, got '{}'",
+                name
+            ))),
+        }
+    }
+
+    /// Deregisters the temporary table named `name`.
+    ///
+    /// Returns the registered provider, if any
+    pub fn deregister_temp_table(
+        &self,
+        name: &str,
+    ) -> Result<Option<Arc<dyn TableProvider>>> {
+        self.state.read().temp_tables.deregister_table(name)
+    }
+
     /// Return true if the specified table exists in the schema provider.
     pub fn table_exist<'a>(
         &'a self,
@@ -1003,8 +1169,14 @@ impl SessionContext {
         table_ref: impl Into<TableReference<'a>>,
     ) -> Result<Arc<DataFrame>> {
         let table_ref = table_ref.into();
-        let schema = self.state.read().schema_for_ref(table_ref)?;
-        match schema.table(table_ref.table()) {
+        let provider = {
+            let state = self.state.read();
+            match state.temp_table(table_ref) {
+                Some(provider) => Some(provider),
+                None => state.schema_for_ref(table_ref)?.table(table_ref.table()),
+            }
+        };
+        match provider {
             Some(ref provider) => {
                 let plan = LogicalPlanBuilder::scan(
                     table_ref.table(),
@@ -1054,7 +1226,7 @@ impl SessionContext {
     ) -> Result<Arc<dyn ExecutionPlan>> {
         let state_cloned = {
             let mut state = self.state.write();
-            state.execution_props.start_execution();
+            state.start_execution();
 
             // We need to clone `state` to release the lock that is not `Send`. We could
             // make the lock `Send` by using `tokio::sync::Mutex`, but that would require to
@@ -1088,7 +1260,7 @@ impl SessionContext {
         path: impl AsRef<str>,
     ) -> Result<()> {
         let state = self.state.read().clone();
-        plan_to_json(&state, plan, path).await
+        plan_to_json(&state, plan, path, FileCompressionType::UNCOMPRESSED).await
     }
 
     /// Executes a query and writes the results to a partitioned Parquet file.
@@ -1102,6 +1274,29 @@ impl SessionContext {
         plan_to_parquet(&state, plan, path, writer_properties).await
     }
 
+    /// Executes a query and writes the results to Arrow IPC files, laid out
+    /// according to `mode`.
+    pub async fn write_ipc(
+        &self,
+        plan: Arc<dyn ExecutionPlan>,
+        path: impl AsRef<str>,
+        mode: IpcWriteMode,
+    ) -> Result<()> {
+        let state = self.state.read().clone();
+        plan_to_ipc(&state, plan, path, mode).await
+    }
+
+    /// Executes a query and writes the results to `writer` in the Arrow IPC
+    /// streaming format, as they are produced.
+    pub async fn write_ipc_stream<W: std::io::Write>(
+        &self,
+        plan: Arc<dyn ExecutionPlan>,
+        writer: W,
+    ) -> Result<()> {
+        let state = self.state.read().clone();
+        plan_to_ipc_stream(&state, plan, writer).await
+    }
+
     /// Get a new TaskContext to run in this session
     pub fn task_ctx(&self) -> Arc<TaskContext> {
         Arc::new(TaskContext::from(self))
@@ -1289,6 +1484,11 @@ impl SessionConfig {
         self.set(key, ScalarValue::UInt64(Some(value)))
     }
 
+    /// Set a generic `f64` configuration option
+    pub fn set_f64(self, key: &str, value: f64) -> Self {
+        self.set(key, ScalarValue::Float64(Some(value)))
+    }
+
     /// Set a generic `str` configuration option
     pub fn set_str(self, key: &str, value: &str) -> Self {
         self.set(key, ScalarValue::Utf8(Some(value.to_string())))
@@ -1362,6 +1562,127 @@ impl SessionConfig {
         self
     }
 
+    /// Enables or disables splitting the files of newline delimited JSON
+    /// scans into byte ranges read in parallel
+    pub fn with_repartition_file_scans(self, enabled: bool) -> Self {
+        self.set_bool(OPT_REPARTITION_FILE_SCANS, enabled)
+    }
+
+    /// Customize the minimum size in bytes of the files split into byte
+    /// ranges, smaller files being always read whole
+    pub fn with_repartition_file_min_size(self, size: usize) -> Self {
+        self.set_u64(OPT_REPARTITION_FILE_MIN_SIZE, size as u64)
+    }
+
+    /// Are the files of newline delimited JSON scans split into byte ranges
+    pub fn repartition_file_scans(&self) -> bool {
+        self.config_options
+            .read()
+            .get_bool(OPT_REPARTITION_FILE_SCANS)
+            .unwrap_or_default()
+    }
+
+    /// Enables or disables yielding null rather than an error for the
+    /// integer and floating point arithmetic that overflows or divides by zero
+    pub fn with_lenient_arithmetic(self, enabled: bool) -> Self {
+        self.set_bool(OPT_LENIENT_ARITHMETIC, enabled)
+    }
+
+    /// Does arithmetic that overflows or divides by zero yield null rather
+    /// than an error
+    pub fn lenient_arithmetic(&self) -> bool {
+        self.config_options
+            .read()
+            .get_bool(OPT_LENIENT_ARITHMETIC)
+            .unwrap_or_default()
+    }
+
+    /// Enables or disables wrapping around rather than failing on the
+    /// overflows of integer arithmetic and sums
+    pub fn with_wrapping_arithmetic(self, enabled: bool) -> Self {
+        self.set_bool(OPT_WRAPPING_ARITHMETIC, enabled)
+    }
+
+    /// Do integer arithmetic and sums wrap around rather than fail on overflow
+    pub fn wrapping_arithmetic(&self) -> bool {
+        self.config_options
+            .read()
+            .get_bool(OPT_WRAPPING_ARITHMETIC)
+            .unwrap_or_default()
+    }
+
+    /// Enables or disables failing rather than yielding null on the
+    /// division of a column by a column with zeros
+    pub fn with_checked_division(self, enabled: bool) -> Self {
+        self.set_bool(OPT_CHECKED_DIVISION, enabled)
+    }
+
+    /// Does dividing by zero fail rather than yield null
+    pub fn checked_division(&self) -> bool {
+        self.config_options
+            .read()
+            .get_bool(OPT_CHECKED_DIVISION)
+            .unwrap_or_default()
+    }
+
+    /// Customize the number of input rows a partial aggregation groups
+    /// before deciding whether to skip grouping the rest
+    pub fn with_skip_partial_aggregation_probe_rows(self, rows: usize) -> Self {
+        self.set_u64(OPT_SKIP_PARTIAL_AGGREGATION_PROBE_ROWS, rows as u64)
+    }
+
+    /// Customize the time after which the scans of a query fail rather than
+    /// read more data, counted from the creation of its [`TaskContext`]
+    pub fn with_query_timeout(self, timeout: Duration) -> Self {
+        self.set_u64(OPT_QUERY_TIMEOUT_MS, timeout.as_millis() as u64)
+    }
+
+    /// Get the time after which the scans of a query fail, if any
+    pub fn query_timeout(&self) -> Option<Duration> {
+        match self.config_options.read().get_u64(OPT_QUERY_TIMEOUT_MS) {
+            Some(0) | None => None,
+            Some(millis) => Some(Duration::from_millis(millis)),
+        }
+    }
+
+    /// Customize the ratio of groups to input rows from which a partial
+    /// aggregation skips grouping the rest of its input
+    pub fn with_skip_partial_aggregation_probe_ratio_threshold(
+        self,
+        threshold: f64,
+    ) -> Self {
+        self.set_f64(
+            OPT_SKIP_PARTIAL_AGGREGATION_PROBE_RATIO_THRESHOLD,
+            threshold,
+        )
+    }
+
+    /// Get the number of input rows a partial aggregation groups before
+    /// deciding whether to skip grouping the rest
+    pub fn skip_partial_aggregation_probe_rows(&self) -> usize {
+        self.config_options
+            .read()
+            .get_u64(OPT_SKIP_PARTIAL_AGGREGATION_PROBE_ROWS)
+            .unwrap_or_default() as usize
+    }
+
+    /// Get the ratio of groups to input rows from which a partial
+    /// aggregation skips grouping the rest of its input
+    pub fn skip_partial_aggregation_probe_ratio_threshold(&self) -> f64 {
+        self.config_options
+            .read()
+            .get_f64(OPT_SKIP_PARTIAL_AGGREGATION_PROBE_RATIO_THRESHOLD)
+            .unwrap_or(f64::INFINITY)
+    }
+
+    /// Get the minimum size in bytes of the files split into byte ranges
+    pub fn repartition_file_min_size(&self) -> usize {
+        self.config_options
+            .read()
+            .get_u64(OPT_REPARTITION_FILE_MIN_SIZE)
+            .unwrap_or_default() as usize
+    }
+
     /// Get the currently configured batch size
     pub fn batch_size(&self) -> usize {
         self.config_options
@@ -1502,6 +1823,10 @@ pub struct SessionState {
     pub query_planner: Arc<dyn QueryPlanner + Send + Sync>,
     /// Collection of catalogs containing schemas and ultimately TableProviders
     pub catalog_list: Arc<dyn CatalogList>,
+    /// The temporary tables of the session, which shadow the tables of the
+    /// catalogs for unqualified names. They are not part of `catalog_list`,
+    /// so sessions sharing it do not see them, nor does `information_schema`
+    pub temp_tables: Arc<dyn SchemaProvider>,
     /// Scalar functions that are registered with the context
     pub scalar_functions: HashMap<String, Arc<ScalarUDF>>,
     /// Aggregate functions registered in the context
@@ -1569,6 +1894,8 @@ impl SessionState {
 
         let mut physical_optimizers: Vec<Arc<dyn PhysicalOptimizerRule + Sync + Send>> = vec![
             Arc::new(AggregateStatistics::new()),
+            Arc::new(JsonCountOnly::new()),
+            Arc::new(JsonPartitionValuesOnly::new()),
             Arc::new(HashBuildProbeOrder::new()),
         ];
         physical_optimizers.push(Arc::new(BasicEnforcement::new()));
@@ -1578,15 +1905,14 @@ impl SessionState {
             .get_bool(OPT_COALESCE_BATCHES)
             .unwrap_or_default()
         {
-            physical_optimizers.push(Arc::new(CoalesceBatches::new(
-                config
-                    .config_options
-                    .read()
-                    .get_u64(OPT_COALESCE_TARGET_BATCH_SIZE)
-                    .unwrap_or_default()
-                    .try_into()
-                    .unwrap(),
-            )));
+            // the batches are coalesced to the session batch size by default
+            let target_batch_size = config
+                .config_options
+                .read()
+                .get_u64(OPT_COALESCE_TARGET_BATCH_SIZE)
+                .map(|target_batch_size| target_batch_size.try_into().unwrap())
+                .unwrap_or_else(|| config.batch_size());
+            physical_optimizers.push(Arc::new(CoalesceBatches::new(target_batch_size)));
         }
         physical_optimizers.push(Arc::new(Repartition::new()));
         physical_optimizers.push(Arc::new(BasicEnforcement::new()));
@@ -1598,6 +1924,7 @@ impl SessionState {
             physical_optimizers,
             query_planner: Arc::new(DefaultQueryPlanner {}),
             catalog_list,
+            temp_tables: Arc::new(MemorySchemaProvider::new()),
             scalar_functions: HashMap::new(),
             aggregate_functions: HashMap::new(),
             config,
@@ -1661,22 +1988,41 @@ impl SessionState {
         &'a self,
         table_ref: impl Into<TableReference<'a>>,
     ) -> Result<Arc<dyn SchemaProvider>> {
+        let table_ref = table_ref.into();
         let resolved_ref = self.resolve_table_ref(table_ref);
-        self.catalog_list
-            .catalog(resolved_ref.catalog)
-            .ok_or_else(|| {
-                DataFusionError::Plan(format!(
-                    "failed to resolve catalog: {}",
-                    resolved_ref.catalog
-                ))
-            })?
-            .schema(resolved_ref.schema)
-            .ok_or_else(|| {
-                DataFusionError::Plan(format!(
-                    "failed to resolve schema: {}",
-                    resolved_ref.schema
-                ))
-            })
+        let catalog =
+            self.catalog_list
+                .catalog(resolved_ref.catalog)
+                .ok_or_else(|| {
+                    let mut catalogs = self.catalog_list.catalog_names();
+                    catalogs.sort();
+                    DataFusionError::Plan(format!(
+                        "failed to resolve catalog: {}{}, the catalogs are: {}",
+                        resolved_ref.catalog,
+                        defaults_used(&table_ref),
+                        catalogs.join(", ")
+                    ))
+                })?;
+        catalog.schema(resolved_ref.schema).ok_or_else(|| {
+            let mut schemas = catalog.schema_names();
+            schemas.sort();
+            DataFusionError::Plan(format!(
+                "failed to resolve schema: {} in catalog '{}'{}, the schemas of the catalog are: {}",
+                resolved_ref.schema,
+                resolved_ref.catalog,
+                defaults_used(&table_ref),
+                schemas.join(", ")
+            ))
+        })
+    }
+
+    /// Returns the temporary table that `table_ref` refers to, if any, as
+    /// only unqualified names refer to temporary tables
+    fn temp_table(&self, table_ref: TableReference) -> Option<Arc<dyn TableProvider>> {
+        match table_ref {
+            TableReference::Bare { table } => self.temp_tables.table(table),
+            _ => None,
+        }
     }
 
     /// Replace the default query planner
@@ -1724,6 +2070,65 @@ impl SessionState {
         self
     }
 
+    /// Adds a new [`OptimizerRule`] to run right before the first rule
+    /// named `name`, returning an error if there is no such rule
+    pub fn add_optimizer_rule_before(
+        mut self,
+        name: &str,
+        optimizer_rule: Arc<dyn OptimizerRule + Send + Sync>,
+    ) -> Result<Self> {
+        let idx = rule_position(&self.optimizer.rules, name, |r| r.name())?;
+        self.optimizer.rules.insert(idx, optimizer_rule);
+        Ok(self)
+    }
+
+    /// Adds a new [`OptimizerRule`] to run right after the first rule
+    /// named `name`, returning an error if there is no such rule
+    pub fn add_optimizer_rule_after(
+        mut self,
+        name: &str,
+        optimizer_rule: Arc<dyn OptimizerRule + Send + Sync>,
+    ) -> Result<Self> {
+        let idx = rule_position(&self.optimizer.rules, name, |r| r.name())?;
+        self.optimizer.rules.insert(idx + 1, optimizer_rule);
+        Ok(self)
+    }
+
+    /// Adds a new [`PhysicalOptimizerRule`] to run right before the first
+    /// rule named `name`, returning an error if there is no such rule
+    pub fn add_physical_optimizer_rule_before(
+        mut self,
+        name: &str,
+        optimizer_rule: Arc<dyn PhysicalOptimizerRule + Send + Sync>,
+    ) -> Result<Self> {
+        let idx = rule_position(&self.physical_optimizers, name, |r| r.name())?;
+        self.physical_optimizers.insert(idx, optimizer_rule);
+        Ok(self)
+    }
+
+    /// Adds a new [`PhysicalOptimizerRule`] to run right after the first
+    /// rule named `name`, returning an error if there is no such rule
+    pub fn add_physical_optimizer_rule_after(
+        mut self,
+        name: &str,
+        optimizer_rule: Arc<dyn PhysicalOptimizerRule + Send + Sync>,
+    ) -> Result<Self> {
+        let idx = rule_position(&self.physical_optimizers, name, |r| r.name())?;
+        self.physical_optimizers.insert(idx + 1, optimizer_rule);
+        Ok(self)
+    }
+
+    /// Marks the start of the execution of a query, taking the properties
+    /// of its execution from the current configuration
+    pub fn start_execution(&mut self) {
+        self.execution_props.lenient_arithmetic = self.config.lenient_arithmetic();
+        self.execution_props.wrapping_arithmetic = self.config.wrapping_arithmetic();
+        self.execution_props.checked_division = self.config.checked_division();
+        self.execution_props.current_catalog = self.config.default_catalog.clone();
+        self.execution_props.current_schema = self.config.default_schema.clone();
+        self.execution_props.start_execution();
+    }
+
     /// Optimizes the logical plan by applying optimizer rules.
     pub fn optimize(&self, plan: &LogicalPlan) -> Result<LogicalPlan> {
         let mut optimizer_config = OptimizerConfig::new()
@@ -1743,6 +2148,13 @@ impl SessionState {
             )
             .with_query_execution_start_time(
                 self.execution_props.query_execution_start_time,
+            )
+            .with_lenient_arithmetic(self.config.lenient_arithmetic())
+            .with_wrapping_arithmetic(self.config.wrapping_arithmetic())
+            .with_checked_division(self.config.checked_division())
+            .with_current_catalog_and_schema(
+                &self.config.default_catalog,
+                &self.config.default_schema,
             );
 
         if let LogicalPlan::Explain(e) = plan {
@@ -1784,13 +2196,21 @@ impl SessionState {
 
 impl ContextProvider for SessionState {
     fn get_table_provider(&self, name: TableReference) -> Result<Arc<dyn TableSource>> {
+        if let Some(provider) = self.temp_table(name) {
+            return Ok(provider_as_source(provider));
+        }
         let resolved_ref = self.resolve_table_ref(name);
-        match self.schema_for_ref(resolved_ref) {
+        match self.schema_for_ref(name) {
             Ok(schema) => {
                 let provider = schema.table(resolved_ref.table).ok_or_else(|| {
                     DataFusionError::Plan(format!(
-                        "table '{}.{}.{}' not found",
-                        resolved_ref.catalog, resolved_ref.schema, resolved_ref.table
+                        "table '{}.{}.{}' not found, searched schema '{}' of catalog '{}'{}",
+                        resolved_ref.catalog,
+                        resolved_ref.schema,
+                        resolved_ref.table,
+                        resolved_ref.schema,
+                        resolved_ref.catalog,
+                        defaults_used(&name)
                     ))
                 })?;
                 Ok(provider_as_source(provider))
@@ -1879,6 +2299,8 @@ pub struct TaskContext {
     aggregate_functions: HashMap<String, Arc<AggregateUDF>>,
     /// Runtime environment associated with this task context
     runtime: Arc<RuntimeEnv>,
+    /// The instant past which the scans of this task fail
+    deadline: Option<Instant>,
 }
 
 impl TaskContext {
@@ -1898,9 +2320,23 @@ impl TaskContext {
             scalar_functions,
             aggregate_functions,
             runtime,
+            deadline: None,
         }
     }
 
+    /// Specify the instant past which the scans of this task fail with a
+    /// `ResourcesExhausted` error rather than read more data
+    pub fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Return the instant past which the scans of this task fail, set from
+    /// the query timeout of its session when created from one
+    pub fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+
     /// Return the SessionConfig associated with the Task
     pub fn session_config(&self) -> SessionConfig {
         let task_props = &self.properties;
@@ -1958,6 +2394,20 @@ impl TaskContext {
     }
 }
 
+/// Returns a `ResourcesExhausted` error if `deadline`, the
+/// [`TaskContext::deadline`] of a scan, is set and has passed
+pub(crate) fn check_deadline(deadline: Option<Instant>) -> Result<()> {
+    match deadline {
+        Some(deadline) if Instant::now() >= deadline => {
+            Err(DataFusionError::ResourcesExhausted(format!(
+                "Query deadline exceeded by {:?}",
+                Instant::now() - deadline
+            )))
+        }
+        _ => Ok(()),
+    }
+}
+
 /// Create a new task context instance from SessionContext
 impl From<&SessionContext> for TaskContext {
     fn from(session: &SessionContext) -> Self {
@@ -1971,6 +2421,9 @@ impl From<&SessionContext> for TaskContext {
             )
         };
         let runtime = session.runtime_env();
+        let deadline = config
+            .query_timeout()
+            .map(|timeout| Instant::now() + timeout);
         Self {
             task_id: None,
             session_id,
@@ -1978,6 +2431,7 @@ impl From<&SessionContext> for TaskContext {
             scalar_functions,
             aggregate_functions,
             runtime,
+            deadline,
         }
     }
 }
@@ -1990,6 +2444,9 @@ impl From<&SessionState> for TaskContext {
         let scalar_functions = state.scalar_functions.clone();
         let aggregate_functions = state.aggregate_functions.clone();
         let runtime = state.runtime_env.clone();
+        let deadline = config
+            .query_timeout()
+            .map(|timeout| Instant::now() + timeout);
         Self {
             task_id: None,
             session_id,
@@ -1997,6 +2454,7 @@ impl From<&SessionState> for TaskContext {
             scalar_functions,
             aggregate_functions,
             runtime,
+            deadline,
         }
     }
 }
@@ -2029,23 +2487,54 @@ impl FunctionRegistry for TaskContext {
     }
 }
 
+/// Returns the index of the first of `rules` named `name`
+fn rule_position<T: ?Sized>(
+    rules: &[Arc<T>],
+    name: &str,
+    rule_name: impl Fn(&T) -> &str,
+) -> Result<usize> {
+    rules
+        .iter()
+        .position(|rule| rule_name(rule.as_ref()) == name)
+        .ok_or_else(|| {
+            DataFusionError::Plan(format!("There is no optimizer rule named '{}'", name))
+        })
+}
+
+/// Describes which levels of `table_ref` were taken from the default catalog
+/// and schema, for name resolution errors
+fn defaults_used(table_ref: &TableReference) -> &'static str {
+    match table_ref {
+        TableReference::Bare { .. } => {
+            " (the default catalog and schema, as the name is unqualified)"
+        }
+        TableReference::Partial { .. } => {
+            " (the default catalog, as the name is only qualified by a schema)"
+        }
+        TableReference::Full { .. } => "",
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::assert_batches_eq;
+    use crate::assert_batches_sorted_eq;
     use crate::datasource::datasource::TableProviderFactory;
     use crate::datasource::listing_table_factory::ListingTableFactory;
+    use crate::datasource::MemTable;
     use crate::execution::context::QueryPlanner;
     use crate::execution::runtime_env::RuntimeConfig;
+    use crate::optimizer::optimize_children;
     use crate::physical_plan::expressions::AvgAccumulator;
     use crate::test;
     use crate::test_util::parquet_test_data;
     use crate::variable::VarType;
-    use arrow::array::ArrayRef;
+    use arrow::array::{as_string_array, ArrayRef, BooleanArray, Int32Array};
     use arrow::datatypes::*;
     use arrow::record_batch::RecordBatch;
     use async_trait::async_trait;
-    use datafusion_expr::{create_udaf, create_udf, Expr, Volatility};
+    use datafusion_expr::{create_udaf, create_udf, Expr, Filter, Volatility};
     use datafusion_physical_expr::functions::make_scalar_function;
     use std::fs::File;
     use std::path::PathBuf;
@@ -2339,6 +2828,74 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn custom_optimizer_rule_positions() -> Result<()> {
+        let runtime = Arc::new(RuntimeEnv::default());
+        let session_state = SessionState::with_config_rt(SessionConfig::new(), runtime)
+            .add_optimizer_rule_before("filter_push_down", Arc::new(NegateMarkerRule {}))?
+            .add_physical_optimizer_rule_after(
+                "repartition",
+                Arc::new(NoOpPhysicalRule {}),
+            )?;
+        let ctx = SessionContext::with_state(session_state);
+
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("marker", DataType::Boolean, false),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int32Array::from(vec![1, 2, 3])),
+                Arc::new(BooleanArray::from(vec![true, false, false])),
+            ],
+        )?;
+        let table = MemTable::try_new(schema, vec![vec![batch]])?;
+        ctx.register_table("t", Arc::new(table))?;
+
+        // the filter on the marker column has been negated
+        let results = plan_and_collect(&ctx, "SELECT a FROM t WHERE marker").await?;
+        let expected = vec!["+---+", "| a |", "+---+", "| 2 |", "| 3 |", "+---+"];
+        assert_batches_sorted_eq!(expected, &results);
+
+        // and the rules ran in their configured positions
+        let results =
+            plan_and_collect(&ctx, "EXPLAIN VERBOSE SELECT a FROM t WHERE marker")
+                .await?;
+        let plan_types: Vec<_> = results
+            .iter()
+            .flat_map(|batch| {
+                let plan_types = as_string_array(batch.column(0));
+                plan_types
+                    .iter()
+                    .map(|plan_type| plan_type.unwrap().to_string())
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        let idx = plan_types
+            .iter()
+            .position(|t| t == "logical_plan after negate_marker")
+            .unwrap();
+        assert_eq!(plan_types[idx + 1], "logical_plan after filter_push_down");
+        let idx = plan_types
+            .iter()
+            .position(|t| t == "physical_plan after no_op")
+            .unwrap();
+        assert_eq!(plan_types[idx - 1], "physical_plan after repartition");
+
+        // rules can only be positioned relative to existing ones
+        let err = SessionState::with_config_rt(SessionConfig::new(), ctx.runtime_env())
+            .add_optimizer_rule_after("no_such_rule", Arc::new(NegateMarkerRule {}))
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.to_string(),
+            "Error during planning: There is no optimizer rule named 'no_such_rule'"
+        );
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn disabled_default_catalog_and_schema() -> Result<()> {
         let ctx = SessionContext::with_config(
@@ -2458,6 +3015,101 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn same_named_tables_in_catalogs() -> Result<()> {
+        let ctx = SessionContext::with_config(
+            SessionConfig::new()
+                .with_information_schema(true)
+                .with_default_catalog_and_schema("cat2", "s"),
+        );
+        for (catalog_name, sequence_end) in [("cat1", 2), ("cat2", 3)] {
+            let catalog = MemoryCatalogProvider::new();
+            let schema = MemorySchemaProvider::new();
+            schema.register_table(
+                "t".to_owned(),
+                test::table_with_sequence(1, sequence_end)?,
+            )?;
+            catalog.register_schema("s", Arc::new(schema))?;
+            ctx.register_catalog(catalog_name, Arc::new(catalog));
+        }
+
+        for (table_ref, total) in [
+            ("cat1.s.t", "3"),
+            ("cat2.s.t", "6"),
+            ("s.t", "6"),
+            ("t", "6"),
+        ] {
+            let sql = format!("SELECT SUM(i) AS total FROM {}", table_ref);
+            let result = plan_and_collect(&ctx, &sql).await?;
+            let expected = vec![
+                "+-------+".to_string(),
+                "| total |".to_string(),
+                "+-------+".to_string(),
+                format!("| {}     |", total),
+                "+-------+".to_string(),
+            ];
+            assert_batches_eq!(expected, &result);
+        }
+
+        // the information schema lists the tables of both catalogs
+        let result = plan_and_collect(
+            &ctx,
+            "SELECT table_catalog, table_schema, table_name FROM information_schema.tables \
+             WHERE table_name = 't' ORDER BY table_catalog",
+        )
+        .await?;
+        let expected = vec![
+            "+---------------+--------------+------------+",
+            "| table_catalog | table_schema | table_name |",
+            "+---------------+--------------+------------+",
+            "| cat1          | s            | t          |",
+            "| cat2          | s            | t          |",
+            "+---------------+--------------+------------+",
+        ];
+        assert_batches_eq!(expected, &result);
+
+        // the errors state which levels were resolved from the defaults
+        let err = plan_and_collect(&ctx, "SELECT * FROM u").await.unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Error during planning: table 'cat2.s.u' not found, searched schema 's' \
+             of catalog 'cat2' (the default catalog and schema, as the name is unqualified)"
+        );
+        let err = plan_and_collect(&ctx, "SELECT * FROM x.t")
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Error during planning: failed to resolve schema: x in catalog 'cat2' \
+             (the default catalog, as the name is only qualified by a schema), \
+             the schemas of the catalog are: information_schema, s"
+        );
+        let err = plan_and_collect(&ctx, "SELECT * FROM cat3.s.t")
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Error during planning: failed to resolve catalog: cat3, \
+             the catalogs are: cat1, cat2"
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn sql_create_schema_in_default_catalog() -> Result<()> {
+        let ctx = SessionContext::with_config(
+            SessionConfig::new().with_default_catalog_and_schema("my_catalog", "public"),
+        );
+        ctx.register_catalog("datafusion", Arc::new(MemoryCatalogProvider::new()));
+
+        ctx.sql("CREATE SCHEMA abc").await?.collect().await?;
+
+        assert!(ctx.catalog("my_catalog").unwrap().schema("abc").is_some());
+        assert!(ctx.catalog("datafusion").unwrap().schema("abc").is_none());
+        Ok(())
+    }
+
     #[tokio::test]
     async fn catalogs_not_leaked() {
         // the information schema used to introduce cyclic Arcs
@@ -2530,6 +3182,94 @@ mod tests {
         Ok(())
     }
 
+    async fn count_rows(ctx: &SessionContext, table: &str) -> Result<usize> {
+        let sql = format!("SELECT * FROM {}", table);
+        let batches = ctx.sql(&sql).await?.collect().await?;
+        Ok(batches.iter().map(|batch| batch.num_rows()).sum())
+    }
+
+    #[tokio::test]
+    async fn temp_tables_shadow_catalog_tables() -> Result<()> {
+        let config = SessionConfig::new().with_information_schema(true);
+        let ctx = SessionContext::with_config(config.clone());
+        ctx.register_table("t", test::table_with_sequence(1, 2)?)?;
+        ctx.sql("CREATE TEMPORARY TABLE t AS VALUES (10), (20), (30)")
+            .await?
+            .collect()
+            .await?;
+
+        // the temporary table wins for the unqualified name
+        assert_eq!(count_rows(&ctx, "t").await?, 3);
+        assert_eq!(ctx.table("t")?.collect().await?[0].num_rows(), 3);
+        assert_eq!(count_rows(&ctx, "public.t").await?, 2);
+        assert_eq!(count_rows(&ctx, "datafusion.public.t").await?, 2);
+
+        // another session sharing the catalogs only sees the catalog table
+        let mut state = SessionState::with_config_rt(config, ctx.runtime_env());
+        state.catalog_list = ctx.state().catalog_list;
+        let other = SessionContext::with_state(state);
+        assert_eq!(count_rows(&other, "t").await?, 2);
+
+        // temporary tables are not listed in the information schema
+        for ctx in [&ctx, &other] {
+            let sql = "SELECT table_schema FROM information_schema.tables \
+                       WHERE table_name = 't'";
+            let batches = ctx.sql(sql).await?.collect().await?;
+            let expected = vec![
+                "+--------------+",
+                "| table_schema |",
+                "+--------------+",
+                "| public       |",
+                "+--------------+",
+            ];
+            assert_batches_eq!(expected, &batches);
+        }
+
+        // a qualified name refers to the catalog table only
+        ctx.register_temp_table("x", test::table_with_sequence(1, 1)?)?;
+        let err = ctx.sql("DROP TABLE datafusion.public.x").await.unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Execution error: Table \"datafusion.public.x\" doesn't exist. \
+             Hint: the temporary table 'x' is dropped with its unqualified name."
+        );
+
+        // DROP TABLE drops the temporary table first, noticing the table it
+        // shadowed
+        let batches = ctx.sql("DROP TABLE t").await?.collect().await?;
+        let expected = vec![
+            "+-------------------------------------------------------------------------------+",
+            "| notice                                                                        |",
+            "+-------------------------------------------------------------------------------+",
+            "| Dropped the temporary table 't'. Hint: the table 't' it shadowed still exists |",
+            "+-------------------------------------------------------------------------------+",
+        ];
+        assert_batches_eq!(expected, &batches);
+        assert_eq!(count_rows(&ctx, "t").await?, 2);
+        ctx.sql("DROP TABLE t").await?;
+        assert!(count_rows(&ctx, "t").await.is_err());
+
+        let err = ctx
+            .register_temp_table("public.y", test::table_with_sequence(1, 1)?)
+            .unwrap_err();
+        assert!(matches!(err, DataFusionError::Plan(_)), "{}", err);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn temp_tables_dropped_with_session() -> Result<()> {
+        let ctx = SessionContext::new();
+        let table = test::table_with_sequence(1, 1)?;
+        let table_weak = Arc::downgrade(&table);
+        ctx.register_temp_table("t", table)?;
+        assert_eq!(count_rows(&ctx, "t").await?, 1);
+
+        drop(ctx);
+        assert_eq!(Weak::strong_count(&table_weak), 0);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn read_with_glob_path() -> Result<()> {
         let ctx = SessionContext::new();
@@ -2625,6 +3365,49 @@ mod tests {
         }
     }
 
+    /// Negates filters on the column named `marker`
+    struct NegateMarkerRule {}
+
+    impl OptimizerRule for NegateMarkerRule {
+        fn optimize(
+            &self,
+            plan: &LogicalPlan,
+            optimizer_config: &mut OptimizerConfig,
+        ) -> Result<LogicalPlan> {
+            match plan {
+                LogicalPlan::Filter(filter) if matches!(filter.predicate(), Expr::Column(c) if c.name == "marker") =>
+                {
+                    let input = self.optimize(filter.input(), optimizer_config)?;
+                    Ok(LogicalPlan::Filter(Filter::try_new(
+                        Expr::Not(Box::new(filter.predicate().clone())),
+                        Arc::new(input),
+                    )?))
+                }
+                _ => optimize_children(self, plan, optimizer_config),
+            }
+        }
+
+        fn name(&self) -> &str {
+            "negate_marker"
+        }
+    }
+
+    struct NoOpPhysicalRule {}
+
+    impl PhysicalOptimizerRule for NoOpPhysicalRule {
+        fn optimize(
+            &self,
+            plan: Arc<dyn ExecutionPlan>,
+            _config: &SessionConfig,
+        ) -> Result<Arc<dyn ExecutionPlan>> {
+            Ok(plan)
+        }
+
+        fn name(&self) -> &str {
+            "no_op"
+        }
+    }
+
     /// Execute SQL and return results
     async fn plan_and_collect(
         ctx: &SessionContext,
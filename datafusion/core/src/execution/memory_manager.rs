@@ -292,7 +292,7 @@ impl MemoryManager {
         }
     }
 
-    fn get_tracker_total(&self) -> usize {
+    pub(crate) fn get_tracker_total(&self) -> usize {
         self.trackers_total.load(Ordering::SeqCst)
     }
 
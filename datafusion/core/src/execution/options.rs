@@ -20,19 +20,23 @@
 use std::sync::Arc;
 
 use arrow::datatypes::{Schema, SchemaRef};
+use regex::Regex;
 
 use crate::datasource::file_format::avro::DEFAULT_AVRO_EXTENSION;
 use crate::datasource::file_format::csv::DEFAULT_CSV_EXTENSION;
 use crate::datasource::file_format::file_type::FileCompressionType;
+use crate::datasource::file_format::ipc::DEFAULT_IPC_EXTENSION;
 use crate::datasource::file_format::json::DEFAULT_JSON_EXTENSION;
 use crate::datasource::file_format::parquet::DEFAULT_PARQUET_EXTENSION;
 use crate::datasource::file_format::DEFAULT_SCHEMA_INFER_MAX_RECORD;
 use crate::datasource::{
     file_format::{
-        avro::AvroFormat, csv::CsvFormat, json::JsonFormat, parquet::ParquetFormat,
+        avro::AvroFormat, csv::CsvFormat, ipc::IpcFormat, json::JsonFormat,
+        parquet::ParquetFormat,
     },
     listing::ListingOptions,
 };
+use crate::physical_plan::file_format::JsonScanOptions;
 
 /// Options that control the reading of CSV files.
 ///
@@ -145,13 +149,11 @@ impl<'a> CsvReadOptions<'a> {
             .with_schema_infer_max_rec(Some(self.schema_infer_max_records))
             .with_file_compression_type(self.file_compression_type.to_owned());
 
-        ListingOptions {
-            format: Arc::new(file_format),
-            collect_stat: false,
-            file_extension: self.file_extension.to_owned(),
-            target_partitions,
-            table_partition_cols: self.table_partition_cols.clone(),
-        }
+        ListingOptions::new(Arc::new(file_format))
+            .with_file_extension(self.file_extension)
+            .with_table_partition_cols(self.table_partition_cols.clone())
+            .with_collect_stat(false)
+            .with_target_partitions(target_partitions)
     }
 }
 
@@ -219,13 +221,11 @@ impl<'a> ParquetReadOptions<'a> {
             .with_enable_pruning(self.parquet_pruning)
             .with_skip_metadata(self.skip_metadata);
 
-        ListingOptions {
-            format: Arc::new(file_format),
-            collect_stat: true,
-            file_extension: self.file_extension.to_owned(),
-            target_partitions,
-            table_partition_cols: self.table_partition_cols.clone(),
-        }
+        ListingOptions::new(Arc::new(file_format))
+            .with_file_extension(self.file_extension)
+            .with_table_partition_cols(self.table_partition_cols.clone())
+            .with_collect_stat(true)
+            .with_target_partitions(target_partitions)
     }
 }
 
@@ -268,16 +268,68 @@ impl<'a> AvroReadOptions<'a> {
     pub fn to_listing_options(&self, target_partitions: usize) -> ListingOptions {
         let file_format = AvroFormat::default();
 
-        ListingOptions {
-            format: Arc::new(file_format),
-            collect_stat: false,
-            file_extension: self.file_extension.to_owned(),
-            target_partitions,
-            table_partition_cols: self.table_partition_cols.clone(),
+        ListingOptions::new(Arc::new(file_format))
+            .with_file_extension(self.file_extension)
+            .with_table_partition_cols(self.table_partition_cols.clone())
+            .with_collect_stat(false)
+            .with_target_partitions(target_partitions)
+    }
+}
+
+/// Options that control the reading of Arrow IPC files, in either the
+/// file (Feather v2) or the streaming format.
+///
+/// Note this structure is supplied when a datasource is created and
+/// can not not vary from statement to statement. For settings that
+/// can vary statement to statement see
+/// [`ConfigOptions`](crate::config::ConfigOptions).
+#[derive(Clone)]
+pub struct IpcReadOptions<'a> {
+    /// The data source schema.
+    pub schema: Option<SchemaRef>,
+
+    /// File extension; only files with this extension are selected for data input.
+    /// Defaults to `FileType::ARROW.get_ext().as_str()`.
+    pub file_extension: &'a str,
+    /// Partition Columns
+    pub table_partition_cols: Vec<String>,
+}
+
+impl<'a> Default for IpcReadOptions<'a> {
+    fn default() -> Self {
+        Self {
+            schema: None,
+            file_extension: DEFAULT_IPC_EXTENSION,
+            table_partition_cols: vec![],
         }
     }
 }
 
+impl<'a> IpcReadOptions<'a> {
+    /// Specify table_partition_cols for partition pruning
+    pub fn table_partition_cols(mut self, table_partition_cols: Vec<String>) -> Self {
+        self.table_partition_cols = table_partition_cols;
+        self
+    }
+
+    /// Specify file_extension
+    pub fn file_extension(mut self, file_extension: &'a str) -> Self {
+        self.file_extension = file_extension;
+        self
+    }
+
+    /// Helper to convert these user facing options to `ListingTable` options
+    pub fn to_listing_options(&self, target_partitions: usize) -> ListingOptions {
+        let file_format = IpcFormat::default();
+
+        ListingOptions::new(Arc::new(file_format))
+            .with_file_extension(self.file_extension)
+            .with_table_partition_cols(self.table_partition_cols.clone())
+            .with_collect_stat(false)
+            .with_target_partitions(target_partitions)
+    }
+}
+
 /// Options that control the reading of Line-delimited JSON files (NDJson)
 ///
 /// Note this structure is supplied when a datasource is created and
@@ -297,9 +349,18 @@ pub struct NdJsonReadOptions<'a> {
     pub file_extension: &'a str,
     /// Partition Columns
     pub table_partition_cols: Vec<String>,
-
-    /// File compression type
-    pub file_compression_type: FileCompressionType,
+    /// Regex extracting the partition columns from the path of the files,
+    /// see [`ListingOptions::partition_regex`]
+    pub partition_regex: Option<Regex>,
+
+    /// File compression type, used for every file. Defaults to `None`,
+    /// detecting the compression of each file from its extension such as
+    /// `.gz`, in which case the files with `file_extension` followed by the
+    /// extension of a compression, such as `.json.gz`, are read as well.
+    pub file_compression_type: Option<FileCompressionType>,
+
+    /// Options controlling how the JSON lines are decoded
+    pub scan_options: JsonScanOptions,
 }
 
 impl<'a> Default for NdJsonReadOptions<'a> {
@@ -309,7 +370,9 @@ impl<'a> Default for NdJsonReadOptions<'a> {
             schema_infer_max_records: DEFAULT_SCHEMA_INFER_MAX_RECORD,
             file_extension: DEFAULT_JSON_EXTENSION,
             table_partition_cols: vec![],
-            file_compression_type: FileCompressionType::UNCOMPRESSED,
+            partition_regex: None,
+            file_compression_type: None,
+            scan_options: JsonScanOptions::default(),
         }
     }
 }
@@ -321,6 +384,13 @@ impl<'a> NdJsonReadOptions<'a> {
         self
     }
 
+    /// Specify the regex extracting the table_partition_cols from the
+    /// path of the files, instead of hive style `col=value` folders
+    pub fn partition_regex(mut self, partition_regex: Regex) -> Self {
+        self.partition_regex = Some(partition_regex);
+        self
+    }
+
     /// Specify file_extension
     pub fn file_extension(mut self, file_extension: &'a str) -> Self {
         self.file_extension = file_extension;
@@ -332,20 +402,35 @@ impl<'a> NdJsonReadOptions<'a> {
         mut self,
         file_compression_type: FileCompressionType,
     ) -> Self {
-        self.file_compression_type = file_compression_type;
+        self.file_compression_type = Some(file_compression_type);
+        self
+    }
+
+    /// Specify the schema
+    pub fn schema(mut self, schema: SchemaRef) -> Self {
+        self.schema = Some(schema);
+        self
+    }
+
+    /// Specify the options controlling how the JSON lines are decoded
+    pub fn scan_options(mut self, scan_options: JsonScanOptions) -> Self {
+        self.scan_options = scan_options;
         self
     }
 
     /// Helper to convert these user facing options to `ListingTable` options
     pub fn to_listing_options(&self, target_partitions: usize) -> ListingOptions {
-        let file_format = JsonFormat::default()
-            .with_file_compression_type(self.file_compression_type.to_owned());
-        ListingOptions {
-            format: Arc::new(file_format),
-            collect_stat: false,
-            file_extension: self.file_extension.to_owned(),
-            target_partitions,
-            table_partition_cols: self.table_partition_cols.clone(),
+        let mut file_format =
+            JsonFormat::default().with_scan_options(self.scan_options.clone());
+        if let Some(file_compression_type) = &self.file_compression_type {
+            file_format =
+                file_format.with_file_compression_type(file_compression_type.clone());
         }
+        ListingOptions::new(Arc::new(file_format))
+            .with_file_extension(self.file_extension)
+            .with_table_partition_cols(self.table_partition_cols.clone())
+            .with_collect_stat(false)
+            .with_target_partitions(target_partitions)
+            .with_partition_regex(self.partition_regex.clone())
     }
 }
@@ -34,6 +34,7 @@ use object_store::ObjectStore;
 use std::fmt::{Debug, Formatter};
 use std::path::PathBuf;
 use std::sync::Arc;
+use tokio::sync::Semaphore;
 use url::Url;
 
 #[derive(Clone)]
@@ -47,6 +48,9 @@ pub struct RuntimeEnv {
     pub object_store_registry: Arc<ObjectStoreRegistry>,
     /// TableProviderFactories
     pub table_factories: HashMap<String, Arc<dyn TableProviderFactory>>,
+    /// If set, the permits to read a batch of a file that file scans acquire,
+    /// bounding the files read at once across every scan in this environment
+    pub open_files: Option<Arc<Semaphore>>,
 }
 
 impl Debug for RuntimeEnv {
@@ -63,6 +67,7 @@ impl RuntimeEnv {
             disk_manager,
             object_store_registry,
             table_factories,
+            max_open_files,
         } = config;
 
         Ok(Self {
@@ -70,6 +75,7 @@ impl RuntimeEnv {
             disk_manager: DiskManager::try_new(disk_manager)?,
             object_store_registry,
             table_factories,
+            open_files: max_open_files.map(|max| Arc::new(Semaphore::new(max.max(1)))),
         })
     }
 
@@ -147,6 +153,8 @@ pub struct RuntimeConfig {
     pub object_store_registry: Arc<ObjectStoreRegistry>,
     /// Custom table factories for things like deltalake that are not part of core datafusion
     pub table_factories: HashMap<String, Arc<dyn TableProviderFactory>>,
+    /// The maximum number of files read at once across every file scan
+    pub max_open_files: Option<usize>,
 }
 
 impl RuntimeConfig {
@@ -199,4 +207,14 @@ impl RuntimeConfig {
     pub fn with_temp_file_path(self, path: impl Into<PathBuf>) -> Self {
         self.with_disk_manager(DiskManagerConfig::new_specified(vec![path.into()]))
     }
+
+    /// Specify the maximum number of files read at once across every file
+    /// scan, such as to stay within a low limit of file descriptors.
+    ///
+    /// A scan holds its permit while opening a file or reading a batch of it,
+    /// and releases it once the batch is produced.
+    pub fn with_max_open_files(mut self, max_open_files: usize) -> Self {
+        self.max_open_files = Some(max_open_files);
+        self
+    }
 }
@@ -0,0 +1,532 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! An [Arrow Flight] service serving the tables and query results of a
+//! [`SessionContext`]
+//!
+//! A flight is identified by a [`FlightDescriptor`] that is either
+//!
+//! * a command (`CMD`) containing the text of a SQL query, or
+//! * a path (`PATH`) naming a registered table, given as `[table]`,
+//!   `[schema, table]` or `[catalog, schema, table]`
+//!
+//! [`FlightSqlServer::get_flight_info`](FlightService::get_flight_info)
+//! returns the schema of a flight and a single endpoint whose ticket can be
+//! passed to [`FlightService::do_get`] to stream its record batches.
+//!
+//! ```no_run
+//! # use datafusion::flight::FlightSqlServer;
+//! # use datafusion::prelude::*;
+//! # use arrow_flight::flight_service_server::FlightServiceServer;
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let ctx = SessionContext::new();
+//! ctx.register_csv("example", "tests/example.csv", CsvReadOptions::new())
+//!     .await?;
+//!
+//! let service = FlightServiceServer::new(FlightSqlServer::new(ctx));
+//! tonic::transport::Server::builder()
+//!     .add_service(service)
+//!     .serve("0.0.0.0:50051".parse()?)
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [Arrow Flight]: https://arrow.apache.org/docs/format/Flight.html
+
+use std::pin::Pin;
+use std::sync::Arc;
+
+use arrow::datatypes::Schema;
+use arrow::ipc::writer::IpcWriteOptions;
+use arrow_flight::flight_descriptor::DescriptorType;
+use arrow_flight::flight_service_server::FlightService;
+use arrow_flight::utils::flight_data_from_arrow_batch;
+use arrow_flight::{
+    Action, ActionType, Criteria, Empty, FlightData, FlightDescriptor, FlightEndpoint,
+    FlightInfo, HandshakeRequest, HandshakeResponse, IpcMessage, PutResult, SchemaAsIpc,
+    SchemaResult, Ticket,
+};
+use futures::{Stream, StreamExt, TryStreamExt};
+use prost::Message;
+use tonic::{Request, Response, Status, Streaming};
+
+use crate::catalog::information_schema::INFORMATION_SCHEMA;
+use crate::dataframe::DataFrame;
+use crate::error::DataFusionError;
+use crate::execution::context::SessionContext;
+use crate::logical_expr::LogicalPlan;
+use crate::physical_plan::RecordBatchStream;
+use datafusion_sql::TableReference;
+
+type BoxedFlightStream<T> =
+    Pin<Box<dyn Stream<Item = Result<T, Status>> + Send + 'static>>;
+
+/// A [`FlightService`] that answers queries against the tables registered
+/// with a [`SessionContext`], see the [module level documentation](self)
+#[derive(Clone)]
+pub struct FlightSqlServer {
+    ctx: SessionContext,
+}
+
+impl FlightSqlServer {
+    /// Create a service answering queries using `ctx`
+    pub fn new(ctx: SessionContext) -> Self {
+        Self { ctx }
+    }
+
+    /// The context queries are answered with
+    pub fn context(&self) -> &SessionContext {
+        &self.ctx
+    }
+
+    /// Plans the query identified by `descriptor`, executing it right away
+    /// if it is a statement such as `CREATE TABLE`
+    async fn dataframe(
+        &self,
+        descriptor: &FlightDescriptor,
+    ) -> Result<Arc<DataFrame>, Status> {
+        match DescriptorType::from_i32(descriptor.r#type) {
+            Some(DescriptorType::Cmd) => {
+                let sql = std::str::from_utf8(&descriptor.cmd).map_err(|e| {
+                    Status::invalid_argument(format!("Invalid SQL command: {}", e))
+                })?;
+                self.ctx.sql(sql).await.map_err(to_status)
+            }
+            Some(DescriptorType::Path) => {
+                let table = match descriptor.path.as_slice() {
+                    [table] => TableReference::Bare {
+                        table: table.as_str(),
+                    },
+                    [schema, table] => TableReference::Partial {
+                        schema: schema.as_str(),
+                        table: table.as_str(),
+                    },
+                    [catalog, schema, table] => TableReference::Full {
+                        catalog: catalog.as_str(),
+                        schema: schema.as_str(),
+                        table: table.as_str(),
+                    },
+                    path => {
+                        return Err(Status::invalid_argument(format!(
+                            "Invalid table path {:?}, expected 1 to 3 elements",
+                            path
+                        )))
+                    }
+                };
+                self.ctx.table(table).map_err(to_status)
+            }
+            _ => Err(Status::invalid_argument(format!(
+                "Unsupported flight descriptor type {}",
+                descriptor.r#type
+            ))),
+        }
+    }
+
+    /// The schema of the results of the query identified by `descriptor`,
+    /// planned without being executed: statements such as `CREATE TABLE`
+    /// only take effect once the flight is fetched with
+    /// [`FlightService::do_get`]
+    async fn schema(&self, descriptor: &FlightDescriptor) -> Result<Schema, Status> {
+        match DescriptorType::from_i32(descriptor.r#type) {
+            Some(DescriptorType::Cmd) => {
+                let sql = std::str::from_utf8(&descriptor.cmd).map_err(|e| {
+                    Status::invalid_argument(format!("Invalid SQL command: {}", e))
+                })?;
+                let plan = self.ctx.create_logical_plan(sql).map_err(to_status)?;
+                Ok(match plan {
+                    // statements executed by `SessionContext::sql`, which
+                    // have no results
+                    LogicalPlan::CreateExternalTable(_)
+                    | LogicalPlan::CreateMemoryTable(_)
+                    | LogicalPlan::CreateView(_)
+                    | LogicalPlan::CreateCatalogSchema(_)
+                    | LogicalPlan::CreateCatalog(_)
+                    | LogicalPlan::DropTable(_)
+                    | LogicalPlan::DropView(_)
+                    | LogicalPlan::SetVariable(_) => Schema::empty(),
+                    plan => plan.schema().as_ref().into(),
+                })
+            }
+            _ => Ok(self.dataframe(descriptor).await?.schema().into()),
+        }
+    }
+
+    /// The [`FlightInfo`] of `descriptor`, whose single endpoint's ticket
+    /// is the encoded descriptor itself
+    fn flight_info(
+        descriptor: FlightDescriptor,
+        schema: &Schema,
+    ) -> Result<FlightInfo, Status> {
+        let options = IpcWriteOptions::default();
+        let message: IpcMessage = SchemaAsIpc::new(schema, &options)
+            .try_into()
+            .map_err(|e: arrow::error::ArrowError| Status::internal(e.to_string()))?;
+
+        let endpoint = FlightEndpoint {
+            ticket: Some(Ticket {
+                ticket: descriptor.encode_to_vec(),
+            }),
+            location: vec![],
+        };
+
+        Ok(FlightInfo::new(
+            message,
+            Some(descriptor),
+            vec![endpoint],
+            -1,
+            -1,
+        ))
+    }
+}
+
+#[tonic::async_trait]
+impl FlightService for FlightSqlServer {
+    type HandshakeStream = BoxedFlightStream<HandshakeResponse>;
+    type ListFlightsStream = BoxedFlightStream<FlightInfo>;
+    type DoGetStream = BoxedFlightStream<FlightData>;
+    type DoPutStream = BoxedFlightStream<PutResult>;
+    type DoActionStream = BoxedFlightStream<arrow_flight::Result>;
+    type ListActionsStream = BoxedFlightStream<ActionType>;
+    type DoExchangeStream = BoxedFlightStream<FlightData>;
+
+    async fn handshake(
+        &self,
+        _request: Request<Streaming<HandshakeRequest>>,
+    ) -> Result<Response<Self::HandshakeStream>, Status> {
+        Err(Status::unimplemented("handshake is not supported"))
+    }
+
+    /// Lists every table registered with the context as a `PATH` flight of
+    /// the form `[catalog, schema, table]`
+    async fn list_flights(
+        &self,
+        _request: Request<Criteria>,
+    ) -> Result<Response<Self::ListFlightsStream>, Status> {
+        let mut flights = vec![];
+        for catalog_name in self.ctx.catalog_names() {
+            let catalog = match self.ctx.catalog(&catalog_name) {
+                Some(catalog) => catalog,
+                None => continue,
+            };
+            for schema_name in catalog.schema_names() {
+                if schema_name == INFORMATION_SCHEMA {
+                    continue;
+                }
+                let schema = match catalog.schema(&schema_name) {
+                    Some(schema) => schema,
+                    None => continue,
+                };
+                for table_name in schema.table_names() {
+                    let table = match schema.table(&table_name) {
+                        Some(table) => table,
+                        None => continue,
+                    };
+                    let descriptor = FlightDescriptor::new_path(vec![
+                        catalog_name.clone(),
+                        schema_name.clone(),
+                        table_name,
+                    ]);
+                    flights.push(Self::flight_info(descriptor, &table.schema()));
+                }
+            }
+        }
+
+        let stream = futures::stream::iter(flights).boxed();
+        Ok(Response::new(stream))
+    }
+
+    async fn get_flight_info(
+        &self,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<FlightInfo>, Status> {
+        let descriptor = request.into_inner();
+        let schema = self.schema(&descriptor).await?;
+        Ok(Response::new(Self::flight_info(descriptor, &schema)?))
+    }
+
+    async fn get_schema(
+        &self,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<SchemaResult>, Status> {
+        let descriptor = request.into_inner();
+        let schema = self.schema(&descriptor).await?;
+
+        let options = IpcWriteOptions::default();
+        let schema_result = SchemaAsIpc::new(&schema, &options)
+            .try_into()
+            .map_err(|e: arrow::error::ArrowError| Status::internal(e.to_string()))?;
+        Ok(Response::new(schema_result))
+    }
+
+    /// Executes the flight whose descriptor is encoded in the ticket,
+    /// sending its schema followed by every record batch as it is produced
+    async fn do_get(
+        &self,
+        request: Request<Ticket>,
+    ) -> Result<Response<Self::DoGetStream>, Status> {
+        let ticket = request.into_inner();
+        let descriptor = FlightDescriptor::decode(ticket.ticket.as_slice())
+            .map_err(|e| Status::invalid_argument(format!("Invalid ticket: {}", e)))?;
+
+        let df = self.dataframe(&descriptor).await?;
+        let stream = df.execute_stream().await.map_err(to_status)?;
+
+        let options = IpcWriteOptions::default();
+        let schema = SchemaAsIpc::new(&stream.schema(), &options).into();
+
+        let batches = stream
+            .map_err(|e| to_status(e.into()))
+            .map_ok(move |batch| {
+                let (dictionaries, batch) =
+                    flight_data_from_arrow_batch(&batch, &options);
+                futures::stream::iter(
+                    dictionaries
+                        .into_iter()
+                        .chain(std::iter::once(batch))
+                        .map(Ok),
+                )
+            })
+            .try_flatten();
+
+        let output = futures::stream::once(async { Ok(schema) }).chain(batches);
+        Ok(Response::new(output.boxed()))
+    }
+
+    async fn do_put(
+        &self,
+        _request: Request<Streaming<FlightData>>,
+    ) -> Result<Response<Self::DoPutStream>, Status> {
+        Err(Status::unimplemented("do_put is not supported"))
+    }
+
+    async fn do_action(
+        &self,
+        _request: Request<Action>,
+    ) -> Result<Response<Self::DoActionStream>, Status> {
+        Err(Status::unimplemented("do_action is not supported"))
+    }
+
+    async fn list_actions(
+        &self,
+        _request: Request<Empty>,
+    ) -> Result<Response<Self::ListActionsStream>, Status> {
+        Ok(Response::new(futures::stream::empty().boxed()))
+    }
+
+    async fn do_exchange(
+        &self,
+        _request: Request<Streaming<FlightData>>,
+    ) -> Result<Response<Self::DoExchangeStream>, Status> {
+        Err(Status::unimplemented("do_exchange is not supported"))
+    }
+}
+
+fn to_status(e: DataFusionError) -> Status {
+    match e {
+        DataFusionError::Plan(_) | DataFusionError::SQL(_) => {
+            Status::invalid_argument(e.to_string())
+        }
+        DataFusionError::NotImplemented(_) => Status::unimplemented(e.to_string()),
+        e => Status::internal(e.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_batches_sorted_eq;
+    use crate::prelude::CsvReadOptions;
+    use arrow::datatypes::SchemaRef;
+    use arrow::record_batch::RecordBatch;
+    use arrow_flight::flight_service_client::FlightServiceClient;
+    use arrow_flight::flight_service_server::FlightServiceServer;
+    use arrow_flight::utils::flight_data_to_arrow_batch;
+    use std::collections::HashMap;
+    use tokio::net::TcpListener;
+    use tokio_stream::wrappers::TcpListenerStream;
+    use tonic::transport::{Channel, Server};
+
+    /// Serves a context with the `aggregate_test_100` table registered on an
+    /// ephemeral port, returning a client connected to it
+    async fn start_server() -> crate::error::Result<FlightServiceClient<Channel>> {
+        let ctx = SessionContext::new();
+        let testdata = crate::test_util::arrow_test_data();
+        ctx.register_csv(
+            "aggregate_test_100",
+            &format!("{}/csv/aggregate_test_100.csv", testdata),
+            CsvReadOptions::new(),
+        )
+        .await?;
+
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+        let service = FlightServiceServer::new(FlightSqlServer::new(ctx));
+        tokio::spawn(
+            Server::builder()
+                .add_service(service)
+                .serve_with_incoming(TcpListenerStream::new(listener)),
+        );
+
+        Ok(FlightServiceClient::connect(format!("http://{}", addr))
+            .await
+            .unwrap())
+    }
+
+    /// Fetches the results of the flight with `descriptor`
+    async fn fetch(
+        client: &mut FlightServiceClient<Channel>,
+        descriptor: FlightDescriptor,
+    ) -> (SchemaRef, Vec<RecordBatch>) {
+        let info = client
+            .get_flight_info(descriptor)
+            .await
+            .unwrap()
+            .into_inner();
+        let ticket = info.endpoint[0].ticket.clone().unwrap();
+
+        let schema_result = client
+            .get_schema(info.flight_descriptor.unwrap())
+            .await
+            .unwrap()
+            .into_inner();
+
+        let mut stream = client.do_get(ticket).await.unwrap().into_inner();
+        // the schema is the first message, followed by the record batches
+        let data = stream.message().await.unwrap().unwrap();
+        let schema = Arc::new(Schema::try_from(&data).unwrap());
+        assert_eq!(schema.as_ref(), &Schema::try_from(&schema_result).unwrap());
+
+        let dictionaries_by_id = HashMap::new();
+        let mut batches = vec![];
+        while let Some(data) = stream.message().await.unwrap() {
+            batches.push(
+                flight_data_to_arrow_batch(&data, schema.clone(), &dictionaries_by_id)
+                    .unwrap(),
+            );
+        }
+        (schema, batches)
+    }
+
+    #[tokio::test]
+    async fn do_get_sql_query() -> crate::error::Result<()> {
+        let mut client = start_server().await?;
+
+        let descriptor = FlightDescriptor::new_cmd(
+            "SELECT c1, count(*) AS n FROM aggregate_test_100 GROUP BY c1"
+                .as_bytes()
+                .to_vec(),
+        );
+        let (schema, batches) = fetch(&mut client, descriptor).await;
+        assert_eq!(schema.fields().len(), 2);
+
+        let expected = vec![
+            "+----+----+",
+            "| c1 | n  |",
+            "+----+----+",
+            "| a  | 21 |",
+            "| b  | 19 |",
+            "| c  | 21 |",
+            "| d  | 18 |",
+            "| e  | 21 |",
+            "+----+----+",
+        ];
+        assert_batches_sorted_eq!(expected, &batches);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn list_and_get_tables() -> crate::error::Result<()> {
+        let mut client = start_server().await?;
+
+        let flights: Vec<FlightInfo> = client
+            .list_flights(Criteria::default())
+            .await
+            .unwrap()
+            .into_inner()
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(flights.len(), 1);
+        let descriptor = flights[0].flight_descriptor.clone().unwrap();
+        assert_eq!(
+            descriptor.path,
+            vec!["datafusion", "public", "aggregate_test_100"]
+        );
+
+        let (schema, batches) = fetch(&mut client, descriptor).await;
+        assert_eq!(schema.fields().len(), 13);
+        let rows: usize = batches.iter().map(|batch| batch.num_rows()).sum();
+        assert_eq!(rows, 100);
+
+        let (_, batches) = fetch(
+            &mut client,
+            FlightDescriptor::new_path(vec!["aggregate_test_100".to_string()]),
+        )
+        .await;
+        let rows: usize = batches.iter().map(|batch| batch.num_rows()).sum();
+        assert_eq!(rows, 100);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn statements_are_only_executed_by_do_get() -> crate::error::Result<()> {
+        let mut client = start_server().await?;
+
+        // the metadata of a statement does not execute it, so that it is
+        // executed once by `do_get`
+        let (schema, batches) = fetch(
+            &mut client,
+            FlightDescriptor::new_cmd(
+                "CREATE TABLE t AS SELECT c1 FROM aggregate_test_100"
+                    .as_bytes()
+                    .to_vec(),
+            ),
+        )
+        .await;
+        assert_eq!(schema.fields().len(), 0);
+        assert!(batches.is_empty());
+
+        let (_, batches) = fetch(
+            &mut client,
+            FlightDescriptor::new_cmd("SELECT count(*) AS n FROM t".as_bytes().to_vec()),
+        )
+        .await;
+        let expected = vec!["+-----+", "| n   |", "+-----+", "| 100 |", "+-----+"];
+        assert_batches_sorted_eq!(expected, &batches);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn invalid_query() -> crate::error::Result<()> {
+        let mut client = start_server().await?;
+
+        let status = client
+            .get_flight_info(FlightDescriptor::new_cmd(
+                "SELECT * FROM missing".as_bytes().to_vec(),
+            ))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+
+        Ok(())
+    }
+}
@@ -171,6 +171,7 @@
 //! * Scan CSV: [`CsvExec`](physical_plan::file_format::CsvExec)
 //! * Scan Parquet: [`ParquetExec`](physical_plan::file_format::ParquetExec)
 //! * Scan Avro: [`AvroExec`](physical_plan::file_format::AvroExec)
+//! * Scan Arrow IPC: [`IpcExec`](physical_plan::file_format::IpcExec)
 //! * Scan newline-delimited JSON: [`NdJsonExec`](physical_plan::file_format::NdJsonExec)
 //! * Scan from memory: [`MemoryExec`](physical_plan::memory::MemoryExec)
 //! * Explain the plan: [`ExplainExec`](physical_plan::explain::ExplainExec)
@@ -221,6 +222,8 @@ pub mod dataframe;
 pub mod datasource;
 pub mod error;
 pub mod execution;
+#[cfg(feature = "flight")]
+pub mod flight;
 pub mod physical_optimizer;
 pub mod physical_plan;
 pub mod prelude;
@@ -73,3 +73,66 @@ impl PhysicalOptimizerRule for CoalesceBatches {
         "coalesce_batches"
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execution::context::SessionConfig;
+    use crate::logical_expr::{JoinType, Operator};
+    use crate::physical_plan::displayable;
+    use crate::physical_plan::expressions::{binary, col, lit, Column};
+    use crate::physical_plan::joins::utils::JoinOn;
+    use crate::physical_plan::joins::PartitionMode;
+    use crate::physical_plan::memory::MemoryExec;
+    use crate::physical_plan::{ExecutionPlan, Partitioning};
+    use arrow::datatypes::{DataType, Field, Schema};
+
+    fn memory_exec(name: &str) -> Arc<dyn ExecutionPlan> {
+        let schema =
+            Arc::new(Schema::new(vec![Field::new(name, DataType::UInt32, false)]));
+        Arc::new(MemoryExec::try_new(&[vec![]], schema, None).unwrap())
+    }
+
+    #[test]
+    fn coalesce_after_filters_joins_and_repartitions() -> Result<()> {
+        let left = memory_exec("a");
+        let predicate = binary(
+            col("a", &left.schema())?,
+            Operator::Lt,
+            lit(1u32),
+            &left.schema(),
+        )?;
+        let left = Arc::new(FilterExec::try_new(predicate, left)?);
+        let right = Arc::new(RepartitionExec::try_new(
+            memory_exec("b"),
+            Partitioning::RoundRobinBatch(4),
+        )?);
+        let on: JoinOn = vec![(Column::new("a", 0), Column::new("b", 0))];
+        let plan = Arc::new(HashJoinExec::try_new(
+            left,
+            right,
+            on,
+            None,
+            &JoinType::Inner,
+            PartitionMode::CollectLeft,
+            &false,
+        )?);
+
+        let optimized = CoalesceBatches::new(16).optimize(plan, &SessionConfig::new())?;
+        let expected = [
+            "CoalesceBatchesExec: target_batch_size=16",
+            "  HashJoinExec: mode=CollectLeft, join_type=Inner, on=[(Column { name: \"a\", index: 0 }, Column { name: \"b\", index: 0 })]",
+            "    CoalesceBatchesExec: target_batch_size=16",
+            "      FilterExec: a@0 < 1",
+            "        MemoryExec: partitions=1, partition_sizes=[0]",
+            "    CoalesceBatchesExec: target_batch_size=16",
+            "      RepartitionExec: partitioning=RoundRobinBatch(4)",
+            "        MemoryExec: partitions=1, partition_sizes=[0]",
+        ];
+        let actual = displayable(optimized.as_ref()).indent().to_string();
+        let actual = actual.lines().collect::<Vec<_>>();
+        assert_eq!(&expected[..], &actual[..], "\n\n{:#?}\n", actual);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,198 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Counting the records of JSON files for aggregates that only need row counts
+use std::sync::Arc;
+
+use super::optimizer::PhysicalOptimizerRule;
+use super::utils::{optimize_children, rewrite_json_scan};
+use crate::error::Result;
+use crate::execution::context::SessionConfig;
+use crate::physical_plan::aggregates::{AggregateExec, AggregateMode};
+use crate::physical_plan::file_format::NdJsonExec;
+use crate::physical_plan::{expressions, AggregateExpr, ExecutionPlan};
+
+/// Optimizer that makes the JSON scans feeding aggregates which only
+/// compute `COUNT(*)` count their records rather than decode them, unless
+/// their options may skip or reject lines, see
+/// [`NdJsonExec::get_count_only`](crate::physical_plan::file_format::NdJsonExec::get_count_only)
+#[derive(Default)]
+pub struct JsonCountOnly {}
+
+impl JsonCountOnly {
+    #[allow(missing_docs)]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl PhysicalOptimizerRule for JsonCountOnly {
+    fn optimize(
+        &self,
+        plan: Arc<dyn ExecutionPlan>,
+        config: &SessionConfig,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        if let Some(agg_exec) = plan.as_any().downcast_ref::<AggregateExec>() {
+            if agg_exec.mode() == &AggregateMode::Partial
+                && agg_exec.group_expr().is_empty()
+                && agg_exec
+                    .aggr_expr()
+                    .iter()
+                    .all(|expr| is_count_star(expr.as_ref()))
+            {
+                if let Some(input) =
+                    rewrite_json_scan(agg_exec.input(), &NdJsonExec::get_count_only)?
+                {
+                    return plan.with_new_children(vec![input]);
+                }
+            }
+        }
+        optimize_children(self, plan, config)
+    }
+
+    fn name(&self) -> &str {
+        "json_count_only"
+    }
+}
+
+/// Returns true if `agg_expr` is a count of a non null literal, such as the
+/// `COUNT(UInt8(1))` that `COUNT(*)` is planned as
+fn is_count_star(agg_expr: &dyn AggregateExpr) -> bool {
+    match agg_expr.as_any().downcast_ref::<expressions::Count>() {
+        Some(count) => match count.expressions().as_slice() {
+            [expr] => expr
+                .as_any()
+                .downcast_ref::<expressions::Literal>()
+                .map(|lit| !lit.value().is_null())
+                .unwrap_or(false),
+            _ => false,
+        },
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_batches_eq;
+    use crate::physical_plan::file_format::JsonScanOptions;
+    use crate::physical_plan::{collect, displayable};
+    use crate::prelude::{NdJsonReadOptions, SessionContext};
+    use arrow::datatypes::{DataType, Field, Schema};
+
+    /// Returns the JSON scans of `plan`
+    fn json_scans(plan: &Arc<dyn ExecutionPlan>) -> Vec<NdJsonExec> {
+        let mut scans: Vec<_> = plan.children().iter().flat_map(json_scans).collect();
+        if let Some(json_exec) = plan.as_any().downcast_ref::<NdJsonExec>() {
+            scans.push(json_exec.clone());
+        }
+        scans
+    }
+
+    fn decoded_arrays(scans: &[NdJsonExec]) -> usize {
+        scans
+            .iter()
+            .map(|scan| {
+                scan.metrics()
+                    .unwrap()
+                    .sum_by_name("decoded_arrays")
+                    .unwrap()
+                    .as_usize()
+            })
+            .sum()
+    }
+
+    #[tokio::test]
+    async fn count_star_only_counts_records() -> Result<()> {
+        let ctx = SessionContext::new();
+        ctx.register_json("t", "tests/jsons/2.json", NdJsonReadOptions::default())
+            .await?;
+
+        let df = ctx.sql("SELECT COUNT(*), COUNT(1) FROM t").await?;
+        let plan = df.create_physical_plan().await?;
+        let scans = json_scans(&plan);
+        assert_eq!(scans.len(), 1, "{}", displayable(plan.as_ref()).indent());
+        assert!(scans[0].scan_options().count_only);
+        assert!(scans[0].schema().fields().is_empty());
+
+        let batches = collect(plan.clone(), ctx.task_ctx()).await?;
+        let expected = vec![
+            "+-----------------+-----------------+",
+            "| COUNT(UInt8(1)) | COUNT(Int64(1)) |",
+            "+-----------------+-----------------+",
+            "| 12              | 12              |",
+            "+-----------------+-----------------+",
+        ];
+        assert_batches_eq!(expected, &batches);
+        assert_eq!(decoded_arrays(&json_scans(&plan)), 0);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn count_column_decodes_records() -> Result<()> {
+        let ctx = SessionContext::new();
+        ctx.register_json("t", "tests/jsons/2.json", NdJsonReadOptions::default())
+            .await?;
+
+        // counting the non null values of a column requires decoding it
+        let df = ctx.sql("SELECT COUNT(*), COUNT(a) FROM t").await?;
+        let plan = df.create_physical_plan().await?;
+        let scans = json_scans(&plan);
+        assert!(!scans[0].scan_options().count_only);
+
+        collect(plan.clone(), ctx.task_ctx()).await?;
+        assert!(decoded_arrays(&json_scans(&plan)) > 0);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn count_star_with_skipped_rows_decodes_records() -> Result<()> {
+        let tmp_dir = tempfile::TempDir::new()?;
+        let path = tmp_dir.path().join("invalid.json");
+        std::fs::write(&path, "{\"a\": 1}\nnot json\n{\"a\": 2}\n{\"a\": \n")?;
+
+        let ctx = SessionContext::new();
+        let schema = Schema::new(vec![Field::new("a", DataType::Int64, true)]);
+        let options = NdJsonReadOptions::default()
+            .schema(Arc::new(schema))
+            .scan_options(JsonScanOptions::new().with_skip_invalid_rows(true));
+        ctx.register_json("t", path.to_str().unwrap(), options)
+            .await?;
+
+        // counting the lines would count the invalid ones the scan skips
+        let df = ctx.sql("SELECT COUNT(*) FROM t").await?;
+        let plan = df.create_physical_plan().await?;
+        assert!(!json_scans(&plan)[0].scan_options().count_only);
+        let count = collect(plan, ctx.task_ctx()).await?;
+
+        let rows = ctx.sql("SELECT * FROM t").await?.collect().await?;
+        let rows: usize = rows.iter().map(|batch| batch.num_rows()).sum();
+        assert_eq!(rows, 2);
+        let expected = vec![
+            "+-----------------+",
+            "| COUNT(UInt8(1)) |",
+            "+-----------------+",
+            "| 2               |",
+            "+-----------------+",
+        ];
+        assert_batches_eq!(expected, &count);
+
+        Ok(())
+    }
+}
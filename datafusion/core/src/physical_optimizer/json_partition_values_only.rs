@@ -0,0 +1,97 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Answering queries of only the partition columns of JSON tables from the
+//! paths of their files
+use std::sync::Arc;
+
+use super::optimizer::PhysicalOptimizerRule;
+use super::utils::{optimize_children, rewrite_json_scan};
+use crate::error::Result;
+use crate::execution::context::SessionConfig;
+use crate::physical_plan::aggregates::{AggregateExec, AggregateMode};
+use crate::physical_plan::file_format::NdJsonExec;
+use crate::physical_plan::ExecutionPlan;
+
+/// Optimizer that makes the JSON scans of only partition columns feeding
+/// aggregates without aggregate functions, such as the one `SELECT DISTINCT`
+/// is planned as, produce a row per file holding records rather than read
+/// the files in full, unless their options may skip or reject lines
+#[derive(Default)]
+pub struct JsonPartitionValuesOnly {}
+
+impl JsonPartitionValuesOnly {
+    #[allow(missing_docs)]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl PhysicalOptimizerRule for JsonPartitionValuesOnly {
+    fn optimize(
+        &self,
+        plan: Arc<dyn ExecutionPlan>,
+        config: &SessionConfig,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        if let Some(agg_exec) = plan.as_any().downcast_ref::<AggregateExec>() {
+            // grouping is the same whether a file has one row or many
+            if agg_exec.mode() == &AggregateMode::Partial
+                && !agg_exec.group_expr().is_empty()
+                && agg_exec.aggr_expr().is_empty()
+            {
+                if let Some(input) =
+                    rewrite_json_scan(agg_exec.input(), &partition_values_only)?
+                {
+                    return plan.with_new_children(vec![input]);
+                }
+            }
+        }
+        optimize_children(self, plan, config)
+    }
+
+    fn name(&self) -> &str {
+        "json_partition_values_only"
+    }
+}
+
+/// Returns `json_exec` rewritten to only produce partition values, if it
+/// only projects partition columns and every line that is not blank or a
+/// comment is a record
+fn partition_values_only(json_exec: &NdJsonExec) -> Option<NdJsonExec> {
+    let base_config = json_exec.base_config();
+    let num_file_columns = base_config.file_schema.fields().len();
+    match &base_config.projection {
+        Some(projection)
+            if !projection.is_empty()
+                && projection.iter().all(|idx| *idx >= num_file_columns)
+                && json_exec.scan_options().can_count_records() =>
+        {
+            let scan_options = json_exec
+                .scan_options()
+                .clone()
+                .with_partition_values_only(true);
+            Some(
+                NdJsonExec::new(
+                    base_config.clone(),
+                    json_exec.file_compression_type().clone(),
+                )
+                .with_scan_options(scan_options),
+            )
+        }
+        _ => None,
+    }
+}
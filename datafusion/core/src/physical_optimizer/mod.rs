@@ -22,6 +22,8 @@ pub mod aggregate_statistics;
 pub mod coalesce_batches;
 pub mod enforcement;
 pub mod hash_build_probe_order;
+pub mod json_count_only;
+pub mod json_partition_values_only;
 pub mod merge_exec;
 pub mod optimizer;
 pub mod pruning;
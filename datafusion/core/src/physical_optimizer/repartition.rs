@@ -19,6 +19,7 @@
 use std::sync::Arc;
 
 use super::optimizer::PhysicalOptimizerRule;
+use crate::physical_plan::file_format::NdJsonExec;
 use crate::physical_plan::Partitioning::*;
 use crate::physical_plan::{
     repartition::RepartitionExec, with_new_children_if_necessary, ExecutionPlan,
@@ -148,11 +149,17 @@ impl Repartition {
 /// If 'would_benefit` is false, the upstream operator doesn't
 ///  benefit from additional repartition
 ///
+/// If `repartition_file_scans` is true, a newline delimited JSON scan is
+/// repartitioned by splitting its files of at least
+/// `repartition_file_min_size` bytes into byte ranges rather than by
+/// adding a `RepartitionExec`
 fn optimize_partitions(
     target_partitions: usize,
     plan: Arc<dyn ExecutionPlan>,
     can_reorder: bool,
     would_benefit: bool,
+    repartition_file_scans: bool,
+    repartition_file_min_size: usize,
 ) -> Result<Arc<dyn ExecutionPlan>> {
     // Recurse into children bottom-up (attempt to repartition as
     // early as possible)
@@ -190,6 +197,8 @@ fn optimize_partitions(
                     child.clone(),
                     can_reorder_children,
                     plan.benefits_from_input_partitioning(),
+                    repartition_file_scans,
+                    repartition_file_min_size,
                 )
             })
             .collect::<Result<_>>()?;
@@ -207,6 +216,15 @@ fn optimize_partitions(
     };
 
     if would_benefit && could_repartition && can_reorder {
+        if repartition_file_scans {
+            if let Some(json_exec) = new_plan.as_any().downcast_ref::<NdJsonExec>() {
+                if let Some(repartitioned) = json_exec
+                    .get_repartitioned(target_partitions, repartition_file_min_size)
+                {
+                    return Ok(Arc::new(repartitioned));
+                }
+            }
+        }
         Ok(Arc::new(RepartitionExec::try_new(
             new_plan,
             RoundRobinBatch(target_partitions),
@@ -226,7 +244,14 @@ impl PhysicalOptimizerRule for Repartition {
         if config.target_partitions == 1 {
             Ok(plan)
         } else {
-            optimize_partitions(config.target_partitions, plan, false, false)
+            optimize_partitions(
+                config.target_partitions,
+                plan,
+                false,
+                false,
+                config.repartition_file_scans(),
+                config.repartition_file_min_size(),
+            )
         }
     }
 
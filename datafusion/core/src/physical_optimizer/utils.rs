@@ -21,7 +21,10 @@ use super::optimizer::PhysicalOptimizerRule;
 use crate::execution::context::SessionConfig;
 
 use crate::error::Result;
-use crate::physical_plan::{with_new_children_if_necessary, ExecutionPlan};
+use crate::physical_plan::coalesce_batches::CoalesceBatchesExec;
+use crate::physical_plan::file_format::NdJsonExec;
+use crate::physical_plan::repartition::RepartitionExec;
+use crate::physical_plan::{with_new_children_if_necessary, ExecutionPlan, Partitioning};
 use std::sync::Arc;
 
 /// Convenience rule for writing optimizers: recursively invoke
@@ -45,3 +48,29 @@ pub fn optimize_children(
         with_new_children_if_necessary(plan, children)
     }
 }
+
+/// If `plan` is a JSON scan, possibly below operators that pass its batches
+/// through unchanged, returns it with the scan replaced by the result of
+/// `rewrite`, or `None` if there is no such scan or `rewrite` returns `None`
+pub(crate) fn rewrite_json_scan(
+    plan: &Arc<dyn ExecutionPlan>,
+    rewrite: &dyn Fn(&NdJsonExec) -> Option<NdJsonExec>,
+) -> Result<Option<Arc<dyn ExecutionPlan>>> {
+    if let Some(json_exec) = plan.as_any().downcast_ref::<NdJsonExec>() {
+        return Ok(rewrite(json_exec).map(|json_exec| Arc::new(json_exec) as _));
+    }
+
+    let passes_through = plan.as_any().is::<CoalesceBatchesExec>()
+        || plan
+            .as_any()
+            .downcast_ref::<RepartitionExec>()
+            .map(|exec| matches!(exec.partitioning(), Partitioning::RoundRobinBatch(_)))
+            .unwrap_or(false);
+    match plan.children().as_slice() {
+        [child] if passes_through => match rewrite_json_scan(child, rewrite)? {
+            Some(child) => Ok(Some(plan.clone().with_new_children(vec![child])?)),
+            None => Ok(None),
+        },
+        _ => Ok(None),
+    }
+}
@@ -29,7 +29,8 @@ use futures::{
 
 use crate::error::Result;
 use crate::physical_plan::aggregates::{
-    evaluate_group_by, evaluate_many, AccumulatorItem, AggregateMode, PhysicalGroupBy,
+    evaluate_group_by, evaluate_many, transform_to_states, AccumulatorItem,
+    AccumulatorMemory, AggregateMode, PhysicalGroupBy, SkipAggregationProbe,
 };
 use crate::physical_plan::hash_utils::create_hashes;
 use crate::physical_plan::metrics::{BaselineMetrics, RecordOutput};
@@ -85,6 +86,17 @@ pub(crate) struct GroupedHashAggregateStream {
 
     baseline_metrics: BaselineMetrics,
     random_state: RandomState,
+    /// The hashes of the group keys of the batch being aggregated, reused
+    /// across batches to avoid reallocating it
+    hashes_buffer: Vec<u64>,
+    /// size to be used for resulting RecordBatches
+    batch_size: usize,
+    /// The groups left to emit once the input is exhausted
+    emitter: Option<GroupEmitter>,
+    /// Decides whether a partial aggregation stops grouping its input
+    skip_aggregation_probe: Option<SkipAggregationProbe>,
+    /// Accounts for the memory the accumulators hold, if set
+    accumulator_memory: Option<AccumulatorMemory>,
     finished: bool,
 }
 
@@ -97,6 +109,7 @@ impl GroupedHashAggregateStream {
         aggr_expr: Vec<Arc<dyn AggregateExpr>>,
         input: SendableRecordBatchStream,
         baseline_metrics: BaselineMetrics,
+        batch_size: usize,
     ) -> Result<Self> {
         let timer = baseline_metrics.elapsed_compute().timer();
 
@@ -118,9 +131,29 @@ impl GroupedHashAggregateStream {
             aggregate_expressions,
             accumulators: Default::default(),
             random_state: Default::default(),
+            hashes_buffer: vec![],
+            batch_size,
+            emitter: None,
+            skip_aggregation_probe: None,
+            accumulator_memory: None,
             finished: false,
         })
     }
+
+    /// Pass the input rows on without grouping them once `probe` decides to
+    pub fn with_skip_aggregation_probe(
+        mut self,
+        probe: Option<SkipAggregationProbe>,
+    ) -> Self {
+        self.skip_aggregation_probe = probe;
+        self
+    }
+
+    /// Account for the memory the accumulators hold with `memory`
+    pub fn with_accumulator_memory(mut self, memory: AccumulatorMemory) -> Self {
+        self.accumulator_memory = Some(memory);
+        self
+    }
 }
 
 impl Stream for GroupedHashAggregateStream {
@@ -138,9 +171,49 @@ impl Stream for GroupedHashAggregateStream {
         let elapsed_compute = this.baseline_metrics.elapsed_compute();
 
         loop {
-            let result = match ready!(this.input.poll_next_unpin(cx)) {
+            if let Some(emitter) = &mut this.emitter {
+                let timer = elapsed_compute.timer();
+                let result = emitter.next_batch(
+                    &this.mode,
+                    this.group_by.expr.len(),
+                    &this.schema,
+                );
+                timer.done();
+
+                this.finished = !matches!(result, Ok(Some(_)));
+                return Poll::Ready(
+                    result
+                        .transpose()
+                        .map(|result| result.record_output(&this.baseline_metrics)),
+                );
+            }
+
+            match ready!(this.input.poll_next_unpin(cx)) {
                 Some(Ok(batch)) => {
                     let timer = elapsed_compute.timer();
+                    if let Some(probe) = this
+                        .skip_aggregation_probe
+                        .as_ref()
+                        .filter(|probe| probe.should_skip())
+                    {
+                        probe.record_skipped(batch.num_rows());
+                        let result = transform_to_states(
+                            &batch,
+                            &this.schema,
+                            &this.group_by,
+                            &this.aggr_expr,
+                            &this.aggregate_expressions,
+                        )
+                        .map_err(|e| ArrowError::ExternalError(Box::new(e)));
+                        timer.done();
+
+                        this.finished = result.is_err();
+                        return Poll::Ready(Some(
+                            result.record_output(&this.baseline_metrics),
+                        ));
+                    }
+
+                    let num_rows = batch.num_rows();
                     let result = group_aggregate_batch(
                         &this.mode,
                         &this.random_state,
@@ -149,34 +222,37 @@ impl Stream for GroupedHashAggregateStream {
                         batch,
                         &mut this.accumulators,
                         &this.aggregate_expressions,
+                        &mut this.hashes_buffer,
+                        this.accumulator_memory.as_ref(),
                     );
 
                     timer.done();
 
-                    match result {
-                        Ok(_) => continue,
-                        Err(e) => Err(ArrowError::ExternalError(Box::new(e))),
+                    if let Err(e) = result {
+                        this.finished = true;
+                        return Poll::Ready(Some(Err(ArrowError::ExternalError(
+                            Box::new(e),
+                        ))));
+                    }
+
+                    if let Some(probe) = &mut this.skip_aggregation_probe {
+                        probe
+                            .update_state(num_rows, this.accumulators.group_states.len());
                     }
                 }
-                Some(Err(e)) => Err(e),
-                None => {
+                Some(Err(e)) => {
                     this.finished = true;
-                    let timer = this.baseline_metrics.elapsed_compute().timer();
-                    let result = create_batch_from_map(
-                        &this.mode,
-                        &this.accumulators,
-                        this.group_by.expr.len(),
-                        &this.schema,
-                    )
-                    .record_output(&this.baseline_metrics);
-
-                    timer.done();
-                    result
+                    return Poll::Ready(Some(Err(e)));
                 }
-            };
-
-            this.finished = true;
-            return Poll::Ready(Some(result));
+                None => {
+                    // the hash table is no longer needed to emit the groups
+                    let accumulators = std::mem::take(&mut this.accumulators);
+                    this.emitter = Some(GroupEmitter::new(
+                        accumulators.group_states,
+                        this.batch_size,
+                    ));
+                }
+            }
         }
     }
 }
@@ -188,6 +264,7 @@ impl RecordBatchStream for GroupedHashAggregateStream {
 }
 
 /// TODO: Make this a member function of [`GroupedHashAggregateStream`]
+#[allow(clippy::too_many_arguments)]
 fn group_aggregate_batch(
     mode: &AggregateMode,
     random_state: &RandomState,
@@ -196,6 +273,8 @@ fn group_aggregate_batch(
     batch: RecordBatch,
     accumulators: &mut Accumulators,
     aggregate_expressions: &[Vec<Arc<dyn PhysicalExpr>>],
+    batch_hashes: &mut Vec<u64>,
+    memory: Option<&AccumulatorMemory>,
 ) -> Result<()> {
     // evaluate the grouping expressions
     let group_by_values = evaluate_group_by(group_by, &batch)?;
@@ -214,10 +293,11 @@ fn group_aggregate_batch(
         let mut groups_with_rows = vec![];
 
         // 1.1 Calculate the group keys for the group values
-        let mut batch_hashes = vec![0; batch.num_rows()];
-        create_hashes(&grouping_set_values, random_state, &mut batch_hashes)?;
+        batch_hashes.clear();
+        batch_hashes.resize(batch.num_rows(), 0);
+        create_hashes(&grouping_set_values, random_state, batch_hashes)?;
 
-        for (row, hash) in batch_hashes.into_iter().enumerate() {
+        for (row, hash) in batch_hashes.iter().copied().enumerate() {
             let Accumulators { map, group_states } = accumulators;
 
             let entry = map.get_mut(hash, |(_hash, group_idx)| {
@@ -267,94 +347,124 @@ fn group_aggregate_batch(
             };
         }
 
-        // Collect all indices + offsets based on keys in this vec
-        let mut batch_indices: UInt32Builder = UInt32Builder::with_capacity(0);
-        let mut offsets = vec![0];
-        let mut offset_so_far = 0;
-        for group_idx in groups_with_rows.iter() {
-            let indices = &accumulators.group_states[*group_idx].indices;
-            batch_indices.append_slice(indices);
-            offset_so_far += indices.len();
-            offsets.push(offset_so_far);
-        }
-        let batch_indices = batch_indices.finish();
+        update_group_states(
+            mode,
+            &mut accumulators.group_states,
+            &groups_with_rows,
+            &aggr_input_values,
+            memory,
+        )?;
+    }
 
-        // `Take` all values based on indices into Arrays
-        let values: Vec<Vec<Arc<dyn Array>>> = aggr_input_values
-            .iter()
-            .map(|array| {
-                array
-                    .iter()
-                    .map(|array| {
-                        compute::take(
-                            array.as_ref(),
-                            &batch_indices,
-                            None, // None: no index check
-                        )
-                        .unwrap()
-                    })
-                    .collect()
-                // 2.3
-            })
-            .collect();
-
-        // 2.1 for each key in this batch
-        // 2.2 for each aggregation
-        // 2.3 `slice` from each of its arrays the keys' values
-        // 2.4 update / merge the accumulator with the values
-        // 2.5 clear indices
-        groups_with_rows
-            .iter()
-            .zip(offsets.windows(2))
-            .try_for_each(|(group_idx, offsets)| {
-                let group_state = &mut accumulators.group_states[*group_idx];
-                // 2.2
-                group_state
-                    .accumulator_set
-                    .iter_mut()
-                    .zip(values.iter())
-                    .map(|(accumulator, aggr_array)| {
-                        (
-                            accumulator,
-                            aggr_array
-                                .iter()
-                                .map(|array| {
-                                    // 2.3
-                                    array.slice(offsets[0], offsets[1] - offsets[0])
-                                })
-                                .collect::<Vec<ArrayRef>>(),
-                        )
-                    })
-                    .try_for_each(|(accumulator, values)| match mode {
-                        AggregateMode::Partial => accumulator.update_batch(&values),
+    Ok(())
+}
+
+/// Updates the accumulators of the groups in `groups_with_rows` with the
+/// rows of the batch collected in their `indices`, then clears them, and
+/// accounts for the memory the accumulators grew by with `memory`
+pub(super) fn update_group_states(
+    mode: &AggregateMode,
+    group_states: &mut [GroupState],
+    groups_with_rows: &[usize],
+    aggr_input_values: &[Vec<ArrayRef>],
+    memory: Option<&AccumulatorMemory>,
+) -> Result<()> {
+    // Collect all indices + offsets based on keys in this vec
+    let mut batch_indices: UInt32Builder = UInt32Builder::with_capacity(0);
+    let mut offsets = vec![0];
+    let mut offset_so_far = 0;
+    for group_idx in groups_with_rows.iter() {
+        let indices = &group_states[*group_idx].indices;
+        batch_indices.append_slice(indices);
+        offset_so_far += indices.len();
+        offsets.push(offset_so_far);
+    }
+    let batch_indices = batch_indices.finish();
+
+    // `Take` all values based on indices into Arrays
+    let values: Vec<Vec<Arc<dyn Array>>> = aggr_input_values
+        .iter()
+        .map(|array| {
+            array
+                .iter()
+                .map(|array| {
+                    compute::take(
+                        array.as_ref(),
+                        &batch_indices,
+                        None, // None: no index check
+                    )
+                    .unwrap()
+                })
+                .collect()
+            // 2.3
+        })
+        .collect();
+
+    // 2.1 for each key in this batch
+    // 2.2 for each aggregation
+    // 2.3 `slice` from each of its arrays the keys' values
+    // 2.4 update / merge the accumulator with the values
+    // 2.5 clear indices
+    let (mut size_before, mut size_after) = (0, 0);
+    groups_with_rows
+        .iter()
+        .zip(offsets.windows(2))
+        .try_for_each(|(group_idx, offsets)| {
+            let group_state = &mut group_states[*group_idx];
+            // 2.2
+            group_state
+                .accumulator_set
+                .iter_mut()
+                .zip(values.iter())
+                .map(|(accumulator, aggr_array)| {
+                    (
+                        accumulator,
+                        aggr_array
+                            .iter()
+                            .map(|array| {
+                                // 2.3
+                                array.slice(offsets[0], offsets[1] - offsets[0])
+                            })
+                            .collect::<Vec<ArrayRef>>(),
+                    )
+                })
+                .try_for_each(|(accumulator, values)| {
+                    size_before += accumulator.size();
+                    match mode {
+                        AggregateMode::Partial => accumulator.update_batch(&values)?,
                         AggregateMode::FinalPartitioned | AggregateMode::Final => {
                             // note: the aggregation here is over states, not values, thus the merge
-                            accumulator.merge_batch(&values)
+                            accumulator.merge_batch(&values)?
                         }
-                    })
-                    // 2.5
-                    .and({
-                        group_state.indices.clear();
-                        Ok(())
-                    })
-            })?;
+                    }
+                    size_after += accumulator.size();
+                    Ok(())
+                })
+                // 2.5
+                .and({
+                    group_state.indices.clear();
+                    Ok(())
+                })
+        })?;
+
+    if let Some(memory) = memory {
+        memory.resize(size_before, size_after);
     }
-
     Ok(())
 }
 
 /// The state that is built for each output group.
 #[derive(Debug)]
-struct GroupState {
+pub(super) struct GroupState {
     /// The actual group by values, one for each group column
-    group_by_values: Box<[ScalarValue]>,
+    pub(super) group_by_values: Box<[ScalarValue]>,
 
     // Accumulator state, one for each aggregate
-    accumulator_set: Vec<AccumulatorItem>,
+    pub(super) accumulator_set: Vec<AccumulatorItem>,
 
     /// scratch space used to collect indices for input rows in a
     /// bach that have values to aggregate. Reset on each batch
-    indices: Vec<u32>,
+    pub(super) indices: Vec<u32>,
 }
 
 /// The state of all the groups
@@ -384,17 +494,60 @@ impl std::fmt::Debug for Accumulators {
     }
 }
 
+/// Emits the groups of an aggregation in batches of at most `batch_size`
+/// rows, in the order they were created. The state of the groups of every
+/// batch is released as soon as the batch is emitted, rather than once all
+/// of them are.
+pub(super) struct GroupEmitter {
+    group_states: vec::IntoIter<GroupState>,
+    batch_size: usize,
+    /// True once a batch has been emitted, as an aggregation without any
+    /// group still emits an empty batch
+    emitted: bool,
+}
+
+impl GroupEmitter {
+    pub(super) fn new(group_states: Vec<GroupState>, batch_size: usize) -> Self {
+        Self {
+            group_states: group_states.into_iter(),
+            batch_size: batch_size.max(1),
+            emitted: false,
+        }
+    }
+
+    /// Returns the batch of the next groups, or `None` once all of them
+    /// have been emitted
+    pub(super) fn next_batch(
+        &mut self,
+        mode: &AggregateMode,
+        num_group_expr: usize,
+        output_schema: &Schema,
+    ) -> ArrowResult<Option<RecordBatch>> {
+        let group_states = self
+            .group_states
+            .by_ref()
+            .take(self.batch_size)
+            .collect::<Vec<_>>();
+        if group_states.is_empty() && self.emitted {
+            return Ok(None);
+        }
+        self.emitted = true;
+        create_batch_from_map(mode, &group_states, num_group_expr, output_schema)
+            .map(Some)
+    }
+}
+
 /// Create a RecordBatch with all group keys and accumulator' states or values.
 fn create_batch_from_map(
     mode: &AggregateMode,
-    accumulators: &Accumulators,
+    group_states: &[GroupState],
     num_group_expr: usize,
     output_schema: &Schema,
 ) -> ArrowResult<RecordBatch> {
-    if accumulators.group_states.is_empty() {
+    if group_states.is_empty() {
         return Ok(RecordBatch::new_empty(Arc::new(output_schema.to_owned())));
     }
-    let accs = &accumulators.group_states[0].accumulator_set;
+    let accs = &group_states[0].accumulator_set;
     let mut acc_data_types: Vec<usize> = vec![];
 
     // Calculate number/shape of state arrays
@@ -426,23 +579,21 @@ fn create_batch_from_map(
         for y in 0..state_len {
             match mode {
                 AggregateMode::Partial => {
-                    let res = ScalarValue::iter_to_array(
-                        accumulators.group_states.iter().map(|group_state| {
+                    let res = ScalarValue::iter_to_array(group_states.iter().map(
+                        |group_state| {
                             group_state.accumulator_set[x]
                                 .state()
                                 .and_then(|x| x[y].as_scalar().map(|v| v.clone()))
                                 .expect("unexpected accumulator state in hash aggregate")
-                        }),
-                    )?;
+                        },
+                    ))?;
 
                     columns.push(res);
                 }
                 AggregateMode::Final | AggregateMode::FinalPartitioned => {
-                    let res = ScalarValue::iter_to_array(
-                        accumulators.group_states.iter().map(|group_state| {
-                            group_state.accumulator_set[x].evaluate().unwrap()
-                        }),
-                    )?;
+                    let res = ScalarValue::iter_to_array(group_states.iter().map(
+                        |group_state| group_state.accumulator_set[x].evaluate().unwrap(),
+                    ))?;
                     columns.push(res);
                 }
             }
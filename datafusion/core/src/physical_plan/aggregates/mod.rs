@@ -18,16 +18,22 @@
 //! Aggregates functionalities
 
 use crate::execution::context::TaskContext;
+use crate::execution::runtime_env::RuntimeEnv;
+use crate::execution::MemoryConsumerId;
 use crate::physical_plan::aggregates::hash::GroupedHashAggregateStream;
 use crate::physical_plan::aggregates::no_grouping::AggregateStream;
+use crate::physical_plan::aggregates::primitive_hash::{
+    primitive_group_supported, PrimitiveGroupedHashAggregateStream,
+};
 use crate::physical_plan::metrics::{
-    BaselineMetrics, ExecutionPlanMetricsSet, MetricsSet,
+    self, BaselineMetrics, ExecutionPlanMetricsSet, Gauge, MetricBuilder, MetricsSet,
 };
 use crate::physical_plan::{
     DisplayFormatType, Distribution, ExecutionPlan, Partitioning,
     SendableRecordBatchStream, Statistics,
 };
 use arrow::array::ArrayRef;
+use arrow::compute::concat_batches;
 use arrow::datatypes::{Field, Schema, SchemaRef};
 use arrow::record_batch::RecordBatch;
 use datafusion_common::Result;
@@ -43,6 +49,7 @@ use std::sync::Arc;
 
 mod hash;
 mod no_grouping;
+mod primitive_hash;
 mod row_hash;
 
 use crate::physical_plan::aggregates::row_hash::GroupedHashAggregateStreamV2;
@@ -50,7 +57,9 @@ use crate::physical_plan::EquivalenceProperties;
 pub use datafusion_expr::AggregateFunction;
 use datafusion_physical_expr::aggregate::row_accumulator::RowAccumulator;
 use datafusion_physical_expr::equivalence::project_equivalence_properties;
-pub use datafusion_physical_expr::expressions::create_aggregate_expr;
+pub use datafusion_physical_expr::expressions::{
+    create_aggregate_expr, create_aggregate_expr_with_wrapping,
+};
 use datafusion_physical_expr::normalize_out_expr_with_alias_schema;
 use datafusion_row::{row_supported, RowType};
 
@@ -256,11 +265,36 @@ impl AggregateExec {
         self.input_schema.clone()
     }
 
+    /// Returns true if the rows are grouped by a single column of a
+    /// primitive type, which [`PrimitiveGroupedHashAggregateStream`] groups
+    /// by its values rather than by [`ScalarValue`]s. Aggregations that are
+    /// [`Self::row_aggregate_supported`] keep using the row format
+    ///
+    /// [`ScalarValue`]: datafusion_common::ScalarValue
+    fn primitive_group_supported(&self) -> bool {
+        self.group_by.expr.len() == 1
+            && self.group_by.groups.len() == 1
+            && !self.group_by.contains_null()
+            && primitive_group_supported(self.schema.field(0).data_type())
+    }
+
     fn row_aggregate_supported(&self) -> bool {
         let group_schema = group_schema(&self.schema, self.group_by.expr.len());
         row_supported(&group_schema, RowType::Compact)
             && accumulator_v2_supported(&self.aggr_expr)
     }
+
+    /// Returns true if this partial aggregation can pass its input rows on
+    /// to the final aggregation without grouping them, see
+    /// [`SkipAggregationProbe`]
+    fn skip_partial_aggregation_supported(&self) -> bool {
+        self.mode == AggregateMode::Partial
+            && !self.group_by.expr.is_empty()
+            && self
+                .aggr_expr
+                .iter()
+                .all(|expr| expr.convert_to_state_supported())
+    }
 }
 
 impl ExecutionPlan for AggregateExec {
@@ -347,38 +381,76 @@ impl ExecutionPlan for AggregateExec {
         partition: usize,
         context: Arc<TaskContext>,
     ) -> Result<SendableRecordBatchStream> {
-        let batch_size = context.session_config().batch_size();
+        let config = context.session_config();
+        let batch_size = config.batch_size();
+        let skip_aggregation_probe =
+            self.skip_partial_aggregation_supported().then(|| {
+                SkipAggregationProbe::new(
+                    config.skip_partial_aggregation_probe_rows(),
+                    config.skip_partial_aggregation_probe_ratio_threshold(),
+                    MetricBuilder::new(&self.metrics)
+                        .counter("skipped_aggregation_rows", partition),
+                )
+            });
+        let runtime = context.runtime_env();
         let input = self.input.execute(partition, context)?;
 
         let baseline_metrics = BaselineMetrics::new(&self.metrics, partition);
+        let accumulator_memory =
+            AccumulatorMemory::new(partition, runtime, &baseline_metrics);
 
         if self.group_by.expr.is_empty() {
-            Ok(Box::pin(AggregateStream::new(
-                self.mode,
-                self.schema.clone(),
-                self.aggr_expr.clone(),
-                input,
-                baseline_metrics,
-            )?))
+            Ok(Box::pin(
+                AggregateStream::new(
+                    self.mode,
+                    self.schema.clone(),
+                    self.aggr_expr.clone(),
+                    input,
+                    baseline_metrics,
+                )?
+                .with_accumulator_memory(accumulator_memory),
+            ))
         } else if self.row_aggregate_supported() {
-            Ok(Box::pin(GroupedHashAggregateStreamV2::new(
-                self.mode,
-                self.schema.clone(),
-                self.group_by.clone(),
-                self.aggr_expr.clone(),
-                input,
-                baseline_metrics,
-                batch_size,
-            )?))
+            Ok(Box::pin(
+                GroupedHashAggregateStreamV2::new(
+                    self.mode,
+                    self.schema.clone(),
+                    self.group_by.clone(),
+                    self.aggr_expr.clone(),
+                    input,
+                    baseline_metrics,
+                    batch_size,
+                )?
+                .with_skip_aggregation_probe(skip_aggregation_probe),
+            ))
+        } else if self.primitive_group_supported() {
+            Ok(Box::pin(
+                PrimitiveGroupedHashAggregateStream::new(
+                    self.mode,
+                    self.schema.clone(),
+                    self.group_by.clone(),
+                    self.aggr_expr.clone(),
+                    input,
+                    baseline_metrics,
+                    batch_size,
+                )?
+                .with_skip_aggregation_probe(skip_aggregation_probe)
+                .with_accumulator_memory(accumulator_memory),
+            ))
         } else {
-            Ok(Box::pin(GroupedHashAggregateStream::new(
-                self.mode,
-                self.schema.clone(),
-                self.group_by.clone(),
-                self.aggr_expr.clone(),
-                input,
-                baseline_metrics,
-            )?))
+            Ok(Box::pin(
+                GroupedHashAggregateStream::new(
+                    self.mode,
+                    self.schema.clone(),
+                    self.group_by.clone(),
+                    self.aggr_expr.clone(),
+                    input,
+                    baseline_metrics,
+                    batch_size,
+                )?
+                .with_skip_aggregation_probe(skip_aggregation_probe)
+                .with_accumulator_memory(accumulator_memory),
+            ))
         }
     }
 
@@ -392,7 +464,7 @@ impl ExecutionPlan for AggregateExec {
         f: &mut std::fmt::Formatter,
     ) -> std::fmt::Result {
         match t {
-            DisplayFormatType::Default => {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
                 write!(f, "AggregateExec: mode={:?}", self.mode)?;
                 let g: Vec<String> = if self.group_by.groups.len() == 1 {
                     self.group_by
@@ -517,6 +589,109 @@ fn group_schema(schema: &Schema, group_count: usize) -> SchemaRef {
     Arc::new(Schema::new(group_fields))
 }
 
+/// Decides whether a partial aggregation keeps grouping its input rows.
+///
+/// When nearly every row is its own group, as for a near unique group key,
+/// the partial aggregation barely reduces the rows sent to the final
+/// aggregation while building a hash table as large as its input. Once
+/// `probe_rows_threshold` input rows were grouped, the ratio of groups to
+/// rows is compared to `probe_ratio_threshold` once and for all: if it
+/// reaches it, the remaining input rows are converted into the states of one
+/// group each by [`transform_to_states`], which the final aggregation merges
+/// like any other partial states.
+pub(crate) struct SkipAggregationProbe {
+    probe_rows_threshold: usize,
+    probe_ratio_threshold: f64,
+    input_rows: usize,
+    num_groups: usize,
+    /// Whether the decision was made, after which it no longer changes
+    is_locked: bool,
+    should_skip: bool,
+    /// The number of input rows passed on without grouping them
+    skipped_aggregation_rows: metrics::Count,
+}
+
+impl SkipAggregationProbe {
+    /// Create a new `SkipAggregationProbe`
+    pub(crate) fn new(
+        probe_rows_threshold: usize,
+        probe_ratio_threshold: f64,
+        skipped_aggregation_rows: metrics::Count,
+    ) -> Self {
+        Self {
+            probe_rows_threshold,
+            probe_ratio_threshold,
+            input_rows: 0,
+            num_groups: 0,
+            is_locked: false,
+            should_skip: false,
+            skipped_aggregation_rows,
+        }
+    }
+
+    /// Updates the probe after `input_rows` more rows were grouped, which
+    /// makes `num_groups` groups in total
+    pub(crate) fn update_state(&mut self, input_rows: usize, num_groups: usize) {
+        if self.is_locked {
+            return;
+        }
+        self.input_rows += input_rows;
+        self.num_groups = num_groups;
+        if self.input_rows >= self.probe_rows_threshold {
+            self.should_skip = self.num_groups as f64 / self.input_rows as f64
+                >= self.probe_ratio_threshold;
+            self.is_locked = true;
+        }
+    }
+
+    /// Returns true if the remaining input rows should not be grouped
+    pub(crate) fn should_skip(&self) -> bool {
+        self.should_skip
+    }
+
+    /// Records that the `rows` of a batch were passed on without grouping
+    /// them
+    pub(crate) fn record_skipped(&self, rows: usize) {
+        self.skipped_aggregation_rows.add(rows);
+    }
+}
+
+/// Converts every row of `batch` into the output of a partial aggregation
+/// for a group of its own per grouping set, with the states of
+/// [`AggregateExpr::convert_to_state`]
+fn transform_to_states(
+    batch: &RecordBatch,
+    schema: &SchemaRef,
+    group_by: &PhysicalGroupBy,
+    aggr_expr: &[Arc<dyn AggregateExpr>],
+    aggregate_expressions: &[Vec<Arc<dyn PhysicalExpr>>],
+) -> Result<RecordBatch> {
+    let group_by_values = evaluate_group_by(group_by, batch)?;
+    let aggr_input_values = evaluate_many(aggregate_expressions, batch)?;
+
+    let states = aggr_expr
+        .iter()
+        .zip(aggr_input_values.iter())
+        .map(|(expr, values)| expr.convert_to_state(values))
+        .collect::<Result<Vec<_>>>()?
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+
+    let batches = group_by_values
+        .into_iter()
+        .map(|group_values| {
+            let columns = group_values.into_iter().chain(states.iter().cloned());
+            RecordBatch::try_new(schema.clone(), columns.collect())
+        })
+        .collect::<arrow::error::Result<Vec<_>>>()?;
+
+    match batches.as_slice() {
+        [batch] => Ok(batch.clone()),
+        _ => Ok(concat_batches(schema, &batches)?),
+    }
+}
+
 /// returns physical expressions to evaluate against a batch
 /// The expressions are different depending on `mode`:
 /// * Partial: AggregateExpr::expressions
@@ -575,6 +750,50 @@ fn create_accumulators(
         .collect::<datafusion_common::Result<Vec<_>>>()
 }
 
+/// Registers the memory held by the accumulators of an aggregation, such as
+/// the values buffered by MEDIAN, with the memory manager as tracked usage,
+/// see [`Accumulator::size`], and records it in the `mem_used` metric. The
+/// memory is reclaimed when the aggregation is dropped.
+pub(crate) struct AccumulatorMemory {
+    id: MemoryConsumerId,
+    runtime: Arc<RuntimeEnv>,
+    mem_used: Gauge,
+}
+
+impl AccumulatorMemory {
+    pub(crate) fn new(
+        partition: usize,
+        runtime: Arc<RuntimeEnv>,
+        baseline_metrics: &BaselineMetrics,
+    ) -> Self {
+        Self {
+            id: MemoryConsumerId::new(partition),
+            runtime,
+            mem_used: baseline_metrics.mem_used().clone(),
+        }
+    }
+
+    /// Records that accumulators which held `before` bytes now hold `after`
+    pub(crate) fn resize(&self, before: usize, after: usize) {
+        if after > before {
+            self.runtime.grow_tracker_usage(after - before);
+            self.mem_used.add(after - before);
+        } else if before > after {
+            self.runtime.shrink_tracker_usage(before - after);
+            self.mem_used.sub(before - after);
+        }
+    }
+}
+
+impl Drop for AccumulatorMemory {
+    fn drop(&mut self) {
+        let mem_used = self.mem_used.value();
+        if mem_used != 0 {
+            self.runtime.drop_consumer(&self.id, mem_used);
+        }
+    }
+}
+
 fn accumulator_v2_supported(aggr_expr: &[Arc<dyn AggregateExpr>]) -> bool {
     aggr_expr
         .iter()
@@ -691,19 +910,29 @@ fn evaluate_group_by(
 mod tests {
     use crate::execution::context::TaskContext;
     use crate::from_slice::FromSlice;
+    use crate::physical_plan::aggregates::hash::GroupedHashAggregateStream;
+    use crate::physical_plan::aggregates::primitive_hash::PrimitiveGroupedHashAggregateStream;
+    use crate::physical_plan::aggregates::row_hash::GroupedHashAggregateStreamV2;
     use crate::physical_plan::aggregates::{
         AggregateExec, AggregateMode, PhysicalGroupBy,
     };
-    use crate::physical_plan::expressions::{col, Avg};
+    use crate::physical_plan::expressions::{col, Avg, Sum};
+    use crate::physical_plan::memory::MemoryExec;
+    use crate::physical_plan::metrics::{BaselineMetrics, ExecutionPlanMetricsSet};
     use crate::test::assert_is_pending;
     use crate::test::exec::{assert_strong_count_converges_to_zero, BlockingExec};
     use crate::{assert_batches_sorted_eq, physical_plan::common};
-    use arrow::array::{Float64Array, UInt32Array};
+    use arrow::array::{
+        ArrayRef, Date32Array, Float64Array, Int32Array, Int64Array, StringArray,
+        UInt32Array,
+    };
+    use arrow::compute::concat_batches;
     use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
     use arrow::error::Result as ArrowResult;
     use arrow::record_batch::RecordBatch;
+    use arrow::util::pretty::pretty_format_batches;
     use datafusion_common::{DataFusionError, Result, ScalarValue};
-    use datafusion_physical_expr::expressions::{lit, Count};
+    use datafusion_physical_expr::expressions::{lit, BitXor, Count, Max, Min};
     use datafusion_physical_expr::{AggregateExpr, PhysicalExpr, PhysicalSortExpr};
     use futures::{FutureExt, Stream};
     use std::any::Any;
@@ -715,7 +944,7 @@ mod tests {
         ExecutionPlan, Partitioning, RecordBatchStream, SendableRecordBatchStream,
         Statistics,
     };
-    use crate::prelude::SessionContext;
+    use crate::prelude::{SessionConfig, SessionContext};
 
     /// some mock data to aggregates
     fn some_data() -> (Arc<Schema>, Vec<RecordBatch>) {
@@ -1116,6 +1345,396 @@ mod tests {
         Ok(())
     }
 
+    /// Aggregates `keys` and `values`, split in two batches, both by the
+    /// primitive group path chosen by [`AggregateExec`] and by
+    /// [`GroupedHashAggregateStream`], and asserts that the results match
+    async fn assert_primitive_group_by(keys: ArrayRef, values: Vec<i64>) -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", keys.data_type().clone(), true),
+            Field::new("b", DataType::Int64, false),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![keys, Arc::new(Int64Array::from(values))],
+        )?;
+        let half = batch.num_rows() / 2;
+        let batches = vec![
+            batch.slice(0, half),
+            batch.slice(half, batch.num_rows() - half),
+        ];
+
+        let group_by =
+            PhysicalGroupBy::new_single(vec![(col("a", &schema)?, "a".to_string())]);
+        let aggregates: Vec<Arc<dyn AggregateExpr>> = vec![
+            Arc::new(Count::new(
+                col("b", &schema)?,
+                "COUNT(b)".to_string(),
+                DataType::Int64,
+            )),
+            Arc::new(BitXor::new(
+                col("b", &schema)?,
+                "BIT_XOR(b)",
+                DataType::Int64,
+            )),
+        ];
+
+        let session_ctx = SessionContext::new();
+        let task_ctx = session_ctx.task_ctx();
+        for partitions in [vec![batches], vec![vec![]]] {
+            let input = Arc::new(MemoryExec::try_new(&partitions, schema.clone(), None)?);
+            let exec = AggregateExec::try_new(
+                AggregateMode::Partial,
+                group_by.clone(),
+                aggregates.clone(),
+                input.clone(),
+                schema.clone(),
+            )?;
+            assert!(exec.primitive_group_supported());
+            assert!(!exec.row_aggregate_supported());
+            let actual = common::collect(exec.execute(0, task_ctx.clone())?).await?;
+
+            let generic = GroupedHashAggregateStream::new(
+                AggregateMode::Partial,
+                exec.schema(),
+                group_by.clone(),
+                aggregates.clone(),
+                input.execute(0, task_ctx.clone())?,
+                BaselineMetrics::new(&ExecutionPlanMetricsSet::new(), 0),
+                task_ctx.session_config().batch_size(),
+            )?;
+            let expected = common::collect(Box::pin(generic)).await?;
+
+            let sorted_lines = |batches: &[RecordBatch]| {
+                let formatted = pretty_format_batches(batches).unwrap().to_string();
+                let mut lines = formatted.lines().map(String::from).collect::<Vec<_>>();
+                lines.sort_unstable();
+                lines
+            };
+            assert_eq!(sorted_lines(&expected), sorted_lines(&actual));
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn primitive_group_by() -> Result<()> {
+        let values = vec![1, 2, 3, 4, 5, 6, 7, 8];
+
+        let int64_keys: ArrayRef = Arc::new(Int64Array::from(vec![
+            Some(i64::MIN),
+            None,
+            Some(i64::MAX),
+            Some(0),
+            Some(i64::MAX),
+            None,
+            Some(i64::MIN),
+            Some(-1),
+        ]));
+        assert_primitive_group_by(int64_keys.clone(), values.clone()).await?;
+
+        let keys: ArrayRef = Arc::new(Int32Array::from(vec![
+            Some(i32::MAX),
+            Some(i32::MIN),
+            None,
+            Some(i32::MAX),
+            Some(7),
+            Some(7),
+            None,
+            Some(i32::MIN),
+        ]));
+        assert_primitive_group_by(keys, values.clone()).await?;
+
+        let keys: ArrayRef = Arc::new(Date32Array::from(vec![
+            Some(19000),
+            None,
+            Some(-1),
+            Some(19000),
+            None,
+            Some(0),
+            Some(0),
+            Some(-1),
+        ]));
+        assert_primitive_group_by(keys, values.clone()).await?;
+
+        // the extreme keys and the null group are distinct groups
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int64, true),
+            Field::new("b", DataType::Int64, false),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![int64_keys, Arc::new(Int64Array::from(values))],
+        )?;
+        let input = Arc::new(MemoryExec::try_new(&[vec![batch]], schema.clone(), None)?);
+        let exec = Arc::new(AggregateExec::try_new(
+            AggregateMode::Partial,
+            PhysicalGroupBy::new_single(vec![(col("a", &schema)?, "a".to_string())]),
+            vec![Arc::new(Sum::new(
+                col("b", &schema)?,
+                "SUM(b)",
+                DataType::Int64,
+            ))],
+            input,
+            schema,
+        )?);
+        let session_ctx = SessionContext::new();
+        let result = common::collect(exec.execute(0, session_ctx.task_ctx())?).await?;
+        let expected = vec![
+            "+----------------------+--------+",
+            "| a                    | SUM(b) |",
+            "+----------------------+--------+",
+            "|                      | 8      |",
+            "| -1                   | 8      |",
+            "| -9223372036854775808 | 8      |",
+            "| 0                    | 4      |",
+            "| 9223372036854775807  | 8      |",
+            "+----------------------+--------+",
+        ];
+        assert_batches_sorted_eq!(expected, &result);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn emit_groups_in_batches() -> Result<()> {
+        let num_groups = 1_000_000;
+        let batch_size = 4096;
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int64, false),
+            Field::new("b", DataType::Int64, false),
+        ]));
+        let batches = (0..num_groups)
+            .step_by(100_000)
+            .map(|start| {
+                let keys = Int64Array::from_iter_values(start..start + 100_000);
+                let values =
+                    Int64Array::from_iter_values(keys.values().iter().map(|k| 2 * k));
+                RecordBatch::try_new(
+                    schema.clone(),
+                    vec![Arc::new(keys), Arc::new(values)],
+                )
+            })
+            .collect::<ArrowResult<Vec<_>>>()?;
+        let input = Arc::new(MemoryExec::try_new(&[batches], schema.clone(), None)?);
+
+        let group_by =
+            PhysicalGroupBy::new_single(vec![(col("a", &schema)?, "a".to_string())]);
+        let aggregates: Vec<Arc<dyn AggregateExpr>> = vec![Arc::new(Sum::new(
+            col("b", &schema)?,
+            "SUM(b)",
+            DataType::Int64,
+        ))];
+        let exec = AggregateExec::try_new(
+            AggregateMode::Partial,
+            group_by.clone(),
+            aggregates.clone(),
+            input.clone(),
+            schema.clone(),
+        )?;
+        let output_schema = exec.schema();
+
+        let session_ctx =
+            SessionContext::with_config(SessionConfig::new().with_batch_size(batch_size));
+        let task_ctx = session_ctx.task_ctx();
+        let metrics = || BaselineMetrics::new(&ExecutionPlanMetricsSet::new(), 0);
+        let streams: Vec<SendableRecordBatchStream> = vec![
+            Box::pin(PrimitiveGroupedHashAggregateStream::new(
+                AggregateMode::Partial,
+                output_schema.clone(),
+                group_by.clone(),
+                aggregates.clone(),
+                input.execute(0, task_ctx.clone())?,
+                metrics(),
+                batch_size,
+            )?),
+            Box::pin(GroupedHashAggregateStreamV2::new(
+                AggregateMode::Partial,
+                output_schema.clone(),
+                group_by.clone(),
+                aggregates.clone(),
+                input.execute(0, task_ctx.clone())?,
+                metrics(),
+                batch_size,
+            )?),
+            Box::pin(GroupedHashAggregateStream::new(
+                AggregateMode::Partial,
+                output_schema.clone(),
+                group_by,
+                aggregates,
+                input.execute(0, task_ctx.clone())?,
+                metrics(),
+                batch_size,
+            )?),
+        ];
+
+        for stream in streams {
+            let results = common::collect(stream).await?;
+
+            // the 1M groups are emitted 4096 at a time, in the order they
+            // were first seen
+            assert_eq!(results.len(), 245);
+            assert!(results[..244]
+                .iter()
+                .all(|batch| batch.num_rows() == batch_size));
+            let batch = concat_batches(&output_schema, &results)?;
+            assert_eq!(batch.num_rows(), num_groups as usize);
+            let keys = batch
+                .column(0)
+                .as_any()
+                .downcast_ref::<Int64Array>()
+                .unwrap();
+            let sums = batch
+                .column(1)
+                .as_any()
+                .downcast_ref::<Int64Array>()
+                .unwrap();
+            assert!(keys.values().iter().copied().eq(0..num_groups));
+            assert!(sums
+                .values()
+                .iter()
+                .copied()
+                .eq((0..num_groups).map(|k| 2 * k)));
+        }
+
+        Ok(())
+    }
+
+    /// Aggregates the rows of `keys`, in batches of 100 rows, grouped by
+    /// `group_columns` by a partial and a final aggregation, once never
+    /// skipping the partial aggregation and once probing whether to skip it
+    /// after the first batch. Asserts that the results match and returns the
+    /// number of rows the partial aggregation skipped.
+    async fn skipped_partial_aggregation_rows(
+        keys: Vec<i64>,
+        group_columns: &[&str],
+    ) -> Result<usize> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int64, false),
+            Field::new("b", DataType::Float64, true),
+            Field::new("c", DataType::Utf8, false),
+        ]));
+        let batches = keys
+            .chunks(100)
+            .map(|keys| {
+                let values = keys.iter().map(|k| (k % 7 != 0).then_some(*k as f64));
+                let tags = keys.iter().map(|k| Some(format!("c{}", k % 3)));
+                RecordBatch::try_new(
+                    schema.clone(),
+                    vec![
+                        Arc::new(Int64Array::from_slice(keys)),
+                        Arc::new(values.collect::<Float64Array>()),
+                        Arc::new(tags.collect::<StringArray>()),
+                    ],
+                )
+            })
+            .collect::<ArrowResult<Vec<_>>>()?;
+        let input = Arc::new(MemoryExec::try_new(&[batches], schema.clone(), None)?);
+
+        let group_by = PhysicalGroupBy::new_single(
+            group_columns
+                .iter()
+                .map(|name| Ok((col(name, &schema)?, name.to_string())))
+                .collect::<Result<_>>()?,
+        );
+        let b = col("b", &schema)?;
+        let aggregates: Vec<Arc<dyn AggregateExpr>> = vec![
+            Arc::new(Count::new(b.clone(), "COUNT(b)", DataType::Int64)),
+            Arc::new(Sum::new(b.clone(), "SUM(b)", DataType::Float64)),
+            Arc::new(Avg::new(b.clone(), "AVG(b)", DataType::Float64)),
+            Arc::new(Min::new(b.clone(), "MIN(b)", DataType::Float64)),
+            Arc::new(Max::new(b, "MAX(b)", DataType::Float64)),
+        ];
+
+        let sorted_lines = |batches: &[RecordBatch]| {
+            let formatted = pretty_format_batches(batches).unwrap().to_string();
+            let mut lines = formatted.lines().map(String::from).collect::<Vec<_>>();
+            lines.sort_unstable();
+            lines
+        };
+
+        let mut results = vec![];
+        let mut skipped_rows = vec![];
+        // a ratio threshold greater than 1 never skips the partial aggregation
+        for threshold in [2.0, 0.8] {
+            let partial_aggregate = Arc::new(AggregateExec::try_new(
+                AggregateMode::Partial,
+                group_by.clone(),
+                aggregates.clone(),
+                input.clone(),
+                schema.clone(),
+            )?);
+            let final_group_by = PhysicalGroupBy::new_single(
+                partial_aggregate
+                    .output_group_expr()
+                    .into_iter()
+                    .zip(group_columns)
+                    .map(|(expr, name)| (expr, name.to_string()))
+                    .collect(),
+            );
+            let final_aggregate = Arc::new(AggregateExec::try_new(
+                AggregateMode::Final,
+                final_group_by,
+                aggregates.clone(),
+                partial_aggregate.clone(),
+                schema.clone(),
+            )?);
+
+            let session_ctx = SessionContext::with_config(
+                SessionConfig::new()
+                    .with_skip_partial_aggregation_probe_rows(100)
+                    .with_skip_partial_aggregation_probe_ratio_threshold(threshold),
+            );
+            let result =
+                crate::physical_plan::collect(final_aggregate, session_ctx.task_ctx())
+                    .await?;
+            results.push(sorted_lines(&result));
+
+            let metrics = partial_aggregate.metrics().unwrap();
+            skipped_rows.push(
+                metrics
+                    .sum_by_name("skipped_aggregation_rows")
+                    .unwrap()
+                    .as_usize(),
+            );
+        }
+
+        assert_eq!(results[0], results[1]);
+        assert_eq!(skipped_rows[0], 0);
+        Ok(skipped_rows[1])
+    }
+
+    #[tokio::test]
+    async fn skip_partial_aggregation_of_unique_keys() -> Result<()> {
+        let keys = (0..1000).collect::<Vec<_>>();
+        // every row of the first batch is its own group, so the other 900
+        // rows are passed on to the final aggregation
+        assert_eq!(
+            skipped_partial_aggregation_rows(keys.clone(), &["a"]).await?,
+            900
+        );
+        assert_eq!(
+            skipped_partial_aggregation_rows(keys, &["a", "c"]).await?,
+            900
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn keep_partial_aggregation_of_low_cardinality_keys() -> Result<()> {
+        let keys = (0..1000).map(|i| i % 10).collect::<Vec<_>>();
+        assert_eq!(
+            skipped_partial_aggregation_rows(keys.clone(), &["a"]).await?,
+            0
+        );
+        assert_eq!(
+            skipped_partial_aggregation_rows(keys, &["a", "c"]).await?,
+            0
+        );
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_drop_cancel_with_groups() -> Result<()> {
         let session_ctx = SessionContext::new();
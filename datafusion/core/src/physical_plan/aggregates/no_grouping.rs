@@ -19,7 +19,7 @@
 
 use crate::physical_plan::aggregates::{
     aggregate_expressions, create_accumulators, finalize_aggregation, AccumulatorItem,
-    AggregateMode,
+    AccumulatorMemory, AggregateMode,
 };
 use crate::physical_plan::metrics::{BaselineMetrics, RecordOutput};
 use crate::physical_plan::{RecordBatchStream, SendableRecordBatchStream};
@@ -44,6 +44,8 @@ pub(crate) struct AggregateStream {
     baseline_metrics: BaselineMetrics,
     aggregate_expressions: Vec<Vec<Arc<dyn PhysicalExpr>>>,
     accumulators: Vec<AccumulatorItem>,
+    /// Accounts for the memory the accumulators hold, if set
+    accumulator_memory: Option<AccumulatorMemory>,
     finished: bool,
 }
 
@@ -66,9 +68,16 @@ impl AggregateStream {
             baseline_metrics,
             aggregate_expressions,
             accumulators,
+            accumulator_memory: None,
             finished: false,
         })
     }
+
+    /// Account for the memory the accumulators hold with `memory`
+    pub fn with_accumulator_memory(mut self, memory: AccumulatorMemory) -> Self {
+        self.accumulator_memory = Some(memory);
+        self
+    }
 }
 
 impl Stream for AggregateStream {
@@ -94,6 +103,7 @@ impl Stream for AggregateStream {
                         &batch,
                         &mut this.accumulators,
                         &this.aggregate_expressions,
+                        this.accumulator_memory.as_ref(),
                     );
 
                     timer.done();
@@ -137,6 +147,7 @@ fn aggregate_batch(
     batch: &RecordBatch,
     accumulators: &mut [AccumulatorItem],
     expressions: &[Vec<Arc<dyn PhysicalExpr>>],
+    memory: Option<&AccumulatorMemory>,
 ) -> Result<()> {
     // 1.1 iterate accumulators and respective expressions together
     // 1.2 evaluate expressions
@@ -155,11 +166,16 @@ fn aggregate_batch(
                 .collect::<Result<Vec<_>>>()?;
 
             // 1.3
+            let size = accum.size();
             match mode {
-                AggregateMode::Partial => accum.update_batch(values),
+                AggregateMode::Partial => accum.update_batch(values)?,
                 AggregateMode::Final | AggregateMode::FinalPartitioned => {
-                    accum.merge_batch(values)
+                    accum.merge_batch(values)?
                 }
             }
+            if let Some(memory) = memory {
+                memory.resize(size, accum.size());
+            }
+            Ok(())
         })
 }
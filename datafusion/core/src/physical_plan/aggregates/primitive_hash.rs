@@ -0,0 +1,334 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Hash aggregation grouped by a single column of a primitive integer type,
+//! keyed directly by the values of the column
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use ahash::RandomState;
+use futures::{
+    ready,
+    stream::{Stream, StreamExt},
+};
+
+use crate::error::{DataFusionError, Result};
+use crate::physical_plan::aggregates::hash::{
+    update_group_states, GroupEmitter, GroupState,
+};
+use crate::physical_plan::aggregates::{
+    evaluate_group_by, evaluate_many, transform_to_states, AccumulatorMemory,
+    AggregateMode, PhysicalGroupBy, SkipAggregationProbe,
+};
+use crate::physical_plan::metrics::{BaselineMetrics, RecordOutput};
+use crate::physical_plan::{aggregates, AggregateExpr, PhysicalExpr};
+use crate::physical_plan::{RecordBatchStream, SendableRecordBatchStream};
+use crate::scalar::ScalarValue;
+
+use arrow::array::{as_primitive_array, Array, ArrayRef};
+use arrow::datatypes::{
+    ArrowPrimitiveType, DataType, Date32Type, Int32Type, Int64Type, SchemaRef,
+};
+use arrow::error::{ArrowError, Result as ArrowResult};
+use arrow::record_batch::RecordBatch;
+
+/// Returns true if a single group column of `data_type` can be aggregated
+/// by [`PrimitiveGroupedHashAggregateStream`]
+pub(crate) fn primitive_group_supported(data_type: &DataType) -> bool {
+    matches!(
+        data_type,
+        DataType::Int32 | DataType::Int64 | DataType::Date32
+    )
+}
+
+/// Hash aggregation grouped by a single column of a type that is
+/// [`primitive_group_supported`].
+///
+/// Unlike [`GroupedHashAggregateStream`], which hashes the group values of
+/// every row and compares them to the `ScalarValue`s of the groups, the
+/// groups are looked up by the value of the column itself, as an `i64`.
+/// The rows whose value is null belong to a separate null group.
+///
+/// [`GroupedHashAggregateStream`]: super::hash::GroupedHashAggregateStream
+pub(crate) struct PrimitiveGroupedHashAggregateStream {
+    schema: SchemaRef,
+    input: SendableRecordBatchStream,
+    mode: AggregateMode,
+    aggregate_expressions: Vec<Vec<Arc<dyn PhysicalExpr>>>,
+
+    aggr_expr: Vec<Arc<dyn AggregateExpr>>,
+    group_by: PhysicalGroupBy,
+
+    /// The index in `group_states` of the group of every non null value
+    groups: HashMap<i64, usize, RandomState>,
+    /// The index in `group_states` of the group of the null values
+    null_group: Option<usize>,
+    /// State for each group, in the order they were first seen
+    group_states: Vec<GroupState>,
+
+    baseline_metrics: BaselineMetrics,
+    /// size to be used for resulting RecordBatches
+    batch_size: usize,
+    /// The groups left to emit once the input is exhausted
+    emitter: Option<GroupEmitter>,
+    /// Decides whether a partial aggregation stops grouping its input
+    skip_aggregation_probe: Option<SkipAggregationProbe>,
+    /// Accounts for the memory the accumulators hold, if set
+    accumulator_memory: Option<AccumulatorMemory>,
+    finished: bool,
+}
+
+impl PrimitiveGroupedHashAggregateStream {
+    /// Create a new PrimitiveGroupedHashAggregateStream
+    pub fn new(
+        mode: AggregateMode,
+        schema: SchemaRef,
+        group_by: PhysicalGroupBy,
+        aggr_expr: Vec<Arc<dyn AggregateExpr>>,
+        input: SendableRecordBatchStream,
+        baseline_metrics: BaselineMetrics,
+        batch_size: usize,
+    ) -> Result<Self> {
+        let timer = baseline_metrics.elapsed_compute().timer();
+
+        let aggregate_expressions =
+            aggregates::aggregate_expressions(&aggr_expr, &mode, group_by.expr.len())?;
+
+        timer.done();
+
+        Ok(Self {
+            schema,
+            input,
+            mode,
+            aggregate_expressions,
+            aggr_expr,
+            group_by,
+            groups: Default::default(),
+            null_group: None,
+            group_states: vec![],
+            baseline_metrics,
+            batch_size,
+            emitter: None,
+            skip_aggregation_probe: None,
+            accumulator_memory: None,
+            finished: false,
+        })
+    }
+
+    /// Pass the input rows on without grouping them once `probe` decides to
+    pub fn with_skip_aggregation_probe(
+        mut self,
+        probe: Option<SkipAggregationProbe>,
+    ) -> Self {
+        self.skip_aggregation_probe = probe;
+        self
+    }
+
+    /// Account for the memory the accumulators hold with `memory`
+    pub fn with_accumulator_memory(mut self, memory: AccumulatorMemory) -> Self {
+        self.accumulator_memory = Some(memory);
+        self
+    }
+
+    /// Aggregates the rows of `batch` into their groups
+    fn group_aggregate_batch(&mut self, batch: RecordBatch) -> Result<()> {
+        let group_values = evaluate_group_by(&self.group_by, &batch)?;
+        let aggr_input_values = evaluate_many(&self.aggregate_expressions, &batch)?;
+
+        for grouping_set_values in group_values {
+            let column = match grouping_set_values.as_slice() {
+                [column] => column,
+                _ => {
+                    return Err(DataFusionError::Internal(format!(
+                        "Expected a single group column, got {}",
+                        grouping_set_values.len()
+                    )))
+                }
+            };
+
+            let groups_with_rows = match column.data_type() {
+                DataType::Int32 => self.group_rows::<Int32Type>(column)?,
+                DataType::Int64 => self.group_rows::<Int64Type>(column)?,
+                DataType::Date32 => self.group_rows::<Date32Type>(column)?,
+                data_type => {
+                    return Err(DataFusionError::Internal(format!(
+                        "Unsupported primitive group column type {:?}",
+                        data_type
+                    )))
+                }
+            };
+
+            update_group_states(
+                &self.mode,
+                &mut self.group_states,
+                &groups_with_rows,
+                &aggr_input_values,
+                self.accumulator_memory.as_ref(),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Adds the index of every row of `column` to the indices of its group,
+    /// creating the groups seen for the first time, and returns the groups
+    /// that have rows in this batch
+    fn group_rows<T>(&mut self, column: &ArrayRef) -> Result<Vec<usize>>
+    where
+        T: ArrowPrimitiveType,
+        T::Native: Into<i64>,
+    {
+        let array = as_primitive_array::<T>(column);
+        let mut groups_with_rows = vec![];
+
+        for row in 0..array.len() {
+            let key: Option<i64> = array.is_valid(row).then(|| array.value(row).into());
+            let existing = match key {
+                Some(key) => self.groups.get(&key).copied(),
+                None => self.null_group,
+            };
+            let group_idx = match existing {
+                Some(group_idx) => group_idx,
+                None => {
+                    // Copy the group value out of the array only once per group
+                    let group_idx = self.group_states.len();
+                    self.group_states.push(GroupState {
+                        group_by_values: vec![ScalarValue::try_from_array(column, row)?]
+                            .into_boxed_slice(),
+                        accumulator_set: aggregates::create_accumulators(
+                            &self.aggr_expr,
+                        )?,
+                        indices: vec![],
+                    });
+                    match key {
+                        Some(key) => {
+                            self.groups.insert(key, group_idx);
+                        }
+                        None => self.null_group = Some(group_idx),
+                    }
+                    group_idx
+                }
+            };
+
+            let group_state = &mut self.group_states[group_idx];
+            if group_state.indices.is_empty() {
+                groups_with_rows.push(group_idx);
+            }
+            group_state.indices.push(row as u32);
+        }
+
+        Ok(groups_with_rows)
+    }
+}
+
+impl Stream for PrimitiveGroupedHashAggregateStream {
+    type Item = ArrowResult<RecordBatch>;
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        if this.finished {
+            return Poll::Ready(None);
+        }
+
+        let elapsed_compute = this.baseline_metrics.elapsed_compute().clone();
+
+        loop {
+            if let Some(emitter) = &mut this.emitter {
+                let timer = elapsed_compute.timer();
+                let result = emitter.next_batch(
+                    &this.mode,
+                    this.group_by.expr.len(),
+                    &this.schema,
+                );
+                timer.done();
+
+                this.finished = !matches!(result, Ok(Some(_)));
+                return Poll::Ready(
+                    result
+                        .transpose()
+                        .map(|result| result.record_output(&this.baseline_metrics)),
+                );
+            }
+
+            match ready!(this.input.poll_next_unpin(cx)) {
+                Some(Ok(batch)) => {
+                    let timer = elapsed_compute.timer();
+                    if let Some(probe) = this
+                        .skip_aggregation_probe
+                        .as_ref()
+                        .filter(|probe| probe.should_skip())
+                    {
+                        probe.record_skipped(batch.num_rows());
+                        let result = transform_to_states(
+                            &batch,
+                            &this.schema,
+                            &this.group_by,
+                            &this.aggr_expr,
+                            &this.aggregate_expressions,
+                        )
+                        .map_err(|e| ArrowError::ExternalError(Box::new(e)));
+                        timer.done();
+
+                        this.finished = result.is_err();
+                        return Poll::Ready(Some(
+                            result.record_output(&this.baseline_metrics),
+                        ));
+                    }
+
+                    let num_rows = batch.num_rows();
+                    let result = this.group_aggregate_batch(batch);
+                    timer.done();
+
+                    if let Err(e) = result {
+                        this.finished = true;
+                        return Poll::Ready(Some(Err(ArrowError::ExternalError(
+                            Box::new(e),
+                        ))));
+                    }
+
+                    if let Some(probe) = &mut this.skip_aggregation_probe {
+                        probe.update_state(num_rows, this.group_states.len());
+                    }
+                }
+                Some(Err(e)) => {
+                    this.finished = true;
+                    return Poll::Ready(Some(Err(e)));
+                }
+                None => {
+                    // the groups are no longer looked up once emitted
+                    this.groups = Default::default();
+                    this.null_group = None;
+                    this.emitter = Some(GroupEmitter::new(
+                        std::mem::take(&mut this.group_states),
+                        this.batch_size,
+                    ));
+                }
+            }
+        }
+    }
+}
+
+impl RecordBatchStream for PrimitiveGroupedHashAggregateStream {
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+}
@@ -29,8 +29,8 @@ use futures::{
 
 use crate::error::Result;
 use crate::physical_plan::aggregates::{
-    evaluate_group_by, evaluate_many, group_schema, AccumulatorItemV2, AggregateMode,
-    PhysicalGroupBy,
+    evaluate_group_by, evaluate_many, group_schema, transform_to_states,
+    AccumulatorItemV2, AggregateMode, PhysicalGroupBy, SkipAggregationProbe,
 };
 use crate::physical_plan::hash_utils::create_row_hashes;
 use crate::physical_plan::metrics::{BaselineMetrics, RecordOutput};
@@ -38,10 +38,11 @@ use crate::physical_plan::{aggregates, AggregateExpr, PhysicalExpr};
 use crate::physical_plan::{RecordBatchStream, SendableRecordBatchStream};
 
 use arrow::compute::cast;
-use arrow::datatypes::Schema;
+use arrow::compute::kernels::arity::unary;
+use arrow::datatypes::{DataType, Float32Type, Float64Type, Schema};
 use arrow::{array::ArrayRef, compute};
 use arrow::{
-    array::{Array, UInt32Builder},
+    array::{as_primitive_array, Array, UInt32Builder},
     error::{ArrowError, Result as ArrowResult},
 };
 use arrow::{datatypes::SchemaRef, record_batch::RecordBatch};
@@ -76,6 +77,7 @@ pub(crate) struct GroupedHashAggregateStreamV2 {
     aggr_state: AggregationState,
     aggregate_expressions: Vec<Vec<Arc<dyn PhysicalExpr>>>,
 
+    aggr_expr: Vec<Arc<dyn AggregateExpr>>,
     group_by: PhysicalGroupBy,
     accumulators: Vec<AccumulatorItemV2>,
 
@@ -85,11 +87,16 @@ pub(crate) struct GroupedHashAggregateStreamV2 {
 
     baseline_metrics: BaselineMetrics,
     random_state: RandomState,
+    /// The hashes of the group keys of the batch being aggregated, reused
+    /// across batches to avoid reallocating it
+    hashes_buffer: Vec<u64>,
     /// size to be used for resulting RecordBatches
     batch_size: usize,
     /// if the result is chunked into batches,
     /// last offset is preserved for continuation.
     row_group_skip_position: usize,
+    /// Decides whether a partial aggregation stops grouping its input
+    skip_aggregation_probe: Option<SkipAggregationProbe>,
 }
 
 fn aggr_state_schema(aggr_expr: &[Arc<dyn AggregateExpr>]) -> Result<SchemaRef> {
@@ -131,6 +138,7 @@ impl GroupedHashAggregateStreamV2 {
             schema,
             mode,
             input,
+            aggr_expr,
             group_by,
             accumulators,
             group_schema,
@@ -140,10 +148,21 @@ impl GroupedHashAggregateStreamV2 {
             aggregate_expressions,
             aggr_state: Default::default(),
             random_state: Default::default(),
+            hashes_buffer: vec![],
             batch_size,
             row_group_skip_position: 0,
+            skip_aggregation_probe: None,
         })
     }
+
+    /// Pass the input rows on without grouping them once `probe` decides to
+    pub fn with_skip_aggregation_probe(
+        mut self,
+        probe: Option<SkipAggregationProbe>,
+    ) -> Self {
+        self.skip_aggregation_probe = probe;
+        self
+    }
 }
 
 impl Stream for GroupedHashAggregateStreamV2 {
@@ -162,6 +181,30 @@ impl Stream for GroupedHashAggregateStreamV2 {
                 match ready!(this.input.poll_next_unpin(cx)) {
                     Some(Ok(batch)) => {
                         let timer = elapsed_compute.timer();
+                        if let Some(probe) = this
+                            .skip_aggregation_probe
+                            .as_ref()
+                            .filter(|probe| probe.should_skip())
+                        {
+                            probe.record_skipped(batch.num_rows());
+                            let result = transform_to_states(
+                                &batch,
+                                &this.schema,
+                                &this.group_by,
+                                &this.aggr_expr,
+                                &this.aggregate_expressions,
+                            )
+                            .map_err(|e| ArrowError::ExternalError(Box::new(e)));
+                            timer.done();
+
+                            // the groups are only emitted once the input is
+                            // exhausted, so `row_group_skip_position` stays
+                            return Poll::Ready(Some(
+                                result.record_output(&this.baseline_metrics),
+                            ));
+                        }
+
+                        let num_rows = batch.num_rows();
                         let result = group_aggregate_batch(
                             &this.mode,
                             &this.random_state,
@@ -172,12 +215,21 @@ impl Stream for GroupedHashAggregateStreamV2 {
                             batch,
                             &mut this.aggr_state,
                             &this.aggregate_expressions,
+                            &mut this.hashes_buffer,
                         );
 
                         timer.done();
 
                         match result {
-                            Ok(_) => continue,
+                            Ok(_) => {
+                                if let Some(probe) = &mut this.skip_aggregation_probe {
+                                    probe.update_state(
+                                        num_rows,
+                                        this.aggr_state.group_states.len(),
+                                    );
+                                }
+                                continue;
+                            }
                             Err(e) => Err(ArrowError::ExternalError(Box::new(e))),
                         }
                     }
@@ -232,6 +284,7 @@ fn group_aggregate_batch(
     batch: RecordBatch,
     aggr_state: &mut AggregationState,
     aggregate_expressions: &[Vec<Arc<dyn PhysicalExpr>>],
+    batch_hashes: &mut Vec<u64>,
 ) -> Result<()> {
     // evaluate the grouping expressions
     let grouping_by_values = evaluate_group_by(grouping_set, &batch)?;
@@ -252,10 +305,11 @@ fn group_aggregate_batch(
         let mut groups_with_rows = vec![];
 
         // 1.1 Calculate the group keys for the group values
-        let mut batch_hashes = vec![0; batch.num_rows()];
-        create_row_hashes(&group_rows, random_state, &mut batch_hashes)?;
+        batch_hashes.clear();
+        batch_hashes.resize(batch.num_rows(), 0);
+        create_row_hashes(&group_rows, random_state, batch_hashes)?;
 
-        for (row, hash) in batch_hashes.into_iter().enumerate() {
+        for (row, hash) in batch_hashes.iter().copied().enumerate() {
             let AggregationState { map, group_states } = aggr_state;
 
             let entry = map.get_mut(hash, |(_hash, group_idx)| {
@@ -418,8 +472,25 @@ impl std::fmt::Debug for AggregationState {
     }
 }
 
+/// Maps -0.0 to 0.0 and every NaN to the same NaN in float group keys, as
+/// group rows are compared by their bytes
+fn normalize_float_keys(array: ArrayRef) -> ArrayRef {
+    match array.data_type() {
+        DataType::Float32 => Arc::new(unary::<_, _, Float32Type>(
+            as_primitive_array::<Float32Type>(&array),
+            |v| if v.is_nan() { f32::NAN } else { v + 0.0 },
+        )),
+        DataType::Float64 => Arc::new(unary::<_, _, Float64Type>(
+            as_primitive_array::<Float64Type>(&array),
+            |v| if v.is_nan() { f64::NAN } else { v + 0.0 },
+        )),
+        _ => array,
+    }
+}
+
 /// Create grouping rows
 fn create_group_rows(arrays: Vec<ArrayRef>, schema: &Schema) -> Vec<Vec<u8>> {
+    let arrays: Vec<ArrayRef> = arrays.into_iter().map(normalize_float_keys).collect();
     let mut writer = RowWriter::new(schema, RowType::Compact);
     let mut results = vec![];
     for cur_row in 0..arrays[0].len() {
@@ -442,14 +513,14 @@ fn create_batch_from_map(
     accumulators: &mut [AccumulatorItemV2],
     output_schema: &Schema,
 ) -> ArrowResult<Option<RecordBatch>> {
-    if skip_items > aggr_state.group_states.len() {
-        return Ok(None);
+    if aggr_state.group_states.is_empty() {
+        // an aggregation without any group still emits an empty batch
+        return Ok((skip_items == 0)
+            .then(|| RecordBatch::new_empty(Arc::new(output_schema.to_owned()))));
     }
 
-    if aggr_state.group_states.is_empty() {
-        return Ok(Some(RecordBatch::new_empty(Arc::new(
-            output_schema.to_owned(),
-        ))));
+    if skip_items >= aggr_state.group_states.len() {
+        return Ok(None);
     }
 
     let mut state_accessor = RowAccessor::new(aggr_schema, RowType::WordAligned);
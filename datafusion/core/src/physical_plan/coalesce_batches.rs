@@ -97,7 +97,12 @@ impl ExecutionPlan for CoalesceBatchesExec {
     }
 
     fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
-        None
+        // the batches are only concatenated, in the order they are produced
+        self.input.output_ordering()
+    }
+
+    fn maintains_input_order(&self) -> bool {
+        true
     }
 
     fn equivalence_properties(&self) -> EquivalenceProperties {
@@ -136,7 +141,7 @@ impl ExecutionPlan for CoalesceBatchesExec {
         f: &mut std::fmt::Formatter,
     ) -> std::fmt::Result {
         match t {
-            DisplayFormatType::Default => {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
                 write!(
                     f,
                     "CoalesceBatchesExec: target_batch_size={}",
@@ -302,12 +307,16 @@ mod tests {
     use super::*;
     use crate::config::{OPT_COALESCE_BATCHES, OPT_COALESCE_TARGET_BATCH_SIZE};
     use crate::datasource::MemTable;
+    use crate::logical_expr::Operator;
+    use crate::physical_plan::collect;
+    use crate::physical_plan::expressions::{binary, col, lit};
     use crate::physical_plan::filter::FilterExec;
     use crate::physical_plan::projection::ProjectionExec;
     use crate::physical_plan::{memory::MemoryExec, repartition::RepartitionExec};
     use crate::prelude::{SessionConfig, SessionContext};
     use crate::test::create_vec_batches;
-    use arrow::datatypes::{DataType, Field, Schema};
+    use arrow::array::{as_primitive_array, UInt32Array};
+    use arrow::datatypes::{DataType, Field, Schema, UInt32Type};
 
     #[tokio::test]
     async fn test_custom_batch_size() -> Result<()> {
@@ -325,6 +334,20 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_default_batch_size() -> Result<()> {
+        let ctx = SessionContext::with_config(SessionConfig::new().with_batch_size(1000));
+        let plan = create_physical_plan(ctx).await?;
+        let projection = plan.as_any().downcast_ref::<ProjectionExec>().unwrap();
+        let coalesce = projection
+            .input()
+            .as_any()
+            .downcast_ref::<CoalesceBatchesExec>()
+            .unwrap();
+        assert_eq!(1000, coalesce.target_batch_size);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_disable_coalesce() -> Result<()> {
         let ctx = SessionContext::with_config(
@@ -371,6 +394,51 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_selective_filter() -> Result<()> {
+        // 100 batches of 1000 rows, of which the filter keeps one in a thousand
+        let schema = test_schema();
+        let batches = (0..100)
+            .map(|i| {
+                let values = UInt32Array::from_iter_values(i * 1000..(i + 1) * 1000);
+                RecordBatch::try_new(schema.clone(), vec![Arc::new(values)])
+            })
+            .collect::<ArrowResult<Vec<_>>>()?;
+        let exec = MemoryExec::try_new(&[batches], schema.clone(), None)?;
+        let predicate = binary(
+            binary(col("c0", &schema)?, Operator::Modulo, lit(1000u32), &schema)?,
+            Operator::Eq,
+            lit(7u32),
+            &schema,
+        )?;
+        let filter = FilterExec::try_new(predicate, Arc::new(exec))?;
+        let exec = Arc::new(CoalesceBatchesExec::new(Arc::new(filter), 16));
+
+        let session_ctx = SessionContext::new();
+        let batches = collect(exec, session_ctx.task_ctx()).await?;
+
+        // every row is kept, in order
+        let values = batches
+            .iter()
+            .flat_map(|batch| {
+                as_primitive_array::<UInt32Type>(batch.column(0))
+                    .values()
+                    .to_vec()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(values, (0..100).map(|i| i * 1000 + 7).collect::<Vec<_>>());
+
+        // the batches of a single row are coalesced into batches of the
+        // target size, the rows left at the end of the input being flushed
+        let sizes = batches
+            .iter()
+            .map(|batch| batch.num_rows())
+            .collect::<Vec<_>>();
+        assert_eq!(sizes, vec![16, 16, 16, 16, 16, 16, 4]);
+
+        Ok(())
+    }
+
     fn test_schema() -> Arc<Schema> {
         Arc::new(Schema::new(vec![Field::new("c0", DataType::UInt32, false)]))
     }
@@ -26,10 +26,17 @@ use crate::logical_expr::{StringifiedPlan, ToStringifiedPlan};
 use super::{accept, ExecutionPlan, ExecutionPlanVisitor};
 
 /// Options for controlling how each [`ExecutionPlan`] should format itself
+///
+/// More formats may be added, so implementations outside of this crate
+/// must match the ones they do not distinguish with a wildcard
 #[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
 pub enum DisplayFormatType {
     /// Default, compact format. Example: `FilterExec: c12 < 10.0`
     Default,
+    /// Default format with additional details useful for debugging, used
+    /// by `EXPLAIN VERBOSE`. Example: the size of each file a scan reads
+    Verbose,
 }
 
 /// Wraps an `ExecutionPlan` with various ways to display this plan
@@ -37,6 +44,8 @@ pub struct DisplayableExecutionPlan<'a> {
     inner: &'a dyn ExecutionPlan,
     /// How to show metrics
     show_metrics: ShowMetrics,
+    /// How to format each node
+    format_type: DisplayFormatType,
 }
 
 impl<'a> DisplayableExecutionPlan<'a> {
@@ -46,6 +55,7 @@ impl<'a> DisplayableExecutionPlan<'a> {
         Self {
             inner,
             show_metrics: ShowMetrics::None,
+            format_type: DisplayFormatType::Default,
         }
     }
 
@@ -56,6 +66,7 @@ impl<'a> DisplayableExecutionPlan<'a> {
         Self {
             inner,
             show_metrics: ShowMetrics::Aggregated,
+            format_type: DisplayFormatType::Default,
         }
     }
 
@@ -66,15 +77,27 @@ impl<'a> DisplayableExecutionPlan<'a> {
         Self {
             inner,
             show_metrics: ShowMetrics::Full,
+            format_type: DisplayFormatType::Default,
         }
     }
 
+    /// Format each node in [`DisplayFormatType::Verbose`] rather than
+    /// [`DisplayFormatType::Default`] if `verbose` is true
+    pub fn set_verbose(mut self, verbose: bool) -> Self {
+        self.format_type = if verbose {
+            DisplayFormatType::Verbose
+        } else {
+            DisplayFormatType::Default
+        };
+        self
+    }
+
     /// Return a `format`able structure that produces a single line
     /// per node.
     ///
     /// ```text
     /// ProjectionExec: expr=[a]
-    ///   CoalesceBatchesExec: target_batch_size=4096
+    ///   CoalesceBatchesExec: target_batch_size=8192
     ///     FilterExec: a < 5
     ///       RepartitionExec: partitioning=RoundRobinBatch(16)
     ///         CsvExec: source=...",
@@ -83,12 +106,12 @@ impl<'a> DisplayableExecutionPlan<'a> {
         struct Wrapper<'a> {
             plan: &'a dyn ExecutionPlan,
             show_metrics: ShowMetrics,
+            format_type: DisplayFormatType,
         }
         impl<'a> fmt::Display for Wrapper<'a> {
             fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-                let t = DisplayFormatType::Default;
                 let mut visitor = IndentVisitor {
-                    t,
+                    t: self.format_type,
                     f,
                     indent: 0,
                     show_metrics: self.show_metrics,
@@ -99,6 +122,7 @@ impl<'a> DisplayableExecutionPlan<'a> {
         Wrapper {
             plan: self.inner,
             show_metrics: self.show_metrics,
+            format_type: self.format_type,
         }
     }
 
@@ -108,13 +132,14 @@ impl<'a> DisplayableExecutionPlan<'a> {
         struct Wrapper<'a> {
             plan: &'a dyn ExecutionPlan,
             show_metrics: ShowMetrics,
+            format_type: DisplayFormatType,
         }
 
         impl<'a> fmt::Display for Wrapper<'a> {
             fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
                 let mut visitor = IndentVisitor {
                     f,
-                    t: DisplayFormatType::Default,
+                    t: self.format_type,
                     indent: 0,
                     show_metrics: self.show_metrics,
                 };
@@ -126,6 +151,7 @@ impl<'a> DisplayableExecutionPlan<'a> {
         Wrapper {
             plan: self.inner,
             show_metrics: self.show_metrics,
+            format_type: self.format_type,
         }
     }
 }
@@ -128,11 +128,11 @@ impl ExecutionPlan for AvroExec {
         f: &mut std::fmt::Formatter,
     ) -> std::fmt::Result {
         match t {
-            DisplayFormatType::Default => {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
                 write!(
                     f,
                     "AvroExec: files={}, limit={:?}",
-                    super::FileGroupsDisplay(&self.base_config.file_groups),
+                    super::FileGroupsDisplay(&self.base_config.file_groups, t),
                     self.base_config.limit,
                 )
             }
@@ -158,11 +158,11 @@ impl ExecutionPlan for CsvExec {
         f: &mut std::fmt::Formatter,
     ) -> std::fmt::Result {
         match t {
-            DisplayFormatType::Default => {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
                 write!(
                     f,
                     "CsvExec: files={}, has_header={}, limit={:?}, projection={}",
-                    super::FileGroupsDisplay(&self.base_config.file_groups),
+                    super::FileGroupsDisplay(&self.base_config.file_groups, t),
                     self.has_header,
                     self.base_config.limit,
                     super::ProjectSchemaDisplay(&self.projected_schema),
@@ -217,13 +217,13 @@ impl FileOpener for CsvOpener {
         Ok(Box::pin(async move {
             match store.get(file_meta.location()).await? {
                 GetResult::File(file, _) => {
-                    let decoder = file_compression_type.convert_read(file);
+                    let decoder = file_compression_type.convert_read(file)?;
                     Ok(futures::stream::iter(config.open(decoder, true)).boxed())
                 }
                 GetResult::Stream(s) => {
                     let mut first_chunk = true;
                     let s = s.map_err(Into::<DataFusionError>::into);
-                    let decoder = file_compression_type.convert_stream(s);
+                    let decoder = file_compression_type.convert_stream(s)?;
                     Ok(newline_delimited_stream(decoder)
                         .map_ok(move |bytes| {
                             let reader = config.open(bytes.reader(), first_chunk);
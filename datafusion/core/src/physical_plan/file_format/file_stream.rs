@@ -28,16 +28,19 @@ use std::task::{Context, Poll};
 use std::time::Instant;
 
 use arrow::datatypes::SchemaRef;
-use arrow::{error::Result as ArrowResult, record_batch::RecordBatch};
+use arrow::error::{ArrowError, Result as ArrowResult};
+use arrow::record_batch::RecordBatch;
 use futures::future::BoxFuture;
 use futures::stream::BoxStream;
 use futures::{ready, FutureExt, Stream, StreamExt};
 use object_store::ObjectStore;
+use tokio::sync::{AcquireError, OwnedSemaphorePermit, Semaphore};
 
 use datafusion_common::ScalarValue;
 
 use crate::datasource::listing::PartitionedFile;
 use crate::error::Result;
+use crate::execution::context::check_deadline;
 use crate::execution::context::TaskContext;
 use crate::physical_plan::file_format::{
     FileMeta, FileScanConfig, PartitionColumnProjector,
@@ -81,6 +84,17 @@ pub struct FileStream<F: FileOpener> {
     pc_projector: PartitionColumnProjector,
     /// the store from which to source the files.
     object_store: Arc<dyn ObjectStore>,
+    /// The instant past which no more files are opened
+    deadline: Option<Instant>,
+    /// The permits to read a file shared by every scan of the runtime, if
+    /// the number of open files is limited
+    open_files: Option<Arc<Semaphore>>,
+    /// The permit being acquired to read a batch
+    acquire_permit: Option<
+        BoxFuture<'static, std::result::Result<OwnedSemaphorePermit, AcquireError>>,
+    >,
+    /// The permit held while a batch is read, released once it is produced
+    open_file_permit: Option<OwnedSemaphorePermit>,
     /// The stream state
     state: FileStreamState,
     /// File stream specific metrics
@@ -187,9 +201,8 @@ impl<F: FileOpener> FileStream<F> {
 
         let files = config.file_groups[partition].clone();
 
-        let object_store = context
-            .runtime_env()
-            .object_store(&config.object_store_url)?;
+        let runtime = context.runtime_env();
+        let object_store = runtime.object_store(&config.object_store_url)?;
 
         Ok(Self {
             file_iter: files.into(),
@@ -198,17 +211,52 @@ impl<F: FileOpener> FileStream<F> {
             file_reader,
             pc_projector,
             object_store,
+            deadline: context.deadline(),
+            open_files: runtime.open_files.clone(),
+            acquire_permit: None,
+            open_file_permit: None,
             state: FileStreamState::Idle,
             file_stream_metrics: FileStreamMetrics::new(&metrics, partition),
             baseline_metrics: BaselineMetrics::new(&metrics, partition),
         })
     }
 
+    /// Polls for a permit to read a batch, if the number of files open at
+    /// once is limited, see [`RuntimeConfig::with_max_open_files`]
+    ///
+    /// [`RuntimeConfig::with_max_open_files`]: crate::execution::runtime_env::RuntimeConfig::with_max_open_files
+    fn poll_permit(&mut self, cx: &mut Context<'_>) -> Poll<ArrowResult<()>> {
+        let open_files = match &self.open_files {
+            Some(open_files) if self.open_file_permit.is_none() => open_files,
+            _ => return Poll::Ready(Ok(())),
+        };
+        let future = self
+            .acquire_permit
+            .get_or_insert_with(|| open_files.clone().acquire_owned().boxed());
+        let permit = ready!(future.poll_unpin(cx));
+        self.acquire_permit = None;
+        let permit = permit.map_err(|e| ArrowError::ExternalError(Box::new(e)))?;
+        self.open_file_permit = Some(permit);
+        Poll::Ready(Ok(()))
+    }
+
     fn poll_inner(
         &mut self,
         cx: &mut Context<'_>,
     ) -> Poll<Option<ArrowResult<RecordBatch>>> {
         loop {
+            let reads = match &self.state {
+                FileStreamState::Idle => !self.file_iter.is_empty(),
+                FileStreamState::Open { .. } | FileStreamState::Scan { .. } => true,
+                FileStreamState::Error | FileStreamState::Limit => false,
+            };
+            if reads {
+                if let Err(e) = ready!(self.poll_permit(cx)) {
+                    self.state = FileStreamState::Error;
+                    return Poll::Ready(Some(Err(e)));
+                }
+            }
+
             match &mut self.state {
                 FileStreamState::Idle => {
                     let part_file = match self.file_iter.pop_front() {
@@ -216,6 +264,11 @@ impl<F: FileOpener> FileStream<F> {
                         None => return Poll::Ready(None),
                     };
 
+                    if let Err(e) = check_deadline(self.deadline) {
+                        self.state = FileStreamState::Error;
+                        return Poll::Ready(Some(Err(e.into())));
+                    }
+
                     let file_meta = FileMeta {
                         object_meta: part_file.object_meta,
                         range: part_file.range,
@@ -289,7 +342,7 @@ impl<F: FileOpener> FileStream<F> {
                     }
                 },
                 FileStreamState::Error | FileStreamState::Limit => {
-                    return Poll::Ready(None)
+                    return Poll::Ready(None);
                 }
             }
         }
@@ -305,6 +358,11 @@ impl<F: FileOpener> Stream for FileStream<F> {
     ) -> Poll<Option<Self::Item>> {
         self.file_stream_metrics.time_processing.start();
         let result = self.poll_inner(cx);
+        if result.is_ready() {
+            // the permit is only held until a batch is produced, so that a
+            // stream that is not polled does not hold one
+            self.open_file_permit = None;
+        }
         self.file_stream_metrics.time_processing.stop();
         self.baseline_metrics.record_poll(result)
     }
@@ -320,11 +378,14 @@ impl<F: FileOpener> RecordBatchStream for FileStream<F> {
 mod tests {
     use futures::StreamExt;
 
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
     use super::*;
     use crate::config::ConfigOptions;
     use crate::datasource::object_store::ObjectStoreUrl;
+    use crate::execution::runtime_env::{RuntimeConfig, RuntimeEnv};
     use crate::physical_plan::metrics::ExecutionPlanMetricsSet;
-    use crate::prelude::SessionContext;
+    use crate::prelude::{SessionConfig, SessionContext};
     use crate::{
         error::Result,
         test::{make_partition, object_store::register_test_store},
@@ -448,4 +509,141 @@ mod tests {
 
         Ok(())
     }
+    #[tokio::test]
+    async fn deadline_exceeded() -> Result<()> {
+        let records = vec![make_partition(3)];
+        let file_schema = records[0].schema();
+        let reader = TestOpener { records };
+
+        let ctx = SessionContext::new();
+        register_test_store(&ctx, &[("mock_file1", 10)]);
+        let task_ctx = TaskContext::from(&ctx).with_deadline(Instant::now());
+
+        let config = FileScanConfig {
+            object_store_url: ObjectStoreUrl::parse("test:///").unwrap(),
+            file_schema,
+            file_groups: vec![vec![PartitionedFile::new("mock_file1".to_owned(), 10)]],
+            statistics: Default::default(),
+            projection: None,
+            limit: None,
+            table_partition_cols: vec![],
+            config_options: ConfigOptions::new().into_shareable(),
+        };
+        let mut file_stream = FileStream::new(
+            &config,
+            0,
+            Arc::new(task_ctx),
+            reader,
+            ExecutionPlanMetricsSet::new(),
+        )?;
+
+        let err = file_stream.next().await.unwrap().unwrap_err();
+        assert!(
+            err.to_string().contains("Query deadline exceeded"),
+            "{}",
+            err
+        );
+        assert!(file_stream.next().await.is_none());
+        Ok(())
+    }
+
+    /// Opens files whose batches are each read after yielding once, counting
+    /// the batches read at once
+    #[derive(Clone)]
+    struct CountingOpener {
+        records: Vec<RecordBatch>,
+        reading: Arc<AtomicUsize>,
+        max_reading: Arc<AtomicUsize>,
+    }
+
+    impl FileOpener for CountingOpener {
+        fn open(
+            &self,
+            _store: Arc<dyn ObjectStore>,
+            _file_meta: FileMeta,
+        ) -> Result<FileOpenFuture> {
+            let opener = self.clone();
+            let stream = futures::stream::iter(self.records.clone())
+                .then(move |batch| {
+                    let opener = opener.clone();
+                    async move {
+                        let reading = opener.reading.fetch_add(1, Ordering::SeqCst) + 1;
+                        opener.max_reading.fetch_max(reading, Ordering::SeqCst);
+                        tokio::task::yield_now().await;
+                        opener.reading.fetch_sub(1, Ordering::SeqCst);
+                        Ok(batch)
+                    }
+                })
+                .boxed();
+            Ok(futures::future::ready(Ok(stream)).boxed())
+        }
+    }
+
+    #[tokio::test]
+    async fn max_open_files() -> Result<()> {
+        let records = vec![make_partition(3), make_partition(2)];
+        let config = FileScanConfig {
+            object_store_url: ObjectStoreUrl::parse("test:///").unwrap(),
+            file_schema: records[0].schema(),
+            file_groups: (0..4)
+                .map(|_| vec![PartitionedFile::new("mock_file1".to_owned(), 10)])
+                .collect(),
+            statistics: Default::default(),
+            projection: None,
+            limit: None,
+            table_partition_cols: vec![],
+            config_options: ConfigOptions::new().into_shareable(),
+        };
+        let opener = CountingOpener {
+            records,
+            reading: Default::default(),
+            max_reading: Default::default(),
+        };
+        let streams = |runtime: RuntimeConfig| {
+            let runtime = Arc::new(RuntimeEnv::new(runtime).unwrap());
+            let ctx = SessionContext::with_config_rt(SessionConfig::new(), runtime);
+            register_test_store(&ctx, &[("mock_file1", 10)]);
+            opener.max_reading.store(0, Ordering::SeqCst);
+            (0..4)
+                .map(|partition| {
+                    let metrics = ExecutionPlanMetricsSet::new();
+                    FileStream::new(
+                        &config,
+                        partition,
+                        ctx.task_ctx(),
+                        opener.clone(),
+                        metrics,
+                    )
+                    .unwrap()
+                })
+                .collect::<Vec<_>>()
+        };
+        let read = |streams: Vec<FileStream<CountingOpener>>| {
+            futures::future::join_all(streams.into_iter().map(|stream| {
+                stream
+                    .map(|batch| batch.unwrap().num_rows())
+                    .collect::<Vec<_>>()
+            }))
+        };
+
+        let rows = read(streams(RuntimeConfig::new())).await;
+        assert_eq!(rows, vec![vec![3, 2]; 4]);
+        assert!(opener.max_reading.load(Ordering::SeqCst) > 1);
+
+        let rows = read(streams(RuntimeConfig::new().with_max_open_files(1))).await;
+        assert_eq!(rows, vec![vec![3, 2]; 4]);
+        assert_eq!(opener.max_reading.load(Ordering::SeqCst), 1);
+
+        // a stream within a file holds no permit between its batches, so that
+        // streams read in turn, as when merging them, do not wait on each other
+        let mut streams = streams(RuntimeConfig::new().with_max_open_files(1));
+        for stream in &mut streams {
+            assert_eq!(stream.next().await.unwrap()?.num_rows(), 3);
+        }
+        for stream in &mut streams {
+            assert_eq!(stream.next().await.unwrap()?.num_rows(), 2);
+            assert!(stream.next().await.is_none());
+        }
+        Ok(())
+    }
 }
@@ -0,0 +1,585 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Execution plan for reading Arrow IPC files
+use crate::datasource::file_format::ipc::IpcBatchReader;
+use crate::error::{DataFusionError, Result};
+use crate::execution::context::{SessionState, TaskContext};
+use crate::physical_plan::coalesce_partitions::CoalescePartitionsExec;
+use crate::physical_plan::expressions::PhysicalSortExpr;
+use crate::physical_plan::file_format::file_stream::{
+    FileOpenFuture, FileOpener, FileStream,
+};
+use crate::physical_plan::file_format::{FileMeta, SchemaAdapter};
+use crate::physical_plan::metrics::ExecutionPlanMetricsSet;
+use crate::physical_plan::{
+    DisplayFormatType, ExecutionPlan, Partitioning, SendableRecordBatchStream, Statistics,
+};
+use arrow::datatypes::SchemaRef;
+use arrow::ipc::writer::{FileWriter, StreamWriter};
+
+use futures::{StreamExt, TryStreamExt};
+use object_store::{GetResult, ObjectStore};
+use std::any::Any;
+use std::fs;
+use std::io::{Cursor, Write};
+use std::path::Path;
+use std::sync::Arc;
+use tokio::task::{self, JoinHandle};
+
+use super::FileScanConfig;
+
+/// Execution plan for scanning Arrow IPC data source
+#[derive(Debug, Clone)]
+pub struct IpcExec {
+    base_config: FileScanConfig,
+    projected_statistics: Statistics,
+    projected_schema: SchemaRef,
+    /// Execution metrics
+    metrics: ExecutionPlanMetricsSet,
+}
+
+impl IpcExec {
+    /// Create a new Arrow IPC reader execution plan provided base configurations
+    pub fn new(base_config: FileScanConfig) -> Self {
+        let (projected_schema, projected_statistics) = base_config.project();
+
+        Self {
+            base_config,
+            projected_schema,
+            projected_statistics,
+            metrics: ExecutionPlanMetricsSet::new(),
+        }
+    }
+
+    /// Ref to the base configs
+    pub fn base_config(&self) -> &FileScanConfig {
+        &self.base_config
+    }
+}
+
+impl ExecutionPlan for IpcExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.projected_schema.clone()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(self.base_config.file_groups.len())
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        None
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        Vec::new()
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        _: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        Ok(self)
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        let table_schema = Arc::clone(&self.base_config.file_schema);
+        let projection = self
+            .base_config
+            .file_column_projection_indices()
+            .unwrap_or_else(|| (0..table_schema.fields().len()).collect());
+
+        let opener = IpcOpener {
+            projection: projection.into(),
+            schema_adapter: SchemaAdapter::new(table_schema),
+        };
+
+        let stream = FileStream::new(
+            &self.base_config,
+            partition,
+            context,
+            opener,
+            self.metrics.clone(),
+        )?;
+
+        Ok(Box::pin(stream) as SendableRecordBatchStream)
+    }
+
+    fn fmt_as(
+        &self,
+        t: DisplayFormatType,
+        f: &mut std::fmt::Formatter,
+    ) -> std::fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                write!(
+                    f,
+                    "IpcExec: limit={:?}, files={}",
+                    self.base_config.limit,
+                    super::FileGroupsDisplay(&self.base_config.file_groups, t),
+                )
+            }
+        }
+    }
+
+    fn statistics(&self) -> Statistics {
+        self.projected_statistics.clone()
+    }
+}
+
+struct IpcOpener {
+    /// Projected column indices in the table schema
+    projection: Arc<[usize]>,
+    schema_adapter: SchemaAdapter,
+}
+
+impl FileOpener for IpcOpener {
+    fn open(
+        &self,
+        store: Arc<dyn ObjectStore>,
+        file_meta: FileMeta,
+    ) -> Result<FileOpenFuture> {
+        let projection = self.projection.clone();
+        let schema_adapter = self.schema_adapter.clone();
+        Ok(Box::pin(async move {
+            let stream = match store.get(file_meta.location()).await? {
+                GetResult::File(file, _) => {
+                    let file_schema = IpcBatchReader::try_new(&file, None)?.schema();
+                    let adapted =
+                        schema_adapter.map_projections(&file_schema, &projection)?;
+                    let reader = IpcBatchReader::try_new(file, Some(adapted))?;
+                    futures::stream::iter(reader).boxed()
+                }
+                r @ GetResult::Stream(_) => {
+                    let data = r.bytes().await?;
+                    let file_schema =
+                        IpcBatchReader::try_new(Cursor::new(data.clone()), None)?
+                            .schema();
+                    let adapted =
+                        schema_adapter.map_projections(&file_schema, &projection)?;
+                    let reader =
+                        IpcBatchReader::try_new(Cursor::new(data), Some(adapted))?;
+                    futures::stream::iter(reader).boxed()
+                }
+            };
+
+            Ok(stream
+                .map(move |batch| {
+                    batch.and_then(|b| {
+                        schema_adapter
+                            .adapt_batch(b, &projection)
+                            .map_err(Into::into)
+                    })
+                })
+                .boxed())
+        }))
+    }
+}
+
+/// How the results of a plan are laid out when written as Arrow IPC files
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IpcWriteMode {
+    /// Create a directory at the target path containing one file per
+    /// output partition, named `part-{i}.arrow`
+    PerPartition,
+    /// Coalesce all output partitions into a single file at the target path
+    SingleFile,
+}
+
+impl Default for IpcWriteMode {
+    fn default() -> Self {
+        Self::PerPartition
+    }
+}
+
+/// Executes `plan` and writes the results as Arrow IPC files to `path`,
+/// laid out according to `mode`
+pub async fn plan_to_ipc(
+    state: &SessionState,
+    plan: Arc<dyn ExecutionPlan>,
+    path: impl AsRef<str>,
+    mode: IpcWriteMode,
+) -> Result<()> {
+    let path = path.as_ref();
+    match mode {
+        IpcWriteMode::PerPartition => {
+            // create directory to contain the IPC files (one per partition)
+            let fs_path = Path::new(path);
+            if let Err(e) = fs::create_dir(fs_path) {
+                return Err(DataFusionError::Execution(format!(
+                    "Could not create directory {}: {:?}",
+                    path, e
+                )));
+            }
+
+            let mut tasks = vec![];
+            for i in 0..plan.output_partitioning().partition_count() {
+                let filename = format!("part-{}.arrow", i);
+                let file = fs::File::create(fs_path.join(&filename))?;
+                let writer = FileWriter::try_new(file, &plan.schema())?;
+                let task_ctx = Arc::new(TaskContext::from(state));
+                let stream = plan.execute(i, task_ctx)?;
+                let handle: JoinHandle<Result<()>> =
+                    task::spawn(write_ipc_file(stream, writer));
+                tasks.push(handle);
+            }
+            futures::future::join_all(tasks)
+                .await
+                .into_iter()
+                .try_for_each(|result| {
+                    result.map_err(|e| DataFusionError::Execution(format!("{}", e)))?
+                })?;
+            Ok(())
+        }
+        IpcWriteMode::SingleFile => {
+            let file = fs::File::create(path).map_err(|e| {
+                DataFusionError::Execution(format!(
+                    "Could not create file {}: {:?}",
+                    path, e
+                ))
+            })?;
+            let writer = FileWriter::try_new(file, &plan.schema())?;
+            let stream = execute_coalesced(state, plan)?;
+            write_ipc_file(stream, writer).await
+        }
+    }
+}
+
+/// Executes `plan` and writes the results, coalesced into a single stream,
+/// to `writer` in the Arrow IPC streaming format.
+///
+/// Batches are written as soon as they are produced, so `writer` may be a
+/// socket or any other sink that consumes the data incrementally.
+pub async fn plan_to_ipc_stream<W: Write>(
+    state: &SessionState,
+    plan: Arc<dyn ExecutionPlan>,
+    writer: W,
+) -> Result<()> {
+    let mut writer = StreamWriter::try_new(writer, &plan.schema())?;
+    let mut stream = execute_coalesced(state, plan)?;
+    while let Some(batch) = stream.next().await {
+        writer.write(&batch?)?;
+    }
+    writer.finish()?;
+    Ok(())
+}
+
+/// Executes all partitions of `plan` as a single stream
+fn execute_coalesced(
+    state: &SessionState,
+    plan: Arc<dyn ExecutionPlan>,
+) -> Result<SendableRecordBatchStream> {
+    let plan: Arc<dyn ExecutionPlan> =
+        if plan.output_partitioning().partition_count() == 1 {
+            plan
+        } else {
+            Arc::new(CoalescePartitionsExec::new(plan))
+        };
+    plan.execute(0, Arc::new(TaskContext::from(state)))
+}
+
+async fn write_ipc_file(
+    stream: SendableRecordBatchStream,
+    mut writer: FileWriter<fs::File>,
+) -> Result<()> {
+    stream
+        .map(|batch| writer.write(&batch?))
+        .try_collect::<()>()
+        .await
+        .map_err(DataFusionError::from)?;
+    writer.finish()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_batches_eq;
+    use crate::datasource::{MemTable, TableProvider};
+    use crate::prelude::*;
+    use arrow::array::{ArrayRef, DictionaryArray, Int32Array, StringArray, StructArray};
+    use arrow::compute::concat_batches;
+    use arrow::datatypes::{DataType, Field, Int32Type, Schema};
+    use arrow::ipc::reader::{FileReader, StreamReader};
+    use arrow::record_batch::RecordBatch;
+    use std::fs::File;
+    use tempfile::TempDir;
+
+    fn write_ipc_files(dir: &TempDir) -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("name", DataType::Utf8, true),
+            Field::new("score", DataType::Int32, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int32Array::from(vec![1, 2, 3])),
+                Arc::new(StringArray::from(vec![Some("a"), None, Some("c")])),
+                Arc::new(Int32Array::from(vec![Some(10), Some(20), None])),
+            ],
+        )?;
+
+        let mut writer =
+            FileWriter::try_new(File::create(dir.path().join("a.arrow"))?, &schema)?;
+        writer.write(&batch)?;
+        writer.finish()?;
+
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int32Array::from(vec![4, 5])),
+                Arc::new(StringArray::from(vec![Some("d"), Some("e")])),
+                Arc::new(Int32Array::from(vec![Some(40), Some(50)])),
+            ],
+        )?;
+        let mut writer =
+            StreamWriter::try_new(File::create(dir.path().join("b.arrow"))?, &schema)?;
+        writer.write(&batch)?;
+        writer.finish()?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn register_ipc_and_query_with_projection() -> Result<()> {
+        let dir = TempDir::new()?;
+        write_ipc_files(&dir)?;
+
+        let ctx = SessionContext::new();
+        ctx.register_ipc("t", dir.path().to_str().unwrap(), IpcReadOptions::default())
+            .await?;
+
+        let batches = ctx
+            .sql("SELECT name, id FROM t WHERE id > 1 ORDER BY id")
+            .await?
+            .collect()
+            .await?;
+
+        let expected = vec![
+            "+------+----+",
+            "| name | id |",
+            "+------+----+",
+            "|      | 2  |",
+            "| c    | 3  |",
+            "| d    | 4  |",
+            "| e    | 5  |",
+            "+------+----+",
+        ];
+        assert_batches_eq!(expected, &batches);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn create_external_table_stored_as_arrow() -> Result<()> {
+        let dir = TempDir::new()?;
+        write_ipc_files(&dir)?;
+
+        let ctx = SessionContext::new();
+        ctx.sql(&format!(
+            "CREATE EXTERNAL TABLE t STORED AS ARROW LOCATION '{}'",
+            dir.path().to_str().unwrap()
+        ))
+        .await?;
+
+        let batches = ctx
+            .sql("SELECT sum(score) AS total, count(*) AS n FROM t")
+            .await?
+            .collect()
+            .await?;
+
+        let expected = vec![
+            "+-------+---+",
+            "| total | n |",
+            "+-------+---+",
+            "| 120   | 5 |",
+            "+-------+---+",
+        ];
+        assert_batches_eq!(expected, &batches);
+
+        let batches = ctx.sql("SELECT id FROM t LIMIT 2").await?.collect().await?;
+        let rows: usize = batches.iter().map(|b| b.num_rows()).sum();
+        assert_eq!(rows, 2);
+
+        Ok(())
+    }
+
+    /// A table of two partitions with struct and dictionary columns
+    fn nested_table() -> Result<MemTable> {
+        let struct_fields = vec![
+            Field::new("x", DataType::Int32, false),
+            Field::new("y", DataType::Utf8, true),
+        ];
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("s", DataType::Struct(struct_fields.clone()), false),
+            Field::new(
+                "d",
+                DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8)),
+                true,
+            ),
+        ]));
+
+        let batch = |ids: Vec<i32>, ys: Vec<Option<&str>>, ds: Vec<Option<&str>>| {
+            let s = StructArray::from(vec![
+                (
+                    struct_fields[0].clone(),
+                    Arc::new(Int32Array::from(
+                        ids.iter().map(|i| i * 10).collect::<Vec<_>>(),
+                    )) as ArrayRef,
+                ),
+                (
+                    struct_fields[1].clone(),
+                    Arc::new(StringArray::from(ys)) as ArrayRef,
+                ),
+            ]);
+            let d: DictionaryArray<Int32Type> = ds.into_iter().collect();
+            RecordBatch::try_new(
+                schema.clone(),
+                vec![Arc::new(Int32Array::from(ids)), Arc::new(s), Arc::new(d)],
+            )
+        };
+
+        let partitions = vec![
+            vec![batch(
+                vec![1, 2, 3],
+                vec![Some("a"), None, Some("c")],
+                vec![Some("p"), Some("q"), Some("p")],
+            )?],
+            vec![batch(
+                vec![4, 5],
+                vec![Some("d"), Some("e")],
+                vec![Some("p"), None],
+            )?],
+        ];
+        Ok(MemTable::try_new(schema.clone(), partitions)?)
+    }
+
+    /// All rows of `table`, ordered by `id`, as a single batch
+    async fn sorted_rows(ctx: &SessionContext, table: &str) -> Result<RecordBatch> {
+        let df = ctx
+            .sql(&format!("SELECT * FROM {} ORDER BY id", table))
+            .await?;
+        let schema = Arc::new(Schema::from(df.schema()));
+        Ok(concat_batches(&schema, &df.collect().await?)?)
+    }
+
+    #[tokio::test]
+    async fn write_ipc_per_partition() -> Result<()> {
+        let dir = TempDir::new()?;
+        let out_dir = dir.path().join("out");
+        let ctx = SessionContext::new();
+        let table = nested_table()?;
+        let schema = table.schema();
+        ctx.register_table("source", Arc::new(table))?;
+
+        ctx.table("source")?
+            .write_ipc(out_dir.to_str().unwrap(), IpcWriteMode::PerPartition)
+            .await?;
+
+        let plan = ctx.table("source")?.create_physical_plan().await?;
+        for i in 0..plan.output_partitioning().partition_count() {
+            let file = File::open(out_dir.join(format!("part-{}.arrow", i)))?;
+            let reader = FileReader::try_new(file, None)?;
+            assert_eq!(reader.schema(), schema);
+        }
+
+        ctx.register_ipc(
+            "written",
+            out_dir.to_str().unwrap(),
+            IpcReadOptions::default(),
+        )
+        .await?;
+        assert_eq!(
+            sorted_rows(&ctx, "written").await?,
+            sorted_rows(&ctx, "source").await?
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn write_ipc_single_file() -> Result<()> {
+        let dir = TempDir::new()?;
+        let out_file = dir.path().join("out.arrow");
+        let ctx = SessionContext::new();
+        let table = nested_table()?;
+        let schema = table.schema();
+        ctx.register_table("source", Arc::new(table))?;
+
+        let plan = ctx.table("source")?.create_physical_plan().await?;
+        ctx.write_ipc(plan, out_file.to_str().unwrap(), IpcWriteMode::SingleFile)
+            .await?;
+
+        let reader = FileReader::try_new(File::open(&out_file)?, None)?;
+        assert_eq!(reader.schema(), schema);
+        let rows: usize = reader.map(|batch| batch.unwrap().num_rows()).sum();
+        assert_eq!(rows, 5);
+
+        ctx.register_ipc(
+            "written",
+            out_file.to_str().unwrap(),
+            IpcReadOptions::default(),
+        )
+        .await?;
+        assert_eq!(
+            sorted_rows(&ctx, "written").await?,
+            sorted_rows(&ctx, "source").await?
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn write_ipc_stream_to_writer() -> Result<()> {
+        let ctx = SessionContext::new();
+        let table = nested_table()?;
+        let schema = table.schema();
+        ctx.register_table("source", Arc::new(table))?;
+
+        let mut buf = vec![];
+        ctx.table("source")?.write_ipc_stream(&mut buf).await?;
+
+        let reader = StreamReader::try_new(Cursor::new(buf), None)?;
+        assert_eq!(reader.schema(), schema);
+        let ids: Vec<i32> = reader
+            .flat_map(|batch| {
+                let batch = batch.unwrap();
+                let ids = batch
+                    .column(0)
+                    .as_any()
+                    .downcast_ref::<Int32Array>()
+                    .unwrap();
+                ids.values().to_vec()
+            })
+            .collect();
+        let mut sorted_ids = ids.clone();
+        sorted_ids.sort_unstable();
+        assert_eq!(sorted_ids, vec![1, 2, 3, 4, 5]);
+
+        Ok(())
+    }
+}
@@ -16,32 +16,61 @@
 // under the License.
 
 //! Execution plan for reading line-delimited JSON files
+use crate::config::ConfigOptions;
 use crate::datasource::file_format::file_type::FileCompressionType;
+use crate::datasource::file_format::json::{JsonFormat, DEFAULT_JSON_EXTENSION};
+use crate::datasource::file_format::FileFormat;
+use crate::datasource::listing::{
+    split_files, FileRange, ListingTableUrl, PartitionedFile,
+};
 use crate::error::{DataFusionError, Result};
 use crate::execution::context::SessionState;
 use crate::execution::context::TaskContext;
+use crate::execution::runtime_env::RuntimeEnv;
+use crate::physical_plan::common::AbortOnDropSingle;
 use crate::physical_plan::expressions::PhysicalSortExpr;
 use crate::physical_plan::file_format::delimited_stream::newline_delimited_stream;
 use crate::physical_plan::file_format::file_stream::{
     FileOpenFuture, FileOpener, FileStream,
 };
+use crate::physical_plan::file_format::json_reader::{
+    FileProgress, InFlightLimit, JsonBatchReader, JsonEncoding, JsonProgress,
+    JsonReadOffsets, JsonRecordCounter, JsonRecordValidator, JsonScanOptions,
+    JsonValidation, JsonValidationSummary, ROW_ID_COLUMN,
+};
 use crate::physical_plan::file_format::FileMeta;
-use crate::physical_plan::metrics::ExecutionPlanMetricsSet;
+use crate::physical_plan::metrics::{
+    Count, ExecutionPlanMetricsSet, MemTrackingMetrics, MetricBuilder, MetricsSet,
+};
+use crate::physical_plan::sorts::sort_preserving_merge::SortPreservingMergeStream;
+use crate::physical_plan::sorts::SortedStream;
+use crate::physical_plan::stream::RecordBatchStreamAdapter;
 use crate::physical_plan::{
     DisplayFormatType, ExecutionPlan, Partitioning, SendableRecordBatchStream, Statistics,
 };
-use arrow::json::reader::DecoderOptions;
+use arrow::array::{ArrayRef, Int64Array};
+use arrow::datatypes::{DataType, Field, Schema};
+use arrow::error::{ArrowError, Result as ArrowResult};
+use arrow::record_batch::{RecordBatch, RecordBatchOptions};
 use arrow::{datatypes::SchemaRef, json};
 
-use bytes::Buf;
+use bytes::{Buf, Bytes, BytesMut};
 
+use futures::stream::BoxStream;
 use futures::{StreamExt, TryStreamExt};
 use object_store::{GetResult, ObjectStore};
+use parking_lot::Mutex;
 use std::any::Any;
+use std::collections::{HashMap, VecDeque};
 use std::fs;
+use std::io::{BufRead, BufReader, Read};
 use std::path::Path;
+use std::sync::atomic::{AtomicI64, AtomicUsize, Ordering};
 use std::sync::Arc;
 use tokio::task::{self, JoinHandle};
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_util::io::{StreamReader, SyncIoBridge};
+use tracing::{Instrument, Span};
 
 use super::FileScanConfig;
 
@@ -54,6 +83,11 @@ pub struct NdJsonExec {
     /// Execution metrics
     metrics: ExecutionPlanMetricsSet,
     file_compression_type: FileCompressionType,
+    scan_options: Arc<JsonScanOptions>,
+    /// The order of the rows of every file, see [`Self::with_output_ordering`]
+    output_ordering: Option<Vec<PhysicalSortExpr>>,
+    /// The first bytes of files fetched ahead of the scan, see [`Self::prefetch`]
+    prefetched: Arc<Prefetched>,
 }
 
 impl NdJsonExec {
@@ -70,7 +104,227 @@ impl NdJsonExec {
             projected_statistics,
             metrics: ExecutionPlanMetricsSet::new(),
             file_compression_type,
+            scan_options: Arc::new(JsonScanOptions::default()),
+            output_ordering: None,
+            prefetched: Default::default(),
+        }
+    }
+
+    /// Declare that the rows of every file are sorted by `ordering`, over
+    /// the projected schema of the scan. A partition reading several files
+    /// merges them as they are read, so that all of its rows are in this
+    /// order, only keeping the current batch of every file in memory rather
+    /// than sorting them.
+    pub fn with_output_ordering(mut self, ordering: Vec<PhysicalSortExpr>) -> Self {
+        self.output_ordering = Some(ordering);
+        self
+    }
+
+    /// Set the [`JsonScanOptions`] used to decode the files
+    pub fn with_scan_options(mut self, scan_options: JsonScanOptions) -> Self {
+        self.scan_options = Arc::new(scan_options);
+        self
+    }
+
+    /// Starts fetching the first `max_bytes` bytes of the first file of
+    /// every partition in the background, from the object store of the scan
+    /// registered in `runtime`, so that executing the plan starts decoding
+    /// them without waiting for the store. This is meant to be called on a
+    /// tokio runtime while the rest of the query is planned.
+    ///
+    /// The bytes are only read by the first execution of every partition,
+    /// and the fetches not read yet are cancelled when the plan is dropped.
+    /// Byte range scans, incremental scans and scans only producing
+    /// partition values fetch nothing ahead.
+    pub fn prefetch(&self, runtime: &RuntimeEnv, max_bytes: usize) -> Result<()> {
+        if max_bytes == 0
+            || self.scan_options.read_offsets.is_some()
+            || self.scan_options.partition_values_only
+        {
+            return Ok(());
+        }
+        let handle = tokio::runtime::Handle::try_current().map_err(|e| {
+            DataFusionError::Execution(format!(
+                "Prefetching JSON files requires a tokio runtime: {}",
+                e
+            ))
+        })?;
+        let store = runtime.object_store(&self.base_config.object_store_url)?;
+
+        let mut prefetched = self.prefetched.files.lock();
+        let first_files = self
+            .base_config
+            .file_groups
+            .iter()
+            .filter_map(|files| files.first());
+        for file in first_files {
+            let location = &file.object_meta.location;
+            let size = file.object_meta.size;
+            if file.range.is_some() || size == 0 || prefetched.contains_key(location) {
+                continue;
+            }
+            let store = store.clone();
+            let fetched = location.clone();
+            let fetch = handle.spawn(async move {
+                store.get_range(&fetched, 0..max_bytes.min(size)).await
+            });
+            prefetched.insert(location.clone(), AbortOnDropSingle::new(fetch));
+        }
+        Ok(())
+    }
+
+    /// Ref to the base configs
+    pub fn base_config(&self) -> &FileScanConfig {
+        &self.base_config
+    }
+
+    /// The [`JsonScanOptions`] used to decode the files
+    pub fn scan_options(&self) -> &JsonScanOptions {
+        &self.scan_options
+    }
+
+    /// The compression of the files
+    pub fn file_compression_type(&self) -> &FileCompressionType {
+        &self.file_compression_type
+    }
+
+    /// Redistributes the files of the scan into up to `target_partitions`
+    /// groups of similar size, splitting the uncompressed files of at least
+    /// `repartition_file_min_size` bytes into byte ranges. Smaller files are
+    /// always read whole, as splitting them costs more than it gains.
+    ///
+    /// Returns `None` if no file can be split, or the scan reads its files
+    /// incrementally, only produces their partition values or only
+    /// validates them.
+    pub fn get_repartitioned(
+        &self,
+        target_partitions: usize,
+        repartition_file_min_size: usize,
+    ) -> Option<Self> {
+        if self.scan_options.read_offsets.is_some()
+            || self.scan_options.partition_values_only
+            || self.scan_options.validation.is_some()
+        {
+            return None;
+        }
+        let files = self.base_config.file_groups.iter().flatten();
+        if files.clone().any(|file| file.range.is_some()) {
+            return None;
+        }
+
+        let splittable = |file: &PartitionedFile| {
+            let compression = if self.scan_options.detect_compression {
+                FileCompressionType::from_path(file.object_meta.location.as_ref())
+            } else {
+                self.file_compression_type.to_owned()
+            };
+            compression == FileCompressionType::UNCOMPRESSED
+                && self.scan_options.encoding == JsonEncoding::Utf8
+                && file.object_meta.size >= repartition_file_min_size.max(1)
+        };
+        if !files.clone().any(splittable) {
+            return None;
+        }
+
+        // lay the files end to end and cut them every `target_size` bytes,
+        // a piece of a file being read by the partition its start falls in
+        let total_size: usize = files.clone().map(|file| file.object_meta.size).sum();
+        let target_size =
+            ((total_size + target_partitions - 1) / target_partitions).max(1);
+        let mut file_groups = vec![vec![]; target_partitions];
+        let mut offset = 0;
+        for file in files {
+            let size = file.object_meta.size;
+            if splittable(file) {
+                let mut start = 0;
+                while start < size {
+                    let partition = (offset + start) / target_size;
+                    let end = ((partition + 1) * target_size - offset).min(size);
+                    let range = FileRange {
+                        start: start as i64,
+                        end: end as i64,
+                    };
+                    file_groups[partition.min(target_partitions - 1)].push(
+                        PartitionedFile {
+                            range: Some(range),
+                            ..file.clone()
+                        },
+                    );
+                    start = end;
+                }
+            } else {
+                let partition = (offset / target_size).min(target_partitions - 1);
+                file_groups[partition].push(file.clone());
+            }
+            offset += size;
+        }
+        file_groups.retain(|group| !group.is_empty());
+
+        let mut plan = self.clone();
+        plan.base_config.file_groups = file_groups;
+        Some(plan)
+    }
+
+    /// Returns the scan only counting the records of its files rather than
+    /// decoding them, producing batches with a row count but no columns. The
+    /// files already prefetched are read by the returned scan.
+    ///
+    /// Returns `None` if counting the lines of the files may give another
+    /// number of rows than decoding them, such as when invalid lines are
+    /// skipped, or lines are rejected before being decoded.
+    pub fn get_count_only(&self) -> Option<Self> {
+        if self.scan_options.partition_values_only
+            || !self.scan_options.can_count_records()
+        {
+            return None;
+        }
+
+        let mut plan = self.clone();
+        plan.base_config.projection = Some(vec![]);
+        let (projected_schema, projected_statistics) = plan.base_config.project();
+        plan.projected_schema = projected_schema;
+        plan.projected_statistics = projected_statistics;
+        plan.scan_options =
+            Arc::new(self.scan_options.as_ref().clone().with_count_only(true));
+        // the ordering is over columns that are no longer projected
+        plan.output_ordering = None;
+        Some(plan)
+    }
+
+    /// Validates every record of the files of the scan against its file
+    /// schema, decoding them as the scan would without keeping any column,
+    /// and returns the rows found invalid with the first `max_errors`
+    /// errors.
+    ///
+    /// Invalid rows are reported rather than skipped or failing the scan,
+    /// whatever the options of the scan.
+    pub async fn validate(
+        &self,
+        context: Arc<TaskContext>,
+        max_errors: usize,
+    ) -> Result<JsonValidationSummary> {
+        let validation = Arc::new(JsonValidation::new(max_errors));
+        let mut plan = self.clone();
+        plan.base_config.projection = Some(vec![]);
+        plan.base_config.limit = None;
+        let (projected_schema, projected_statistics) = plan.base_config.project();
+        plan.projected_schema = projected_schema;
+        plan.projected_statistics = projected_statistics;
+        plan.scan_options = Arc::new(
+            self.scan_options
+                .as_ref()
+                .clone()
+                .with_count_only(false)
+                .with_validation(validation.clone()),
+        );
+        plan.output_ordering = None;
+
+        for partition in 0..plan.output_partitioning().partition_count() {
+            plan.execute(partition, context.clone())?
+                .try_for_each(|_| futures::future::ready(Ok(())))
+                .await?;
         }
+        Ok(validation.summary())
     }
 }
 
@@ -88,7 +342,7 @@ impl ExecutionPlan for NdJsonExec {
     }
 
     fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
-        None
+        self.output_ordering.as_deref()
     }
 
     fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
@@ -107,24 +361,128 @@ impl ExecutionPlan for NdJsonExec {
         partition: usize,
         context: Arc<TaskContext>,
     ) -> Result<SendableRecordBatchStream> {
-        let proj = self.base_config.projected_file_column_names();
+        if self.scan_options.count_only && !self.projected_schema.fields().is_empty() {
+            return Err(DataFusionError::Plan(
+                "JSON scans that only count records can not project any column"
+                    .to_string(),
+            ));
+        }
+        if self.scan_options.validation.is_some() {
+            if !self.projected_schema.fields().is_empty() {
+                return Err(DataFusionError::Plan(
+                    "JSON scans that only validate records can not project any column"
+                        .to_string(),
+                ));
+            }
+            if self.scan_options.read_offsets.is_some()
+                || self.scan_options.partition_values_only
+            {
+                return Err(DataFusionError::Plan(
+                    "JSON scans that only validate records must read whole files"
+                        .to_string(),
+                ));
+            }
+        }
+        let mut projection = self.base_config.projected_file_column_names();
+        if self.scan_options.partition_values_only {
+            let projects_file_columns = match &projection {
+                Some(projection) => !projection.is_empty(),
+                None => !self.base_config.file_schema.fields().is_empty(),
+            };
+            if projects_file_columns {
+                return Err(DataFusionError::Plan(
+                    "JSON scans that only produce partition values can not project file columns"
+                        .to_string(),
+                ));
+            }
+        }
+        self.scan_options.parse_default_timezone()?;
+        self.scan_options.check_comment_prefix()?;
 
         let batch_size = context.session_config().batch_size();
-        let file_schema = Arc::clone(&self.base_config.file_schema);
-
-        let options = DecoderOptions::new().with_batch_size(batch_size);
-        let options = if let Some(proj) = proj {
-            options.with_projection(proj)
+        let mut file_schema = Arc::clone(&self.base_config.file_schema);
+        let mut scan_options = self.scan_options.clone();
+        let mut row_ids = None;
+        // a file field named like the row id column is read from the files
+        // unless row ids are added
+        let row_id_index = if scan_options.row_id {
+            file_schema.index_of(ROW_ID_COLUMN).ok()
         } else {
-            options
+            None
         };
+        if let Some(file_index) = row_id_index {
+            // the row id column is not read from the files but added to the
+            // decoded batches, at its position among the projected columns
+            let index = match &mut projection {
+                Some(projection) => {
+                    let index = projection.iter().position(|name| name == ROW_ID_COLUMN);
+                    projection.retain(|name| name != ROW_ID_COLUMN);
+                    index
+                }
+                None => Some(file_index),
+            };
+            let mut fields = file_schema.fields().clone();
+            fields.remove(file_index);
+            file_schema = Arc::new(Schema::new_with_metadata(
+                fields,
+                file_schema.metadata().clone(),
+            ));
+
+            if let Some(index) = index {
+                row_ids = Some(Arc::new(RowIds::try_new(partition, index)?));
+                // records only need to be counted if no other column is read
+                let reads_columns = match &projection {
+                    Some(projection) => !projection.is_empty(),
+                    None => !file_schema.fields().is_empty(),
+                };
+                if !reads_columns && scan_options.can_count_records() {
+                    scan_options =
+                        Arc::new(scan_options.as_ref().clone().with_count_only(true));
+                }
+            }
+        }
+        if let Some(deadline) = context.deadline() {
+            scan_options =
+                Arc::new(scan_options.as_ref().clone().with_deadline(deadline));
+        }
+
+        let max_bytes = self.scan_options.max_in_flight_bytes;
+        let max_batches = self.scan_options.max_batches_in_flight;
+        let in_flight = (max_bytes.is_some() || max_batches.is_some()).then(|| {
+            let peak = MetricBuilder::new(&self.metrics)
+                .gauge("peak_in_flight_bytes", partition);
+            let peak_batches = MetricBuilder::new(&self.metrics)
+                .gauge("peak_batches_in_flight", partition);
+            Arc::new(InFlightLimit::new(
+                max_bytes,
+                max_batches,
+                peak,
+                peak_batches,
+            ))
+        });
+        let decoded_arrays =
+            MetricBuilder::new(&self.metrics).counter("decoded_arrays", partition);
 
         let opener = JsonOpener {
+            batch_size,
+            projection,
             file_schema,
-            options,
             file_compression_type: self.file_compression_type.to_owned(),
+            scan_options,
+            in_flight,
+            decoded_arrays,
+            row_ids,
+            partition,
+            span: tracing::debug_span!("json_scan", partition),
+            prefetched: self.prefetched.clone(),
         };
 
+        if let Some(ordering) = &self.output_ordering {
+            if self.base_config.file_groups[partition].len() > 1 {
+                return self.execute_merged(partition, context, opener, ordering);
+            }
+        }
+
         let stream = FileStream::new(
             &self.base_config,
             partition,
@@ -142,12 +500,12 @@ impl ExecutionPlan for NdJsonExec {
         f: &mut std::fmt::Formatter,
     ) -> std::fmt::Result {
         match t {
-            DisplayFormatType::Default => {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
                 write!(
                     f,
                     "JsonExec: limit={:?}, files={}",
                     self.base_config.limit,
-                    super::FileGroupsDisplay(&self.base_config.file_groups),
+                    super::FileGroupsDisplay(&self.base_config.file_groups, t),
                 )
             }
         }
@@ -156,12 +514,188 @@ impl ExecutionPlan for NdJsonExec {
     fn statistics(&self) -> Statistics {
         self.projected_statistics.clone()
     }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        Some(self.metrics.clone_inner())
+    }
 }
 
+impl FileScanConfig {
+    /// Create the config of a scan of the newline delimited JSON files at
+    /// `path` in `store`, with the schema `format` infers from them.
+    ///
+    /// `path` is parsed as a [`ListingTableUrl`], so that it may be a file,
+    /// a directory whose files are listed recursively, or a glob such as
+    /// `data/*/events-*.json`. Only the files whose name ends with
+    /// [`DEFAULT_JSON_EXTENSION`], optionally followed by the extension of
+    /// a compression, are scanned, sorted by path and split into up to
+    /// `target_partitions` groups. Fails if there is no such file.
+    pub async fn try_from_path(
+        path: impl AsRef<str>,
+        store: Arc<dyn ObjectStore>,
+        format: &JsonFormat,
+        target_partitions: usize,
+    ) -> Result<Self> {
+        let table_path = ListingTableUrl::parse(path.as_ref())?;
+        let mut files: Vec<_> = table_path
+            .list_all_files(store.as_ref(), DEFAULT_JSON_EXTENSION, true)
+            .try_collect()
+            .await?;
+        if files.is_empty() {
+            return Err(DataFusionError::Plan(format!(
+                "No JSON files found at '{}'",
+                path.as_ref()
+            )));
+        }
+        files.sort_by(|a, b| a.location.cmp(&b.location));
+
+        let file_schema = format.infer_schema(&store, &files).await?;
+        let file_groups = split_files(
+            files.into_iter().map(PartitionedFile::from).collect(),
+            target_partitions.max(1),
+        );
+
+        Ok(Self {
+            object_store_url: table_path.object_store(),
+            file_schema,
+            file_groups,
+            statistics: Statistics::default(),
+            projection: None,
+            limit: None,
+            table_partition_cols: vec![],
+            config_options: ConfigOptions::new().into_shareable(),
+        })
+    }
+}
+
+impl NdJsonExec {
+    /// Merges the sorted files of `partition`, each read by its own
+    /// [`FileStream`], into a single stream sorted by `ordering`
+    fn execute_merged(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+        opener: JsonOpener,
+        ordering: &[PhysicalSortExpr],
+    ) -> Result<SendableRecordBatchStream> {
+        let batch_size = context.session_config().batch_size();
+        let streams = self.base_config.file_groups[partition]
+            .iter()
+            .enumerate()
+            .map(|(file_index, file)| {
+                // the file is placed at the index of the partition, so that
+                // the metrics of its stream are those of the partition
+                let mut file_groups = vec![vec![]; partition + 1];
+                file_groups[partition] = vec![file.clone()];
+                let config = FileScanConfig {
+                    file_groups,
+                    ..self.base_config.clone()
+                };
+                let mut opener = opener.clone();
+                opener.in_flight = opener
+                    .in_flight
+                    .map(|in_flight| Arc::new(in_flight.new_independent()));
+                // the ids are assigned before the files are merged, so that
+                // they do not depend on the order the merge reads them in
+                opener.row_ids = match &opener.row_ids {
+                    Some(row_ids) => Some(Arc::new(row_ids.for_file(file_index)?)),
+                    None => None,
+                };
+                let stream = FileStream::new(
+                    &config,
+                    partition,
+                    context.clone(),
+                    opener,
+                    self.metrics.clone(),
+                )?;
+                Ok(SortedStream::new(Box::pin(stream), 0))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        // the output rows are already counted by the file streams
+        let tracking_metrics =
+            MemTrackingMetrics::new(&ExecutionPlanMetricsSet::new(), partition);
+        let merged: SendableRecordBatchStream =
+            Box::pin(SortPreservingMergeStream::new_from_streams(
+                streams,
+                self.projected_schema.clone(),
+                ordering,
+                tracking_metrics,
+                batch_size,
+            )?);
+
+        // every file stream is limited, but not their merge
+        Ok(match self.base_config.limit {
+            Some(limit) => {
+                let limited = merged.scan(limit, |remain, batch| {
+                    let batch = match batch {
+                        Ok(_) if *remain == 0 => return futures::future::ready(None),
+                        Ok(batch) => {
+                            let batch = batch.slice(0, batch.num_rows().min(*remain));
+                            *remain -= batch.num_rows();
+                            Ok(batch)
+                        }
+                        Err(e) => Err(e),
+                    };
+                    futures::future::ready(Some(batch))
+                });
+                Box::pin(RecordBatchStreamAdapter::new(
+                    self.projected_schema.clone(),
+                    limited,
+                ))
+            }
+            None => merged,
+        })
+    }
+}
+
+#[derive(Clone)]
 struct JsonOpener {
-    options: DecoderOptions,
+    batch_size: usize,
+    projection: Option<Vec<String>>,
     file_schema: SchemaRef,
     file_compression_type: FileCompressionType,
+    scan_options: Arc<JsonScanOptions>,
+    /// Limits the batches decoded ahead of being consumed, shared by all
+    /// the files of a partition
+    in_flight: Option<Arc<InFlightLimit>>,
+    /// The number of arrays decoded from the files
+    decoded_arrays: Count,
+    /// Assigns the ids of the records of the partition if the row id column
+    /// is projected, see [`JsonScanOptions::row_id`]
+    row_ids: Option<Arc<RowIds>>,
+    /// The partition the files are opened for
+    partition: usize,
+    /// The span of the scan of the partition, the parent of the spans of
+    /// opening and reading its files
+    span: Span,
+    /// The first bytes of files fetched ahead, see [`NdJsonExec::prefetch`]
+    prefetched: Arc<Prefetched>,
+}
+
+/// The fetch of the first bytes of a file, cancelled when dropped
+type PrefetchedBytes = AbortOnDropSingle<object_store::Result<Bytes>>;
+
+/// The first bytes of files fetched in the background ahead of their scan,
+/// see [`NdJsonExec::prefetch`]
+#[derive(Default)]
+struct Prefetched {
+    files: Mutex<HashMap<object_store::path::Path, PrefetchedBytes>>,
+}
+
+impl Prefetched {
+    /// Takes the fetch of the first bytes of the file at `location`, if any
+    fn take(&self, location: &object_store::path::Path) -> Option<PrefetchedBytes> {
+        self.files.lock().remove(location)
+    }
+}
+
+impl std::fmt::Debug for Prefetched {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Prefetched")
+            .field("files", &self.files.lock().keys().collect::<Vec<_>>())
+            .finish()
+    }
 }
 
 impl FileOpener for JsonOpener {
@@ -170,41 +704,753 @@ impl FileOpener for JsonOpener {
         store: Arc<dyn ObjectStore>,
         file_meta: FileMeta,
     ) -> Result<FileOpenFuture> {
-        let options = self.options.clone();
+        let batch_size = self.batch_size;
+        let projection = self.projection.clone();
         let schema = self.file_schema.clone();
-        let file_compression_type = self.file_compression_type.to_owned();
-        Ok(Box::pin(async move {
-            match store.get(file_meta.location()).await? {
-                GetResult::File(file, _) => {
-                    let decoder = file_compression_type.convert_read(file);
-                    let reader = json::Reader::new(decoder, schema.clone(), options);
-                    Ok(futures::stream::iter(reader).boxed())
+        let file_compression_type = if self.scan_options.detect_compression {
+            FileCompressionType::from_path(file_meta.location().as_ref())
+        } else {
+            self.file_compression_type.to_owned()
+        };
+        let scan_options = self.scan_options.clone();
+        let in_flight = self.in_flight.clone();
+        let decoded_arrays = self.decoded_arrays.clone();
+        let row_ids = self.row_ids.clone();
+        let prefetched = self.prefetched.take(file_meta.location());
+        let progress = self.scan_options.progress.clone().map(|progress| {
+            Arc::new(ProgressReporter {
+                progress,
+                location: file_meta.location().clone(),
+                bytes_read: AtomicUsize::new(0),
+            })
+        });
+        let reporter = progress.clone();
+        let open_span = tracing::debug_span!(
+            parent: &self.span,
+            "json_open",
+            path = %file_meta.location(),
+            partition = self.partition,
+        );
+        let read_span = tracing::debug_span!(
+            parent: &self.span,
+            "json_read",
+            path = %file_meta.location(),
+            partition = self.partition,
+        );
+        let stream: FileOpenFuture = Box::pin(async move {
+            let add_bytes_read = move |bytes: usize| {
+                if let Some(reporter) = &reporter {
+                    reporter.bytes_read.fetch_add(bytes, Ordering::Relaxed);
                 }
-                GetResult::Stream(s) => {
-                    let s = s.map_err(Into::into);
-                    let decoder = file_compression_type.convert_stream(s);
-
-                    Ok(newline_delimited_stream(decoder)
-                        .map_ok(move |bytes| {
-                            let reader = json::Reader::new(
-                                bytes.reader(),
-                                schema.clone(),
-                                options.clone(),
-                            );
-                            futures::stream::iter(reader)
+            };
+
+            if scan_options.partition_values_only {
+                // all records of a file have the same partition values, which
+                // are added to this row by the file stream, if it has any
+                if file_meta.object_meta.size == 0
+                    || !has_records(
+                        store.as_ref(),
+                        file_meta.location(),
+                        &file_compression_type,
+                        &scan_options,
+                    )
+                    .await?
+                {
+                    return Ok(futures::stream::empty().boxed());
+                }
+                let options = RecordBatchOptions::new().with_row_count(Some(1));
+                let batch = RecordBatch::try_new_with_options(
+                    Arc::new(Schema::empty()),
+                    vec![],
+                    &options,
+                );
+                return Ok(futures::stream::once(async { batch }).boxed());
+            }
+
+            if let Some(read_offsets) = scan_options.read_offsets.clone() {
+                if file_compression_type != FileCompressionType::UNCOMPRESSED {
+                    return Err(DataFusionError::NotImplemented(
+                        "Incremental JSON scans of compressed files are not supported"
+                            .to_string(),
+                    ));
+                }
+                if scan_options.encoding != JsonEncoding::Utf8 {
+                    return Err(DataFusionError::NotImplemented(
+                        "Incremental JSON scans of files not encoded in UTF-8 are not supported"
+                            .to_string(),
+                    ));
+                }
+
+                let location = file_meta.location().clone();
+                let size = file_meta.object_meta.size;
+                // the first bytes of the file tell whether it is still the
+                // file whose offset was recorded
+                let prefix = if size > 0 {
+                    store
+                        .get_range(&location, 0..size.min(READ_OFFSET_PREFIX_LEN))
+                        .await?
+                } else {
+                    Bytes::new()
+                };
+                let start = read_offsets.resume_offset(&location, size, &prefix);
+
+                return Ok(read_appended_lines(
+                    AppendedLines {
+                        store,
+                        location,
+                        prefix,
+                        read_offsets,
+                        fetched: start,
+                        size,
+                        partial: BytesMut::new(),
+                        pending: VecDeque::new(),
+                        current: None,
+                        failed: false,
+                        add_bytes_read: Box::new(add_bytes_read),
+                    },
+                    schema,
+                    batch_size,
+                    projection,
+                    scan_options,
+                ));
+            }
+
+            // an empty file has no records, and may lack the header its
+            // decompression would expect
+            if file_meta.object_meta.size == 0 {
+                return Ok(futures::stream::empty().boxed());
+            }
+
+            if let Some(range) = &file_meta.range {
+                if scan_options.validation.is_some() {
+                    return Err(DataFusionError::NotImplemented(
+                        "Validating byte ranges of JSON files is not supported"
+                            .to_string(),
+                    ));
+                }
+                if file_compression_type != FileCompressionType::UNCOMPRESSED {
+                    return Err(DataFusionError::NotImplemented(
+                        "Byte range scans of compressed JSON files are not supported"
+                            .to_string(),
+                    ));
+                }
+                if scan_options.encoding != JsonEncoding::Utf8 {
+                    return Err(DataFusionError::NotImplemented(
+                        "Byte range scans of JSON files not encoded in UTF-8 are not supported"
+                            .to_string(),
+                    ));
+                }
+                let bytes = get_line_range(
+                    store.as_ref(),
+                    file_meta.location(),
+                    range,
+                    file_meta.object_meta.size,
+                )
+                .await?;
+                add_bytes_read(bytes.len());
+                return Ok(read_batches(
+                    bytes.reader(),
+                    schema,
+                    batch_size,
+                    projection,
+                    scan_options,
+                ));
+            }
+
+            let stream = match prefetched {
+                Some(prefix) => {
+                    let prefix = prefix.await.map_err(|e| {
+                        DataFusionError::Execution(format!(
+                            "Prefetching {} failed: {}",
+                            file_meta.location(),
+                            e
+                        ))
+                    })??;
+                    let (start, size) = (prefix.len(), file_meta.object_meta.size);
+                    let location = file_meta.location().clone();
+                    // the rest of the file is only fetched once its prefix
+                    // has been read
+                    let rest = if start < size {
+                        futures::stream::once(async move {
+                            store.get_range(&location, start..size).await
                         })
-                        .try_flatten()
-                        .boxed())
+                        .boxed()
+                    } else {
+                        futures::stream::empty().boxed()
+                    };
+                    futures::stream::once(async { Ok(prefix) })
+                        .chain(rest)
+                        .boxed()
                 }
+                None => match store.get(file_meta.location()).await? {
+                    GetResult::File(file, _) => {
+                        let file = CountingRead {
+                            inner: file,
+                            add_bytes_read,
+                        };
+                        let decoder = file_compression_type.convert_read(file)?;
+                        let decoder = scan_options.encoding.convert_read(decoder);
+                        if scan_options.validation.is_some() {
+                            return Ok(futures::stream::iter(validate_records(
+                                BufReader::new(decoder),
+                                schema,
+                                batch_size,
+                                &scan_options,
+                                file_meta.location(),
+                            ))
+                            .boxed());
+                        }
+                        return Ok(read_batches(
+                            BufReader::new(decoder),
+                            schema,
+                            batch_size,
+                            projection,
+                            scan_options,
+                        ));
+                    }
+                    GetResult::Stream(s) => s,
+                },
+            };
+
+            let s = stream
+                .inspect_ok(move |bytes| add_bytes_read(bytes.len()))
+                .map_err(Into::into);
+            let decoder = file_compression_type.convert_stream(s)?;
+            let decoder = scan_options.encoding.convert_stream(decoder);
+
+            if scan_options.validation.is_some() {
+                let location = file_meta.location().clone();
+                return Ok(read_stream_batches(decoder, move |reader| {
+                    validate_records(reader, schema, batch_size, &scan_options, &location)
+                }));
             }
+
+            Ok(read_stream_batches(decoder, move |reader| {
+                batch_iter(reader, schema, batch_size, projection, scan_options)
+            }))
+        });
+        let stream: FileOpenFuture = Box::pin(stream.instrument(open_span));
+
+        Ok(Box::pin(async move {
+            // the records are decoded as the batches are polled
+            let stream = instrument_stream(stream.await?, read_span)
+                .inspect_ok(move |batch| decoded_arrays.add(batch.num_columns()))
+                .boxed();
+            let stream = match progress {
+                Some(progress) => progress.report_batches(stream),
+                None => stream,
+            };
+            let stream = match in_flight {
+                Some(in_flight) => in_flight.limit(stream),
+                None => stream,
+            };
+            // the ids are assigned as the batches are consumed, as the files
+            // of a partition may be decoded ahead of being read
+            Ok(match row_ids {
+                Some(row_ids) => stream.map(move |batch| row_ids.add_to(batch?)).boxed(),
+                None => stream,
+            })
         }))
     }
 }
 
+/// Counts the bytes read from `inner`, before they are decompressed
+struct CountingRead<R, F> {
+    inner: R,
+    add_bytes_read: F,
+}
+
+impl<R: Read, F: Fn(usize)> Read for CountingRead<R, F> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        (self.add_bytes_read)(read);
+        Ok(read)
+    }
+}
+
+/// Reports the progress of reading a file of a JSON scan, see
+/// [`JsonScanOptions::progress`]
+struct ProgressReporter {
+    progress: JsonProgress,
+    location: object_store::path::Path,
+    /// Added to as the file is read, which may be on another thread than
+    /// the one decoding it
+    bytes_read: AtomicUsize,
+}
+
+impl ProgressReporter {
+    fn report(&self, rows: usize, done: bool) {
+        self.progress.report(FileProgress {
+            location: self.location.clone(),
+            bytes_read: self.bytes_read.load(Ordering::Relaxed),
+            rows,
+            done,
+        })
+    }
+
+    /// Reports the progress after every batch of `stream`, decoded from the
+    /// file, and once more at its end
+    fn report_batches(
+        self: Arc<Self>,
+        stream: BoxStream<'static, ArrowResult<RecordBatch>>,
+    ) -> BoxStream<'static, ArrowResult<RecordBatch>> {
+        futures::stream::unfold(Some((stream, 0)), move |state| {
+            let reporter = self.clone();
+            async move {
+                let (mut stream, mut rows) = state?;
+                match stream.next().await {
+                    Some(batch) => {
+                        if let Ok(batch) = &batch {
+                            rows += batch.num_rows();
+                            reporter.report(rows, false);
+                        }
+                        Some((batch, Some((stream, rows))))
+                    }
+                    None => {
+                        reporter.report(rows, true);
+                        None
+                    }
+                }
+            }
+        })
+        .boxed()
+    }
+}
+
+/// Assigns the ids of the records read by a partition of a JSON scan, or by
+/// a file of a partition merging its sorted files, see
+/// [`JsonScanOptions::row_id`]
+#[derive(Debug)]
+struct RowIds {
+    /// The position of the row id column among the projected file columns
+    index: usize,
+    /// The id of the first record of the partition or file
+    first: i64,
+    /// The number of low bits of an id holding the position of the record
+    /// within the partition or file
+    row_bits: u32,
+    /// The id of the next record of the partition or file
+    next: AtomicI64,
+}
+
+impl RowIds {
+    /// The number of low bits of an id holding the position of the record
+    /// within its partition
+    const ROW_BITS: u32 = 40;
+
+    /// The number of low bits of an id holding the position of the record
+    /// within its file, when the sorted files of a partition are merged
+    const FILE_ROW_BITS: u32 = 28;
+
+    fn try_new(partition: usize, index: usize) -> Result<Self> {
+        let first = i64::try_from(partition)
+            .ok()
+            .filter(|partition| *partition <= i64::MAX >> Self::ROW_BITS)
+            .map(|partition| partition << Self::ROW_BITS)
+            .ok_or_else(|| {
+                DataFusionError::Execution(format!(
+                    "JSON scan partition {} is too large to assign row ids",
+                    partition
+                ))
+            })?;
+        Ok(Self {
+            index,
+            first,
+            row_bits: Self::ROW_BITS,
+            next: AtomicI64::new(first),
+        })
+    }
+
+    /// Returns the ids of the records of the file at `file_index` among the
+    /// files of the partition, which are merged rather than read one after
+    /// another. The index of the file is held by the bits above
+    /// [`Self::FILE_ROW_BITS`].
+    fn for_file(&self, file_index: usize) -> Result<Self> {
+        let max_files = 1 << (Self::ROW_BITS - Self::FILE_ROW_BITS);
+        if file_index >= max_files {
+            return Err(DataFusionError::Execution(format!(
+                "A sorted JSON scan partition merges more than {} files, the most row ids can be assigned to",
+                max_files
+            )));
+        }
+        let first = self.first + ((file_index as i64) << Self::FILE_ROW_BITS);
+        Ok(Self {
+            index: self.index,
+            first,
+            row_bits: Self::FILE_ROW_BITS,
+            next: AtomicI64::new(first),
+        })
+    }
+
+    /// Inserts the ids of the rows of `batch` at the position of the row id
+    /// column
+    fn add_to(&self, batch: RecordBatch) -> ArrowResult<RecordBatch> {
+        let num_rows = batch.num_rows() as i64;
+        let start = self.next.fetch_add(num_rows, Ordering::Relaxed);
+        if start - self.first + num_rows > 1 << self.row_bits {
+            let scope = match self.row_bits {
+                Self::ROW_BITS => "A JSON scan partition",
+                _ => "A file of a sorted JSON scan partition",
+            };
+            return Err(ArrowError::JsonError(format!(
+                "{} read more than 2^{} records, the most row ids can be assigned to",
+                scope, self.row_bits
+            )));
+        }
+        let ids: ArrayRef =
+            Arc::new(Int64Array::from_iter_values(start..start + num_rows));
+
+        let schema = batch.schema();
+        let mut fields = schema.fields().clone();
+        let mut columns = batch.columns().to_vec();
+        let index = self.index.min(columns.len());
+        fields.insert(index, Field::new(ROW_ID_COLUMN, DataType::Int64, false));
+        columns.insert(index, ids);
+
+        let schema = Schema::new_with_metadata(fields, schema.metadata().clone());
+        let options = RecordBatchOptions::new().with_row_count(Some(batch.num_rows()));
+        RecordBatch::try_new_with_options(Arc::new(schema), columns, &options)
+    }
+}
+
+/// The number of bytes fetched at a time to read the end of the last line of
+/// a byte range
+const LINE_RANGE_FETCH_SIZE: usize = 64 * 1024;
+
+/// Reads the lines of the file at `location` that start within `range`, the
+/// line crossing the end of the range being read whole, so that the adjacent
+/// ranges splitting a file read each of its lines exactly once
+async fn get_line_range(
+    store: &dyn ObjectStore,
+    location: &object_store::path::Path,
+    range: &FileRange,
+    size: usize,
+) -> Result<Bytes> {
+    let start = range.start as usize;
+    let end = (range.end as usize).min(size);
+    if start >= end {
+        return Ok(Bytes::new());
+    }
+
+    // a line starts at the start of the range if the byte before it ends
+    // the previous line
+    let fetch_start = start.saturating_sub(1);
+    let mut bytes = store.get_range(location, fetch_start..end).await?.to_vec();
+    let first = if start == 0 {
+        0
+    } else {
+        match bytes.iter().position(|b| *b == b'\n') {
+            Some(idx) => idx + 1,
+            // no line starts within the range
+            None => return Ok(Bytes::new()),
+        }
+    };
+
+    let mut fetched = end;
+    while fetched < size && bytes.last() != Some(&b'\n') {
+        let next = (fetched + LINE_RANGE_FETCH_SIZE).min(size);
+        let more = store.get_range(location, fetched..next).await?;
+        match more.iter().position(|b| *b == b'\n') {
+            Some(idx) => bytes.extend_from_slice(&more[..=idx]),
+            None => bytes.extend_from_slice(&more),
+        }
+        fetched = next;
+    }
+    Ok(Bytes::from(bytes).slice(first..))
+}
+
+/// Enters `span` whenever `stream` is polled
+fn instrument_stream<T: Send + 'static>(
+    mut stream: BoxStream<'static, T>,
+    span: Span,
+) -> BoxStream<'static, T> {
+    futures::stream::poll_fn(move |cx| {
+        let _entered = span.enter();
+        stream.poll_next_unpin(cx)
+    })
+    .boxed()
+}
+
+/// The number of leading bytes of a file recorded with its read offset, see
+/// [`JsonReadOffsets`]
+const READ_OFFSET_PREFIX_LEN: usize = 64;
+
+/// The number of bytes an incremental scan fetches at once
+const APPENDED_CHUNK_SIZE: usize = 1024 * 1024;
+
+/// The state of the incremental scan of a file, see [`read_appended_lines`]
+struct AppendedLines {
+    store: Arc<dyn ObjectStore>,
+    location: object_store::path::Path,
+    /// The first bytes of the file, recorded with its offset
+    prefix: Bytes,
+    read_offsets: Arc<JsonReadOffsets>,
+    /// The offset up to which the file has been fetched
+    fetched: usize,
+    /// The size of the file when the scan started
+    size: usize,
+    /// The fetched bytes following the last complete line
+    partial: BytesMut,
+    /// The complete lines fetched but not decoded yet, in groups of at most
+    /// `batch_size` lines, with the offset of the end of each group
+    pending: VecDeque<(Bytes, usize)>,
+    /// The batches of the group of lines being decoded, with the offset of
+    /// the end of the group
+    current: Option<(BoxStream<'static, ArrowResult<RecordBatch>>, usize)>,
+    /// Whether the scan failed, which ends it
+    failed: bool,
+    add_bytes_read: Box<dyn Fn(usize) + Send>,
+}
+
+impl AppendedLines {
+    /// Appends the fetched `bytes`, grouping the lines they complete by at
+    /// most `batch_size` lines
+    fn push(&mut self, bytes: Bytes, batch_size: usize) {
+        (self.add_bytes_read)(bytes.len());
+        self.fetched += bytes.len();
+        self.partial.extend_from_slice(&bytes);
+        let complete = match self.partial.iter().rposition(|b| *b == b'\n') {
+            Some(idx) => self.partial.split_to(idx + 1).freeze(),
+            None => return,
+        };
+        // the offset of the first complete line in the file
+        let offset = self.fetched - self.partial.len() - complete.len();
+
+        let (mut start, mut lines) = (0, 0);
+        for (idx, byte) in complete.iter().enumerate() {
+            if *byte == b'\n' {
+                lines += 1;
+                if lines == batch_size || idx + 1 == complete.len() {
+                    self.pending
+                        .push_back((complete.slice(start..idx + 1), offset + idx + 1));
+                    start = idx + 1;
+                    lines = 0;
+                }
+            }
+        }
+    }
+}
+
+/// Reads the complete lines appended to a file since the offset recorded in
+/// its [`JsonReadOffsets`], fetching them in chunks as the batches are
+/// consumed.
+///
+/// The lines are decoded in groups of at most `batch_size` lines, and the
+/// recorded offset only moves past a group once all its batches have been
+/// consumed, so that the lines of a scan that fails or stops early, such as
+/// under a `LIMIT`, are read again by the next scan. A partially written
+/// last line is left for the next scan.
+fn read_appended_lines(
+    lines: AppendedLines,
+    schema: SchemaRef,
+    batch_size: usize,
+    projection: Option<Vec<String>>,
+    scan_options: Arc<JsonScanOptions>,
+) -> BoxStream<'static, ArrowResult<RecordBatch>> {
+    futures::stream::unfold(lines, move |mut lines| {
+        let schema = schema.clone();
+        let projection = projection.clone();
+        let scan_options = scan_options.clone();
+        async move {
+            loop {
+                if lines.failed {
+                    return None;
+                }
+                if let Some((batches, end)) = lines.current.as_mut() {
+                    let end = *end;
+                    match batches.next().await {
+                        Some(Ok(batch)) => return Some((Ok(batch), lines)),
+                        Some(Err(e)) => {
+                            lines.failed = true;
+                            return Some((Err(e), lines));
+                        }
+                        None => {
+                            // every batch of the group has been consumed
+                            lines.current = None;
+                            lines.read_offsets.advance(
+                                &lines.location,
+                                end,
+                                lines.prefix.clone(),
+                            );
+                        }
+                    }
+                } else if let Some((group, end)) = lines.pending.pop_front() {
+                    let batches = read_batches(
+                        group.reader(),
+                        schema.clone(),
+                        batch_size,
+                        projection.clone(),
+                        scan_options.clone(),
+                    );
+                    lines.current = Some((batches, end));
+                } else if lines.fetched < lines.size {
+                    let end = lines.size.min(lines.fetched + APPENDED_CHUNK_SIZE);
+                    match lines
+                        .store
+                        .get_range(&lines.location, lines.fetched..end)
+                        .await
+                    {
+                        Ok(bytes) => lines.push(bytes, batch_size),
+                        Err(e) => {
+                            lines.failed = true;
+                            return Some((
+                                Err(ArrowError::ExternalError(Box::new(e))),
+                                lines,
+                            ));
+                        }
+                    }
+                } else {
+                    return None;
+                }
+            }
+        }
+    })
+    .boxed()
+}
+
+/// Returns whether the file at `location` holds any record, only reading it
+/// up to its first one
+async fn has_records(
+    store: &dyn ObjectStore,
+    location: &object_store::path::Path,
+    file_compression_type: &FileCompressionType,
+    scan_options: &JsonScanOptions,
+) -> Result<bool> {
+    let comment_prefix = scan_options.comment_prefix.as_deref();
+    match store.get(location).await? {
+        GetResult::File(file, _) => {
+            let decoder = file_compression_type.convert_read(file)?;
+            let decoder = scan_options.encoding.convert_read(decoder);
+            let mut counter =
+                JsonRecordCounter::new(BufReader::new(decoder), 1, comment_prefix);
+            Ok(counter.next().transpose()?.is_some())
+        }
+        GetResult::Stream(s) => {
+            let decoder = file_compression_type.convert_stream(s.map_err(Into::into))?;
+            let decoder = scan_options.encoding.convert_stream(decoder);
+            // every chunk ends with a complete line, but the last
+            let mut chunks = newline_delimited_stream(decoder).boxed();
+            while let Some(chunk) = chunks.next().await {
+                let mut counter =
+                    JsonRecordCounter::new(chunk?.reader(), 1, comment_prefix);
+                if counter.next().transpose()?.is_some() {
+                    return Ok(true);
+                }
+            }
+            Ok(false)
+        }
+    }
+}
+
+/// The batches read from a file of a JSON scan as they are decoded
+type BatchIter = Box<dyn Iterator<Item = ArrowResult<RecordBatch>> + Send>;
+
+/// Returns the batches of the newline delimited JSON in `reader`, only
+/// counting its records if [`JsonScanOptions::count_only`] is set
+fn batch_iter<R: BufRead + Send + 'static>(
+    reader: R,
+    schema: SchemaRef,
+    batch_size: usize,
+    projection: Option<Vec<String>>,
+    scan_options: Arc<JsonScanOptions>,
+) -> BatchIter {
+    if scan_options.count_only {
+        Box::new(JsonRecordCounter::new(
+            reader,
+            batch_size,
+            scan_options.comment_prefix.as_deref(),
+        ))
+    } else {
+        Box::new(JsonBatchReader::new(
+            reader,
+            schema,
+            batch_size,
+            projection,
+            scan_options,
+        ))
+    }
+}
+
+/// Reads the batches of the newline delimited JSON in `reader`, see
+/// [`batch_iter`]
+fn read_batches<R: BufRead + Send + 'static>(
+    reader: R,
+    schema: SchemaRef,
+    batch_size: usize,
+    projection: Option<Vec<String>>,
+    scan_options: Arc<JsonScanOptions>,
+) -> BoxStream<'static, ArrowResult<RecordBatch>> {
+    futures::stream::iter(batch_iter(
+        reader,
+        schema,
+        batch_size,
+        projection,
+        scan_options,
+    ))
+    .boxed()
+}
+
+/// Reads the batches `read` returns for a reader of the bytes of `stream`,
+/// however many chunks they arrive in.
+///
+/// `read` runs on a blocking thread, reading the chunks through a
+/// [`SyncIoBridge`] as they arrive, so that a single reader reads the whole
+/// file, keeping the state it accumulates across lines such as the invalid
+/// lines skipped so far and the line numbers of errors. The thread decodes
+/// at most one batch ahead of those consumed, and stops once they no longer
+/// are.
+fn read_stream_batches<F>(
+    stream: BoxStream<'static, Result<Bytes>>,
+    read: F,
+) -> BoxStream<'static, ArrowResult<RecordBatch>>
+where
+    F: FnOnce(Box<dyn BufRead + Send>) -> BatchIter + Send + 'static,
+{
+    let stream = stream.map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e));
+    let reader: Box<dyn BufRead + Send> =
+        Box::new(SyncIoBridge::new(StreamReader::new(stream)));
+    let (sender, receiver) = tokio::sync::mpsc::channel(1);
+    let handle = task::spawn_blocking(move || {
+        for batch in read(reader) {
+            if sender.blocking_send(batch).is_err() {
+                // the scan was dropped
+                break;
+            }
+        }
+    });
+    // the batches end with an error if the thread panicked
+    let panicked = futures::stream::once(handle).filter_map(|result| async move {
+        result
+            .err()
+            .map(|e| Err(ArrowError::ExternalError(Box::new(e))))
+    });
+    ReceiverStream::new(receiver).chain(panicked).boxed()
+}
+
+/// Validates the records of the newline delimited JSON in `reader`, the lines
+/// of the file at `location`, see [`JsonScanOptions::validation`]
+fn validate_records<R: BufRead + Send + 'static>(
+    reader: R,
+    schema: SchemaRef,
+    batch_size: usize,
+    scan_options: &JsonScanOptions,
+    location: &object_store::path::Path,
+) -> BatchIter {
+    match JsonRecordValidator::try_new(reader, schema, batch_size, scan_options, location)
+    {
+        Ok(validator) => Box::new(validator),
+        Err(e) => Box::new(std::iter::once(Err(e))),
+    }
+}
+
+/// Executes `plan` and writes the results to one newline delimited JSON
+/// file per partition in the directory `path`, compressed with
+/// `file_compression_type`
 pub async fn plan_to_json(
     state: &SessionState,
     plan: Arc<dyn ExecutionPlan>,
     path: impl AsRef<str>,
+    file_compression_type: FileCompressionType,
 ) -> Result<()> {
     let path = path.as_ref();
     // create directory to contain the CSV files (one per partition)
@@ -214,18 +1460,25 @@ pub async fn plan_to_json(
             let mut tasks = vec![];
             for i in 0..plan.output_partitioning().partition_count() {
                 let plan = plan.clone();
-                let filename = format!("part-{}.json", i);
+                let filename =
+                    format!("part-{}.json{}", i, file_compression_type.get_ext());
                 let path = fs_path.join(&filename);
                 let file = fs::File::create(path)?;
-                let mut writer = json::LineDelimitedWriter::new(file);
+                let mut encoder = file_compression_type.convert_write(file)?;
                 let task_ctx = Arc::new(TaskContext::from(state));
                 let stream = plan.execute(i, task_ctx)?;
                 let handle: JoinHandle<Result<()>> = task::spawn(async move {
+                    let mut writer = json::LineDelimitedWriter::new(&mut encoder);
                     stream
                         .map(|batch| writer.write(batch?))
                         .try_collect()
                         .await
-                        .map_err(DataFusionError::from)
+                        .map_err(DataFusionError::from)?;
+                    drop(writer);
+                    // the compressed data is incomplete until finished,
+                    // which may fail like any write
+                    encoder.finish()?;
+                    Ok(())
                 });
                 tasks.push(handle);
             }
@@ -250,11 +1503,10 @@ mod tests {
     use arrow::datatypes::{Field, Schema};
     use futures::StreamExt;
     use object_store::local::LocalFileSystem;
+    use std::collections::HashMap;
 
     use crate::assert_batches_eq;
-    use crate::config::ConfigOptions;
     use crate::datasource::file_format::file_type::FileType;
-    use crate::datasource::file_format::{json::JsonFormat, FileFormat};
     use crate::datasource::listing::PartitionedFile;
     use crate::datasource::object_store::ObjectStoreUrl;
     use crate::physical_plan::file_format::chunked_store::ChunkedStore;
@@ -305,7 +1557,9 @@ mod tests {
         file_compression_type,
         case(FileCompressionType::UNCOMPRESSED),
         case(FileCompressionType::GZIP),
-        case(FileCompressionType::BZIP2)
+        case(FileCompressionType::BZIP2),
+        case(FileCompressionType::XZ),
+        case(FileCompressionType::ZSTD)
     )]
     #[tokio::test]
     async fn nd_json_exec_file_without_projection(
@@ -376,7 +1630,9 @@ mod tests {
         file_compression_type,
         case(FileCompressionType::UNCOMPRESSED),
         case(FileCompressionType::GZIP),
-        case(FileCompressionType::BZIP2)
+        case(FileCompressionType::BZIP2),
+        case(FileCompressionType::XZ),
+        case(FileCompressionType::ZSTD)
     )]
     #[tokio::test]
     async fn nd_json_exec_file_with_missing_column(
@@ -429,7 +1685,9 @@ mod tests {
         file_compression_type,
         case(FileCompressionType::UNCOMPRESSED),
         case(FileCompressionType::GZIP),
-        case(FileCompressionType::BZIP2)
+        case(FileCompressionType::BZIP2),
+        case(FileCompressionType::XZ),
+        case(FileCompressionType::ZSTD)
     )]
     #[tokio::test]
     async fn nd_json_exec_file_projection(
@@ -476,65 +1734,1730 @@ mod tests {
         Ok(())
     }
 
-    #[tokio::test]
-    async fn write_json_results() -> Result<()> {
-        // create partitioned input file and context
-        let tmp_dir = TempDir::new()?;
-        let ctx =
-            SessionContext::with_config(SessionConfig::new().with_target_partitions(8));
+    fn positional_schema() -> SchemaRef {
+        use arrow::datatypes::DataType;
+        Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int64, true),
+            Field::new("b", DataType::Utf8, true),
+            Field::new("c", DataType::Boolean, true),
+        ]))
+    }
 
-        let path = format!("{}/1.json", TEST_DATA_BASE);
+    #[tokio::test]
+    async fn nd_json_exec_positional_arrays() -> Result<()> {
+        let ctx = SessionContext::new();
+        let options = NdJsonReadOptions::default()
+            .schema(positional_schema())
+            .scan_options(JsonScanOptions::new().with_positional_arrays(true));
 
-        // register json file with the execution context
-        ctx.register_json("test", path.as_str(), NdJsonReadOptions::default())
+        let results = ctx
+            .read_json(format!("{}/positional.json", TEST_DATA_BASE), options)
+            .await?
+            .collect()
             .await?;
 
-        // execute a simple query and write the results to CSV
-        let out_dir = tmp_dir.as_ref().to_str().unwrap().to_string() + "/out";
-        let df = ctx.sql("SELECT a, b FROM test").await?;
-        df.write_json(&out_dir).await?;
+        assert_batches_eq!(
+            &[
+                "+-----+---+-------+",
+                "| a   | b | c     |",
+                "+-----+---+-------+",
+                "| 1   | a | true  |",
+                "| -10 | b | false |",
+                "| 2   |   |       |",
+                "+-----+---+-------+",
+            ],
+            &results
+        );
 
-        // create a new context and verify that the results were saved to a partitioned csv file
-        let ctx = SessionContext::new();
+        Ok(())
+    }
 
-        // register each partition as well as the top level dir
-        let json_read_option = NdJsonReadOptions::default();
-        ctx.register_json(
-            "part0",
-            &format!("{}/part-0.json", out_dir),
-            json_read_option.clone(),
-        )
-        .await?;
-        ctx.register_json("allparts", &out_dir, json_read_option)
-            .await?;
+    #[tokio::test]
+    async fn nd_json_exec_scalar_lines() -> Result<()> {
+        use arrow::datatypes::DataType;
 
-        let part0 = ctx.sql("SELECT a, b FROM part0").await?.collect().await?;
-        let allparts = ctx
-            .sql("SELECT a, b FROM allparts")
+        let ctx = SessionContext::new();
+        let options = NdJsonReadOptions::default()
+            .scan_options(JsonScanOptions::new().with_scalar_column("n"));
+        let results = ctx
+            .read_json(format!("{}/scalar_ints.json", TEST_DATA_BASE), options)
             .await?
             .collect()
             .await?;
 
-        let allparts_count: usize = allparts.iter().map(|batch| batch.num_rows()).sum();
+        assert_eq!(
+            results[0].schema().fields(),
+            &vec![Field::new("n", DataType::Int64, true)]
+        );
+        assert_batches_eq!(
+            &[
+                "+----+", "| n  |", "+----+", "| 42 |", "| 7  |", "|    |", "| -1 |",
+                "+----+",
+            ],
+            &results
+        );
 
-        assert_eq!(part0[0].schema(), allparts[0].schema());
+        let options = NdJsonReadOptions::default()
+            .scan_options(JsonScanOptions::new().with_scalar_column("day"));
+        let results = ctx
+            .read_json(format!("{}/scalar_strings.json", TEST_DATA_BASE), options)
+            .await?
+            .collect()
+            .await?;
 
-        assert_eq!(allparts_count, 4);
+        assert_eq!(
+            results[0].schema().fields(),
+            &vec![Field::new("day", DataType::Utf8, true)]
+        );
+        assert_batches_eq!(
+            &[
+                "+------------+",
+                "| day        |",
+                "+------------+",
+                "| 2021-01-01 |",
+                "| 2021-01-02 |",
+                "+------------+",
+            ],
+            &results
+        );
 
         Ok(())
     }
 
-    #[rstest(
-        file_compression_type,
-        case(FileCompressionType::UNCOMPRESSED),
-        case(FileCompressionType::GZIP),
-        case(FileCompressionType::BZIP2)
-    )]
     #[tokio::test]
-    async fn test_chunked(file_compression_type: FileCompressionType) {
-        let mut ctx = SessionContext::new();
+    async fn nd_json_exec_positional_arrays_length_mismatch() -> Result<()> {
+        let ctx = SessionContext::new();
+        let options = NdJsonReadOptions::default()
+            .schema(positional_schema())
+            .scan_options(JsonScanOptions::new().with_positional_arrays(true));
+
+        let err = ctx
+            .read_json(
+                format!("{}/positional_mismatch.json", TEST_DATA_BASE),
+                options,
+            )
+            .await?
+            .collect()
+            .await
+            .unwrap_err();
 
-        for chunk_size in [10, 20, 30, 40] {
+        assert_eq!(
+            err.to_string(),
+            "Arrow error: Json error: Positional JSON array on line 2 has 2 elements but the schema has 3 fields"
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn nd_json_exec_infer_durations() -> Result<()> {
+        use arrow::array::{DurationNanosecondArray, IntervalMonthDayNanoArray};
+        use arrow::datatypes::{
+            DataType, IntervalMonthDayNanoType, IntervalUnit, TimeUnit,
+        };
+
+        let ctx = SessionContext::new();
+        let options = NdJsonReadOptions::default()
+            .scan_options(JsonScanOptions::new().with_infer_durations(true));
+        let df = ctx
+            .read_json(format!("{}/durations.json", TEST_DATA_BASE), options)
+            .await?;
+
+        let schema = df.schema();
+        assert_eq!(
+            schema.field_with_unqualified_name("elapsed")?.data_type(),
+            &DataType::Duration(TimeUnit::Nanosecond)
+        );
+        assert_eq!(
+            schema.field_with_unqualified_name("period")?.data_type(),
+            &DataType::Interval(IntervalUnit::MonthDayNano)
+        );
+
+        let results = df.select_columns(&["elapsed", "period"])?.collect().await?;
+        assert_eq!(results.len(), 1);
+
+        let elapsed = results[0]
+            .column(0)
+            .as_any()
+            .downcast_ref::<DurationNanosecondArray>()
+            .unwrap();
+        let expected = vec![
+            Some(5_400_000_000_000),
+            Some(-500_000_000),
+            None,
+            Some(86_402_000_000_000),
+        ];
+        assert_eq!(elapsed.iter().collect::<Vec<_>>(), expected);
+
+        let period = results[0]
+            .column(1)
+            .as_any()
+            .downcast_ref::<IntervalMonthDayNanoArray>()
+            .unwrap();
+        let expected = vec![
+            Some(IntervalMonthDayNanoType::make_value(1, 2, 0)),
+            Some(IntervalMonthDayNanoType::make_value(12, 0, 0)),
+            Some(IntervalMonthDayNanoType::make_value(0, 0, 10_000_000_000)),
+            None,
+        ];
+        assert_eq!(period.iter().collect::<Vec<_>>(), expected);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn nd_json_exec_duration_hints() -> Result<()> {
+        use arrow::array::DurationMillisecondArray;
+        use arrow::datatypes::{DataType, TimeUnit};
+
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int64, true),
+            Field::new("elapsed", DataType::Duration(TimeUnit::Millisecond), true),
+        ]));
+
+        let ctx = SessionContext::new();
+        let options = NdJsonReadOptions::default().schema(schema.clone());
+        let results = ctx
+            .read_json(format!("{}/durations.json", TEST_DATA_BASE), options)
+            .await?
+            .collect()
+            .await?;
+
+        assert_eq!(results[0].schema(), schema);
+        let elapsed = results[0]
+            .column(1)
+            .as_any()
+            .downcast_ref::<DurationMillisecondArray>()
+            .unwrap();
+        assert_eq!(
+            elapsed.iter().collect::<Vec<_>>(),
+            vec![Some(5_400_000), Some(-500), None, Some(86_402_000)]
+        );
+
+        // months can not be represented as a fixed duration
+        let schema = Arc::new(Schema::new(vec![Field::new(
+            "period",
+            DataType::Duration(TimeUnit::Second),
+            true,
+        )]));
+        let options = NdJsonReadOptions::default().schema(schema);
+        let err = ctx
+            .read_json(format!("{}/durations.json", TEST_DATA_BASE), options)
+            .await?
+            .collect()
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Arrow error: Json error: ISO-8601 duration 'P1M2D' in column 'period' can not be represented as Duration(Second)"
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn nd_json_exec_narrow_integer_hints() -> Result<()> {
+        use arrow::array::Int32Array;
+        use arrow::datatypes::DataType;
+
+        let path = format!("{}/narrow_ints.json", TEST_DATA_BASE);
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, true)]));
+
+        let ctx = SessionContext::new();
+        let options = NdJsonReadOptions::default().schema(schema.clone());
+        let results = ctx.read_json(&path, options).await?.collect().await?;
+
+        assert_eq!(results[0].schema(), schema);
+        let a = results[0]
+            .column(0)
+            .as_any()
+            .downcast_ref::<Int32Array>()
+            .unwrap();
+        assert_eq!(a.iter().collect::<Vec<_>>(), vec![Some(1), Some(i32::MIN)]);
+
+        // values out of the range of the hinted type are an error
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Int32, true),
+        ]));
+        let options = NdJsonReadOptions::default().schema(schema.clone());
+        let err = ctx
+            .read_json(&path, options)
+            .await?
+            .collect()
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Arrow error: Json error: Value 3000000000 is out of range for Int32 column 'b' on line 2"
+        );
+
+        // lenient arithmetic does not change how the files are read
+        let ctx = SessionContext::with_config(
+            SessionConfig::new().with_lenient_arithmetic(true),
+        );
+        let options = NdJsonReadOptions::default().schema(schema.clone());
+        let result = ctx.read_json(&path, options).await?.collect().await;
+        assert!(result.is_err());
+
+        // such values are null if the scan options ask for it
+        let options = NdJsonReadOptions::default()
+            .schema(schema)
+            .scan_options(JsonScanOptions::new().with_null_on_integer_overflow(true));
+        let results = ctx.read_json(&path, options).await?.collect().await?;
+        assert_batches_eq!(
+            &[
+                "+-------------+------------+",
+                "| a           | b          |",
+                "+-------------+------------+",
+                "| 1           | 2147483647 |",
+                "| -2147483648 |            |",
+                "+-------------+------------+",
+            ],
+            &results
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn nd_json_exec_deadline() -> Result<()> {
+        use crate::physical_plan::collect;
+        use std::time::{Duration, Instant};
+
+        let ctx = SessionContext::new();
+        let plan = ctx
+            .read_json(
+                format!("{}/1.json", TEST_DATA_BASE),
+                NdJsonReadOptions::default(),
+            )
+            .await?
+            .create_physical_plan()
+            .await?;
+
+        // a deadline which has passed fails the scan
+        let task_ctx = TaskContext::from(&ctx).with_deadline(Instant::now());
+        let err = collect(plan.clone(), Arc::new(task_ctx)).await.unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Resources exhausted: Query deadline exceeded"),
+            "{}",
+            err
+        );
+
+        // the deadline of each query is set from the timeout of its session
+        let config = SessionConfig::new().with_query_timeout(Duration::from_secs(3600));
+        let ctx = SessionContext::with_config(config);
+        let before = Instant::now();
+        let task_ctx = ctx.task_ctx();
+        let deadline = task_ctx.deadline().unwrap();
+        assert!(deadline >= before + Duration::from_secs(3600));
+        assert!(deadline <= Instant::now() + Duration::from_secs(3600));
+        let batches = collect(plan, task_ctx).await?;
+        assert_eq!(batches.iter().map(|b| b.num_rows()).sum::<usize>(), 4);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn nd_json_exec_default_timezone() -> Result<()> {
+        use arrow::array::TimestampMillisecondArray;
+        use arrow::datatypes::{DataType, TimeUnit};
+
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int64, true),
+            Field::new("ts", DataType::Timestamp(TimeUnit::Millisecond, None), true),
+        ]));
+
+        let ctx = SessionContext::new();
+        let options = NdJsonReadOptions::default()
+            .schema(schema.clone())
+            .scan_options(
+                JsonScanOptions::new().with_default_timezone("America/New_York"),
+            );
+        let results = ctx
+            .read_json(format!("{}/naive_timestamps.json", TEST_DATA_BASE), options)
+            .await?
+            .collect()
+            .await?;
+
+        assert_eq!(
+            results[0].schema().field(1).data_type(),
+            &DataType::Timestamp(
+                TimeUnit::Millisecond,
+                Some("America/New_York".to_string())
+            )
+        );
+        let ts = results[0]
+            .column(1)
+            .as_any()
+            .downcast_ref::<TimestampMillisecondArray>()
+            .unwrap();
+        assert_eq!(
+            ts.iter().collect::<Vec<_>>(),
+            vec![
+                // 12:00 EST is 17:00 UTC
+                Some(1_614_618_000_000),
+                // 12:00 EDT is 16:00 UTC
+                Some(1_625_155_200_500),
+                // explicit offsets are kept
+                Some(1_614_600_000_000),
+                None
+            ]
+        );
+
+        let options = NdJsonReadOptions::default()
+            .schema(schema)
+            .scan_options(JsonScanOptions::new().with_default_timezone("Mars/Olympus"));
+        let err = ctx
+            .read_json(format!("{}/naive_timestamps.json", TEST_DATA_BASE), options)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Arrow error: Json error: Invalid default timezone 'Mars/Olympus'"
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn nd_json_exec_incremental() -> Result<()> {
+        use std::io::Write;
+
+        let tmp_dir = TempDir::new()?;
+        let path = tmp_dir.path().join("log.json");
+        let append = |lines: &str| -> Result<()> {
+            let mut file = fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&path)?;
+            file.write_all(lines.as_bytes())?;
+            Ok(())
+        };
+
+        append("{\"a\": 1}\n{\"a\": 2}\n")?;
+
+        let ctx = SessionContext::new();
+        let options = NdJsonReadOptions::default()
+            .scan_options(JsonScanOptions::new().with_incremental(true));
+        ctx.register_json("log", path.to_str().unwrap(), options)
+            .await?;
+
+        let query = "SELECT a FROM log ORDER BY a";
+        let results = ctx.sql(query).await?.collect().await?;
+        #[rustfmt::skip]
+        let expected = vec![
+            "+---+",
+            "| a |",
+            "+---+",
+            "| 1 |",
+            "| 2 |",
+            "+---+",
+        ];
+        assert_batches_eq!(expected, &results);
+
+        // the partially written last line is left for the next scan
+        append("{\"a\": 3}\n{\"a\":")?;
+        let results = ctx.sql(query).await?.collect().await?;
+        #[rustfmt::skip]
+        let expected = vec![
+            "+---+",
+            "| a |",
+            "+---+",
+            "| 3 |",
+            "+---+",
+        ];
+        assert_batches_eq!(expected, &results);
+
+        append(" 4}\n")?;
+        let results = ctx.sql(query).await?.collect().await?;
+        #[rustfmt::skip]
+        let expected = vec![
+            "+---+",
+            "| a |",
+            "+---+",
+            "| 4 |",
+            "+---+",
+        ];
+        assert_batches_eq!(expected, &results);
+
+        // nothing new has been appended
+        let results = ctx.sql(query).await?.collect().await?;
+        assert_eq!(results.iter().map(|b| b.num_rows()).sum::<usize>(), 0);
+
+        // a truncated file is read again from the start
+        fs::write(&path, "{\"a\": 5}\n")?;
+        let results = ctx.sql(query).await?.collect().await?;
+        #[rustfmt::skip]
+        let expected = vec![
+            "+---+",
+            "| a |",
+            "+---+",
+            "| 5 |",
+            "+---+",
+        ];
+        assert_batches_eq!(expected, &results);
+
+        // so is a rotated file longer than the offset of the previous one
+        fs::write(&path, "{\"a\": 6}\n{\"a\": 7}\n")?;
+        let results = ctx.sql(query).await?.collect().await?;
+        #[rustfmt::skip]
+        let expected = vec![
+            "+---+",
+            "| a |",
+            "+---+",
+            "| 6 |",
+            "| 7 |",
+            "+---+",
+        ];
+        assert_batches_eq!(expected, &results);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn nd_json_exec_incremental_offsets_follow_consumed_batches() -> Result<()> {
+        let tmp_dir = TempDir::new()?;
+        let path = tmp_dir.path().join("log.json");
+        fs::write(&path, "{\"a\": 1}\n{\"a\": 2}\n{\"a\": 3}\n")?;
+        let location = object_store::path::Path::from_filesystem_path(&path).unwrap();
+
+        let config = SessionConfig::new()
+            .with_batch_size(1)
+            .with_target_partitions(1);
+        let ctx = SessionContext::with_config(config);
+        let scan_options = JsonScanOptions::new().with_incremental(true);
+        let read_offsets = scan_options.read_offsets.clone().unwrap();
+        let options = NdJsonReadOptions::default().scan_options(scan_options);
+        ctx.register_json("log", path.to_str().unwrap(), options)
+            .await?;
+
+        // the offset only moves past the lines of a batch once the next one
+        // is requested, so the lines of the batches of a scan that stops
+        // early are read again
+        let mut stream = ctx.sql("SELECT a FROM log").await?.execute_stream().await?;
+        stream.next().await.unwrap()?;
+        assert_eq!(read_offsets.offset(&location), 0);
+        stream.next().await.unwrap()?;
+        assert_eq!(read_offsets.offset(&location), 9);
+        drop(stream);
+
+        let results = ctx.sql("SELECT a FROM log").await?.collect().await?;
+        #[rustfmt::skip]
+        let expected = vec![
+            "+---+",
+            "| a |",
+            "+---+",
+            "| 2 |",
+            "| 3 |",
+            "+---+",
+        ];
+        assert_batches_eq!(expected, &results);
+        assert_eq!(read_offsets.offset(&location), 27);
+
+        // the offset does not move past lines that fail to decode
+        let mut file = fs::OpenOptions::new().append(true).open(&path)?;
+        std::io::Write::write_all(&mut file, b"not json\n")?;
+        assert!(ctx.sql("SELECT a FROM log").await?.collect().await.is_err());
+        assert_eq!(read_offsets.offset(&location), 27);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn file_scan_config_try_from_path() -> Result<()> {
+        let tmp_dir = TempDir::new()?;
+        let dir = tmp_dir.path();
+        fs::create_dir_all(dir.join("a/b"))?;
+        fs::create_dir_all(dir.join("c"))?;
+        fs::write(dir.join("a/1.json"), "{\"a\": 1}\n")?;
+        fs::write(dir.join("a/b/2.json"), "{\"a\": 2, \"b\": \"x\"}\n")?;
+        fs::write(dir.join("a/notes.txt"), "not json\n")?;
+        fs::write(dir.join("c/3.json"), "{\"a\": 3}\n")?;
+
+        let ctx = SessionContext::new();
+        let store = ctx
+            .runtime_env()
+            .object_store(&ObjectStoreUrl::local_filesystem())?;
+        let format = JsonFormat::default();
+
+        // the glob matches the files of the subdirectories of `a` as well
+        let glob = format!("{}/a/*.json", dir.to_str().unwrap());
+        let config =
+            FileScanConfig::try_from_path(&glob, store.clone(), &format, 4).await?;
+        let names: Vec<_> = config
+            .file_groups
+            .iter()
+            .flatten()
+            .map(|file| {
+                file.object_meta
+                    .location
+                    .as_ref()
+                    .rsplit('/')
+                    .next()
+                    .unwrap()
+            })
+            .collect();
+        assert_eq!(names, vec!["1.json", "2.json"]);
+        assert_eq!(config.file_groups.len(), 2);
+        let fields: Vec<_> = config
+            .file_schema
+            .fields()
+            .iter()
+            .map(|f| f.name().as_str())
+            .collect();
+        assert_eq!(fields, vec!["a", "b"]);
+
+        let exec = NdJsonExec::new(config, FileCompressionType::UNCOMPRESSED);
+        let results =
+            crate::physical_plan::collect(Arc::new(exec), ctx.task_ctx()).await?;
+        #[rustfmt::skip]
+        let expected = vec![
+            "+---+---+",
+            "| a | b |",
+            "+---+---+",
+            "| 1 |   |",
+            "| 2 | x |",
+            "+---+---+",
+        ];
+        assert_batches_eq!(expected, &results);
+
+        // a directory is listed recursively
+        let path = dir.to_str().unwrap();
+        let config =
+            FileScanConfig::try_from_path(path, store.clone(), &format, 1).await?;
+        assert_eq!(config.file_groups.len(), 1);
+        assert_eq!(config.file_groups[0].len(), 3);
+
+        let missing = format!("{}/c/*.ndjson", dir.to_str().unwrap());
+        let err = FileScanConfig::try_from_path(&missing, store, &format, 1)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("No JSON files found"), "{}", err);
+
+        Ok(())
+    }
+
+    /// The name, parent and fields of a span
+    struct CapturedSpan {
+        name: &'static str,
+        parent: Option<u64>,
+        fields: HashMap<String, String>,
+    }
+
+    /// Captures the spans created while it is the default subscriber, whose
+    /// ids are one plus their index in `spans`
+    #[derive(Clone, Default)]
+    struct SpanCapture {
+        spans: Arc<parking_lot::Mutex<Vec<CapturedSpan>>>,
+        /// The spans entered, on a single thread
+        entered: Arc<parking_lot::Mutex<Vec<u64>>>,
+    }
+
+    struct FieldVisitor<'a>(&'a mut HashMap<String, String>);
+
+    impl tracing::field::Visit for FieldVisitor<'_> {
+        fn record_debug(
+            &mut self,
+            field: &tracing::field::Field,
+            value: &dyn std::fmt::Debug,
+        ) {
+            self.0
+                .insert(field.name().to_string(), format!("{:?}", value));
+        }
+    }
+
+    impl tracing::Subscriber for SpanCapture {
+        fn enabled(&self, _metadata: &tracing::Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, attrs: &tracing::span::Attributes<'_>) -> tracing::Id {
+            let mut fields = HashMap::new();
+            attrs.record(&mut FieldVisitor(&mut fields));
+            let parent = match attrs.parent() {
+                Some(parent) => Some(parent.into_u64()),
+                None if attrs.is_contextual() => self.entered.lock().last().copied(),
+                None => None,
+            };
+            let mut spans = self.spans.lock();
+            spans.push(CapturedSpan {
+                name: attrs.metadata().name(),
+                parent,
+                fields,
+            });
+            tracing::Id::from_u64(spans.len() as u64)
+        }
+
+        fn record(&self, _span: &tracing::Id, _values: &tracing::span::Record<'_>) {}
+
+        fn record_follows_from(&self, _span: &tracing::Id, _follows: &tracing::Id) {}
+
+        fn event(&self, _event: &tracing::Event<'_>) {}
+
+        fn enter(&self, span: &tracing::Id) {
+            self.entered.lock().push(span.into_u64());
+        }
+
+        fn exit(&self, _span: &tracing::Id) {
+            self.entered.lock().pop();
+        }
+    }
+
+    #[tokio::test]
+    async fn nd_json_exec_tracing_spans() -> Result<()> {
+        let tmp_dir = TempDir::new()?;
+        let dir = tmp_dir.path();
+        fs::write(dir.join("1.json"), "{\"a\": 1}\n{\"a\": 2}\n")?;
+        fs::write(dir.join("2.json"), "{\"a\": 3}\n")?;
+
+        let capture = SpanCapture::default();
+        let _guard = tracing::subscriber::set_default(capture.clone());
+
+        let ctx = SessionContext::new();
+        let store = ctx
+            .runtime_env()
+            .object_store(&ObjectStoreUrl::local_filesystem())?;
+        let path = dir.to_str().unwrap();
+        let config =
+            FileScanConfig::try_from_path(path, store, &JsonFormat::default(), 1).await?;
+        let exec = NdJsonExec::new(config, FileCompressionType::UNCOMPRESSED);
+        let results =
+            crate::physical_plan::collect(Arc::new(exec), ctx.task_ctx()).await?;
+        assert_eq!(results.iter().map(|b| b.num_rows()).sum::<usize>(), 3);
+
+        let spans = capture.spans.lock();
+        let ids_named = |name: &str| -> Vec<u64> {
+            (1..=spans.len() as u64)
+                .filter(|id| spans[*id as usize - 1].name == name)
+                .collect()
+        };
+        let field = |id: u64, name: &str| -> String {
+            let value = &spans[id as usize - 1].fields[name];
+            value.rsplit('/').next().unwrap().to_string()
+        };
+        let parent = |id: u64| spans[id as usize - 1].parent;
+
+        // the schema is inferred from every file
+        let infer = ids_named("json_infer_schema");
+        let files: Vec<_> = infer.iter().map(|id| field(*id, "path")).collect();
+        assert_eq!(files, vec!["1.json", "2.json"]);
+
+        // every file of the partition is opened and read in the span of its
+        // scan, and its batches deserialized in the span of its read
+        let scan = ids_named("json_scan");
+        assert_eq!(scan.len(), 1);
+        assert_eq!(field(scan[0], "partition"), "0");
+        for name in ["json_open", "json_read"] {
+            let ids = ids_named(name);
+            let files: Vec<_> = ids.iter().map(|id| field(*id, "path")).collect();
+            assert_eq!(files, vec!["1.json", "2.json"], "{}", name);
+            for id in ids {
+                assert_eq!(field(id, "partition"), "0");
+                assert_eq!(parent(id), Some(scan[0]));
+            }
+        }
+        let deserialize = ids_named("json_deserialize");
+        for read in ids_named("json_read") {
+            assert!(
+                deserialize.iter().any(|id| parent(*id) == Some(read)),
+                "no batch deserialized in the read of {}",
+                field(read, "path")
+            );
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn nd_json_exec_max_in_flight_bytes() -> Result<()> {
+        use crate::physical_plan::common::batch_byte_size;
+        use crate::test::object_store::local_unpartitioned_file;
+
+        // a wide fixture of 1000 objects with 100 fields each
+        let tmp_dir = TempDir::new()?;
+        let path = tmp_dir.path().join("wide.json");
+        let data: String = (0..1000)
+            .map(|row| {
+                let fields: Vec<_> = (0..100)
+                    .map(|col| format!("\"c{}\": \"value {} of row {}\"", col, col, row))
+                    .collect();
+                format!("{{{}}}\n", fields.join(", "))
+            })
+            .collect();
+        fs::write(&path, data)?;
+
+        let session_ctx =
+            SessionContext::with_config(SessionConfig::new().with_batch_size(50));
+        let store = session_ctx
+            .runtime_env()
+            .object_store(&ObjectStoreUrl::local_filesystem())?;
+        let meta = local_unpartitioned_file(&path);
+        let file_schema = JsonFormat::default()
+            .infer_schema(&store, &[meta.clone()])
+            .await?;
+        let exec = |scan_options: JsonScanOptions| {
+            NdJsonExec::new(
+                FileScanConfig {
+                    object_store_url: ObjectStoreUrl::local_filesystem(),
+                    file_groups: vec![vec![meta.clone().into()]],
+                    file_schema: file_schema.clone(),
+                    statistics: Statistics::default(),
+                    projection: None,
+                    limit: None,
+                    table_partition_cols: vec![],
+                    config_options: ConfigOptions::new().into_shareable(),
+                },
+                FileCompressionType::UNCOMPRESSED,
+            )
+            .with_scan_options(scan_options)
+        };
+
+        // measure the size of a batch without a limit
+        let batches = exec(JsonScanOptions::new())
+            .execute(0, session_ctx.task_ctx())?
+            .collect::<Vec<_>>()
+            .await;
+        let batch_bytes = batches
+            .into_iter()
+            .map(|batch| batch.map(|batch| batch_byte_size(&batch)))
+            .collect::<arrow::error::Result<Vec<_>>>()?
+            .into_iter()
+            .max()
+            .unwrap();
+
+        let max_bytes = 3 * batch_bytes;
+        let exec = exec(JsonScanOptions::new().with_max_in_flight_bytes(max_bytes));
+        let mut stream = exec.execute(0, session_ctx.task_ctx())?;
+        let mut rows = 0;
+        while let Some(batch) = stream.next().await {
+            rows += batch?.num_rows();
+        }
+        assert_eq!(rows, 1000);
+
+        // the peak is reported, the limit itself is tested by the limiter
+        let peak = exec
+            .metrics()
+            .unwrap()
+            .sum_by_name("peak_in_flight_bytes")
+            .unwrap()
+            .as_usize();
+        assert!(peak >= batch_bytes, "{} < {}", peak, batch_bytes);
+        assert!(peak <= max_bytes, "{} > {}", peak, max_bytes);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn nd_json_exec_max_batches_in_flight() -> Result<()> {
+        let tmp_dir = TempDir::new()?;
+        let path = tmp_dir.path().join("batches.json");
+        let data: String = (0..1000).map(|i| format!("{{\"a\": {}}}\n", i)).collect();
+        fs::write(&path, data)?;
+
+        let config = file_scan_config(vec![vec![PartitionedFile::from(
+            crate::test::object_store::local_unpartitioned_file(path),
+        )]]);
+        let max_batches = 3;
+        let exec = NdJsonExec::new(config, FileCompressionType::UNCOMPRESSED)
+            .with_scan_options(
+                JsonScanOptions::new().with_max_batches_in_flight(max_batches),
+            );
+
+        let session_ctx =
+            SessionContext::with_config(SessionConfig::new().with_batch_size(10));
+        let mut stream = exec.execute(0, session_ctx.task_ctx())?;
+        let mut rows = 0;
+        while let Some(batch) = stream.next().await {
+            rows += batch?.num_rows();
+        }
+        assert_eq!(rows, 1000);
+
+        let peak = exec
+            .metrics()
+            .unwrap()
+            .sum_by_name("peak_batches_in_flight")
+            .unwrap()
+            .as_usize();
+        assert!((1..=max_batches).contains(&peak), "{}", peak);
+
+        Ok(())
+    }
+
+    /// An in memory store recording the range of every read, `None` for a
+    /// whole file, and holding it until a permit of `gate` is added
+    #[derive(Debug)]
+    struct GatedStore {
+        inner: object_store::memory::InMemory,
+        gate: tokio::sync::Semaphore,
+        reads: Mutex<Vec<Option<std::ops::Range<usize>>>>,
+    }
+
+    impl GatedStore {
+        async fn pass(&self, range: Option<std::ops::Range<usize>>) {
+            self.reads.lock().push(range);
+            self.gate.acquire().await.unwrap().forget();
+        }
+    }
+
+    impl std::fmt::Display for GatedStore {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "Gated({})", self.inner)
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl ObjectStore for GatedStore {
+        async fn put(
+            &self,
+            location: &object_store::path::Path,
+            bytes: Bytes,
+        ) -> object_store::Result<()> {
+            self.inner.put(location, bytes).await
+        }
+
+        async fn put_multipart(
+            &self,
+            _location: &object_store::path::Path,
+        ) -> object_store::Result<(
+            object_store::MultipartId,
+            Box<dyn tokio::io::AsyncWrite + Unpin + Send>,
+        )> {
+            Err(object_store::Error::NotImplemented)
+        }
+
+        async fn abort_multipart(
+            &self,
+            _location: &object_store::path::Path,
+            _multipart_id: &object_store::MultipartId,
+        ) -> object_store::Result<()> {
+            Err(object_store::Error::NotImplemented)
+        }
+
+        async fn get(
+            &self,
+            location: &object_store::path::Path,
+        ) -> object_store::Result<GetResult> {
+            self.pass(None).await;
+            self.inner.get(location).await
+        }
+
+        async fn get_range(
+            &self,
+            location: &object_store::path::Path,
+            range: std::ops::Range<usize>,
+        ) -> object_store::Result<Bytes> {
+            self.pass(Some(range.clone())).await;
+            self.inner.get_range(location, range).await
+        }
+
+        async fn head(
+            &self,
+            location: &object_store::path::Path,
+        ) -> object_store::Result<object_store::ObjectMeta> {
+            self.inner.head(location).await
+        }
+
+        async fn delete(
+            &self,
+            _location: &object_store::path::Path,
+        ) -> object_store::Result<()> {
+            Err(object_store::Error::NotImplemented)
+        }
+
+        async fn list(
+            &self,
+            _prefix: Option<&object_store::path::Path>,
+        ) -> object_store::Result<
+            BoxStream<'_, object_store::Result<object_store::ObjectMeta>>,
+        > {
+            Err(object_store::Error::NotImplemented)
+        }
+
+        async fn list_with_delimiter(
+            &self,
+            _prefix: Option<&object_store::path::Path>,
+        ) -> object_store::Result<object_store::ListResult> {
+            Err(object_store::Error::NotImplemented)
+        }
+
+        async fn copy(
+            &self,
+            _from: &object_store::path::Path,
+            _to: &object_store::path::Path,
+        ) -> object_store::Result<()> {
+            Err(object_store::Error::NotImplemented)
+        }
+
+        async fn copy_if_not_exists(
+            &self,
+            _from: &object_store::path::Path,
+            _to: &object_store::path::Path,
+        ) -> object_store::Result<()> {
+            Err(object_store::Error::NotImplemented)
+        }
+    }
+
+    #[tokio::test]
+    async fn nd_json_exec_prefetch() -> Result<()> {
+        use arrow::datatypes::DataType;
+
+        let store = Arc::new(GatedStore {
+            inner: object_store::memory::InMemory::new(),
+            gate: tokio::sync::Semaphore::new(0),
+            reads: Default::default(),
+        });
+        let location = object_store::path::Path::from("gated.json");
+        store
+            .inner
+            .put(&location, "{\"a\": 1}\n{\"a\": 2}\n".into())
+            .await?;
+        let file = PartitionedFile::from(store.head(&location).await?);
+        let ctx = SessionContext::new();
+        ctx.runtime_env()
+            .register_object_store("gated", "", store.clone());
+        let exec = || {
+            NdJsonExec::new(
+                FileScanConfig {
+                    object_store_url: ObjectStoreUrl::parse("gated://").unwrap(),
+                    file_groups: vec![vec![file.clone()]],
+                    file_schema: Arc::new(Schema::new(vec![Field::new(
+                        "a",
+                        DataType::Int64,
+                        true,
+                    )])),
+                    statistics: Statistics::default(),
+                    projection: None,
+                    limit: None,
+                    table_partition_cols: vec![],
+                    config_options: ConfigOptions::new().into_shareable(),
+                },
+                FileCompressionType::UNCOMPRESSED,
+            )
+        };
+        // the fetch starts before the plan is executed, and is cancelled
+        // with the plan
+        let dropped_exec = exec();
+        dropped_exec.prefetch(&ctx.runtime_env(), 1024)?;
+        for _ in 0..100 {
+            if !store.reads.lock().is_empty() {
+                break;
+            }
+            tokio::task::yield_now().await;
+        }
+        assert_eq!(*store.reads.lock(), vec![Some(0..18)]);
+        let references = Arc::strong_count(&store);
+        drop(dropped_exec);
+        for _ in 0..100 {
+            if Arc::strong_count(&store) < references {
+                break;
+            }
+            tokio::task::yield_now().await;
+        }
+        // the aborted fetch released the store without reading past the gate
+        assert!(Arc::strong_count(&store) < references);
+        store.reads.lock().clear();
+
+        // no read is held from now on
+        store.gate.add_permits(100);
+        let scan = |exec: NdJsonExec| {
+            let task_ctx = ctx.task_ctx();
+            let store = store.clone();
+            async move {
+                let batches = exec.execute(0, task_ctx)?.try_collect::<Vec<_>>().await?;
+                let rows = batches.iter().map(|batch| batch.num_rows()).sum::<usize>();
+                assert_eq!(rows, 2);
+                Ok::<_, DataFusionError>(std::mem::take(&mut *store.reads.lock()))
+            }
+        };
+
+        // without prefetching, the file is read when executed
+        assert_eq!(scan(exec()).await?, vec![None]);
+
+        // the whole file is fetched ahead, and not read again
+        let warm_exec = exec();
+        warm_exec.prefetch(&ctx.runtime_env(), 1024)?;
+        assert_eq!(scan(warm_exec).await?, vec![Some(0..18)]);
+
+        // only the first line is fetched ahead, then the rest of the file
+        let partial_exec = exec();
+        partial_exec.prefetch(&ctx.runtime_env(), 9)?;
+        assert_eq!(scan(partial_exec).await?, vec![Some(0..9), Some(9..18)]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn write_json_results() -> Result<()> {
+        // create partitioned input file and context
+        let tmp_dir = TempDir::new()?;
+        let ctx =
+            SessionContext::with_config(SessionConfig::new().with_target_partitions(8));
+
+        let path = format!("{}/1.json", TEST_DATA_BASE);
+
+        // register json file with the execution context
+        ctx.register_json("test", path.as_str(), NdJsonReadOptions::default())
+            .await?;
+
+        // execute a simple query and write the results to CSV
+        let out_dir = tmp_dir.as_ref().to_str().unwrap().to_string() + "/out";
+        let df = ctx.sql("SELECT a, b FROM test").await?;
+        df.write_json(&out_dir).await?;
+
+        // create a new context and verify that the results were saved to a partitioned csv file
+        let ctx = SessionContext::new();
+
+        // register each partition as well as the top level dir
+        let json_read_option = NdJsonReadOptions::default();
+        ctx.register_json(
+            "part0",
+            &format!("{}/part-0.json", out_dir),
+            json_read_option.clone(),
+        )
+        .await?;
+        ctx.register_json("allparts", &out_dir, json_read_option)
+            .await?;
+
+        let part0 = ctx.sql("SELECT a, b FROM part0").await?.collect().await?;
+        let allparts = ctx
+            .sql("SELECT a, b FROM allparts")
+            .await?
+            .collect()
+            .await?;
+
+        let allparts_count: usize = allparts.iter().map(|batch| batch.num_rows()).sum();
+
+        assert_eq!(part0[0].schema(), allparts[0].schema());
+
+        assert_eq!(allparts_count, 4);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn empty_files_in_file_group() -> Result<()> {
+        let tmp_dir = TempDir::new()?;
+        let files = [
+            ("a.json", "{\"a\": 1}\n{\"a\": 2}\n{\"a\": 3}\n"),
+            ("b.json", ""),
+            ("c.json", "\n"),
+            ("d.json", "{\"a\": 4}\n{\"a\": 5}"),
+            ("e.json.gz", ""),
+        ];
+        for (name, data) in files {
+            fs::write(tmp_dir.path().join(name), data)?;
+        }
+
+        // a single partition reads every file of the directory in turn
+        let ctx =
+            SessionContext::with_config(SessionConfig::new().with_target_partitions(1));
+        let path = format!("{}/", tmp_dir.path().to_str().unwrap());
+        ctx.register_json("t", &path, NdJsonReadOptions::default())
+            .await?;
+
+        let df = ctx.sql("SELECT a FROM t").await?;
+        let plan = df.create_physical_plan().await?;
+        let json_exec = plan
+            .as_any()
+            .downcast_ref::<crate::physical_plan::projection::ProjectionExec>()
+            .and_then(|exec| exec.input().as_any().downcast_ref::<NdJsonExec>())
+            .or_else(|| plan.as_any().downcast_ref::<NdJsonExec>())
+            .expect("a JSON scan");
+        assert_eq!(json_exec.base_config().file_groups.len(), 1);
+        assert_eq!(json_exec.base_config().file_groups[0].len(), 5);
+
+        let results = crate::physical_plan::collect(plan, ctx.task_ctx()).await?;
+        let count: usize = results.iter().map(|batch| batch.num_rows()).sum();
+        assert_eq!(count, 5);
+
+        let results = ctx.sql("SELECT COUNT(*) FROM t").await?.collect().await?;
+        let expected = vec![
+            "+-----------------+",
+            "| COUNT(UInt8(1)) |",
+            "+-----------------+",
+            "| 5               |",
+            "+-----------------+",
+        ];
+        assert_batches_eq!(expected, &results);
+
+        Ok(())
+    }
+
+    fn file_scan_config(file_groups: Vec<Vec<PartitionedFile>>) -> FileScanConfig {
+        FileScanConfig {
+            object_store_url: ObjectStoreUrl::local_filesystem(),
+            file_groups,
+            file_schema: Arc::new(Schema::new(vec![Field::new(
+                "a",
+                arrow::datatypes::DataType::Int64,
+                true,
+            )])),
+            statistics: Statistics::default(),
+            projection: None,
+            limit: None,
+            table_partition_cols: vec![],
+            config_options: ConfigOptions::new().into_shareable(),
+        }
+    }
+
+    #[test]
+    fn repartition_file_min_size() {
+        let files = vec![
+            PartitionedFile::new("a.json".to_string(), 100),
+            PartitionedFile::new("b.json".to_string(), 1000),
+            PartitionedFile::new("c.json".to_string(), 50),
+        ];
+        let exec = NdJsonExec::new(
+            file_scan_config(vec![files]),
+            FileCompressionType::UNCOMPRESSED,
+        );
+
+        // the files under the threshold are read whole, the larger one is
+        // cut every 288 bytes of the 1150 bytes of the scan
+        let repartitioned = exec.get_repartitioned(4, 200).unwrap();
+        let file_groups: Vec<Vec<_>> = repartitioned
+            .base_config()
+            .file_groups
+            .iter()
+            .map(|group| {
+                group
+                    .iter()
+                    .map(|file| {
+                        let range =
+                            file.range.as_ref().map(|range| (range.start, range.end));
+                        (file.object_meta.location.to_string(), range)
+                    })
+                    .collect()
+            })
+            .collect();
+        let expected = vec![
+            vec![
+                ("a.json".to_string(), None),
+                ("b.json".to_string(), Some((0, 188))),
+            ],
+            vec![("b.json".to_string(), Some((188, 476)))],
+            vec![("b.json".to_string(), Some((476, 764)))],
+            vec![
+                ("b.json".to_string(), Some((764, 1000))),
+                ("c.json".to_string(), None),
+            ],
+        ];
+        assert_eq!(file_groups, expected);
+        assert_eq!(repartitioned.output_partitioning().partition_count(), 4);
+
+        // no file reaches the threshold
+        assert!(exec.get_repartitioned(4, 2000).is_none());
+
+        // compressed files are never split
+        let exec = NdJsonExec::new(
+            file_scan_config(vec![vec![PartitionedFile::new(
+                "b.json.gz".to_string(),
+                1000,
+            )]]),
+            FileCompressionType::GZIP,
+        );
+        assert!(exec.get_repartitioned(4, 200).is_none());
+    }
+
+    #[tokio::test]
+    async fn read_repartitioned_files() -> Result<()> {
+        let tmp_dir = TempDir::new()?;
+        let large = (0..100)
+            .map(|i| format!("{{\"a\": {}, \"s\": \"{}\"}}\n", i, "x".repeat(i % 7)))
+            .collect::<String>();
+        let files = [
+            ("large.json", large.as_str()),
+            ("small.json", "{\"a\": 100}\n{\"a\": 101}\n"),
+        ];
+        let mut partitioned_files = vec![];
+        for (name, data) in files {
+            let path = tmp_dir.path().join(name);
+            fs::write(&path, data)?;
+            partitioned_files.push(PartitionedFile::from(
+                crate::test::object_store::local_unpartitioned_file(path),
+            ));
+        }
+
+        let exec = NdJsonExec::new(
+            file_scan_config(vec![partitioned_files]),
+            FileCompressionType::UNCOMPRESSED,
+        )
+        .get_repartitioned(3, 100)
+        .unwrap();
+        let file_groups = &exec.base_config().file_groups;
+        assert_eq!(file_groups.len(), 3);
+        let small = file_groups
+            .iter()
+            .flatten()
+            .filter(|file| file.object_meta.location.as_ref().ends_with("small.json"))
+            .collect::<Vec<_>>();
+        assert_eq!(small.len(), 1);
+        assert!(small[0].range.is_none());
+
+        // every line is read by exactly one of the ranges splitting its file
+        let session_ctx = SessionContext::new();
+        let results =
+            crate::physical_plan::collect(Arc::new(exec), session_ctx.task_ctx()).await?;
+        let mut values = results
+            .iter()
+            .flat_map(|batch| {
+                let array = batch
+                    .column(0)
+                    .as_any()
+                    .downcast_ref::<arrow::array::Int64Array>()
+                    .unwrap();
+                array.values().to_vec()
+            })
+            .collect::<Vec<_>>();
+        values.sort_unstable();
+        assert_eq!(values, (0..102).collect::<Vec<i64>>());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn read_row_ids() -> Result<()> {
+        let tmp_dir = TempDir::new()?;
+        let files = [
+            ("1.json", "{\"a\": 1}\n{\"a\": 2}\n"),
+            ("2.json", "{\"a\": 3}\n"),
+            ("3.json", "{\"a\": 4}\n{\"a\": 5}\n"),
+        ];
+        let mut partitioned_files = vec![];
+        for (name, data) in files {
+            let path = tmp_dir.path().join(name);
+            fs::write(&path, data)?;
+            partitioned_files.push(PartitionedFile::from(
+                crate::test::object_store::local_unpartitioned_file(path),
+            ));
+        }
+        let last = partitioned_files.split_off(2);
+
+        let scan_options = JsonScanOptions::new().with_row_id(true);
+        let mut config = file_scan_config(vec![partitioned_files, last]);
+        config.file_schema = scan_options.apply_to_schema(config.file_schema)?;
+        let exec = NdJsonExec::new(config, FileCompressionType::UNCOMPRESSED)
+            .with_scan_options(scan_options);
+        assert_eq!(exec.schema().field(1).name(), ROW_ID_COLUMN);
+
+        let session_ctx = SessionContext::new();
+        let mut ids = vec![];
+        for partition in 0..2 {
+            let results = exec
+                .execute(partition, session_ctx.task_ctx())?
+                .try_collect::<Vec<_>>()
+                .await?;
+            let mut partition_ids = vec![];
+            for batch in results {
+                assert_eq!(batch.schema(), exec.schema());
+                let array = batch
+                    .column(1)
+                    .as_any()
+                    .downcast_ref::<Int64Array>()
+                    .unwrap();
+                partition_ids.extend_from_slice(array.values());
+            }
+            ids.push(partition_ids);
+        }
+
+        // the ids of every partition are prefixed by its index, and follow
+        // the order of its files, so that no two partitions share an id
+        let first = 1 << RowIds::ROW_BITS;
+        assert_eq!(ids, vec![vec![0, 1, 2], vec![first, first + 1]]);
+        let mut all = ids.concat();
+        all.sort_unstable();
+        all.dedup();
+        assert_eq!(all.len(), 5);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn read_row_id_field_without_row_ids() -> Result<()> {
+        let tmp_dir = TempDir::new()?;
+        let path = tmp_dir.path().join("1.json");
+        fs::write(&path, "{\"a\": 1, \"_row_id\": 7}\n")?;
+        let file = PartitionedFile::from(
+            crate::test::object_store::local_unpartitioned_file(path),
+        );
+
+        let mut config = file_scan_config(vec![vec![file]]);
+        config.file_schema = Arc::new(Schema::new(vec![
+            Field::new("a", arrow::datatypes::DataType::Int64, true),
+            Field::new(ROW_ID_COLUMN, arrow::datatypes::DataType::Int64, true),
+        ]));
+        let exec = NdJsonExec::new(config, FileCompressionType::UNCOMPRESSED);
+
+        let session_ctx = SessionContext::new();
+        let results = exec
+            .execute(0, session_ctx.task_ctx())?
+            .try_collect::<Vec<_>>()
+            .await?;
+        assert_batches_eq!(
+            &[
+                "+---+---------+",
+                "| a | _row_id |",
+                "+---+---------+",
+                "| 1 | 7       |",
+                "+---+---------+",
+            ],
+            &results
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn merge_overlapping_sorted_files() -> Result<()> {
+        use crate::execution::runtime_env::{RuntimeConfig, RuntimeEnv};
+
+        let tmp_dir = TempDir::new()?;
+        let mut partitioned_files = vec![];
+        for (name, start) in [("even.json", 0), ("odd.json", 1)] {
+            let path = tmp_dir.path().join(name);
+            let data = (0..500)
+                .map(|i| format!("{{\"a\": {}}}\n", 2 * i + start))
+                .collect::<String>();
+            fs::write(&path, data)?;
+            partitioned_files.push(PartitionedFile::from(
+                crate::test::object_store::local_unpartitioned_file(path),
+            ));
+        }
+
+        let config = file_scan_config(vec![partitioned_files]);
+        let ordering = vec![PhysicalSortExpr {
+            expr: crate::physical_plan::expressions::col("a", &config.file_schema)?,
+            options: Default::default(),
+        }];
+        let session_ctx =
+            SessionContext::with_config(SessionConfig::new().with_batch_size(10));
+
+        // the rows of both files are interleaved in a single sorted stream
+        let exec = NdJsonExec::new(config.clone(), FileCompressionType::UNCOMPRESSED)
+            .with_output_ordering(ordering.clone());
+        assert_eq!(exec.output_ordering(), Some(ordering.as_slice()));
+        let results = exec
+            .execute(0, session_ctx.task_ctx())?
+            .try_collect::<Vec<_>>()
+            .await?;
+        let values = results
+            .iter()
+            .flat_map(|batch| {
+                let array = batch
+                    .column(0)
+                    .as_any()
+                    .downcast_ref::<Int64Array>()
+                    .unwrap();
+                array.values().to_vec()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(values, (0..1000).collect::<Vec<_>>());
+
+        // the files merged take turns with a single permit to read a file
+        let runtime = RuntimeEnv::new(RuntimeConfig::new().with_max_open_files(1))?;
+        let limited_ctx = SessionContext::with_config_rt(
+            SessionConfig::new().with_batch_size(10),
+            Arc::new(runtime),
+        );
+        let results = exec
+            .execute(0, limited_ctx.task_ctx())?
+            .try_collect::<Vec<_>>()
+            .await?;
+        let rows = results.iter().map(|batch| batch.num_rows()).sum::<usize>();
+        assert_eq!(rows, 1000);
+
+        // the files are streamed, so that a limit only decodes the batches
+        // needed for the first rows of the merge
+        let mut config = config;
+        config.limit = Some(5);
+        let exec = NdJsonExec::new(config, FileCompressionType::UNCOMPRESSED)
+            .with_output_ordering(ordering);
+        let results = exec
+            .execute(0, session_ctx.task_ctx())?
+            .try_collect::<Vec<_>>()
+            .await?;
+        let expected = vec![
+            "+---+", "| a |", "+---+", "| 0 |", "| 1 |", "| 2 |", "| 3 |", "| 4 |",
+            "+---+",
+        ];
+        assert_batches_eq!(expected, &results);
+        let decoded_arrays = exec
+            .metrics()
+            .unwrap()
+            .sum_by_name("decoded_arrays")
+            .unwrap()
+            .as_usize();
+        assert!(decoded_arrays < 20, "{} arrays decoded", decoded_arrays);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn merge_sorted_files_with_row_ids() -> Result<()> {
+        let tmp_dir = TempDir::new()?;
+        let mut partitioned_files = vec![];
+        for (name, start) in [("even.json", 0), ("odd.json", 1)] {
+            let path = tmp_dir.path().join(name);
+            let data = (0..50)
+                .map(|i| format!("{{\"a\": {}}}\n", 2 * i + start))
+                .collect::<String>();
+            fs::write(&path, data)?;
+            partitioned_files.push(PartitionedFile::from(
+                crate::test::object_store::local_unpartitioned_file(path),
+            ));
+        }
+
+        let scan_options = JsonScanOptions::new().with_row_id(true);
+        let mut config = file_scan_config(vec![partitioned_files]);
+        config.file_schema = scan_options.apply_to_schema(config.file_schema)?;
+        let ordering = vec![PhysicalSortExpr {
+            expr: crate::physical_plan::expressions::col("a", &config.file_schema)?,
+            options: Default::default(),
+        }];
+        let exec = NdJsonExec::new(config, FileCompressionType::UNCOMPRESSED)
+            .with_scan_options(scan_options)
+            .with_output_ordering(ordering);
+
+        // the ids of the rows of a file are assigned before the merge, so
+        // that they do not depend on how the batches of the files interleave
+        let mut all_ids = vec![];
+        for batch_size in [1, 7, 100] {
+            let session_ctx = SessionContext::with_config(
+                SessionConfig::new().with_batch_size(batch_size),
+            );
+            let results = exec
+                .execute(0, session_ctx.task_ctx())?
+                .try_collect::<Vec<_>>()
+                .await?;
+            let mut ids = vec![];
+            for batch in results {
+                let array = batch
+                    .column(1)
+                    .as_any()
+                    .downcast_ref::<Int64Array>()
+                    .unwrap();
+                ids.extend_from_slice(array.values());
+            }
+            all_ids.push(ids);
+        }
+
+        let odd = 1 << RowIds::FILE_ROW_BITS;
+        let expected = (0..50).flat_map(|i| [i, odd + i]).collect::<Vec<_>>();
+        assert_eq!(all_ids, vec![expected; 3]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn report_file_progress() -> Result<()> {
+        let tmp_dir = TempDir::new()?;
+        let path = tmp_dir.path().join("progress.json");
+        let data = (0..1000)
+            .map(|i| format!("{{\"a\": {}, \"b\": \"row {:04}\"}}\n", i, i))
+            .collect::<String>();
+        fs::write(&path, &data)?;
+        let file = crate::test::object_store::local_unpartitioned_file(path);
+        let location = file.location.clone();
+
+        let reports = Arc::new(parking_lot::Mutex::new(vec![]));
+        let scan_options = JsonScanOptions::new().with_progress({
+            let reports = reports.clone();
+            move |progress| reports.lock().push(progress)
+        });
+        let mut config = file_scan_config(vec![vec![PartitionedFile::from(file)]]);
+        config.file_schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int64, true),
+            Field::new("b", DataType::Utf8, true),
+        ]));
+        let exec = NdJsonExec::new(config, FileCompressionType::UNCOMPRESSED)
+            .with_scan_options(scan_options);
+
+        let session_ctx =
+            SessionContext::with_config(SessionConfig::new().with_batch_size(100));
+        let results = exec
+            .execute(0, session_ctx.task_ctx())?
+            .try_collect::<Vec<_>>()
+            .await?;
+        assert_eq!(results.len(), 10);
+
+        // one report per batch, and one at the end of the file
+        let reports = reports.lock().clone();
+        assert_eq!(reports.len(), 11);
+        for (report, next) in reports.iter().zip(&reports[1..]) {
+            assert!(report.bytes_read <= next.bytes_read, "{:?}", reports);
+        }
+        // the file is read incrementally as it is decoded
+        assert!(reports[0].bytes_read < data.len(), "{:?}", reports);
+        for (i, report) in reports.iter().enumerate() {
+            assert_eq!(report.location, location);
+            assert_eq!(report.rows, ((i + 1) * 100).min(1000));
+            assert_eq!(report.done, i == 10);
+        }
+        assert_eq!(reports[10].bytes_read, data.len());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn detect_compression_from_extension() -> Result<()> {
+        use std::io::Write;
+
+        let tmp_dir = TempDir::new()?;
+        let compressions = [
+            FileCompressionType::UNCOMPRESSED,
+            FileCompressionType::GZIP,
+            FileCompressionType::BZIP2,
+            FileCompressionType::XZ,
+            FileCompressionType::ZSTD,
+        ];
+        for (i, file_compression_type) in compressions.iter().enumerate() {
+            let ext =
+                FileType::JSON.get_ext_with_compression(file_compression_type.clone())?;
+            let file = fs::File::create(tmp_dir.path().join(format!("{}{}", i, ext)))?;
+            let mut writer = file_compression_type.convert_write(file)?;
+            writeln!(writer, "{{\"a\": {}, \"b\": \"{}\"}}", i, ext)?;
+            writer.finish()?;
+        }
+        // files without the JSON extension are ignored
+        fs::write(tmp_dir.path().join("other.csv.gz"), "a,b")?;
+
+        let path = format!("{}/", tmp_dir.path().to_str().unwrap());
+        let mut ctx = SessionContext::new();
+        ctx.register_json("t", &path, NdJsonReadOptions::default())
+            .await?;
+        let results = ctx
+            .sql("SELECT a, b FROM t ORDER BY a")
+            .await?
+            .collect()
+            .await?;
+        let expected = vec![
+            "+---+-----------+",
+            "| a | b         |",
+            "+---+-----------+",
+            "| 0 | .json     |",
+            "| 1 | .json.gz  |",
+            "| 2 | .json.bz2 |",
+            "| 3 | .json.xz  |",
+            "| 4 | .json.zst |",
+            "+---+-----------+",
+        ];
+        assert_batches_eq!(expected, &results);
+
+        // an explicit compression type applies to every file with the extension
+        let options = NdJsonReadOptions::default()
+            .file_extension(".json.gz")
+            .file_compression_type(FileCompressionType::GZIP);
+        let results = ctx.read_json(&path, options).await?.collect().await?;
+        let expected = vec![
+            "+---+----------+",
+            "| a | b        |",
+            "+---+----------+",
+            "| 1 | .json.gz |",
+            "+---+----------+",
+        ];
+        assert_batches_eq!(expected, &results);
+
+        let options = NdJsonReadOptions::default()
+            .file_compression_type(FileCompressionType::UNCOMPRESSED);
+        let results = ctx.read_json(&path, options).await?.collect().await?;
+        assert_eq!(results.iter().map(|b| b.num_rows()).sum::<usize>(), 1);
+
+        Ok(())
+    }
+
+    #[rstest(ext, case(".json.bz2"), case(".json.xz"))]
+    #[tokio::test]
+    async fn read_compressed_fixtures(ext: &str) -> Result<()> {
+        let mut ctx = SessionContext::new();
+        let path = format!("{}/1.json", TEST_DATA_BASE);
+        let expected = ctx
+            .read_json(&path, NdJsonReadOptions::default())
+            .await?
+            .collect()
+            .await?;
+
+        // the compression is detected from the extension, so the batches and
+        // their inferred schema match those of the uncompressed file
+        let compressed_path = format!("{}{}", path, &ext[".json".len()..]);
+        let options = NdJsonReadOptions::default().file_extension(ext);
+        let results = ctx
+            .read_json(&compressed_path, options)
+            .await?
+            .collect()
+            .await?;
+        assert_eq!(results, expected);
+
+        Ok(())
+    }
+
+    #[rstest(
+        file_compression_type,
+        case(FileCompressionType::UNCOMPRESSED),
+        case(FileCompressionType::GZIP),
+        case(FileCompressionType::BZIP2),
+        case(FileCompressionType::XZ),
+        case(FileCompressionType::ZSTD)
+    )]
+    #[tokio::test]
+    async fn write_compressed_json_results(
+        file_compression_type: FileCompressionType,
+    ) -> Result<()> {
+        let tmp_dir = TempDir::new()?;
+        let ctx =
+            SessionContext::with_config(SessionConfig::new().with_target_partitions(2));
+        ctx.register_json(
+            "test",
+            &format!("{}/1.json", TEST_DATA_BASE),
+            NdJsonReadOptions::default(),
+        )
+        .await?;
+
+        let out_dir = tmp_dir.as_ref().to_str().unwrap().to_string() + "/out";
+        ctx.sql("SELECT a, b FROM test")
+            .await?
+            .write_json_with_compression(&out_dir, file_compression_type.clone())
+            .await?;
+
+        // every partition is written with the extension of the compression
+        let ext =
+            FileType::JSON.get_ext_with_compression(file_compression_type.clone())?;
+        let files = fs::read_dir(&out_dir)?
+            .map(|entry| Ok(entry?.file_name().to_string_lossy().to_string()))
+            .collect::<Result<Vec<_>>>()?;
+        assert!(!files.is_empty());
+        assert!(
+            files.iter().all(|file| file.ends_with(ext.as_str())),
+            "{:?}",
+            files
+        );
+
+        let options = NdJsonReadOptions::default()
+            .file_extension(ext.as_str())
+            .file_compression_type(file_compression_type);
+        let results = SessionContext::new()
+            .read_json(&out_dir, options)
+            .await?
+            .collect()
+            .await?;
+        let count: usize = results.iter().map(|batch| batch.num_rows()).sum();
+        assert_eq!(count, 4);
+
+        Ok(())
+    }
+
+    #[rstest(
+        file_compression_type,
+        case(FileCompressionType::UNCOMPRESSED),
+        case(FileCompressionType::GZIP),
+        case(FileCompressionType::BZIP2),
+        case(FileCompressionType::XZ),
+        case(FileCompressionType::ZSTD)
+    )]
+    #[tokio::test]
+    async fn test_chunked(file_compression_type: FileCompressionType) {
+        let mut ctx = SessionContext::new();
+
+        for chunk_size in [10, 20, 30, 40] {
             ctx.runtime_env().register_object_store(
                 "file",
                 "",
@@ -593,6 +3516,208 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn nd_json_exec_chunked_invalid_lines() -> Result<()> {
+        use arrow::datatypes::DataType;
+
+        // lines 2, 5 and 8 are invalid, and every chunk of the file holds
+        // at most part of one of them
+        let data: String = (0..10)
+            .map(|i| match i % 3 {
+                1 => format!("{{\"a\": {}\n", i),
+                _ => format!("{{\"a\": {}}}\n", i),
+            })
+            .collect();
+        let inner = Arc::new(object_store::memory::InMemory::new());
+        let location = object_store::path::Path::from("invalid.json");
+        inner.put(&location, data.into()).await?;
+        let file = PartitionedFile::from(inner.head(&location).await?);
+
+        let ctx = SessionContext::new();
+        ctx.runtime_env().register_object_store(
+            "chunked",
+            "",
+            Arc::new(ChunkedStore::new(inner, 4)),
+        );
+        let scan = |scan_options: JsonScanOptions| {
+            let exec = NdJsonExec::new(
+                FileScanConfig {
+                    object_store_url: ObjectStoreUrl::parse("chunked://").unwrap(),
+                    file_groups: vec![vec![file.clone()]],
+                    file_schema: Arc::new(Schema::new(vec![Field::new(
+                        "a",
+                        DataType::Int64,
+                        true,
+                    )])),
+                    statistics: Statistics::default(),
+                    projection: None,
+                    limit: None,
+                    table_partition_cols: vec![],
+                    config_options: ConfigOptions::new().into_shareable(),
+                },
+                FileCompressionType::UNCOMPRESSED,
+            )
+            .with_scan_options(scan_options);
+            let task_ctx = ctx.task_ctx();
+            async move {
+                let batches = exec.execute(0, task_ctx)?.try_collect::<Vec<_>>().await?;
+                Ok::<_, DataFusionError>(
+                    batches.iter().map(|batch| batch.num_rows()).sum::<usize>(),
+                )
+            }
+        };
+
+        // a single reader reads the whole file, skipping its invalid lines
+        let options = JsonScanOptions::new().with_skip_invalid_rows(true);
+        assert_eq!(scan(options.clone()).await?, 7);
+        assert_eq!(scan(options.clone().with_max_error_count(3)).await?, 7);
+
+        // and counting them over the whole file
+        let err = scan(options.clone().with_max_error_count(2))
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Skipped 3 invalid JSON lines, more than the maximum of 2"),
+            "{}",
+            err
+        );
+        let err = scan(options.with_max_error_fraction(0.25))
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("Skipped 3 of 10 JSON lines"),
+            "{}",
+            err
+        );
+        Ok(())
+    }
+
+    #[rstest(
+        file_compression_type,
+        case(FileCompressionType::UNCOMPRESSED),
+        case(FileCompressionType::GZIP)
+    )]
+    #[tokio::test]
+    async fn validate_json_records(
+        file_compression_type: FileCompressionType,
+    ) -> Result<()> {
+        let data = "{\"a\": 1, \"b\": 2}\n{\"a\": 2, \"b\": 300}\n\n{\"a\": 3, \"b\": 4\n{\"a\": 4, \"b\": 6}\n";
+        let tmp_dir = TempDir::new()?;
+        let path = tmp_dir.path().join("data.json");
+        if file_compression_type == FileCompressionType::GZIP {
+            let mut encoder = flate2::write::GzEncoder::new(
+                fs::File::create(&path)?,
+                flate2::Compression::default(),
+            );
+            std::io::Write::write_all(&mut encoder, data.as_bytes())?;
+            encoder.finish()?;
+        } else {
+            fs::write(&path, data)?;
+        }
+
+        let mut config = file_scan_config(vec![vec![PartitionedFile::from(
+            crate::test::object_store::local_unpartitioned_file(path),
+        )]]);
+        config.file_schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int64, true),
+            Field::new("b", DataType::Int8, true),
+        ]));
+        let exec = NdJsonExec::new(config, file_compression_type);
+
+        // the file is read whole from the local file system, and in chunks
+        // of a few bytes from the chunked store
+        for chunk_size in [None, Some(8)] {
+            let ctx = SessionContext::new();
+            if let Some(chunk_size) = chunk_size {
+                ctx.runtime_env().register_object_store(
+                    "file",
+                    "",
+                    Arc::new(ChunkedStore::new(
+                        Arc::new(LocalFileSystem::new()),
+                        chunk_size,
+                    )),
+                );
+            }
+
+            let summary = exec.validate(ctx.task_ctx(), 10).await?;
+            assert!(!summary.is_valid());
+            assert_eq!(summary.rows, 4);
+            assert_eq!(summary.invalid_rows, 2);
+            let lines: Vec<_> = summary.errors.iter().map(|e| e.line).collect();
+            assert_eq!(lines, vec![Some(2), Some(4)]);
+            assert!(
+                summary.errors[0].message.contains("out of range"),
+                "{}",
+                summary.errors[0].message
+            );
+            assert!(
+                summary.errors[1].message.contains("Not valid JSON"),
+                "{}",
+                summary.errors[1].message
+            );
+            assert!(summary.errors[0].location.ends_with("data.json"));
+
+            // only the first errors are kept
+            let summary = exec.validate(ctx.task_ctx(), 1).await?;
+            assert_eq!(summary.invalid_rows, 2);
+            assert_eq!(summary.errors.len(), 1);
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn validate_required_keys_in_chunks() -> Result<()> {
+        let tmp_dir = TempDir::new()?;
+        let mut partitioned_files = vec![];
+        for (name, data) in [
+            (
+                "some.json",
+                "{\"a\": 1}\n{\"a\": 2}\n{\"a\": 3, \"e\": true}\n",
+            ),
+            ("none.json", "{\"a\": 4}\n{\"a\": 5}\n"),
+        ] {
+            let path = tmp_dir.path().join(name);
+            fs::write(&path, data)?;
+            partitioned_files.push(PartitionedFile::from(
+                crate::test::object_store::local_unpartitioned_file(path),
+            ));
+        }
+
+        let mut config = file_scan_config(vec![partitioned_files]);
+        config.file_schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int64, true),
+            Field::new("e", DataType::Boolean, false),
+        ]));
+        let exec = NdJsonExec::new(config, FileCompressionType::UNCOMPRESSED);
+
+        // the files are streamed in chunks of a few bytes, but the key of the
+        // field that is not nullable only needs to be in a line of each file
+        let ctx = SessionContext::new();
+        ctx.runtime_env().register_object_store(
+            "file",
+            "",
+            Arc::new(ChunkedStore::new(Arc::new(LocalFileSystem::new()), 8)),
+        );
+        let summary = exec.validate(ctx.task_ctx(), 10).await?;
+        assert_eq!(summary.rows, 5);
+        assert_eq!(summary.invalid_rows, 0);
+        assert_eq!(summary.errors.len(), 1);
+        let error = &summary.errors[0];
+        assert!(error.location.ends_with("none.json"));
+        assert_eq!(error.line, None);
+        assert!(
+            error
+                .message
+                .contains("Field 'e' is not nullable but has no value in any row"),
+            "{}",
+            error.message
+        );
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn write_json_results_error_handling() -> Result<()> {
         let ctx = SessionContext::new();
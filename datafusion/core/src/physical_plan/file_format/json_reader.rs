@@ -0,0 +1,4641 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! A reader of newline delimited JSON that layers DataFusion specific
+//! options on top of the arrow JSON [`Decoder`]
+
+use std::borrow::Cow;
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::io::{BufRead, Read};
+use std::ops::RangeInclusive;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+
+use arrow::array::{
+    make_array, new_empty_array, Array, ArrayData, ArrayRef, BinaryArray,
+    BooleanBufferBuilder, Decimal128Array, DurationMicrosecondArray,
+    DurationMillisecondArray, DurationNanosecondArray, DurationSecondArray, Float32Array,
+    Float64Array, IntervalDayTimeArray, IntervalMonthDayNanoArray,
+    IntervalYearMonthArray, LargeStringBuilder, ListArray, StringArray, UInt32Array,
+};
+use arrow::buffer::Buffer;
+use arrow::compute::kernels::cast_utils::string_to_timestamp_nanos;
+use arrow::compute::take;
+use arrow::datatypes::{
+    DataType, Field, IntervalDayTimeType, IntervalMonthDayNanoType, IntervalUnit, Schema,
+    SchemaRef, TimeUnit,
+};
+use arrow::error::{ArrowError, Result as ArrowResult};
+use arrow::json::reader::{Decoder, DecoderOptions};
+use arrow::record_batch::{RecordBatch, RecordBatchOptions};
+use bytes::Bytes;
+use chrono::{NaiveDate, NaiveDateTime, TimeZone};
+use chrono_tz::Tz;
+use futures::stream::BoxStream;
+use futures::{Stream, StreamExt, TryStreamExt};
+use object_store::path::Path;
+use parking_lot::Mutex;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use serde_json::{Map, Value};
+use tokio::sync::Semaphore;
+use tokio_stream::wrappers::ReceiverStream;
+
+use crate::execution::context::check_deadline;
+use crate::physical_plan::coalesce_batches::concat_batches;
+use crate::physical_plan::common::{batch_byte_size, AbortOnDropSingle};
+use crate::physical_plan::metrics::Gauge;
+use crate::scalar::ScalarValue;
+
+/// Options controlling how newline delimited JSON is read.
+///
+/// These are shared by schema inference in
+/// [`JsonFormat`](crate::datasource::file_format::json::JsonFormat) and
+/// by scanning in [`NdJsonExec`](super::NdJsonExec), so that both see
+/// the same values for every line.
+#[derive(Debug, Clone)]
+pub struct JsonScanOptions {
+    /// If true, every line is a JSON array such as `[1, "a", true]` whose
+    /// elements map positionally to the fields of the schema. This
+    /// requires the schema to be provided explicitly, as it can not be
+    /// inferred. Defaults to false.
+    pub positional_arrays: bool,
+    /// If set, every line that is not a JSON object, such as `42` or
+    /// `"2021-01-01"`, is read as a record with a single field of this name
+    /// holding the value. Defaults to `None`, such lines being invalid.
+    pub scalar_column: Option<String>,
+    /// If set, files are scanned incrementally: every execution only reads
+    /// the complete lines appended since the previous one, tracked in these
+    /// offsets. Only uncompressed files can be scanned incrementally.
+    /// Defaults to `None`, reading every file in full.
+    pub read_offsets: Option<Arc<JsonReadOffsets>>,
+    /// If true, schema inference maps top level string fields whose values
+    /// are all ISO-8601 durations such as `"PT1H30M"` to
+    /// `Duration(Nanosecond)`, or to `Interval(MonthDayNano)` if any of them
+    /// has a year or month component. Fields declared with a `Duration` or
+    /// `Interval` type in an explicit schema are always read from such
+    /// strings. Defaults to false.
+    pub infer_durations: bool,
+    /// If set, the maximum total size in bytes of the batches each partition
+    /// decodes ahead of them being consumed. Decoding pauses before each
+    /// batch until one of the size of the last batch decoded fits next to
+    /// those outstanding. A single batch larger than this is still decoded
+    /// once no other batch is outstanding. Defaults to `None`, decoding a
+    /// batch only when it is polled.
+    pub max_in_flight_bytes: Option<usize>,
+    /// If set, the maximum number of batches each partition decodes ahead
+    /// of them being consumed, which is also the depth of the channel they
+    /// are sent through to the consumer. Combined with
+    /// `max_in_flight_bytes`, decoding pauses whenever either is reached.
+    /// Defaults to `None`, decoding a batch only when it is polled unless
+    /// `max_in_flight_bytes` is set.
+    pub max_batches_in_flight: Option<usize>,
+    /// If true, the records of the files are only counted rather than
+    /// decoded, producing batches with a row count but no columns. This
+    /// requires that no columns are projected, and is set by the physical
+    /// optimizer for scans only feeding `COUNT(*)` whose options neither
+    /// skip nor reject lines. Records are not validated when counted.
+    /// Defaults to false.
+    pub(crate) count_only: bool,
+    /// If set, the IANA time zone such as `"America/New_York"` that
+    /// timestamp strings without an offset, such as `"2021-03-01 12:00:00"`,
+    /// are assumed to be in when read into top level `Timestamp` fields.
+    /// Top level `Timestamp` fields of the schema without a time zone are
+    /// given this one. Defaults to `None`, reading such strings as UTC.
+    pub default_timezone: Option<String>,
+    /// If set, collects the warnings raised while inferring the schema,
+    /// such as fields read as `Utf8` because their values have conflicting
+    /// types. Warnings are also logged. Defaults to `None`, only logging
+    /// them.
+    pub diagnostics: Option<Arc<JsonDiagnostics>>,
+    /// If true, every file is decompressed according to the extension of
+    /// its path, such as `.gz` for gzip, rather than with the compression
+    /// type of the scan. [`JsonFormat`] sets this when no compression type
+    /// is specified. Defaults to false.
+    ///
+    /// [`JsonFormat`]: crate::datasource::file_format::json::JsonFormat
+    pub detect_compression: bool,
+    /// The top level `Utf8` and `List` fields that are read as `LargeUtf8`
+    /// and `LargeList`, whose 64 bit offsets allow the strings or list
+    /// elements of a single array, such as one concatenating many batches,
+    /// to exceed 2 GiB. Fields declared with a `LargeUtf8` or `LargeList`
+    /// type in an explicit schema are always read as such. Defaults to none.
+    pub large_columns: Vec<String>,
+    /// If set, schema inference reads a top level `Utf8` or `List` field as
+    /// `LargeUtf8` or `LargeList` if the total length in bytes of its
+    /// strings, or the total number of its list elements, exceeds this over
+    /// the records inferred from. Defaults to `None`, only reading the
+    /// [`Self::large_columns`] as such.
+    pub large_offsets_threshold: Option<usize>,
+    /// If true, schema inference reads a top level field whose values are
+    /// all non empty lists of the same length, such as the 3 element vector
+    /// `[1.0, 0.5, 2.0]`, as a `FixedSizeList` of that length rather than a
+    /// `List`. Only the records inferred from are considered, a list of
+    /// another length read into such a field failing the scan. Fields
+    /// declared with a `FixedSizeList` type in an explicit schema are always
+    /// read as such. Defaults to false.
+    pub infer_fixed_size_lists: bool,
+    /// If true, the files are only read up to their first record, every
+    /// file holding a record producing a single row holding its partition
+    /// values. This requires that only partition columns are projected, and
+    /// is set by the physical optimizer for scans only feeding aggregates
+    /// without aggregate functions, such as `SELECT DISTINCT` of partition
+    /// columns, whose results do not depend on the number of records of a
+    /// file, unless lines may be skipped or rejected. Defaults to false.
+    pub(crate) partition_values_only: bool,
+    /// If set, the rows read from each file are shuffled within consecutive
+    /// windows of at least this many rows, the order of the windows being
+    /// kept. This is a local shuffle: a row only moves within its window,
+    /// so that the order of the rows is only randomized within this
+    /// distance. Defaults to `None`, keeping the order of the files.
+    pub shuffle_window_rows: Option<usize>,
+    /// The seed of the shuffle of the rows of every file, the rows of the
+    /// same file being shuffled the same way by every scan with the same
+    /// seed and batch size, see [`Self::shuffle_window_rows`]. Defaults to 0.
+    pub shuffle_seed: u64,
+    /// If true, the `NaN`, `Infinity` and `-Infinity` literals some
+    /// producers write, which strict JSON forbids, are read into top level
+    /// `Float32` and `Float64` fields as the corresponding values, and
+    /// schema inference reads fields holding them as `Float64`. Lines
+    /// holding such literals nested in objects or lists are invalid, as
+    /// are all lines holding them if false, the default.
+    pub allow_nonfinite_floats: bool,
+    /// If true, numbers out of the range of the top level `Int8`, `Int16`,
+    /// `Int32`, `UInt8`, `UInt16` and `UInt32` fields of the schema they are
+    /// read into are read as nulls. Defaults to false, such numbers failing
+    /// the scan.
+    pub null_on_integer_overflow: bool,
+    /// If set, the instant past which reading another batch fails the scan
+    /// with a `ResourcesExhausted` error. [`NdJsonExec`](super::NdJsonExec)
+    /// sets this to the deadline of the `TaskContext` it executes in.
+    /// Defaults to `None`, scans running to completion.
+    pub(crate) deadline: Option<Instant>,
+    /// The new names of top level keys, such as `"created-at"` to
+    /// `created_at`, under which they are read and inferred. A renamed key
+    /// takes the place of any key already named like it. An explicit schema
+    /// names the fields after renaming. Defaults to none.
+    pub field_renames: HashMap<String, String>,
+    /// If true, lines that are not valid JSON, or whose values can not be
+    /// read into their field, such as a number out of the range of a narrow
+    /// integer field, are skipped rather than failing the scan. Values of a
+    /// type the arrow decoder can not read into their field still fail the
+    /// scan. Defaults to false.
+    pub skip_invalid_rows: bool,
+    /// If set, reading a file fails once more than this many of its lines
+    /// were skipped, see [`Self::skip_invalid_rows`]. Defaults to `None`.
+    pub max_error_count: Option<usize>,
+    /// If set, reading a file fails if the fraction of its lines that were
+    /// skipped, see [`Self::skip_invalid_rows`], exceeds this once the end
+    /// of the file is reached. Defaults to `None`.
+    pub max_error_fraction: Option<f64>,
+    /// The top level fields holding base64 encoded bytes, such as
+    /// `"aGVsbG8="`, read as `Binary` by decoding their strings with the
+    /// given alphabet. Schema inference reads these fields as `Binary`
+    /// rather than `Utf8`. Strings that are not valid base64 fail the scan,
+    /// or are skipped if [`Self::skip_invalid_rows`] is set. Defaults to
+    /// none.
+    pub base64_columns: HashMap<String, Base64Encoding>,
+    /// The top level fields holding timestamps written as integers, such as
+    /// the epoch milliseconds `1609459200000`, read as `Timestamp` of the
+    /// given unit, the integers being the number of such units since the
+    /// Unix epoch. Schema inference reads these fields as `Timestamp` rather
+    /// than `Int64`, and timestamp strings are still read into them.
+    /// Defaults to none.
+    pub epoch_timestamp_columns: HashMap<String, TimeUnit>,
+    /// If true, the schema is given a non nullable `Int64` column named
+    /// [`ROW_ID_COLUMN`] holding an id for every record, unique across the
+    /// partitions of the scan. The id of a record is the index of its
+    /// partition shifted left by 40 bits, combined with its position among
+    /// the records read by that partition, so that a partition can read up
+    /// to 2^40 records. The sorted files of a partition whose
+    /// [`NdJsonExec::with_output_ordering`](super::NdJsonExec::with_output_ordering)
+    /// is set are merged rather than read one after another, the position of
+    /// a record then being the index of its file among those of the
+    /// partition shifted left by 28 bits, combined with its position among
+    /// the records of its file, so that such a partition can merge up to
+    /// 2^12 files of up to 2^28 records each.
+    ///
+    /// Other partitions read their files one after another, so the ids are only
+    /// stable across scans reading the same files in the same order over
+    /// the same partitions: adding a file, or changing
+    /// `datafusion.execution.target_partitions` or the repartitioning of
+    /// file scans, may change them. They are also not stable if
+    /// [`Self::shuffle_window_rows`] or [`Self::skip_invalid_rows`] is set,
+    /// or if the files are scanned incrementally. Defaults to false.
+    pub row_id: bool,
+    /// If set, called with the [`FileProgress`] of a file after each batch
+    /// decoded from it, and once more when its end is reached, such as to
+    /// show the progress of an ingestion. The partitions of a scan read
+    /// their files concurrently, so the callback may be called from several
+    /// threads at once, but is never called while IO is pending or a lock
+    /// is held. Defaults to `None`.
+    pub progress: Option<JsonProgress>,
+    /// If set, lines starting with this prefix after any leading whitespace,
+    /// such as `# generated by ...` for the prefix `#`, are comments that
+    /// are skipped when reading and inferring the schema. The prefix can not
+    /// be empty. Defaults to `None`, such lines being invalid.
+    pub comment_prefix: Option<String>,
+    /// If true, lines of only whitespace are skipped when reading and
+    /// inferring the schema, otherwise they are invalid. Defaults to true.
+    pub skip_blank_lines: bool,
+    /// If set, strings read into the top level `Float32`, `Float64` and
+    /// `Decimal128` fields of the schema, such as `"1.234,56"` with the
+    /// European separators, are parsed as numbers written with these
+    /// separators, numbers still being read as is. Schema inference still
+    /// reads fields holding such strings as `Utf8`, so that the schema has
+    /// to be provided explicitly. Defaults to `None`.
+    pub numeric_locale: Option<NumericLocale>,
+    /// The top level columns the schema of the files must have, such as
+    /// the columns of a data contract, whether the schema is inferred or
+    /// explicit. A schema lacking any of them fails when the table is
+    /// created, before any file is scanned. Defaults to none.
+    pub required_columns: Vec<String>,
+    /// The dot separated paths of nested fields, such as `payload.a` for
+    /// the field `a` of the top level struct `payload`, read into top level
+    /// columns named after the path and appended to the schema, so that
+    /// they can be selected without the struct they are nested in. Paths
+    /// name keys after [`Self::field_renames`], and a record missing a key
+    /// of the path reads it as null. Defaults to none.
+    pub expand_paths: Vec<String>,
+    /// The character encoding of the files, which are transcoded to UTF-8
+    /// after being decompressed, before the schema is inferred from them
+    /// and they are read. A byte order mark starting a file is skipped.
+    /// Files not encoded in UTF-8 can not be split into byte ranges or
+    /// scanned incrementally. Defaults to [`JsonEncoding::Utf8`].
+    pub encoding: JsonEncoding,
+    /// If true, the structs of the schema, whether explicit or inferred,
+    /// are replaced by top level columns for each of their fields that is
+    /// not a struct, named after its path, such as `payload.a` for the
+    /// field `a` of the top level struct `payload`, so that the schema has
+    /// no struct columns. Objects are flattened likewise when they are
+    /// read, and a record missing a key reads it as null. A flattened name
+    /// that is also the name of another column, such as a top level
+    /// `payload.a` field, fails the scan. Defaults to false.
+    pub auto_flatten: bool,
+    /// The separator of the path of the columns [`Self::auto_flatten`]
+    /// produces. Defaults to `None`, separating them with `.`
+    pub flatten_separator: Option<String>,
+    /// If set, the maximum number of arrays and objects a value may be
+    /// nested in, the top level object of a record being at depth 1. A
+    /// line nested deeper fails, like an invalid one, before it is parsed,
+    /// so that adversarial input can not exhaust the stack. Values nested
+    /// deeper than 128 levels are always rejected by the JSON parser.
+    /// Defaults to `None`, bounded by the parser only.
+    pub max_depth: Option<usize>,
+    /// If set, the records of the files are only validated rather than
+    /// decoded into rows, see [`NdJsonExec::validate`](super::NdJsonExec::validate).
+    /// Every record is decoded on its own, as a scan would, and those failing
+    /// are recorded in it rather than failing the scan, which produces
+    /// batches with a row count but no columns, like [`Self::count_only`].
+    /// This requires that no columns are projected and that the files are
+    /// read whole. Defaults to `None`.
+    pub(crate) validation: Option<Arc<JsonValidation>>,
+}
+
+/// The name of the column holding the id of every record, see
+/// [`JsonScanOptions::row_id`]
+pub const ROW_ID_COLUMN: &str = "_row_id";
+
+/// The alphabet of a base64 encoded field, see
+/// [`JsonScanOptions::base64_columns`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Base64Encoding {
+    /// The standard alphabet of RFC 4648, with `+` and `/`
+    Standard,
+    /// The URL and filename safe alphabet of RFC 4648, with `-` and `_`
+    UrlSafe,
+}
+
+impl Base64Encoding {
+    fn decode(&self, s: &str) -> Result<Vec<u8>, base64::DecodeError> {
+        let config = match self {
+            Self::Standard => base64::STANDARD,
+            Self::UrlSafe => base64::URL_SAFE,
+        };
+        base64::decode_config(s, config)
+    }
+}
+
+/// The separators of the numbers written as strings, see
+/// [`JsonScanOptions::numeric_locale`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NumericLocale {
+    /// The separator of the integer and fractional parts, such as `,`
+    pub decimal_separator: char,
+    /// The separator of the groups of digits of the integer part, such as
+    /// `.`, removed wherever it appears before the decimal separator
+    pub thousands_separator: Option<char>,
+}
+
+impl NumericLocale {
+    /// Create a locale with the given separators
+    pub fn new(decimal_separator: char, thousands_separator: Option<char>) -> Self {
+        Self {
+            decimal_separator,
+            thousands_separator,
+        }
+    }
+
+    /// The separators of most European locales, such as `1.234,56`
+    pub fn european() -> Self {
+        Self::new(',', Some('.'))
+    }
+
+    /// Rewrites the number `s`, written with these separators, with a `.`
+    /// decimal separator and no thousands separators, or returns `None` if
+    /// it has several decimal separators or a thousands separator after one
+    fn normalize(&self, s: &str) -> Option<String> {
+        let mut result = String::with_capacity(s.len());
+        let mut fractional = false;
+        for c in s.trim().chars() {
+            if c == self.decimal_separator {
+                if fractional {
+                    return None;
+                }
+                fractional = true;
+                result.push('.');
+            } else if Some(c) == self.thousands_separator {
+                if fractional {
+                    return None;
+                }
+            } else {
+                result.push(c);
+            }
+        }
+        Some(result)
+    }
+}
+
+/// The character encoding of JSON files, see [`JsonScanOptions::encoding`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JsonEncoding {
+    /// UTF-8, which JSON is parsed as without transcoding it
+    Utf8,
+    /// UTF-16 with little endian code units
+    Utf16Le,
+    /// UTF-16 with big endian code units
+    Utf16Be,
+    /// ISO-8859-1, every byte being the code point of the same value
+    Latin1,
+}
+
+impl Default for JsonEncoding {
+    fn default() -> Self {
+        Self::Utf8
+    }
+}
+
+impl JsonEncoding {
+    /// Transcodes the bytes read from `reader` to UTF-8
+    pub(crate) fn convert_read<R: Read + Send + 'static>(
+        self,
+        reader: R,
+    ) -> Box<dyn Read + Send> {
+        match self {
+            Self::Utf8 => Box::new(reader),
+            _ => Box::new(TranscodingRead {
+                inner: reader,
+                transcoder: Transcoder::new(self),
+                input: vec![0; TRANSCODING_BUFFER_SIZE],
+                output: vec![],
+                position: 0,
+            }),
+        }
+    }
+
+    /// Transcodes the chunks of bytes of `stream` to UTF-8
+    pub(crate) fn convert_stream<S>(
+        self,
+        stream: S,
+    ) -> BoxStream<'static, crate::error::Result<Bytes>>
+    where
+        S: Stream<Item = crate::error::Result<Bytes>> + Send + 'static,
+    {
+        if self == Self::Utf8 {
+            return stream.boxed();
+        }
+        let state = Some((stream.boxed(), Transcoder::new(self)));
+        futures::stream::unfold(state, |state| async move {
+            let (mut stream, mut transcoder) = state?;
+            match stream.next().await {
+                Some(Ok(bytes)) => {
+                    let mut output = vec![];
+                    let result = transcoder
+                        .transcode(&bytes, &mut output)
+                        .map(|_| Bytes::from(output))
+                        .map_err(Into::into);
+                    Some((result, Some((stream, transcoder))))
+                }
+                Some(Err(e)) => Some((Err(e), Some((stream, transcoder)))),
+                None => transcoder.finish().err().map(|e| (Err(e.into()), None)),
+            }
+        })
+        .boxed()
+    }
+}
+
+/// The size of the chunks a [`TranscodingRead`] reads from its input
+const TRANSCODING_BUFFER_SIZE: usize = 8 * 1024;
+
+/// Transcodes chunks of bytes of a [`JsonEncoding`] to UTF-8, keeping the
+/// bytes of a character split across chunks until the next chunk
+#[derive(Debug)]
+struct Transcoder {
+    encoding: JsonEncoding,
+    /// The bytes of the incomplete character ending the previous chunk
+    pending: Vec<u8>,
+    /// Whether a character was read, a byte order mark only being skipped
+    /// before the first one
+    started: bool,
+}
+
+impl Transcoder {
+    fn new(encoding: JsonEncoding) -> Self {
+        Self {
+            encoding,
+            pending: vec![],
+            started: false,
+        }
+    }
+
+    /// Appends the UTF-8 encoding of the characters of `bytes` to `output`
+    fn transcode(&mut self, bytes: &[u8], output: &mut Vec<u8>) -> ArrowResult<()> {
+        let little_endian = match self.encoding {
+            JsonEncoding::Utf8 => {
+                output.extend_from_slice(bytes);
+                return Ok(());
+            }
+            JsonEncoding::Latin1 => {
+                bytes.iter().for_each(|b| self.push(char::from(*b), output));
+                return Ok(());
+            }
+            JsonEncoding::Utf16Le => true,
+            JsonEncoding::Utf16Be => false,
+        };
+        let unit = |bytes: &[u8]| {
+            if little_endian {
+                u16::from_le_bytes([bytes[0], bytes[1]])
+            } else {
+                u16::from_be_bytes([bytes[0], bytes[1]])
+            }
+        };
+
+        self.pending.extend_from_slice(bytes);
+        // a high surrogate ending the chunk waits for the low surrogate
+        // completing its character
+        let len = self.pending.len() / 2;
+        let complete = match len.checked_sub(1) {
+            Some(last) if (0xD800..0xDC00).contains(&unit(&self.pending[last * 2..])) => {
+                last
+            }
+            _ => len,
+        };
+        let pending = std::mem::take(&mut self.pending);
+        let units = pending[..complete * 2].chunks_exact(2).map(unit);
+        for c in char::decode_utf16(units) {
+            let c = c.map_err(|e| {
+                ArrowError::JsonError(format!(
+                    "Invalid UTF-16 JSON input: unpaired surrogate {:#06x}",
+                    e.unpaired_surrogate()
+                ))
+            })?;
+            self.push(c, output);
+        }
+        self.pending = pending[complete * 2..].to_vec();
+        Ok(())
+    }
+
+    /// Appends the UTF-8 encoding of `c` to `output`, unless it is the byte
+    /// order mark starting the input, which only tells its endianness
+    fn push(&mut self, c: char, output: &mut Vec<u8>) {
+        if !std::mem::replace(&mut self.started, true) && c == '\u{feff}' {
+            return;
+        }
+        let mut buf = [0; 4];
+        output.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+    }
+
+    /// Fails if the input ended within a character
+    fn finish(&self) -> ArrowResult<()> {
+        if self.pending.is_empty() {
+            Ok(())
+        } else {
+            Err(ArrowError::JsonError(format!(
+                "{:?} JSON input ends within a character",
+                self.encoding
+            )))
+        }
+    }
+}
+
+/// Reads the UTF-8 transcoding of a reader of another [`JsonEncoding`]
+struct TranscodingRead<R> {
+    inner: R,
+    transcoder: Transcoder,
+    /// The buffer the input is read into
+    input: Vec<u8>,
+    /// The transcoded output not read yet from `position` on
+    output: Vec<u8>,
+    position: usize,
+}
+
+impl<R: Read> Read for TranscodingRead<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let invalid_data =
+            |e: ArrowError| std::io::Error::new(std::io::ErrorKind::InvalidData, e);
+        while self.position == self.output.len() {
+            self.output.clear();
+            self.position = 0;
+            let read = self.inner.read(&mut self.input)?;
+            if read == 0 {
+                self.transcoder.finish().map_err(invalid_data)?;
+                return Ok(0);
+            }
+            self.transcoder
+                .transcode(&self.input[..read], &mut self.output)
+                .map_err(invalid_data)?;
+        }
+        let len = buf.len().min(self.output.len() - self.position);
+        buf[..len].copy_from_slice(&self.output[self.position..self.position + len]);
+        self.position += len;
+        Ok(len)
+    }
+}
+
+impl Default for JsonScanOptions {
+    fn default() -> Self {
+        Self {
+            positional_arrays: false,
+            scalar_column: None,
+            read_offsets: None,
+            infer_durations: false,
+            max_in_flight_bytes: None,
+            max_batches_in_flight: None,
+            count_only: false,
+            default_timezone: None,
+            diagnostics: None,
+            detect_compression: false,
+            large_columns: vec![],
+            large_offsets_threshold: None,
+            infer_fixed_size_lists: false,
+            partition_values_only: false,
+            shuffle_window_rows: None,
+            shuffle_seed: 0,
+            allow_nonfinite_floats: false,
+            null_on_integer_overflow: false,
+            deadline: None,
+            field_renames: HashMap::new(),
+            skip_invalid_rows: false,
+            max_error_count: None,
+            max_error_fraction: None,
+            base64_columns: HashMap::new(),
+            epoch_timestamp_columns: HashMap::new(),
+            row_id: false,
+            progress: None,
+            comment_prefix: None,
+            skip_blank_lines: true,
+            numeric_locale: None,
+            required_columns: vec![],
+            expand_paths: vec![],
+            encoding: JsonEncoding::Utf8,
+            auto_flatten: false,
+            flatten_separator: None,
+            max_depth: None,
+            validation: None,
+        }
+    }
+}
+
+impl JsonScanOptions {
+    /// Create options with every setting at its default
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Specify whether every line is a positional JSON array
+    pub fn with_positional_arrays(mut self, positional_arrays: bool) -> Self {
+        self.positional_arrays = positional_arrays;
+        self
+    }
+
+    /// Specify the name of the field lines that are not objects are read into
+    pub fn with_scalar_column(mut self, scalar_column: impl Into<String>) -> Self {
+        self.scalar_column = Some(scalar_column.into());
+        self
+    }
+
+    /// Specify whether files are scanned incrementally, starting from new
+    /// [`JsonReadOffsets`] if so
+    pub fn with_incremental(mut self, incremental: bool) -> Self {
+        self.read_offsets = incremental.then(|| Arc::new(JsonReadOffsets::default()));
+        self
+    }
+
+    /// Specify whether string fields holding ISO-8601 durations are inferred
+    /// as `Duration` or `Interval` fields
+    pub fn with_infer_durations(mut self, infer_durations: bool) -> Self {
+        self.infer_durations = infer_durations;
+        self
+    }
+
+    /// Specify the maximum total size in bytes of the batches each partition
+    /// decodes ahead of them being consumed
+    pub fn with_max_in_flight_bytes(mut self, max_in_flight_bytes: usize) -> Self {
+        self.max_in_flight_bytes = Some(max_in_flight_bytes);
+        self
+    }
+
+    /// Specify the maximum number of batches each partition decodes ahead
+    /// of them being consumed
+    pub fn with_max_batches_in_flight(mut self, max_batches_in_flight: usize) -> Self {
+        self.max_batches_in_flight = Some(max_batches_in_flight);
+        self
+    }
+
+    /// Specify whether files only produce a row of their partition values
+    /// rather than being read in full
+    pub(crate) fn with_partition_values_only(
+        mut self,
+        partition_values_only: bool,
+    ) -> Self {
+        self.partition_values_only = partition_values_only;
+        self
+    }
+
+    /// Specify that the rows of each file are shuffled within windows of
+    /// `window_rows` rows, seeded by `seed`
+    pub fn with_shuffle(mut self, window_rows: usize, seed: u64) -> Self {
+        self.shuffle_window_rows = Some(window_rows);
+        self.shuffle_seed = seed;
+        self
+    }
+
+    /// Whether counting the non blank lines that are not comments gives
+    /// the number of records decoding them would, rather than lines being
+    /// skipped, or rejected before they are decoded
+    pub(crate) fn can_count_records(&self) -> bool {
+        !self.skip_invalid_rows
+            && self.skip_blank_lines
+            && self.max_error_count.is_none()
+            && self.max_error_fraction.is_none()
+            && self.max_depth.is_none()
+            && !self.auto_flatten
+            && self.expand_paths.is_empty()
+            && self.encoding == JsonEncoding::Utf8
+            && self.validation.is_none()
+    }
+
+    /// Specify whether records are only counted rather than decoded
+    pub(crate) fn with_count_only(mut self, count_only: bool) -> Self {
+        self.count_only = count_only;
+        self
+    }
+
+    /// Specify the time zone timestamps without an offset are assumed to be in
+    pub fn with_default_timezone(mut self, default_timezone: impl Into<String>) -> Self {
+        self.default_timezone = Some(default_timezone.into());
+        self
+    }
+
+    /// Specify where the warnings raised while inferring the schema are
+    /// collected
+    pub fn with_diagnostics(mut self, diagnostics: Arc<JsonDiagnostics>) -> Self {
+        self.diagnostics = Some(diagnostics);
+        self
+    }
+
+    /// Specify whether the compression of every file is detected from the
+    /// extension of its path
+    pub fn with_detect_compression(mut self, detect_compression: bool) -> Self {
+        self.detect_compression = detect_compression;
+        self
+    }
+
+    /// Specify whether the `NaN`, `Infinity` and `-Infinity` literals are
+    /// read as floating point numbers
+    pub fn with_allow_nonfinite_floats(mut self, allow_nonfinite_floats: bool) -> Self {
+        self.allow_nonfinite_floats = allow_nonfinite_floats;
+        self
+    }
+
+    /// Specify whether numbers out of the range of the narrow integer
+    /// fields they are read into are read as nulls rather than an error
+    pub fn with_null_on_integer_overflow(
+        mut self,
+        null_on_integer_overflow: bool,
+    ) -> Self {
+        self.null_on_integer_overflow = null_on_integer_overflow;
+        self
+    }
+
+    /// Specify the instant past which reading another batch fails the scan
+    pub(crate) fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Specify the new names of top level keys, from the original name to
+    /// the name of the column they are read into
+    pub fn with_field_renames(mut self, field_renames: HashMap<String, String>) -> Self {
+        self.field_renames = field_renames;
+        self
+    }
+
+    /// Specify whether invalid lines are skipped rather than failing the scan
+    pub fn with_skip_invalid_rows(mut self, skip_invalid_rows: bool) -> Self {
+        self.skip_invalid_rows = skip_invalid_rows;
+        self
+    }
+
+    /// Specify the number of skipped lines above which reading a file fails
+    pub fn with_max_error_count(mut self, max_error_count: usize) -> Self {
+        self.max_error_count = Some(max_error_count);
+        self
+    }
+
+    /// Specify the fraction of skipped lines above which reading a file fails
+    pub fn with_max_error_fraction(mut self, max_error_fraction: f64) -> Self {
+        self.max_error_fraction = Some(max_error_fraction);
+        self
+    }
+
+    /// Specify that the top level field `name` holds bytes base64 encoded
+    /// with `encoding`, read as `Binary`
+    pub fn with_base64_column(
+        mut self,
+        name: impl Into<String>,
+        encoding: Base64Encoding,
+    ) -> Self {
+        self.base64_columns.insert(name.into(), encoding);
+        self
+    }
+
+    /// Specify that the top level field `name` holds integer timestamps in
+    /// `unit` since the epoch, read as `Timestamp`
+    pub fn with_epoch_timestamp_column(
+        mut self,
+        name: impl Into<String>,
+        unit: TimeUnit,
+    ) -> Self {
+        self.epoch_timestamp_columns.insert(name.into(), unit);
+        self
+    }
+
+    /// Specify the top level fields read as `LargeUtf8` or `LargeList`
+    pub fn with_large_columns(mut self, large_columns: Vec<String>) -> Self {
+        self.large_columns = large_columns;
+        self
+    }
+
+    /// Specify the total length of the strings or list elements of a field
+    /// above which schema inference reads it as `LargeUtf8` or `LargeList`
+    pub fn with_large_offsets_threshold(mut self, threshold: usize) -> Self {
+        self.large_offsets_threshold = Some(threshold);
+        self
+    }
+
+    /// Specify whether fields only holding lists of the same length are
+    /// inferred as `FixedSizeList` fields
+    pub fn with_infer_fixed_size_lists(mut self, infer_fixed_size_lists: bool) -> Self {
+        self.infer_fixed_size_lists = infer_fixed_size_lists;
+        self
+    }
+
+    /// Specify whether a column holding the id of every record is added
+    pub fn with_row_id(mut self, row_id: bool) -> Self {
+        self.row_id = row_id;
+        self
+    }
+
+    /// Specify the prefix of the comment lines that are skipped
+    pub fn with_comment_prefix(mut self, comment_prefix: impl Into<String>) -> Self {
+        self.comment_prefix = Some(comment_prefix.into());
+        self
+    }
+
+    /// Specify whether lines of only whitespace are skipped
+    pub fn with_skip_blank_lines(mut self, skip_blank_lines: bool) -> Self {
+        self.skip_blank_lines = skip_blank_lines;
+        self
+    }
+
+    /// Returns an error if [`Self::comment_prefix`] is empty, which would
+    /// make every line a comment
+    pub(crate) fn check_comment_prefix(&self) -> ArrowResult<()> {
+        match &self.comment_prefix {
+            Some(prefix) if prefix.is_empty() => Err(ArrowError::JsonError(
+                "The comment prefix of JSON lines can not be empty".to_string(),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Specify the character encoding of the files
+    pub fn with_encoding(mut self, encoding: JsonEncoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// Specify the separators of the numbers written as strings
+    pub fn with_numeric_locale(mut self, numeric_locale: NumericLocale) -> Self {
+        self.numeric_locale = Some(numeric_locale);
+        self
+    }
+
+    /// Specify the columns the schema of the files must have
+    pub fn with_required_columns(mut self, required_columns: Vec<String>) -> Self {
+        self.required_columns = required_columns;
+        self
+    }
+
+    /// Specify the nested paths read into top level columns
+    pub fn with_expand_paths(mut self, expand_paths: Vec<String>) -> Self {
+        self.expand_paths = expand_paths;
+        self
+    }
+
+    /// Specify whether nested fields are flattened into top level columns
+    pub fn with_auto_flatten(mut self, auto_flatten: bool) -> Self {
+        self.auto_flatten = auto_flatten;
+        self
+    }
+
+    /// Specify the separator of the path of the flattened columns
+    pub fn with_flatten_separator(mut self, separator: impl Into<String>) -> Self {
+        self.flatten_separator = Some(separator.into());
+        self
+    }
+
+    /// Specify the maximum nesting depth of the values read
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Specify the validation the invalid records are recorded in, only
+    /// validating the files rather than decoding them
+    pub(crate) fn with_validation(mut self, validation: Arc<JsonValidation>) -> Self {
+        self.validation = Some(validation);
+        self
+    }
+
+    /// Specify the callback the progress of reading every file is reported to
+    pub fn with_progress(
+        mut self,
+        progress: impl Fn(FileProgress) + Send + Sync + 'static,
+    ) -> Self {
+        self.progress = Some(JsonProgress::new(progress));
+        self
+    }
+
+    /// Raise a warning, logging it and collecting it in
+    /// [`Self::diagnostics`] if set
+    pub(crate) fn warn(&self, message: String) {
+        log::warn!("{}", message);
+        if let Some(diagnostics) = &self.diagnostics {
+            diagnostics.messages.lock().push(message);
+        }
+    }
+
+    /// Parses [`Self::default_timezone`], if set
+    pub(crate) fn parse_default_timezone(&self) -> ArrowResult<Option<Tz>> {
+        self.default_timezone
+            .as_deref()
+            .map(|timezone| {
+                timezone.parse::<Tz>().map_err(|_| {
+                    ArrowError::JsonError(format!(
+                        "Invalid default timezone '{}'",
+                        timezone
+                    ))
+                })
+            })
+            .transpose()
+    }
+
+    /// Adjusts the types of the fields of `schema`, whether explicit or
+    /// inferred, to these options, see [`Self::epoch_timestamp_columns`],
+    /// [`Self::default_timezone`], [`Self::base64_columns`] and
+    /// [`Self::large_columns`], adds the [`Self::expand_paths`] and
+    /// [`Self::row_id`] columns and flattens structs if
+    /// [`Self::auto_flatten`] is set
+    pub(crate) fn apply_to_schema(&self, schema: SchemaRef) -> ArrowResult<SchemaRef> {
+        let schema = self.apply_expand_paths(schema)?;
+        let schema = self.apply_auto_flatten(schema)?;
+        let schema = self.apply_epoch_timestamp_columns(schema);
+        let schema = self.apply_default_timezone(schema)?;
+        let schema = self.apply_base64_columns(schema);
+        let schema = self.apply_large_columns(schema);
+        Ok(self.apply_row_id(schema))
+    }
+
+    /// Appends the [`Self::row_id`] column to `schema` if set and not
+    /// already present
+    fn apply_row_id(&self, schema: SchemaRef) -> SchemaRef {
+        if !self.row_id || schema.field_with_name(ROW_ID_COLUMN).is_ok() {
+            return schema;
+        }
+
+        let mut fields = schema.fields().clone();
+        fields.push(Field::new(ROW_ID_COLUMN, DataType::Int64, false));
+        Arc::new(Schema::new_with_metadata(fields, schema.metadata().clone()))
+    }
+
+    /// Appends the [`Self::expand_paths`] columns missing from `schema`,
+    /// typed like the nested field they are read from
+    fn apply_expand_paths(&self, schema: SchemaRef) -> ArrowResult<SchemaRef> {
+        let missing = self
+            .expand_paths
+            .iter()
+            .filter(|path| schema.field_with_name(path).is_err())
+            .collect::<Vec<_>>();
+        if missing.is_empty() {
+            return Ok(schema);
+        }
+
+        let mut fields = schema.fields().clone();
+        for path in missing {
+            let data_type = nested_field_type(&schema, path).ok_or_else(|| {
+                ArrowError::SchemaError(format!(
+                    "Expanded path '{}' is not a nested field of the schema",
+                    path
+                ))
+            })?;
+            fields.push(Field::new(path, data_type, true));
+        }
+        Ok(Arc::new(Schema::new_with_metadata(
+            fields,
+            schema.metadata().clone(),
+        )))
+    }
+
+    /// The separator of the path of the flattened columns, see
+    /// [`Self::flatten_separator`]
+    fn flatten_separator(&self) -> &str {
+        self.flatten_separator.as_deref().unwrap_or(".")
+    }
+
+    /// Replaces the structs of `schema` by the columns of their fields if
+    /// [`Self::auto_flatten`] is set, failing if two columns have the same
+    /// name
+    fn apply_auto_flatten(&self, schema: SchemaRef) -> ArrowResult<SchemaRef> {
+        let has_structs = schema
+            .fields()
+            .iter()
+            .any(|field| matches!(field.data_type(), DataType::Struct(_)));
+        if !self.auto_flatten || !has_structs {
+            return Ok(schema);
+        }
+
+        let mut fields = vec![];
+        flatten_fields(schema.fields(), None, self.flatten_separator(), &mut fields);
+        let mut names = HashSet::new();
+        for field in &fields {
+            if !names.insert(field.name()) {
+                return Err(ArrowError::SchemaError(format!(
+                    "Flattened column '{}' conflicts with another column of the same name",
+                    field.name()
+                )));
+            }
+        }
+        Ok(Arc::new(Schema::new_with_metadata(
+            fields,
+            schema.metadata().clone(),
+        )))
+    }
+
+    /// Reads the `Int64` [`Self::epoch_timestamp_columns`] of `schema` as
+    /// `Timestamp`, which the decoder reads integers into as is
+    fn apply_epoch_timestamp_columns(&self, schema: SchemaRef) -> SchemaRef {
+        if self.epoch_timestamp_columns.is_empty() {
+            return schema;
+        }
+
+        let fields = schema
+            .fields()
+            .iter()
+            .map(|field| match field.data_type() {
+                DataType::Int64 => match self.epoch_timestamp_columns.get(field.name()) {
+                    Some(unit) => Field::new(
+                        field.name(),
+                        DataType::Timestamp(unit.clone(), None),
+                        field.is_nullable(),
+                    )
+                    .with_metadata(field.metadata().cloned()),
+                    None => field.clone(),
+                },
+                _ => field.clone(),
+            })
+            .collect();
+        Arc::new(Schema::new_with_metadata(fields, schema.metadata().clone()))
+    }
+
+    /// Gives the top level `Timestamp` fields of `schema` without a time zone
+    /// the [`Self::default_timezone`], if set
+    fn apply_default_timezone(&self, schema: SchemaRef) -> ArrowResult<SchemaRef> {
+        if self.parse_default_timezone()?.is_none() {
+            return Ok(schema);
+        }
+
+        let fields = schema
+            .fields()
+            .iter()
+            .map(|field| match field.data_type() {
+                DataType::Timestamp(unit, None) => Field::new(
+                    field.name(),
+                    DataType::Timestamp(unit.clone(), self.default_timezone.clone()),
+                    field.is_nullable(),
+                )
+                .with_metadata(field.metadata().cloned()),
+                _ => field.clone(),
+            })
+            .collect();
+        Ok(Arc::new(Schema::new_with_metadata(
+            fields,
+            schema.metadata().clone(),
+        )))
+    }
+
+    /// Reads the `Utf8` [`Self::base64_columns`] of `schema` as `Binary`
+    fn apply_base64_columns(&self, schema: SchemaRef) -> SchemaRef {
+        if self.base64_columns.is_empty() {
+            return schema;
+        }
+
+        let fields = schema
+            .fields()
+            .iter()
+            .map(|field| match field.data_type() {
+                DataType::Utf8 if self.base64_columns.contains_key(field.name()) => {
+                    Field::new(field.name(), DataType::Binary, field.is_nullable())
+                        .with_metadata(field.metadata().cloned())
+                }
+                _ => field.clone(),
+            })
+            .collect();
+        Arc::new(Schema::new_with_metadata(fields, schema.metadata().clone()))
+    }
+
+    /// Returns the [`Self::base64_columns`] read as `Binary` fields of
+    /// `schema`, keyed by name
+    fn base64_fields(&self, schema: &Schema) -> HashMap<String, Base64Encoding> {
+        schema
+            .fields()
+            .iter()
+            .filter(|field| field.data_type() == &DataType::Binary)
+            .filter_map(|field| {
+                let encoding = self.base64_columns.get(field.name())?;
+                Some((field.name().clone(), *encoding))
+            })
+            .collect()
+    }
+
+    /// Reads the [`Self::large_columns`] of `schema` as `LargeUtf8` or
+    /// `LargeList`
+    fn apply_large_columns(&self, schema: SchemaRef) -> SchemaRef {
+        if self.large_columns.is_empty() {
+            return schema;
+        }
+
+        let fields = schema
+            .fields()
+            .iter()
+            .map(|field| match large_offset_type(field.data_type()) {
+                Some(data_type) if self.large_columns.contains(field.name()) => {
+                    Field::new(field.name(), data_type, field.is_nullable())
+                        .with_metadata(field.metadata().cloned())
+                }
+                _ => field.clone(),
+            })
+            .collect();
+        Arc::new(Schema::new_with_metadata(fields, schema.metadata().clone()))
+    }
+}
+
+/// The type of the field at the dot separated `path` through the structs of
+/// `schema`
+fn nested_field_type(schema: &Schema, path: &str) -> Option<DataType> {
+    let mut segments = path.split('.');
+    let first = segments.next()?;
+    let mut data_type = schema.field_with_name(first).ok()?.data_type();
+    for segment in segments {
+        data_type = match data_type {
+            DataType::Struct(fields) => {
+                fields.iter().find(|f| f.name() == segment)?.data_type()
+            }
+            _ => return None,
+        };
+    }
+    Some(data_type.clone())
+}
+
+/// Appends the fields of `fields` that are not structs to `flattened`, and
+/// those of the structs recursively, named after their path from the top
+/// level joined by `separator`
+fn flatten_fields(
+    fields: &[Field],
+    prefix: Option<&str>,
+    separator: &str,
+    flattened: &mut Vec<Field>,
+) {
+    for field in fields {
+        let name = match prefix {
+            Some(prefix) => format!("{}{}{}", prefix, separator, field.name()),
+            None => field.name().clone(),
+        };
+        match field.data_type() {
+            DataType::Struct(children) => {
+                flatten_fields(children, Some(&name), separator, flattened)
+            }
+            // a nested field is null whenever a struct it is nested in is
+            data_type if prefix.is_some() => flattened.push(
+                Field::new(&name, data_type.clone(), true)
+                    .with_metadata(field.metadata().cloned()),
+            ),
+            _ => flattened.push(field.clone()),
+        }
+    }
+}
+
+/// The value at the dot separated `path` through the objects of `map`, null
+/// if missing
+fn nested_value(map: &Map<String, Value>, path: &str) -> Value {
+    let mut segments = path.split('.');
+    let first = segments.next().and_then(|segment| map.get(segment));
+    segments
+        .fold(first, |value, segment| value.and_then(|v| v.get(segment)))
+        .cloned()
+        .unwrap_or(Value::Null)
+}
+
+/// The type with 64 bit offsets of a `Utf8` or `List` type
+fn large_offset_type(data_type: &DataType) -> Option<DataType> {
+    match data_type {
+        DataType::Utf8 => Some(DataType::LargeUtf8),
+        DataType::List(field) => Some(DataType::LargeList(field.clone())),
+        _ => None,
+    }
+}
+
+/// The type with 32 bit offsets of a `LargeUtf8` or `LargeList` type
+fn small_offset_type(data_type: &DataType) -> Option<DataType> {
+    match data_type {
+        DataType::LargeUtf8 => Some(DataType::Utf8),
+        DataType::LargeList(field) => Some(DataType::List(field.clone())),
+        _ => None,
+    }
+}
+
+/// The byte offsets up to which each file has been read by an incremental
+/// scan, see [`JsonScanOptions::read_offsets`].
+///
+/// As the offsets are shared by every plan created from the same options,
+/// a table registered with a session resumes where its previous scan
+/// stopped. The first bytes of every file are recorded with its offset: a
+/// file that is found to be shorter than its recorded offset, or to start
+/// with other bytes, is assumed to have been truncated or rotated, and is
+/// read again from the start.
+#[derive(Debug, Default)]
+pub struct JsonReadOffsets {
+    offsets: Mutex<HashMap<Path, ReadOffset>>,
+}
+
+/// The offset up to which a file has been read, and its first bytes
+#[derive(Debug)]
+struct ReadOffset {
+    offset: usize,
+    prefix: Bytes,
+}
+
+impl JsonReadOffsets {
+    /// The offset up to which `location` has been read, 0 if it has not
+    /// been read yet
+    pub fn offset(&self, location: &Path) -> usize {
+        self.offsets
+            .lock()
+            .get(location)
+            .map(|read| read.offset)
+            .unwrap_or_default()
+    }
+
+    /// Forget all offsets, so that the next scan reads every file in full
+    pub fn reset(&self) {
+        self.offsets.lock().clear()
+    }
+
+    /// Returns the offset to resume reading `location` from given its
+    /// current `size` and first bytes, resetting it if the file has shrunk
+    /// or does not start with the bytes recorded with its offset
+    pub(crate) fn resume_offset(
+        &self,
+        location: &Path,
+        size: usize,
+        prefix: &[u8],
+    ) -> usize {
+        let mut offsets = self.offsets.lock();
+        match offsets.get(location) {
+            Some(read) if read.offset <= size && prefix.starts_with(&read.prefix) => {
+                read.offset
+            }
+            Some(_) => {
+                offsets.remove(location);
+                0
+            }
+            None => 0,
+        }
+    }
+
+    /// Record that `location`, starting with `prefix`, has been read up to
+    /// `offset`
+    pub(crate) fn advance(&self, location: &Path, offset: usize, prefix: Bytes) {
+        self.offsets
+            .lock()
+            .insert(location.clone(), ReadOffset { offset, prefix });
+    }
+}
+
+/// The warnings raised while reading newline delimited JSON, see
+/// [`JsonScanOptions::diagnostics`]
+#[derive(Debug, Default)]
+pub struct JsonDiagnostics {
+    messages: Mutex<Vec<String>>,
+}
+
+impl JsonDiagnostics {
+    /// The warnings raised so far, in the order they were raised
+    pub fn messages(&self) -> Vec<String> {
+        self.messages.lock().clone()
+    }
+
+    /// Forget all warnings raised so far
+    pub fn clear(&self) {
+        self.messages.lock().clear()
+    }
+}
+
+/// The progress of reading a file of a JSON scan, see
+/// [`JsonScanOptions::progress`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileProgress {
+    /// The path of the file
+    pub location: Path,
+    /// The number of bytes read from the object store so far, before any
+    /// decompression
+    pub bytes_read: usize,
+    /// The number of rows produced from the file so far
+    pub rows: usize,
+    /// True for the last report of the file, once its end was reached
+    pub done: bool,
+}
+
+/// A callback the progress of reading every file of a JSON scan is reported
+/// to, see [`JsonScanOptions::progress`]
+#[derive(Clone)]
+pub struct JsonProgress(Arc<dyn Fn(FileProgress) + Send + Sync>);
+
+impl JsonProgress {
+    /// Create a callback calling `f` with every report
+    pub fn new(f: impl Fn(FileProgress) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(f))
+    }
+
+    pub(crate) fn report(&self, progress: FileProgress) {
+        (self.0)(progress)
+    }
+}
+
+impl std::fmt::Debug for JsonProgress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("JsonProgress").finish()
+    }
+}
+
+/// The number of decoded batches buffered between the producer and the
+/// consumer of a stream limited by [`InFlightLimit`], unless
+/// [`JsonScanOptions::max_batches_in_flight`] is set
+const IN_FLIGHT_CHANNEL_CAPACITY: usize = 16;
+
+/// Limits the total size and the number of the batches that a partition of
+/// a JSON scan has decoded but that have not been consumed yet, see
+/// [`JsonScanOptions::max_in_flight_bytes`] and
+/// [`JsonScanOptions::max_batches_in_flight`]
+#[derive(Debug)]
+pub(crate) struct InFlightLimit {
+    /// One permit per byte, capped at `u32::MAX` as permits are acquired
+    /// with a `u32`, if the size of the batches is limited
+    max_bytes: Option<usize>,
+    bytes: Option<Semaphore>,
+    /// One permit per batch, if the number of batches is limited
+    max_batches: Option<usize>,
+    batches: Option<Semaphore>,
+    /// The size of the last batch decoded, which is reserved before
+    /// decoding the next one
+    estimate: AtomicUsize,
+    in_flight: AtomicUsize,
+    peak: AtomicUsize,
+    batches_in_flight: AtomicUsize,
+    peak_batches: AtomicUsize,
+    /// Reports the peak number of bytes in flight
+    peak_metric: Gauge,
+    /// Reports the peak number of batches in flight
+    peak_batches_metric: Gauge,
+}
+
+impl InFlightLimit {
+    pub(crate) fn new(
+        max_bytes: Option<usize>,
+        max_batches: Option<usize>,
+        peak_metric: Gauge,
+        peak_batches_metric: Gauge,
+    ) -> Self {
+        let max_bytes = max_bytes.map(|max_bytes| max_bytes.clamp(1, u32::MAX as usize));
+        let max_batches = max_batches.map(|max_batches| max_batches.max(1));
+        Self {
+            max_bytes,
+            bytes: max_bytes.map(Semaphore::new),
+            max_batches,
+            batches: max_batches.map(Semaphore::new),
+            estimate: AtomicUsize::new(0),
+            in_flight: AtomicUsize::new(0),
+            peak: AtomicUsize::new(0),
+            batches_in_flight: AtomicUsize::new(0),
+            peak_batches: AtomicUsize::new(0),
+            peak_metric,
+            peak_batches_metric,
+        }
+    }
+
+    /// A limit of the same size for a stream that is consumed independently
+    /// of this one, reporting its peaks to the same metrics. Sharing a limit
+    /// between streams that are consumed together, like the inputs of a
+    /// merge, could block a stream on the batches buffered by another.
+    pub(crate) fn new_independent(&self) -> Self {
+        Self::new(
+            self.max_bytes,
+            self.max_batches,
+            self.peak_metric.clone(),
+            self.peak_batches_metric.clone(),
+        )
+    }
+
+    /// The number of permits reserved for a batch of `bytes`
+    fn permits(&self, bytes: usize) -> u32 {
+        bytes.min(self.max_bytes.unwrap_or(u32::MAX as usize)) as u32
+    }
+
+    /// Waits until a batch of the size of the last one decoded fits in the
+    /// limit, returning the number of bytes reserved for it
+    async fn reserve(&self) -> usize {
+        // the semaphores are never closed
+        if let Some(batches) = &self.batches {
+            if let Ok(permit) = batches.acquire().await {
+                permit.forget();
+            }
+        }
+        let reserved = self.estimate.load(Ordering::SeqCst);
+        if let Some(semaphore) = &self.bytes {
+            if let Ok(permits) = semaphore.acquire_many(self.permits(reserved)).await {
+                permits.forget();
+            }
+        }
+        reserved
+    }
+
+    /// Gives back a reservation that no batch has been decoded into
+    fn cancel(&self, reserved: usize) {
+        if let Some(semaphore) = &self.bytes {
+            semaphore.add_permits(self.permits(reserved) as usize);
+        }
+        if let Some(batches) = &self.batches {
+            batches.add_permits(1);
+        }
+    }
+
+    /// Adjusts a reservation to the `bytes` of the batch decoded into it and
+    /// records the batch as being in flight. A batch larger than its
+    /// reservation only waits for the difference once decoded, so the limit
+    /// is exceeded by at most the growth of a batch over the previous one.
+    async fn resize(&self, reserved: usize, bytes: usize) {
+        self.estimate.store(bytes, Ordering::SeqCst);
+        if let Some(semaphore) = &self.bytes {
+            let (reserved, permits) = (self.permits(reserved), self.permits(bytes));
+            if permits > reserved {
+                if let Ok(permits) = semaphore.acquire_many(permits - reserved).await {
+                    permits.forget();
+                }
+            } else {
+                semaphore.add_permits((reserved - permits) as usize);
+            }
+        }
+
+        let in_flight = self.in_flight.fetch_add(bytes, Ordering::SeqCst) + bytes;
+        let peak = self
+            .peak
+            .fetch_max(in_flight, Ordering::SeqCst)
+            .max(in_flight);
+        self.peak_metric.set(peak);
+
+        let batches = self.batches_in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        let peak_batches = self
+            .peak_batches
+            .fetch_max(batches, Ordering::SeqCst)
+            .max(batches);
+        self.peak_batches_metric.set(peak_batches);
+    }
+
+    /// Records a batch of `bytes` as consumed
+    fn release(&self, bytes: usize) {
+        self.in_flight.fetch_sub(bytes, Ordering::SeqCst);
+        self.batches_in_flight.fetch_sub(1, Ordering::SeqCst);
+        if let Some(semaphore) = &self.bytes {
+            semaphore.add_permits(self.permits(bytes) as usize);
+        }
+        if let Some(batches) = &self.batches {
+            batches.add_permits(1);
+        }
+    }
+
+    /// Decodes the batches of `stream` on a separate task, pausing before
+    /// decoding a batch whenever it would not fit in the limit next to the
+    /// batches not yet consumed from the returned stream
+    pub(crate) fn limit(
+        self: &Arc<Self>,
+        mut stream: BoxStream<'static, ArrowResult<RecordBatch>>,
+    ) -> BoxStream<'static, ArrowResult<RecordBatch>> {
+        let (sender, receiver) = tokio::sync::mpsc::channel(
+            self.max_batches.unwrap_or(IN_FLIGHT_CHANNEL_CAPACITY),
+        );
+
+        let limiter = self.clone();
+        let producer = tokio::spawn(async move {
+            loop {
+                // the room for a batch is reserved before decoding it
+                let reserved = limiter.reserve().await;
+                let batch = match stream.next().await {
+                    Some(batch) => batch,
+                    None => return limiter.cancel(reserved),
+                };
+                match &batch {
+                    Ok(batch) => limiter.resize(reserved, batch_byte_size(batch)).await,
+                    Err(_) => limiter.cancel(reserved),
+                }
+                if sender.send(batch).await.is_err() {
+                    // the consumer has been dropped
+                    return;
+                }
+            }
+        });
+        let producer = AbortOnDropSingle::new(producer);
+
+        let limiter = self.clone();
+        ReceiverStream::new(receiver)
+            .inspect_ok(move |batch| {
+                // owned by the stream so that the producer is aborted once
+                // the stream is dropped
+                let _ = &producer;
+                limiter.release(batch_byte_size(batch));
+            })
+            .boxed()
+    }
+}
+
+/// An iterator over the JSON values of newline delimited input, one per
+/// non blank line, after applying the line level transformations
+/// configured in [`JsonScanOptions`]
+pub(crate) struct JsonValueIter<R: BufRead> {
+    reader: R,
+    options: Arc<JsonScanOptions>,
+    /// The schema lines are read against, if known
+    schema: Option<SchemaRef>,
+    /// If set, the only keys of objects kept, all others are dropped as
+    /// soon as a line is parsed
+    projection: Option<HashSet<String>>,
+    /// The time zone timestamp strings without an offset are assumed to be
+    /// in, and the unit of the top level timestamp fields they are read into
+    timestamps: Option<(Tz, HashMap<String, TimeUnit>)>,
+    /// The top level floating point fields whose numbers are replaced by
+    /// strings, to be decoded alongside the non finite literals, see
+    /// [`JsonScanOptions::allow_nonfinite_floats`]
+    nonfinite_floats: HashSet<String>,
+    /// The top level floating point fields whose strings are parsed as
+    /// numbers, see [`JsonScanOptions::numeric_locale`]
+    localized_numbers: HashSet<String>,
+    /// The top level integer fields narrower than 64 bits, whose numbers are
+    /// checked to be in the range of their type
+    narrow_integers: HashMap<String, DataType>,
+    /// The top level base64 encoded binary and localized decimal fields,
+    /// whose values are decoded as lines are read
+    decoded_columns: Vec<DecodedColumn>,
+    /// Whether the values of [`Self::decoded_columns`] are taken out of the
+    /// objects produced and kept for [`Self::take_decoded`], rather than
+    /// only checked
+    keep_decoded: bool,
+    /// The top level fields that are not nullable whose key is absent from
+    /// all the objects produced so far, see [`Self::with_required_keys`]
+    absent_required: Vec<String>,
+    line_buf: String,
+    /// The 1-based number of the last line read
+    line_number: usize,
+    /// Whether the end of the input was reached
+    ended: bool,
+    /// The number of non blank lines read and of those skipped, see
+    /// [`JsonScanOptions::skip_invalid_rows`]
+    rows_read: usize,
+    rows_skipped: usize,
+    /// The error of the first line skipped
+    first_error: Option<ArrowError>,
+}
+
+impl<R: BufRead> JsonValueIter<R> {
+    /// Create an iterator over the values in `reader`. `schema` is required
+    /// by options that depend on the field order, such as
+    /// [`JsonScanOptions::positional_arrays`]
+    pub(crate) fn new(
+        reader: R,
+        options: Arc<JsonScanOptions>,
+        schema: Option<SchemaRef>,
+    ) -> Self {
+        // an invalid time zone is reported before scanning, see
+        // `NdJsonExec::execute`
+        let timezone = options.parse_default_timezone().ok().flatten();
+        let timestamps = timezone.zip(schema.as_ref()).map(|(timezone, schema)| {
+            let units = schema
+                .fields()
+                .iter()
+                .filter_map(|field| match field.data_type() {
+                    DataType::Timestamp(unit, _) => {
+                        Some((field.name().clone(), unit.clone()))
+                    }
+                    _ => None,
+                })
+                .collect();
+            (timezone, units)
+        });
+
+        let nonfinite_floats = schema
+            .iter()
+            .flat_map(|schema| schema.fields())
+            .filter(|field| reads_nonfinite_floats(field, &options))
+            .map(|field| field.name().clone())
+            .collect();
+
+        let localized_numbers = schema
+            .iter()
+            .flat_map(|schema| schema.fields())
+            .filter(|field| reads_localized_numbers(field, &options))
+            .filter(|field| !matches!(field.data_type(), DataType::Decimal128(_, _)))
+            .map(|field| field.name().clone())
+            .collect();
+
+        let narrow_integers = schema
+            .iter()
+            .flat_map(|schema| schema.fields())
+            .filter(|field| narrow_integer_range(field.data_type()).is_some())
+            .map(|field| (field.name().clone(), field.data_type().clone()))
+            .collect();
+
+        let decoded_columns = schema
+            .as_ref()
+            .map(|schema| decoded_columns(schema, &options))
+            .unwrap_or_default();
+
+        Self {
+            reader,
+            options,
+            schema,
+            projection: None,
+            timestamps,
+            nonfinite_floats,
+            localized_numbers,
+            narrow_integers,
+            decoded_columns,
+            keep_decoded: false,
+            absent_required: vec![],
+            line_buf: String::new(),
+            line_number: 0,
+            ended: false,
+            rows_read: 0,
+            rows_skipped: 0,
+            first_error: None,
+        }
+    }
+
+    /// The 1-based number of the last line read, 0 if none was
+    pub(crate) fn line_number(&self) -> usize {
+        self.line_number
+    }
+
+    /// Only keep the keys named in `projection` in the objects produced
+    pub(crate) fn with_projection(mut self, projection: Option<&[String]>) -> Self {
+        if let Some(projection) = projection {
+            self.decoded_columns
+                .retain(|column| projection.contains(&column.name));
+            self.absent_required
+                .retain(|name| projection.contains(name));
+        }
+        self.projection = projection.map(|names| names.iter().cloned().collect());
+        self
+    }
+
+    /// Take the values of base64 encoded binary and localized decimal fields
+    /// out of the objects produced, keeping them decoded for
+    /// [`Self::take_decoded`] so that they are not decoded again
+    pub(crate) fn with_keep_decoded(mut self, keep_decoded: bool) -> Self {
+        self.keep_decoded = keep_decoded;
+        self
+    }
+
+    /// Fail once the input ends if the key of a top level field of the
+    /// schema that is not nullable is absent from all the objects produced,
+    /// rather than reading it as nulls. This must precede any call to
+    /// [`Self::with_projection`]
+    pub(crate) fn with_required_keys(mut self) -> Self {
+        self.absent_required = self
+            .schema
+            .iter()
+            .flat_map(|schema| schema.fields())
+            .filter(|field| !field.is_nullable())
+            .map(|field| field.name().clone())
+            .collect();
+        self
+    }
+
+    /// The names and types of the fields whose values are decoded as lines
+    /// are read, see [`Self::with_keep_decoded`]
+    fn decoded_types(&self) -> HashMap<String, DataType> {
+        self.decoded_columns
+            .iter()
+            .map(|column| (column.name.clone(), column.data_type()))
+            .collect()
+    }
+
+    /// Takes the values kept for the objects produced since the last call,
+    /// see [`Self::with_keep_decoded`], as arrays keyed by field name
+    fn take_decoded(&mut self) -> ArrowResult<HashMap<String, ArrayRef>> {
+        self.decoded_columns
+            .iter_mut()
+            .map(|column| Ok((column.name.clone(), column.finish()?)))
+            .collect()
+    }
+
+    /// Inserts the values of `map` into `flattened`, keyed by their path
+    /// joined by the separator of [`JsonScanOptions::auto_flatten`]. Nested
+    /// objects are flattened too, unless the schema reads their path as a
+    /// column, such as one whose values have conflicting types
+    fn flatten_object(
+        &self,
+        map: Map<String, Value>,
+        prefix: Option<&str>,
+        flattened: &mut Map<String, Value>,
+    ) -> ArrowResult<()> {
+        let separator = self.options.flatten_separator();
+        for (key, value) in map {
+            let name = match prefix {
+                Some(prefix) => format!("{}{}{}", prefix, separator, key),
+                None => key,
+            };
+            let is_column = self
+                .schema
+                .as_ref()
+                .map_or(false, |schema| schema.field_with_name(&name).is_ok());
+            match value {
+                Value::Object(map) if !is_column => {
+                    self.flatten_object(map, Some(&name), flattened)?
+                }
+                value => {
+                    if flattened.insert(name.clone(), value).is_some() {
+                        return Err(ArrowError::JsonError(format!(
+                            "Flattened key '{}' conflicts with another key of the same name",
+                            name
+                        )));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Fails if the arrays and objects of `line` are nested deeper than
+    /// [`JsonScanOptions::max_depth`]
+    fn check_depth(&self, line: &str) -> ArrowResult<()> {
+        let max_depth = match self.options.max_depth {
+            Some(max_depth) => max_depth,
+            None => return Ok(()),
+        };
+        let (mut depth, mut in_string, mut escaped) = (0_usize, false, false);
+        for byte in line.bytes() {
+            if in_string {
+                match byte {
+                    _ if escaped => escaped = false,
+                    b'\\' => escaped = true,
+                    b'"' => in_string = false,
+                    _ => {}
+                }
+                continue;
+            }
+            match byte {
+                b'"' => in_string = true,
+                b'[' | b'{' => {
+                    depth += 1;
+                    if depth > max_depth {
+                        return Err(ArrowError::JsonError(format!(
+                            "JSON value on line {} is nested deeper than the maximum depth of {}",
+                            self.line_number, max_depth
+                        )));
+                    }
+                }
+                // the parser reports unbalanced brackets
+                b']' | b'}' => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn convert(&self, value: Value) -> ArrowResult<Value> {
+        let value = match value {
+            Value::Object(map) if !self.options.field_renames.is_empty() => {
+                self.rename_fields(map)
+            }
+            value => value,
+        };
+
+        let value = match value {
+            Value::Array(values) if self.options.positional_arrays => {
+                self.positional_to_object(values)?
+            }
+            value => value,
+        };
+
+        let value = match (value, &self.options.scalar_column) {
+            (Value::Object(map), _) => Value::Object(map),
+            (value, Some(name)) => {
+                Value::Object(std::iter::once((name.clone(), value)).collect())
+            }
+            (value, None) => value,
+        };
+
+        let value = match value {
+            Value::Object(mut map) if !self.options.expand_paths.is_empty() => {
+                for path in &self.options.expand_paths {
+                    let expanded = nested_value(&map, path);
+                    map.insert(path.clone(), expanded);
+                }
+                Value::Object(map)
+            }
+            value => value,
+        };
+
+        let value = match value {
+            Value::Object(map) if self.options.auto_flatten => {
+                let mut flattened = Map::new();
+                self.flatten_object(map, None, &mut flattened)?;
+                Value::Object(flattened)
+            }
+            value => value,
+        };
+
+        let value = match (value, &self.projection) {
+            (Value::Object(mut map), Some(projection)) => {
+                map.retain(|key, _| projection.contains(key));
+                Value::Object(map)
+            }
+            (value, _) => value,
+        };
+
+        let value = match (value, &self.options.numeric_locale) {
+            (Value::Object(mut map), Some(locale))
+                if !self.localized_numbers.is_empty() =>
+            {
+                for name in &self.localized_numbers {
+                    if let Some(value) = map.get_mut(name) {
+                        self.convert_localized_number(value, name, locale)?;
+                    }
+                }
+                Value::Object(map)
+            }
+            (value, _) => value,
+        };
+
+        let value = match value {
+            Value::Object(mut map) if !self.nonfinite_floats.is_empty() => {
+                for name in &self.nonfinite_floats {
+                    if let Some(value) = map.get_mut(name) {
+                        if value.is_number() {
+                            *value = Value::String(value.to_string());
+                        }
+                    }
+                }
+                Value::Object(map)
+            }
+            value => value,
+        };
+
+        let value = match value {
+            Value::Object(mut map) if !self.narrow_integers.is_empty() => {
+                for (name, data_type) in &self.narrow_integers {
+                    if let Some(value) = map.get_mut(name) {
+                        self.check_integer_range(value, name, data_type)?;
+                    }
+                }
+                Value::Object(map)
+            }
+            value => value,
+        };
+
+        match (value, &self.timestamps) {
+            (Value::Object(mut map), Some((timezone, units))) => {
+                for (name, unit) in units {
+                    if let Some(value) = map.get_mut(name) {
+                        self.convert_timestamp(value, name, unit, timezone)?;
+                    }
+                }
+                Ok(Value::Object(map))
+            }
+            (value, _) => Ok(value),
+        }
+    }
+
+    /// Replaces a timestamp string with its value in `unit` since the epoch,
+    /// assuming it is in `timezone` if it has no offset. The decoder would
+    /// otherwise assume such strings are in UTC.
+    fn convert_timestamp(
+        &self,
+        value: &mut Value,
+        name: &str,
+        unit: &TimeUnit,
+        timezone: &Tz,
+    ) -> ArrowResult<()> {
+        if let Value::String(s) = value {
+            let timestamp = parse_timestamp(s, unit, timezone).ok_or_else(|| {
+                ArrowError::JsonError(format!(
+                    "Invalid timestamp '{}' in column '{}' on line {}",
+                    s, name, self.line_number
+                ))
+            })?;
+            *value = Value::from(timestamp);
+        }
+        Ok(())
+    }
+
+    /// Replaces a number written as a string with the separators of `locale`
+    /// by the number itself, read into a floating point field. The decoder
+    /// would otherwise fail to read such strings into numbers.
+    fn convert_localized_number(
+        &self,
+        value: &mut Value,
+        name: &str,
+        locale: &NumericLocale,
+    ) -> ArrowResult<()> {
+        let number = match &*value {
+            Value::String(s) => locale.normalize(s).ok_or(s.as_str()),
+            _ => return Ok(()),
+        };
+        let number = number.map_err(|s| self.invalid_number(s, name))?;
+
+        match number.parse::<f64>() {
+            Ok(f) if f.is_finite() => *value = Value::from(f),
+            // non finite literals are left to be parsed as such, if allowed
+            _ if self.options.allow_nonfinite_floats
+                && NONFINITE_FLOATS.contains(&number.as_str()) => {}
+            _ => return Err(self.invalid_number(&number, name)),
+        }
+        Ok(())
+    }
+
+    fn invalid_number(&self, number: &str, name: &str) -> ArrowError {
+        ArrowError::JsonError(format!(
+            "Invalid number '{}' in column '{}' on line {}",
+            number, name, self.line_number
+        ))
+    }
+
+    /// Decodes the values of the [`Self::decoded_columns`] of `value`, which
+    /// are taken out of it and kept if [`Self::keep_decoded`] is set. Nothing
+    /// is kept unless all of them are decoded, so that the values kept stay
+    /// aligned with the objects produced.
+    fn decode_columns(&mut self, mut value: Value) -> ArrowResult<Value> {
+        if self.decoded_columns.is_empty() {
+            return Ok(value);
+        }
+        let mut object = value.as_object_mut();
+        let decoded = self
+            .decoded_columns
+            .iter()
+            .map(|column| {
+                let value = object.as_ref().and_then(|object| object.get(&column.name));
+                self.decode_value(column, value)
+            })
+            .collect::<ArrowResult<Vec<_>>>()?;
+        if self.keep_decoded {
+            for (column, decoded) in self.decoded_columns.iter_mut().zip(decoded) {
+                if let Some(object) = object.as_mut() {
+                    object.remove(&column.name);
+                }
+                column.push(decoded);
+            }
+        }
+        Ok(value)
+    }
+
+    /// Decodes the value of `column` in an object, `None` if its key is
+    /// absent
+    fn decode_value(
+        &self,
+        column: &DecodedColumn,
+        value: Option<&Value>,
+    ) -> ArrowResult<DecodedValue> {
+        let name = &column.name;
+        let value = value.filter(|value| !value.is_null());
+        match &column.values {
+            DecodedValues::Binary(encoding, _) => {
+                let binary = match value {
+                    None => None,
+                    Some(Value::String(s)) => Some(encoding.decode(s).map_err(|e| {
+                        ArrowError::JsonError(format!(
+                            "Invalid base64 in column '{}' on line {}: {}",
+                            name, self.line_number, e
+                        ))
+                    })?),
+                    Some(other) => {
+                        return Err(ArrowError::JsonError(format!(
+                        "Expected a base64 string in column '{}' on line {}, found {}",
+                        name, self.line_number, other
+                    )))
+                    }
+                };
+                Ok(DecodedValue::Binary(binary))
+            }
+            DecodedValues::Decimal(data_type, _) => {
+                let number = match value {
+                    None => return Ok(DecodedValue::Decimal(None)),
+                    Some(Value::String(s)) => {
+                        let locale = self.options.numeric_locale.as_ref();
+                        locale
+                            .and_then(|locale| locale.normalize(s))
+                            .ok_or_else(|| self.invalid_number(s, name))?
+                    }
+                    Some(Value::Number(n)) => n.to_string(),
+                    Some(other) => {
+                        return Err(self.invalid_number(&other.to_string(), name))
+                    }
+                };
+                match ScalarValue::try_from_string(number.clone(), data_type) {
+                    Ok(ScalarValue::Decimal128(Some(v), _, _)) => {
+                        Ok(DecodedValue::Decimal(Some(v)))
+                    }
+                    _ => Err(self.invalid_number(&number, name)),
+                }
+            }
+        }
+    }
+
+    /// Checks that an integer read into the field `name` is in the range of
+    /// its `data_type`, replacing it by null if it is not and
+    /// [`JsonScanOptions::null_on_integer_overflow`] is set. The decoder would
+    /// otherwise silently read such integers as nulls.
+    fn check_integer_range(
+        &self,
+        value: &mut Value,
+        name: &str,
+        data_type: &DataType,
+    ) -> ArrowResult<()> {
+        let in_range = match (&*value, narrow_integer_range(data_type)) {
+            (Value::Number(n), Some(range)) if n.is_i64() || n.is_u64() => {
+                n.as_i64().map_or(false, |v| range.contains(&v))
+            }
+            _ => true,
+        };
+        if in_range {
+            Ok(())
+        } else if self.options.null_on_integer_overflow {
+            *value = Value::Null;
+            Ok(())
+        } else {
+            Err(ArrowError::JsonError(format!(
+                "Value {} is out of range for {} column '{}' on line {}",
+                value, data_type, name, self.line_number
+            )))
+        }
+    }
+
+    /// Renames the keys of `map` according to
+    /// [`JsonScanOptions::field_renames`]
+    fn rename_fields(&self, mut map: Map<String, Value>) -> Value {
+        let renamed = self
+            .options
+            .field_renames
+            .iter()
+            .filter_map(|(from, to)| Some((to.clone(), map.remove(from)?)))
+            .collect::<Vec<_>>();
+        map.extend(renamed);
+        Value::Object(map)
+    }
+
+    /// Records that the current line was skipped because of `error`,
+    /// returning an error if this exceeds
+    /// [`JsonScanOptions::max_error_count`]
+    fn skip(&mut self, error: ArrowError) -> Option<ArrowError> {
+        log::debug!("Skipping JSON line {}: {}", self.line_number, error);
+        self.rows_skipped += 1;
+        let first_error = self.first_error.get_or_insert(error);
+        match self.options.max_error_count {
+            Some(max_count) if self.rows_skipped > max_count => {
+                Some(ArrowError::JsonError(format!(
+                    "Skipped {} invalid JSON lines, more than the maximum of {}, the first error being: {}",
+                    self.rows_skipped, max_count, first_error
+                )))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns an error if the fraction of the lines read that were skipped
+    /// exceeds [`JsonScanOptions::max_error_fraction`]
+    fn check_error_fraction(&mut self) -> Option<ArrowError> {
+        let max_fraction = self.options.max_error_fraction?;
+        if self.rows_skipped == 0 {
+            return None;
+        }
+        let fraction = self.rows_skipped as f64 / self.rows_read as f64;
+        // only reported once, the end of the input being reached again
+        // when polled after the error
+        let rows_skipped = std::mem::take(&mut self.rows_skipped);
+        let first_error = self.first_error.take()?;
+        (fraction > max_fraction).then(|| {
+            ArrowError::JsonError(format!(
+                "Skipped {} of {} JSON lines, a fraction of {:.3} above the maximum of {}, the first error being: {}",
+                rows_skipped, self.rows_read, fraction, max_fraction, first_error
+            ))
+        })
+    }
+
+    /// The keys of [`Self::absent_required`] found in `value`
+    fn found_required_keys(&self, value: &Value) -> Vec<String> {
+        match value.as_object() {
+            Some(object) => self
+                .absent_required
+                .iter()
+                .filter(|name| object.contains_key(name.as_str()))
+                .cloned()
+                .collect(),
+            None => vec![],
+        }
+    }
+
+    /// Returns an error if a key of [`Self::absent_required`] is absent from
+    /// all the objects produced, if any was
+    fn check_required_keys(&mut self) -> Option<ArrowError> {
+        // only reported once, as for `check_error_fraction`
+        let absent = std::mem::take(&mut self.absent_required);
+        if self.rows_read == self.rows_skipped {
+            return None;
+        }
+        absent.first().map(|name| {
+            ArrowError::JsonError(format!(
+                "Field '{}' is not nullable but has no value in any row",
+                name
+            ))
+        })
+    }
+
+    /// Converts a positional array into an object keyed by the schema's
+    /// field names
+    fn positional_to_object(&self, values: Vec<Value>) -> ArrowResult<Value> {
+        let schema = self.schema.as_ref().ok_or_else(|| {
+            ArrowError::JsonError(
+                "Reading positional JSON arrays requires an explicit schema".to_string(),
+            )
+        })?;
+
+        let fields = schema.fields();
+        if values.len() != fields.len() {
+            return Err(ArrowError::JsonError(format!(
+                "Positional JSON array on line {} has {} elements but the schema has {} fields",
+                self.line_number,
+                values.len(),
+                fields.len()
+            )));
+        }
+
+        Ok(Value::Object(
+            fields
+                .iter()
+                .map(|field| field.name().clone())
+                .zip(values)
+                .collect(),
+        ))
+    }
+}
+
+impl<R: BufRead> Iterator for JsonValueIter<R> {
+    type Item = ArrowResult<Value>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            self.line_buf.clear();
+            match self.reader.read_line(&mut self.line_buf) {
+                Ok(0) => {
+                    self.ended = true;
+                    let absent_key = self.check_required_keys();
+                    return self.check_error_fraction().or(absent_key).map(Err);
+                }
+                Ok(_) => self.line_number += 1,
+                Err(e) => {
+                    return Some(Err(ArrowError::JsonError(format!(
+                        "Failed to read JSON record: {}",
+                        e
+                    ))))
+                }
+            }
+
+            // a blank line that is not skipped is not valid JSON
+            let line = self.line_buf.trim();
+            if line.is_empty() && self.options.skip_blank_lines {
+                continue;
+            }
+            if let Some(prefix) = &self.options.comment_prefix {
+                if line.starts_with(prefix.as_str()) {
+                    continue;
+                }
+            }
+
+            let line = if self.options.allow_nonfinite_floats {
+                replace_nonfinite_floats(line, self.schema.is_none())
+            } else {
+                Ok(Cow::Borrowed(line))
+            };
+            let value = line
+                .and_then(|line| {
+                    self.check_depth(&line)?;
+                    serde_json::from_str(&line).map_err(|e| {
+                        ArrowError::JsonError(format!("Not valid JSON: {}", e))
+                    })
+                })
+                .and_then(|value| self.convert(value))
+                .and_then(|value| {
+                    // taken before the keys of decoded columns are removed
+                    let found = self.found_required_keys(&value);
+                    let value = self.decode_columns(value)?;
+                    self.absent_required.retain(|name| !found.contains(name));
+                    Ok(value)
+                });
+            self.rows_read += 1;
+            match value {
+                Err(e) if self.options.skip_invalid_rows => {
+                    if let Some(e) = self.skip(e) {
+                        return Some(Err(e));
+                    }
+                }
+                value => return Some(value),
+            }
+        }
+    }
+}
+
+/// A top level field whose values are decoded as lines are read rather than
+/// by the decoder, see [`JsonValueIter::with_keep_decoded`]
+struct DecodedColumn {
+    name: String,
+    values: DecodedValues,
+}
+
+/// The values kept of a [`DecodedColumn`]
+enum DecodedValues {
+    /// Bytes encoded in base64, see [`JsonScanOptions::base64_columns`]
+    Binary(Base64Encoding, Vec<Option<Vec<u8>>>),
+    /// Decimals of the given type, written with the separators of
+    /// [`JsonScanOptions::numeric_locale`]
+    Decimal(DataType, Vec<Option<i128>>),
+}
+
+/// The decoded value of a [`DecodedColumn`] in an object
+enum DecodedValue {
+    Binary(Option<Vec<u8>>),
+    Decimal(Option<i128>),
+}
+
+impl DecodedColumn {
+    fn data_type(&self) -> DataType {
+        match &self.values {
+            DecodedValues::Binary(_, _) => DataType::Binary,
+            DecodedValues::Decimal(data_type, _) => data_type.clone(),
+        }
+    }
+
+    fn push(&mut self, value: DecodedValue) {
+        match (&mut self.values, value) {
+            (DecodedValues::Binary(_, values), DecodedValue::Binary(value)) => {
+                values.push(value)
+            }
+            (DecodedValues::Decimal(_, values), DecodedValue::Decimal(value)) => {
+                values.push(value)
+            }
+            _ => unreachable!("a value is decoded for the type of its column"),
+        }
+    }
+
+    /// Builds the array of the values kept, which are cleared
+    fn finish(&mut self) -> ArrowResult<ArrayRef> {
+        let array: ArrayRef = match &mut self.values {
+            DecodedValues::Binary(_, values) => {
+                Arc::new(BinaryArray::from_iter(values.drain(..)))
+            }
+            DecodedValues::Decimal(data_type, values) => {
+                let (precision, scale) = match data_type {
+                    DataType::Decimal128(precision, scale) => (*precision, *scale),
+                    other => {
+                        return Err(ArrowError::JsonError(format!(
+                            "Can not parse decimal numbers as {}",
+                            other
+                        )))
+                    }
+                };
+                Arc::new(
+                    values
+                        .drain(..)
+                        .collect::<Decimal128Array>()
+                        .with_precision_and_scale(precision, scale)?,
+                )
+            }
+        };
+        Ok(array)
+    }
+}
+
+/// Returns the [`DecodedColumn`]s of the top level fields of `schema`
+fn decoded_columns(schema: &Schema, options: &JsonScanOptions) -> Vec<DecodedColumn> {
+    let base64_fields = options.base64_fields(schema);
+    schema
+        .fields()
+        .iter()
+        .filter_map(|field| {
+            let values = match base64_fields.get(field.name()) {
+                Some(encoding) => DecodedValues::Binary(*encoding, vec![]),
+                None if reads_localized_numbers(field, options)
+                    && matches!(field.data_type(), DataType::Decimal128(_, _)) =>
+                {
+                    DecodedValues::Decimal(field.data_type().clone(), vec![])
+                }
+                None => return None,
+            };
+            Some(DecodedColumn {
+                name: field.name().clone(),
+                values,
+            })
+        })
+        .collect()
+}
+
+/// Returns the range of the values of `data_type` if it is an integer type
+/// narrower than 64 bits
+fn narrow_integer_range(data_type: &DataType) -> Option<RangeInclusive<i64>> {
+    match data_type {
+        DataType::Int8 => Some(i8::MIN as i64..=i8::MAX as i64),
+        DataType::Int16 => Some(i16::MIN as i64..=i16::MAX as i64),
+        DataType::Int32 => Some(i32::MIN as i64..=i32::MAX as i64),
+        DataType::UInt8 => Some(0..=u8::MAX as i64),
+        DataType::UInt16 => Some(0..=u16::MAX as i64),
+        DataType::UInt32 => Some(0..=u32::MAX as i64),
+        _ => None,
+    }
+}
+
+/// The literals read as floating point numbers if
+/// [`JsonScanOptions::allow_nonfinite_floats`] is set, `-Infinity` first so
+/// that it is not read as `-` followed by `Infinity`
+const NONFINITE_FLOATS: [&str; 3] = ["-Infinity", "Infinity", "NaN"];
+
+/// Returns true if `field` is read from numbers and non finite literals
+/// decoded as strings, see [`JsonScanOptions::allow_nonfinite_floats`]
+fn reads_nonfinite_floats(field: &Field, options: &JsonScanOptions) -> bool {
+    options.allow_nonfinite_floats
+        && matches!(field.data_type(), DataType::Float32 | DataType::Float64)
+}
+
+/// Returns true if `field` is read from numbers written as strings with the
+/// separators of [`JsonScanOptions::numeric_locale`]
+fn reads_localized_numbers(field: &Field, options: &JsonScanOptions) -> bool {
+    options.numeric_locale.is_some()
+        && matches!(
+            field.data_type(),
+            DataType::Float32 | DataType::Float64 | DataType::Decimal128(_, _)
+        )
+}
+
+/// Replaces the non finite literals outside the strings of `line`, which
+/// are not valid JSON, so that it can be parsed.
+///
+/// When `inferring` a schema they are replaced by `0.0` so that their fields
+/// are inferred as floats. Otherwise they are replaced by strings of
+/// themselves, parsed once decoded. Nested ones cannot be decoded into
+/// floats and are an error, as they would be if not allowed.
+fn replace_nonfinite_floats(line: &str, inferring: bool) -> ArrowResult<Cow<'_, str>> {
+    if !line.contains("NaN") && !line.contains("Infinity") {
+        return Ok(Cow::Borrowed(line));
+    }
+
+    let mut result = String::with_capacity(line.len() + 8);
+    let mut depth = 0_usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut rest = line;
+    while let Some(c) = rest.chars().next() {
+        if in_string {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == '"' {
+                in_string = false;
+            }
+        } else {
+            match c {
+                '"' => in_string = true,
+                '{' | '[' => depth += 1,
+                '}' | ']' => depth = depth.saturating_sub(1),
+                _ => {
+                    if let Some(literal) =
+                        NONFINITE_FLOATS.iter().find(|l| rest.starts_with(*l))
+                    {
+                        if depth > 1 {
+                            return Err(ArrowError::JsonError(format!(
+                                "Not valid JSON: {} nested in an object or list",
+                                literal
+                            )));
+                        }
+                        if inferring {
+                            result.push_str("0.0");
+                        } else {
+                            result.push('"');
+                            result.push_str(literal);
+                            result.push('"');
+                        }
+                        rest = &rest[literal.len()..];
+                        continue;
+                    }
+                }
+            }
+        }
+        result.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    Ok(Cow::Owned(result))
+}
+
+/// Parses the strings of the floating point column `name`, decoded from
+/// numbers and non finite literals, into an array of `data_type`
+fn parse_float_array(
+    name: &str,
+    array: &ArrayRef,
+    data_type: &DataType,
+) -> ArrowResult<ArrayRef> {
+    let strings = array
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .ok_or_else(|| {
+            ArrowError::JsonError(format!(
+                "Expected the floats of column '{}' to be decoded as strings",
+                name
+            ))
+        })?;
+    let invalid = |s: &str| {
+        ArrowError::JsonError(format!(
+            "Invalid floating point number '{}' in column '{}'",
+            s, name
+        ))
+    };
+
+    let array: ArrayRef = match data_type {
+        DataType::Float32 => Arc::new(Float32Array::from(parse_strings(strings, |s| {
+            s.parse::<f32>().map_err(|_| invalid(s))
+        })?)),
+        DataType::Float64 => Arc::new(Float64Array::from(parse_strings(strings, |s| {
+            s.parse::<f64>().map_err(|_| invalid(s))
+        })?)),
+        other => {
+            return Err(ArrowError::JsonError(format!(
+                "Can not parse floating point numbers as {}",
+                other
+            )))
+        }
+    };
+    Ok(array)
+}
+
+/// Converts the lists of the column `name` to the fixed size lists of
+/// `data_type`, failing if any of them has another length
+fn fixed_size_list_array(
+    name: &str,
+    array: &ArrayRef,
+    data_type: &DataType,
+) -> ArrowResult<ArrayRef> {
+    let lists = array.as_any().downcast_ref::<ListArray>().ok_or_else(|| {
+        ArrowError::JsonError(format!(
+            "Expected the fixed size lists of column '{}' to be decoded as lists",
+            name
+        ))
+    })?;
+    let size = match data_type {
+        DataType::FixedSizeList(_, size) => *size as usize,
+        _ => {
+            return Err(ArrowError::JsonError(format!(
+                "Expected column '{}' to be a fixed size list, found {:?}",
+                name, data_type
+            )))
+        }
+    };
+
+    // a null list still holds `size` null elements
+    let offsets = lists.value_offsets();
+    let mut indices = Vec::with_capacity(lists.len() * size);
+    for i in 0..lists.len() {
+        if lists.is_null(i) {
+            indices.extend(std::iter::repeat(None).take(size));
+            continue;
+        }
+        let (start, end) = (offsets[i] as u32, offsets[i + 1] as u32);
+        if (end - start) as usize != size {
+            return Err(ArrowError::JsonError(format!(
+                "Expected the lists of column '{}' to have {} elements, found {}",
+                name,
+                size,
+                end - start
+            )));
+        }
+        indices.extend((start..end).map(Some));
+    }
+    let values = take(lists.values().as_ref(), &UInt32Array::from(indices), None)?;
+
+    let data = lists.data();
+    let nulls = data
+        .null_buffer()
+        .map(|nulls| nulls.bit_slice(data.offset(), data.len()));
+    let data = ArrayData::builder(data_type.clone())
+        .len(lists.len())
+        .null_bit_buffer(nulls)
+        .add_child_data(values.data().clone())
+        .build()?;
+    Ok(make_array(data))
+}
+
+/// Reads [`RecordBatch`]es of at most `batch_size` rows from newline
+/// delimited JSON, honoring the given [`JsonScanOptions`]
+///
+/// Top level fields of a `Duration` or `Interval` type are read from
+/// ISO-8601 duration strings such as `"PT1H30M"`.
+pub struct JsonBatchReader<R: BufRead> {
+    values: JsonValueIter<R>,
+    decoder: Decoder,
+    /// The fields decoded as strings that are then parsed as durations,
+    /// keyed by name
+    durations: HashMap<String, DataType>,
+    /// The timestamp fields with a time zone, which the decoder does not
+    /// support, decoded without it, keyed by name
+    zoned_timestamps: HashMap<String, DataType>,
+    /// The projected `LargeUtf8` and `LargeList` fields, which the decoder
+    /// does not support, whose values are taken out of the rows it decodes
+    /// and built with 64 bit offsets, keyed by name
+    large_offsets: HashMap<String, DataType>,
+    /// The `FixedSizeList` fields, which the decoder does not support,
+    /// decoded as `List` and then converted, keyed by name
+    fixed_size_lists: HashMap<String, DataType>,
+    /// The floating point fields decoded as strings that are then parsed,
+    /// keyed by name, see [`JsonScanOptions::allow_nonfinite_floats`]
+    nonfinite_floats: HashMap<String, DataType>,
+    /// The base64 encoded binary and localized decimal fields, whose values
+    /// are decoded as lines are read and taken out of the rows the decoder
+    /// reads, keyed by name, see [`JsonValueIter::with_keep_decoded`]
+    decoded_columns: HashMap<String, DataType>,
+    /// The indices of the decoded columns in the order of the projection,
+    /// if it differs from the order of the schema they are decoded in
+    projection_order: Option<Vec<usize>>,
+    /// Shuffles the decoded rows, if
+    /// [`JsonScanOptions::shuffle_window_rows`] is set
+    shuffle: Option<ShuffleWindow>,
+    /// See [`JsonScanOptions::deadline`]
+    deadline: Option<Instant>,
+}
+
+impl<R: BufRead> JsonBatchReader<R> {
+    /// Create a reader of `reader` against the file schema `schema`, only
+    /// producing the columns named in `projection` if provided
+    ///
+    /// The fields of the JSON objects are mapped to the columns by name,
+    /// whatever their order, and the columns are produced in the order of
+    /// `schema`, or of `projection` if provided. A field of `schema` whose
+    /// key is absent from all the rows of `reader` is read as nulls, unless
+    /// it is not nullable, which is an error once `reader` ends.
+    pub fn new(
+        reader: R,
+        schema: SchemaRef,
+        batch_size: usize,
+        projection: Option<Vec<String>>,
+        options: Arc<JsonScanOptions>,
+    ) -> Self {
+        let shuffle = options.shuffle_window_rows.map(|window_rows| {
+            ShuffleWindow::new(window_rows, options.shuffle_seed, batch_size)
+        });
+        let deadline = options.deadline;
+
+        let nonfinite_floats: HashMap<_, _> = schema
+            .fields()
+            .iter()
+            .filter(|field| reads_nonfinite_floats(field, &options))
+            .map(|field| (field.name().clone(), field.data_type().clone()))
+            .collect();
+
+        // the decoder builds the projected columns in the order of the schema
+        // rather than of the projection, so they are reordered once decoded
+        let (projection, projection_order) = match projection {
+            Some(projection) => {
+                let (projection, order) = schema_ordered_projection(&schema, projection);
+                (Some(projection), order)
+            }
+            None => (None, None),
+        };
+
+        // drop the values of unprojected fields before they are buffered by
+        // the decoder, rather than only skipping them when building arrays
+        let values = JsonValueIter::new(reader, options, Some(schema.clone()))
+            .with_keep_decoded(true)
+            .with_required_keys();
+        let decoded_columns = values.decoded_types();
+        let values = values.with_projection(projection.as_deref());
+
+        let durations: HashMap<_, _> = schema
+            .fields()
+            .iter()
+            .filter(|field| is_duration_type(field.data_type()))
+            .map(|field| (field.name().clone(), field.data_type().clone()))
+            .collect();
+
+        let zoned_timestamps: HashMap<_, _> = schema
+            .fields()
+            .iter()
+            .filter(|field| matches!(field.data_type(), DataType::Timestamp(_, Some(_))))
+            .map(|field| (field.name().clone(), field.data_type().clone()))
+            .collect();
+
+        let large_offsets: HashMap<_, _> = schema
+            .fields()
+            .iter()
+            .filter(|field| small_offset_type(field.data_type()).is_some())
+            .filter(|field| match &projection {
+                Some(projection) => projection.contains(field.name()),
+                None => true,
+            })
+            .map(|field| (field.name().clone(), field.data_type().clone()))
+            .collect();
+
+        let fixed_size_lists: HashMap<_, _> = schema
+            .fields()
+            .iter()
+            .filter(|field| matches!(field.data_type(), DataType::FixedSizeList(_, _)))
+            .map(|field| (field.name().clone(), field.data_type().clone()))
+            .collect();
+
+        // the decoder does not support durations, so they are decoded as the
+        // strings they are written as, nor time zones, which are dropped, nor
+        // 64 bit offsets, whose values are built separately, leaving the
+        // decoder only nulls to narrow offsets, nor fixed size lists, which are
+        // decoded as lists, nor non finite floats, which are decoded as
+        // strings, nor base64 encoded binaries and localized decimals, which
+        // are decoded as lines are read, leaving the decoder only nulls
+        let decoder_schema = if durations.is_empty()
+            && zoned_timestamps.is_empty()
+            && large_offsets.is_empty()
+            && fixed_size_lists.is_empty()
+            && nonfinite_floats.is_empty()
+            && decoded_columns.is_empty()
+        {
+            schema
+        } else {
+            let fields = schema
+                .fields()
+                .iter()
+                .map(|field| match field.data_type() {
+                    _ if durations.contains_key(field.name())
+                        || nonfinite_floats.contains_key(field.name())
+                        || decoded_columns.contains_key(field.name()) =>
+                    {
+                        Field::new(field.name(), DataType::Utf8, field.is_nullable())
+                    }
+                    DataType::Timestamp(unit, Some(_)) => Field::new(
+                        field.name(),
+                        DataType::Timestamp(unit.clone(), None),
+                        field.is_nullable(),
+                    ),
+                    DataType::FixedSizeList(item, _) => Field::new(
+                        field.name(),
+                        DataType::List(item.clone()),
+                        field.is_nullable(),
+                    ),
+                    data_type => match small_offset_type(data_type) {
+                        Some(data_type) => {
+                            Field::new(field.name(), data_type, field.is_nullable())
+                        }
+                        None => field.clone(),
+                    },
+                })
+                .collect();
+            Arc::new(Schema::new_with_metadata(fields, schema.metadata().clone()))
+        };
+
+        let decoder_options = DecoderOptions::new().with_batch_size(batch_size);
+        let decoder_options = match projection {
+            Some(projection) => decoder_options.with_projection(projection),
+            None => decoder_options,
+        };
+
+        Self {
+            values,
+            decoder: Decoder::new(decoder_schema, decoder_options),
+            durations,
+            zoned_timestamps,
+            large_offsets,
+            fixed_size_lists,
+            nonfinite_floats,
+            decoded_columns,
+            projection_order,
+            shuffle,
+            deadline,
+        }
+    }
+
+    /// Decodes the next batch of the rows in the order they are read
+    fn next_decoded(&mut self) -> Option<ArrowResult<RecordBatch>> {
+        // a child of the span of the file being read, if any
+        let _span = tracing::debug_span!("json_deserialize").entered();
+        if let Err(e) = check_deadline(self.deadline) {
+            return Some(Err(e.into()));
+        }
+        let mut large_columns: Vec<_> = self
+            .large_offsets
+            .iter()
+            .map(|(name, data_type)| LargeColumn::new(name, data_type))
+            .collect();
+        let batch = if large_columns.is_empty() {
+            self.decoder.next_batch(&mut self.values)
+        } else {
+            let mut values = LargeColumnValues {
+                values: &mut self.values,
+                columns: &mut large_columns,
+            };
+            self.decoder.next_batch(&mut values)
+        };
+        // taken whether or not the batch is decoded, to stay aligned with it
+        let decoded = self.values.take_decoded();
+        Some(batch.transpose()?.and_then(|batch| {
+            let batch = self.convert_columns(batch, large_columns, decoded?)?;
+            match &self.projection_order {
+                Some(order) => batch.project(order),
+                None => Ok(batch),
+            }
+        }))
+    }
+
+    /// Parses the string columns of duration and non finite float fields in
+    /// `batch`, restores the time zone of timestamp fields, replaces the
+    /// columns of large fields by `large_columns` and of base64 encoded
+    /// binary and localized decimal fields by `decoded`, and converts the
+    /// lists of fixed size list fields
+    fn convert_columns(
+        &self,
+        batch: RecordBatch,
+        mut large_columns: Vec<LargeColumn>,
+        mut decoded: HashMap<String, ArrayRef>,
+    ) -> ArrowResult<RecordBatch> {
+        if self.durations.is_empty()
+            && self.zoned_timestamps.is_empty()
+            && self.large_offsets.is_empty()
+            && self.fixed_size_lists.is_empty()
+            && self.nonfinite_floats.is_empty()
+            && self.decoded_columns.is_empty()
+        {
+            return Ok(batch);
+        }
+
+        let schema = batch.schema();
+        let mut fields = Vec::with_capacity(batch.num_columns());
+        let mut columns = Vec::with_capacity(batch.num_columns());
+        for (field, column) in schema.fields().iter().zip(batch.columns()) {
+            let name = field.name();
+            let (data_type, column) = if let Some(data_type) = self.durations.get(name) {
+                (data_type, parse_duration_array(name, column, data_type)?)
+            } else if let Some(data_type) = self.zoned_timestamps.get(name) {
+                let data = column
+                    .data()
+                    .clone()
+                    .into_builder()
+                    .data_type(data_type.clone())
+                    .build()?;
+                (data_type, make_array(data))
+            } else if let Some(index) =
+                large_columns.iter().position(|large| &large.name == name)
+            {
+                let large = large_columns.swap_remove(index);
+                (&self.large_offsets[name], large.finish()?)
+            } else if let Some(data_type) = self.fixed_size_lists.get(name) {
+                (data_type, fixed_size_list_array(name, column, data_type)?)
+            } else if let Some(data_type) = self.nonfinite_floats.get(name) {
+                (data_type, parse_float_array(name, column, data_type)?)
+            } else if let Some(column) = decoded.remove(name) {
+                (&self.decoded_columns[name], column)
+            } else {
+                fields.push(field.clone());
+                columns.push(column.clone());
+                continue;
+            };
+            fields.push(Field::new(name, data_type.clone(), field.is_nullable()));
+            columns.push(column);
+        }
+
+        let schema = Schema::new_with_metadata(fields, schema.metadata().clone());
+        RecordBatch::try_new(Arc::new(schema), columns)
+    }
+}
+
+/// The values of a `LargeUtf8` or `LargeList` field, which the decoder does
+/// not support, taken out of the rows of a batch before they are decoded
+struct LargeColumn {
+    name: String,
+    data_type: DataType,
+    values: LargeValues,
+}
+
+enum LargeValues {
+    Utf8(LargeStringBuilder),
+    List {
+        item: Field,
+        /// The end offsets of the lists, after a leading 0
+        offsets: Vec<i64>,
+        nulls: BooleanBufferBuilder,
+        /// The items of all lists, decoded once the batch is complete
+        items: Vec<Value>,
+    },
+}
+
+impl LargeColumn {
+    fn new(name: &str, data_type: &DataType) -> Self {
+        let values = match data_type {
+            DataType::LargeList(item) => LargeValues::List {
+                item: item.as_ref().clone(),
+                offsets: vec![0],
+                nulls: BooleanBufferBuilder::new(0),
+                items: vec![],
+            },
+            _ => LargeValues::Utf8(LargeStringBuilder::new()),
+        };
+        Self {
+            name: name.to_string(),
+            data_type: data_type.clone(),
+            values,
+        }
+    }
+
+    /// Appends the value of a row, `None` if its key is absent, read like
+    /// the decoder reads `Utf8` and `List` fields
+    fn push(&mut self, value: Option<Value>) {
+        match &mut self.values {
+            LargeValues::Utf8(builder) => match value {
+                None | Some(Value::Null) => builder.append_null(),
+                Some(Value::String(value)) => builder.append_value(value),
+                Some(value) => builder.append_value(value.to_string()),
+            },
+            LargeValues::List {
+                offsets,
+                nulls,
+                items,
+                ..
+            } => {
+                match value {
+                    None | Some(Value::Null) => nulls.append(false),
+                    Some(Value::Array(values)) => {
+                        items.extend(values);
+                        nulls.append(true);
+                    }
+                    // a single value is read as a list of that value
+                    Some(value) => {
+                        items.push(value);
+                        nulls.append(true);
+                    }
+                }
+                offsets.push(items.len() as i64);
+            }
+        }
+    }
+
+    /// Builds the column of the values appended, decoding the items of
+    /// lists
+    fn finish(self) -> ArrowResult<ArrayRef> {
+        match self.values {
+            LargeValues::Utf8(mut builder) => Ok(Arc::new(builder.finish())),
+            LargeValues::List {
+                item,
+                offsets,
+                mut nulls,
+                items,
+            } => {
+                let decoder = Decoder::new(
+                    Arc::new(Schema::new(vec![item.clone()])),
+                    DecoderOptions::new().with_batch_size(items.len().max(1)),
+                );
+                let mut rows = items.into_iter().map(|value| {
+                    let mut row = Map::new();
+                    row.insert(item.name().clone(), value);
+                    Ok(Value::Object(row))
+                });
+                let items = match decoder.next_batch(&mut rows)? {
+                    Some(batch) => batch.column(0).clone(),
+                    None => new_empty_array(item.data_type()),
+                };
+                let data = ArrayData::builder(self.data_type)
+                    .len(offsets.len() - 1)
+                    .add_buffer(Buffer::from_slice_ref(&offsets))
+                    .add_child_data(items.data().clone())
+                    .null_bit_buffer(Some(nulls.finish()))
+                    .build()?;
+                Ok(make_array(data))
+            }
+        }
+    }
+}
+
+/// The values of the rows that are decoded, whose [`LargeColumn`] values
+/// are taken out as they are read, so that the decoder only sees nulls
+struct LargeColumnValues<'a, I> {
+    values: &'a mut I,
+    columns: &'a mut [LargeColumn],
+}
+
+impl<'a, I: Iterator<Item = ArrowResult<Value>>> Iterator for LargeColumnValues<'a, I> {
+    type Item = ArrowResult<Value>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut value = self.values.next()?;
+        if let Ok(value) = &mut value {
+            let mut object = value.as_object_mut();
+            for column in self.columns.iter_mut() {
+                column.push(
+                    object
+                        .as_mut()
+                        .and_then(|object| object.remove(&column.name)),
+                );
+            }
+        }
+        Some(value)
+    }
+}
+
+/// Returns the names of the fields of `projection` that are in `schema`, in
+/// the order of `schema`, and the indices of the columns projected in that
+/// order that produce them in the order of `projection`, unless it is the
+/// same
+fn schema_ordered_projection(
+    schema: &Schema,
+    projection: Vec<String>,
+) -> (Vec<String>, Option<Vec<usize>>) {
+    let indices: Vec<_> = projection
+        .iter()
+        .filter_map(|name| schema.index_of(name).ok())
+        .collect();
+    let mut ordered = indices.clone();
+    ordered.sort_unstable();
+    ordered.dedup();
+
+    let names = ordered
+        .iter()
+        .map(|index| schema.field(*index).name().clone())
+        .collect();
+    let order = (indices != ordered).then(|| {
+        indices
+            .iter()
+            .map(|index| ordered.binary_search(index).unwrap())
+            .collect()
+    });
+    (names, order)
+}
+
+impl<R: BufRead> Iterator for JsonBatchReader<R> {
+    type Item = ArrowResult<RecordBatch>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.shuffle.is_none() {
+            return self.next_decoded();
+        }
+
+        loop {
+            if let Some(batch) =
+                self.shuffle.as_mut().and_then(|s| s.shuffled.pop_front())
+            {
+                return Some(Ok(batch));
+            }
+
+            // decode the next window, at least `window_rows` rows unless the
+            // input ends
+            let window_rows = self.shuffle.as_ref()?.window_rows;
+            let mut window = vec![];
+            let mut rows = 0;
+            while rows < window_rows {
+                match self.next_decoded() {
+                    Some(Ok(batch)) => {
+                        rows += batch.num_rows();
+                        window.push(batch);
+                    }
+                    Some(Err(e)) => return Some(Err(e)),
+                    None => break,
+                }
+            }
+            if window.is_empty() {
+                return None;
+            }
+            if let Err(e) = self.shuffle.as_mut()?.shuffle(window, rows) {
+                return Some(Err(e));
+            }
+        }
+    }
+}
+
+/// Shuffles the rows of consecutive windows of decoded batches, see
+/// [`JsonScanOptions::shuffle_window_rows`]
+struct ShuffleWindow {
+    window_rows: usize,
+    batch_size: usize,
+    rng: StdRng,
+    /// The batches of the shuffled rows of the last window not yet returned
+    shuffled: VecDeque<RecordBatch>,
+}
+
+impl ShuffleWindow {
+    fn new(window_rows: usize, seed: u64, batch_size: usize) -> Self {
+        Self {
+            window_rows: window_rows.max(1),
+            batch_size: batch_size.max(1),
+            rng: StdRng::seed_from_u64(seed),
+            shuffled: VecDeque::new(),
+        }
+    }
+
+    /// Shuffles the `rows` rows of `window`, to be returned in batches of
+    /// up to `batch_size` rows
+    fn shuffle(&mut self, window: Vec<RecordBatch>, rows: usize) -> ArrowResult<()> {
+        let schema = window[0].schema();
+        let batch = concat_batches(&schema, &window, rows)?;
+
+        let mut indices: Vec<u32> = (0..rows as u32).collect();
+        indices.shuffle(&mut self.rng);
+        let indices = UInt32Array::from(indices);
+        let columns = batch
+            .columns()
+            .iter()
+            .map(|column| take(column.as_ref(), &indices, None))
+            .collect::<ArrowResult<Vec<_>>>()?;
+        let options = RecordBatchOptions::new().with_row_count(Some(rows));
+        let batch = RecordBatch::try_new_with_options(schema, columns, &options)?;
+
+        let mut offset = 0;
+        while offset < rows {
+            let len = self.batch_size.min(rows - offset);
+            self.shuffled.push_back(batch.slice(offset, len));
+            offset += len;
+        }
+        Ok(())
+    }
+}
+
+/// A reader of newline delimited JSON that only counts its records, without
+/// parsing them, producing batches of up to `batch_size` rows but no columns.
+/// As when decoding, lines of only whitespace and comment lines are not
+/// records.
+pub(crate) struct JsonRecordCounter<R: BufRead> {
+    reader: R,
+    batch_size: usize,
+    schema: SchemaRef,
+    /// See [`JsonScanOptions::comment_prefix`], empty if not set
+    comment_prefix: Vec<u8>,
+    /// What has been seen since the last newline
+    line: LineState,
+}
+
+impl<R: BufRead> JsonRecordCounter<R> {
+    /// Create a counter of the records of `reader`, skipping the lines
+    /// starting with `comment_prefix` if set
+    pub(crate) fn new(
+        reader: R,
+        batch_size: usize,
+        comment_prefix: Option<&str>,
+    ) -> Self {
+        Self {
+            reader,
+            batch_size,
+            schema: Arc::new(Schema::empty()),
+            comment_prefix: comment_prefix.unwrap_or_default().as_bytes().to_vec(),
+            line: LineState::Blank,
+        }
+    }
+}
+
+/// What a [`JsonRecordCounter`] has seen of the line being read
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LineState {
+    /// Only whitespace
+    Blank,
+    /// Whitespace followed by this many bytes of the comment prefix
+    Prefix(usize),
+    /// A record
+    Record,
+    /// A comment
+    Comment,
+}
+
+impl LineState {
+    /// Whether the line is a record if it ends here
+    fn is_record(&self) -> bool {
+        matches!(self, Self::Record | Self::Prefix(_))
+    }
+
+    /// The state of the line once `byte`, which is not a newline, is read
+    fn advance(self, byte: u8, comment_prefix: &[u8]) -> Self {
+        let matched = match self {
+            Self::Blank if byte.is_ascii_whitespace() => return self,
+            Self::Blank => 0,
+            Self::Prefix(matched) => matched,
+            Self::Record | Self::Comment => return self,
+        };
+        if comment_prefix.get(matched) != Some(&byte) {
+            Self::Record
+        } else if matched + 1 == comment_prefix.len() {
+            Self::Comment
+        } else {
+            Self::Prefix(matched + 1)
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for JsonRecordCounter<R> {
+    type Item = ArrowResult<RecordBatch>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut rows = 0;
+        while rows < self.batch_size {
+            let buf = match self.reader.fill_buf() {
+                Ok(buf) => buf,
+                Err(e) => {
+                    return Some(Err(ArrowError::JsonError(format!(
+                        "Failed to read JSON record: {}",
+                        e
+                    ))))
+                }
+            };
+            if buf.is_empty() {
+                // the last record may not be terminated by a newline
+                if std::mem::replace(&mut self.line, LineState::Blank).is_record() {
+                    rows += 1;
+                }
+                break;
+            }
+
+            let mut consumed = buf.len();
+            for (idx, byte) in buf.iter().enumerate() {
+                if *byte == b'\n' {
+                    if std::mem::replace(&mut self.line, LineState::Blank).is_record() {
+                        rows += 1;
+                        if rows == self.batch_size {
+                            consumed = idx + 1;
+                            break;
+                        }
+                    }
+                } else {
+                    self.line = self.line.advance(*byte, &self.comment_prefix);
+                }
+            }
+            self.reader.consume(consumed);
+        }
+
+        (rows > 0).then(|| {
+            let options = RecordBatchOptions::new().with_row_count(Some(rows));
+            RecordBatch::try_new_with_options(self.schema.clone(), vec![], &options)
+        })
+    }
+}
+
+/// The default maximum number of errors a [`JsonValidation`] records
+pub const DEFAULT_MAX_VALIDATION_ERRORS: usize = 100;
+
+/// An invalid record found by a scan validating its files, see
+/// [`NdJsonExec::validate`](super::NdJsonExec::validate)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonValidationError {
+    /// The path of the file
+    pub location: String,
+    /// The 1-based number of the line of the record in the file, `None` for
+    /// an error of the whole file, such as the key of a field that is not
+    /// nullable being absent from all its records
+    pub line: Option<usize>,
+    /// Why the record is invalid, as the error a scan would fail with
+    pub message: String,
+}
+
+/// The outcome of validating newline delimited JSON files, see
+/// [`NdJsonExec::validate`](super::NdJsonExec::validate)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct JsonValidationSummary {
+    /// The number of records read, blank and comment lines not being
+    /// records
+    pub rows: usize,
+    /// The number of records that are invalid
+    pub invalid_rows: usize,
+    /// The first errors found, up to the maximum of the validation, in the
+    /// order of their lines within each file
+    pub errors: Vec<JsonValidationError>,
+}
+
+impl JsonValidationSummary {
+    /// Returns true if every record is valid
+    pub fn is_valid(&self) -> bool {
+        self.invalid_rows == 0 && self.errors.is_empty()
+    }
+}
+
+/// The invalid records found by a scan validating its files, see
+/// [`NdJsonExec::validate`](super::NdJsonExec::validate)
+#[derive(Debug)]
+pub struct JsonValidation {
+    max_errors: usize,
+    summary: Mutex<JsonValidationSummary>,
+}
+
+impl Default for JsonValidation {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_VALIDATION_ERRORS)
+    }
+}
+
+impl JsonValidation {
+    /// Create a validation recording up to `max_errors` errors, any further
+    /// invalid record only being counted
+    pub fn new(max_errors: usize) -> Self {
+        Self {
+            max_errors,
+            summary: Mutex::new(JsonValidationSummary::default()),
+        }
+    }
+
+    /// The outcome of the validation so far
+    pub fn summary(&self) -> JsonValidationSummary {
+        self.summary.lock().clone()
+    }
+
+    fn add_valid_rows(&self, rows: usize) {
+        self.summary.lock().rows += rows;
+    }
+
+    fn add_error(&self, location: &str, line: Option<usize>, error: ArrowError) {
+        let mut summary = self.summary.lock();
+        if line.is_some() {
+            summary.rows += 1;
+            summary.invalid_rows += 1;
+        }
+        if summary.errors.len() < self.max_errors {
+            summary.errors.push(JsonValidationError {
+                location: location.to_string(),
+                line,
+                message: error.to_string(),
+            });
+        }
+    }
+}
+
+/// A reader of newline delimited JSON that only validates its records,
+/// decoding them one at a time with a [`JsonBatchReader`] so that a record
+/// is invalid exactly if it would fail a scan. Invalid records are recorded
+/// in [`JsonScanOptions::validation`] rather than failing, and batches of up
+/// to `batch_size` valid rows but no columns are produced, as by
+/// [`JsonRecordCounter`].
+pub(crate) struct JsonRecordValidator<R: BufRead> {
+    reader: JsonBatchReader<R>,
+    validation: Arc<JsonValidation>,
+    batch_size: usize,
+    schema: SchemaRef,
+    /// The path of the file read
+    location: String,
+}
+
+impl<R: BufRead> JsonRecordValidator<R> {
+    /// Create a validator of the records of `reader` against the file schema
+    /// `schema`, which are the lines of the file at `location`
+    pub(crate) fn try_new(
+        reader: R,
+        schema: SchemaRef,
+        batch_size: usize,
+        options: &JsonScanOptions,
+        location: &Path,
+    ) -> ArrowResult<Self> {
+        let validation = options.validation.clone().ok_or_else(|| {
+            ArrowError::JsonError("JSON validation is not enabled".to_string())
+        })?;
+        // every invalid record is reported rather than skipped
+        let options = JsonScanOptions {
+            skip_invalid_rows: false,
+            max_error_count: None,
+            max_error_fraction: None,
+            shuffle_window_rows: None,
+            ..options.clone()
+        };
+        Ok(Self {
+            reader: JsonBatchReader::new(reader, schema, 1, None, Arc::new(options)),
+            validation,
+            batch_size,
+            schema: Arc::new(Schema::empty()),
+            location: location.to_string(),
+        })
+    }
+}
+
+impl<R: BufRead> Iterator for JsonRecordValidator<R> {
+    type Item = ArrowResult<RecordBatch>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut rows = 0;
+        while rows < self.batch_size {
+            let line_number = self.reader.values.line_number();
+            match self.reader.next() {
+                Some(Ok(batch)) => rows += batch.num_rows(),
+                Some(Err(e)) => {
+                    let line = match self.reader.values.line_number() {
+                        // nothing was read, the input failing or the scan
+                        // being past its deadline
+                        line if line == line_number && !self.reader.values.ended => {
+                            return Some(Err(e))
+                        }
+                        // an error of the whole input, once it ended
+                        line if line == line_number => None,
+                        line => Some(line),
+                    };
+                    self.validation.add_error(&self.location, line, e);
+                }
+                None => break,
+            }
+        }
+        self.validation.add_valid_rows(rows);
+
+        (rows > 0).then(|| {
+            let options = RecordBatchOptions::new().with_row_count(Some(rows));
+            RecordBatch::try_new_with_options(self.schema.clone(), vec![], &options)
+        })
+    }
+}
+
+/// Parses a timestamp string into its value in `unit` since the epoch,
+/// assuming it is in `timezone` if it has no offset. Returns `None` if `s`
+/// is not a valid timestamp, does not exist in `timezone` or overflows.
+fn parse_timestamp(s: &str, unit: &TimeUnit, timezone: &Tz) -> Option<i64> {
+    const NAIVE_FORMATS: [&str; 2] = ["%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%d %H:%M:%S%.f"];
+
+    let unit_nanos = match unit {
+        TimeUnit::Second => NANOS_PER_SECOND,
+        TimeUnit::Millisecond => 1_000_000,
+        TimeUnit::Microsecond => 1_000,
+        TimeUnit::Nanosecond => 1,
+    };
+    let naive = NAIVE_FORMATS
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(s, format).ok())
+        .or_else(|| {
+            NaiveDate::parse_from_str(s, "%Y-%m-%d")
+                .ok()
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+        });
+
+    match naive {
+        Some(naive) => {
+            // the earlier of the two instants an ambiguous local time maps to
+            let utc = timezone.from_local_datetime(&naive).earliest()?.naive_utc();
+            utc.timestamp()
+                .checked_mul(NANOS_PER_SECOND / unit_nanos)?
+                .checked_add(utc.timestamp_subsec_nanos() as i64 / unit_nanos)
+        }
+        None => string_to_timestamp_nanos(s)
+            .ok()
+            .map(|nanos| nanos.div_euclid(unit_nanos)),
+    }
+}
+
+fn is_duration_type(data_type: &DataType) -> bool {
+    matches!(data_type, DataType::Duration(_) | DataType::Interval(_))
+}
+
+/// The components of an ISO-8601 duration. Years and weeks are folded into
+/// months and days respectively, and hours and minutes into nanoseconds.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct IsoDuration {
+    pub months: i64,
+    pub days: i64,
+    pub nanos: i64,
+}
+
+const NANOS_PER_SECOND: i64 = 1_000_000_000;
+const NANOS_PER_DAY: i64 = 86_400 * NANOS_PER_SECOND;
+
+/// Parses an ISO-8601 duration such as `P1Y2M3DT4H5M6.7S`, `PT1H30M` or
+/// `P2W`, optionally preceded by `-`. Only the seconds may be fractional.
+/// Returns `None` if `s` is not a valid duration or overflows.
+pub(crate) fn parse_iso8601_duration(s: &str) -> Option<IsoDuration> {
+    let (negative, s) = match s.strip_prefix('-') {
+        Some(s) => (true, s),
+        None => (false, s),
+    };
+    let s = s.strip_prefix('P')?;
+    let (date, time) = match s.split_once('T') {
+        Some((date, time)) => (date, Some(time)),
+        None => (s, None),
+    };
+
+    let date = split_duration_components(date, &['Y', 'M', 'W', 'D'])?;
+    let time = match time {
+        Some(time) => {
+            let time = split_duration_components(time, &['H', 'M', 'S'])?;
+            if time.is_empty() {
+                return None;
+            }
+            time
+        }
+        None => vec![],
+    };
+    if date.is_empty() && time.is_empty() {
+        return None;
+    }
+
+    let mut duration = IsoDuration::default();
+    for (value, designator) in date {
+        let value = value.parse::<i64>().ok()?;
+        match designator {
+            'Y' => {
+                duration.months = duration.months.checked_add(value.checked_mul(12)?)?
+            }
+            'M' => duration.months = duration.months.checked_add(value)?,
+            'W' => duration.days = duration.days.checked_add(value.checked_mul(7)?)?,
+            _ => duration.days = duration.days.checked_add(value)?,
+        }
+    }
+    for (value, designator) in time {
+        let nanos = match designator {
+            'H' => value
+                .parse::<i64>()
+                .ok()?
+                .checked_mul(3_600 * NANOS_PER_SECOND)?,
+            'M' => value
+                .parse::<i64>()
+                .ok()?
+                .checked_mul(60 * NANOS_PER_SECOND)?,
+            _ => parse_seconds(value)?,
+        };
+        duration.nanos = duration.nanos.checked_add(nanos)?;
+    }
+
+    if negative {
+        duration.months = duration.months.checked_neg()?;
+        duration.days = duration.days.checked_neg()?;
+        duration.nanos = duration.nanos.checked_neg()?;
+    }
+    Some(duration)
+}
+
+/// Splits `s` into its numbers and their designators, which must appear in
+/// the order given by `designators`, each at most once
+fn split_duration_components<'a>(
+    mut s: &'a str,
+    designators: &[char],
+) -> Option<Vec<(&'a str, char)>> {
+    let mut components = vec![];
+    let mut next = 0;
+    while !s.is_empty() {
+        let end = s.find(|c: char| !(c.is_ascii_digit() || c == '.'))?;
+        if end == 0 {
+            return None;
+        }
+        let designator = s[end..].chars().next()?;
+        next += designators[next..].iter().position(|d| *d == designator)? + 1;
+        components.push((&s[..end], designator));
+        s = &s[end + designator.len_utf8()..];
+    }
+    Some(components)
+}
+
+/// Parses seconds with an optional fraction of up to 9 digits as nanoseconds
+fn parse_seconds(s: &str) -> Option<i64> {
+    let (whole, fraction) = s.split_once('.').unwrap_or((s, ""));
+    if whole.is_empty()
+        || fraction.len() > 9
+        || !fraction.bytes().all(|b| b.is_ascii_digit())
+    {
+        return None;
+    }
+    let fraction = format!("{:0<9}", fraction).parse::<i64>().ok()?;
+    whole
+        .parse::<i64>()
+        .ok()?
+        .checked_mul(NANOS_PER_SECOND)?
+        .checked_add(fraction)
+}
+
+/// Parses the ISO-8601 duration strings of the column `name` into an array
+/// of `data_type`
+fn parse_duration_array(
+    name: &str,
+    array: &ArrayRef,
+    data_type: &DataType,
+) -> ArrowResult<ArrayRef> {
+    let strings = array
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .ok_or_else(|| {
+            ArrowError::JsonError(format!(
+                "Expected the durations of column '{}' to be decoded as strings",
+                name
+            ))
+        })?;
+
+    let convert = |s: &str| -> ArrowResult<IsoDuration> {
+        parse_iso8601_duration(s).ok_or_else(|| {
+            ArrowError::JsonError(format!(
+                "Invalid ISO-8601 duration '{}' in column '{}'",
+                s, name
+            ))
+        })
+    };
+    let unrepresentable = |s: &str| {
+        ArrowError::JsonError(format!(
+            "ISO-8601 duration '{}' in column '{}' can not be represented as {}",
+            s, name, data_type
+        ))
+    };
+
+    let array: ArrayRef = match data_type {
+        DataType::Duration(unit) => {
+            let unit_nanos = match unit {
+                TimeUnit::Second => NANOS_PER_SECOND,
+                TimeUnit::Millisecond => 1_000_000,
+                TimeUnit::Microsecond => 1_000,
+                TimeUnit::Nanosecond => 1,
+            };
+            let values = parse_strings(strings, |s| {
+                let d = convert(s)?;
+                d.days
+                    .checked_mul(NANOS_PER_DAY)
+                    .and_then(|nanos| nanos.checked_add(d.nanos))
+                    .filter(|nanos| d.months == 0 && nanos % unit_nanos == 0)
+                    .map(|nanos| nanos / unit_nanos)
+                    .ok_or_else(|| unrepresentable(s))
+            })?;
+            match unit {
+                TimeUnit::Second => Arc::new(DurationSecondArray::from(values)),
+                TimeUnit::Millisecond => Arc::new(DurationMillisecondArray::from(values)),
+                TimeUnit::Microsecond => Arc::new(DurationMicrosecondArray::from(values)),
+                TimeUnit::Nanosecond => Arc::new(DurationNanosecondArray::from(values)),
+            }
+        }
+        DataType::Interval(IntervalUnit::YearMonth) => {
+            let values = parse_strings(strings, |s| {
+                let d = convert(s)?;
+                i32::try_from(d.months)
+                    .ok()
+                    .filter(|_| d.days == 0 && d.nanos == 0)
+                    .ok_or_else(|| unrepresentable(s))
+            })?;
+            Arc::new(IntervalYearMonthArray::from(values))
+        }
+        DataType::Interval(IntervalUnit::DayTime) => {
+            let values = parse_strings(strings, |s| {
+                let d = convert(s)?;
+                let days = i32::try_from(d.days).ok();
+                let millis = i32::try_from(d.nanos / 1_000_000).ok();
+                match (days, millis) {
+                    (Some(days), Some(millis))
+                        if d.months == 0 && d.nanos % 1_000_000 == 0 =>
+                    {
+                        Ok(IntervalDayTimeType::make_value(days, millis))
+                    }
+                    _ => Err(unrepresentable(s)),
+                }
+            })?;
+            Arc::new(IntervalDayTimeArray::from(values))
+        }
+        DataType::Interval(IntervalUnit::MonthDayNano) => {
+            let values = parse_strings(strings, |s| {
+                let d = convert(s)?;
+                match (i32::try_from(d.months), i32::try_from(d.days)) {
+                    (Ok(months), Ok(days)) => {
+                        Ok(IntervalMonthDayNanoType::make_value(months, days, d.nanos))
+                    }
+                    _ => Err(unrepresentable(s)),
+                }
+            })?;
+            Arc::new(IntervalMonthDayNanoArray::from(values))
+        }
+        other => {
+            return Err(ArrowError::JsonError(format!(
+                "Can not parse ISO-8601 durations as {}",
+                other
+            )))
+        }
+    };
+    Ok(array)
+}
+
+/// Applies `f` to every non null value of `strings`
+fn parse_strings<T>(
+    strings: &StringArray,
+    f: impl Fn(&str) -> ArrowResult<T>,
+) -> ArrowResult<Vec<Option<T>>> {
+    strings.iter().map(|s| s.map(&f).transpose()).collect()
+}
+
+/// The kind of values observed for a top level field by [`DurationDetector`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DetectedKind {
+    /// Only ISO-8601 durations without a year or month component
+    Duration,
+    /// Only ISO-8601 durations, some with a year or month component
+    Interval,
+    /// At least one value that is not an ISO-8601 duration
+    Other,
+}
+
+/// Detects the top level fields of JSON objects whose values are all
+/// ISO-8601 durations, see [`JsonScanOptions::infer_durations`]
+#[derive(Debug, Default)]
+pub(crate) struct DurationDetector {
+    fields: HashMap<String, DetectedKind>,
+}
+
+impl DurationDetector {
+    /// Record the values of the top level fields of `value`
+    pub(crate) fn observe(&mut self, value: &Value) {
+        let map = match value {
+            Value::Object(map) => map,
+            _ => return,
+        };
+        for (key, value) in map {
+            let kind = match value {
+                Value::Null => continue,
+                Value::String(s) => match parse_iso8601_duration(s) {
+                    Some(d) if d.months != 0 => DetectedKind::Interval,
+                    Some(_) => DetectedKind::Duration,
+                    None => DetectedKind::Other,
+                },
+                _ => DetectedKind::Other,
+            };
+            let detected = self.fields.entry(key.clone()).or_insert(kind);
+            *detected = match (*detected, kind) {
+                (DetectedKind::Other, _) | (_, DetectedKind::Other) => {
+                    DetectedKind::Other
+                }
+                (DetectedKind::Interval, _) | (_, DetectedKind::Interval) => {
+                    DetectedKind::Interval
+                }
+                _ => DetectedKind::Duration,
+            };
+        }
+    }
+
+    /// Replace the type of the string fields of `schema` detected to only
+    /// hold durations
+    pub(crate) fn apply(&self, schema: Schema) -> Schema {
+        let fields = schema
+            .fields()
+            .iter()
+            .map(|field| {
+                let data_type = match self.fields.get(field.name()) {
+                    Some(DetectedKind::Duration)
+                        if field.data_type() == &DataType::Utf8 =>
+                    {
+                        DataType::Duration(TimeUnit::Nanosecond)
+                    }
+                    Some(DetectedKind::Interval)
+                        if field.data_type() == &DataType::Utf8 =>
+                    {
+                        DataType::Interval(IntervalUnit::MonthDayNano)
+                    }
+                    _ => return field.clone(),
+                };
+                Field::new(field.name(), data_type, field.is_nullable())
+            })
+            .collect();
+        Schema::new_with_metadata(fields, schema.metadata().clone())
+    }
+}
+
+/// Sums the lengths of the strings and lists of the top level fields of JSON
+/// objects, to read the fields whose total exceeds
+/// [`JsonScanOptions::large_offsets_threshold`] with 64 bit offsets
+#[derive(Debug)]
+pub(crate) struct LargeOffsetDetector {
+    threshold: usize,
+    lengths: HashMap<String, usize>,
+}
+
+impl LargeOffsetDetector {
+    pub(crate) fn new(threshold: usize) -> Self {
+        Self {
+            threshold,
+            lengths: HashMap::new(),
+        }
+    }
+
+    /// Record the lengths of the top level fields of `value`, in bytes for
+    /// strings and in elements for lists
+    pub(crate) fn observe(&mut self, value: &Value) {
+        let map = match value {
+            Value::Object(map) => map,
+            _ => return,
+        };
+        for (key, value) in map {
+            let len = match value {
+                Value::String(s) => s.len(),
+                Value::Array(values) => values.len(),
+                _ => continue,
+            };
+            let total = self.lengths.entry(key.clone()).or_default();
+            *total = total.saturating_add(len);
+        }
+    }
+
+    /// Replace the `Utf8` and `List` fields of `schema` whose total length
+    /// exceeds the threshold with `LargeUtf8` and `LargeList` fields
+    pub(crate) fn apply(&self, schema: Schema) -> Schema {
+        let fields = schema
+            .fields()
+            .iter()
+            .map(|field| {
+                let exceeded = self
+                    .lengths
+                    .get(field.name())
+                    .map_or(false, |len| *len > self.threshold);
+                match large_offset_type(field.data_type()) {
+                    Some(data_type) if exceeded => {
+                        Field::new(field.name(), data_type, field.is_nullable())
+                    }
+                    _ => field.clone(),
+                }
+            })
+            .collect();
+        Schema::new_with_metadata(fields, schema.metadata().clone())
+    }
+}
+
+/// Records the lengths of the lists of the top level fields of JSON objects,
+/// to read the fields whose lists all have the same length as
+/// `FixedSizeList`, see [`JsonScanOptions::infer_fixed_size_lists`]
+#[derive(Debug, Default)]
+pub(crate) struct FixedSizeListDetector {
+    /// The length of the lists of every field, `None` if they differ or the
+    /// field holds values other than lists
+    lengths: HashMap<String, Option<usize>>,
+}
+
+impl FixedSizeListDetector {
+    /// Record the lengths of the lists of the top level fields of `value`
+    pub(crate) fn observe(&mut self, value: &Value) {
+        let map = match value {
+            Value::Object(map) => map,
+            _ => return,
+        };
+        for (key, value) in map {
+            let len = match value {
+                Value::Null => continue,
+                Value::Array(values) => Some(values.len()),
+                _ => None,
+            };
+            let detected = self.lengths.entry(key.clone()).or_insert(len);
+            if *detected != len {
+                *detected = None;
+            }
+        }
+    }
+
+    /// Replace the `List` fields of `schema` whose lists all have the same
+    /// non zero length with `FixedSizeList` fields
+    pub(crate) fn apply(&self, schema: Schema) -> Schema {
+        let fields = schema
+            .fields()
+            .iter()
+            .map(|field| {
+                let len = self
+                    .lengths
+                    .get(field.name())
+                    .copied()
+                    .flatten()
+                    .filter(|len| *len > 0)
+                    .and_then(|len| i32::try_from(len).ok());
+                match (field.data_type(), len) {
+                    (DataType::List(item), Some(len)) => Field::new(
+                        field.name(),
+                        DataType::FixedSizeList(item.clone(), len),
+                        field.is_nullable(),
+                    ),
+                    _ => field.clone(),
+                }
+            })
+            .collect();
+        Schema::new_with_metadata(fields, schema.metadata().clone())
+    }
+}
+
+/// Records the types of the values of the top level fields of JSON objects,
+/// to report the fields that schema inference reads as `Utf8` because their
+/// values have conflicting types, such as numbers and strings
+#[derive(Debug, Default)]
+pub(crate) struct TypeConflictDetector {
+    fields: HashMap<String, BTreeSet<&'static str>>,
+}
+
+impl TypeConflictDetector {
+    /// Record the types of the values of the top level fields of `value`
+    pub(crate) fn observe(&mut self, value: &Value) {
+        let map = match value {
+            Value::Object(map) => map,
+            _ => return,
+        };
+        for (key, value) in map {
+            let observed = match value {
+                Value::Null => continue,
+                Value::Bool(_) => "Boolean",
+                Value::Number(n) if n.is_f64() => "Float64",
+                Value::Number(_) => "Int64",
+                Value::String(_) => "Utf8",
+                Value::Array(_) => "List",
+                Value::Object(_) => "Struct",
+            };
+            self.fields.entry(key.clone()).or_default().insert(observed);
+        }
+    }
+
+    /// Warn about the `Utf8` fields of the inferred `schema` whose values
+    /// were observed to have more than one type
+    pub(crate) fn report(&self, schema: &Schema, options: &JsonScanOptions) {
+        for field in schema.fields() {
+            if field.data_type() != &DataType::Utf8 {
+                continue;
+            }
+            match self.fields.get(field.name()) {
+                Some(observed) if observed.len() > 1 => options.warn(format!(
+                    "JSON schema inference read column '{}' as Utf8 because its values have conflicting types: {}",
+                    field.name(),
+                    observed.iter().copied().collect::<Vec<_>>().join(", ")
+                )),
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Records the top level fields of JSON objects that were only observed
+/// with `null` values, whose type schema inference can not resolve yet
+#[derive(Debug, Default)]
+pub(crate) struct NullFieldDetector {
+    /// Fields observed with a value other than `null`
+    typed: HashSet<String>,
+    /// Fields only observed with `null` values so far
+    unresolved: HashSet<String>,
+}
+
+impl NullFieldDetector {
+    /// Record whether the top level fields of `value` are `null`
+    pub(crate) fn observe(&mut self, value: &Value) {
+        let map = match value {
+            Value::Object(map) => map,
+            _ => return,
+        };
+        for (key, value) in map {
+            if value.is_null() {
+                if !self.typed.contains(key) {
+                    self.unresolved.insert(key.clone());
+                }
+            } else if self.typed.insert(key.clone()) {
+                self.unresolved.remove(key);
+            }
+        }
+    }
+
+    /// Returns true if every field observed so far had a value other than
+    /// `null`
+    pub(crate) fn is_resolved(&self) -> bool {
+        self.unresolved.is_empty()
+    }
+
+    /// Appends the fields only observed with `null` values to `schema`, as
+    /// nullable `Utf8` fields, in the order of their names
+    pub(crate) fn apply(&self, schema: Schema) -> Schema {
+        let unresolved: BTreeSet<_> = self
+            .unresolved
+            .iter()
+            .filter(|name| schema.field_with_name(name).is_err())
+            .collect();
+        if unresolved.is_empty() {
+            return schema;
+        }
+
+        let mut fields = schema.fields().clone();
+        fields.extend(
+            unresolved
+                .into_iter()
+                .map(|name| Field::new(name, DataType::Utf8, true)),
+        );
+        Schema::new_with_metadata(fields, schema.metadata().clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{BooleanArray, Int64Array, LargeListArray, LargeStringArray};
+    use std::io::Cursor;
+
+    const DATA: &str = r#"{"a": 1, "b": "x", "c": [1, 2, 3]}
+
+{"a": 2, "b": "y", "c": [4], "d": {"e": true}}
+"#;
+
+    #[test]
+    fn value_iter_drops_unprojected_keys() {
+        let projection = vec!["a".to_string(), "d".to_string()];
+        let values: Vec<_> =
+            JsonValueIter::new(Cursor::new(DATA), Arc::new(JsonScanOptions::new()), None)
+                .with_projection(Some(&projection))
+                .collect::<ArrowResult<_>>()
+                .unwrap();
+
+        assert_eq!(
+            values,
+            vec![
+                serde_json::json!({"a": 1}),
+                serde_json::json!({"a": 2, "d": {"e": true}}),
+            ]
+        );
+    }
+
+    #[test]
+    fn batch_reader_only_builds_projected_arrays() {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int64, true),
+            Field::new("b", DataType::Utf8, true),
+            Field::new(
+                "c",
+                DataType::List(Box::new(Field::new("item", DataType::Int64, true))),
+                true,
+            ),
+        ]));
+
+        let mut reader = JsonBatchReader::new(
+            Cursor::new(DATA),
+            schema,
+            1024,
+            Some(vec!["b".to_string()]),
+            Arc::new(JsonScanOptions::new()),
+        );
+
+        let batch = reader.next().unwrap().unwrap();
+        assert!(reader.next().is_none());
+        assert_eq!(batch.num_rows(), 2);
+        assert_eq!(batch.num_columns(), 1);
+        assert_eq!(batch.schema().field(0).name(), "b");
+    }
+
+    #[test]
+    fn batch_reader_schema_order() {
+        // the fields are listed in another order than the keys of DATA
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("b", DataType::Utf8, true),
+            Field::new("e", DataType::Boolean, true),
+            Field::new("a", DataType::Int64, false),
+        ]));
+
+        let mut reader = JsonBatchReader::new(
+            Cursor::new(DATA),
+            schema.clone(),
+            1024,
+            None,
+            Arc::new(JsonScanOptions::new()),
+        );
+        let batch = reader.next().unwrap().unwrap();
+        assert!(reader.next().is_none());
+        assert_eq!(batch.schema(), schema);
+        let b = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        assert_eq!(b.iter().collect::<Vec<_>>(), vec![Some("x"), Some("y")]);
+        // the nullable field absent from all rows is read as nulls
+        assert_eq!(batch.column(1).null_count(), 2);
+        let a = batch
+            .column(2)
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .unwrap();
+        assert_eq!(a.values(), &[1, 2]);
+
+        // the projected columns are produced in the order of the projection
+        let mut reader = JsonBatchReader::new(
+            Cursor::new(DATA),
+            schema.clone(),
+            1024,
+            Some(vec!["a".to_string(), "b".to_string()]),
+            Arc::new(JsonScanOptions::new()),
+        );
+        let batch = reader.next().unwrap().unwrap();
+        assert_eq!(batch.schema().field(0).name(), "a");
+        assert_eq!(batch.schema().field(1).name(), "b");
+        let a = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .unwrap();
+        assert_eq!(a.values(), &[1, 2]);
+        let b = batch
+            .column(1)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        assert_eq!(b.iter().collect::<Vec<_>>(), vec![Some("x"), Some("y")]);
+
+        // a field absent from all rows can not be read if it is not nullable
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int64, false),
+            Field::new("e", DataType::Boolean, false),
+        ]));
+        let mut reader = JsonBatchReader::new(
+            Cursor::new(DATA),
+            schema,
+            1024,
+            None,
+            Arc::new(JsonScanOptions::new()),
+        );
+        let err = reader.next().unwrap().unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Field 'e' is not nullable but has no value in any row"),
+            "{}",
+            err
+        );
+
+        // but it can be absent from all the rows of a batch
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int64, false),
+            Field::new("e", DataType::Boolean, false),
+        ]));
+        let data = "{\"a\": 1}\n{\"a\": 2, \"e\": true}\n";
+        let reader = JsonBatchReader::new(
+            Cursor::new(data),
+            schema,
+            1,
+            None,
+            Arc::new(JsonScanOptions::new()),
+        );
+        let batches = reader.collect::<ArrowResult<Vec<_>>>().unwrap();
+        assert_eq!(batches.len(), 2);
+        assert_eq!(batches[1].column(1).null_count(), 0);
+    }
+
+    #[test]
+    fn batch_reader_large_columns() {
+        let item = Box::new(Field::new("item", DataType::Int64, true));
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("b", DataType::Utf8, true),
+            Field::new("c", DataType::List(item.clone()), true),
+        ]));
+        let options = JsonScanOptions::new()
+            .with_large_columns(vec!["b".to_string(), "c".to_string()]);
+        let schema = options.apply_to_schema(schema).unwrap();
+
+        let mut reader = JsonBatchReader::new(
+            Cursor::new(DATA),
+            schema,
+            1024,
+            None,
+            Arc::new(options),
+        );
+
+        let batch = reader.next().unwrap().unwrap();
+        assert_eq!(batch.schema().field(0).data_type(), &DataType::LargeUtf8);
+        assert_eq!(
+            batch.schema().field(1).data_type(),
+            &DataType::LargeList(item)
+        );
+        let strings = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<LargeStringArray>()
+            .unwrap();
+        assert_eq!(
+            strings.iter().collect::<Vec<_>>(),
+            vec![Some("x"), Some("y")]
+        );
+        let lists = batch
+            .column(1)
+            .as_any()
+            .downcast_ref::<LargeListArray>()
+            .unwrap();
+        assert_eq!(lists.value_offsets(), &[0, 3, 4]);
+        // the values are decoded straight into the 64 bit offset arrays
+        let items = lists.values();
+        let items = items.as_any().downcast_ref::<Int64Array>().unwrap();
+        assert_eq!(items.values(), &[1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn batch_reader_expand_paths() {
+        let payload = DataType::Struct(vec![
+            Field::new("a", DataType::Int64, true),
+            Field::new("b", DataType::Utf8, true),
+        ]);
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int64, true),
+            Field::new("payload", payload, true),
+        ]));
+        let options = JsonScanOptions::new()
+            .with_expand_paths(vec!["payload.a".to_string(), "payload.b".to_string()]);
+        let schema = options.apply_to_schema(schema).unwrap();
+        assert_eq!(schema.field(2).name(), "payload.a");
+        assert_eq!(schema.field(2).data_type(), &DataType::Int64);
+        assert_eq!(schema.field(3).name(), "payload.b");
+        assert_eq!(schema.field(3).data_type(), &DataType::Utf8);
+
+        let data = r#"{"id": 1, "payload": {"a": 10, "b": "x"}}
+{"id": 2, "payload": {"b": "y"}}
+{"id": 3}
+"#;
+        let mut reader = JsonBatchReader::new(
+            Cursor::new(data),
+            schema,
+            1024,
+            Some(vec!["payload.a".to_string(), "payload.b".to_string()]),
+            Arc::new(options),
+        );
+
+        let batch = reader.next().unwrap().unwrap();
+        assert_eq!(batch.num_columns(), 2);
+        let a = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .unwrap();
+        assert_eq!(a.iter().collect::<Vec<_>>(), vec![Some(10), None, None]);
+        let b = batch
+            .column(1)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        assert_eq!(
+            b.iter().collect::<Vec<_>>(),
+            vec![Some("x"), Some("y"), None]
+        );
+    }
+
+    #[test]
+    fn batch_reader_auto_flatten() {
+        let inner = DataType::Struct(vec![Field::new("c", DataType::Boolean, true)]);
+        let payload = DataType::Struct(vec![
+            Field::new("a", DataType::Int64, true),
+            Field::new("b", inner, true),
+        ]);
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int64, true),
+            Field::new("payload", payload, true),
+        ]));
+        let options = JsonScanOptions::new().with_auto_flatten(true);
+        let flattened = options.apply_to_schema(schema.clone()).unwrap();
+        let names = flattened
+            .fields()
+            .iter()
+            .map(|field| field.name().as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["id", "payload.a", "payload.b.c"]);
+        assert_eq!(flattened.field(2).data_type(), &DataType::Boolean);
+
+        let options = options.with_flatten_separator("__");
+        let flattened = options.apply_to_schema(schema).unwrap();
+        assert_eq!(flattened.field(2).name(), "payload__b__c");
+
+        let data = r#"{"id": 1, "payload": {"a": 10, "b": {"c": true}}}
+{"id": 2, "payload": {"b": {}}}
+{"id": 3}
+"#;
+        let mut reader = JsonBatchReader::new(
+            Cursor::new(data),
+            flattened,
+            1024,
+            None,
+            Arc::new(options),
+        );
+
+        let batch = reader.next().unwrap().unwrap();
+        assert_eq!(batch.num_columns(), 3);
+        let a = batch
+            .column(1)
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .unwrap();
+        assert_eq!(a.iter().collect::<Vec<_>>(), vec![Some(10), None, None]);
+        let c = batch
+            .column(2)
+            .as_any()
+            .downcast_ref::<BooleanArray>()
+            .unwrap();
+        assert_eq!(c.iter().collect::<Vec<_>>(), vec![Some(true), None, None]);
+    }
+
+    #[test]
+    fn auto_flatten_conflict() {
+        let payload = DataType::Struct(vec![Field::new("a", DataType::Int64, true)]);
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("payload.a", DataType::Utf8, true),
+            Field::new("payload", payload, true),
+        ]));
+        let options = JsonScanOptions::new().with_auto_flatten(true);
+        let err = options.apply_to_schema(schema).unwrap_err();
+        assert!(err.to_string().contains("'payload.a' conflicts"), "{}", err);
+    }
+
+    #[test]
+    fn max_depth() {
+        // brackets in strings do not count
+        let shallow = r#"{"a": [[1]], "b": "[[[[{{{{"}"#;
+        let deep = format!(r#"{{"a": {}{}}}"#, "[".repeat(10_000), "]".repeat(10_000));
+        let data = format!("{}\n{}\n", shallow, deep);
+        let options = Arc::new(JsonScanOptions::new().with_max_depth(3));
+        let mut values = JsonValueIter::new(Cursor::new(data), options, None);
+
+        assert_eq!(
+            values.next().unwrap().unwrap()["a"],
+            serde_json::json!([[1]])
+        );
+        let err = values.next().unwrap().unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("line 2 is nested deeper than the maximum depth of 3"),
+            "{}",
+            err
+        );
+        assert!(values.next().is_none());
+    }
+
+    #[test]
+    fn batch_reader_shuffle() {
+        let data: String = (0..20).map(|i| format!("{{\"a\": {}}}\n", i)).collect();
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int64, true)]));
+        let read = |seed: u64| -> Vec<i64> {
+            let options = JsonScanOptions::new().with_shuffle(8, seed);
+            let reader = JsonBatchReader::new(
+                Cursor::new(data.clone()),
+                schema.clone(),
+                3,
+                None,
+                Arc::new(options),
+            );
+            reader
+                .flat_map(|batch| {
+                    let batch = batch.unwrap();
+                    assert!(batch.num_rows() <= 3);
+                    batch
+                        .column(0)
+                        .as_any()
+                        .downcast_ref::<arrow::array::Int64Array>()
+                        .unwrap()
+                        .values()
+                        .to_vec()
+                })
+                .collect()
+        };
+
+        let shuffled = read(42);
+        assert_eq!(shuffled, read(42));
+        assert_ne!(shuffled, read(7));
+        assert_ne!(shuffled, (0..20).collect::<Vec<_>>());
+
+        // the batches of 3 rows are shuffled in windows of 9, 9 and 2 rows
+        let mut windows: Vec<Vec<i64>> =
+            shuffled.chunks(9).map(|window| window.to_vec()).collect();
+        for window in &mut windows {
+            window.sort_unstable();
+        }
+        assert_eq!(
+            windows,
+            vec![(0..9).collect(), (9..18).collect(), vec![18, 19]]
+        );
+    }
+
+    #[test]
+    fn batch_reader_deadline() {
+        let data: String = (0..100).map(|i| format!("{{\"a\": {}}}\n", i)).collect();
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int64, true)]));
+        let read = |deadline| {
+            let options = JsonScanOptions::new().with_deadline(deadline);
+            let reader = Cursor::new(data.clone());
+            JsonBatchReader::new(reader, schema.clone(), 10, None, Arc::new(options))
+                .collect::<ArrowResult<Vec<_>>>()
+        };
+
+        // a deadline which has passed fails the scan before any batch
+        let error = read(Instant::now()).unwrap_err();
+        assert!(
+            error.to_string().contains("Query deadline exceeded"),
+            "{}",
+            error
+        );
+
+        let batches =
+            read(Instant::now() + std::time::Duration::from_secs(3600)).unwrap();
+        assert_eq!(batches.iter().map(|b| b.num_rows()).sum::<usize>(), 100);
+    }
+
+    #[test]
+    fn batch_reader_skip_invalid_rows() {
+        // 9 in 10 lines are malformed
+        let data: String = (0..100)
+            .map(|i| match i % 10 {
+                0 => format!("{{\"a\": {}}}\n", i),
+                _ => format!("{{\"a\": {}\n", i),
+            })
+            .collect();
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int64, true)]));
+        let read = |options: JsonScanOptions| {
+            JsonBatchReader::new(
+                Cursor::new(data.clone()),
+                schema.clone(),
+                1024,
+                None,
+                Arc::new(options),
+            )
+            .map(|batch| batch.map(|batch| batch.num_rows()))
+            .sum::<ArrowResult<usize>>()
+        };
+
+        let err = read(JsonScanOptions::new()).unwrap_err();
+        assert!(err.to_string().contains("Not valid JSON"), "{}", err);
+
+        let options = JsonScanOptions::new().with_skip_invalid_rows(true);
+        assert_eq!(read(options.clone()).unwrap(), 10);
+        assert_eq!(
+            read(options.clone().with_max_error_fraction(0.9)).unwrap(),
+            10
+        );
+
+        let err = read(options.clone().with_max_error_fraction(0.5)).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Skipped 90 of 100 JSON lines, a fraction of 0.900 above the maximum of 0.5"),
+            "{}",
+            err
+        );
+
+        let err = read(options.with_max_error_count(20)).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Skipped 21 invalid JSON lines, more than the maximum of 20, the first error being: Json error: Not valid JSON"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn batch_reader_base64_columns() {
+        let data = "{\"a\": \"aGVsbG8=\", \"b\": \"-_8=\"}\n{\"a\": null, \"b\": \"\"}\n{\"a\": \"!!\", \"b\": \"AA==\"}\n";
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Utf8, true),
+            Field::new("b", DataType::Utf8, true),
+        ]));
+        let options = JsonScanOptions::new()
+            .with_base64_column("a", Base64Encoding::Standard)
+            .with_base64_column("b", Base64Encoding::UrlSafe);
+        let schema = options.apply_to_schema(schema).unwrap();
+        assert_eq!(schema.field(0).data_type(), &DataType::Binary);
+        assert_eq!(schema.field(1).data_type(), &DataType::Binary);
+        let read = |options: JsonScanOptions| {
+            JsonBatchReader::new(
+                Cursor::new(data),
+                schema.clone(),
+                1024,
+                None,
+                Arc::new(options),
+            )
+            .collect::<ArrowResult<Vec<_>>>()
+        };
+
+        let err = read(options.clone()).unwrap_err();
+        assert!(
+            err.to_string().contains(
+                "Invalid base64 in column 'a' on line 3: Invalid byte 33, offset 0."
+            ),
+            "{}",
+            err
+        );
+
+        let batches = read(options.clone().with_skip_invalid_rows(true)).unwrap();
+        assert_eq!(batches.len(), 1);
+        let column = |i: usize| {
+            let binaries = batches[0]
+                .column(i)
+                .as_any()
+                .downcast_ref::<BinaryArray>()
+                .unwrap();
+            binaries
+                .iter()
+                .map(|b| b.map(<[u8]>::to_vec))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(column(0), vec![Some(b"hello".to_vec()), None]);
+        assert_eq!(column(1), vec![Some(vec![0xfb, 0xff]), Some(vec![])]);
+
+        // the values of unprojected columns are not decoded
+        let batches = JsonBatchReader::new(
+            Cursor::new(data),
+            schema.clone(),
+            2,
+            Some(vec!["b".to_string()]),
+            Arc::new(options),
+        )
+        .collect::<ArrowResult<Vec<_>>>()
+        .unwrap();
+        let binaries: Vec<_> = batches
+            .iter()
+            .flat_map(|batch| {
+                let binaries = batch
+                    .column(0)
+                    .as_any()
+                    .downcast_ref::<BinaryArray>()
+                    .unwrap();
+                binaries
+                    .iter()
+                    .map(|b| b.map(<[u8]>::to_vec))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        assert_eq!(
+            binaries,
+            vec![Some(vec![0xfb, 0xff]), Some(vec![]), Some(vec![0])]
+        );
+    }
+
+    #[test]
+    fn batch_reader_numeric_locale() {
+        let data = "{\"a\": \"1.234,56\", \"b\": \"-1.000.000,5\"}\n{\"a\": 7.5, \"b\": 12}\n{\"a\": null, \"b\": \"0,25\"}\n{\"a\": \"1,2,3\", \"b\": \"1\"}\n";
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Float64, true),
+            Field::new("b", DataType::Decimal128(10, 2), true),
+        ]));
+        let options =
+            JsonScanOptions::new().with_numeric_locale(NumericLocale::european());
+        let read = |options: JsonScanOptions| {
+            JsonBatchReader::new(
+                Cursor::new(data),
+                schema.clone(),
+                1024,
+                None,
+                Arc::new(options),
+            )
+            .collect::<ArrowResult<Vec<_>>>()
+        };
+
+        let err = read(options.clone()).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Invalid number '1,2,3' in column 'a' on line 4"),
+            "{}",
+            err
+        );
+
+        let batches = read(options.with_skip_invalid_rows(true)).unwrap();
+        assert_eq!(batches.len(), 1);
+        let floats = batches[0]
+            .column(0)
+            .as_any()
+            .downcast_ref::<Float64Array>()
+            .unwrap();
+        assert_eq!(
+            floats.iter().collect::<Vec<_>>(),
+            vec![Some(1234.56), Some(7.5), None]
+        );
+        let decimals = batches[0]
+            .column(1)
+            .as_any()
+            .downcast_ref::<Decimal128Array>()
+            .unwrap();
+        assert_eq!(decimals.data_type(), &DataType::Decimal128(10, 2));
+        assert_eq!(
+            decimals.iter().collect::<Vec<_>>(),
+            vec![Some(-100_000_050), Some(1_200), Some(25)]
+        );
+    }
+
+    #[test]
+    fn record_counter() {
+        // a tiny buffer so that records span several reads
+        let data = format!("{}  \n{}\n{{\"a\": 4}}", DATA, DATA);
+        let reader = std::io::BufReader::with_capacity(3, Cursor::new(data));
+        let rows: Vec<_> = JsonRecordCounter::new(reader, 2, None)
+            .map(|batch| {
+                let batch = batch.unwrap();
+                assert_eq!(batch.num_columns(), 0);
+                batch.num_rows()
+            })
+            .collect();
+        assert_eq!(rows, vec![2, 2, 1]);
+
+        // comments are not records, even if their prefix spans reads, but
+        // lines only starting like the prefix are
+        let data = "// a comment\n{\"a\": 1}\n   //\n/{\"a\": 2}\n\n{\"a\": 3}";
+        let count = |comment_prefix| {
+            let reader = std::io::BufReader::with_capacity(3, Cursor::new(data));
+            JsonRecordCounter::new(reader, 2, comment_prefix)
+                .map(|batch| batch.unwrap().num_rows())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(count(Some("//")), vec![2, 1]);
+        assert_eq!(count(None), vec![2, 2, 1]);
+    }
+
+    #[test]
+    fn transcode_encodings() {
+        let text = "{\"a\": \"café 😀\"}\n";
+        let utf16 = |little_endian: bool| {
+            let mut bytes = if little_endian {
+                vec![0xFF, 0xFE]
+            } else {
+                vec![0xFE, 0xFF]
+            };
+            for unit in text.encode_utf16() {
+                bytes.extend(if little_endian {
+                    unit.to_le_bytes()
+                } else {
+                    unit.to_be_bytes()
+                });
+            }
+            bytes
+        };
+        let latin1 = b"{\"a\": \"caf\xe9\"}\n".to_vec();
+        let cases = [
+            (JsonEncoding::Utf16Le, utf16(true), text),
+            (JsonEncoding::Utf16Be, utf16(false), text),
+            (JsonEncoding::Latin1, latin1, "{\"a\": \"café\"}\n"),
+        ];
+        for (encoding, bytes, expected) in cases {
+            // one byte at a time, so that characters and surrogate pairs
+            // span chunks
+            let mut transcoder = Transcoder::new(encoding);
+            let mut output = vec![];
+            for byte in &bytes {
+                transcoder.transcode(&[*byte], &mut output).unwrap();
+            }
+            transcoder.finish().unwrap();
+            assert_eq!(String::from_utf8(output).unwrap(), expected);
+
+            let mut output = String::new();
+            encoding
+                .convert_read(Cursor::new(bytes))
+                .read_to_string(&mut output)
+                .unwrap();
+            assert_eq!(output, expected);
+        }
+
+        // a truncated character or an unpaired surrogate is invalid
+        let mut output = vec![];
+        let mut transcoder = Transcoder::new(JsonEncoding::Utf16Le);
+        transcoder.transcode(&[b'a', 0, b'b'], &mut output).unwrap();
+        assert!(transcoder.finish().is_err());
+        let mut transcoder = Transcoder::new(JsonEncoding::Utf16Le);
+        assert!(transcoder
+            .transcode(&[0x3D, 0xD8, b'a', 0], &mut output)
+            .is_err());
+    }
+
+    #[test]
+    fn parse_timestamps_in_timezone() {
+        let timezone: Tz = "America/New_York".parse().unwrap();
+        let cases = [
+            ("2021-03-01 12:00:00", TimeUnit::Second, Some(1_614_618_000)),
+            (
+                "2021-03-01T12:00:00.25",
+                TimeUnit::Millisecond,
+                Some(1_614_618_000_250),
+            ),
+            ("2021-03-01", TimeUnit::Second, Some(1_614_574_800)),
+            (
+                "2021-03-01T12:00:00+01:00",
+                TimeUnit::Second,
+                Some(1_614_596_400),
+            ),
+            // the first of the two 01:30 when clocks are set back
+            ("2021-11-07 01:30:00", TimeUnit::Second, Some(1_636_263_000)),
+            // does not exist as clocks are set forward
+            ("2021-03-14 02:30:00", TimeUnit::Second, None),
+            ("yesterday", TimeUnit::Second, None),
+        ];
+        for (s, unit, expected) in cases {
+            assert_eq!(parse_timestamp(s, &unit, &timezone), expected, "{}", s);
+        }
+    }
+
+    #[test]
+    fn parse_iso8601_durations() {
+        let duration = |months, days, nanos| {
+            Some(IsoDuration {
+                months,
+                days,
+                nanos,
+            })
+        };
+        let cases = [
+            ("PT1H30M", duration(0, 0, 5_400_000_000_000)),
+            ("P1Y2M3DT4H5M6.7S", duration(14, 3, 14_706_700_000_000)),
+            ("P2W", duration(0, 14, 0)),
+            ("PT0.000000001S", duration(0, 0, 1)),
+            ("-P1DT1S", duration(0, -1, -1_000_000_000)),
+            ("P", None),
+            ("PT", None),
+            ("P1D T1H", None),
+            ("PT1.5H", None),
+            ("PT1S1M", None),
+            ("P1M1M", None),
+            ("1H", None),
+            ("PT0.0000000001S", None),
+        ];
+        for (s, expected) in cases {
+            assert_eq!(parse_iso8601_duration(s), expected, "{}", s);
+        }
+    }
+
+    #[tokio::test]
+    async fn in_flight_limit_reserves_before_decoding() {
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int64, false)]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![Arc::new(Int64Array::from_iter_values(0..100))],
+        )
+        .unwrap();
+        let batch_bytes = batch_byte_size(&batch);
+
+        for (max_bytes, max_batches, ahead) in
+            [(Some(3 * batch_bytes), None, 3), (None, Some(2), 2)]
+        {
+            // counts the batches decoded, whether consumed or not
+            let decoded = Arc::new(AtomicUsize::new(0));
+            let stream = {
+                let (decoded, batch) = (decoded.clone(), batch.clone());
+                futures::stream::iter(0..10)
+                    .map(move |_| {
+                        decoded.fetch_add(1, Ordering::SeqCst);
+                        Ok(batch.clone())
+                    })
+                    .boxed()
+            };
+            let limit = Arc::new(InFlightLimit::new(
+                max_bytes,
+                max_batches,
+                Gauge::new(),
+                Gauge::new(),
+            ));
+            let mut stream = limit.limit(stream);
+
+            let mut consumed = 0;
+            loop {
+                // let the producer decode as far ahead as the limit allows
+                for _ in 0..10 {
+                    tokio::task::yield_now().await;
+                }
+                assert_eq!(
+                    decoded.load(Ordering::SeqCst),
+                    (consumed + ahead).min(10),
+                    "{:?} {:?}",
+                    max_bytes,
+                    max_batches
+                );
+                match stream.next().await {
+                    Some(batch) => {
+                        batch.unwrap();
+                        consumed += 1;
+                    }
+                    None => break,
+                }
+            }
+            assert_eq!(consumed, 10);
+        }
+    }
+}
@@ -23,7 +23,9 @@ mod chunked_store;
 mod csv;
 mod delimited_stream;
 mod file_stream;
+mod ipc;
 mod json;
+mod json_reader;
 mod parquet;
 
 pub(crate) use self::csv::plan_to_csv;
@@ -39,8 +41,19 @@ use arrow::{
 };
 pub use avro::AvroExec;
 pub use file_stream::{FileOpenFuture, FileOpener, FileStream};
+pub(crate) use ipc::{plan_to_ipc, plan_to_ipc_stream};
+pub use ipc::{IpcExec, IpcWriteMode};
 pub(crate) use json::plan_to_json;
 pub use json::NdJsonExec;
+pub use json_reader::{
+    Base64Encoding, FileProgress, JsonBatchReader, JsonDiagnostics, JsonEncoding,
+    JsonProgress, JsonReadOffsets, JsonScanOptions, JsonValidation, JsonValidationError,
+    JsonValidationSummary, NumericLocale, DEFAULT_MAX_VALIDATION_ERRORS, ROW_ID_COLUMN,
+};
+pub(crate) use json_reader::{
+    DurationDetector, FixedSizeListDetector, JsonRecordValidator, JsonValueIter,
+    LargeOffsetDetector, NullFieldDetector, TypeConflictDetector,
+};
 use parking_lot::RwLock;
 
 use crate::datasource::{listing::PartitionedFile, object_store::ObjectStoreUrl};
@@ -62,7 +75,7 @@ use std::{
     vec,
 };
 
-use super::{ColumnStatistics, Statistics};
+use super::{ColumnStatistics, DisplayFormatType, Statistics};
 
 lazy_static! {
     /// The datatype used for all partitioning columns for now
@@ -176,8 +189,11 @@ impl FileScanConfig {
 }
 
 /// A wrapper to customize partitioned file display
+///
+/// In [`DisplayFormatType::Verbose`] the size of every file follows its
+/// location, as well as the byte range read of it if it is only partly read
 #[derive(Debug)]
-struct FileGroupsDisplay<'a>(&'a [Vec<PartitionedFile>]);
+struct FileGroupsDisplay<'a>(&'a [Vec<PartitionedFile>], DisplayFormatType);
 
 impl<'a> Display for FileGroupsDisplay<'a> {
     fn fmt(&self, f: &mut Formatter) -> FmtResult {
@@ -186,7 +202,19 @@ impl<'a> Display for FileGroupsDisplay<'a> {
             .iter()
             .map(|pp| {
                 pp.iter()
-                    .map(|pf| pf.object_meta.location.as_ref())
+                    .map(|pf| {
+                        let location = pf.object_meta.location.as_ref();
+                        match (self.1, &pf.range) {
+                            (DisplayFormatType::Default, _) => location.to_string(),
+                            (DisplayFormatType::Verbose, None) => {
+                                format!("{} (size={})", location, pf.object_meta.size)
+                            }
+                            (DisplayFormatType::Verbose, Some(range)) => format!(
+                                "{} (size={}, range={}..{})",
+                                location, pf.object_meta.size, range.start, range.end
+                            ),
+                        }
+                    })
                     .collect::<Vec<_>>()
                     .join(", ")
             })
@@ -300,13 +300,13 @@ impl ExecutionPlan for ParquetExec {
         f: &mut std::fmt::Formatter,
     ) -> std::fmt::Result {
         match t {
-            DisplayFormatType::Default => {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
                 if let Some(pre) = &self.pruning_predicate {
                     write!(
                         f,
                         "ParquetExec: limit={:?}, partitions={}, predicate={}, projection={}",
                         self.base_config.limit,
-                        super::FileGroupsDisplay(&self.base_config.file_groups),
+                        super::FileGroupsDisplay(&self.base_config.file_groups, t),
                         pre.predicate_expr(),
                         super::ProjectSchemaDisplay(&self.projected_schema),
                     )
@@ -315,7 +315,7 @@ impl ExecutionPlan for ParquetExec {
                         f,
                         "ParquetExec: limit={:?}, partitions={}, projection={}",
                         self.base_config.limit,
-                        super::FileGroupsDisplay(&self.base_config.file_groups),
+                        super::FileGroupsDisplay(&self.base_config.file_groups, t),
                         super::ProjectSchemaDisplay(&self.projected_schema),
                     )
                 }
@@ -23,19 +23,23 @@ use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll};
 
+use futures::ready;
+
 use super::expressions::PhysicalSortExpr;
 use super::{RecordBatchStream, SendableRecordBatchStream, Statistics};
 use crate::error::{DataFusionError, Result};
 use crate::physical_plan::{
-    metrics::{BaselineMetrics, ExecutionPlanMetricsSet, MetricsSet},
+    metrics::{
+        BaselineMetrics, Count, ExecutionPlanMetricsSet, MetricBuilder, MetricsSet,
+    },
     Column, DisplayFormatType, EquivalenceProperties, ExecutionPlan, Partitioning,
     PhysicalExpr,
 };
-use arrow::array::BooleanArray;
-use arrow::compute::filter_record_batch;
+use arrow::array::{Array, BooleanArray};
+use arrow::compute::{prep_null_mask_filter, FilterBuilder};
 use arrow::datatypes::{DataType, SchemaRef};
 use arrow::error::Result as ArrowResult;
-use arrow::record_batch::RecordBatch;
+use arrow::record_batch::{RecordBatch, RecordBatchOptions};
 use datafusion_expr::Operator;
 use datafusion_physical_expr::expressions::BinaryExpr;
 use datafusion_physical_expr::split_conjunction;
@@ -85,6 +89,15 @@ impl FilterExec {
     pub fn input(&self) -> &Arc<dyn ExecutionPlan> {
         &self.input
     }
+
+    /// The fraction of the input rows that passed the filter so far, over
+    /// all partitions executed, or `None` if no row was read
+    pub fn selectivity(&self) -> Option<f64> {
+        let metrics = self.metrics.clone_inner();
+        let input_rows = metrics.sum_by_name("input_rows")?.as_usize();
+        let output_rows = metrics.output_rows().unwrap_or_default();
+        (input_rows > 0).then(|| output_rows as f64 / input_rows as f64)
+    }
 }
 
 impl ExecutionPlan for FilterExec {
@@ -144,11 +157,14 @@ impl ExecutionPlan for FilterExec {
     ) -> Result<SendableRecordBatchStream> {
         debug!("Start FilterExec::execute for partition {} of context session_id {} and task_id {:?}", partition, context.session_id(), context.task_id());
         let baseline_metrics = BaselineMetrics::new(&self.metrics, partition);
+        let input_rows =
+            MetricBuilder::new(&self.metrics).counter("input_rows", partition);
         Ok(Box::pin(FilterExecStream {
             schema: self.input.schema(),
             predicate: self.predicate.clone(),
             input: self.input.execute(partition, context)?,
             baseline_metrics,
+            input_rows,
         }))
     }
 
@@ -158,7 +174,7 @@ impl ExecutionPlan for FilterExec {
         f: &mut std::fmt::Formatter,
     ) -> std::fmt::Result {
         match t {
-            DisplayFormatType::Default => {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
                 write!(f, "FilterExec: {}", self.predicate)
             }
         }
@@ -185,29 +201,56 @@ struct FilterExecStream {
     input: SendableRecordBatchStream,
     /// runtime metrics recording
     baseline_metrics: BaselineMetrics,
+    /// The number of rows read from the input
+    input_rows: Count,
 }
 
+/// Filters `batch` by `predicate`, returning `None` if no row passes it.
+/// If every row passes it, `batch` is returned without copying its columns.
 fn batch_filter(
     batch: &RecordBatch,
     predicate: &Arc<dyn PhysicalExpr>,
-) -> ArrowResult<RecordBatch> {
-    predicate
+) -> ArrowResult<Option<RecordBatch>> {
+    let array = predicate
         .evaluate(batch)
-        .map(|v| v.into_array(batch.num_rows()))
-        .map_err(DataFusionError::into)
-        .and_then(|array| {
-            array
-                .as_any()
-                .downcast_ref::<BooleanArray>()
-                .ok_or_else(|| {
-                    DataFusionError::Internal(
-                        "Filter predicate evaluated to non-boolean value".to_string(),
-                    )
-                    .into()
-                })
-                // apply filter array to record batch
-                .and_then(|filter_array| filter_record_batch(batch, filter_array))
-        })
+        .map(|v| v.into_array(batch.num_rows()))?;
+    let filter_array =
+        array
+            .as_any()
+            .downcast_ref::<BooleanArray>()
+            .ok_or_else(|| {
+                DataFusionError::Internal(
+                    "Filter predicate evaluated to non-boolean value".to_string(),
+                )
+            })?;
+
+    // rows for which the predicate is null do not pass it
+    let prepared;
+    let filter_array = if filter_array.null_count() > 0 {
+        prepared = prep_null_mask_filter(filter_array);
+        &prepared
+    } else {
+        filter_array
+    };
+    let true_count = filter_array
+        .values()
+        .count_set_bits_offset(filter_array.offset(), filter_array.len());
+    if true_count == 0 {
+        return Ok(None);
+    }
+    if true_count == batch.num_rows() {
+        return Ok(Some(batch.clone()));
+    }
+
+    // the selected rows are found once for all columns
+    let filter = FilterBuilder::new(filter_array).optimize().build();
+    let columns = batch
+        .columns()
+        .iter()
+        .map(|column| filter.filter(column.as_ref()))
+        .collect::<ArrowResult<Vec<_>>>()?;
+    let options = RecordBatchOptions::new().with_row_count(Some(true_count));
+    RecordBatch::try_new_with_options(batch.schema(), columns, &options).map(Some)
 }
 
 impl Stream for FilterExecStream {
@@ -217,16 +260,23 @@ impl Stream for FilterExecStream {
         mut self: Pin<&mut Self>,
         cx: &mut Context<'_>,
     ) -> Poll<Option<Self::Item>> {
-        let poll = self.input.poll_next_unpin(cx).map(|x| match x {
-            Some(Ok(batch)) => {
-                let timer = self.baseline_metrics.elapsed_compute().timer();
-                let filtered_batch = batch_filter(&batch, &self.predicate);
-                timer.done();
-                Some(filtered_batch)
+        let poll = loop {
+            match ready!(self.input.poll_next_unpin(cx)) {
+                Some(Ok(batch)) => {
+                    let timer = self.baseline_metrics.elapsed_compute().timer();
+                    self.input_rows.add(batch.num_rows());
+                    let filtered_batch = batch_filter(&batch, &self.predicate);
+                    timer.done();
+                    match filtered_batch {
+                        // skip batches without any row passing the filter
+                        Ok(None) => continue,
+                        filtered_batch => break filtered_batch.transpose(),
+                    }
+                }
+                other => break other,
             }
-            other => other,
-        });
-        self.baseline_metrics.record_poll(poll)
+        };
+        self.baseline_metrics.record_poll(Poll::Ready(poll))
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
@@ -277,12 +327,17 @@ pub type EqualAndNonEqual<'a> =
 mod tests {
 
     use super::*;
+    use crate::assert_batches_eq;
+    use crate::physical_plan::common;
     use crate::physical_plan::expressions::*;
+    use crate::physical_plan::memory::MemoryExec;
     use crate::physical_plan::ExecutionPlan;
     use crate::physical_plan::{collect, with_new_children_if_necessary};
     use crate::prelude::SessionContext;
     use crate::test;
     use crate::test_util;
+    use arrow::array::{Int32Array, StringArray};
+    use arrow::datatypes::{Field, Schema};
     use datafusion_expr::Operator;
     use std::iter::Iterator;
 
@@ -338,6 +393,80 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    #[allow(clippy::vtable_address_comparisons)]
+    async fn filter_all_or_no_rows() -> Result<()> {
+        let session_ctx = SessionContext::new();
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Utf8, false),
+        ]));
+        let batches = vec![
+            RecordBatch::try_new(
+                schema.clone(),
+                vec![
+                    Arc::new(Int32Array::from(vec![Some(1), Some(2), Some(3)])),
+                    Arc::new(StringArray::from(vec!["x", "y", "z"])),
+                ],
+            )?,
+            RecordBatch::try_new(
+                schema.clone(),
+                vec![
+                    Arc::new(Int32Array::from(vec![Some(4), None, Some(6), Some(7)])),
+                    Arc::new(StringArray::from(vec!["u", "v", "w", "t"])),
+                ],
+            )?,
+        ];
+        let input = Arc::new(MemoryExec::try_new(
+            &[batches.clone()],
+            schema.clone(),
+            None,
+        )?);
+        let filter = |op, value: i32| -> Result<FilterExec> {
+            let predicate = binary(col("a", &schema)?, op, lit(value), &schema)?;
+            FilterExec::try_new(predicate, input.clone())
+        };
+
+        // every row of the first batch passes, which is not copied, and
+        // none of the second, which is not emitted
+        let exec = filter(Operator::Lt, 4)?;
+        assert_eq!(exec.selectivity(), None);
+        let results = exec.execute(0, session_ctx.task_ctx())?;
+        let results = common::collect(results).await?;
+        assert_eq!(results.len(), 1);
+        for (column, input) in results[0].columns().iter().zip(batches[0].columns()) {
+            assert!(Arc::ptr_eq(column, input));
+        }
+        let metrics = exec.metrics().unwrap();
+        assert_eq!(metrics.sum_by_name("input_rows").unwrap().as_usize(), 7);
+        assert_eq!(metrics.output_rows(), Some(3));
+        assert_eq!(exec.selectivity(), Some(3.0 / 7.0));
+
+        // the null is filtered out along with the rows that do not pass
+        let exec = filter(Operator::GtEq, 2)?;
+        let results = common::collect(exec.execute(0, session_ctx.task_ctx())?).await?;
+        let expected = vec![
+            "+---+---+",
+            "| a | b |",
+            "+---+---+",
+            "| 2 | y |",
+            "| 3 | z |",
+            "| 4 | u |",
+            "| 6 | w |",
+            "| 7 | t |",
+            "+---+---+",
+        ];
+        assert_batches_eq!(expected, &results);
+        assert_eq!(exec.selectivity(), Some(5.0 / 7.0));
+
+        let exec = filter(Operator::Gt, 10)?;
+        let results = common::collect(exec.execute(0, session_ctx.task_ctx())?).await?;
+        assert!(results.is_empty());
+        assert_eq!(exec.selectivity(), Some(0.0));
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn collect_columns_predicates() -> Result<()> {
         let schema = test_util::aggr_test_schema();
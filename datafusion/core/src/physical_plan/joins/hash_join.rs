@@ -62,9 +62,10 @@ use crate::physical_plan::{
     expressions::PhysicalSortExpr,
     hash_utils::create_hashes,
     joins::utils::{
-        adjust_right_output_partitioning, build_join_schema, check_join_is_valid,
-        combine_join_equivalence_properties, estimate_join_statistics,
-        partitioned_join_output_partitioning, ColumnIndex, JoinFilter, JoinOn, JoinSide,
+        adjust_right_output_partitioning, build_batch_from_indices, build_join_schema,
+        check_join_is_valid, combine_join_equivalence_properties,
+        estimate_join_statistics, partitioned_join_output_partitioning, ColumnIndex,
+        JoinFilter, JoinOn, JoinSide,
     },
     metrics::{self, ExecutionPlanMetricsSet, MetricBuilder, MetricsSet},
     DisplayFormatType, Distribution, EquivalenceProperties, ExecutionPlan, Partitioning,
@@ -73,6 +74,7 @@ use crate::physical_plan::{
 
 use crate::error::{DataFusionError, Result};
 use crate::logical_expr::JoinType;
+use crate::scalar::ScalarValue;
 
 use crate::arrow::array::BooleanBufferBuilder;
 use crate::arrow::datatypes::TimeUnit;
@@ -404,12 +406,13 @@ impl ExecutionPlan for HashJoinExec {
             join_metrics: HashJoinMetrics::new(partition, &self.metrics),
             null_equals_null: self.null_equals_null,
             is_exhausted: false,
+            hashes_buffer: vec![],
         }))
     }
 
     fn fmt_as(&self, t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
         match t {
-            DisplayFormatType::Default => {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
                 let display_filter = self.filter.as_ref().map_or_else(
                     || "".to_string(),
                     |f| format!(", filter={:?}", f.expression()),
@@ -620,6 +623,9 @@ struct HashJoinStream {
     column_indices: Vec<ColumnIndex>,
     /// If null_equals_null is true, null == null else null != null
     null_equals_null: bool,
+    /// The hashes of the keys of the right batch being probed, reused across
+    /// batches to avoid reallocating it
+    hashes_buffer: Vec<u64>,
 }
 
 impl RecordBatchStream for HashJoinStream {
@@ -628,53 +634,6 @@ impl RecordBatchStream for HashJoinStream {
     }
 }
 
-/// Returns a new [RecordBatch] by combining the `left` and `right` according to `indices`.
-/// The resulting batch has [Schema] `schema`.
-/// # Error
-/// This function errors when:
-/// *
-fn build_batch_from_indices(
-    schema: &Schema,
-    left: &RecordBatch,
-    right: &RecordBatch,
-    left_indices: UInt64Array,
-    right_indices: UInt32Array,
-    column_indices: &[ColumnIndex],
-) -> ArrowResult<(RecordBatch, UInt64Array)> {
-    // build the columns of the new [RecordBatch]:
-    // 1. pick whether the column is from the left or right
-    // 2. based on the pick, `take` items from the different RecordBatches
-    let mut columns: Vec<Arc<dyn Array>> = Vec::with_capacity(schema.fields().len());
-
-    for column_index in column_indices {
-        let array = match column_index.side {
-            JoinSide::Left => {
-                let array = left.column(column_index.index);
-                if array.is_empty() || left_indices.null_count() == left_indices.len() {
-                    // Outer join would generate a null index when finding no match at our side.
-                    // Therefore, it's possible we are empty but need to populate an n-length null array,
-                    // where n is the length of the index array.
-                    assert_eq!(left_indices.null_count(), left_indices.len());
-                    new_null_array(array.data_type(), left_indices.len())
-                } else {
-                    compute::take(array.as_ref(), &left_indices, None)?
-                }
-            }
-            JoinSide::Right => {
-                let array = right.column(column_index.index);
-                if array.is_empty() || right_indices.null_count() == right_indices.len() {
-                    assert_eq!(right_indices.null_count(), right_indices.len());
-                    new_null_array(array.data_type(), right_indices.len())
-                } else {
-                    compute::take(array.as_ref(), &right_indices, None)?
-                }
-            }
-        };
-        columns.push(array);
-    }
-    RecordBatch::try_new(Arc::new(schema.clone()), columns).map(|x| (x, left_indices))
-}
-
 #[allow(clippy::too_many_arguments)]
 fn build_batch(
     batch: &RecordBatch,
@@ -687,6 +646,7 @@ fn build_batch(
     column_indices: &[ColumnIndex],
     random_state: &RandomState,
     null_equals_null: &bool,
+    hashes_buffer: &mut Vec<u64>,
 ) -> ArrowResult<(RecordBatch, UInt64Array)> {
     let (left_indices, right_indices) = build_join_indexes(
         left_data,
@@ -696,6 +656,7 @@ fn build_batch(
         on_right,
         random_state,
         null_equals_null,
+        hashes_buffer,
     )
     .unwrap();
 
@@ -757,6 +718,7 @@ fn build_batch(
 // (0, 0)     (1, 2)
 // (1, 1)     (1, 1)
 // (1, 0)     (1, 2)
+#[allow(clippy::too_many_arguments)]
 fn build_join_indexes(
     left_data: &JoinLeftData,
     right: &RecordBatch,
@@ -765,6 +727,7 @@ fn build_join_indexes(
     right_on: &[Column],
     random_state: &RandomState,
     null_equals_null: &bool,
+    hashes_buffer: &mut Vec<u64>,
 ) -> Result<(UInt64Array, UInt32Array)> {
     let keys_values = right_on
         .iter()
@@ -774,7 +737,8 @@ fn build_join_indexes(
         .iter()
         .map(|c| Ok(c.evaluate(&left_data.1)?.into_array(left_data.1.num_rows())))
         .collect::<Result<Vec<_>>>()?;
-    let hashes_buffer = &mut vec![0; keys_values[0].len()];
+    hashes_buffer.clear();
+    hashes_buffer.resize(right.num_rows(), 0);
     let hash_values = create_hashes(&keys_values, random_state, hashes_buffer)?;
     let left = &left_data.0;
 
@@ -1050,6 +1014,8 @@ fn apply_join_filter(
                 .evaluate_selection(&intermediate_batch, &has_match)?
                 .into_array(intermediate_batch.num_rows());
             let mask = as_boolean_array(&filter_result);
+            // a null filter result rejects the pair, like a false one
+            let is_match = |pos: usize| mask.is_valid(pos) && mask.value(pos);
 
             let mut left_rebuilt = UInt64Builder::with_capacity(0);
             let mut right_rebuilt = UInt32Builder::with_capacity(0);
@@ -1066,16 +1032,16 @@ fn apply_join_filter(
                             left_rebuilt.append_null();
                         }
                         // If has match append matched row indices
-                        if mask.value(pos) {
+                        if is_match(pos) {
                             right_rebuilt.append_value(right_indices.value(pos));
                             left_rebuilt.append_value(left_indices.value(pos));
                         };
 
                         // Calculate if current row index has match
                         let has_match = if right_indices.value(pos) != state.0 {
-                            mask.value(pos)
+                            is_match(pos)
                         } else {
-                            cmp::max(mask.value(pos), state.1)
+                            cmp::max(is_match(pos), state.1)
                         };
 
                         Ok((right_indices.value(pos), has_match))
@@ -1107,6 +1073,24 @@ macro_rules! equal_rows_elem {
     }};
 }
 
+// Like equal_rows_elem, but all NaNs are equal, so that float keys match the
+// way they are hashed
+macro_rules! equal_rows_elem_float {
+    ($array_type:ident, $l: ident, $r: ident, $left: ident, $right: ident, $null_equals_null: ident) => {{
+        let left_array = $l.as_any().downcast_ref::<$array_type>().unwrap();
+        let right_array = $r.as_any().downcast_ref::<$array_type>().unwrap();
+
+        match (left_array.is_null($left), right_array.is_null($right)) {
+            (false, false) => {
+                let (l, r) = (left_array.value($left), right_array.value($right));
+                l == r || (l.is_nan() && r.is_nan())
+            }
+            (true, true) => $null_equals_null,
+            _ => false,
+        }
+    }};
+}
+
 macro_rules! equal_rows_elem_with_string_dict {
     ($key_array_type:ident, $l: ident, $r: ident, $left: ident, $right: ident, $null_equals_null: ident) => {{
         let left_array: &DictionaryArray<$key_array_type> =
@@ -1200,10 +1184,10 @@ fn equal_rows(
                 equal_rows_elem!(UInt64Array, l, r, left, right, null_equals_null)
             }
             DataType::Float32 => {
-                equal_rows_elem!(Float32Array, l, r, left, right, null_equals_null)
+                equal_rows_elem_float!(Float32Array, l, r, left, right, null_equals_null)
             }
             DataType::Float64 => {
-                equal_rows_elem!(Float64Array, l, r, left, right, null_equals_null)
+                equal_rows_elem_float!(Float64Array, l, r, left, right, null_equals_null)
             }
             DataType::Date32 => {
                 equal_rows_elem!(Date32Array, l, r, left, right, null_equals_null)
@@ -1377,6 +1361,19 @@ fn equal_rows(
                     }
                 }
             }
+            DataType::List(_) | DataType::Struct(_) => {
+                match (l.is_null(left), r.is_null(right)) {
+                    (false, false) => match ScalarValue::try_from_array(l, left) {
+                        Ok(value) => value.eq_array(r, right),
+                        Err(e) => {
+                            err = Some(Err(e));
+                            false
+                        }
+                    },
+                    (true, true) => null_equals_null,
+                    _ => false,
+                }
+            }
             other => {
                 // This is internal because we should have caught this before.
                 err = Some(Err(DataFusionError::Internal(format!(
@@ -1478,6 +1475,7 @@ impl HashJoinStream {
                         &self.column_indices,
                         &self.random_state,
                         &self.null_equals_null,
+                        &mut self.hashes_buffer,
                     );
                     self.join_metrics.input_batches.add(1);
                     self.join_metrics.input_rows.add(batch.num_rows());
@@ -1990,6 +1988,74 @@ mod tests {
         Ok(())
     }
 
+    /// Test where the right batches hashed with the same buffer have
+    /// different sizes, giving the same results as a single right batch
+    #[tokio::test]
+    async fn join_inner_two_right_batches_of_different_sizes() -> Result<()> {
+        let session_ctx = SessionContext::new();
+        let task_ctx = session_ctx.task_ctx();
+        let left = build_table(
+            ("a1", &vec![1, 2, 2, 3]),
+            ("b1", &vec![4, 5, 6, 7]),
+            ("c1", &vec![7, 8, 9, 10]),
+        );
+
+        let batch1 = build_table_i32(
+            ("a1", &vec![1, 2, 2, 5]),
+            ("b1", &vec![4, 6, 5, 4]),
+            ("c2", &vec![70, 80, 90, 100]),
+        );
+        let batch2 =
+            build_table_i32(("a1", &vec![3]), ("b1", &vec![7]), ("c2", &vec![110]));
+        let schema = batch1.schema();
+        let concatenated = concat_batches(&schema, &[batch1.clone(), batch2.clone()], 5)?;
+        let on = vec![
+            (
+                Column::new_with_schema("a1", &left.schema())?,
+                Column::new_with_schema("a1", &schema)?,
+            ),
+            (
+                Column::new_with_schema("b1", &left.schema())?,
+                Column::new_with_schema("b1", &schema)?,
+            ),
+        ];
+
+        let right = Arc::new(MemoryExec::try_new(
+            &[vec![batch1, batch2]],
+            schema.clone(),
+            None,
+        )?);
+        let (_, batches) = join_collect(
+            left.clone(),
+            right,
+            on.clone(),
+            &JoinType::Inner,
+            false,
+            task_ctx.clone(),
+        )
+        .await?;
+        assert_eq!(batches.len(), 2);
+
+        let right = Arc::new(MemoryExec::try_new(&[vec![concatenated]], schema, None)?);
+        let (_, single_batch) =
+            join_collect(left, right, on, &JoinType::Inner, false, task_ctx).await?;
+
+        let expected = vec![
+            "+----+----+----+----+----+-----+",
+            "| a1 | b1 | c1 | a1 | b1 | c2  |",
+            "+----+----+----+----+----+-----+",
+            "| 1  | 4  | 7  | 1  | 4  | 70  |",
+            "| 2  | 5  | 8  | 2  | 5  | 90  |",
+            "| 2  | 6  | 9  | 2  | 6  | 80  |",
+            "| 3  | 7  | 10 | 3  | 7  | 110 |",
+            "+----+----+----+----+----+-----+",
+        ];
+        assert_batches_sorted_eq!(expected, &batches);
+        assert_batches_sorted_eq!(expected, &single_batch);
+
+        Ok(())
+    }
+
     fn build_table_two_batches(
         a: (&str, &Vec<i32>),
         b: (&str, &Vec<i32>),
@@ -2627,6 +2693,7 @@ mod tests {
             &[Column::new("a", 0)],
             &random_state,
             &false,
+            &mut vec![],
         )?;
 
         let mut left_ids = UInt64Builder::with_capacity(0);
@@ -2883,6 +2950,64 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn join_full_with_null_filter_result() -> Result<()> {
+        let session_ctx = SessionContext::new();
+        let task_ctx = session_ctx.task_ctx();
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Int32, true),
+            Field::new("c", DataType::Int32, true),
+        ]));
+        let left_batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int32Array::from(vec![1, 2])),
+                Arc::new(Int32Array::from(vec![10, 20])),
+                Arc::new(Int32Array::from(vec![Some(5), None])),
+            ],
+        )?;
+        let right_batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int32Array::from(vec![100, 200])),
+                Arc::new(Int32Array::from(vec![1, 2])),
+                Arc::new(Int32Array::from(vec![3, 3])),
+            ],
+        )?;
+        let left = Arc::new(MemoryExec::try_new(
+            &[vec![left_batch]],
+            schema.clone(),
+            None,
+        )?);
+        let right = Arc::new(MemoryExec::try_new(&[vec![right_batch]], schema, None)?);
+        let on = vec![(
+            Column::new_with_schema("a", &left.schema()).unwrap(),
+            Column::new_with_schema("b", &right.schema()).unwrap(),
+        )];
+        let filter = prepare_join_filter();
+
+        let join = join_with_filter(left, right, on, filter, &JoinType::Full, false)?;
+
+        let stream = join.execute(0, task_ctx)?;
+        let batches = common::collect(stream).await?;
+
+        // the filter is null for the second pair of matching keys, whose rows
+        // are produced unmatched
+        let expected = vec![
+            "+---+----+---+-----+---+---+",
+            "| a | b  | c | a   | b | c |",
+            "+---+----+---+-----+---+---+",
+            "|   |    |   | 200 | 2 | 3 |",
+            "| 1 | 10 | 5 | 100 | 1 | 3 |",
+            "| 2 | 20 |   |     |   |   |",
+            "+---+----+---+-----+---+---+",
+        ];
+        assert_batches_sorted_eq!(expected, &batches);
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn join_date32() -> Result<()> {
         let schema = Arc::new(Schema::new(vec![
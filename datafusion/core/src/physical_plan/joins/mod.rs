@@ -19,6 +19,7 @@
 
 mod cross_join;
 mod hash_join;
+mod nested_loop_join;
 mod sort_merge_join;
 pub mod utils;
 
@@ -33,6 +34,7 @@ pub enum PartitionMode {
 
 pub use cross_join::CrossJoinExec;
 pub use hash_join::HashJoinExec;
+pub use nested_loop_join::NestedLoopJoinExec;
 
 // Note: SortMergeJoin is not used in plans yet
 pub use sort_merge_join::SortMergeJoinExec;
@@ -0,0 +1,801 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines the nested loop join plan, which joins the rows of both sides
+//! satisfying an arbitrary filter, for joins without equality predicates
+
+use std::any::Any;
+use std::fmt;
+use std::sync::Arc;
+use std::task::Poll;
+use std::time::Instant;
+
+use arrow::array::{
+    as_boolean_array, Array, BooleanBufferBuilder, UInt32Array, UInt64Array,
+};
+use arrow::datatypes::SchemaRef;
+use arrow::error::Result as ArrowResult;
+use arrow::record_batch::RecordBatch;
+use futures::{ready, Stream, StreamExt, TryStreamExt};
+use log::debug;
+
+use crate::error::{DataFusionError, Result};
+use crate::execution::context::TaskContext;
+use crate::logical_expr::JoinType;
+use crate::physical_plan::common::batch_byte_size;
+use crate::physical_plan::{
+    coalesce_batches::concat_batches,
+    coalesce_partitions::CoalescePartitionsExec,
+    expressions::PhysicalSortExpr,
+    joins::utils::{
+        adjust_right_output_partitioning, build_batch_from_indices, build_join_schema,
+        check_join_is_valid, ColumnIndex, JoinFilter, OnceAsync, OnceFut,
+    },
+    metrics::{
+        self, ExecutionPlanMetricsSet, MemTrackingMetrics, MetricBuilder, MetricsSet,
+    },
+    DisplayFormatType, Distribution, ExecutionPlan, Partitioning, RecordBatchStream,
+    SendableRecordBatchStream, Statistics,
+};
+
+/// The rows of the build side, and the tracking of the memory they use
+type JoinBuildData = (RecordBatch, MemTrackingMetrics);
+
+/// The number of pairs of rows the filter is evaluated on at once, unless
+/// the build side has more rows
+const FILTER_BATCH_PAIRS: usize = 8192;
+
+/// Join execution plan that evaluates the filter on every pair of rows of
+/// its sides, for joins without any equality predicate such as
+/// `a.x < b.y`, that can not be hashed.
+///
+/// One side, the build side, is loaded in memory and shared by the output
+/// partitions, each one joining a partition of the other side, the probe
+/// side, to it. The build side is the side whose unmatched rows need not be
+/// produced: the right side of inner and left joins, and the left side of
+/// right joins. Both sides of full joins are read from a single partition,
+/// the right side being the build side.
+#[derive(Debug)]
+pub struct NestedLoopJoinExec {
+    /// left side
+    pub(crate) left: Arc<dyn ExecutionPlan>,
+    /// right side
+    pub(crate) right: Arc<dyn ExecutionPlan>,
+    /// Filter the pairs of rows are joined on, all pairs being joined if
+    /// `None`
+    pub(crate) filter: Option<JoinFilter>,
+    /// How the join is performed
+    pub(crate) join_type: JoinType,
+    /// The schema once the join is applied
+    schema: SchemaRef,
+    /// Build-side data
+    build_fut: OnceAsync<JoinBuildData>,
+    /// Execution metrics
+    metrics: ExecutionPlanMetricsSet,
+    /// Information of index and left / right placement of columns
+    column_indices: Vec<ColumnIndex>,
+}
+
+impl NestedLoopJoinExec {
+    /// Tries to create a new [NestedLoopJoinExec].
+    /// # Error
+    /// This function errors when the join type is not an inner, left,
+    /// right or full join
+    pub fn try_new(
+        left: Arc<dyn ExecutionPlan>,
+        right: Arc<dyn ExecutionPlan>,
+        filter: Option<JoinFilter>,
+        join_type: &JoinType,
+    ) -> Result<Self> {
+        if !matches!(
+            join_type,
+            JoinType::Inner | JoinType::Left | JoinType::Right | JoinType::Full
+        ) {
+            return Err(DataFusionError::NotImplemented(format!(
+                "NestedLoopJoinExec does not support {} joins",
+                join_type
+            )));
+        }
+
+        let left_schema = left.schema();
+        let right_schema = right.schema();
+        check_join_is_valid(&left_schema, &right_schema, &[])?;
+        let (schema, column_indices) =
+            build_join_schema(&left_schema, &right_schema, join_type);
+
+        Ok(NestedLoopJoinExec {
+            left,
+            right,
+            filter,
+            join_type: *join_type,
+            schema: Arc::new(schema),
+            build_fut: Default::default(),
+            metrics: ExecutionPlanMetricsSet::new(),
+            column_indices,
+        })
+    }
+
+    /// left side
+    pub fn left(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.left
+    }
+
+    /// right side
+    pub fn right(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.right
+    }
+
+    /// Filter the pairs of rows are joined on
+    pub fn filter(&self) -> Option<&JoinFilter> {
+        self.filter.as_ref()
+    }
+
+    /// How the join is performed
+    pub fn join_type(&self) -> &JoinType {
+        &self.join_type
+    }
+}
+
+/// Returns true if the left side is the side loaded in memory for
+/// `join_type`, see [`NestedLoopJoinExec`]
+fn left_is_build_side(join_type: JoinType) -> bool {
+    join_type == JoinType::Right
+}
+
+impl ExecutionPlan for NestedLoopJoinExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn required_input_distribution(&self) -> Vec<Distribution> {
+        match self.join_type {
+            JoinType::Full => {
+                vec![Distribution::SinglePartition, Distribution::SinglePartition]
+            }
+            join_type if left_is_build_side(join_type) => vec![
+                Distribution::SinglePartition,
+                Distribution::UnspecifiedDistribution,
+            ],
+            _ => vec![
+                Distribution::UnspecifiedDistribution,
+                Distribution::SinglePartition,
+            ],
+        }
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        match self.join_type {
+            JoinType::Full => Partitioning::UnknownPartitioning(1),
+            // the columns of the left side come first
+            join_type if left_is_build_side(join_type) => {
+                adjust_right_output_partitioning(
+                    self.right.output_partitioning(),
+                    self.left.schema().fields().len(),
+                )
+            }
+            _ => self.left.output_partitioning(),
+        }
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        None
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![self.left.clone(), self.right.clone()]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        Ok(Arc::new(NestedLoopJoinExec::try_new(
+            children[0].clone(),
+            children[1].clone(),
+            self.filter.clone(),
+            &self.join_type,
+        )?))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        let (build, probe) = if left_is_build_side(self.join_type) {
+            (self.left.clone(), self.right.clone())
+        } else {
+            (self.right.clone(), self.left.clone())
+        };
+
+        let build_fut = self.build_fut.once(|| {
+            load_build_input(build, context.clone(), self.metrics.clone(), partition)
+        });
+        let probe = probe.execute(partition, context)?;
+
+        Ok(Box::pin(NestedLoopJoinStream {
+            schema: self.schema.clone(),
+            filter: self.filter.clone(),
+            join_type: self.join_type,
+            build_fut,
+            probe,
+            visited_build_side: None,
+            column_indices: self.column_indices.clone(),
+            join_metrics: NestedLoopJoinMetrics::new(partition, &self.metrics),
+        }))
+    }
+
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                let display_filter = self.filter.as_ref().map_or_else(
+                    || "".to_string(),
+                    |f| format!(", filter={:?}", f.expression()),
+                );
+                write!(
+                    f,
+                    "NestedLoopJoinExec: join_type={:?}{}",
+                    self.join_type, display_filter
+                )
+            }
+        }
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        Some(self.metrics.clone_inner())
+    }
+
+    fn statistics(&self) -> Statistics {
+        // TODO stats: the selectivity of the filter is unknown
+        Statistics::default()
+    }
+}
+
+/// Asynchronously collect the build side into a single batch, registering
+/// the memory it uses with the memory manager until it is dropped
+async fn load_build_input(
+    build: Arc<dyn ExecutionPlan>,
+    context: Arc<TaskContext>,
+    metrics: ExecutionPlanMetricsSet,
+    partition: usize,
+) -> Result<JoinBuildData> {
+    let start = Instant::now();
+
+    // merge all build parts into a single stream
+    let merge = {
+        if build.output_partitioning().partition_count() != 1 {
+            Arc::new(CoalescePartitionsExec::new(build.clone()))
+        } else {
+            build.clone()
+        }
+    };
+    let stream = merge.execute(0, context.clone())?;
+
+    let (batches, num_rows) = stream
+        .try_fold((Vec::new(), 0usize), |mut acc, batch| async {
+            acc.1 += batch.num_rows();
+            acc.0.push(batch);
+            Ok(acc)
+        })
+        .await?;
+
+    let merged_batch = concat_batches(&build.schema(), &batches, num_rows)?;
+
+    let tracking_metrics =
+        MemTrackingMetrics::new_with_rt(&metrics, partition, context.runtime_env());
+    tracking_metrics.init_mem_used(batch_byte_size(&merged_batch));
+
+    debug!(
+        "Built build-side of nested loop join containing {} rows in {} ms",
+        num_rows,
+        start.elapsed().as_millis()
+    );
+
+    Ok((merged_batch, tracking_metrics))
+}
+
+/// Metrics for NestedLoopJoinExec
+#[derive(Debug)]
+struct NestedLoopJoinMetrics {
+    /// Total time for joining probe-side batches to the build-side batch
+    join_time: metrics::Time,
+    /// Number of batches consumed by this operator
+    input_batches: metrics::Count,
+    /// Number of rows consumed by this operator
+    input_rows: metrics::Count,
+    /// Number of batches produced by this operator
+    output_batches: metrics::Count,
+    /// Number of rows produced by this operator
+    output_rows: metrics::Count,
+}
+
+impl NestedLoopJoinMetrics {
+    fn new(partition: usize, metrics: &ExecutionPlanMetricsSet) -> Self {
+        Self {
+            join_time: MetricBuilder::new(metrics).subset_time("join_time", partition),
+            input_batches: MetricBuilder::new(metrics)
+                .counter("input_batches", partition),
+            input_rows: MetricBuilder::new(metrics).counter("input_rows", partition),
+            output_batches: MetricBuilder::new(metrics)
+                .counter("output_batches", partition),
+            output_rows: MetricBuilder::new(metrics).output_rows(partition),
+        }
+    }
+}
+
+/// A stream that joins the batches of the probe side, as they arrive, to
+/// the build side
+struct NestedLoopJoinStream {
+    /// Output schema
+    schema: SchemaRef,
+    /// join filter
+    filter: Option<JoinFilter>,
+    /// type of the join
+    join_type: JoinType,
+    /// future for the data of the build side
+    build_fut: OnceFut<JoinBuildData>,
+    /// probe side
+    probe: SendableRecordBatchStream,
+    /// Keeps track of the build side rows matched by any probe row, for
+    /// full joins, until their unmatched rows are produced
+    visited_build_side: Option<BooleanBufferBuilder>,
+    /// Information of index and left / right placement of columns
+    column_indices: Vec<ColumnIndex>,
+    /// Metrics
+    join_metrics: NestedLoopJoinMetrics,
+}
+
+impl RecordBatchStream for NestedLoopJoinStream {
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+}
+
+/// Returns the indices of the pairs of build and probe rows satisfying
+/// `filter`, every pair if `None`
+fn matched_indices(
+    build: &RecordBatch,
+    probe: &RecordBatch,
+    filter: Option<&JoinFilter>,
+    left_is_build: bool,
+) -> Result<(Vec<u32>, Vec<u32>)> {
+    let build_rows = build.num_rows();
+    let probe_rows_per_chunk = (FILTER_BATCH_PAIRS / build_rows.max(1)).max(1);
+
+    let mut build_indices = vec![];
+    let mut probe_indices = vec![];
+    for start in (0..probe.num_rows()).step_by(probe_rows_per_chunk) {
+        let end = (start + probe_rows_per_chunk).min(probe.num_rows());
+        let chunk_build: Vec<u32> =
+            (start..end).flat_map(|_| 0..build_rows as u32).collect();
+        let chunk_probe: Vec<u32> = (start..end)
+            .flat_map(|row| std::iter::repeat(row as u32).take(build_rows))
+            .collect();
+
+        let filter = match filter {
+            Some(filter) => filter,
+            None => {
+                build_indices.extend(chunk_build);
+                probe_indices.extend(chunk_probe);
+                continue;
+            }
+        };
+
+        let (left, right, left_indices, right_indices) = if left_is_build {
+            (build, probe, &chunk_build, &chunk_probe)
+        } else {
+            (probe, build, &chunk_probe, &chunk_build)
+        };
+        let (intermediate_batch, _) = build_batch_from_indices(
+            filter.schema(),
+            left,
+            right,
+            UInt64Array::from_iter_values(left_indices.iter().map(|i| *i as u64)),
+            UInt32Array::from_iter_values(right_indices.iter().copied()),
+            filter.column_indices(),
+        )?;
+        let filter_result = filter
+            .expression()
+            .evaluate(&intermediate_batch)?
+            .into_array(intermediate_batch.num_rows());
+        let mask = as_boolean_array(&filter_result);
+        for (idx, (build_idx, probe_idx)) in
+            chunk_build.into_iter().zip(chunk_probe).enumerate()
+        {
+            if mask.is_valid(idx) && mask.value(idx) {
+                build_indices.push(build_idx);
+                probe_indices.push(probe_idx);
+            }
+        }
+    }
+    Ok((build_indices, probe_indices))
+}
+
+/// Joins the rows of `probe` to the rows of `build` satisfying `filter`,
+/// adding the unmatched rows of `probe` for outer joins, and marking the
+/// matched rows of `build` in `visited_build_side` if set
+fn join_probe_batch(
+    build: &RecordBatch,
+    probe: &RecordBatch,
+    filter: Option<&JoinFilter>,
+    join_type: JoinType,
+    schema: &SchemaRef,
+    column_indices: &[ColumnIndex],
+    visited_build_side: Option<&mut BooleanBufferBuilder>,
+) -> Result<RecordBatch> {
+    let left_is_build = left_is_build_side(join_type);
+    let (build_matched, probe_matched) =
+        matched_indices(build, probe, filter, left_is_build)?;
+
+    if let Some(visited_build_side) = visited_build_side {
+        for idx in &build_matched {
+            visited_build_side.set_bit(*idx as usize, true);
+        }
+    }
+
+    let mut build_indices: Vec<Option<u32>> =
+        build_matched.into_iter().map(Some).collect();
+    let mut probe_indices: Vec<Option<u32>> =
+        probe_matched.iter().copied().map(Some).collect();
+    // the probe side of left, right and full joins is an outer side
+    if join_type != JoinType::Inner {
+        let mut matched = vec![false; probe.num_rows()];
+        for idx in probe_matched {
+            matched[idx as usize] = true;
+        }
+        for (idx, _) in matched.iter().enumerate().filter(|(_, m)| !**m) {
+            build_indices.push(None);
+            probe_indices.push(Some(idx as u32));
+        }
+    }
+
+    let (left, right, left_indices, right_indices) = if left_is_build {
+        (build, probe, build_indices, probe_indices)
+    } else {
+        (probe, build, probe_indices, build_indices)
+    };
+    let (batch, _) = build_batch_from_indices(
+        schema,
+        left,
+        right,
+        left_indices.into_iter().map(|i| i.map(u64::from)).collect(),
+        right_indices.into_iter().collect(),
+        column_indices,
+    )?;
+    Ok(batch)
+}
+
+/// Produces the rows of the build side of a full join that no probe row
+/// matched, with nulls for the columns of the probe side
+fn produce_unmatched_build_rows(
+    build: &RecordBatch,
+    probe_schema: SchemaRef,
+    schema: &SchemaRef,
+    column_indices: &[ColumnIndex],
+    visited_build_side: &BooleanBufferBuilder,
+) -> ArrowResult<RecordBatch> {
+    let right_indices: UInt32Array = (0..visited_build_side.len())
+        .filter(|idx| !visited_build_side.get_bit(*idx))
+        .map(|idx| Some(idx as u32))
+        .collect();
+    let left_indices: UInt64Array = right_indices.iter().map(|_| None::<u64>).collect();
+    let (batch, _) = build_batch_from_indices(
+        schema,
+        &RecordBatch::new_empty(probe_schema),
+        build,
+        left_indices,
+        right_indices,
+        column_indices,
+    )?;
+    Ok(batch)
+}
+
+impl NestedLoopJoinStream {
+    /// Separate implementation function that unpins the
+    /// [`NestedLoopJoinStream`] so that partial borrows work correctly
+    fn poll_next_impl(
+        &mut self,
+        cx: &mut std::task::Context<'_>,
+    ) -> Poll<Option<ArrowResult<RecordBatch>>> {
+        let (build, _) = match ready!(self.build_fut.get(cx)) {
+            Ok(build_data) => build_data,
+            Err(e) => return Poll::Ready(Some(Err(e))),
+        };
+
+        if self.join_type == JoinType::Full && self.visited_build_side.is_none() {
+            let mut buffer = BooleanBufferBuilder::new(build.num_rows());
+            buffer.append_n(build.num_rows(), false);
+            self.visited_build_side = Some(buffer);
+        }
+
+        match ready!(self.probe.poll_next_unpin(cx)) {
+            Some(Ok(batch)) => {
+                let timer = self.join_metrics.join_time.timer();
+                let result = join_probe_batch(
+                    build,
+                    &batch,
+                    self.filter.as_ref(),
+                    self.join_type,
+                    &self.schema,
+                    &self.column_indices,
+                    self.visited_build_side.as_mut(),
+                );
+                timer.done();
+                self.join_metrics.input_batches.add(1);
+                self.join_metrics.input_rows.add(batch.num_rows());
+                if let Ok(ref batch) = result {
+                    self.join_metrics.output_batches.add(1);
+                    self.join_metrics.output_rows.add(batch.num_rows());
+                }
+                Poll::Ready(Some(result.map_err(Into::into)))
+            }
+            Some(Err(e)) => Poll::Ready(Some(Err(e))),
+            None => {
+                // only the build side of full joins has unmatched rows to
+                // produce, once
+                let visited_build_side = match self.visited_build_side.take() {
+                    Some(visited_build_side) => visited_build_side,
+                    None => return Poll::Ready(None),
+                };
+                let timer = self.join_metrics.join_time.timer();
+                let result = produce_unmatched_build_rows(
+                    build,
+                    self.probe.schema(),
+                    &self.schema,
+                    &self.column_indices,
+                    &visited_build_side,
+                );
+                timer.done();
+                if let Ok(ref batch) = result {
+                    self.join_metrics.output_batches.add(1);
+                    self.join_metrics.output_rows.add(batch.num_rows());
+                }
+                Poll::Ready(Some(result))
+            }
+        }
+    }
+}
+
+impl Stream for NestedLoopJoinStream {
+    type Item = ArrowResult<RecordBatch>;
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        self.poll_next_impl(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::physical_expr::expressions::BinaryExpr;
+    use crate::physical_plan::joins::utils::JoinSide;
+    use crate::physical_plan::joins::{HashJoinExec, PartitionMode};
+    use crate::physical_plan::{
+        common, expressions::Column, memory::MemoryExec, PhysicalExpr,
+    };
+    use crate::prelude::SessionContext;
+    use crate::{
+        assert_batches_sorted_eq,
+        test::{build_table_i32, columns},
+    };
+    use arrow::datatypes::{DataType, Field, Schema};
+    use arrow::util::pretty::pretty_format_batches;
+    use datafusion_expr::Operator;
+
+    fn build_table(
+        a: (&str, &Vec<i32>),
+        b: (&str, &Vec<i32>),
+        c: (&str, &Vec<i32>),
+    ) -> Arc<dyn ExecutionPlan> {
+        let batch = build_table_i32(a, b, c);
+        let schema = batch.schema();
+        Arc::new(MemoryExec::try_new(&[vec![batch]], schema, None).unwrap())
+    }
+
+    /// A filter comparing the `c` column of the left side to the `c`
+    /// column of the right side
+    fn prepare_join_filter(op: Operator) -> JoinFilter {
+        let column_indices = vec![
+            ColumnIndex {
+                index: 2,
+                side: JoinSide::Left,
+            },
+            ColumnIndex {
+                index: 2,
+                side: JoinSide::Right,
+            },
+        ];
+        let intermediate_schema = Schema::new(vec![
+            Field::new("c", DataType::Int32, true),
+            Field::new("c", DataType::Int32, true),
+        ]);
+        let filter_expression = Arc::new(BinaryExpr::new(
+            Arc::new(Column::new("c", 0)),
+            op,
+            Arc::new(Column::new("c", 1)),
+        )) as Arc<dyn PhysicalExpr>;
+
+        JoinFilter::new(filter_expression, column_indices, intermediate_schema)
+    }
+
+    fn sorted_lines(batches: &[RecordBatch]) -> Vec<String> {
+        let formatted = pretty_format_batches(batches).unwrap().to_string();
+        let mut lines: Vec<String> = formatted.lines().map(String::from).collect();
+        lines.sort_unstable();
+        lines
+    }
+
+    #[tokio::test]
+    async fn join_left_without_keys() -> Result<()> {
+        let session_ctx = SessionContext::new();
+        let task_ctx = session_ctx.task_ctx();
+        let left = build_table(
+            ("a", &vec![0, 1, 2, 2]),
+            ("b", &vec![4, 5, 7, 8]),
+            ("c", &vec![7, 8, 9, 1]),
+        );
+        let right = build_table(
+            ("a", &vec![10, 20, 30, 40]),
+            ("b", &vec![2, 2, 3, 4]),
+            ("c", &vec![7, 5, 6, 4]),
+        );
+        let filter = prepare_join_filter(Operator::Lt);
+
+        let join =
+            NestedLoopJoinExec::try_new(left, right, Some(filter), &JoinType::Left)?;
+
+        let columns = columns(&join.schema());
+        assert_eq!(columns, vec!["a", "b", "c", "a", "b", "c"]);
+
+        let stream = join.execute(0, task_ctx)?;
+        let batches = common::collect(stream).await?;
+
+        let expected = vec![
+            "+---+---+---+----+---+---+",
+            "| a | b | c | a  | b | c |",
+            "+---+---+---+----+---+---+",
+            "| 0 | 4 | 7 |    |   |   |",
+            "| 1 | 5 | 8 |    |   |   |",
+            "| 2 | 7 | 9 |    |   |   |",
+            "| 2 | 8 | 1 | 10 | 2 | 7 |",
+            "| 2 | 8 | 1 | 20 | 2 | 5 |",
+            "| 2 | 8 | 1 | 30 | 3 | 6 |",
+            "| 2 | 8 | 1 | 40 | 4 | 4 |",
+            "+---+---+---+----+---+---+",
+        ];
+        assert_batches_sorted_eq!(expected, &batches);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn join_inner_same_as_hash_join_with_filter() -> Result<()> {
+        let session_ctx = SessionContext::new();
+        let task_ctx = session_ctx.task_ctx();
+        let left = build_table(
+            ("a", &vec![0, 1, 2, 2, 3]),
+            ("b", &vec![4, 5, 7, 8, 9]),
+            ("c", &vec![7, 8, 9, 1, 5]),
+        );
+        let right = build_table(
+            ("a", &vec![10, 20, 30, 40, 50]),
+            ("b", &vec![2, 2, 3, 4, 3]),
+            ("c", &vec![7, 5, 6, 4, 1]),
+        );
+
+        // hash join on left.a = right.b filtered on left.c > right.c
+        let on = vec![(
+            Column::new_with_schema("a", &left.schema()).unwrap(),
+            Column::new_with_schema("b", &right.schema()).unwrap(),
+        )];
+        let hash_join = HashJoinExec::try_new(
+            left.clone(),
+            right.clone(),
+            on,
+            Some(prepare_join_filter(Operator::Gt)),
+            &JoinType::Inner,
+            PartitionMode::CollectLeft,
+            &false,
+        )?;
+        let expected = common::collect(hash_join.execute(0, task_ctx.clone())?).await?;
+
+        // the same predicates evaluated on every pair of rows
+        let column_indices = vec![
+            ColumnIndex {
+                index: 0,
+                side: JoinSide::Left,
+            },
+            ColumnIndex {
+                index: 2,
+                side: JoinSide::Left,
+            },
+            ColumnIndex {
+                index: 1,
+                side: JoinSide::Right,
+            },
+            ColumnIndex {
+                index: 2,
+                side: JoinSide::Right,
+            },
+        ];
+        let intermediate_schema = Schema::new(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("c", DataType::Int32, true),
+            Field::new("b", DataType::Int32, true),
+            Field::new("c", DataType::Int32, true),
+        ]);
+        let filter_expression = Arc::new(BinaryExpr::new(
+            Arc::new(BinaryExpr::new(
+                Arc::new(Column::new("a", 0)),
+                Operator::Eq,
+                Arc::new(Column::new("b", 2)),
+            )),
+            Operator::And,
+            Arc::new(BinaryExpr::new(
+                Arc::new(Column::new("c", 1)),
+                Operator::Gt,
+                Arc::new(Column::new("c", 3)),
+            )),
+        )) as Arc<dyn PhysicalExpr>;
+        let filter =
+            JoinFilter::new(filter_expression, column_indices, intermediate_schema);
+        let nested_loop_join =
+            NestedLoopJoinExec::try_new(left, right, Some(filter), &JoinType::Inner)?;
+        let actual = common::collect(nested_loop_join.execute(0, task_ctx)?).await?;
+
+        assert!(actual.iter().map(|b| b.num_rows()).sum::<usize>() > 0);
+        assert_eq!(sorted_lines(&expected), sorted_lines(&actual));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn join_registers_build_side_memory() -> Result<()> {
+        let session_ctx = SessionContext::new();
+        let task_ctx = session_ctx.task_ctx();
+        let runtime = task_ctx.runtime_env();
+        let tracked_before = runtime.memory_manager.get_tracker_total();
+        let left = build_table(
+            ("a", &vec![0, 1, 2, 2]),
+            ("b", &vec![4, 5, 7, 8]),
+            ("c", &vec![7, 8, 9, 1]),
+        );
+        let right = build_table(
+            ("a", &vec![10, 20, 30, 40]),
+            ("b", &vec![2, 2, 3, 4]),
+            ("c", &vec![7, 5, 6, 4]),
+        );
+
+        let join = NestedLoopJoinExec::try_new(left, right, None, &JoinType::Inner)?;
+        let batches = common::collect(join.execute(0, task_ctx)?).await?;
+        assert_eq!(batches.iter().map(|b| b.num_rows()).sum::<usize>(), 16);
+        assert!(runtime.memory_manager.get_tracker_total() > tracked_before);
+
+        // the build side is released with the plan
+        drop(join);
+        assert_eq!(runtime.memory_manager.get_tracker_total(), tracked_before);
+
+        Ok(())
+    }
+}
@@ -46,6 +46,9 @@ pub struct MemoryExec {
     projected_schema: SchemaRef,
     /// Optional projection
     projection: Option<Vec<usize>>,
+    /// Statistics of the data before projection, if known rather than
+    /// computed from the batches
+    statistics: Option<Statistics>,
 }
 
 impl fmt::Debug for MemoryExec {
@@ -109,7 +112,7 @@ impl ExecutionPlan for MemoryExec {
         f: &mut std::fmt::Formatter,
     ) -> std::fmt::Result {
         match t {
-            DisplayFormatType::Default => {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
                 let partitions: Vec<_> =
                     self.partitions.iter().map(|b| b.len()).collect();
                 write!(
@@ -122,8 +125,25 @@ impl ExecutionPlan for MemoryExec {
         }
     }
 
-    /// We recompute the statistics dynamically from the arrow metadata as it is pretty cheap to do so
+    /// We recompute the statistics dynamically from the arrow metadata as it is pretty cheap to do so,
+    /// unless they are provided with [`MemoryExec::with_statistics`]
     fn statistics(&self) -> Statistics {
+        if let Some(statistics) = &self.statistics {
+            let column_statistics =
+                match (&statistics.column_statistics, &self.projection) {
+                    (Some(column_statistics), Some(projection)) => Some(
+                        projection
+                            .iter()
+                            .map(|i| column_statistics[*i].clone())
+                            .collect(),
+                    ),
+                    (column_statistics, _) => column_statistics.clone(),
+                };
+            return Statistics {
+                column_statistics,
+                ..statistics.clone()
+            };
+        }
         common::compute_record_batch_statistics(
             &self.partitions,
             &self.schema,
@@ -146,8 +166,16 @@ impl MemoryExec {
             schema,
             projected_schema,
             projection,
+            statistics: None,
         })
     }
+
+    /// Use `statistics` as the statistics of the batches before projection,
+    /// rather than computing them from the batches
+    pub fn with_statistics(mut self, statistics: Statistics) -> Self {
+        self.statistics = Some(statistics);
+        self
+    }
 }
 
 /// Iterator over batches
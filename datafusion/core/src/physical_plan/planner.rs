@@ -35,15 +35,18 @@ use crate::logical_expr::{
     CrossJoin, Expr, LogicalPlan, Partitioning as LogicalPartitioning, PlanType,
     Repartition, ToStringifiedPlan, Union, UserDefinedLogicalNode,
 };
-use crate::logical_expr::{Limit, Values};
+use crate::logical_expr::{JoinType, Limit, Values};
 use crate::physical_expr::create_physical_expr;
 use crate::physical_optimizer::optimizer::PhysicalOptimizerRule;
 use crate::physical_plan::aggregates::{AggregateExec, AggregateMode, PhysicalGroupBy};
 use crate::physical_plan::explain::ExplainExec;
-use crate::physical_plan::expressions::{Column, PhysicalSortExpr};
+use crate::physical_plan::expressions::{
+    Column, FilteredAggregateExpr, PhysicalSortExpr,
+};
 use crate::physical_plan::filter::FilterExec;
 use crate::physical_plan::joins::CrossJoinExec;
 use crate::physical_plan::joins::HashJoinExec;
+use crate::physical_plan::joins::NestedLoopJoinExec;
 use crate::physical_plan::limit::{GlobalLimitExec, LocalLimitExec};
 use crate::physical_plan::projection::ProjectionExec;
 use crate::physical_plan::repartition::RepartitionExec;
@@ -93,6 +96,21 @@ fn create_function_physical_name(
     Ok(format!("{}({}{})", fun, distinct_str, names.join(",")))
 }
 
+/// Appends the `FILTER` clause of an aggregate to its name, if any
+fn create_filter_physical_name(
+    name: String,
+    filter: &Option<Box<Expr>>,
+) -> Result<String> {
+    match filter {
+        Some(filter) => Ok(format!(
+            "{} FILTER (WHERE {})",
+            name,
+            create_physical_name(filter, false)?
+        )),
+        None => Ok(name),
+    }
+}
+
 fn physical_name(e: &Expr) -> Result<String> {
     create_physical_name(e, true)
 }
@@ -193,19 +211,14 @@ fn create_physical_name(e: &Expr, is_first_expr: bool) -> Result<String> {
             fun,
             distinct,
             args,
-            ..
-        } => create_function_physical_name(&fun.to_string(), *distinct, args),
+            filter,
+        } => {
+            let name = create_function_physical_name(&fun.to_string(), *distinct, args)?;
+            create_filter_physical_name(name, filter)
+        }
         Expr::AggregateUDF { fun, args, filter } => {
-            if filter.is_some() {
-                return Err(DataFusionError::Execution(
-                    "aggregate expression with filter is not supported".to_string(),
-                ));
-            }
-            let mut names = Vec::with_capacity(args.len());
-            for e in args {
-                names.push(create_physical_name(e, false)?);
-            }
-            Ok(format!("{}({})", fun.name, names.join(",")))
+            let name = create_function_physical_name(&fun.name, false, args)?;
+            create_filter_physical_name(name, filter)
         }
         Expr::GroupingSet(grouping_set) => match grouping_set {
             GroupingSet::Rollup(exprs) => Ok(format!(
@@ -832,8 +845,25 @@ impl DefaultPhysicalPlanner {
                             )),
                         })
                         .collect::<Result<Vec<_>>>()?;
+                    // If every partition of the input is already sorted, such as a scan
+                    // of individually sorted files, merging the partitions sorts the rows
+                    let input_sorted =
+                        physical_input.output_ordering().map_or(false, |ordering| {
+                            ordering.len() >= sort_expr.len()
+                                && sort_expr.iter().zip(ordering).all(|(a, b)| a == b)
+                        });
                     // If we have a `LIMIT` can run sort/limts in parallel (similar to TopK)
-                    Ok(if fetch.is_some() && session_state.config.target_partitions > 1 {
+                    Ok(if input_sorted {
+                        let merge: Arc<dyn ExecutionPlan> = Arc::new(
+                            SortPreservingMergeExec::new(sort_expr, physical_input),
+                        );
+                        match fetch {
+                            Some(fetch) => {
+                                Arc::new(GlobalLimitExec::new(merge, 0, Some(*fetch)))
+                            }
+                            None => merge,
+                        }
+                    } else if fetch.is_some() && session_state.config.target_partitions > 1 {
                         let sort = SortExec::new_with_partitioning(
                             sort_expr,
                             physical_input,
@@ -931,7 +961,20 @@ impl DefaultPhysicalPlanner {
                         _ => None
                     };
 
-                    if session_state.config.target_partitions > 1
+                    if join_on.is_empty()
+                        && matches!(
+                            join_type,
+                            JoinType::Inner | JoinType::Left | JoinType::Right | JoinType::Full
+                        )
+                    {
+                        // without equality predicates, the rows can not be hashed
+                        Ok(Arc::new(NestedLoopJoinExec::try_new(
+                            physical_left,
+                            physical_right,
+                            join_filter,
+                            join_type,
+                        )?))
+                    } else if session_state.config.target_partitions > 1
                         && session_state.config.repartition_joins
                     {
                         // Use hash partition by default to parallelize hash joins
@@ -1518,12 +1561,12 @@ pub fn create_aggregate_expr_with_name(
     physical_input_schema: &Schema,
     execution_props: &ExecutionProps,
 ) -> Result<Arc<dyn AggregateExpr>> {
-    match e {
+    let (aggregate, filter) = match e {
         Expr::AggregateFunction {
             fun,
             distinct,
             args,
-            ..
+            filter,
         } => {
             let args = args
                 .iter()
@@ -1536,15 +1579,17 @@ pub fn create_aggregate_expr_with_name(
                     )
                 })
                 .collect::<Result<Vec<_>>>()?;
-            aggregates::create_aggregate_expr(
+            let aggregate = aggregates::create_aggregate_expr_with_wrapping(
                 fun,
                 *distinct,
                 &args,
                 physical_input_schema,
                 name,
-            )
+                execution_props.wrapping_arithmetic,
+            )?;
+            (aggregate, filter)
         }
-        Expr::AggregateUDF { fun, args, .. } => {
+        Expr::AggregateUDF { fun, args, filter } => {
             let args = args
                 .iter()
                 .map(|e| {
@@ -1557,12 +1602,28 @@ pub fn create_aggregate_expr_with_name(
                 })
                 .collect::<Result<Vec<_>>>()?;
 
-            udaf::create_aggregate_expr(fun, &args, physical_input_schema, name)
+            let aggregate =
+                udaf::create_aggregate_expr(fun, &args, physical_input_schema, name)?;
+            (aggregate, filter)
         }
-        other => Err(DataFusionError::Internal(format!(
-            "Invalid aggregate expression '{:?}'",
-            other
-        ))),
+        other => {
+            return Err(DataFusionError::Internal(format!(
+                "Invalid aggregate expression '{:?}'",
+                other
+            )))
+        }
+    };
+    match filter {
+        Some(filter) => {
+            let filter = create_physical_expr(
+                filter,
+                logical_input_schema,
+                physical_input_schema,
+                execution_props,
+            )?;
+            Ok(Arc::new(FilteredAggregateExpr::new(aggregate, filter)))
+        }
+        None => Ok(aggregate),
     }
 }
 
@@ -1641,19 +1702,27 @@ impl DefaultPhysicalPlanner {
                     .await?;
 
                 stringified_plans.push(
-                    displayable(input.as_ref()).to_stringified(InitialPhysicalPlan),
+                    displayable(input.as_ref())
+                        .set_verbose(e.verbose)
+                        .to_stringified(InitialPhysicalPlan),
                 );
 
                 let input =
                     self.optimize_internal(input, session_state, |plan, optimizer| {
                         let optimizer_name = optimizer.name().to_string();
                         let plan_type = OptimizedPhysicalPlan { optimizer_name };
-                        stringified_plans
-                            .push(displayable(plan).to_stringified(plan_type));
+                        stringified_plans.push(
+                            displayable(plan)
+                                .set_verbose(e.verbose)
+                                .to_stringified(plan_type),
+                        );
                     })?;
 
-                stringified_plans
-                    .push(displayable(input.as_ref()).to_stringified(FinalPhysicalPlan));
+                stringified_plans.push(
+                    displayable(input.as_ref())
+                        .set_verbose(e.verbose)
+                        .to_stringified(FinalPhysicalPlan),
+                );
             }
 
             Ok(Some(Arc::new(ExplainExec::new(
@@ -1687,6 +1756,11 @@ impl DefaultPhysicalPlanner {
         let mut new_plan = plan;
         for optimizer in optimizers {
             new_plan = optimizer.optimize(new_plan, &session_state.config)?;
+            trace!(
+                "Physical plan after {}:\n{}\n",
+                optimizer.name(),
+                displayable(new_plan.as_ref()).indent()
+            );
             observer(new_plan.as_ref(), optimizer.as_ref())
         }
         debug!(
@@ -1730,6 +1804,7 @@ mod tests {
     use fmt::Debug;
     use std::collections::HashMap;
     use std::convert::TryFrom;
+    use std::hash::Hasher;
     use std::{any::Any, fmt};
 
     fn make_session_state() -> SessionState {
@@ -2244,6 +2319,12 @@ mod tests {
         ) -> Arc<dyn UserDefinedLogicalNode> {
             unimplemented!("NoOp");
         }
+
+        fn dyn_hash(&self, _state: &mut dyn Hasher) {}
+
+        fn dyn_eq(&self, other: &dyn UserDefinedLogicalNode) -> bool {
+            other.as_any().is::<Self>()
+        }
     }
 
     #[derive(Debug)]
@@ -2294,7 +2375,7 @@ mod tests {
             f: &mut std::fmt::Formatter,
         ) -> std::fmt::Result {
             match t {
-                DisplayFormatType::Default => {
+                DisplayFormatType::Default | DisplayFormatType::Verbose => {
                     write!(f, "NoOpExecutionPlan")
                 }
             }
@@ -387,7 +387,7 @@ impl ExecutionPlan for RepartitionExec {
         f: &mut std::fmt::Formatter,
     ) -> std::fmt::Result {
         match t {
-            DisplayFormatType::Default => {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
                 write!(f, "RepartitionExec: partitioning={:?}", self.partitioning)
             }
         }
@@ -15,8 +15,14 @@
 // specific language governing permissions and limitations
 // under the License.
 
-use arrow::row::{Row, Rows};
+use arrow::array::{build_compare, Array, ArrayRef, DynComparator};
+use arrow::compute::SortOptions;
+use arrow::error::Result as ArrowResult;
+use arrow::row::Rows;
+use parking_lot::RwLock;
 use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::sync::Arc;
 
 /// A `SortKeyCursor` is created from a `RecordBatch`, and a set of
 /// `PhysicalExpr` that when evaluated on the `RecordBatch` yield the sort keys.
@@ -25,8 +31,10 @@ use std::cmp::Ordering;
 /// of the provided `RecordBatch`
 ///
 /// `SortKeyCursor::compare` can then be used to compare the sort key pointed to
-/// by this row cursor, with that of another `SortKeyCursor`. A cursor stores
-/// a row comparator for each other cursor that it is compared to.
+/// by this row cursor, with that of another `SortKeyCursor`. The sort keys
+/// are compared in the row format if their types support it, otherwise a
+/// cursor stores a row comparator for each other cursor that it is compared
+/// to.
 pub struct SortKeyCursor {
     stream_idx: usize,
     cur_row: usize,
@@ -35,7 +43,22 @@ pub struct SortKeyCursor {
     // An id uniquely identifying the record batch scanned by this cursor.
     batch_id: usize,
 
-    rows: Rows,
+    keys: SortKeys,
+}
+
+/// The sort keys of the rows of a [`SortKeyCursor`]
+enum SortKeys {
+    /// The keys encoded in the row format, compared by their bytes
+    Rows(Rows),
+    /// The arrays of the keys, of types the row format does not support,
+    /// compared column by column
+    Arrays {
+        arrays: Vec<ArrayRef>,
+        options: Arc<[SortOptions]>,
+        /// The comparators of the arrays with those of the batches of other
+        /// cursors, keyed by their batch id
+        comparators: RwLock<HashMap<usize, Arc<Vec<DynComparator>>>>,
+    },
 }
 
 impl std::fmt::Debug for SortKeyCursor {
@@ -56,10 +79,38 @@ impl SortKeyCursor {
             cur_row: 0,
             num_rows: rows.num_rows(),
             batch_id,
-            rows,
+            keys: SortKeys::Rows(rows),
         }
     }
 
+    /// Create a new SortKeyCursor comparing the sort keys `arrays` with
+    /// `options` column by column, for keys of types the row format does not
+    /// support. All cursors compared to each other must be created the same
+    /// way.
+    ///
+    /// Returns an error if the keys can not be compared
+    pub fn try_new_from_arrays(
+        stream_idx: usize,
+        batch_id: usize,
+        arrays: Vec<ArrayRef>,
+        options: Arc<[SortOptions]>,
+    ) -> ArrowResult<Self> {
+        for array in &arrays {
+            build_compare(array.as_ref(), array.as_ref())?;
+        }
+        Ok(Self {
+            stream_idx,
+            cur_row: 0,
+            num_rows: arrays.first().map_or(0, |array| array.len()),
+            batch_id,
+            keys: SortKeys::Arrays {
+                arrays,
+                options,
+                comparators: RwLock::new(HashMap::new()),
+            },
+        })
+    }
+
     #[inline(always)]
     /// Return the stream index of this cursor
     pub fn stream_idx(&self) -> usize {
@@ -87,15 +138,73 @@ impl SortKeyCursor {
         t
     }
 
-    /// Returns the current row
-    fn current(&self) -> Row<'_> {
-        self.rows.row(self.cur_row)
+    /// Compares the sort keys of the current rows of this cursor and `other`
+    fn compare(&self, other: &Self) -> Ordering {
+        let (arrays, options, comparators, other_arrays) = match (&self.keys, &other.keys)
+        {
+            (SortKeys::Rows(rows), SortKeys::Rows(other_rows)) => {
+                return rows.row(self.cur_row).cmp(&other_rows.row(other.cur_row))
+            }
+            (
+                SortKeys::Arrays {
+                    arrays,
+                    options,
+                    comparators,
+                },
+                SortKeys::Arrays {
+                    arrays: other_arrays,
+                    ..
+                },
+            ) => (arrays, options, comparators, other_arrays),
+            _ => unreachable!("cursors of the same merge have the same kind of keys"),
+        };
+
+        let cached = comparators.read().get(&other.batch_id).cloned();
+        let batch_comparators = match cached {
+            Some(batch_comparators) => batch_comparators,
+            None => {
+                let batch_comparators = arrays
+                    .iter()
+                    .zip(other_arrays)
+                    .map(|(left, right)| {
+                        // the types were checked when the cursors were created
+                        build_compare(left.as_ref(), right.as_ref())
+                            .expect("sort keys of the same types are comparable")
+                    })
+                    .collect::<Vec<_>>();
+                let batch_comparators = Arc::new(batch_comparators);
+                comparators
+                    .write()
+                    .insert(other.batch_id, batch_comparators.clone());
+                batch_comparators
+            }
+        };
+
+        for (i, comparator) in batch_comparators.iter().enumerate() {
+            let left_valid = arrays[i].is_valid(self.cur_row);
+            let right_valid = other_arrays[i].is_valid(other.cur_row);
+            let ordering = match (left_valid, right_valid) {
+                (false, false) => Ordering::Equal,
+                (false, true) if options[i].nulls_first => Ordering::Less,
+                (false, true) => Ordering::Greater,
+                (true, false) if options[i].nulls_first => Ordering::Greater,
+                (true, false) => Ordering::Less,
+                (true, true) if options[i].descending => {
+                    comparator(self.cur_row, other.cur_row).reverse()
+                }
+                (true, true) => comparator(self.cur_row, other.cur_row),
+            };
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+        }
+        Ordering::Equal
     }
 }
 
 impl PartialEq for SortKeyCursor {
     fn eq(&self, other: &Self) -> bool {
-        self.current() == other.current()
+        self.compare(other) == Ordering::Equal
     }
 }
 
@@ -109,8 +218,7 @@ impl PartialOrd for SortKeyCursor {
 
 impl Ord for SortKeyCursor {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.current()
-            .cmp(&other.current())
+        self.compare(other)
             .then_with(|| self.stream_idx.cmp(&other.stream_idx))
     }
 }
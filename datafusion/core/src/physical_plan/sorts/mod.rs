@@ -22,6 +22,7 @@ use std::fmt::{Debug, Formatter};
 
 mod cursor;
 mod index;
+pub mod row_format;
 pub mod sort;
 pub mod sort_preserving_merge;
 
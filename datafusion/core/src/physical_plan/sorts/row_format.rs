@@ -0,0 +1,336 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Comparison of multi-column keys in the order preserving [row format],
+//! which encodes the values of every row into bytes whose lexicographic
+//! order is the order of the keys. Nulls first or last and descending
+//! keys are part of the encoding, so that two rows compare with a single
+//! `memcmp` rather than dispatching on the type of every column.
+//!
+//! The row format does not support every type, such as nested types,
+//! which are compared with the per-column comparators of arrow instead.
+//!
+//! [row format]: arrow::row
+
+use crate::error::Result;
+use arrow::array::{ArrayRef, UInt32Array};
+use arrow::compute::{lexsort_to_indices, SortColumn, SortOptions};
+use arrow::datatypes::DataType;
+use arrow::row::{RowConverter, SortField};
+
+/// Returns true if values of `data_type` can be encoded in the row format
+pub fn row_format_supported(data_type: &DataType) -> bool {
+    use DataType::*;
+    match data_type {
+        Dictionary(_, value_type) => {
+            !matches!(value_type.as_ref(), Dictionary(_, _))
+                && row_format_supported(value_type)
+        }
+        Null
+        | Boolean
+        | Int8
+        | Int16
+        | Int32
+        | Int64
+        | UInt8
+        | UInt16
+        | UInt32
+        | UInt64
+        | Float32
+        | Float64
+        | Date32
+        | Date64
+        | Time32(_)
+        | Time64(_)
+        | Timestamp(_, _)
+        | Decimal128(_, _)
+        | Binary
+        | LargeBinary
+        | Utf8
+        | LargeUtf8 => true,
+        _ => false,
+    }
+}
+
+/// Creates a [`RowConverter`] of keys of the given types and sort options,
+/// or `None` if any of the types is not supported by the row format
+pub fn try_row_converter(keys: Vec<(DataType, SortOptions)>) -> Option<RowConverter> {
+    if !keys
+        .iter()
+        .all(|(data_type, _)| row_format_supported(data_type))
+    {
+        return None;
+    }
+    let fields = keys
+        .into_iter()
+        .map(|(data_type, options)| SortField::new_with_options(data_type, options))
+        .collect();
+    Some(RowConverter::new(fields))
+}
+
+/// Sorts the rows of `sort_columns` like [`lexsort_to_indices`], returning
+/// the indices of the first `fetch` rows in order if set.
+///
+/// Keys of several columns are encoded in the row format and sorted by
+/// their bytes. A single column, which [`lexsort_to_indices`] sorts with a
+/// kernel specialized for its type, or columns of a type the row format
+/// does not support, are sorted by [`lexsort_to_indices`].
+pub fn sort_to_indices(
+    sort_columns: &[SortColumn],
+    fetch: Option<usize>,
+) -> Result<UInt32Array> {
+    let keys = sort_columns
+        .iter()
+        .map(|column| {
+            (
+                column.values.data_type().clone(),
+                column.options.unwrap_or_default(),
+            )
+        })
+        .collect();
+    let mut converter = match try_row_converter(keys) {
+        Some(converter) if sort_columns.len() > 1 => converter,
+        _ => return Ok(lexsort_to_indices(sort_columns, fetch)?),
+    };
+
+    let columns = sort_columns
+        .iter()
+        .map(|column| column.values.clone())
+        .collect::<Vec<ArrayRef>>();
+    let rows = converter.convert_columns(&columns)?;
+
+    let mut indices = (0..rows.num_rows() as u32).collect::<Vec<_>>();
+    let compare = |a: &u32, b: &u32| rows.row(*a as usize).cmp(&rows.row(*b as usize));
+    match fetch {
+        Some(fetch) if fetch < indices.len() => {
+            // only the first `fetch` rows need to be sorted
+            if fetch > 0 {
+                indices.select_nth_unstable_by(fetch - 1, compare);
+            }
+            indices.truncate(fetch);
+            indices.sort_unstable_by(compare);
+        }
+        _ => indices.sort_unstable_by(compare),
+    }
+    Ok(UInt32Array::from(indices))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{
+        BinaryArray, BooleanArray, Date32Array, Decimal128Array, DictionaryArray,
+        Float64Array, Int32Array, Int64Array, Int8Array, LargeStringArray, StringArray,
+        TimestampNanosecondArray, UInt16Array,
+    };
+    use arrow::compute::take;
+    use arrow::datatypes::Int32Type;
+    use std::sync::Arc;
+
+    /// Sorts `column` followed by a tie breaking column, both by the row
+    /// format and by [`lexsort_to_indices`], in every direction and null
+    /// ordering, and asserts that the keys come out in the same order
+    fn assert_same_order(column: ArrayRef) {
+        let len = column.len() as i32;
+        let tie_breaker: ArrayRef = Arc::new(Int32Array::from_iter(
+            (0..len).map(|i| (i % 3 != 0).then(|| i % 4)),
+        ));
+
+        for descending in [false, true] {
+            for nulls_first in [false, true] {
+                let options = Some(SortOptions {
+                    descending,
+                    nulls_first,
+                });
+                let sort_columns = vec![
+                    SortColumn {
+                        values: column.clone(),
+                        options,
+                    },
+                    SortColumn {
+                        values: tie_breaker.clone(),
+                        options,
+                    },
+                ];
+
+                for fetch in [None, Some(0), Some(3)] {
+                    let expected = lexsort_to_indices(&sort_columns, fetch).unwrap();
+                    let actual = sort_to_indices(&sort_columns, fetch).unwrap();
+                    assert_eq!(expected.len(), actual.len());
+
+                    // rows with equal keys may be ordered differently
+                    for sort_column in &sort_columns {
+                        let values = sort_column.values.as_ref();
+                        let expected = take(values, &expected, None).unwrap();
+                        let actual = take(values, &actual, None).unwrap();
+                        assert_eq!(
+                            expected.as_ref(),
+                            actual.as_ref(),
+                            "{:?} keys sorted with {:?} and fetch {:?}",
+                            column.data_type(),
+                            options,
+                            fetch
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn sort_supported_types() {
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(BooleanArray::from(vec![
+                Some(true),
+                None,
+                Some(false),
+                Some(true),
+                None,
+                Some(false),
+                Some(false),
+            ])),
+            Arc::new(Int8Array::from(vec![
+                Some(-1),
+                None,
+                Some(i8::MIN),
+                Some(i8::MAX),
+                Some(0),
+                None,
+                Some(-1),
+            ])),
+            Arc::new(Int64Array::from(vec![
+                Some(5),
+                Some(-3),
+                None,
+                Some(i64::MIN),
+                Some(5),
+                Some(i64::MAX),
+                None,
+            ])),
+            Arc::new(UInt16Array::from(vec![
+                Some(7),
+                None,
+                Some(u16::MAX),
+                Some(0),
+                Some(7),
+                Some(256),
+                Some(1),
+            ])),
+            Arc::new(Float64Array::from(vec![
+                Some(1.5),
+                None,
+                Some(-2.25),
+                Some(f64::INFINITY),
+                Some(f64::NEG_INFINITY),
+                Some(1.5),
+                Some(1e-10),
+            ])),
+            Arc::new(Date32Array::from(vec![
+                Some(19000),
+                None,
+                Some(-1),
+                Some(0),
+                Some(19000),
+                None,
+                Some(18000),
+            ])),
+            Arc::new(TimestampNanosecondArray::from(vec![
+                Some(1_000_000_000),
+                Some(-5),
+                None,
+                Some(1_000_000_000),
+                Some(0),
+                Some(i64::MAX),
+                None,
+            ])),
+            Arc::new(
+                Decimal128Array::from(vec![
+                    Some(12345),
+                    None,
+                    Some(-12345),
+                    Some(0),
+                    Some(12345),
+                    Some(99_999_999_999),
+                    Some(-1),
+                ])
+                .with_precision_and_scale(38, 2)
+                .unwrap(),
+            ),
+            Arc::new(StringArray::from(vec![
+                Some("b"),
+                None,
+                Some(""),
+                Some("ab"),
+                Some("a"),
+                Some("b"),
+                Some("a\u{0}"),
+            ])),
+            Arc::new(LargeStringArray::from(vec![
+                Some("zz"),
+                Some("z"),
+                None,
+                Some(""),
+                None,
+                Some("zz"),
+                Some("y"),
+            ])),
+            Arc::new(BinaryArray::from(vec![
+                Some(&b"\x00"[..]),
+                None,
+                Some(&b""[..]),
+                Some(&b"\xff\x00"[..]),
+                Some(&b"\xff"[..]),
+                Some(&b"\x00\x00"[..]),
+                None,
+            ])),
+            Arc::new(
+                vec![
+                    Some("c"),
+                    None,
+                    Some("a"),
+                    Some("b"),
+                    Some("a"),
+                    Some("c"),
+                    None,
+                ]
+                .into_iter()
+                .collect::<DictionaryArray<Int32Type>>(),
+            ),
+        ];
+
+        for column in columns {
+            assert!(row_format_supported(column.data_type()));
+            assert_same_order(column);
+        }
+    }
+
+    #[test]
+    fn sort_unsupported_types() {
+        let data_type = DataType::List(Box::new(arrow::datatypes::Field::new(
+            "item",
+            DataType::Int32,
+            true,
+        )));
+        assert!(!row_format_supported(&data_type));
+        let options = SortOptions::default();
+        assert!(try_row_converter(vec![
+            (DataType::Int32, options),
+            (data_type, options)
+        ])
+        .is_none());
+    }
+}
@@ -24,8 +24,9 @@ use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll};
 
+use arrow::compute::SortOptions;
 use arrow::error::ArrowError;
-use arrow::row::{RowConverter, SortField};
+use arrow::row::RowConverter;
 use arrow::{
     array::{make_array as make_arrow_array, MutableArrayData},
     datatypes::SchemaRef,
@@ -42,6 +43,7 @@ use crate::execution::context::TaskContext;
 use crate::physical_plan::metrics::{
     ExecutionPlanMetricsSet, MemTrackingMetrics, MetricsSet,
 };
+use crate::physical_plan::sorts::row_format::try_row_converter;
 use crate::physical_plan::sorts::{RowIndex, SortKeyCursor, SortedStream};
 use crate::physical_plan::stream::RecordBatchReceiverStream;
 use crate::physical_plan::{
@@ -246,7 +248,7 @@ impl ExecutionPlan for SortPreservingMergeExec {
         f: &mut std::fmt::Formatter,
     ) -> std::fmt::Result {
         match t {
-            DisplayFormatType::Default => {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
                 let expr: Vec<String> = self.expr.iter().map(|e| e.to_string()).collect();
                 write!(f, "SortPreservingMergeExec: [{}]", expr.join(","))
             }
@@ -329,8 +331,13 @@ pub(crate) struct SortPreservingMergeStream {
     /// target batch size
     batch_size: usize,
 
-    /// row converter
-    row_converter: RowConverter,
+    /// Converts the sort keys to the row format, `None` if their types are
+    /// not supported by it
+    row_converter: Option<RowConverter>,
+
+    /// The options of the sort keys, to compare them column by column if
+    /// they are not converted to the row format
+    sort_options: Arc<[SortOptions]>,
 }
 
 impl SortPreservingMergeStream {
@@ -349,14 +356,12 @@ impl SortPreservingMergeStream {
         tracking_metrics.init_mem_used(streams.iter().map(|s| s.mem_used).sum());
         let wrappers = streams.into_iter().map(|s| s.stream.fuse()).collect();
 
-        let sort_fields = expressions
+        let sort_keys = expressions
             .iter()
-            .map(|expr| {
-                let data_type = expr.expr.data_type(&schema)?;
-                Ok(SortField::new_with_options(data_type, expr.options))
-            })
+            .map(|expr| Ok((expr.expr.data_type(&schema)?, expr.options)))
             .collect::<Result<Vec<_>>>()?;
-        let row_converter = RowConverter::new(sort_fields);
+        let row_converter = try_row_converter(sort_keys);
+        let sort_options = expressions.iter().map(|expr| expr.options).collect();
 
         Ok(Self {
             schema,
@@ -371,6 +376,7 @@ impl SortPreservingMergeStream {
             heap: BinaryHeap::with_capacity(stream_count),
             batch_size,
             row_converter,
+            sort_options,
         })
     }
 
@@ -409,20 +415,27 @@ impl SortPreservingMergeStream {
                             })
                             .collect::<Result<Vec<_>>>()?;
 
-                        let rows = match self.row_converter.convert_columns(&cols) {
-                            Ok(rows) => rows,
-                            Err(e) => {
-                                return Poll::Ready(Err(ArrowError::ExternalError(
-                                    Box::new(e),
-                                )));
+                        // assign this batch an ID
+                        let batch_id = self.next_batch_id;
+                        let cursor = match &mut self.row_converter {
+                            Some(row_converter) => {
+                                let rows = match row_converter.convert_columns(&cols) {
+                                    Ok(rows) => rows,
+                                    Err(e) => {
+                                        return Poll::Ready(Err(
+                                            ArrowError::ExternalError(Box::new(e)),
+                                        ));
+                                    }
+                                };
+                                SortKeyCursor::new(idx, batch_id, rows)
                             }
+                            None => SortKeyCursor::try_new_from_arrays(
+                                idx,
+                                batch_id,
+                                cols,
+                                self.sort_options.clone(),
+                            )?,
                         };
-
-                        let cursor = SortKeyCursor::new(
-                            idx,
-                            self.next_batch_id, // assign this batch an ID
-                            rows,
-                        );
                         self.next_batch_id += 1;
                         self.heap.push(Reverse(cursor));
                         self.cursor_finished[idx] = false;
@@ -630,12 +643,17 @@ mod tests {
     use futures::FutureExt;
     use tokio_stream::StreamExt;
 
-    use crate::arrow::array::{Int32Array, StringArray, TimestampNanosecondArray};
+    use crate::arrow::array::{
+        as_primitive_array, DurationSecondArray, Int32Array, StringArray,
+        TimestampNanosecondArray,
+    };
+    use crate::arrow::datatypes::Int32Type;
     use crate::from_slice::FromSlice;
     use crate::physical_plan::coalesce_partitions::CoalescePartitionsExec;
     use crate::physical_plan::expressions::col;
     use crate::physical_plan::memory::MemoryExec;
     use crate::physical_plan::metrics::MetricValue;
+    use crate::physical_plan::sorts::row_format::row_format_supported;
     use crate::physical_plan::sorts::sort::SortExec;
     use crate::physical_plan::{collect, common};
     use crate::prelude::{SessionConfig, SessionContext};
@@ -858,6 +876,46 @@ mod tests {
         .await;
     }
 
+    #[tokio::test]
+    async fn test_merge_keys_without_row_format() {
+        let session_ctx = SessionContext::new();
+        let task_ctx = session_ctx.task_ctx();
+        let batch = |d: Vec<Option<i64>>, i: Vec<i32>| {
+            let d: ArrayRef = Arc::new(DurationSecondArray::from(d));
+            let i: ArrayRef = Arc::new(Int32Array::from_slice(&i));
+            RecordBatch::try_from_iter(vec![("d", d), ("i", i)]).unwrap()
+        };
+        let partitions = vec![
+            vec![
+                batch(vec![Some(9), Some(5)], vec![1, 2]),
+                batch(vec![Some(5), None], vec![3, 4]),
+            ],
+            vec![batch(vec![Some(7), Some(5), None], vec![5, 6, 7])],
+        ];
+        let schema = partitions[0][0].schema();
+        assert!(!row_format_supported(schema.field(0).data_type()));
+
+        // the durations are compared column by column, across batches
+        let sort = vec![
+            PhysicalSortExpr {
+                expr: col("d", &schema).unwrap(),
+                options: SortOptions {
+                    descending: true,
+                    nulls_first: false,
+                },
+            },
+            PhysicalSortExpr {
+                expr: col("i", &schema).unwrap(),
+                options: Default::default(),
+            },
+        ];
+        let exec = MemoryExec::try_new(&partitions, schema, None).unwrap();
+        let merged = sorted_merge(Arc::new(exec), sort, task_ctx).await;
+
+        let i = as_primitive_array::<Int32Type>(merged.column(1));
+        assert_eq!(i.values(), &[1, 5, 2, 3, 6, 4, 7]);
+    }
+
     async fn _test_merge(
         partitions: &[Vec<RecordBatch>],
         exp: &[&str],
@@ -0,0 +1,201 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Execution plan for reading batches from streams as they arrive
+
+use std::any::Any;
+use std::fmt;
+use std::sync::Arc;
+
+use arrow::datatypes::SchemaRef;
+use parking_lot::Mutex;
+
+use super::expressions::PhysicalSortExpr;
+use super::{
+    DisplayFormatType, ExecutionPlan, Partitioning, SendableRecordBatchStream, Statistics,
+};
+use crate::error::{DataFusionError, Result};
+use crate::execution::context::TaskContext;
+
+/// A partition of a [`StreamingTableExec`], which creates the stream of its
+/// batches each time the partition is executed
+pub trait PartitionStream: Send + Sync {
+    /// Returns the schema of the batches of this partition
+    fn schema(&self) -> &SchemaRef;
+
+    /// Returns a stream of the batches of this partition
+    fn execute(&self, ctx: Arc<TaskContext>) -> Result<SendableRecordBatchStream>;
+}
+
+/// A [`PartitionStream`] over a single stream that has already been opened,
+/// such as batches received over the network.
+///
+/// Its batches can only be read once: executing the partition again returns
+/// an error. Implement [`PartitionStream`] to create a new stream on each
+/// execution if the data can be replayed.
+pub struct OneShotPartitionStream {
+    schema: SchemaRef,
+    stream: Mutex<Option<SendableRecordBatchStream>>,
+}
+
+impl OneShotPartitionStream {
+    /// Create a partition that yields the batches of `stream`
+    pub fn new(stream: SendableRecordBatchStream) -> Self {
+        Self {
+            schema: stream.schema(),
+            stream: Mutex::new(Some(stream)),
+        }
+    }
+}
+
+impl fmt::Debug for OneShotPartitionStream {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("OneShotPartitionStream")
+            .field("schema", &self.schema)
+            .field("consumed", &self.stream.lock().is_none())
+            .finish()
+    }
+}
+
+impl PartitionStream for OneShotPartitionStream {
+    fn schema(&self) -> &SchemaRef {
+        &self.schema
+    }
+
+    fn execute(&self, _ctx: Arc<TaskContext>) -> Result<SendableRecordBatchStream> {
+        self.stream.lock().take().ok_or_else(|| {
+            DataFusionError::Execution(
+                "The stream of this partition has already been consumed".to_string(),
+            )
+        })
+    }
+}
+
+/// Execution plan for reading the batches of [`PartitionStream`]s as they
+/// arrive, one partition per stream
+pub struct StreamingTableExec {
+    partitions: Vec<Arc<dyn PartitionStream>>,
+    schema: SchemaRef,
+}
+
+impl StreamingTableExec {
+    /// Create a new execution plan reading `partitions`, which must all
+    /// produce batches of `schema`
+    pub fn try_new(
+        schema: SchemaRef,
+        partitions: Vec<Arc<dyn PartitionStream>>,
+    ) -> Result<Self> {
+        if let Some(partition) = partitions.iter().find(|p| p.schema() != &schema) {
+            return Err(DataFusionError::Plan(format!(
+                "Mismatch between the schema of a partition stream {:?} and the table schema {:?}",
+                partition.schema(),
+                schema
+            )));
+        }
+        Ok(Self { partitions, schema })
+    }
+
+    /// The partitions of this plan
+    pub fn partitions(&self) -> &[Arc<dyn PartitionStream>] {
+        &self.partitions
+    }
+}
+
+impl fmt::Debug for StreamingTableExec {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("StreamingTableExec")
+            .field("partitions", &self.partitions.len())
+            .field("schema", &self.schema)
+            .finish()
+    }
+}
+
+impl ExecutionPlan for StreamingTableExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(self.partitions.len())
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        None
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        // this is a leaf node and has no children
+        vec![]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        _: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        Err(DataFusionError::Internal(format!(
+            "Children cannot be replaced in {:?}",
+            self
+        )))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        let stream = self
+            .partitions
+            .get(partition)
+            .ok_or_else(|| {
+                DataFusionError::Internal(format!(
+                    "StreamingTableExec invalid partition {} (expected less than {})",
+                    partition,
+                    self.partitions.len()
+                ))
+            })?
+            .execute(context)?;
+        if stream.schema() != self.schema {
+            return Err(DataFusionError::Execution(format!(
+                "Mismatch between the schema of the stream of partition {} {:?} and the table schema {:?}",
+                partition,
+                stream.schema(),
+                self.schema
+            )));
+        }
+        Ok(stream)
+    }
+
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                write!(
+                    f,
+                    "StreamingTableExec: partitions={}",
+                    self.partitions.len()
+                )
+            }
+        }
+    }
+
+    fn statistics(&self) -> Statistics {
+        Statistics::default()
+    }
+}
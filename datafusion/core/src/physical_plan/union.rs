@@ -31,7 +31,6 @@ use arrow::{
     record_batch::RecordBatch,
 };
 use futures::{Stream, StreamExt};
-use itertools::Itertools;
 use log::debug;
 use log::warn;
 
@@ -65,19 +64,19 @@ pub struct UnionExec {
 impl UnionExec {
     /// Create a new UnionExec
     pub fn new(inputs: Vec<Arc<dyn ExecutionPlan>>) -> Self {
-        let fields: Vec<Field> = (0..inputs[0].schema().fields().len())
-            .map(|i| {
-                inputs
-                    .iter()
-                    .filter_map(|input| {
-                        if input.schema().fields().len() > i {
-                            Some(input.schema().field(i).clone())
-                        } else {
-                            None
-                        }
-                    })
-                    .find_or_first(|f| f.is_nullable())
-                    .unwrap()
+        // the columns are named after those of the first input, and are
+        // nullable if they are in any input
+        let fields: Vec<Field> = inputs[0]
+            .schema()
+            .fields()
+            .iter()
+            .enumerate()
+            .map(|(i, field)| {
+                let nullable = inputs.iter().any(|input| {
+                    let schema = input.schema();
+                    schema.fields().get(i).map_or(false, |f| f.is_nullable())
+                });
+                field.clone().with_nullable(nullable)
             })
             .collect();
 
@@ -235,7 +234,7 @@ impl ExecutionPlan for UnionExec {
         f: &mut std::fmt::Formatter,
     ) -> std::fmt::Result {
         match t {
-            DisplayFormatType::Default => {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
                 write!(f, "UnionExec")
             }
         }
@@ -33,10 +33,6 @@ use array::ArrayRef;
 use arrow::array::{self, Array, Decimal128Builder, Int32Array};
 use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
 use arrow::record_batch::RecordBatch;
-use bzip2::write::BzEncoder;
-use bzip2::Compression as BzCompression;
-use flate2::write::GzEncoder;
-use flate2::Compression as GzCompression;
 use futures::{Future, FutureExt};
 use std::fs::File;
 use std::io::prelude::*;
@@ -110,15 +106,7 @@ pub fn partitioned_file_groups(
 
         let file = File::create(&filename).unwrap();
 
-        let encoder: Box<dyn Write + Send> = match file_compression_type.to_owned() {
-            FileCompressionType::UNCOMPRESSED => Box::new(file),
-            FileCompressionType::GZIP => {
-                Box::new(GzEncoder::new(file, GzCompression::default()))
-            }
-            FileCompressionType::BZIP2 => {
-                Box::new(BzEncoder::new(file, BzCompression::default()))
-            }
-        };
+        let encoder = file_compression_type.convert_write(file)?;
 
         let writer = BufWriter::new(encoder);
         writers.push(writer);
@@ -143,8 +131,8 @@ pub fn partitioned_file_groups(
             writers[partition].write_all(b"\n").unwrap();
         }
     }
-    for w in writers.iter_mut() {
-        w.flush().unwrap();
+    for w in writers {
+        w.into_inner().map_err(|e| e.into_error())?.finish()?;
     }
 
     Ok(files
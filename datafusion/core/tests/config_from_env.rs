@@ -45,5 +45,5 @@ fn get_config_int_from_env_invalid() {
     env::set_var(env_key, "abc");
     let config = ConfigOptions::from_env();
     env::remove_var(env_key);
-    assert_eq!(config.get_u64(config_key).unwrap_or_default(), 4096); // set to its default value
+    assert_eq!(config.get_u64(config_key), None); // unset, as by default
 }
@@ -138,14 +138,10 @@ impl ExecutionPlan for CustomExecutionPlan {
 
     fn fmt_as(
         &self,
-        t: DisplayFormatType,
+        _t: DisplayFormatType,
         f: &mut std::fmt::Formatter,
     ) -> std::fmt::Result {
-        match t {
-            DisplayFormatType::Default => {
-                write!(f, "CustomExecutionPlan: projection={:#?}", self.projection)
-            }
-        }
+        write!(f, "CustomExecutionPlan: projection={:#?}", self.projection)
     }
 
     fn statistics(&self) -> Statistics {
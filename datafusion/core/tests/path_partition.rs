@@ -20,6 +20,7 @@
 use std::fs::File;
 use std::io::{Read, Seek, SeekFrom};
 use std::ops::Range;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 
 use async_trait::async_trait;
@@ -29,12 +30,12 @@ use datafusion::datasource::listing::ListingTableUrl;
 use datafusion::{
     assert_batches_sorted_eq,
     datasource::{
-        file_format::{csv::CsvFormat, parquet::ParquetFormat},
+        file_format::{csv::CsvFormat, json::JsonFormat, parquet::ParquetFormat},
         listing::{ListingOptions, ListingTable, ListingTableConfig},
     },
     error::Result,
     physical_plan::ColumnStatistics,
-    prelude::SessionContext,
+    prelude::{NdJsonReadOptions, SessionContext},
     test_util::{self, arrow_test_data, parquet_test_data},
 };
 use datafusion_common::ScalarValue;
@@ -291,6 +292,98 @@ async fn csv_grouping_by_partition() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn json_distinct_partition_col_reads_first_records() -> Result<()> {
+    let ctx = SessionContext::new();
+
+    let store = register_partitioned_json(
+        &ctx,
+        &[
+            "year=2021/month=09/file.json",
+            "year=2021/month=10/file.json",
+            "year=2022/month=01/file.json",
+        ],
+        &["year", "month"],
+        "mirror:///",
+        "tests/jsons/2.json",
+    )
+    .await;
+
+    let reads = store.reads();
+    let result = ctx
+        .sql("SELECT DISTINCT year, month FROM t")
+        .await?
+        .collect()
+        .await?;
+
+    let expected = vec![
+        "+------+-------+",
+        "| year | month |",
+        "+------+-------+",
+        "| 2021 | 09    |",
+        "| 2021 | 10    |",
+        "| 2022 | 01    |",
+        "+------+-------+",
+    ];
+    assert_batches_sorted_eq!(expected, &result);
+    // every file is only read up to its first record
+    assert_eq!(store.reads(), reads + 3);
+
+    // every record still has a row when they are not deduplicated
+    let result = ctx
+        .sql("SELECT year, COUNT(*) FROM t GROUP BY year")
+        .await?
+        .collect()
+        .await?;
+
+    let expected = vec![
+        "+------+-----------------+",
+        "| year | COUNT(UInt8(1)) |",
+        "+------+-----------------+",
+        "| 2021 | 24              |",
+        "| 2022 | 12              |",
+        "+------+-----------------+",
+    ];
+    assert_batches_sorted_eq!(expected, &result);
+    assert!(store.reads() > reads + 3);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn json_distinct_partition_col_skips_files_without_records() -> Result<()> {
+    let tmp_dir = tempfile::TempDir::new()?;
+    let files = [
+        ("year=2021/file.json", "{\"a\": 1}\n"),
+        ("year=2022/file.json", "\n  \n"),
+        ("year=2023/file.json", "\n{\"a\": 2}"),
+    ];
+    for (path, data) in files {
+        let path = tmp_dir.path().join(path);
+        std::fs::create_dir_all(path.parent().unwrap())?;
+        std::fs::write(path, data)?;
+    }
+
+    let ctx = SessionContext::new();
+    let options = NdJsonReadOptions::default().table_partition_cols(vec!["year".into()]);
+    ctx.register_json("t", tmp_dir.path().to_str().unwrap(), options)
+        .await?;
+
+    // a file of blank lines has no record, so no partition value
+    let result = ctx
+        .sql("SELECT DISTINCT year FROM t")
+        .await?
+        .collect()
+        .await?;
+
+    let expected = vec![
+        "+------+", "| year |", "+------+", "| 2021 |", "| 2023 |", "+------+",
+    ];
+    assert_batches_sorted_eq!(expected, &result);
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn parquet_multiple_partitions() -> Result<()> {
     let ctx = SessionContext::new();
@@ -485,6 +578,43 @@ async fn register_partitioned_alltypes_parquet(
         .expect("registering listing table failed");
 }
 
+async fn register_partitioned_json(
+    ctx: &SessionContext,
+    store_paths: &[&str],
+    partition_cols: &[&str],
+    table_path: &str,
+    source_file: &str,
+) -> Arc<MirroringObjectStore> {
+    let store = Arc::new(MirroringObjectStore::new(
+        source_file.to_owned(),
+        store_paths,
+    ));
+    ctx.runtime_env()
+        .register_object_store("mirror", "", store.clone());
+
+    let mut options = ListingOptions::new(Arc::new(JsonFormat::default()));
+    options.table_partition_cols = partition_cols.iter().map(|&s| s.to_owned()).collect();
+
+    let table_path = ListingTableUrl::parse(table_path).unwrap();
+    let store_path =
+        ListingTableUrl::parse(format!("mirror:///{}", store_paths[0])).unwrap();
+
+    let file_schema = options
+        .infer_schema(&ctx.state(), &store_path)
+        .await
+        .expect("JSON schema inference failed");
+
+    let config = ListingTableConfig::new(table_path)
+        .with_listing_options(options)
+        .with_schema(file_schema);
+
+    let table = ListingTable::try_new(config).unwrap();
+
+    ctx.register_table("t", Arc::new(table))
+        .expect("registering listing table failed");
+    store
+}
+
 #[derive(Debug)]
 /// An object store implem that is mirrors a given file to multiple paths.
 pub struct MirroringObjectStore {
@@ -494,6 +624,8 @@ pub struct MirroringObjectStore {
     mirrored_file: String,
     /// Size of the mirrored file
     file_size: u64,
+    /// The number of times the content of a file was read
+    reads: AtomicUsize,
 }
 
 impl std::fmt::Display for MirroringObjectStore {
@@ -503,13 +635,23 @@ impl std::fmt::Display for MirroringObjectStore {
 }
 
 impl MirroringObjectStore {
-    pub fn new_arc(mirrored_file: String, paths: &[&str]) -> Arc<dyn ObjectStore> {
+    pub fn new(mirrored_file: String, paths: &[&str]) -> Self {
         let metadata = std::fs::metadata(&mirrored_file).expect("Local file metadata");
-        Arc::new(Self {
+        Self {
             files: paths.iter().map(|&f| f.to_owned()).collect(),
             mirrored_file,
             file_size: metadata.len(),
-        })
+            reads: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn new_arc(mirrored_file: String, paths: &[&str]) -> Arc<dyn ObjectStore> {
+        Arc::new(Self::new(mirrored_file, paths))
+    }
+
+    /// The number of times the content of a file was read
+    pub fn reads(&self) -> usize {
+        self.reads.load(Ordering::SeqCst)
     }
 }
 
@@ -536,6 +678,7 @@ impl ObjectStore for MirroringObjectStore {
 
     async fn get(&self, location: &Path) -> object_store::Result<GetResult> {
         self.files.iter().find(|x| *x == location.as_ref()).unwrap();
+        self.reads.fetch_add(1, Ordering::SeqCst);
         let path = std::path::PathBuf::from(&self.mirrored_file);
         let file = File::open(&path).unwrap();
         Ok(GetResult::File(file, path))
@@ -547,6 +690,7 @@ impl ObjectStore for MirroringObjectStore {
         range: Range<usize>,
     ) -> object_store::Result<Bytes> {
         self.files.iter().find(|x| *x == location.as_ref()).unwrap();
+        self.reads.fetch_add(1, Ordering::SeqCst);
         let path = std::path::PathBuf::from(&self.mirrored_file);
         let mut file = File::open(&path).unwrap();
         file.seek(SeekFrom::Start(range.start as u64)).unwrap();
@@ -19,22 +19,26 @@ use arrow::array::{as_primitive_array, Int32Builder, Int64Array};
 use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
 use arrow::record_batch::RecordBatch;
 use async_trait::async_trait;
+use datafusion::assert_batches_sorted_eq;
 use datafusion::datasource::datasource::{TableProvider, TableType};
 use datafusion::error::Result;
 use datafusion::execution::context::{SessionContext, SessionState, TaskContext};
-use datafusion::logical_expr::{Expr, TableProviderFilterPushDown};
+use datafusion::logical_expr::{Expr, Operator, TableProviderFilterPushDown};
 use datafusion::physical_plan::common::SizedRecordBatchStream;
 use datafusion::physical_plan::expressions::PhysicalSortExpr;
+use datafusion::physical_plan::filter::FilterExec;
+use datafusion::physical_plan::memory::MemoryExec;
 use datafusion::physical_plan::metrics::{ExecutionPlanMetricsSet, MemTrackingMetrics};
 use datafusion::physical_plan::{
-    DisplayFormatType, ExecutionPlan, Partitioning, SendableRecordBatchStream, Statistics,
+    collect, DisplayFormatType, ExecutionPlan, Partitioning, SendableRecordBatchStream,
+    Statistics,
 };
 use datafusion::prelude::*;
 use datafusion::scalar::ScalarValue;
 use datafusion_common::DataFusionError;
 use datafusion_expr::expr::{BinaryExpr, Cast};
 use std::ops::Deref;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 fn create_batch(value: i32, num_rows: usize) -> Result<RecordBatch> {
     let mut builder = Int32Builder::with_capacity(num_rows);
@@ -102,14 +106,10 @@ impl ExecutionPlan for CustomPlan {
 
     fn fmt_as(
         &self,
-        t: DisplayFormatType,
+        _t: DisplayFormatType,
         f: &mut std::fmt::Formatter,
     ) -> std::fmt::Result {
-        match t {
-            DisplayFormatType::Default => {
-                write!(f, "CustomPlan: batch_size={}", self.batches.len(),)
-            }
-        }
+        write!(f, "CustomPlan: batch_size={}", self.batches.len(),)
     }
 
     fn statistics(&self) -> Statistics {
@@ -238,3 +238,191 @@ async fn test_filter_pushdown_results() -> Result<()> {
     assert_provider_row_count(2, 0).await?;
     Ok(())
 }
+
+/// The filters and limit received by a [`NegotiatingProvider`] scan
+type ScanArgs = (Vec<Expr>, Option<usize>);
+
+/// A provider of the values `0..10` in the column `a` that applies `a < n`
+/// filters exactly, only uses `a > n` filters as a hint and ignores all
+/// other filters
+struct NegotiatingProvider {
+    batch: RecordBatch,
+    scans: Mutex<Vec<ScanArgs>>,
+}
+
+impl NegotiatingProvider {
+    fn new() -> Result<Self> {
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int64, false)]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![Arc::new(Int64Array::from_iter_values(0..10))],
+        )?;
+        Ok(Self {
+            batch,
+            scans: Mutex::new(vec![]),
+        })
+    }
+
+    /// Returns the operator and literal value of `a <op> n` filters
+    fn parse_filter(filter: &Expr) -> Option<(Operator, i64)> {
+        match filter {
+            Expr::BinaryExpr(BinaryExpr { op, right, .. }) => match right.as_ref() {
+                Expr::Literal(ScalarValue::Int64(Some(v))) => Some((*op, *v)),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+#[async_trait]
+impl TableProvider for NegotiatingProvider {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.batch.schema()
+    }
+
+    fn table_type(&self) -> TableType {
+        TableType::Base
+    }
+
+    async fn scan(
+        &self,
+        _state: &SessionState,
+        projection: &Option<Vec<usize>>,
+        filters: &[Expr],
+        limit: Option<usize>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        self.scans.lock().unwrap().push((filters.to_vec(), limit));
+
+        let mut values: Vec<i64> = (0..10).collect();
+        for filter in filters {
+            if let Some((Operator::Lt, bound)) = Self::parse_filter(filter) {
+                values.retain(|v| *v < bound);
+            }
+        }
+        if let Some(limit) = limit {
+            values.truncate(limit);
+        }
+
+        let batch = RecordBatch::try_new(
+            self.schema(),
+            vec![Arc::new(Int64Array::from(values))],
+        )?;
+        Ok(Arc::new(MemoryExec::try_new(
+            &[vec![batch]],
+            self.schema(),
+            projection.clone(),
+        )?))
+    }
+
+    fn supports_filter_pushdown(
+        &self,
+        filter: &Expr,
+    ) -> Result<TableProviderFilterPushDown> {
+        Ok(match Self::parse_filter(filter) {
+            Some((Operator::Lt, _)) => TableProviderFilterPushDown::Exact,
+            Some((Operator::Gt, _)) => TableProviderFilterPushDown::Inexact,
+            _ => TableProviderFilterPushDown::Unsupported,
+        })
+    }
+}
+
+/// Returns the predicates of all [`FilterExec`]s in `plan`
+fn filter_exec_predicates(plan: &Arc<dyn ExecutionPlan>) -> Vec<String> {
+    let mut predicates = vec![];
+    if let Some(filter) = plan.as_any().downcast_ref::<FilterExec>() {
+        predicates.push(filter.predicate().to_string());
+    }
+    for child in plan.children() {
+        predicates.extend(filter_exec_predicates(&child));
+    }
+    predicates
+}
+
+#[tokio::test]
+async fn test_exact_filters_are_not_reapplied() -> Result<()> {
+    let provider = Arc::new(NegotiatingProvider::new()?);
+    let ctx = SessionContext::new();
+
+    let df = ctx
+        .read_table(provider.clone())?
+        .filter(
+            col("a")
+                .lt(lit(5i64))
+                .and(col("a").gt(lit(1i64)))
+                .and(col("a").not_eq(lit(3i64))),
+        )?
+        .limit(0, Some(1))?;
+
+    let plan = df.create_physical_plan().await?;
+    // the exact filter is only applied by the provider, while the inexact
+    // and unsupported ones are evaluated again
+    assert_eq!(
+        filter_exec_predicates(&plan),
+        vec!["a@0 > 1 AND a@0 != 3".to_string()]
+    );
+
+    let results = collect(plan, ctx.task_ctx()).await?;
+    #[rustfmt::skip]
+    let expected = vec![
+        "+---+",
+        "| a |",
+        "+---+",
+        "| 2 |",
+        "+---+",
+    ];
+    assert_batches_sorted_eq!(expected, &results);
+
+    // only the filters the provider claimed are passed to the scan, and
+    // the limit is withheld as the inexact filter may still drop rows
+    let scans = provider.scans.lock().unwrap();
+    assert_eq!(scans.len(), 1);
+    let (filters, limit) = &scans[0];
+    let ops: Vec<_> = filters
+        .iter()
+        .map(|f| NegotiatingProvider::parse_filter(f).unwrap().0)
+        .collect();
+    assert_eq!(ops, vec![Operator::Lt, Operator::Gt]);
+    assert_eq!(*limit, None);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_limit_with_exact_filters() -> Result<()> {
+    let provider = Arc::new(NegotiatingProvider::new()?);
+    let ctx = SessionContext::new();
+
+    let df = ctx
+        .read_table(provider.clone())?
+        .filter(col("a").lt(lit(5i64)))?
+        .limit(0, Some(2))?;
+
+    let plan = df.create_physical_plan().await?;
+    assert!(filter_exec_predicates(&plan).is_empty());
+
+    let results = collect(plan, ctx.task_ctx()).await?;
+    #[rustfmt::skip]
+    let expected = vec![
+        "+---+",
+        "| a |",
+        "+---+",
+        "| 0 |",
+        "| 1 |",
+        "+---+",
+    ];
+    assert_batches_sorted_eq!(expected, &results);
+
+    // with every filter handled exactly, the scan also receives the limit
+    let scans = provider.scans.lock().unwrap();
+    assert_eq!(scans.len(), 1);
+    let (filters, limit) = &scans[0];
+    assert_eq!(filters.len(), 1);
+    assert_eq!(*limit, Some(2));
+
+    Ok(())
+}
@@ -220,6 +220,178 @@ async fn csv_query_stddev_6() -> Result<()> {
     Ok(())
 }
 
+/// The covariance of `pairs` computed with the two pass formula, dividing the
+/// sum of the products of the deviations by the number of pairs minus `ddof`
+fn two_pass_covariance(pairs: &[(f64, f64)], ddof: usize) -> Option<f64> {
+    if pairs.len() <= ddof {
+        return None;
+    }
+    let n = pairs.len() as f64;
+    let mean_x = pairs.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = pairs.iter().map(|(_, y)| y).sum::<f64>() / n;
+    let products = pairs
+        .iter()
+        .map(|(x, y)| (x - mean_x) * (y - mean_y))
+        .sum::<f64>();
+    Some(products / (pairs.len() - ddof) as f64)
+}
+
+/// The expected var_samp, var_pop, stddev, stddev_pop of the `x` values and
+/// covar_samp, covar_pop, corr of the non null pairs of `rows`
+fn two_pass_statistics(rows: &[(Option<f64>, Option<f64>)]) -> Vec<Option<f64>> {
+    let xs = rows
+        .iter()
+        .filter_map(|(x, _)| x.map(|x| (x, x)))
+        .collect::<Vec<_>>();
+    let pairs = rows
+        .iter()
+        .filter_map(|(x, y)| x.zip(*y))
+        .collect::<Vec<_>>();
+    let corr = if pairs.len() < 2 {
+        None
+    } else {
+        let xx = pairs.iter().map(|(x, _)| (*x, *x)).collect::<Vec<_>>();
+        let yy = pairs.iter().map(|(_, y)| (*y, *y)).collect::<Vec<_>>();
+        Some(
+            two_pass_covariance(&pairs, 0).unwrap()
+                / (two_pass_covariance(&xx, 0).unwrap()
+                    * two_pass_covariance(&yy, 0).unwrap())
+                .sqrt(),
+        )
+    };
+    vec![
+        two_pass_covariance(&xs, 1),
+        two_pass_covariance(&xs, 0),
+        two_pass_covariance(&xs, 1).map(f64::sqrt),
+        two_pass_covariance(&xs, 0).map(f64::sqrt),
+        two_pass_covariance(&pairs, 1),
+        two_pass_covariance(&pairs, 0),
+        corr,
+    ]
+}
+
+/// The rows of the `Float64` columns of `batches` after the first `skip` ones
+fn float64_rows(batches: &[RecordBatch], skip: usize) -> Vec<Vec<Option<f64>>> {
+    batches
+        .iter()
+        .flat_map(|batch| {
+            (0..batch.num_rows()).map(move |row| {
+                batch.columns()[skip..]
+                    .iter()
+                    .map(|column| {
+                        let array =
+                            column.as_any().downcast_ref::<Float64Array>().unwrap();
+                        (!array.is_null(row)).then(|| array.value(row))
+                    })
+                    .collect()
+            })
+        })
+        .collect()
+}
+
+fn assert_statistics_eq(expected: &[Vec<Option<f64>>], actual: &[Vec<Option<f64>>]) {
+    assert_eq!(expected.len(), actual.len());
+    for (expected, actual) in expected.iter().zip(actual) {
+        for (expected, actual) in expected.iter().zip(actual) {
+            match (expected, actual) {
+                (Some(expected), Some(actual)) => {
+                    assert!(
+                        (expected - actual).abs() < 1e-9,
+                        "{} != {}",
+                        expected,
+                        actual
+                    )
+                }
+                _ => assert_eq!(expected, actual),
+            }
+        }
+    }
+}
+
+#[tokio::test]
+async fn statistical_aggregates_match_two_pass() -> Result<()> {
+    let data = vec![
+        (1, Some(1.0), Some(2.0)),
+        (1, Some(2.0), Some(3.0)),
+        (1, Some(4.0), Some(5.0)),
+        (1, Some(7.0), Some(11.0)),
+        // a single element group
+        (2, Some(3.0), Some(9.0)),
+        // pairs with a null value are ignored by covar and corr
+        (3, Some(10.0), Some(5.0)),
+        (3, Some(20.0), None),
+        (3, None, Some(7.0)),
+    ];
+    let ctx = SessionContext::new();
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("g", DataType::Int32, false),
+        Field::new("x", DataType::Float64, true),
+        Field::new("y", DataType::Float64, true),
+    ]));
+    let batch = RecordBatch::try_new(
+        schema,
+        vec![
+            Arc::new(Int32Array::from_iter_values(data.iter().map(|r| r.0))),
+            Arc::new(Float64Array::from_iter(data.iter().map(|r| r.1))),
+            Arc::new(Float64Array::from_iter(data.iter().map(|r| r.2))),
+        ],
+    )?;
+    ctx.register_batch("t", batch)?;
+    let rows_of = |g: i32| {
+        data.iter()
+            .filter(|r| r.0 == g)
+            .map(|r| (r.1, r.2))
+            .collect::<Vec<_>>()
+    };
+    let expected = [1, 2, 3]
+        .into_iter()
+        .map(|g| two_pass_statistics(&rows_of(g)))
+        .collect::<Vec<_>>();
+    // the sample statistics of a single element group are null
+    assert_eq!(expected[1][0], None);
+
+    let functions = "var_samp(x), var_pop(x), stddev(x), stddev_pop(x), \
+                     covar_samp(x, y), covar_pop(x, y), corr(x, y)";
+    let sql = format!("SELECT g, {} FROM t GROUP BY g ORDER BY g", functions);
+    let actual = execute_to_batches(&ctx, &sql).await;
+    assert_statistics_eq(&expected, &float64_rows(&actual, 1));
+
+    // over a whole partition
+    let windows = functions.replace(')', ") OVER (PARTITION BY g)");
+    let sql = format!("SELECT g, {} FROM t ORDER BY g", windows);
+    let actual = execute_to_batches(&ctx, &sql).await;
+    let expected_rows = [1, 2, 3]
+        .into_iter()
+        .flat_map(|g| vec![expected[g as usize - 1].clone(); rows_of(g).len()])
+        .collect::<Vec<_>>();
+    assert_statistics_eq(&expected_rows, &float64_rows(&actual, 1));
+
+    // over a sliding frame, which retracts the rows leaving it
+    let windows = functions.replace(
+        ')',
+        ") OVER (PARTITION BY g ORDER BY x ROWS BETWEEN 1 PRECEDING AND CURRENT ROW)",
+    );
+    let sql = format!(
+        "SELECT g, x, {} FROM t WHERE x IS NOT NULL AND y IS NOT NULL ORDER BY g, x",
+        windows
+    );
+    let actual = execute_to_batches(&ctx, &sql).await;
+    let expected_rows = [1, 2, 3]
+        .into_iter()
+        .flat_map(|g| {
+            let rows = rows_of(g)
+                .into_iter()
+                .filter(|(x, y)| x.is_some() && y.is_some())
+                .collect::<Vec<_>>();
+            (0..rows.len())
+                .map(|i| two_pass_statistics(&rows[i.saturating_sub(1)..=i]))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    assert_statistics_eq(&expected_rows, &float64_rows(&actual, 2));
+    Ok(())
+}
+
 #[tokio::test]
 async fn csv_query_approx_median_1() -> Result<()> {
     let ctx = SessionContext::new();
@@ -418,6 +590,146 @@ async fn approx_median_f64_nan() -> Result<()> {
     .await
 }
 
+#[tokio::test]
+async fn median_and_mode_group_by() -> Result<()> {
+    let ctx = SessionContext::new();
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("g", DataType::Int32, false),
+        Field::new("a", DataType::Int64, true),
+        Field::new("d", DataType::Date32, true),
+    ]));
+    let batch = RecordBatch::try_new(
+        schema,
+        vec![
+            Arc::new(Int32Array::from(vec![1, 1, 1, 1, 2, 2, 3])),
+            Arc::new(Int64Array::from(vec![
+                Some(10),
+                Some(2),
+                Some(2),
+                Some(3),
+                Some(7),
+                Some(5),
+                None,
+            ])),
+            Arc::new(Date32Array::from(vec![
+                Some(0),
+                Some(3),
+                Some(4),
+                Some(10),
+                Some(1),
+                Some(2),
+                None,
+            ])),
+        ],
+    )?;
+    ctx.register_batch("t", batch)?;
+    let sql = "SELECT g, median(a), mode(a), median(d) FROM t GROUP BY g";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+---+-------------+-----------+-------------+",
+        "| g | MEDIAN(t.a) | MODE(t.a) | MEDIAN(t.d) |",
+        "+---+-------------+-----------+-------------+",
+        "| 1 | 2           | 2         | 1970-01-04  |",
+        "| 2 | 6           | 5         | 1970-01-02  |",
+        "| 3 |             |           |             |",
+        "+---+-------------+-----------+-------------+",
+    ];
+    assert_batches_sorted_eq!(expected, &actual);
+    Ok(())
+}
+
+#[tokio::test]
+async fn bitwise_and_boolean_aggregates() -> Result<()> {
+    let ctx = SessionContext::new();
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("g", DataType::Int32, false),
+        Field::new("a", DataType::Int32, true),
+        Field::new("b", DataType::Boolean, true),
+    ]));
+    let batch = RecordBatch::try_new(
+        schema,
+        vec![
+            Arc::new(Int32Array::from(vec![1, 1, 1, 1, 2, 2, 3])),
+            Arc::new(Int32Array::from(vec![
+                Some(6),
+                None,
+                Some(12),
+                Some(14),
+                None,
+                None,
+                Some(5),
+            ])),
+            Arc::new(BooleanArray::from(vec![
+                Some(true),
+                None,
+                Some(false),
+                Some(true),
+                None,
+                None,
+                Some(true),
+            ])),
+        ],
+    )?;
+    ctx.register_batch("t", batch)?;
+    let keys = RecordBatch::try_new(
+        Arc::new(Schema::new(vec![Field::new("k", DataType::Int32, false)])),
+        vec![Arc::new(Int32Array::from(vec![1, 4]))],
+    )?;
+    ctx.register_batch("k", keys)?;
+
+    // null values are skipped, and a group of null values only is null
+    let sql = "SELECT g, bit_and(a) AS b_and, bit_or(a) AS b_or, bit_xor(a) AS b_xor, \
+               bool_and(b) AS all_b, bool_or(b) AS any_b FROM t GROUP BY g";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+---+-------+------+-------+-------+-------+",
+        "| g | b_and | b_or | b_xor | all_b | any_b |",
+        "+---+-------+------+-------+-------+-------+",
+        "| 1 | 4     | 14   | 4     | false | true  |",
+        "| 2 |       |      |       |       |       |",
+        "| 3 | 5     | 5    | 5     | true  | true  |",
+        "+---+-------+------+-------+-------+-------+",
+    ];
+    assert_batches_sorted_eq!(expected, &actual);
+
+    // the empty group of an outer join is null
+    let sql = "SELECT k, bit_or(a) AS b_or, every(b) AS all_b, any(b) AS any_b \
+               FROM k LEFT JOIN t ON k = g GROUP BY k";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+---+------+-------+-------+",
+        "| k | b_or | all_b | any_b |",
+        "+---+------+-------+-------+",
+        "| 1 | 14   | false | true  |",
+        "| 4 |      |       |       |",
+        "+---+------+-------+-------+",
+    ];
+    assert_batches_sorted_eq!(expected, &actual);
+
+    // over a sliding frame, which retracts the rows leaving it
+    let frame =
+        "OVER (PARTITION BY g ORDER BY a ROWS BETWEEN 1 PRECEDING AND CURRENT ROW)";
+    let sql = format!(
+        "SELECT g, a, bit_and(a) {frame} AS b_and, bit_or(a) {frame} AS b_or, \
+         bit_xor(a) {frame} AS b_xor, bool_and(b) {frame} AS all_b, \
+         bool_or(b) {frame} AS any_b FROM t WHERE a IS NOT NULL ORDER BY g, a",
+        frame = frame
+    );
+    let actual = execute_to_batches(&ctx, &sql).await;
+    let expected = vec![
+        "+---+----+-------+------+-------+-------+-------+",
+        "| g | a  | b_and | b_or | b_xor | all_b | any_b |",
+        "+---+----+-------+------+-------+-------+-------+",
+        "| 1 | 6  | 6     | 6    | 6     | true  | true  |",
+        "| 1 | 12 | 4     | 14   | 10    | false | true  |",
+        "| 1 | 14 | 12    | 14   | 2     | false | true  |",
+        "| 3 | 5  | 5     | 5    | 5     | true  | true  |",
+        "+---+----+-------+------+-------+-------+-------+",
+    ];
+    assert_batches_eq!(expected, &actual);
+    Ok(())
+}
+
 async fn median_test(
     func: &str,
     data_type: DataType,
@@ -991,6 +1303,58 @@ async fn csv_query_approx_percentile_cont_with_histogram_bins() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn sum_overflow() -> Result<()> {
+    let values = "(VALUES (9223372036854775807, 'a'), (1, 'a'))";
+    let queries = [
+        format!("SELECT SUM(column1) AS s FROM {}", values),
+        format!("SELECT SUM(column1) AS s FROM {} GROUP BY column2", values),
+    ];
+
+    // integer sums that overflow fail the query by default
+    let ctx = SessionContext::new();
+    for sql in &queries {
+        let err = plan_and_collect(&ctx, sql).await.unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Integer overflow computing SUM(column1)"),
+            "{}: unexpected error {}",
+            sql,
+            err
+        );
+    }
+
+    // and wrap around when asked to
+    let ctx =
+        SessionContext::with_config(SessionConfig::new().with_wrapping_arithmetic(true));
+    for sql in &queries {
+        let actual = execute_to_batches(&ctx, sql).await;
+        let expected = vec![
+            "+----------------------+",
+            "| s                    |",
+            "+----------------------+",
+            "| -9223372036854775808 |",
+            "+----------------------+",
+        ];
+        assert_batches_eq!(expected, &actual);
+    }
+
+    // Int32 sums accumulate in Int64
+    let ctx = SessionContext::new();
+    let sql = "SELECT SUM(CAST(column1 AS INT)) AS s \
+               FROM (VALUES (2147483647), (2147483647))";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+------------+",
+        "| s          |",
+        "+------------+",
+        "| 4294967294 |",
+        "+------------+",
+    ];
+    assert_batches_eq!(expected, &actual);
+    Ok(())
+}
+
 #[tokio::test]
 async fn csv_query_sum_crossjoin() {
     let ctx = SessionContext::new();
@@ -1295,6 +1659,180 @@ async fn csv_query_array_agg_one() -> Result<()> {
     Ok(())
 }
 
+fn register_ordered_aggregate_table(ctx: &SessionContext) -> Result<()> {
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("g", DataType::Utf8, false),
+        Field::new("k", DataType::Int32, false),
+        Field::new("v", DataType::Utf8, true),
+    ]));
+    let batch1 = RecordBatch::try_new(
+        schema.clone(),
+        vec![
+            Arc::new(StringArray::from(vec!["a", "a", "b"])),
+            Arc::new(Int32Array::from_slice([3, 1, 2])),
+            Arc::new(StringArray::from(vec![Some("x3"), None, Some("y2")])),
+        ],
+    )?;
+    let batch2 = RecordBatch::try_new(
+        schema.clone(),
+        vec![
+            Arc::new(StringArray::from(vec!["b", "a", "b"])),
+            Arc::new(Int32Array::from_slice([1, 2, 3])),
+            Arc::new(StringArray::from(vec![Some("y1"), Some("x2"), Some("y3")])),
+        ],
+    )?;
+    let provider = MemTable::try_new(schema, vec![vec![batch1], vec![batch2]])?;
+    ctx.register_table("t", Arc::new(provider))?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn ordered_first_last_value() -> Result<()> {
+    let ctx = SessionContext::new();
+    register_ordered_aggregate_table(&ctx)?;
+    let sql = "SELECT g, \
+        first_value(v ORDER BY k) AS first_v, \
+        last_value(v ORDER BY k) AS last_v, \
+        first_value(v ORDER BY k DESC) AS first_desc \
+        FROM t GROUP BY g ORDER BY g";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+---+---------+--------+------------+",
+        "| g | first_v | last_v | first_desc |",
+        "+---+---------+--------+------------+",
+        "| a |         | x3     | x3         |",
+        "| b | y1      | y3     | y3         |",
+        "+---+---------+--------+------------+",
+    ];
+    assert_batches_eq!(expected, &actual);
+    Ok(())
+}
+
+#[tokio::test]
+async fn ordered_first_last_value_nulls() -> Result<()> {
+    let ctx = SessionContext::new();
+    let sql = "SELECT first_value(v ORDER BY k NULLS FIRST) AS a, \
+        first_value(v ORDER BY k) AS b, \
+        first_value(v ORDER BY k DESC NULLS LAST) AS c, \
+        last_value(v ORDER BY k DESC) AS d \
+        FROM (SELECT column1 AS k, column2 AS v \
+        FROM (VALUES (1, 'a'), (NULL, 'b'), (2, 'c'))) t";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+---+---+---+---+",
+        "| a | b | c | d |",
+        "+---+---+---+---+",
+        "| b | a | c | a |",
+        "+---+---+---+---+",
+    ];
+    assert_batches_eq!(expected, &actual);
+    Ok(())
+}
+
+#[tokio::test]
+async fn ordered_aggregate_invalid_arguments() -> Result<()> {
+    let ctx = SessionContext::new();
+    register_ordered_aggregate_table(&ctx)?;
+    let cases = [
+        // the order is only given by ORDER BY
+        (
+            "SELECT first_value(v, k, true) FROM t",
+            "takes a single argument",
+        ),
+        (
+            "SELECT first_value(v ORDER BY k) OVER (PARTITION BY g) FROM t",
+            "ORDER BY in a function call is only supported",
+        ),
+    ];
+    for (sql, expected) in cases {
+        let err = plan_and_collect(&ctx, sql).await.unwrap_err();
+        assert!(err.to_string().contains(expected), "{}", err);
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn ordered_array_agg_and_string_agg() -> Result<()> {
+    let ctx = SessionContext::new();
+    register_ordered_aggregate_table(&ctx)?;
+    let sql = "SELECT g, \
+        array_agg(v ORDER BY k) AS vs, \
+        string_agg(v, ',') AS s \
+        FROM (SELECT * FROM t ORDER BY k) t GROUP BY g ORDER BY g";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+---+--------------+----------+",
+        "| g | vs           | s        |",
+        "+---+--------------+----------+",
+        "| a | [, x2, x3]   | x2,x3    |",
+        "| b | [y1, y2, y3] | y1,y2,y3 |",
+        "+---+--------------+----------+",
+    ];
+    assert_batches_eq!(expected, &actual);
+    Ok(())
+}
+
+#[tokio::test]
+async fn string_agg_requires_literal_delimiter() -> Result<()> {
+    let ctx = SessionContext::new();
+    register_ordered_aggregate_table(&ctx)?;
+    let err = plan_and_collect(&ctx, "SELECT string_agg(v, g) FROM t")
+        .await
+        .unwrap_err();
+    assert!(
+        err.to_string().contains("must be a string literal"),
+        "{}",
+        err
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn csv_query_aggregate_filter() -> Result<()> {
+    let ctx = SessionContext::new();
+    register_aggregate_csv(&ctx).await?;
+    let sql = "SELECT c1, \
+        count(*) AS cnt, \
+        count(*) FILTER (WHERE c2 = 1) AS cnt_1, \
+        sum(c3) FILTER (WHERE c2 > 3) AS sum_gt_3, \
+        max(c3) FILTER (WHERE c2 = 2) AS max_2 \
+        FROM aggregate_test_100 GROUP BY c1 ORDER BY c1";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+----+-----+-------+----------+-------+",
+        "| c1 | cnt | cnt_1 | sum_gt_3 | max_2 |",
+        "+----+-----+-------+----------+-------+",
+        "| a  | 21  | 5     | -224     | 45    |",
+        "| b  | 19  | 3     | -224     | 68    |",
+        "| c  | 21  | 4     | -19      | 29    |",
+        "| d  | 18  | 7     | 63       | 122   |",
+        "| e  | 21  | 3     | 239      | 97    |",
+        "+----+-----+-------+----------+-------+",
+    ];
+    assert_batches_eq!(expected, &actual);
+    Ok(())
+}
+
+#[tokio::test]
+async fn aggregate_filter_without_group_by() -> Result<()> {
+    let ctx = SessionContext::new();
+    register_aggregate_csv(&ctx).await?;
+    let sql = "SELECT count(*) FILTER (WHERE c2 = 1) AS cnt_1, \
+        count(*) FILTER (WHERE c2 <> 1) AS cnt_not_1, \
+        count(*) AS cnt \
+        FROM aggregate_test_100";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+-------+-----------+-----+",
+        "| cnt_1 | cnt_not_1 | cnt |",
+        "+-------+-----------+-----+",
+        "| 22    | 78        | 100 |",
+        "+-------+-----------+-----+",
+    ];
+    assert_batches_eq!(expected, &actual);
+    Ok(())
+}
+
 #[tokio::test]
 async fn csv_query_array_agg_with_overflow() -> Result<()> {
     let ctx = SessionContext::new();
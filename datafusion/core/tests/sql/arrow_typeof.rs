@@ -137,3 +137,81 @@ async fn arrow_typeof_utf8() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn arrow_typeof_nested() -> Result<()> {
+    let ctx = SessionContext::new();
+    let sql = "SELECT arrow_typeof(make_array(1, 2))";
+    let actual = execute(&ctx, sql).await;
+    let expected = "FixedSizeList(Field { name: \"item\", data_type: Int64, nullable: true, dict_id: 0, dict_is_ordered: false, metadata: None }, 2)";
+    assert_eq!(expected, &actual[0][0]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn arrow_cast_dictionary() -> Result<()> {
+    let ctx = SessionContext::new();
+    let sql = "SELECT arrow_cast('foo', 'Dictionary(Int32, Utf8)') AS d, \
+               arrow_typeof(arrow_cast('foo', 'Dictionary(Int32, Utf8)')) AS t";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+-----+-------------------------+",
+        "| d   | t                       |",
+        "+-----+-------------------------+",
+        "| foo | Dictionary(Int32, Utf8) |",
+        "+-----+-------------------------+",
+    ];
+    assert_batches_eq!(expected, &actual);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn arrow_cast_timestamp_with_timezone() -> Result<()> {
+    let ctx = SessionContext::new();
+    let sql =
+        "SELECT arrow_typeof(arrow_cast(to_timestamp('2020-09-08T12:00:00+00:00'), \
+               'Timestamp(Second, Some(\"+08:00\"))'))";
+    let actual = execute(&ctx, sql).await;
+    let expected = "Timestamp(Second, Some(\"+08:00\"))";
+    assert_eq!(expected, &actual[0][0]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn arrow_cast_invalid_type() -> Result<()> {
+    let ctx = SessionContext::new();
+
+    let err = ctx
+        .sql("SELECT arrow_cast(1, 'Foo(Int32)')")
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Error during planning: Unsupported type 'Foo(Int32)': unknown data type 'Foo' at position 3"
+    );
+
+    let err = ctx
+        .sql("SELECT arrow_cast(1, 'Dictionary(Int32 Utf8)')")
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Error during planning: Unsupported type 'Dictionary(Int32 Utf8)': expected ',' at position 17"
+    );
+
+    let err = ctx
+        .sql("SELECT arrow_cast(1, 'Int' || '32')")
+        .await
+        .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("arrow_cast requires its second argument to be a constant string"),
+        "{}",
+        err
+    );
+
+    Ok(())
+}
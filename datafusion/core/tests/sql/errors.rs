@@ -80,7 +80,9 @@ async fn query_cte_incorrect() -> Result<()> {
     assert!(plan.is_err());
     assert_eq!(
         format!("{}", plan.unwrap_err()),
-        "Error during planning: table 'datafusion.public.t' not found"
+        "Error during planning: table 'datafusion.public.t' not found, \
+         searched schema 'public' of catalog 'datafusion' \
+         (the default catalog and schema, as the name is unqualified)"
     );
 
     // forward referencing
@@ -89,7 +91,9 @@ async fn query_cte_incorrect() -> Result<()> {
     assert!(plan.is_err());
     assert_eq!(
         format!("{}", plan.unwrap_err()),
-        "Error during planning: table 'datafusion.public.u' not found"
+        "Error during planning: table 'datafusion.public.u' not found, \
+         searched schema 'public' of catalog 'datafusion' \
+         (the default catalog and schema, as the name is unqualified)"
     );
 
     // wrapping should hide u
@@ -98,7 +102,9 @@ async fn query_cte_incorrect() -> Result<()> {
     assert!(plan.is_err());
     assert_eq!(
         format!("{}", plan.unwrap_err()),
-        "Error during planning: table 'datafusion.public.u' not found"
+        "Error during planning: table 'datafusion.public.u' not found, \
+         searched schema 'public' of catalog 'datafusion' \
+         (the default catalog and schema, as the name is unqualified)"
     );
 
     Ok(())
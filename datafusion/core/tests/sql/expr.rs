@@ -698,6 +698,85 @@ async fn test_mathematical_expressions_with_null() -> Result<()> {
     test_expression!("atan2(NULL, NULL)", "NULL");
     test_expression!("atan2(1, NULL)", "NULL");
     test_expression!("atan2(NULL, 1)", "NULL");
+    test_expression!("round(NULL, 2)", "NULL");
+    test_expression!("round(1.5, NULL)", "NULL");
+    test_expression!("trunc(NULL, 2)", "NULL");
+    test_expression!("log(NULL, 2)", "NULL");
+    test_expression!("log(2, NULL)", "NULL");
+    test_expression!("cbrt(NULL)", "NULL");
+    test_expression!("degrees(NULL)", "NULL");
+    test_expression!("radians(NULL)", "NULL");
+    test_expression!("factorial(NULL)", "NULL");
+    test_expression!("gcd(NULL, 2)", "NULL");
+    test_expression!("lcm(2, NULL)", "NULL");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_mathematical_expressions() -> Result<()> {
+    test_expression!("round(3.14159, 2)", "3.14");
+    test_expression!("round(1234.5, -2)", "1200");
+    // rounds half away from zero rather than to even
+    test_expression!("round(2.5)", "3");
+    test_expression!("round(-2.5)", "-3");
+    test_expression!("round(0.125, 2)", "0.13");
+    test_expression!("round(CAST(2.5 AS FLOAT), 0)", "3");
+    test_expression!("trunc(3.789, 1)", "3.7");
+    test_expression!("trunc(-3.789, 2)", "-3.78");
+    test_expression!("log(2, 8)", "3");
+    test_expression!("log(3, 81)", "4");
+    test_expression!("log(100)", "2");
+    test_expression!("cbrt(-8)", "-2");
+    test_expression!("cbrt(27.0)", "3");
+    test_expression!("degrees(pi())", "180");
+    test_expression!("radians(180) = pi()", "true");
+    test_expression!("power(2, 10)", "1024");
+    test_expression!("power(-2, 3)", "-8");
+    test_expression!("power(4, 0.5)", "2");
+    test_expression!("factorial(0)", "1");
+    test_expression!("factorial(20)", "2432902008176640000");
+    test_expression!("gcd(12, -18)", "6");
+    test_expression!("gcd(0, 0)", "0");
+    test_expression!("lcm(-4, 6)", "12");
+    test_expression!("lcm(0, 6)", "0");
+    test_expression!("power(CAST(NULL AS DOUBLE), 0.5)", "NULL");
+    test_expression!("arrow_typeof(round(CAST(2.5 AS FLOAT), 1))", "Float32");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_mathematical_expressions_integer_overflow() -> Result<()> {
+    let ctx = SessionContext::new();
+    for (sql, expected) in [
+        (
+            "SELECT power(2, 63)",
+            "Integer overflow computing power(2, 63)",
+        ),
+        (
+            "SELECT power(2, -1)",
+            "Negative exponent -1 for function power",
+        ),
+        (
+            "SELECT factorial(21)",
+            "Integer overflow computing factorial(21)",
+        ),
+        (
+            "SELECT factorial(-1)",
+            "Factorial of negative number -1 is undefined",
+        ),
+        (
+            "SELECT lcm(9223372036854775807, 2)",
+            "Integer overflow computing lcm(9223372036854775807, 2)",
+        ),
+    ] {
+        let err = plan_and_collect(&ctx, sql).await.unwrap_err();
+        assert!(
+            err.to_string().contains(expected),
+            "{}: unexpected error {}",
+            sql,
+            err
+        );
+    }
     Ok(())
 }
 
@@ -1151,6 +1230,59 @@ async fn test_regex_expressions() -> Result<()> {
     test_expression!("regexp_match('aa', '.*-(\\d)')", "NULL");
     test_expression!("regexp_match(NULL, '.*-(\\d)')", "NULL");
     test_expression!("regexp_match('aaa-0', NULL)", "NULL");
+    test_expression!("regexp_match('FooBar', 'o(b)', 'i')", "[B]");
+    test_expression!("regexp_replace('aaa', 'a', 'b')", "baa");
+    test_expression!("regexp_replace('aaa', 'a', 'b', 'g')", "bbb");
+    test_expression!("regexp_replace('aAa', 'a', 'b', 'gi')", "bbb");
+    test_expression!(
+        "regexp_replace('foobarbaz', 'b(..)', 'X$1Y', 'g')",
+        "fooXarYXazY"
+    );
+    test_expression!("regexp_replace('cost', 'cost', '$$1')", "$1");
+    test_expression!("regexp_like('FooBar', '^foo')", "false");
+    test_expression!("regexp_like('FooBar', '^foo', 'i')", "true");
+    test_expression!("regexp_like(NULL, '^foo')", "NULL");
+    Ok(())
+}
+
+#[tokio::test]
+#[cfg_attr(not(feature = "regex_expressions"), ignore)]
+async fn invalid_literal_regex_fails_planning() -> Result<()> {
+    let ctx = SessionContext::new();
+    register_aggregate_csv(&ctx).await?;
+
+    for (sql, function) in [
+        (
+            "SELECT regexp_match(c1, '(') FROM aggregate_test_100",
+            "regexp_match",
+        ),
+        (
+            "SELECT regexp_replace(c1, '[a', 'b') FROM aggregate_test_100",
+            "regexp_replace",
+        ),
+        (
+            "SELECT c1 FROM aggregate_test_100 WHERE regexp_like(c1, 'a{2')",
+            "regexp_like",
+        ),
+    ] {
+        let plan = ctx.create_logical_plan(sql)?;
+        let err = ctx.create_physical_plan(&plan).await.unwrap_err();
+        assert!(matches!(err, DataFusionError::Plan(_)), "{}", err);
+        assert!(
+            err.to_string().contains(&format!("for {}", function)),
+            "{}",
+            err
+        );
+    }
+
+    let plan = ctx.create_logical_plan(
+        "SELECT regexp_like(c1, 'a', 'g') FROM aggregate_test_100",
+    )?;
+    let err = ctx.create_physical_plan(&plan).await.unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Error during planning: regexp_like() does not support the \"global\" option"
+    );
     Ok(())
 }
 
@@ -1168,6 +1300,112 @@ async fn test_cast_expressions() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn try_cast_invalid_values() -> Result<()> {
+    let ctx = SessionContext::new();
+    let sql = "SELECT TRY_CAST(column1 AS BIGINT) AS i, \
+                      TRY_CAST(column2 AS DATE) AS d, \
+                      TRY_CAST(column3 AS TIMESTAMP) AS t \
+               FROM (VALUES \
+                 ('12', '2021-03-01', '2021-03-01T12:30:00'), \
+                 ('abc', '2021-02-30', '2021-03-01T25:00:00'), \
+                 ('1.5', 'abc', 'abc'))";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+----+------------+---------------------+",
+        "| i  | d          | t                   |",
+        "+----+------------+---------------------+",
+        "| 12 | 2021-03-01 | 2021-03-01T12:30:00 |",
+        "|    |            |                     |",
+        "|    |            |                     |",
+        "+----+------------+---------------------+",
+    ];
+    assert_batches_eq!(expected, &actual);
+
+    // CAST fails the query on the same values
+    let sql = "SELECT CAST(column1 AS BIGINT) FROM (VALUES ('12'), ('abc'))";
+    assert!(plan_and_collect(&ctx, sql).await.is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn lenient_arithmetic() -> Result<()> {
+    let sql = "SELECT column1 / column2 AS q, column1 % column2 AS r, column3 + 1 AS s \
+               FROM (VALUES (7, 0, 9223372036854775807), (7, 2, 1))";
+
+    // division by a literal zero fails the query by default, while division
+    // by a column with zeros yields null for those rows
+    let ctx = SessionContext::new();
+    let err = plan_and_collect(&ctx, "SELECT 1 / 0").await.unwrap_err();
+    assert!(err.to_string().contains("Divide by zero"), "{}", err);
+    let actual =
+        execute_to_batches(&ctx, "SELECT column1 / column2 AS q FROM (VALUES (7, 0))")
+            .await;
+    let expected = vec!["+---+", "| q |", "+---+", "|   |", "+---+"];
+    assert_batches_eq!(expected, &actual);
+
+    // both fail the query with checked division
+    let ctx =
+        SessionContext::with_config(SessionConfig::new().with_checked_division(true));
+    for sql in [
+        "SELECT 1 / 0",
+        "SELECT column1 / column2 FROM (VALUES (7, 0))",
+    ] {
+        let err = plan_and_collect(&ctx, sql).await.unwrap_err();
+        assert!(
+            err.to_string().contains("Divide by zero"),
+            "{}: unexpected error {}",
+            sql,
+            err
+        );
+    }
+
+    let ctx =
+        SessionContext::with_config(SessionConfig::new().with_lenient_arithmetic(true));
+    let actual = execute_to_batches(&ctx, "SELECT 1 / 0 AS q").await;
+    let expected = vec!["+---+", "| q |", "+---+", "|   |", "+---+"];
+    assert_batches_eq!(expected, &actual);
+
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+---+---+---+",
+        "| q | r | s |",
+        "+---+---+---+",
+        "|   |   |   |",
+        "| 3 | 1 | 2 |",
+        "+---+---+---+",
+    ];
+    assert_batches_eq!(expected, &actual);
+    Ok(())
+}
+
+#[tokio::test]
+async fn wrapping_arithmetic() -> Result<()> {
+    let sql = "SELECT column1 + 1 AS s FROM (VALUES (9223372036854775807))";
+
+    // integer overflows fail the query naming the expression by default
+    let ctx = SessionContext::new();
+    let err = plan_and_collect(&ctx, sql).await.unwrap_err();
+    assert!(
+        err.to_string().contains("in column1@0 + 1"),
+        "unexpected error {}",
+        err
+    );
+
+    let ctx =
+        SessionContext::with_config(SessionConfig::new().with_wrapping_arithmetic(true));
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+----------------------+",
+        "| s                    |",
+        "+----------------------+",
+        "| -9223372036854775808 |",
+        "+----------------------+",
+    ];
+    assert_batches_eq!(expected, &actual);
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_random_expression() -> Result<()> {
     let ctx = create_ctx();
@@ -1189,6 +1427,29 @@ async fn test_uuid_expression() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_xxhash64_expression() -> Result<()> {
+    test_expression!("xxhash64('a')", "13625245857104323011");
+    test_expression!("hash('a')", "13625245857104323011");
+    test_expression!("xxhash64(1)", "11468921228449061269");
+    test_expression!("xxhash64('a', 1)", "10939182464651890343");
+    test_expression!("xxhash64('a', NULL)", "NULL");
+    test_expression!("arrow_typeof(xxhash64(1.5, true))", "UInt64");
+
+    // types without a canonical encoding are rejected when planning
+    let ctx = SessionContext::new();
+    let err = plan_and_collect(&ctx, "SELECT xxhash64('a', make_array(1, 2))")
+        .await
+        .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("The function does not support arguments of type List"),
+        "{}",
+        err
+    );
+    Ok(())
+}
+
 #[tokio::test]
 async fn case_with_bool_type_result() -> Result<()> {
     let ctx = SessionContext::new();
@@ -254,18 +254,18 @@ async fn coalesce_result_with_default_value() -> Result<()> {
 
     let ctx = SessionContext::new();
     ctx.register_batch("test", data)?;
-    let sql = "SELECT COALESCE(c1, c2, '-1') FROM test";
+    let sql = "SELECT COALESCE(c1, c2, -1) FROM test";
     let actual = execute_to_batches(&ctx, sql).await;
     let expected = vec![
-        "+--------------------------------------+",
-        "| coalesce(test.c1,test.c2,Utf8(\"-1\")) |",
-        "+--------------------------------------+",
-        "| 0                                    |",
-        "| 1                                    |",
-        "| 1                                    |",
-        "| 1                                    |",
-        "| -1                                   |",
-        "+--------------------------------------+",
+        "+-------------------------------------+",
+        "| coalesce(test.c1,test.c2,Int64(-1)) |",
+        "+-------------------------------------+",
+        "| 0                                   |",
+        "| 1                                   |",
+        "| 1                                   |",
+        "| 1                                   |",
+        "| -1                                  |",
+        "+-------------------------------------+",
     ];
     assert_batches_eq!(expected, &actual);
     Ok(())
@@ -334,6 +334,128 @@ async fn coalesce_mul_with_default_value() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn coalesce_guards_erroring_arguments() -> Result<()> {
+    let ctx =
+        SessionContext::with_config(SessionConfig::new().with_checked_division(true));
+    // `10 / column2` is only evaluated on the rows where column1 is NULL,
+    // so the zero divisor in the first row does not fail the query
+    let sql = "SELECT COALESCE(column1, 10 / column2, 1 / 0) AS c, \
+               IFNULL(column1, 0) AS i, NVL(column1, 0) AS n \
+               FROM (VALUES (1, 0), (NULL, 5))";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+---+---+---+",
+        "| c | i | n |",
+        "+---+---+---+",
+        "| 1 | 1 | 1 |",
+        "| 2 | 0 | 0 |",
+        "+---+---+---+",
+    ];
+    assert_batches_eq!(expected, &actual);
+
+    // the error surfaces once a row needs the erroring argument
+    let sql = "SELECT COALESCE(column1, 10 / column2) FROM (VALUES (NULL, 0))";
+    let err = plan_and_collect(&ctx, sql).await.unwrap_err();
+    assert!(err.to_string().contains("Divide by zero"), "{}", err);
+    Ok(())
+}
+
+#[tokio::test]
+async fn coalesce_requires_common_type() -> Result<()> {
+    let ctx = SessionContext::new();
+    for sql in [
+        "SELECT COALESCE(column1, 'none') FROM (VALUES (1))",
+        "SELECT NULLIF(column1, 'a') FROM (VALUES (1))",
+        "SELECT GREATEST(column1, 'a', 2.5) FROM (VALUES (1))",
+    ] {
+        let err = plan_and_collect(&ctx, sql).await.unwrap_err();
+        assert!(
+            err.to_string().contains("There is no common type"),
+            "{}: unexpected error {}",
+            sql,
+            err
+        );
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn nullif_any_type() -> Result<()> {
+    let ctx = SessionContext::new();
+    let sql = "SELECT NULLIF(column1, 'b') AS s, NULLIF(column2, 2.5) AS f \
+               FROM (VALUES ('a', 1.5), ('b', 2.5), (NULL, NULL))";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+---+-----+",
+        "| s | f   |",
+        "+---+-----+",
+        "| a | 1.5 |",
+        "|   |     |",
+        "|   |     |",
+        "+---+-----+",
+    ];
+    assert_batches_eq!(expected, &actual);
+    Ok(())
+}
+
+#[tokio::test]
+async fn greatest_and_least() -> Result<()> {
+    let ctx = SessionContext::new();
+    // NULL arguments are ignored, the result is only NULL when all are NULL
+    let sql = "SELECT GREATEST(column1, column2, 3) AS g, LEAST(column1, column2) AS l \
+               FROM (VALUES (1, 5.5), (NULL, 2.5), (NULL, NULL))";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+-----+-----+",
+        "| g   | l   |",
+        "+-----+-----+",
+        "| 5.5 | 1   |",
+        "| 3   | 2.5 |",
+        "| 3   |     |",
+        "+-----+-----+",
+    ];
+    assert_batches_eq!(expected, &actual);
+
+    let sql = "SELECT GREATEST('apple', 'pear', NULL), LEAST('apple', 'pear', NULL)";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+-------------------------------------------+----------------------------------------+",
+        "| greatest(Utf8(\"apple\"),Utf8(\"pear\"),NULL) | least(Utf8(\"apple\"),Utf8(\"pear\"),NULL) |",
+        "+-------------------------------------------+----------------------------------------+",
+        "| pear                                      | apple                                  |",
+        "+-------------------------------------------+----------------------------------------+",
+    ];
+    assert_batches_eq!(expected, &actual);
+    Ok(())
+}
+
+#[tokio::test]
+async fn session_functions() -> Result<()> {
+    let ctx = SessionContext::with_config(
+        SessionConfig::new().with_default_catalog_and_schema("my_catalog", "my_schema"),
+    );
+    let sql = "SELECT current_catalog() AS catalog, current_schema() AS schema";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+------------+-----------+",
+        "| catalog    | schema    |",
+        "+------------+-----------+",
+        "| my_catalog | my_schema |",
+        "+------------+-----------+",
+    ];
+    assert_batches_eq!(expected, &actual);
+
+    let actual = execute(&ctx, "SELECT version()").await;
+    assert!(
+        actual[0][0].starts_with("Apache Arrow DataFusion "),
+        "{}",
+        actual[0][0]
+    );
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn case_sensitive_identifiers_functions() {
     let ctx = SessionContext::new();
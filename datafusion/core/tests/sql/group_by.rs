@@ -582,6 +582,120 @@ async fn group_by_largeutf8() {
     assert_batches_sorted_eq!(expected, &results);
 }
 
+#[tokio::test]
+async fn group_by_list() -> Result<()> {
+    let ctx = SessionContext::new();
+
+    let list_array: ListArray = ListArray::from_iter_primitive::<Int64Type, _, _>(vec![
+        Some(vec![Some(1), Some(2)]),
+        Some(vec![Some(3)]),
+        Some(vec![Some(1), Some(2)]),
+        None,
+        Some(vec![Some(3)]),
+        Some(vec![Some(1), Some(2)]),
+    ]);
+    let list_array = Arc::new(list_array);
+
+    let val_array: Int64Array = vec![1, 2, 2, 4, 1, 1].into();
+    let val_array = Arc::new(val_array);
+
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("l", list_array.data_type().clone(), true),
+        Field::new("val", val_array.data_type().clone(), false),
+    ]));
+
+    let batch = RecordBatch::try_new(schema.clone(), vec![list_array, val_array])?;
+
+    ctx.register_batch("t", batch)?;
+
+    let results =
+        plan_and_collect(&ctx, "SELECT l, count(val), sum(val) FROM t GROUP BY l")
+            .await?;
+
+    let expected = vec![
+        "+--------+--------------+------------+",
+        "| l      | COUNT(t.val) | SUM(t.val) |",
+        "+--------+--------------+------------+",
+        "|        | 1            | 4          |",
+        "| [1, 2] | 3            | 4          |",
+        "| [3]    | 2            | 3          |",
+        "+--------+--------------+------------+",
+    ];
+    assert_batches_sorted_eq!(expected, &results);
+
+    let results = plan_and_collect(&ctx, "SELECT min(l), max(l) FROM t").await?;
+
+    let expected = vec![
+        "+----------+----------+",
+        "| MIN(t.l) | MAX(t.l) |",
+        "+----------+----------+",
+        "| [1, 2]   | [3]      |",
+        "+----------+----------+",
+    ];
+    assert_batches_eq!(expected, &results);
+    Ok(())
+}
+
+#[tokio::test]
+async fn group_by_float_nan_and_negative_zero() -> Result<()> {
+    let ctx = SessionContext::new();
+
+    let float_array = Arc::new(Float64Array::from_slice(&[
+        0.0,
+        -0.0,
+        f64::NAN,
+        f64::from_bits(f64::NAN.to_bits() | 1),
+        1.0,
+        -0.0,
+    ]));
+    let str_array: LargeStringArray = vec!["a"; 6].into_iter().map(Some).collect();
+    let str_array = Arc::new(str_array);
+    let val_array = Arc::new(Int64Array::from_slice(&[1, 2, 3, 4, 5, 6]));
+
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("f", DataType::Float64, false),
+        Field::new("s", DataType::LargeUtf8, false),
+        Field::new("val", DataType::Int64, false),
+    ]));
+    let batch =
+        RecordBatch::try_new(schema.clone(), vec![float_array, str_array, val_array])?;
+    ctx.register_batch("t", batch)?;
+
+    // -0.0 is grouped with 0.0, and all NaNs form a single group
+    let results =
+        plan_and_collect(&ctx, "SELECT f, count(val), sum(val) FROM t GROUP BY f")
+            .await?;
+    let expected = vec![
+        "+-----+--------------+------------+",
+        "| f   | COUNT(t.val) | SUM(t.val) |",
+        "+-----+--------------+------------+",
+        "| 0   | 3            | 9          |",
+        "| 1   | 1            | 5          |",
+        "| NaN | 2            | 7          |",
+        "+-----+--------------+------------+",
+    ];
+    assert_batches_sorted_eq!(expected, &results);
+
+    // LargeUtf8 keys are not supported by the row format, so these groups
+    // are compared as scalar values
+    let results = plan_and_collect(
+        &ctx,
+        "SELECT f, s, count(val), sum(val) FROM t GROUP BY f, s",
+    )
+    .await?;
+    let expected = vec![
+        "+-----+---+--------------+------------+",
+        "| f   | s | COUNT(t.val) | SUM(t.val) |",
+        "+-----+---+--------------+------------+",
+        "| 0   | a | 3            | 9          |",
+        "| 1   | a | 1            | 5          |",
+        "| NaN | a | 2            | 7          |",
+        "+-----+---+--------------+------------+",
+    ];
+    assert_batches_sorted_eq!(expected, &results);
+    Ok(())
+}
+
 #[tokio::test]
 async fn group_by_dictionary() {
     async fn run_test_case<K: ArrowDictionaryKeyType>() {
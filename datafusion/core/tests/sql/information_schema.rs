@@ -38,7 +38,9 @@ async fn information_schema_tables_not_exist_by_default() {
     assert_eq!(
         err.to_string(),
         // Error propagates from SessionState::schema_for_ref
-        "Error during planning: failed to resolve schema: information_schema"
+        "Error during planning: failed to resolve schema: information_schema \
+         in catalog 'datafusion' (the default catalog, as the name is only qualified \
+         by a schema), the schemas of the catalog are: public"
     );
 }
 
@@ -271,7 +273,9 @@ async fn information_schema_describe_table_not_exists() {
     let err = plan_and_collect(&ctx, sql_all).await.unwrap_err();
     assert_eq!(
         err.to_string(),
-        "Error during planning: table 'datafusion.public.table' not found"
+        "Error during planning: table 'datafusion.public.table' not found, \
+         searched schema 'public' of catalog 'datafusion' \
+         (the default catalog and schema, as the name is unqualified)"
     );
 }
 
@@ -368,7 +372,9 @@ async fn information_schema_show_columns() {
     assert_eq!(
         err.to_string(),
         // Error propagates from SessionState::get_table_provider
-        "Error during planning: table 'datafusion.public.T' not found"
+        "Error during planning: table 'datafusion.public.T' not found, \
+         searched schema 'public' of catalog 'datafusion' \
+         (the default catalog and schema, as the name is unqualified)"
     );
 }
 
@@ -431,7 +437,9 @@ async fn information_schema_show_table_table_names() {
     assert_eq!(
         err.to_string(),
         // Error propagates from SessionState::get_table_provider
-        "Error during planning: table 'datafusion.public.t2' not found"
+        "Error during planning: table 'datafusion.public.t2' not found, \
+         searched schema 'public' of catalog 'datafusion' \
+         (the default catalog and schema, as the name is unqualified)"
     );
 
     let err = plan_and_collect(&ctx, "SHOW columns from datafusion.public.t2")
@@ -440,7 +448,8 @@ async fn information_schema_show_table_table_names() {
     assert_eq!(
         err.to_string(),
         // Error propagates from SessionState::get_table_provider
-        "Error during planning: table 'datafusion.public.t2' not found"
+        "Error during planning: table 'datafusion.public.t2' not found, \
+         searched schema 'public' of catalog 'datafusion'"
     );
 }
 
@@ -491,7 +500,9 @@ async fn information_schema_columns_not_exist_by_default() {
     assert_eq!(
         err.to_string(),
         // Error propagates from SessionState::schema_for_ref
-        "Error during planning: failed to resolve schema: information_schema"
+        "Error during planning: failed to resolve schema: information_schema \
+         in catalog 'datafusion' (the default catalog, as the name is only qualified \
+         by a schema), the schemas of the catalog are: public"
     );
 }
 
@@ -692,24 +703,33 @@ async fn show_all() {
 
     // Has all the default values, should be in order by name
     let expected = vec![
-        "+-------------------------------------------------+---------+",
-        "| name                                            | setting |",
-        "+-------------------------------------------------+---------+",
-        "| datafusion.catalog.location                     | NULL    |",
-        "| datafusion.catalog.type                         | NULL    |",
-        "| datafusion.execution.batch_size                 | 8192    |",
-        "| datafusion.execution.coalesce_batches           | true    |",
-        "| datafusion.execution.coalesce_target_batch_size | 4096    |",
-        "| datafusion.execution.parquet.enable_page_index  | false   |",
-        "| datafusion.execution.parquet.pushdown_filters   | false   |",
-        "| datafusion.execution.parquet.reorder_filters    | false   |",
-        "| datafusion.execution.time_zone                  | +00:00  |",
-        "| datafusion.explain.logical_plan_only            | false   |",
-        "| datafusion.explain.physical_plan_only           | false   |",
-        "| datafusion.optimizer.filter_null_join_keys      | false   |",
-        "| datafusion.optimizer.max_passes                 | 3       |",
-        "| datafusion.optimizer.skip_failed_rules          | true    |",
-        "+-------------------------------------------------+---------+",
+        "+---------------------------------------------------------------------+----------+",
+        "| name                                                                | setting  |",
+        "+---------------------------------------------------------------------+----------+",
+        "| datafusion.catalog.location                                         | NULL     |",
+        "| datafusion.catalog.type                                             | NULL     |",
+        "| datafusion.execution.batch_size                                     | 8192     |",
+        "| datafusion.execution.coalesce_batches                               | true     |",
+        "| datafusion.execution.coalesce_target_batch_size                     | NULL     |",
+        "| datafusion.execution.parquet.enable_page_index                      | false    |",
+        "| datafusion.execution.parquet.pushdown_filters                       | false    |",
+        "| datafusion.execution.parquet.reorder_filters                        | false    |",
+        "| datafusion.execution.query_timeout_ms                               | 0        |",
+        "| datafusion.execution.skip_partial_aggregation_probe_ratio_threshold | 0.8      |",
+        "| datafusion.execution.skip_partial_aggregation_probe_rows            | 100000   |",
+        "| datafusion.execution.time_zone                                      | +00:00   |",
+        "| datafusion.explain.logical_plan_only                                | false    |",
+        "| datafusion.explain.physical_plan_only                               | false    |",
+        "| datafusion.format.max_column_width                                  | 0        |",
+        "| datafusion.optimizer.filter_null_join_keys                          | false    |",
+        "| datafusion.optimizer.max_passes                                     | 3        |",
+        "| datafusion.optimizer.repartition_file_min_size                      | 10485760 |",
+        "| datafusion.optimizer.repartition_file_scans                         | false    |",
+        "| datafusion.optimizer.skip_failed_rules                              | true     |",
+        "| datafusion.sql.checked_division                                     | false    |",
+        "| datafusion.sql.lenient_arithmetic                                   | false    |",
+        "| datafusion.sql.wrapping_arithmetic                                  | false    |",
+        "+---------------------------------------------------------------------+----------+",
     ];
 
     assert_batches_eq!(expected, &results);
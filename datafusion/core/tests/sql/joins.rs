@@ -190,6 +190,74 @@ async fn equijoin_left_and_condition_from_both() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn join_null_keys() -> Result<()> {
+    let ctx = SessionContext::new();
+    let schema = Arc::new(Schema::new(vec![Field::new("k", DataType::Int32, true)]));
+    let l = RecordBatch::try_new(
+        schema.clone(),
+        vec![Arc::new(Int32Array::from(vec![Some(1), None, Some(3)]))],
+    )?;
+    let r = RecordBatch::try_new(
+        schema,
+        vec![Arc::new(Int32Array::from(vec![None, Some(3), Some(4)]))],
+    )?;
+    ctx.register_batch("l", l)?;
+    ctx.register_batch("r", r)?;
+
+    // null keys do not match with `=`
+    let sql = "SELECT l.k AS lk, r.k AS rk FROM l JOIN r ON l.k = r.k";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+----+----+",
+        "| lk | rk |",
+        "+----+----+",
+        "| 3  | 3  |",
+        "+----+----+",
+    ];
+    assert_batches_eq!(expected, &actual);
+
+    // but match each other with IS NOT DISTINCT FROM
+    let sql = "SELECT l.k AS lk, r.k AS rk FROM l JOIN r ON l.k IS NOT DISTINCT FROM r.k ORDER BY lk";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+----+----+",
+        "| lk | rk |",
+        "+----+----+",
+        "| 3  | 3  |",
+        "|    |    |",
+        "+----+----+",
+    ];
+    assert_batches_eq!(expected, &actual);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn equijoin_full_and_condition_from_both() -> Result<()> {
+    let ctx = create_join_context("t1_id", "t2_id")?;
+    // the rows of both sides whose matching keys fail the condition are
+    // produced unmatched
+    let sql =
+        "SELECT t1_id, t1_int, t2_id, t2_int FROM t1 FULL JOIN t2 ON t1_id = t2_id AND t1_int >= t2_int ORDER BY t1_id, t2_id";
+    let expected = vec![
+        "+-------+--------+-------+--------+",
+        "| t1_id | t1_int | t2_id | t2_int |",
+        "+-------+--------+-------+--------+",
+        "| 11    | 1      |       |        |",
+        "| 22    | 2      | 22    | 1      |",
+        "| 33    | 3      |       |        |",
+        "| 44    | 4      | 44    | 3      |",
+        "|       |        | 11    | 3      |",
+        "|       |        | 55    | 3      |",
+        "+-------+--------+-------+--------+",
+    ];
+    let actual = execute_to_batches(&ctx, sql).await;
+    assert_batches_eq!(expected, &actual);
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn equijoin_right_and_condition_from_right() -> Result<()> {
     let ctx = create_join_context("t1_id", "t2_id")?;
@@ -576,6 +644,314 @@ async fn left_join_using() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn join_using_vs_on() -> Result<()> {
+    let ctx = create_join_context("id", "id")?;
+
+    // the column of a right join is the one of its right input
+    let sql = "SELECT id, t1_name, t2_name FROM t1 RIGHT JOIN t2 USING (id) ORDER BY id";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+----+---------+---------+",
+        "| id | t1_name | t2_name |",
+        "+----+---------+---------+",
+        "| 11 | a       | z       |",
+        "| 22 | b       | y       |",
+        "| 44 | d       | x       |",
+        "| 55 |         | w       |",
+        "+----+---------+---------+",
+    ];
+    assert_batches_eq!(expected, &actual);
+
+    // the columns of both inputs of a full join are coalesced, and only
+    // expanded once by a wildcard
+    let sql = "SELECT * FROM t1 FULL JOIN t2 USING (id) ORDER BY id";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+----+---------+--------+---------+--------+",
+        "| id | t1_name | t1_int | t2_name | t2_int |",
+        "+----+---------+--------+---------+--------+",
+        "| 11 | a       | 1      | z       | 3      |",
+        "| 22 | b       | 2      | y       | 1      |",
+        "| 33 | c       | 3      |         |        |",
+        "| 44 | d       | 4      | x       | 3      |",
+        "| 55 |         |        | w       | 3      |",
+        "+----+---------+--------+---------+--------+",
+    ];
+    assert_batches_eq!(expected, &actual);
+
+    let sql = "SELECT id, COUNT(t2_name) AS n FROM t1 FULL JOIN t2 USING (id) \
+        GROUP BY id ORDER BY id";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+----+---+",
+        "| id | n |",
+        "+----+---+",
+        "| 11 | 1 |",
+        "| 22 | 1 |",
+        "| 33 | 0 |",
+        "| 44 | 1 |",
+        "| 55 | 1 |",
+        "+----+---+",
+    ];
+    assert_batches_eq!(expected, &actual);
+
+    // the columns of both inputs of a join on an expression are kept
+    let sql = "SELECT * FROM t1 RIGHT JOIN t2 ON t1.id = t2.id ORDER BY t2.id";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+----+---------+--------+----+---------+--------+",
+        "| id | t1_name | t1_int | id | t2_name | t2_int |",
+        "+----+---------+--------+----+---------+--------+",
+        "| 11 | a       | 1      | 11 | z       | 3      |",
+        "| 22 | b       | 2      | 22 | y       | 1      |",
+        "| 44 | d       | 4      | 44 | x       | 3      |",
+        "|    |         |        | 55 | w       | 3      |",
+        "+----+---------+--------+----+---------+--------+",
+    ];
+    assert_batches_eq!(expected, &actual);
+
+    // so that they can only be told apart by their qualifier
+    let sql = "SELECT id FROM t1 RIGHT JOIN t2 ON t1.id = t2.id";
+    let err = ctx.sql(sql).await.unwrap_err();
+    assert_contains!(
+        err.to_string(),
+        "Ambiguous reference to unqualified field 'id', which could be any of 't1'.'id', 't2'.'id'"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn join_using_column_references() -> Result<()> {
+    let ctx = create_join_context("id", "id")?;
+
+    // the column of a USING join has the same value wherever it is referenced
+    let test_cases = vec![
+        (
+            "SELECT t2_name FROM t1 FULL JOIN t2 USING (id) WHERE id = 55",
+            vec![
+                "+---------+",
+                "| t2_name |",
+                "+---------+",
+                "| w       |",
+                "+---------+",
+            ],
+        ),
+        (
+            "SELECT id + 1 AS next FROM t1 RIGHT JOIN t2 USING (id) ORDER BY next",
+            vec![
+                "+------+", "| next |", "+------+", "| 12   |", "| 23   |", "| 45   |",
+                "| 56   |", "+------+",
+            ],
+        ),
+        (
+            "SELECT id, COUNT(*) AS n FROM t1 FULL JOIN t2 USING (id) \
+            GROUP BY id HAVING id > 40 ORDER BY id",
+            vec![
+                "+----+---+",
+                "| id | n |",
+                "+----+---+",
+                "| 44 | 1 |",
+                "| 55 | 1 |",
+                "+----+---+",
+            ],
+        ),
+        (
+            "SELECT t1_name, t2_name FROM t1 FULL JOIN t2 USING (id) ORDER BY id DESC",
+            vec![
+                "+---------+---------+",
+                "| t1_name | t2_name |",
+                "+---------+---------+",
+                "|         | w       |",
+                "| d       | x       |",
+                "| c       |         |",
+                "| b       | y       |",
+                "| a       | z       |",
+                "+---------+---------+",
+            ],
+        ),
+        // the value of the left input of the full join is the one of the
+        // left join, which is coalesced with the right input
+        (
+            "SELECT id, t1_name, c.t2_name FROM t1 LEFT JOIN t2 USING (id) \
+            FULL JOIN t2 c USING (id) WHERE id > 30 ORDER BY id",
+            vec![
+                "+----+---------+---------+",
+                "| id | t1_name | t2_name |",
+                "+----+---------+---------+",
+                "| 33 | c       |         |",
+                "| 44 | d       | x       |",
+                "| 55 |         | w       |",
+                "+----+---------+---------+",
+            ],
+        ),
+        (
+            "SELECT * FROM t1 LEFT JOIN t2 USING (id) FULL JOIN t2 c USING (id) \
+            ORDER BY id",
+            vec![
+                "+----+---------+--------+---------+--------+---------+--------+",
+                "| id | t1_name | t1_int | t2_name | t2_int | t2_name | t2_int |",
+                "+----+---------+--------+---------+--------+---------+--------+",
+                "| 11 | a       | 1      | z       | 3      | z       | 3      |",
+                "| 22 | b       | 2      | y       | 1      | y       | 1      |",
+                "| 33 | c       | 3      |         |        |         |        |",
+                "| 44 | d       | 4      | x       | 3      | x       | 3      |",
+                "| 55 |         |        |         |        | w       | 3      |",
+                "+----+---------+--------+---------+--------+---------+--------+",
+            ],
+        ),
+        (
+            "SELECT t2_name FROM t1 NATURAL RIGHT JOIN t2 WHERE id = 55",
+            vec![
+                "+---------+",
+                "| t2_name |",
+                "+---------+",
+                "| w       |",
+                "+---------+",
+            ],
+        ),
+    ];
+    for (sql, expected) in test_cases {
+        let actual = execute_to_batches(&ctx, sql).await;
+        assert_batches_eq!(expected, &actual);
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn join_qualified_names() -> Result<()> {
+    let ctx = create_join_context("id", "id")?;
+
+    // self join
+    let sql = "SELECT a.id, b.id, b.t1_name FROM t1 a JOIN t1 b ON a.id = b.id + 11 \
+        ORDER BY a.id";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+----+----+---------+",
+        "| id | id | t1_name |",
+        "+----+----+---------+",
+        "| 22 | 11 | a       |",
+        "| 33 | 22 | b       |",
+        "| 44 | 33 | c       |",
+        "+----+----+---------+",
+    ];
+    assert_batches_eq!(expected, &actual);
+
+    let sql = "SELECT id FROM t1 a JOIN t1 b ON a.id = b.id";
+    let err = ctx.sql(sql).await.unwrap_err();
+    assert_contains!(
+        err.to_string(),
+        "Ambiguous reference to unqualified field 'id', which could be any of 'a'.'id', 'b'.'id'"
+    );
+
+    // three way join, whose inputs all have an `id` column
+    let sql = "SELECT t1.id, t2.id, c.id, c.t1_name FROM t1 \
+        JOIN t2 ON t1.id = t2.id \
+        JOIN t1 c ON t2.id = c.id + 11 \
+        ORDER BY t1.id";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+----+----+----+---------+",
+        "| id | id | id | t1_name |",
+        "+----+----+----+---------+",
+        "| 22 | 22 | 11 | a       |",
+        "| 44 | 44 | 33 | c       |",
+        "+----+----+----+---------+",
+    ];
+    assert_batches_eq!(expected, &actual);
+
+    // the columns of all the inputs of consecutive USING joins are one
+    let sql = "SELECT * FROM t1 JOIN t2 USING (id) JOIN t1 c USING (id) ORDER BY id";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+----+---------+--------+---------+--------+---------+--------+",
+        "| id | t1_name | t1_int | t2_name | t2_int | t1_name | t1_int |",
+        "+----+---------+--------+---------+--------+---------+--------+",
+        "| 11 | a       | 1      | z       | 3      | a       | 1      |",
+        "| 22 | b       | 2      | y       | 1      | b       | 2      |",
+        "| 44 | d       | 4      | x       | 3      | d       | 4      |",
+        "+----+---------+--------+---------+--------+---------+--------+",
+    ];
+    assert_batches_eq!(expected, &actual);
+    Ok(())
+}
+
+#[tokio::test]
+async fn natural_join() -> Result<()> {
+    let ctx = create_join_context("id", "id")?;
+
+    // a natural join is a join using the column names common to both sides
+    let test_cases = vec![
+        (
+            "SELECT * FROM t1 NATURAL JOIN t2 ORDER BY id",
+            "SELECT * FROM t1 JOIN t2 USING (id) ORDER BY id",
+            vec![
+                "+----+---------+--------+---------+--------+",
+                "| id | t1_name | t1_int | t2_name | t2_int |",
+                "+----+---------+--------+---------+--------+",
+                "| 11 | a       | 1      | z       | 3      |",
+                "| 22 | b       | 2      | y       | 1      |",
+                "| 44 | d       | 4      | x       | 3      |",
+                "+----+---------+--------+---------+--------+",
+            ],
+        ),
+        (
+            "SELECT * FROM t1 NATURAL LEFT JOIN t2 ORDER BY id",
+            "SELECT * FROM t1 LEFT JOIN t2 USING (id) ORDER BY id",
+            vec![
+                "+----+---------+--------+---------+--------+",
+                "| id | t1_name | t1_int | t2_name | t2_int |",
+                "+----+---------+--------+---------+--------+",
+                "| 11 | a       | 1      | z       | 3      |",
+                "| 22 | b       | 2      | y       | 1      |",
+                "| 33 | c       | 3      |         |        |",
+                "| 44 | d       | 4      | x       | 3      |",
+                "+----+---------+--------+---------+--------+",
+            ],
+        ),
+        (
+            "SELECT * FROM t1 NATURAL FULL JOIN t2 ORDER BY id",
+            "SELECT * FROM t1 FULL JOIN t2 USING (id) ORDER BY id",
+            vec![
+                "+----+---------+--------+---------+--------+",
+                "| id | t1_name | t1_int | t2_name | t2_int |",
+                "+----+---------+--------+---------+--------+",
+                "| 11 | a       | 1      | z       | 3      |",
+                "| 22 | b       | 2      | y       | 1      |",
+                "| 33 | c       | 3      |         |        |",
+                "| 44 | d       | 4      | x       | 3      |",
+                "| 55 |         |        | w       | 3      |",
+                "+----+---------+--------+---------+--------+",
+            ],
+        ),
+    ];
+    for (natural_sql, using_sql, expected) in test_cases {
+        let actual = execute_to_batches(&ctx, natural_sql).await;
+        assert_batches_eq!(expected, &actual);
+        let actual = execute_to_batches(&ctx, using_sql).await;
+        assert_batches_eq!(expected, &actual);
+    }
+
+    // the join columns are found by their names on either side
+    let sql = "SELECT * FROM t1 JOIN t2 USING (t1_name)";
+    let err = ctx.sql(sql).await.unwrap_err();
+    assert_contains!(
+        err.to_string(),
+        "No field named 't1_name'. Valid fields are 't2'.'id', 't2'.'t2_name', 't2'.'t2_int'"
+    );
+
+    let ctx = create_join_context("t1_id", "t2_id")?;
+    let sql = "SELECT * FROM t1 NATURAL JOIN t2";
+    let err = ctx.sql(sql).await.unwrap_err();
+    assert_contains!(
+        err.to_string(),
+        "NATURAL JOIN requires a column name common to both sides, but the left side \
+        has columns 't1_id', 't1_name', 't1_int' and the right side has columns \
+        't2_id', 't2_name', 't2_int'"
+    );
+    Ok(())
+}
+
 #[tokio::test]
 async fn equijoin_implicit_syntax() -> Result<()> {
     let ctx = create_join_context("t1_id", "t2_id")?;
@@ -1765,3 +2141,157 @@ async fn reduce_full_join_to_inner_join() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn join_on_list_and_float_keys() -> Result<()> {
+    let ctx = SessionContext::new();
+
+    let list_type = DataType::List(Box::new(Field::new("item", DataType::Int64, true)));
+    let t1_schema = Arc::new(Schema::new(vec![
+        Field::new("t1_id", DataType::Int64, false),
+        Field::new("t1_list", list_type.clone(), true),
+        Field::new("t1_float", DataType::Float64, false),
+    ]));
+    let t1 = RecordBatch::try_new(
+        t1_schema,
+        vec![
+            Arc::new(Int64Array::from_slice(&[1, 2, 3])),
+            Arc::new(ListArray::from_iter_primitive::<Int64Type, _, _>(vec![
+                Some(vec![Some(1), Some(2)]),
+                Some(vec![Some(3)]),
+                None,
+            ])),
+            Arc::new(Float64Array::from_slice(&[0.0, f64::NAN, 1.0])),
+        ],
+    )?;
+    let t2_schema = Arc::new(Schema::new(vec![
+        Field::new("t2_id", DataType::Int64, false),
+        Field::new("t2_list", list_type, true),
+        Field::new("t2_float", DataType::Float64, false),
+    ]));
+    let t2 = RecordBatch::try_new(
+        t2_schema,
+        vec![
+            Arc::new(Int64Array::from_slice(&[10, 20, 30])),
+            Arc::new(ListArray::from_iter_primitive::<Int64Type, _, _>(vec![
+                Some(vec![Some(3)]),
+                Some(vec![Some(1), Some(2)]),
+                None,
+            ])),
+            Arc::new(Float64Array::from_slice(&[
+                f64::from_bits(f64::NAN.to_bits() | 1),
+                -0.0,
+                2.0,
+            ])),
+        ],
+    )?;
+    ctx.register_batch("t1", t1)?;
+    ctx.register_batch("t2", t2)?;
+
+    // null lists do not match
+    let sql = "SELECT t1_id, t2_id FROM t1 JOIN t2 ON t1_list = t2_list ORDER BY t1_id";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+-------+-------+",
+        "| t1_id | t2_id |",
+        "+-------+-------+",
+        "| 1     | 20    |",
+        "| 2     | 10    |",
+        "+-------+-------+",
+    ];
+    assert_batches_eq!(expected, &actual);
+
+    // -0.0 matches 0.0, and NaNs match each other
+    let sql = "SELECT t1_id, t2_id FROM t1 JOIN t2 ON t1_float = t2_float ORDER BY t1_id";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+-------+-------+",
+        "| t1_id | t2_id |",
+        "+-------+-------+",
+        "| 1     | 20    |",
+        "| 2     | 10    |",
+        "+-------+-------+",
+    ];
+    assert_batches_eq!(expected, &actual);
+    Ok(())
+}
+
+#[tokio::test]
+async fn left_join_aggregate_nullability() -> Result<()> {
+    let ctx = SessionContext::new();
+    let schema = Arc::new(Schema::new(vec![Field::new("id", DataType::Int32, false)]));
+    let batch =
+        RecordBatch::try_new(schema, vec![Arc::new(Int32Array::from_slice([1, 2]))])?;
+    ctx.register_batch("l", batch.clone())?;
+    ctx.register_batch("r", batch)?;
+
+    let sql = "SELECT l.id AS l_id, r.id AS r_id, count(*) AS cnt, sum(l.id) AS total, \
+               coalesce(r.id, 0) AS r_id_or_zero \
+               FROM l LEFT JOIN r ON l.id = r.id GROUP BY l.id, r.id";
+    let df = ctx.sql(sql).await?;
+    let physical_plan = df.create_physical_plan().await?;
+    let expected = vec![
+        ("l_id", false),
+        ("r_id", true),
+        ("cnt", false),
+        ("total", true),
+        ("r_id_or_zero", false),
+    ];
+
+    let logical: Vec<_> = df
+        .schema()
+        .fields()
+        .iter()
+        .map(|f| (f.name().as_str(), f.is_nullable()))
+        .collect();
+    assert_eq!(logical, expected);
+
+    let schema = physical_plan.schema();
+    let physical: Vec<_> = schema
+        .fields()
+        .iter()
+        .map(|f| (f.name().as_str(), f.is_nullable()))
+        .collect();
+    assert_eq!(physical, expected);
+    Ok(())
+}
+
+#[tokio::test]
+async fn join_without_equijoin_keys() -> Result<()> {
+    let ctx = create_join_context("t1_id", "t2_id")?;
+
+    // the unmatched rows of the outer sides are joined to nulls
+    let sql = "SELECT t1_id, t1_name, t2_id FROM t1 LEFT JOIN t2 ON t1_id > t2_id + 20 ORDER BY t1_id, t2_id";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+-------+---------+-------+",
+        "| t1_id | t1_name | t2_id |",
+        "+-------+---------+-------+",
+        "| 11    | a       |       |",
+        "| 22    | b       |       |",
+        "| 33    | c       | 11    |",
+        "| 44    | d       | 11    |",
+        "| 44    | d       | 22    |",
+        "+-------+---------+-------+",
+    ];
+    assert_batches_eq!(expected, &actual);
+
+    let sql = "SELECT t1_id, t1_name, t2_id FROM t1 FULL JOIN t2 ON t1_id > t2_id + 20 ORDER BY t1_id, t2_id";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+-------+---------+-------+",
+        "| t1_id | t1_name | t2_id |",
+        "+-------+---------+-------+",
+        "| 11    | a       |       |",
+        "| 22    | b       |       |",
+        "| 33    | c       | 11    |",
+        "| 44    | d       | 11    |",
+        "| 44    | d       | 22    |",
+        "|       |         | 44    |",
+        "|       |         | 55    |",
+        "+-------+---------+-------+",
+    ];
+    assert_batches_eq!(expected, &actual);
+
+    Ok(())
+}
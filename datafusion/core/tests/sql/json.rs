@@ -102,3 +102,350 @@ async fn json_explain() {
     ];
     assert_eq!(expected, actual);
 }
+
+#[tokio::test]
+#[cfg_attr(tarpaulin, ignore)]
+async fn json_explain_verbose_file_ranges() {
+    let config = SessionConfig::new()
+        .with_target_partitions(2)
+        .with_repartition_file_scans(true)
+        .with_repartition_file_min_size(100);
+    let ctx = SessionContext::with_config(config);
+    let path = format!("{}/2.json", TEST_DATA_BASE);
+    ctx.register_json("t1", &path, NdJsonReadOptions::default())
+        .await
+        .unwrap();
+
+    // the 471 bytes of the file are split in two ranges
+    let sql = "EXPLAIN VERBOSE SELECT count(*) from t1";
+    let actual = execute(&ctx, sql).await;
+    let actual = normalize_vec_for_explain(actual);
+    let physical_plan = actual
+        .iter()
+        .find(|row| row[0] == "physical_plan")
+        .map(|row| row[1].as_str())
+        .unwrap();
+    assert_contains!(
+        physical_plan,
+        "JsonExec: limit=None, files=[\
+        WORKING_DIR/tests/jsons/2.json (size=471, range=0..236), \
+        WORKING_DIR/tests/jsons/2.json (size=471, range=236..471)]"
+    );
+
+    // the sizes and ranges are only shown by the verbose explain
+    let actual = execute(&ctx, "EXPLAIN SELECT count(*) from t1").await;
+    let actual = normalize_vec_for_explain(actual);
+    assert_not_contains!(&actual[1][1], "size=");
+}
+
+#[tokio::test]
+async fn json_query_directory() -> Result<()> {
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+
+    let tmp_dir = TempDir::new()?;
+    std::fs::write(
+        tmp_dir.path().join("part-0.json"),
+        "{\"a\": 1, \"b\": \"x\"}\n{\"a\": 2, \"b\": \"y\"}\n",
+    )?;
+    let file = File::create(tmp_dir.path().join("part-1.json.gz"))?;
+    let mut encoder = GzEncoder::new(file, Compression::default());
+    encoder.write_all(b"{\"a\": 3, \"b\": \"z\", \"c\": true}\n")?;
+    encoder.finish()?;
+    // files without a JSON extension are not read
+    std::fs::write(tmp_dir.path().join("notes.txt"), "not json")?;
+
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("a", DataType::Int32, true),
+        Field::new("b", DataType::Utf8, true),
+    ]));
+    let ctx = SessionContext::new();
+    ctx.register_json(
+        "t",
+        tmp_dir.path().to_str().unwrap(),
+        NdJsonReadOptions::default().schema(schema.clone()),
+    )
+    .await?;
+
+    assert_eq!(ctx.table("t")?.schema().fields().len(), 2);
+    assert_eq!(
+        ctx.table("t")?.schema().field(0).data_type(),
+        &DataType::Int32
+    );
+
+    let sql = "SELECT a, b FROM t ORDER BY a";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+---+---+",
+        "| a | b |",
+        "+---+---+",
+        "| 1 | x |",
+        "| 2 | y |",
+        "| 3 | z |",
+        "+---+---+",
+    ];
+    assert_batches_eq!(expected, &actual);
+    Ok(())
+}
+
+#[tokio::test]
+async fn json_partition_regex() -> Result<()> {
+    let tmp_dir = TempDir::new()?;
+    let files = [
+        ("events_2021-03-01.json", "{\"a\": 1}\n{\"a\": 2}\n"),
+        ("events_2021-03-02.json", "{\"a\": 3}\n"),
+        // files not matching the regex are not read
+        ("other.json", "{\"a\": 4}\n"),
+    ];
+    for (name, data) in files {
+        std::fs::write(tmp_dir.path().join(name), data)?;
+    }
+
+    let ctx = SessionContext::new();
+    ctx.register_json(
+        "t",
+        tmp_dir.path().to_str().unwrap(),
+        NdJsonReadOptions::default()
+            .table_partition_cols(vec!["day".to_string()])
+            .partition_regex(
+                regex::Regex::new(r"^events_(?P<day>\d{4}-\d{2}-\d{2})\.json$").unwrap(),
+            ),
+    )
+    .await?;
+
+    let sql = "SELECT day, a FROM t ORDER BY a";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+------------+---+",
+        "| day        | a |",
+        "+------------+---+",
+        "| 2021-03-01 | 1 |",
+        "| 2021-03-01 | 2 |",
+        "| 2021-03-02 | 3 |",
+        "+------------+---+",
+    ];
+    assert_batches_eq!(expected, &actual);
+
+    // the partitions are pruned by their extracted values
+    let sql = "SELECT day, a FROM t WHERE day = '2021-03-02'";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+------------+---+",
+        "| day        | a |",
+        "+------------+---+",
+        "| 2021-03-02 | 3 |",
+        "+------------+---+",
+    ];
+    assert_batches_eq!(expected, &actual);
+
+    // every partition column must be captured by the regex
+    let err = ctx
+        .register_json(
+            "u",
+            tmp_dir.path().to_str().unwrap(),
+            NdJsonReadOptions::default()
+                .table_partition_cols(vec!["month".to_string()])
+                .partition_regex(regex::Regex::new(r"events_(?P<day>.*)\.json").unwrap()),
+        )
+        .await
+        .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("Partition column month is not a named capture group"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn json_group_by_struct() -> Result<()> {
+    let tmp_dir = TempDir::new()?;
+    std::fs::write(
+        tmp_dir.path().join("structs.json"),
+        "{\"id\": 1, \"s\": {\"x\": 1, \"y\": 2}}\n\
+         {\"id\": 2, \"s\": {\"x\": 1, \"y\": 3}}\n\
+         {\"id\": 3, \"s\": {\"x\": 1, \"y\": 2}}\n",
+    )?;
+
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("id", DataType::Int64, true),
+        Field::new(
+            "s",
+            DataType::Struct(vec![
+                Field::new("x", DataType::Int64, true),
+                Field::new("y", DataType::Int64, true),
+            ]),
+            true,
+        ),
+    ]));
+    let ctx = SessionContext::new();
+    ctx.register_json(
+        "t",
+        tmp_dir.path().to_str().unwrap(),
+        NdJsonReadOptions::default().schema(schema),
+    )
+    .await?;
+
+    let sql = "SELECT s, count(*) AS n, sum(id) AS ids FROM t GROUP BY s";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+------------------+---+-----+",
+        "| s                | n | ids |",
+        "+------------------+---+-----+",
+        "| {\"x\": 1, \"y\": 2} | 2 | 4   |",
+        "| {\"x\": 1, \"y\": 3} | 1 | 2   |",
+        "+------------------+---+-----+",
+    ];
+    assert_batches_sorted_eq!(expected, &actual);
+    Ok(())
+}
+
+#[tokio::test]
+async fn json_skip_comment_lines() -> Result<()> {
+    let ctx = SessionContext::new();
+    let path = format!("{}/comments.json", TEST_DATA_BASE);
+    let scan_options = datafusion::physical_plan::file_format::JsonScanOptions::new()
+        .with_comment_prefix("#");
+    ctx.register_json(
+        "t",
+        &path,
+        NdJsonReadOptions::default().scan_options(scan_options),
+    )
+    .await?;
+
+    let actual = execute_to_batches(&ctx, "SELECT a, b FROM t").await;
+    let expected = vec![
+        "+---+---+",
+        "| a | b |",
+        "+---+---+",
+        "| 1 | x |",
+        "| 2 | y |",
+        "| 4 | z |",
+        "+---+---+",
+    ];
+    assert_batches_eq!(expected, &actual);
+
+    // comment lines are not counted as records either
+    let actual = execute_to_batches(&ctx, "SELECT count(*) AS n FROM t").await;
+    let expected = vec!["+---+", "| n |", "+---+", "| 3 |", "+---+"];
+    assert_batches_eq!(expected, &actual);
+
+    // without the option the comments are not valid JSON
+    let ctx = SessionContext::new();
+    let err = ctx
+        .register_json("t", &path, NdJsonReadOptions::default())
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("Not valid JSON"), "{}", err);
+
+    // nor are the blank lines, unless skipped
+    let scan_options = datafusion::physical_plan::file_format::JsonScanOptions::new()
+        .with_comment_prefix("#")
+        .with_skip_blank_lines(false);
+    let err = ctx
+        .register_json(
+            "t",
+            &path,
+            NdJsonReadOptions::default().scan_options(scan_options),
+        )
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("Not valid JSON"), "{}", err);
+
+    let scan_options = datafusion::physical_plan::file_format::JsonScanOptions::new()
+        .with_comment_prefix("");
+    let err = ctx
+        .register_json(
+            "t",
+            &path,
+            NdJsonReadOptions::default().scan_options(scan_options),
+        )
+        .await
+        .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("The comment prefix of JSON lines can not be empty"),
+        "{}",
+        err
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn json_utf16le_encoding() -> Result<()> {
+    let ctx = SessionContext::new();
+    let path = format!("{}/utf16le.json", TEST_DATA_BASE);
+    let scan_options = datafusion::physical_plan::file_format::JsonScanOptions::new()
+        .with_encoding(datafusion::physical_plan::file_format::JsonEncoding::Utf16Le);
+    ctx.register_json(
+        "t",
+        &path,
+        NdJsonReadOptions::default().scan_options(scan_options),
+    )
+    .await?;
+
+    let batches = execute_to_batches(&ctx, "SELECT id, name FROM t ORDER BY id").await;
+    let names: Vec<String> = batches
+        .iter()
+        .flat_map(|batch| {
+            let names = batch
+                .column(1)
+                .as_any()
+                .downcast_ref::<StringArray>()
+                .unwrap();
+            names
+                .iter()
+                .flatten()
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    assert_eq!(names, vec!["café", "naïve 😀", "日本"]);
+
+    // read as UTF-8, the file is not valid JSON
+    let ctx = SessionContext::new();
+    let result = ctx
+        .register_json("t", &path, NdJsonReadOptions::default())
+        .await;
+    assert!(result.is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn json_auto_flatten() -> Result<()> {
+    let ctx = SessionContext::new();
+    let path = format!("{}/nested.json", TEST_DATA_BASE);
+    let scan_options = datafusion::physical_plan::file_format::JsonScanOptions::new()
+        .with_auto_flatten(true);
+    ctx.register_json(
+        "t",
+        &path,
+        NdJsonReadOptions::default().scan_options(scan_options),
+    )
+    .await?;
+
+    let sql = "SELECT * FROM t ORDER BY id";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+----+-------------------+------------------+-----------+",
+        "| id | user.address.city | user.address.zip | user.name |",
+        "+----+-------------------+------------------+-----------+",
+        "| 1  | Paris             | 75001            | a         |",
+        "| 2  | Lyon              |                  | b         |",
+        "| 3  |                   |                  | c         |",
+        "+----+-------------------+------------------+-----------+",
+    ];
+    assert_batches_eq!(expected, &actual);
+
+    // the flattened columns are selected by their quoted dotted name
+    let sql = "SELECT \"user.address.city\" FROM t WHERE \"user.name\" = 'b'";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+-------------------+",
+        "| user.address.city |",
+        "+-------------------+",
+        "| Lyon              |",
+        "+-------------------+",
+    ];
+    assert_batches_eq!(expected, &actual);
+    Ok(())
+}
@@ -35,6 +35,34 @@ async fn csv_query_with_predicate() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn csv_query_with_string_literal_predicate() -> Result<()> {
+    let ctx = SessionContext::new();
+    register_aggregate_csv(&ctx).await?;
+    // the strings are parsed as numbers rather than compared to the
+    // numbers formatted as strings
+    let sql =
+        "SELECT c1, c12 FROM aggregate_test_100 WHERE c12 > '0.376' AND c12 < '0.4'";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+----+---------------------+",
+        "| c1 | c12                 |",
+        "+----+---------------------+",
+        "| e  | 0.39144436569161134 |",
+        "| d  | 0.38870280983958583 |",
+        "+----+---------------------+",
+    ];
+    assert_batches_eq!(expected, &actual);
+
+    let sql = "SELECT c1 FROM aggregate_test_100 WHERE c12 > 'high'";
+    let err = plan_and_collect(&ctx, sql).await.unwrap_err();
+    assert_contains!(
+        err.to_string(),
+        "Cannot compare Float64 with 'high', which is not a valid Float64"
+    );
+    Ok(())
+}
+
 #[tokio::test]
 async fn csv_query_with_negative_predicate() -> Result<()> {
     let ctx = SessionContext::new();
@@ -236,6 +264,97 @@ async fn like_on_string_dictionaries() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn ilike_and_similar_to_on_string_dictionaries() -> Result<()> {
+    let input = vec![Some("foo"), Some("Bar"), None, Some("fa%zz")]
+        .into_iter()
+        .collect::<DictionaryArray<Int32Type>>();
+
+    let batch = RecordBatch::try_from_iter(vec![("c1", Arc::new(input) as _)]).unwrap();
+
+    let ctx = SessionContext::new();
+    ctx.register_batch("test", batch)?;
+
+    let sql = "SELECT * FROM test WHERE c1 ILIKE '%A%'";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+-------+",
+        "| c1    |",
+        "+-------+",
+        "| Bar   |",
+        "| fa%zz |",
+        "+-------+",
+    ];
+    assert_batches_eq!(expected, &actual);
+
+    let sql = "SELECT * FROM test WHERE c1 NOT ILIKE 'F%'";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec!["+-----+", "| c1  |", "+-----+", "| Bar |", "+-----+"];
+    assert_batches_eq!(expected, &actual);
+
+    // without ESCAPE a backslash escapes the wildcards of both LIKE and ILIKE
+    for sql in [
+        "SELECT * FROM test WHERE c1 LIKE 'fa\\%%'",
+        "SELECT * FROM test WHERE c1 ILIKE 'FA\\%%'",
+    ] {
+        let actual = execute_to_batches(&ctx, sql).await;
+        let expected = vec![
+            "+-------+",
+            "| c1    |",
+            "+-------+",
+            "| fa%zz |",
+            "+-------+",
+        ];
+        assert_batches_eq!(expected, &actual);
+    }
+
+    let sql = "SELECT * FROM test WHERE c1 LIKE '_a!%%' ESCAPE '!'";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+-------+",
+        "| c1    |",
+        "+-------+",
+        "| fa%zz |",
+        "+-------+",
+    ];
+    assert_batches_eq!(expected, &actual);
+
+    let sql = "SELECT * FROM test WHERE c1 SIMILAR TO '(fo+|Ba_)'";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+-----+", "| c1  |", "+-----+", "| foo |", "| Bar |", "+-----+",
+    ];
+    assert_batches_eq!(expected, &actual);
+
+    let sql = "SELECT * FROM test WHERE c1 NOT SIMILAR TO '%\\%%'";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+-----+", "| c1  |", "+-----+", "| foo |", "| Bar |", "+-----+",
+    ];
+    assert_batches_eq!(expected, &actual);
+    Ok(())
+}
+
+#[tokio::test]
+async fn between_and_in_list_coercion() -> Result<()> {
+    let ctx = SessionContext::new();
+    let sql = "SELECT \
+        to_timestamp('2020-09-08T12:30:00') BETWEEN '2020-09-08T12:00:00' AND '2020-09-08T13:00:00' AS ts, \
+        5 BETWEEN 1 AND 4.5 AS num, \
+        2 IN (1, 2.0) AS a, \
+        2 IN (1, 2.5) AS b";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+------+-------+------+-------+",
+        "| ts   | num   | a    | b     |",
+        "+------+-------+------+-------+",
+        "| true | false | true | false |",
+        "+------+-------+------+-------+",
+    ];
+    assert_batches_eq!(expected, &actual);
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_regexp_is_match() -> Result<()> {
     let input = vec![Some("foo"), Some("Barrr"), Some("Bazzz"), Some("ZZZZZ")]
@@ -297,6 +416,48 @@ async fn test_regexp_is_match() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+#[cfg_attr(not(feature = "regex_expressions"), ignore)]
+async fn regexp_like_in_where_clause() -> Result<()> {
+    let input = vec![Some("Apple"), Some("banana"), Some("APRICOT"), None]
+        .into_iter()
+        .collect::<StringArray>();
+
+    let batch = RecordBatch::try_from_iter(vec![("c1", Arc::new(input) as _)]).unwrap();
+
+    let ctx = SessionContext::new();
+    ctx.register_batch("test", batch)?;
+
+    let sql = "SELECT * FROM test WHERE regexp_like(c1, '^ap')";
+    let actual = execute_to_batches(&ctx, sql).await;
+    assert_batches_eq!(vec!["++", "++"], &actual);
+
+    let sql = "SELECT * FROM test WHERE regexp_like(c1, '^ap', 'i')";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+---------+",
+        "| c1      |",
+        "+---------+",
+        "| Apple   |",
+        "| APRICOT |",
+        "+---------+",
+    ];
+    assert_batches_eq!(expected, &actual);
+
+    let sql = "SELECT * FROM test WHERE NOT regexp_like(c1, 'an')";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+---------+",
+        "| c1      |",
+        "+---------+",
+        "| Apple   |",
+        "| APRICOT |",
+        "+---------+",
+    ];
+    assert_batches_eq!(expected, &actual);
+    Ok(())
+}
+
 #[tokio::test]
 async fn string_coercion() -> Result<()> {
     let vendor_id_utf8: StringArray =
@@ -656,8 +817,8 @@ where
     let formatted = plan.display_indent_schema().to_string();
     let actual: Vec<&str> = formatted.trim().lines().collect();
     let expected =vec![
-        "Projection: part.p_partkey, SUM(lineitem.l_extendedprice), AVG(lineitem.l_discount), COUNT(DISTINCT partsupp.ps_suppkey) [p_partkey:Int64, SUM(lineitem.l_extendedprice):Decimal128(25, 2);N, AVG(lineitem.l_discount):Decimal128(19, 6);N, COUNT(DISTINCT partsupp.ps_suppkey):Int64;N]",
-        "  Aggregate: groupBy=[[part.p_partkey]], aggr=[[SUM(lineitem.l_extendedprice), AVG(lineitem.l_discount), COUNT(DISTINCT partsupp.ps_suppkey)]] [p_partkey:Int64, SUM(lineitem.l_extendedprice):Decimal128(25, 2);N, AVG(lineitem.l_discount):Decimal128(19, 6);N, COUNT(DISTINCT partsupp.ps_suppkey):Int64;N]",
+        "Projection: part.p_partkey, SUM(lineitem.l_extendedprice), AVG(lineitem.l_discount), COUNT(DISTINCT partsupp.ps_suppkey) [p_partkey:Int64, SUM(lineitem.l_extendedprice):Decimal128(25, 2);N, AVG(lineitem.l_discount):Decimal128(19, 6);N, COUNT(DISTINCT partsupp.ps_suppkey):Int64]",
+        "  Aggregate: groupBy=[[part.p_partkey]], aggr=[[SUM(lineitem.l_extendedprice), AVG(lineitem.l_discount), COUNT(DISTINCT partsupp.ps_suppkey)]] [p_partkey:Int64, SUM(lineitem.l_extendedprice):Decimal128(25, 2);N, AVG(lineitem.l_discount):Decimal128(19, 6);N, COUNT(DISTINCT partsupp.ps_suppkey):Int64]",
         "    Inner Join: part.p_partkey = partsupp.ps_partkey [l_partkey:Int64, l_extendedprice:Decimal128(15, 2), l_discount:Decimal128(15, 2), p_partkey:Int64, p_brand:Utf8, ps_partkey:Int64, ps_suppkey:Int64]",
         "      Inner Join: lineitem.l_partkey = part.p_partkey [l_partkey:Int64, l_extendedprice:Decimal128(15, 2), l_discount:Decimal128(15, 2), p_partkey:Int64, p_brand:Utf8]",
         "        TableScan: lineitem projection=[l_partkey, l_extendedprice, l_discount] [l_partkey:Int64, l_extendedprice:Decimal128(15, 2), l_discount:Decimal128(15, 2)]",
@@ -482,6 +482,76 @@ async fn select_distinct_from_utf8() {
     assert_batches_eq!(expected, &actual);
 }
 
+#[tokio::test]
+async fn select_distinct_on() -> Result<()> {
+    let ctx = SessionContext::new();
+    let batch = RecordBatch::try_from_iter(vec![
+        (
+            "k",
+            Arc::new(StringArray::from_slice(&["a", "b", "a", "c", "b", "a"])) as _,
+        ),
+        (
+            "ts",
+            Arc::new(Int64Array::from_slice(&[1, 5, 3, 2, 4, 2])) as _,
+        ),
+        (
+            "v",
+            Arc::new(Int64Array::from_slice(&[10, 20, 30, 40, 50, 60])) as _,
+        ),
+    ])?;
+    ctx.register_batch("t", batch)?;
+
+    // the latest row of each key
+    let sql = "SELECT DISTINCT ON (k) k, ts, v FROM t ORDER BY k, ts DESC";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+---+----+----+",
+        "| k | ts | v  |",
+        "+---+----+----+",
+        "| a | 3  | 30 |",
+        "| b | 5  | 20 |",
+        "| c | 2  | 40 |",
+        "+---+----+----+",
+    ];
+    assert_batches_eq!(expected, &actual);
+
+    // the rows can be ordered by columns that are not selected
+    let sql = "SELECT DISTINCT ON (k) k, v FROM t ORDER BY k, ts DESC";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+---+----+",
+        "| k | v  |",
+        "+---+----+",
+        "| a | 30 |",
+        "| b | 20 |",
+        "| c | 40 |",
+        "+---+----+",
+    ];
+    assert_batches_eq!(expected, &actual);
+
+    // the limit applies to the deduplicated rows
+    let sql = "SELECT DISTINCT ON (k) k, ts, v FROM t ORDER BY k DESC, ts LIMIT 2";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+---+----+----+",
+        "| k | ts | v  |",
+        "+---+----+----+",
+        "| c | 2  | 40 |",
+        "| b | 4  | 50 |",
+        "+---+----+----+",
+    ];
+    assert_batches_eq!(expected, &actual);
+
+    // the ORDER BY has to start with the DISTINCT ON expressions
+    let sql = "SELECT DISTINCT ON (k) k, ts, v FROM t ORDER BY ts DESC";
+    let err = ctx.create_logical_plan(sql).unwrap_err();
+    assert!(err.to_string().contains(
+        "SELECT DISTINCT ON expressions must match initial ORDER BY expressions"
+    ));
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn use_between_expression_in_select_query() -> Result<()> {
     let ctx = SessionContext::new();
@@ -704,6 +774,47 @@ async fn query_nested_get_indexed_field_on_struct() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn query_struct_comparison() -> Result<()> {
+    let ctx = SessionContext::new();
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("a", DataType::Int64, false),
+        Field::new("b", DataType::Utf8, false),
+    ]));
+    let data = RecordBatch::try_new(
+        schema,
+        vec![
+            Arc::new(Int64Array::from_slice(&[1, 1, 2, 0])),
+            Arc::new(StringArray::from_slice(&["x", "y", "x", "z"])),
+        ],
+    )?;
+    ctx.register_batch("t", data)?;
+
+    let sql = "SELECT a, b FROM t WHERE struct(a, b) = struct(1, 'x')";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+---+---+",
+        "| a | b |",
+        "+---+---+",
+        "| 1 | x |",
+        "+---+---+",
+    ];
+    assert_batches_eq!(expected, &actual);
+
+    let sql = "SELECT a, b FROM t WHERE struct(a, b) > struct(1, 'x') ORDER BY a";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+---+---+",
+        "| a | b |",
+        "+---+---+",
+        "| 1 | y |",
+        "| 2 | x |",
+        "+---+---+",
+    ];
+    assert_batches_eq!(expected, &actual);
+    Ok(())
+}
+
 #[tokio::test]
 async fn query_on_string_dictionary() -> Result<()> {
     // Test to ensure DataFusion can operate on dictionary types
@@ -394,6 +394,52 @@ async fn from_unixtime() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn format_and_parse_with_format_strings() -> Result<()> {
+    let ctx = SessionContext::new();
+    ctx.register_table("ts_data", make_timestamp_nano_table()?)?;
+
+    let sql = "SELECT \
+        to_char(to_timestamp('2021-03-04 17:06:07.089', '%Y-%m-%d %H:%M:%S%.3f'), '%d.%m.%Y %I:%M:%S%.3f %p') AS t, \
+        date_format(to_date('4/3/2021', '%d/%m/%Y'), '%Y-%m-%d') AS d, \
+        to_date('2021-03-04') AS dd";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+----------------------------+------------+------------+",
+        "| t                          | d          | dd         |",
+        "+----------------------------+------------+------------+",
+        "| 04.03.2021 05:06:07.089 PM | 2021-03-04 | 2021-03-04 |",
+        "+----------------------------+------------+------------+",
+    ];
+    assert_batches_eq!(expected, &actual);
+
+    // timestamps with a time zone are formatted in it
+    let sql = "SELECT to_char(now(), '%z') AS z";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+-------+",
+        "| z     |",
+        "+-------+",
+        "| +0000 |",
+        "+-------+",
+    ];
+    assert_batches_eq!(expected, &actual);
+
+    // an invalid literal format fails planning
+    let sql = "SELECT to_char(ts, '%Y-%Q') FROM ts_data";
+    let err = ctx
+        .sql(sql)
+        .await?
+        .create_physical_plan()
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Error during planning: Invalid format string '%Y-%Q' for to_char"
+    );
+    Ok(())
+}
+
 #[tokio::test]
 async fn count_distinct_timestamps() -> Result<()> {
     let ctx = SessionContext::new();
@@ -522,6 +568,59 @@ async fn test_now_in_view() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_now_across_union_of_scans() -> Result<()> {
+    let config = SessionConfig::new().with_target_partitions(4);
+    let ctx = SessionContext::with_config(config);
+    register_aggregate_csv(&ctx).await?;
+
+    let sql = "SELECT COUNT(DISTINCT t) AS times, COUNT(*) AS num_rows FROM ( \
+        SELECT now() AS t FROM aggregate_test_100 \
+        UNION ALL \
+        SELECT CURRENT_TIMESTAMP AS t FROM aggregate_test_100)";
+    let actual = execute_to_batches(&ctx, sql).await;
+    let expected = vec![
+        "+-------+----------+",
+        "| times | num_rows |",
+        "+-------+----------+",
+        "| 1     | 200      |",
+        "+-------+----------+",
+    ];
+    assert_batches_eq!(expected, &actual);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fixed_query_execution_start_time() -> Result<()> {
+    let ctx = SessionContext::new();
+    let start_time = Utc.ymd(2021, 3, 1).and_hms(23, 30, 0);
+    ctx.set_query_execution_start_time(Some(start_time));
+
+    let sql = "SELECT CAST(now() AS BIGINT) AS nanos, current_date() AS today, \
+        current_time() AS time_of_day";
+    let expected = vec![
+        "+---------------------+------------+-------------+",
+        "| nanos               | today      | time_of_day |",
+        "+---------------------+------------+-------------+",
+        "| 1614641400000000000 | 2021-03-01 | 23:30:00    |",
+        "+---------------------+------------+-------------+",
+    ];
+    // every query starts at the fixed time
+    for _ in 0..2 {
+        let actual = execute_to_batches(&ctx, sql).await;
+        assert_batches_eq!(expected, &actual);
+    }
+
+    // queries start at the current time again once reset
+    ctx.set_query_execution_start_time(None);
+    let actual = execute(&ctx, "SELECT CAST(now() AS BIGINT)").await;
+    let now = actual[0][0].parse::<i64>().unwrap();
+    assert!(now > start_time.timestamp_nanos(), "{}", now);
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn timestamp_minmax() -> Result<()> {
     let ctx = SessionContext::new();
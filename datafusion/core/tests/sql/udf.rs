@@ -166,5 +166,20 @@ async fn simple_udaf() -> Result<()> {
     ];
     assert_batches_eq!(expected, &result);
 
+    let result = plan_and_collect(
+        &ctx,
+        "SELECT MY_AVG(a) FILTER (WHERE a > 2) AS avg_gt_2 FROM t",
+    )
+    .await?;
+
+    let expected = vec![
+        "+----------+",
+        "| avg_gt_2 |",
+        "+----------+",
+        "| 4        |",
+        "+----------+",
+    ];
+    assert_batches_eq!(expected, &result);
+
     Ok(())
 }
@@ -123,3 +123,68 @@ async fn union_schemas() -> Result<()> {
     assert_batches_eq!(expected, &result);
     Ok(())
 }
+
+#[tokio::test]
+async fn union_all_coerced_columns() -> Result<()> {
+    let ctx = SessionContext::new();
+    let left = RecordBatch::try_from_iter(vec![
+        ("a", Arc::new(Int32Array::from_slice(&[1, 2])) as ArrayRef),
+        (
+            "b",
+            Arc::new(StringArray::from_slice(&["x", "y"])) as ArrayRef,
+        ),
+    ])?;
+    let dictionary: DictionaryArray<Int32Type> = vec!["z", "z"].into_iter().collect();
+    let right = RecordBatch::try_from_iter(vec![
+        (
+            "c",
+            Arc::new(Int64Array::from(vec![Some(3), None])) as ArrayRef,
+        ),
+        ("d", Arc::new(dictionary) as ArrayRef),
+    ])?;
+    ctx.register_batch("t1", left)?;
+    ctx.register_batch("t2", right)?;
+
+    // the columns are matched by position, cast to their common type and
+    // named after the left ones
+    let expected = vec![
+        "+---+---+",
+        "| a | b |",
+        "+---+---+",
+        "|   | z |",
+        "| 1 | x |",
+        "| 2 | y |",
+        "| 3 | z |",
+        "+---+---+",
+    ];
+    let sql = "SELECT a, b FROM t1 UNION ALL SELECT c, d FROM t2";
+    let actual = execute_to_batches(&ctx, sql).await;
+    assert_batches_sorted_eq!(expected, &actual);
+
+    let actual = ctx.table("t1")?.union(ctx.table("t2")?)?.collect().await?;
+    assert_batches_sorted_eq!(expected, &actual);
+
+    let sql = "SELECT a FROM t1 UNION SELECT c FROM t2 UNION SELECT 1";
+    let actual = execute_to_batches(&ctx, sql).await;
+    #[rustfmt::skip]
+    let expected = vec![
+        "+---+",
+        "| a |",
+        "+---+",
+        "|   |",
+        "| 1 |",
+        "| 2 |",
+        "| 3 |",
+        "+---+",
+    ];
+    assert_batches_sorted_eq!(expected, &actual);
+
+    let sql = "SELECT b FROM t1 UNION ALL SELECT true";
+    let err = plan_and_collect(&ctx, sql).await.unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Error during planning: UNION Column Boolean(true) (type: Boolean) is \
+        not compatible with column b (type: Utf8)"
+    );
+    Ok(())
+}
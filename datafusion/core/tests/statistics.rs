@@ -153,19 +153,15 @@ impl ExecutionPlan for StatisticsValidation {
 
     fn fmt_as(
         &self,
-        t: DisplayFormatType,
+        _t: DisplayFormatType,
         f: &mut std::fmt::Formatter,
     ) -> std::fmt::Result {
-        match t {
-            DisplayFormatType::Default => {
-                write!(
-                    f,
-                    "StatisticsValidation: col_count={}, row_count={:?}",
-                    self.schema.fields().len(),
-                    self.stats.num_rows,
-                )
-            }
-        }
+        write!(
+            f,
+            "StatisticsValidation: col_count={}, row_count={:?}",
+            self.schema.fields().len(),
+            self.stats.num_rows,
+        )
     }
 }
 
@@ -69,15 +69,19 @@ use arrow::{
 };
 use datafusion::{
     common::cast::as_int64_array,
-    common::DFSchemaRef,
+    common::{DFSchema, DFSchemaRef},
     error::{DataFusionError, Result},
     execution::{
         context::{QueryPlanner, SessionState, TaskContext},
         runtime_env::RuntimeEnv,
     },
-    logical_expr::{Expr, Extension, Limit, LogicalPlan, Sort, UserDefinedLogicalNode},
+    logical_expr::{
+        col, EmptyRelation, Expr, Extension, Limit, LogicalPlan, Sort,
+        UserDefinedLogicalNode,
+    },
     optimizer::{optimize_children, OptimizerConfig, OptimizerRule},
     physical_plan::{
+        displayable,
         expressions::PhysicalSortExpr,
         planner::{DefaultPhysicalPlanner, ExtensionPlanner},
         DisplayFormatType, Distribution, ExecutionPlan, Partitioning, PhysicalPlanner,
@@ -87,6 +91,8 @@ use datafusion::{
 };
 
 use fmt::Debug;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
 use std::task::{Context, Poll};
 use std::{any::Any, collections::BTreeMap, fmt, sync::Arc};
 
@@ -245,6 +251,71 @@ async fn topk_plan() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+// Run a query filtering the output of a TopK, showing that the optimizer
+// rules run after the TopK rule pass the node through, and that the filter
+// is not pushed below it
+async fn topk_filtered_query() -> Result<()> {
+    let mut ctx = setup_table(make_topk_context()).await?;
+    let query = "SELECT customer_id, revenue FROM \
+        (SELECT customer_id, revenue FROM sales ORDER BY revenue DESC LIMIT 3) AS top \
+        WHERE revenue < 250 ORDER BY revenue DESC";
+
+    let plan = ctx.sql(query).await?.to_logical_plan()?;
+    let formatted = format!("{:?}", plan);
+    let filter = formatted.find("Filter:").unwrap();
+    let topk = formatted.find("TopK: k=3").unwrap();
+    assert!(filter < topk, "Filter pushed below TopK:\n{}", formatted);
+    assert!(
+        formatted.contains("TableScan: sales projection=[customer_id, revenue]"),
+        "Projection not pushed through TopK:\n{}",
+        formatted
+    );
+
+    // the extension planner turned the node into a TopKExec
+    let physical_plan = ctx.create_physical_plan(&plan).await?;
+    let formatted = displayable(physical_plan.as_ref()).indent().to_string();
+    assert!(formatted.contains("TopKExec: k=3"), "{}", formatted);
+
+    let expected = vec![
+        "+-------------+---------+",
+        "| customer_id | revenue |",
+        "+-------------+---------+",
+        "| jorge       | 200     |",
+        "| andy        | 150     |",
+        "+-------------+---------+",
+    ];
+    let s = exec_sql(&mut ctx, query).await?;
+    assert_eq!(expected, s.lines().collect::<Vec<_>>(), "{}", s);
+
+    Ok(())
+}
+
+#[test]
+fn topk_node_equality() -> Result<()> {
+    let input = LogicalPlan::EmptyRelation(EmptyRelation {
+        produce_one_row: false,
+        schema: Arc::new(DFSchema::empty()),
+    });
+    let node = |k: usize, column: &str| TopKPlanNode {
+        k,
+        input: input.clone(),
+        expr: col(column).sort(false, true),
+    };
+    let hash = |node: &dyn UserDefinedLogicalNode| {
+        let mut hasher = DefaultHasher::new();
+        node.dyn_hash(&mut hasher);
+        hasher.finish()
+    };
+
+    assert!(node(3, "revenue").dyn_eq(&node(3, "revenue")));
+    assert_eq!(hash(&node(3, "revenue")), hash(&node(3, "revenue")));
+    assert!(!node(3, "revenue").dyn_eq(&node(4, "revenue")));
+    assert!(!node(3, "revenue").dyn_eq(&node(3, "customer_id")));
+
+    Ok(())
+}
+
 fn make_topk_context() -> SessionContext {
     let config = SessionConfig::new().with_target_partitions(48);
     let runtime = Arc::new(RuntimeEnv::default());
@@ -377,6 +448,19 @@ impl UserDefinedLogicalNode for TopKPlanNode {
             expr: exprs[0].clone(),
         })
     }
+
+    fn dyn_hash(&self, state: &mut dyn Hasher) {
+        let mut state = state;
+        self.k.hash(&mut state);
+        self.expr.hash(&mut state);
+    }
+
+    fn dyn_eq(&self, other: &dyn UserDefinedLogicalNode) -> bool {
+        match other.as_any().downcast_ref::<Self>() {
+            Some(other) => self.k == other.k && self.expr == other.expr,
+            None => false,
+        }
+    }
 }
 
 /// Physical planner for TopK nodes
@@ -483,14 +567,10 @@ impl ExecutionPlan for TopKExec {
 
     fn fmt_as(
         &self,
-        t: DisplayFormatType,
+        _t: DisplayFormatType,
         f: &mut std::fmt::Formatter,
     ) -> std::fmt::Result {
-        match t {
-            DisplayFormatType::Default => {
-                write!(f, "TopKExec: k={}", self.k)
-            }
-        }
+        write!(f, "TopKExec: k={}", self.k)
     }
 
     fn statistics(&self) -> Statistics {
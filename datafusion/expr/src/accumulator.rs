@@ -54,6 +54,13 @@ pub trait Accumulator: Send + Sync + Debug {
 
     /// returns its value based on its current state.
     fn evaluate(&self) -> Result<ScalarValue>;
+
+    /// Returns the memory held by the accumulator in bytes, including `Self`.
+    /// Accumulators buffering values, such as MEDIAN, include them so that the
+    /// memory of the aggregation is accounted for.
+    fn size(&self) -> usize {
+        std::mem::size_of_val(self)
+    }
 }
 
 /// Representation of internal accumulator state. Accumulators can potentially have a mix of
@@ -63,6 +63,24 @@ pub enum AggregateFunction {
     ApproxMedian,
     /// Grouping
     Grouping,
+    /// The first value of a group, optionally ordered by a key
+    FirstValue,
+    /// The last value of a group, optionally ordered by a key
+    LastValue,
+    /// string_agg
+    StringAgg,
+    /// mode
+    Mode,
+    /// bit_and
+    BitAnd,
+    /// bit_or
+    BitOr,
+    /// bit_xor
+    BitXor,
+    /// bool_and
+    BoolAnd,
+    /// bool_or
+    BoolOr,
 }
 
 impl fmt::Display for AggregateFunction {
@@ -101,6 +119,17 @@ impl FromStr for AggregateFunction {
             }
             "approx_median" => AggregateFunction::ApproxMedian,
             "grouping" => AggregateFunction::Grouping,
+            "first_value" => AggregateFunction::FirstValue,
+            "last_value" => AggregateFunction::LastValue,
+            "string_agg" => AggregateFunction::StringAgg,
+            "mode" => AggregateFunction::Mode,
+            "bit_and" => AggregateFunction::BitAnd,
+            "bit_or" => AggregateFunction::BitOr,
+            "bit_xor" => AggregateFunction::BitXor,
+            "bool_and" => AggregateFunction::BoolAnd,
+            "every" => AggregateFunction::BoolAnd,
+            "bool_or" => AggregateFunction::BoolOr,
+            "any" => AggregateFunction::BoolOr,
             _ => {
                 return Err(DataFusionError::Plan(format!(
                     "There is no built-in function named {}",
@@ -155,10 +184,18 @@ pub fn return_type(
         AggregateFunction::ApproxPercentileContWithWeight => {
             Ok(coerced_data_types[0].clone())
         }
-        AggregateFunction::ApproxMedian | AggregateFunction::Median => {
+        AggregateFunction::ApproxMedian
+        | AggregateFunction::Median
+        | AggregateFunction::Mode => Ok(coerced_data_types[0].clone()),
+        AggregateFunction::Grouping => Ok(DataType::Int32),
+        AggregateFunction::FirstValue | AggregateFunction::LastValue => {
             Ok(coerced_data_types[0].clone())
         }
-        AggregateFunction::Grouping => Ok(DataType::Int32),
+        AggregateFunction::StringAgg => Ok(coerced_data_types[0].clone()),
+        AggregateFunction::BitAnd
+        | AggregateFunction::BitOr
+        | AggregateFunction::BitXor => Ok(coerced_data_types[0].clone()),
+        AggregateFunction::BoolAnd | AggregateFunction::BoolOr => Ok(DataType::Boolean),
     }
 }
 
@@ -168,8 +205,16 @@ pub fn signature(fun: &AggregateFunction) -> Signature {
     match fun {
         AggregateFunction::Count
         | AggregateFunction::ApproxDistinct
-        | AggregateFunction::Grouping
-        | AggregateFunction::ArrayAgg => Signature::any(1, Volatility::Immutable),
+        | AggregateFunction::Grouping => Signature::any(1, Volatility::Immutable),
+        // the value, optionally followed by the key the values are ordered by,
+        // whether this order is descending and whether null keys are first
+        AggregateFunction::ArrayAgg
+        | AggregateFunction::FirstValue
+        | AggregateFunction::LastValue => Signature::one_of(
+            vec![TypeSignature::Any(1), TypeSignature::Any(4)],
+            Volatility::Immutable,
+        ),
+        AggregateFunction::StringAgg => Signature::any(2, Volatility::Immutable),
         AggregateFunction::Min | AggregateFunction::Max => {
             let valid = STRINGS
                 .iter()
@@ -187,10 +232,26 @@ pub fn signature(fun: &AggregateFunction) -> Signature {
         | AggregateFunction::VariancePop
         | AggregateFunction::Stddev
         | AggregateFunction::StddevPop
-        | AggregateFunction::Median
         | AggregateFunction::ApproxMedian => {
             Signature::uniform(1, NUMERICS.to_vec(), Volatility::Immutable)
         }
+        AggregateFunction::Median | AggregateFunction::Mode => {
+            let valid = NUMERICS
+                .iter()
+                .chain(DATES.iter())
+                .chain(TIMESTAMPS.iter())
+                .cloned()
+                .collect::<Vec<_>>();
+            Signature::uniform(1, valid, Volatility::Immutable)
+        }
+        AggregateFunction::BitAnd
+        | AggregateFunction::BitOr
+        | AggregateFunction::BitXor => {
+            Signature::uniform(1, INTEGERS.to_vec(), Volatility::Immutable)
+        }
+        AggregateFunction::BoolAnd | AggregateFunction::BoolOr => {
+            Signature::exact(vec![DataType::Boolean], Volatility::Immutable)
+        }
         AggregateFunction::Covariance | AggregateFunction::CovariancePop => {
             Signature::uniform(2, NUMERICS.to_vec(), Volatility::Immutable)
         }
@@ -36,28 +36,46 @@ pub enum BuiltinScalarFunction {
     Atan,
     /// atan2
     Atan2,
+    /// cbrt
+    Cbrt,
     /// ceil
     Ceil,
     /// coalesce
     Coalesce,
+    /// greatest
+    Greatest,
+    /// least
+    Least,
     /// cos
     Cos,
+    /// degrees
+    Degrees,
     /// Digest
     Digest,
     /// exp
     Exp,
+    /// factorial
+    Factorial,
     /// floor
     Floor,
+    /// gcd, Greatest common divisor
+    Gcd,
+    /// lcm, Least common multiple
+    Lcm,
     /// ln, Natural logarithm
     Ln,
-    /// log, same as log10
+    /// log, same as log10 with one argument, the logarithm to the given base with two
     Log,
     /// log10
     Log10,
     /// log2
     Log2,
+    /// pi
+    Pi,
     /// power
     Power,
+    /// radians
+    Radians,
     /// round
     Round,
     /// signum
@@ -144,6 +162,10 @@ pub enum BuiltinScalarFunction {
     Substr,
     /// to_hex
     ToHex,
+    /// to_char
+    ToChar,
+    /// to_date
+    ToDate,
     /// to_timestamp
     ToTimestamp,
     /// to_timestamp_millis
@@ -170,10 +192,20 @@ pub enum BuiltinScalarFunction {
     Uuid,
     /// regexp_match
     RegexpMatch,
+    /// regexp_like
+    RegexpLike,
     /// struct
     Struct,
     /// arrow_typeof
     ArrowTypeof,
+    /// xxhash64
+    XxHash64,
+    /// version
+    Version,
+    /// current_catalog
+    CurrentCatalog,
+    /// current_schema
+    CurrentSchema,
 }
 
 impl BuiltinScalarFunction {
@@ -187,6 +219,10 @@ impl BuiltinScalarFunction {
                 | BuiltinScalarFunction::CurrentDate
                 | BuiltinScalarFunction::CurrentTime
                 | BuiltinScalarFunction::Uuid
+                | BuiltinScalarFunction::Pi
+                | BuiltinScalarFunction::Version
+                | BuiltinScalarFunction::CurrentCatalog
+                | BuiltinScalarFunction::CurrentSchema
         )
     }
     /// Returns the [Volatility] of the builtin function.
@@ -198,16 +234,25 @@ impl BuiltinScalarFunction {
             BuiltinScalarFunction::Asin => Volatility::Immutable,
             BuiltinScalarFunction::Atan => Volatility::Immutable,
             BuiltinScalarFunction::Atan2 => Volatility::Immutable,
+            BuiltinScalarFunction::Cbrt => Volatility::Immutable,
             BuiltinScalarFunction::Ceil => Volatility::Immutable,
             BuiltinScalarFunction::Coalesce => Volatility::Immutable,
+            BuiltinScalarFunction::Greatest => Volatility::Immutable,
+            BuiltinScalarFunction::Least => Volatility::Immutable,
             BuiltinScalarFunction::Cos => Volatility::Immutable,
+            BuiltinScalarFunction::Degrees => Volatility::Immutable,
             BuiltinScalarFunction::Exp => Volatility::Immutable,
+            BuiltinScalarFunction::Factorial => Volatility::Immutable,
             BuiltinScalarFunction::Floor => Volatility::Immutable,
+            BuiltinScalarFunction::Gcd => Volatility::Immutable,
+            BuiltinScalarFunction::Lcm => Volatility::Immutable,
             BuiltinScalarFunction::Ln => Volatility::Immutable,
             BuiltinScalarFunction::Log => Volatility::Immutable,
             BuiltinScalarFunction::Log10 => Volatility::Immutable,
             BuiltinScalarFunction::Log2 => Volatility::Immutable,
+            BuiltinScalarFunction::Pi => Volatility::Immutable,
             BuiltinScalarFunction::Power => Volatility::Immutable,
+            BuiltinScalarFunction::Radians => Volatility::Immutable,
             BuiltinScalarFunction::Round => Volatility::Immutable,
             BuiltinScalarFunction::Signum => Volatility::Immutable,
             BuiltinScalarFunction::Sin => Volatility::Immutable,
@@ -250,6 +295,8 @@ impl BuiltinScalarFunction {
             BuiltinScalarFunction::Strpos => Volatility::Immutable,
             BuiltinScalarFunction::Substr => Volatility::Immutable,
             BuiltinScalarFunction::ToHex => Volatility::Immutable,
+            BuiltinScalarFunction::ToChar => Volatility::Immutable,
+            BuiltinScalarFunction::ToDate => Volatility::Immutable,
             BuiltinScalarFunction::ToTimestamp => Volatility::Immutable,
             BuiltinScalarFunction::ToTimestampMillis => Volatility::Immutable,
             BuiltinScalarFunction::ToTimestampMicros => Volatility::Immutable,
@@ -258,14 +305,19 @@ impl BuiltinScalarFunction {
             BuiltinScalarFunction::Trim => Volatility::Immutable,
             BuiltinScalarFunction::Upper => Volatility::Immutable,
             BuiltinScalarFunction::RegexpMatch => Volatility::Immutable,
+            BuiltinScalarFunction::RegexpLike => Volatility::Immutable,
             BuiltinScalarFunction::Struct => Volatility::Immutable,
             BuiltinScalarFunction::FromUnixtime => Volatility::Immutable,
             BuiltinScalarFunction::ArrowTypeof => Volatility::Immutable,
+            BuiltinScalarFunction::XxHash64 => Volatility::Immutable,
+            BuiltinScalarFunction::Version => Volatility::Immutable,
 
             // Stable builtin functions
             BuiltinScalarFunction::Now => Volatility::Stable,
             BuiltinScalarFunction::CurrentDate => Volatility::Stable,
             BuiltinScalarFunction::CurrentTime => Volatility::Stable,
+            BuiltinScalarFunction::CurrentCatalog => Volatility::Stable,
+            BuiltinScalarFunction::CurrentSchema => Volatility::Stable,
 
             // Volatile builtin functions
             BuiltinScalarFunction::Random => Volatility::Volatile,
@@ -291,15 +343,22 @@ impl FromStr for BuiltinScalarFunction {
             "asin" => BuiltinScalarFunction::Asin,
             "atan" => BuiltinScalarFunction::Atan,
             "atan2" => BuiltinScalarFunction::Atan2,
+            "cbrt" => BuiltinScalarFunction::Cbrt,
             "ceil" => BuiltinScalarFunction::Ceil,
             "cos" => BuiltinScalarFunction::Cos,
+            "degrees" => BuiltinScalarFunction::Degrees,
             "exp" => BuiltinScalarFunction::Exp,
+            "factorial" => BuiltinScalarFunction::Factorial,
             "floor" => BuiltinScalarFunction::Floor,
+            "gcd" => BuiltinScalarFunction::Gcd,
+            "lcm" => BuiltinScalarFunction::Lcm,
             "ln" => BuiltinScalarFunction::Ln,
             "log" => BuiltinScalarFunction::Log,
             "log10" => BuiltinScalarFunction::Log10,
             "log2" => BuiltinScalarFunction::Log2,
+            "pi" => BuiltinScalarFunction::Pi,
             "power" | "pow" => BuiltinScalarFunction::Power,
+            "radians" => BuiltinScalarFunction::Radians,
             "round" => BuiltinScalarFunction::Round,
             "signum" => BuiltinScalarFunction::Signum,
             "sin" => BuiltinScalarFunction::Sin,
@@ -308,7 +367,9 @@ impl FromStr for BuiltinScalarFunction {
             "trunc" => BuiltinScalarFunction::Trunc,
 
             // conditional functions
-            "coalesce" => BuiltinScalarFunction::Coalesce,
+            "coalesce" | "ifnull" | "nvl" => BuiltinScalarFunction::Coalesce,
+            "greatest" => BuiltinScalarFunction::Greatest,
+            "least" => BuiltinScalarFunction::Least,
 
             // array functions
             "make_array" => BuiltinScalarFunction::MakeArray,
@@ -354,19 +415,26 @@ impl FromStr for BuiltinScalarFunction {
             "strpos" => BuiltinScalarFunction::Strpos,
             "substr" => BuiltinScalarFunction::Substr,
             "to_hex" => BuiltinScalarFunction::ToHex,
+            "to_char" | "date_format" => BuiltinScalarFunction::ToChar,
+            "to_date" => BuiltinScalarFunction::ToDate,
             "to_timestamp" => BuiltinScalarFunction::ToTimestamp,
             "to_timestamp_millis" => BuiltinScalarFunction::ToTimestampMillis,
             "to_timestamp_micros" => BuiltinScalarFunction::ToTimestampMicros,
             "to_timestamp_seconds" => BuiltinScalarFunction::ToTimestampSeconds,
-            "now" => BuiltinScalarFunction::Now,
+            "now" | "current_timestamp" => BuiltinScalarFunction::Now,
             "translate" => BuiltinScalarFunction::Translate,
             "trim" => BuiltinScalarFunction::Trim,
             "upper" => BuiltinScalarFunction::Upper,
             "uuid" => BuiltinScalarFunction::Uuid,
             "regexp_match" => BuiltinScalarFunction::RegexpMatch,
+            "regexp_like" => BuiltinScalarFunction::RegexpLike,
             "struct" => BuiltinScalarFunction::Struct,
             "from_unixtime" => BuiltinScalarFunction::FromUnixtime,
             "arrow_typeof" => BuiltinScalarFunction::ArrowTypeof,
+            "xxhash64" | "hash" => BuiltinScalarFunction::XxHash64,
+            "version" => BuiltinScalarFunction::Version,
+            "current_catalog" => BuiltinScalarFunction::CurrentCatalog,
+            "current_schema" => BuiltinScalarFunction::CurrentSchema,
             _ => {
                 return Err(DataFusionError::Plan(format!(
                     "There is no built-in function named {}",
@@ -19,7 +19,7 @@
 
 use crate::expr::{BinaryExpr, Cast, GroupingSet};
 use crate::{
-    aggregate_function, built_in_function, conditional_expressions::CaseBuilder,
+    aggregate_function, built_in_function, conditional_expressions::CaseBuilder, lit,
     logical_plan::Subquery, AccumulatorFunctionImplementation, AggregateUDF,
     BuiltinScalarFunction, Expr, LogicalPlan, Operator, ReturnTypeFunction,
     ScalarFunctionImplementation, ScalarUDF, Signature, StateTypeFunction, Volatility,
@@ -116,6 +116,101 @@ pub fn count_distinct(expr: Expr) -> Expr {
     }
 }
 
+/// Create an expression to represent the array_agg() aggregate function
+pub fn array_agg(expr: Expr) -> Expr {
+    Expr::AggregateFunction {
+        fun: aggregate_function::AggregateFunction::ArrayAgg,
+        distinct: false,
+        args: vec![expr],
+        filter: None,
+    }
+}
+
+/// Create an expression to represent the array_agg() aggregate function, the
+/// values of the list being ordered by `order_by`, see [`first_value_ordered`]
+pub fn array_agg_ordered(expr: Expr, order_by: Expr) -> Expr {
+    Expr::AggregateFunction {
+        fun: aggregate_function::AggregateFunction::ArrayAgg,
+        distinct: false,
+        args: ordered_aggregate_args(expr, order_by),
+        filter: None,
+    }
+}
+
+/// Create an expression to represent the first_value() aggregate function,
+/// the first value of `expr` read in each group
+pub fn first_value(expr: Expr) -> Expr {
+    Expr::AggregateFunction {
+        fun: aggregate_function::AggregateFunction::FirstValue,
+        distinct: false,
+        args: vec![expr],
+        filter: None,
+    }
+}
+
+/// Create an expression to represent the first_value() aggregate function,
+/// the value of `expr` of the first row of each group ordered by `order_by`,
+/// either a key such as `col("ts")` or a sort expression such as
+/// `col("ts").sort(false, true)`. Null keys are ordered last unless the sort
+/// expression puts them first.
+pub fn first_value_ordered(expr: Expr, order_by: Expr) -> Expr {
+    Expr::AggregateFunction {
+        fun: aggregate_function::AggregateFunction::FirstValue,
+        distinct: false,
+        args: ordered_aggregate_args(expr, order_by),
+        filter: None,
+    }
+}
+
+/// Create an expression to represent the last_value() aggregate function,
+/// the last value of `expr` read in each group
+pub fn last_value(expr: Expr) -> Expr {
+    Expr::AggregateFunction {
+        fun: aggregate_function::AggregateFunction::LastValue,
+        distinct: false,
+        args: vec![expr],
+        filter: None,
+    }
+}
+
+/// Create an expression to represent the last_value() aggregate function,
+/// the value of `expr` of the last row of each group ordered by `order_by`,
+/// see [`first_value_ordered`]
+pub fn last_value_ordered(expr: Expr, order_by: Expr) -> Expr {
+    Expr::AggregateFunction {
+        fun: aggregate_function::AggregateFunction::LastValue,
+        distinct: false,
+        args: ordered_aggregate_args(expr, order_by),
+        filter: None,
+    }
+}
+
+/// The arguments of an aggregate of `expr` ordered by `order_by`, either a
+/// key or a sort expression: the value, the key, whether the order is
+/// descending and whether null keys are first
+pub fn ordered_aggregate_args(expr: Expr, order_by: Expr) -> Vec<Expr> {
+    match order_by {
+        Expr::Sort {
+            expr: key,
+            asc,
+            nulls_first,
+        } => vec![expr, *key, lit(!asc), lit(nulls_first)],
+        key => vec![expr, key, lit(false), lit(false)],
+    }
+}
+
+/// Create an expression to represent the string_agg() aggregate function,
+/// concatenating the non null values of `expr` separated by `delimiter`,
+/// which must be a string literal
+pub fn string_agg(expr: Expr, delimiter: Expr) -> Expr {
+    Expr::AggregateFunction {
+        fun: aggregate_function::AggregateFunction::StringAgg,
+        distinct: false,
+        args: vec![expr, delimiter],
+        filter: None,
+    }
+}
+
 /// Create an in_list expression
 pub fn in_list(expr: Expr, list: Vec<Expr>, negated: bool) -> Expr {
     Expr::InList {
@@ -153,6 +248,14 @@ pub fn random() -> Expr {
     }
 }
 
+/// Returns an approximate value of π
+pub fn pi() -> Expr {
+    Expr::ScalarFunction {
+        fun: built_in_function::BuiltinScalarFunction::Pi,
+        args: vec![],
+    }
+}
+
 /// Returns the approximate number of distinct input values.
 /// This function provides an approximation of count(DISTINCT x).
 /// Zero is returned if all input values are null.
@@ -169,6 +272,76 @@ pub fn approx_distinct(expr: Expr) -> Expr {
     }
 }
 
+/// Calculate the exact median of `expr`.
+pub fn median(expr: Expr) -> Expr {
+    Expr::AggregateFunction {
+        fun: aggregate_function::AggregateFunction::Median,
+        distinct: false,
+        args: vec![expr],
+        filter: None,
+    }
+}
+
+/// Calculate the most frequent value of `expr`, the smallest of them on ties.
+pub fn mode(expr: Expr) -> Expr {
+    Expr::AggregateFunction {
+        fun: aggregate_function::AggregateFunction::Mode,
+        distinct: false,
+        args: vec![expr],
+        filter: None,
+    }
+}
+
+/// Calculate the bitwise AND of the non null values of `expr`.
+pub fn bit_and(expr: Expr) -> Expr {
+    Expr::AggregateFunction {
+        fun: aggregate_function::AggregateFunction::BitAnd,
+        distinct: false,
+        args: vec![expr],
+        filter: None,
+    }
+}
+
+/// Calculate the bitwise OR of the non null values of `expr`.
+pub fn bit_or(expr: Expr) -> Expr {
+    Expr::AggregateFunction {
+        fun: aggregate_function::AggregateFunction::BitOr,
+        distinct: false,
+        args: vec![expr],
+        filter: None,
+    }
+}
+
+/// Calculate the bitwise XOR of the non null values of `expr`.
+pub fn bit_xor(expr: Expr) -> Expr {
+    Expr::AggregateFunction {
+        fun: aggregate_function::AggregateFunction::BitXor,
+        distinct: false,
+        args: vec![expr],
+        filter: None,
+    }
+}
+
+/// Calculate whether all non null values of the boolean `expr` are true.
+pub fn bool_and(expr: Expr) -> Expr {
+    Expr::AggregateFunction {
+        fun: aggregate_function::AggregateFunction::BoolAnd,
+        distinct: false,
+        args: vec![expr],
+        filter: None,
+    }
+}
+
+/// Calculate whether any non null value of the boolean `expr` is true.
+pub fn bool_or(expr: Expr) -> Expr {
+    Expr::AggregateFunction {
+        fun: aggregate_function::AggregateFunction::BoolOr,
+        distinct: false,
+        args: vec![expr],
+        filter: None,
+    }
+}
+
 /// Calculate an approximation of the median for `expr`.
 pub fn approx_median(expr: Expr) -> Expr {
     Expr::AggregateFunction {
@@ -370,9 +543,16 @@ unary_scalar_expr!(Exp, exp, "base 2 logarithm");
 unary_scalar_expr!(Log2, log2, "base 10 logarithm");
 unary_scalar_expr!(Log10, log10, "base 10 logarithm");
 unary_scalar_expr!(Ln, ln, "natural logarithm");
+unary_scalar_expr!(Cbrt, cbrt, "cube root of a number");
+unary_scalar_expr!(Degrees, degrees, "radians converted to degrees");
+unary_scalar_expr!(Radians, radians, "degrees converted to radians");
+unary_scalar_expr!(Factorial, factorial, "factorial of a non-negative integer");
 scalar_expr!(NullIf, nullif, arg_1, arg_2);
 scalar_expr!(Power, power, base, exponent);
 scalar_expr!(Atan2, atan2, y, x);
+scalar_expr!(Log, log, base, x);
+scalar_expr!(Gcd, gcd, x, y);
+scalar_expr!(Lcm, lcm, x, y);
 
 // string functions
 scalar_expr!(Ascii, ascii, string);
@@ -410,15 +590,21 @@ nary_scalar_expr!(Lpad, lpad);
 nary_scalar_expr!(Rpad, rpad);
 nary_scalar_expr!(RegexpReplace, regexp_replace);
 nary_scalar_expr!(RegexpMatch, regexp_match);
+nary_scalar_expr!(RegexpLike, regexp_like);
+nary_scalar_expr!(Greatest, greatest);
+nary_scalar_expr!(Least, least);
 nary_scalar_expr!(Btrim, btrim);
 //there is a func concat_ws before, so use concat_ws_expr as name.c
 nary_scalar_expr!(ConcatWithSeparator, concat_ws_expr);
 nary_scalar_expr!(Concat, concat_expr);
+nary_scalar_expr!(XxHash64, xxhash64);
 
 // date functions
 scalar_expr!(DatePart, date_part, part, date);
 scalar_expr!(DateTrunc, date_trunc, part, date);
 scalar_expr!(DateBin, date_bin, stride, source, origin);
+scalar_expr!(ToChar, to_char, datetime, format);
+nary_scalar_expr!(ToDate, to_date);
 scalar_expr!(ToTimestampMillis, to_timestamp_millis, date);
 scalar_expr!(ToTimestampMicros, to_timestamp_micros, date);
 scalar_expr!(ToTimestampSeconds, to_timestamp_seconds, date);
@@ -476,6 +662,30 @@ pub fn current_time() -> Expr {
     }
 }
 
+/// Returns the version of DataFusion as a string value
+pub fn version() -> Expr {
+    Expr::ScalarFunction {
+        fun: BuiltinScalarFunction::Version,
+        args: vec![],
+    }
+}
+
+/// Returns the name of the default catalog of the session as a string value
+pub fn current_catalog() -> Expr {
+    Expr::ScalarFunction {
+        fun: BuiltinScalarFunction::CurrentCatalog,
+        args: vec![],
+    }
+}
+
+/// Returns the name of the default schema of the session as a string value
+pub fn current_schema() -> Expr {
+    Expr::ScalarFunction {
+        fun: BuiltinScalarFunction::CurrentSchema,
+        args: vec![],
+    }
+}
+
 /// Create a CASE WHEN statement with literal WHEN expressions for comparison to the base expression.
 pub fn case(expr: Expr) -> CaseBuilder {
     CaseBuilder::new(Some(Box::new(expr)), vec![], vec![], None)
@@ -628,7 +838,14 @@ mod test {
         test_unary_scalar_expr!(Log2, log2);
         test_unary_scalar_expr!(Log10, log10);
         test_unary_scalar_expr!(Ln, ln);
+        test_unary_scalar_expr!(Cbrt, cbrt);
+        test_unary_scalar_expr!(Degrees, degrees);
+        test_unary_scalar_expr!(Radians, radians);
+        test_unary_scalar_expr!(Factorial, factorial);
         test_scalar_expr!(Atan2, atan2, y, x);
+        test_scalar_expr!(Log, log, base, x);
+        test_scalar_expr!(Gcd, gcd, x, y);
+        test_scalar_expr!(Lcm, lcm, x, y);
 
         test_scalar_expr!(Ascii, ascii, input);
         test_scalar_expr!(BitLength, bit_length, string);
@@ -648,6 +865,10 @@ mod test {
         test_scalar_expr!(OctetLength, octet_length, string);
         test_nary_scalar_expr!(RegexpMatch, regexp_match, string, pattern);
         test_nary_scalar_expr!(RegexpMatch, regexp_match, string, pattern, flags);
+        test_nary_scalar_expr!(RegexpLike, regexp_like, string, pattern);
+        test_nary_scalar_expr!(RegexpLike, regexp_like, string, pattern, flags);
+        test_nary_scalar_expr!(Greatest, greatest, x, y, z);
+        test_nary_scalar_expr!(Least, least, x, y);
         test_nary_scalar_expr!(
             RegexpReplace,
             regexp_replace,
@@ -687,6 +908,9 @@ mod test {
         test_scalar_expr!(DatePart, date_part, part, date);
         test_scalar_expr!(DateTrunc, date_trunc, part, date);
         test_scalar_expr!(DateBin, date_bin, stride, source, origin);
+        test_scalar_expr!(ToChar, to_char, datetime, format);
+        test_nary_scalar_expr!(ToDate, to_date, string);
+        test_nary_scalar_expr!(ToDate, to_date, string, format);
         test_scalar_expr!(FromUnixtime, from_unixtime, unixtime);
 
         test_unary_scalar_expr!(ArrowTypeof, arrow_typeof);
@@ -703,6 +927,22 @@ mod test {
         }
     }
 
+    #[test]
+    fn session_function_definitions() {
+        for (expr, name) in [
+            (version(), BuiltinScalarFunction::Version),
+            (current_catalog(), BuiltinScalarFunction::CurrentCatalog),
+            (current_schema(), BuiltinScalarFunction::CurrentSchema),
+        ] {
+            if let Expr::ScalarFunction { fun, args } = expr {
+                assert_eq!(name, fun);
+                assert_eq!(0, args.len());
+            } else {
+                unreachable!();
+            }
+        }
+    }
+
     #[test]
     fn digest_function_definitions() {
         if let Expr::ScalarFunction { fun, args } = digest(col("tableA.a"), lit("md5")) {
@@ -19,7 +19,7 @@
 
 use crate::expr::{Between, BinaryExpr, Case, Cast, GetIndexedField, GroupingSet, Like};
 use crate::logical_plan::{Aggregate, Projection};
-use crate::utils::{from_plan, grouping_set_to_exprlist};
+use crate::utils::{from_plan, grouping_set_to_exprlist, resolve_using_join_columns};
 use crate::{Expr, ExprSchemable, LogicalPlan};
 use datafusion_common::Result;
 use datafusion_common::{Column, DFSchema};
@@ -249,7 +249,7 @@ impl ExprRewritable for Expr {
                 args: rewrite_vec(args, rewriter)?,
                 fun,
                 distinct,
-                filter,
+                filter: rewrite_option_box(filter, rewriter)?,
             },
             Expr::GroupingSet(grouping_set) => match grouping_set {
                 GroupingSet::Rollup(exprs) => {
@@ -270,7 +270,7 @@ impl ExprRewritable for Expr {
             Expr::AggregateUDF { args, fun, filter } => Expr::AggregateUDF {
                 args: rewrite_vec(args, rewriter)?,
                 fun,
-                filter,
+                filter: rewrite_option_box(filter, rewriter)?,
             },
             Expr::InList {
                 expr,
@@ -418,11 +418,29 @@ fn rewrite_sort_col_by_aggs(expr: Expr, plan: &LogicalPlan) -> Result<Expr> {
 }
 
 /// Recursively call [`Column::normalize_with_schemas`] on all Column expressions
-/// in the `expr` expression tree.
+/// in the `expr` expression tree, after replacing the columns of `Using` joins
+/// by their value, see [`resolve_using_join_columns`].
 pub fn normalize_col(expr: Expr, plan: &LogicalPlan) -> Result<Expr> {
+    let expr = resolve_using_join_columns(expr, plan)?;
     normalize_col_with_schemas(expr, &plan.all_schemas(), &plan.using_columns()?)
 }
 
+/// Like [`normalize_col`], but a bare column replaced by the value of a `Using`
+/// join keeps its name, as an expression of a projection must.
+pub fn normalize_projected_col(expr: Expr, plan: &LogicalPlan) -> Result<Expr> {
+    let name = match &expr {
+        Expr::Column(Column {
+            relation: None,
+            name,
+        }) => name.clone(),
+        _ => return normalize_col(expr, plan),
+    };
+    match normalize_col(expr, plan)? {
+        expr @ Expr::Column(_) => Ok(expr),
+        expr => Ok(expr.alias(&name)),
+    }
+}
+
 /// Recursively call [`Column::normalize_with_schemas`] on all Column expressions
 /// in the `expr` expression tree.
 pub fn normalize_col_with_schemas(
@@ -522,10 +540,17 @@ pub fn unnormalize_cols(exprs: impl IntoIterator<Item = Expr>) -> Vec<Expr> {
 
 /// Returns plan with expressions coerced to types compatible with
 /// schema types
+///
+/// The expressions of a projection are cast in place, while the output
+/// columns of any other plan are cast by a projection added on top of it.
 pub fn coerce_plan_expr_for_schema(
     plan: &LogicalPlan,
     schema: &DFSchema,
 ) -> Result<LogicalPlan> {
+    if !matches!(plan, LogicalPlan::Projection(_)) {
+        return coerce_plan_output_for_schema(plan, schema);
+    }
+
     let new_expr = plan
         .expressions()
         .into_iter()
@@ -554,6 +579,42 @@ pub fn coerce_plan_expr_for_schema(
     from_plan(plan, &new_expr, &new_inputs)
 }
 
+/// Projects the output columns of `plan` cast to the types of the fields of
+/// `schema` at the same position, keeping their names, unless they all have
+/// these types already
+fn coerce_plan_output_for_schema(
+    plan: &LogicalPlan,
+    schema: &DFSchema,
+) -> Result<LogicalPlan> {
+    let plan_schema = plan.schema();
+    let mut needs_cast = false;
+    let new_expr = plan_schema
+        .fields()
+        .iter()
+        .zip(schema.fields())
+        .map(|(field, target)| {
+            let expr = Expr::Column(field.qualified_column());
+            if field.data_type() == target.data_type() {
+                Ok(expr)
+            } else {
+                needs_cast = true;
+                Ok(expr
+                    .cast_to(target.data_type(), plan_schema)?
+                    .alias(field.name()))
+            }
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    if !needs_cast {
+        return Ok(plan.clone());
+    }
+    Ok(LogicalPlan::Projection(Projection::try_new(
+        new_expr,
+        Arc::new(plan.clone()),
+        None,
+    )?))
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
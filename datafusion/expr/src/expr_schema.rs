@@ -19,7 +19,10 @@ use super::{Between, Expr, Like};
 use crate::expr::{BinaryExpr, Cast, GetIndexedField};
 use crate::field_util::get_indexed_field;
 use crate::type_coercion::binary::binary_operator_data_type;
-use crate::{aggregate_function, function, window_function};
+use crate::{
+    aggregate_function, function, window_function, AggregateFunction,
+    BuiltinScalarFunction,
+};
 use arrow::compute::can_cast_types;
 use arrow::datatypes::DataType;
 use datafusion_common::{DFField, DFSchema, DataFusionError, ExprSchema, Result};
@@ -183,6 +186,23 @@ impl ExprSchemable for Expr {
                 }
             }
             Expr::Cast(Cast { expr, .. }) => expr.nullable(input_schema),
+            Expr::ScalarFunction {
+                fun: BuiltinScalarFunction::Coalesce,
+                args,
+            } => {
+                // COALESCE is only NULL if all of its arguments are
+                for arg in args {
+                    if !arg.nullable(input_schema)? {
+                        return Ok(false);
+                    }
+                }
+                Ok(true)
+            }
+            // COUNT is 0 rather than NULL for no rows
+            Expr::AggregateFunction {
+                fun: AggregateFunction::Count,
+                ..
+            } => Ok(false),
             Expr::ScalarVariable(_, _)
             | Expr::TryCast { .. }
             | Expr::ScalarFunction { .. }
@@ -275,9 +295,9 @@ impl ExprSchemable for Expr {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{col, lit};
+    use crate::{coalesce, col, count, lit, min, sum};
     use arrow::datatypes::DataType;
-    use datafusion_common::Column;
+    use datafusion_common::{Column, ScalarValue};
 
     #[test]
     fn expr_schema_nullability() {
@@ -288,6 +308,34 @@ mod tests {
             .unwrap());
     }
 
+    #[test]
+    fn aggregate_function_nullability() {
+        let nullable = MockExprSchema::new().with_nullable(true);
+        let not_nullable = MockExprSchema::new();
+
+        // the count of no rows is 0, while their sum or min is NULL
+        for schema in [&nullable, &not_nullable] {
+            assert!(!count(col("foo")).nullable(schema).unwrap());
+            assert!(sum(col("foo")).nullable(schema).unwrap());
+            assert!(min(col("foo")).nullable(schema).unwrap());
+        }
+    }
+
+    #[test]
+    fn literal_and_coalesce_nullability() {
+        let nullable = MockExprSchema::new().with_nullable(true);
+        assert!(!lit(1).nullable(&nullable).unwrap());
+        assert!(lit(ScalarValue::Int32(None)).nullable(&nullable).unwrap());
+
+        // COALESCE is NULL only if all of its arguments are
+        let expr = coalesce(vec![col("foo"), lit(0)]);
+        assert!(!expr.nullable(&nullable).unwrap());
+        let expr = coalesce(vec![col("foo"), lit(ScalarValue::Int32(None))]);
+        assert!(expr.nullable(&nullable).unwrap());
+        let expr = coalesce(vec![col("foo")]);
+        assert!(!expr.nullable(&MockExprSchema::new()).unwrap());
+    }
+
     #[test]
     fn expr_schema_data_type() {
         let expr = col("foo");
@@ -17,14 +17,15 @@
 
 //! Function module contains typing and signature for built-in and user defined functions.
 
-use crate::nullif::SUPPORTED_NULLIF_TYPES;
 use crate::type_coercion::functions::data_types;
 use crate::ColumnarValue;
 use crate::{
-    array_expressions, conditional_expressions, struct_expressions, Accumulator,
-    BuiltinScalarFunction, Signature, TypeSignature,
+    array_expressions, struct_expressions, Accumulator, BuiltinScalarFunction, Signature,
+    TypeSignature, TIMEZONE_WILDCARD,
+};
+use arrow::datatypes::{
+    DataType, Field, IntervalUnit, TimeUnit, DECIMAL128_MAX_PRECISION,
 };
-use arrow::datatypes::{DataType, Field, IntervalUnit, TimeUnit};
 use datafusion_common::{DataFusionError, Result};
 use std::sync::Arc;
 
@@ -107,8 +108,15 @@ pub fn return_type(
         )));
     }
 
-    // verify that this is a valid set of data types for this function
-    data_types(input_expr_types, &signature(fun))?;
+    // verify that this is a valid set of data types for this function, and
+    // compute the return type from the types the arguments are coerced to, as
+    // the physical expression is evaluated on those
+    let coerced_types = data_types(input_expr_types, &signature(fun))?;
+    let input_expr_types = if coerced_types.is_empty() {
+        input_expr_types
+    } else {
+        &coerced_types
+    };
 
     // the return type of the built in function.
     // Some built-in functions' return type depends on the incoming type.
@@ -126,8 +134,10 @@ pub fn return_type(
             utf8_to_int_type(&input_expr_types[0], "character_length")
         }
         BuiltinScalarFunction::Chr => Ok(DataType::Utf8),
-        BuiltinScalarFunction::Coalesce => {
-            // COALESCE has multiple args and they might get coerced, get a preview of this
+        BuiltinScalarFunction::Coalesce
+        | BuiltinScalarFunction::Greatest
+        | BuiltinScalarFunction::Least => {
+            // These have multiple args and they might get coerced, get a preview of this
             let coerced_types = data_types(input_expr_types, &signature(fun));
             coerced_types.map(|types| types[0].clone())
         }
@@ -203,6 +213,8 @@ pub fn return_type(
                 ));
             }
         }),
+        BuiltinScalarFunction::ToChar => Ok(DataType::Utf8),
+        BuiltinScalarFunction::ToDate => Ok(DataType::Date32),
         BuiltinScalarFunction::ToTimestamp => {
             Ok(DataType::Timestamp(TimeUnit::Nanosecond, None))
         }
@@ -229,6 +241,7 @@ pub fn return_type(
         }
         BuiltinScalarFunction::Trim => utf8_to_str_type(&input_expr_types[0], "trim"),
         BuiltinScalarFunction::Upper => utf8_to_str_type(&input_expr_types[0], "upper"),
+        BuiltinScalarFunction::RegexpLike => Ok(DataType::Boolean),
         BuiltinScalarFunction::RegexpMatch => Ok(match input_expr_types[0] {
             DataType::LargeUtf8 => {
                 DataType::List(Box::new(Field::new("item", DataType::LargeUtf8, true)))
@@ -250,7 +263,15 @@ pub fn return_type(
             _ => Ok(DataType::Float64),
         },
 
-        BuiltinScalarFunction::Struct => Ok(DataType::Struct(vec![])),
+        BuiltinScalarFunction::Struct => Ok(DataType::Struct(
+            input_expr_types
+                .iter()
+                .enumerate()
+                .map(|(i, data_type)| {
+                    Field::new(&format!("c{}", i), data_type.clone(), true)
+                })
+                .collect(),
+        )),
 
         BuiltinScalarFunction::Atan2 => match &input_expr_types[0] {
             DataType::Float32 => Ok(DataType::Float32),
@@ -258,19 +279,31 @@ pub fn return_type(
         },
 
         BuiltinScalarFunction::ArrowTypeof => Ok(DataType::Utf8),
+        BuiltinScalarFunction::XxHash64 => Ok(DataType::UInt64),
+        BuiltinScalarFunction::Version
+        | BuiltinScalarFunction::CurrentCatalog
+        | BuiltinScalarFunction::CurrentSchema => Ok(DataType::Utf8),
+
+        BuiltinScalarFunction::Pi => Ok(DataType::Float64),
+        BuiltinScalarFunction::Factorial
+        | BuiltinScalarFunction::Gcd
+        | BuiltinScalarFunction::Lcm => Ok(DataType::Int64),
 
         BuiltinScalarFunction::Abs
         | BuiltinScalarFunction::Acos
         | BuiltinScalarFunction::Asin
         | BuiltinScalarFunction::Atan
+        | BuiltinScalarFunction::Cbrt
         | BuiltinScalarFunction::Ceil
         | BuiltinScalarFunction::Cos
+        | BuiltinScalarFunction::Degrees
         | BuiltinScalarFunction::Exp
         | BuiltinScalarFunction::Floor
         | BuiltinScalarFunction::Log
         | BuiltinScalarFunction::Ln
         | BuiltinScalarFunction::Log10
         | BuiltinScalarFunction::Log2
+        | BuiltinScalarFunction::Radians
         | BuiltinScalarFunction::Round
         | BuiltinScalarFunction::Signum
         | BuiltinScalarFunction::Sin
@@ -300,10 +333,9 @@ pub fn signature(fun: &BuiltinScalarFunction) -> Signature {
         BuiltinScalarFunction::Concat | BuiltinScalarFunction::ConcatWithSeparator => {
             Signature::variadic(vec![DataType::Utf8], fun.volatility())
         }
-        BuiltinScalarFunction::Coalesce => Signature::variadic(
-            conditional_expressions::SUPPORTED_COALESCE_TYPES.to_vec(),
-            fun.volatility(),
-        ),
+        BuiltinScalarFunction::Coalesce
+        | BuiltinScalarFunction::Greatest
+        | BuiltinScalarFunction::Least => Signature::variadic_common(fun.volatility()),
         BuiltinScalarFunction::SHA224
         | BuiltinScalarFunction::SHA256
         | BuiltinScalarFunction::SHA384
@@ -379,15 +411,48 @@ pub fn signature(fun: &BuiltinScalarFunction) -> Signature {
             ],
             fun.volatility(),
         ),
-        BuiltinScalarFunction::ToTimestamp => Signature::uniform(
-            1,
+        BuiltinScalarFunction::ToChar => Signature::one_of(
+            [DataType::Date32, DataType::Date64]
+                .into_iter()
+                .chain(
+                    [
+                        TimeUnit::Nanosecond,
+                        TimeUnit::Microsecond,
+                        TimeUnit::Millisecond,
+                        TimeUnit::Second,
+                    ]
+                    .into_iter()
+                    .flat_map(|unit| {
+                        [None, Some(TIMEZONE_WILDCARD.to_string())]
+                            .into_iter()
+                            .map(move |tz| DataType::Timestamp(unit.clone(), tz))
+                    }),
+                )
+                .map(|data_type| TypeSignature::Exact(vec![data_type, DataType::Utf8]))
+                .collect(),
+            fun.volatility(),
+        ),
+        BuiltinScalarFunction::ToDate => Signature::one_of(
             vec![
-                DataType::Int64,
-                DataType::Timestamp(TimeUnit::Nanosecond, None),
-                DataType::Timestamp(TimeUnit::Microsecond, None),
-                DataType::Timestamp(TimeUnit::Millisecond, None),
-                DataType::Timestamp(TimeUnit::Second, None),
-                DataType::Utf8,
+                TypeSignature::Exact(vec![DataType::Utf8]),
+                TypeSignature::Exact(vec![DataType::Utf8, DataType::Utf8]),
+            ],
+            fun.volatility(),
+        ),
+        BuiltinScalarFunction::ToTimestamp => Signature::one_of(
+            vec![
+                TypeSignature::Uniform(
+                    1,
+                    vec![
+                        DataType::Int64,
+                        DataType::Timestamp(TimeUnit::Nanosecond, None),
+                        DataType::Timestamp(TimeUnit::Microsecond, None),
+                        DataType::Timestamp(TimeUnit::Millisecond, None),
+                        DataType::Timestamp(TimeUnit::Second, None),
+                        DataType::Utf8,
+                    ],
+                ),
+                TypeSignature::Exact(vec![DataType::Utf8, DataType::Utf8]),
             ],
             fun.volatility(),
         ),
@@ -560,26 +625,26 @@ pub fn signature(fun: &BuiltinScalarFunction) -> Signature {
             fun.volatility(),
         ),
 
-        BuiltinScalarFunction::NullIf => {
-            Signature::uniform(2, SUPPORTED_NULLIF_TYPES.to_vec(), fun.volatility())
+        BuiltinScalarFunction::NullIf => Signature::uniform_common(2, fun.volatility()),
+        BuiltinScalarFunction::RegexpMatch | BuiltinScalarFunction::RegexpLike => {
+            Signature::one_of(
+                vec![
+                    TypeSignature::Exact(vec![DataType::Utf8, DataType::Utf8]),
+                    TypeSignature::Exact(vec![DataType::LargeUtf8, DataType::Utf8]),
+                    TypeSignature::Exact(vec![
+                        DataType::Utf8,
+                        DataType::Utf8,
+                        DataType::Utf8,
+                    ]),
+                    TypeSignature::Exact(vec![
+                        DataType::LargeUtf8,
+                        DataType::Utf8,
+                        DataType::Utf8,
+                    ]),
+                ],
+                fun.volatility(),
+            )
         }
-        BuiltinScalarFunction::RegexpMatch => Signature::one_of(
-            vec![
-                TypeSignature::Exact(vec![DataType::Utf8, DataType::Utf8]),
-                TypeSignature::Exact(vec![DataType::LargeUtf8, DataType::Utf8]),
-                TypeSignature::Exact(vec![
-                    DataType::Utf8,
-                    DataType::Utf8,
-                    DataType::Utf8,
-                ]),
-                TypeSignature::Exact(vec![
-                    DataType::LargeUtf8,
-                    DataType::Utf8,
-                    DataType::Utf8,
-                ]),
-            ],
-            fun.volatility(),
-        ),
         BuiltinScalarFunction::Random => Signature::exact(vec![], fun.volatility()),
         BuiltinScalarFunction::Uuid => Signature::exact(vec![], fun.volatility()),
         BuiltinScalarFunction::Power => Signature::one_of(
@@ -589,7 +654,7 @@ pub fn signature(fun: &BuiltinScalarFunction) -> Signature {
             ],
             fun.volatility(),
         ),
-        BuiltinScalarFunction::Round => Signature::one_of(
+        BuiltinScalarFunction::Round | BuiltinScalarFunction::Trunc => Signature::one_of(
             vec![
                 TypeSignature::Exact(vec![DataType::Float64, DataType::Int64]),
                 TypeSignature::Exact(vec![DataType::Float32, DataType::Int64]),
@@ -598,6 +663,22 @@ pub fn signature(fun: &BuiltinScalarFunction) -> Signature {
             ],
             fun.volatility(),
         ),
+        BuiltinScalarFunction::Log => Signature::one_of(
+            vec![
+                TypeSignature::Exact(vec![DataType::Float64, DataType::Float64]),
+                TypeSignature::Exact(vec![DataType::Float32, DataType::Float32]),
+                TypeSignature::Exact(vec![DataType::Float64]),
+                TypeSignature::Exact(vec![DataType::Float32]),
+            ],
+            fun.volatility(),
+        ),
+        BuiltinScalarFunction::Pi => Signature::exact(vec![], fun.volatility()),
+        BuiltinScalarFunction::Factorial => {
+            Signature::exact(vec![DataType::Int64], fun.volatility())
+        }
+        BuiltinScalarFunction::Gcd | BuiltinScalarFunction::Lcm => {
+            Signature::exact(vec![DataType::Int64, DataType::Int64], fun.volatility())
+        }
         BuiltinScalarFunction::Atan2 => Signature::one_of(
             vec![
                 TypeSignature::Exact(vec![DataType::Float32, DataType::Float32]),
@@ -606,6 +687,42 @@ pub fn signature(fun: &BuiltinScalarFunction) -> Signature {
             fun.volatility(),
         ),
         BuiltinScalarFunction::ArrowTypeof => Signature::any(1, fun.volatility()),
+        BuiltinScalarFunction::XxHash64 => Signature::variadic_any_of(
+            vec![
+                DataType::Utf8,
+                DataType::LargeUtf8,
+                DataType::Binary,
+                DataType::LargeBinary,
+                DataType::Boolean,
+                DataType::Int8,
+                DataType::Int16,
+                DataType::Int32,
+                DataType::Int64,
+                DataType::UInt8,
+                DataType::UInt16,
+                DataType::UInt32,
+                DataType::UInt64,
+                DataType::Float32,
+                DataType::Float64,
+                DataType::Decimal128(DECIMAL128_MAX_PRECISION, 0),
+                DataType::Date32,
+                DataType::Date64,
+                DataType::Time32(TimeUnit::Second),
+                DataType::Time32(TimeUnit::Millisecond),
+                DataType::Time64(TimeUnit::Microsecond),
+                DataType::Time64(TimeUnit::Nanosecond),
+                DataType::Timestamp(TimeUnit::Second, None),
+                DataType::Timestamp(TimeUnit::Millisecond, None),
+                DataType::Timestamp(TimeUnit::Microsecond, None),
+                DataType::Timestamp(TimeUnit::Nanosecond, None),
+            ],
+            fun.volatility(),
+        ),
+        BuiltinScalarFunction::Version
+        | BuiltinScalarFunction::CurrentCatalog
+        | BuiltinScalarFunction::CurrentSchema => {
+            Signature::exact(vec![], fun.volatility())
+        }
         // math expressions expect 1 argument of type f64 or f32
         // priority is given to f64 because e.g. `sqrt(1i32)` is in IR (real numbers) and thus we
         // return the best approximation for it (in f64).
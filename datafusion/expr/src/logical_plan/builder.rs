@@ -18,11 +18,12 @@
 //! This module provides a builder for creating LogicalPlans
 
 use crate::expr_rewriter::{
-    coerce_plan_expr_for_schema, normalize_col, normalize_cols, rewrite_sort_cols_by_aggs,
+    coerce_plan_expr_for_schema, normalize_col, normalize_col_with_schemas,
+    normalize_cols, normalize_projected_col, rewrite_sort_cols_by_aggs,
 };
 use crate::type_coercion::binary::comparison_coercion;
 use crate::utils::{columnize_expr, exprlist_to_fields, from_plan};
-use crate::{and, binary_expr, Operator};
+use crate::{and, binary_expr, BinaryExpr, Operator};
 use crate::{
     logical_plan::{
         Aggregate, Analyze, CrossJoin, Distinct, EmptyRelation, Explain, Filter, Join,
@@ -32,9 +33,9 @@ use crate::{
     },
     utils::{
         can_hash, expand_qualified_wildcard, expand_wildcard, expr_to_columns,
-        group_window_expr_by_sort_keys,
+        find_using_join_column, group_window_expr_by_sort_keys,
     },
-    Expr, ExprSchemable, TableSource,
+    BuiltInWindowFunction, Expr, ExprSchemable, TableSource, WindowFunction,
 };
 use arrow::datatypes::{DataType, Schema, SchemaRef};
 use datafusion_common::{
@@ -332,13 +333,15 @@ impl LogicalPlanBuilder {
                 mut expr,
                 schema: _,
                 alias,
-            }) if missing_cols
-                .iter()
-                .all(|c| input.schema().field_from_column(c).is_ok()) =>
+            }) if missing_cols.iter().all(|c| {
+                input.schema().field_from_column(c).is_ok()
+                    || (c.relation.is_none()
+                        && matches!(find_using_join_column(&input, &c.name), Ok(Some(_))))
+            }) =>
             {
                 let mut missing_exprs = missing_cols
                     .iter()
-                    .map(|c| normalize_col(Expr::Column(c.clone()), &input))
+                    .map(|c| normalize_projected_col(Expr::Column(c.clone()), &input))
                     .collect::<Result<Vec<_>>>()?;
 
                 // Do not let duplicate columns to be added, some of the
@@ -419,6 +422,9 @@ impl LogicalPlanBuilder {
     }
 
     /// Apply a union, preserving duplicate rows
+    ///
+    /// The columns of both plans are matched by position and cast to their
+    /// common type, and are named after the columns of this plan.
     pub fn union(&self, plan: LogicalPlan) -> Result<Self> {
         Ok(Self::from(union_with_alias(self.plan.clone(), plan, None)?))
     }
@@ -436,9 +442,7 @@ impl LogicalPlanBuilder {
             _ => plan,
         };
 
-        Ok(Self::from(LogicalPlan::Distinct(Distinct {
-            input: Arc::new(union_with_alias(left_plan, right_plan, None)?),
-        })))
+        Self::from(left_plan).union(right_plan)?.distinct()
     }
 
     /// Apply deduplication: Only distinct (different) values are returned)
@@ -448,6 +452,74 @@ impl LogicalPlanBuilder {
         })))
     }
 
+    /// Apply a `DISTINCT ON`: only the first row of each group of rows with the
+    /// same `on_expr` values is returned, rows of a group being ordered by
+    /// `sort_expr`. As in PostgreSQL, a non-empty `sort_expr` must start with the
+    /// `on_expr` expressions (in any order). The returned rows are not sorted.
+    pub fn distinct_on(&self, on_expr: Vec<Expr>, sort_expr: Vec<Expr>) -> Result<Self> {
+        if on_expr.is_empty() {
+            return Err(DataFusionError::Plan(
+                "DISTINCT ON requires at least one expression".to_string(),
+            ));
+        }
+        let on_expr = normalize_cols(on_expr, &self.plan)?;
+        let sort_expr = normalize_cols(sort_expr, &self.plan)?
+            .into_iter()
+            .map(|e| match e {
+                Expr::Sort { .. } => e,
+                e => Expr::Sort {
+                    expr: Box::new(e),
+                    asc: true,
+                    nulls_first: false,
+                },
+            })
+            .collect::<Vec<_>>();
+
+        if !sort_expr.is_empty() {
+            let leading = sort_expr
+                .iter()
+                .take(on_expr.len())
+                .map(|e| match e {
+                    Expr::Sort { expr, .. } => expr.as_ref(),
+                    e => e,
+                })
+                .collect::<Vec<_>>();
+            if leading.len() < on_expr.len()
+                || on_expr.iter().any(|e| !leading.contains(&e))
+            {
+                return Err(DataFusionError::Plan(format!(
+                    "SELECT DISTINCT ON expressions must match initial ORDER BY expressions, \
+                     got DISTINCT ON {:?} and ORDER BY {:?}",
+                    on_expr, sort_expr
+                )));
+            }
+        }
+
+        let row_number = Expr::WindowFunction {
+            fun: WindowFunction::BuiltInWindowFunction(BuiltInWindowFunction::RowNumber),
+            args: vec![],
+            partition_by: on_expr,
+            order_by: sort_expr,
+            window_frame: None,
+        };
+        let row_number_col = Expr::Column(Column::from_name(row_number.display_name()?));
+        let columns = self
+            .plan
+            .schema()
+            .fields()
+            .iter()
+            .map(|f| Expr::Column(f.qualified_column()))
+            .collect::<Vec<_>>();
+
+        self.window(vec![row_number])?
+            .filter(binary_expr(
+                row_number_col,
+                Operator::Eq,
+                Expr::Literal(ScalarValue::UInt64(Some(1))),
+            ))?
+            .project(columns)
+    }
+
     /// Apply a join with on constraint.
     ///
     /// Filter expression expected to contain non-equality predicates that can not be pushed
@@ -463,6 +535,78 @@ impl LogicalPlanBuilder {
         self.join_detailed(right, join_type, join_keys, filter, false)
     }
 
+    /// Apply a join on the conjunction of arbitrary boolean expressions, such
+    /// as `col("a").gt(col("b"))`.
+    ///
+    /// Equality predicates between a column of each input are used as the
+    /// keys of the join, and any other predicates filter the matched rows.
+    /// Inner joins without such an equality predicate are planned as a cross
+    /// join followed by a filter, and outer joins as a join without keys,
+    /// evaluating the filter on every pair of rows. Semi and anti joins
+    /// require an equality predicate.
+    pub fn join_on(
+        &self,
+        right: &LogicalPlan,
+        join_type: JoinType,
+        on_exprs: impl IntoIterator<Item = Expr>,
+    ) -> Result<Self> {
+        let join_schema = Arc::new(self.plan.schema().join(right.schema())?);
+
+        let mut keys = vec![];
+        let mut filters = vec![];
+        for expr in on_exprs {
+            // resolve every column eagerly, so that unknown or ambiguous
+            // references are reported as such
+            let mut columns = HashSet::new();
+            expr_to_columns(&expr, &mut columns)?;
+            for column in &columns {
+                join_schema.field_from_column(column)?;
+            }
+            let expr = normalize_col_with_schemas(expr, &[&join_schema], &[])?;
+            split_join_on(
+                expr,
+                self.plan.schema(),
+                right.schema(),
+                &mut keys,
+                &mut filters,
+            );
+        }
+
+        for filter in &filters {
+            let data_type = filter.get_type(&join_schema)?;
+            if data_type != DataType::Boolean {
+                return Err(DataFusionError::Plan(format!(
+                    "Join predicate {:?} must be a boolean expression, but is {:?}",
+                    filter, data_type
+                )));
+            }
+        }
+        let filter = filters.into_iter().reduce(Expr::and);
+
+        if keys.is_empty() {
+            match join_type {
+                JoinType::Inner => {
+                    let join = self.cross_join(right)?;
+                    return match filter {
+                        Some(filter) => join.filter(filter),
+                        None => Ok(join),
+                    };
+                }
+                JoinType::Left | JoinType::Right | JoinType::Full => {}
+                _ => {
+                    return Err(DataFusionError::NotImplemented(format!(
+                        "{} joins without an equality predicate between the inputs are not supported",
+                        join_type
+                    )))
+                }
+            }
+        }
+
+        let (left_keys, right_keys): (Vec<Column>, Vec<Column>) =
+            keys.into_iter().unzip();
+        self.join(right, join_type, (left_keys, right_keys), filter)
+    }
+
     fn normalize(
         plan: &LogicalPlan,
         column: impl Into<Column> + Clone,
@@ -808,6 +952,59 @@ impl LogicalPlanBuilder {
     }
 }
 
+/// Splits the conjunction `expr` into the equality predicates between a
+/// hashable column of each side of a join, accumulated into `keys` as
+/// (left, right) pairs, and the other predicates, accumulated into `filters`
+fn split_join_on(
+    expr: Expr,
+    left_schema: &DFSchema,
+    right_schema: &DFSchema,
+    keys: &mut Vec<(Column, Column)>,
+    filters: &mut Vec<Expr>,
+) {
+    match expr {
+        Expr::BinaryExpr(BinaryExpr {
+            left,
+            op: Operator::And,
+            right,
+        }) => {
+            split_join_on(*left, left_schema, right_schema, keys, filters);
+            split_join_on(*right, left_schema, right_schema, keys, filters);
+        }
+        Expr::BinaryExpr(BinaryExpr {
+            left,
+            op: Operator::Eq,
+            right,
+        }) => match (left.as_ref(), right.as_ref()) {
+            (Expr::Column(l), Expr::Column(r)) => {
+                let is_key = |l: &Column, r: &Column| {
+                    matches!(
+                        (left_schema.field_from_column(l), right_schema.field_from_column(r)),
+                        (Ok(field), Ok(_)) if can_hash(field.data_type())
+                    )
+                };
+                if is_key(l, r) {
+                    keys.push((l.clone(), r.clone()));
+                } else if is_key(r, l) {
+                    keys.push((r.clone(), l.clone()));
+                } else {
+                    filters.push(Expr::BinaryExpr(BinaryExpr {
+                        left,
+                        op: Operator::Eq,
+                        right,
+                    }));
+                }
+            }
+            _ => filters.push(Expr::BinaryExpr(BinaryExpr {
+                left,
+                op: Operator::Eq,
+                right,
+            })),
+        },
+        expr => filters.push(expr),
+    }
+}
+
 /// Creates a schema for a join operation.
 /// The fields from the left side are first
 pub fn build_join_schema(
@@ -815,12 +1012,24 @@ pub fn build_join_schema(
     right: &DFSchema,
     join_type: &JoinType,
 ) -> Result<DFSchema> {
+    // the fields of a side padded with nulls by an outer join are nullable
+    let nullable_fields = |fields: &[DFField], padded: bool| -> Vec<DFField> {
+        fields
+            .iter()
+            .map(|field| {
+                let nullable = padded || field.is_nullable();
+                field.clone().with_nullable(nullable)
+            })
+            .collect()
+    };
     let fields: Vec<DFField> = match join_type {
         JoinType::Inner | JoinType::Left | JoinType::Full | JoinType::Right => {
-            let right_fields = right.fields().iter();
-            let left_fields = left.fields().iter();
+            let left_padded = matches!(join_type, JoinType::Right | JoinType::Full);
+            let right_padded = matches!(join_type, JoinType::Left | JoinType::Full);
+            let left_fields = nullable_fields(left.fields(), left_padded);
+            let right_fields = nullable_fields(right.fields(), right_padded);
             // left then right
-            left_fields.chain(right_fields).cloned().collect()
+            left_fields.into_iter().chain(right_fields).collect()
         }
         JoinType::LeftSemi | JoinType::LeftAnti => {
             // Only use the left side for the schema
@@ -986,8 +1195,10 @@ pub fn project_with_alias(
             }
             Expr::QualifiedWildcard { ref qualifier } => projected_expr
                 .extend(expand_qualified_wildcard(qualifier, input_schema, &plan)?),
-            _ => projected_expr
-                .push(columnize_expr(normalize_col(e, &plan)?, input_schema)),
+            _ => projected_expr.push(columnize_expr(
+                normalize_projected_col(e, &plan)?,
+                input_schema,
+            )),
         }
     }
     validate_unique_names("Projections", projected_expr.iter())?;
@@ -1131,6 +1342,105 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn plan_builder_distinct_on() -> Result<()> {
+        let plan =
+            table_scan(Some("employee_csv"), &employee_schema(), Some(vec![3, 4]))?
+                .distinct_on(
+                    vec![col("state")],
+                    vec![
+                        col("state").sort(true, false),
+                        col("salary").sort(false, true),
+                    ],
+                )?
+                .build()?;
+
+        let expected = "Projection: employee_csv.state, employee_csv.salary\
+        \n  Filter: ROW_NUMBER() PARTITION BY [employee_csv.state] ORDER BY [employee_csv.state ASC NULLS LAST, employee_csv.salary DESC NULLS FIRST] = UInt64(1)\
+        \n    WindowAggr: windowExpr=[[ROW_NUMBER() PARTITION BY [employee_csv.state] ORDER BY [employee_csv.state ASC NULLS LAST, employee_csv.salary DESC NULLS FIRST]]]\
+        \n      TableScan: employee_csv projection=[state, salary]";
+
+        assert_eq!(expected, format!("{:?}", plan));
+
+        // the ORDER BY has to lead with the DISTINCT ON expressions
+        let err = table_scan(Some("employee_csv"), &employee_schema(), None)?
+            .distinct_on(vec![col("state")], vec![col("salary").sort(false, true)])
+            .unwrap_err();
+        assert!(err.to_string().contains(
+            "SELECT DISTINCT ON expressions must match initial ORDER BY expressions"
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn plan_builder_join_on() -> Result<()> {
+        let t2 = table_scan(Some("t2"), &employee_schema(), None)?.build()?;
+
+        // equality between the inputs is a key, the rest filters matches
+        let plan = table_scan(Some("t1"), &employee_schema(), None)?
+            .join_on(
+                &t2,
+                JoinType::Left,
+                vec![
+                    col("t2.id").eq(col("t1.id")),
+                    col("t1.salary").gt(col("t2.salary")),
+                ],
+            )?
+            .build()?;
+        let expected = "Left Join: t1.id = t2.id Filter: t1.salary > t2.salary\
+        \n  TableScan: t1\
+        \n  TableScan: t2";
+        assert_eq!(expected, format!("{:?}", plan));
+
+        // without any equality, inner joins filter a cross join and outer
+        // joins have no keys
+        let on = vec![col("t1.salary").gt(col("t2.salary"))];
+        let plan = table_scan(Some("t1"), &employee_schema(), None)?
+            .join_on(&t2, JoinType::Inner, on.clone())?
+            .build()?;
+        let expected = "Filter: t1.salary > t2.salary\
+        \n  CrossJoin:\
+        \n    TableScan: t1\
+        \n    TableScan: t2";
+        assert_eq!(expected, format!("{:?}", plan));
+
+        let plan = table_scan(Some("t1"), &employee_schema(), None)?
+            .join_on(&t2, JoinType::Full, on.clone())?
+            .build()?;
+        let expected = "Full Join:  Filter: t1.salary > t2.salary\
+        \n  TableScan: t1\
+        \n  TableScan: t2";
+        assert_eq!(expected, format!("{:?}", plan));
+
+        let err = table_scan(Some("t1"), &employee_schema(), None)?
+            .join_on(&t2, JoinType::LeftSemi, on)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "This feature is not implemented: LeftSemi joins without an equality predicate between the inputs are not supported"
+        );
+
+        // column references are resolved against both inputs
+        let err = table_scan(Some("t1"), &employee_schema(), None)?
+            .join_on(&t2, JoinType::Inner, vec![col("id").eq(col("t2.id"))])
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Schema error: Ambiguous reference to unqualified field 'id', which could be any of 't1'.'id', 't2'.'id'"
+        );
+
+        let err = table_scan(Some("t1"), &employee_schema(), None)?
+            .join_on(&t2, JoinType::Inner, vec![col("t1.salary") + col("t2.id")])
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Error during planning: Join predicate t1.salary + t2.id must be a boolean expression, but is Int32"
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn plan_using_join_wildcard_projection() -> Result<()> {
         let t2 = table_scan(Some("t2"), &employee_schema(), None)?.build()?;
@@ -1215,6 +1525,109 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn plan_builder_union_coerced_columns() -> Result<()> {
+        let left_schema = Schema::new(vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("b", DataType::Utf8, false),
+            Field::new("c", DataType::Utf8, false),
+        ]);
+        let dictionary =
+            DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8));
+        let right_schema = Schema::new(vec![
+            Field::new("x", DataType::Int64, false),
+            Field::new("y", dictionary.clone(), false),
+            Field::new("z", DataType::Utf8, true),
+        ]);
+        let left = table_scan(Some("t1"), &left_schema, None)?;
+        let right = table_scan(Some("t2"), &right_schema, None)?.build()?;
+
+        // the columns are matched by position and named after the left ones,
+        // only the left columns are cast to the wider or dictionary types
+        let plan = left.union(right.clone())?.build()?;
+        let expected = "Union        \n  Projection: CAST(t1.a AS Int64) AS a, CAST(t1.b AS Dictionary(Int32, Utf8)) AS b, t1.c        \n    TableScan: t1        \n  TableScan: t2";
+        assert_eq!(expected, format!("{:?}", plan));
+
+        let fields = plan.schema().fields();
+        assert_eq!(
+            fields.iter().map(|f| f.name().as_str()).collect::<Vec<_>>(),
+            vec!["a", "b", "c"]
+        );
+        assert_eq!(
+            fields
+                .iter()
+                .map(|f| f.data_type().clone())
+                .collect::<Vec<_>>(),
+            vec![DataType::Int64, dictionary, DataType::Utf8]
+        );
+        assert_eq!(
+            fields.iter().map(|f| f.is_nullable()).collect::<Vec<_>>(),
+            vec![false, false, true]
+        );
+
+        let plan = left.union_distinct(right)?.build()?;
+        let expected = "Distinct:        \n  Union        \n    Projection: CAST(t1.a AS Int64) AS a, CAST(t1.b AS Dictionary(Int32, Utf8)) AS b, t1.c        \n      TableScan: t1        \n    TableScan: t2";
+        assert_eq!(expected, format!("{:?}", plan));
+
+        Ok(())
+    }
+
+    #[test]
+    fn plan_builder_union_incompatible_columns_error() -> Result<()> {
+        let plan1 = table_scan(None, &employee_schema(), Some(vec![0]))?;
+        let plan2 = table_scan(
+            None,
+            &Schema::new(vec![Field::new("flag", DataType::Boolean, false)]),
+            None,
+        )?;
+
+        let expected = "Error during planning: UNION Column flag (type: Boolean) is \
+        not compatible with column id (type: Int32)";
+        let err_msg1 = plan1.union(plan2.build()?).unwrap_err();
+        let err_msg2 = plan1.union_distinct(plan2.build()?).unwrap_err();
+
+        assert_eq!(err_msg1.to_string(), expected);
+        assert_eq!(err_msg2.to_string(), expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn plan_builder_join_nullability() -> Result<()> {
+        let t1 = test_table_scan_with_name("t1")?;
+        let t2 = test_table_scan_with_name("t2")?;
+
+        // the sides an outer join pads with NULLs become nullable
+        for (join_type, left_nullable, right_nullable) in [
+            (JoinType::Inner, false, false),
+            (JoinType::Left, false, true),
+            (JoinType::Right, true, false),
+            (JoinType::Full, true, true),
+        ] {
+            let plan = LogicalPlanBuilder::from(t1.clone())
+                .join(&t2, join_type, (vec!["t1.a"], vec!["t2.a"]), None)?
+                .build()?;
+            let nullability: Vec<_> = plan
+                .schema()
+                .fields()
+                .iter()
+                .map(|field| field.is_nullable())
+                .collect();
+            let mut expected = vec![left_nullable; 3];
+            expected.extend(vec![right_nullable; 3]);
+            assert_eq!(nullability, expected, "{} join", join_type);
+        }
+
+        // semi and anti joins only output rows of one side as they are
+        for join_type in [JoinType::LeftSemi, JoinType::LeftAnti] {
+            let plan = LogicalPlanBuilder::from(t1.clone())
+                .join(&t2, join_type, (vec!["t1.a"], vec!["t2.a"]), None)?
+                .build()?;
+            assert!(plan.schema().fields().iter().all(|f| !f.is_nullable()));
+        }
+        Ok(())
+    }
+
     #[test]
     fn plan_builder_simple_distinct() -> Result<()> {
         let plan =
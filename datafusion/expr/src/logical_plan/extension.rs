@@ -18,7 +18,7 @@
 //! This module defines the interface for logical nodes
 use crate::{Expr, LogicalPlan};
 use datafusion_common::DFSchemaRef;
-use std::{any::Any, collections::HashSet, fmt, sync::Arc};
+use std::{any::Any, collections::HashSet, fmt, hash::Hasher, sync::Arc};
 
 /// This defines the interface for `LogicalPlan` nodes that can be
 /// used to extend DataFusion with custom relational operators.
@@ -77,4 +77,14 @@ pub trait UserDefinedLogicalNode: fmt::Debug + Send + Sync {
         exprs: &[Expr],
         inputs: &[LogicalPlan],
     ) -> Arc<dyn UserDefinedLogicalNode>;
+
+    /// Feed the definition of this node, excluding its inputs, to
+    /// `state`. Nodes that are equal according to `dyn_eq` must feed the
+    /// same values.
+    fn dyn_hash(&self, state: &mut dyn Hasher);
+
+    /// Returns true if `other` is a node of the same type as this one with
+    /// an equal definition, excluding their inputs. This lets optimizer
+    /// rules recognize equivalent extension nodes.
+    fn dyn_eq(&self, other: &dyn UserDefinedLogicalNode) -> bool;
 }
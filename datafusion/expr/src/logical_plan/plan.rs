@@ -316,10 +316,33 @@ impl LogicalPlan {
         }
     }
 
-    /// returns all `Using` join columns in a logical plan
+    /// returns all `Using` join columns in a logical plan, as the sets of
+    /// columns joined on each other, such as `{t1.id, t2.id, t3.id}` for
+    /// `t1 JOIN t2 USING (id) JOIN t3 USING (id)`
     pub fn using_columns(&self) -> Result<Vec<HashSet<Column>>, DataFusionError> {
+        let mut using_columns: Vec<HashSet<Column>> = vec![];
+        for (_, left, right) in self.using_join_columns()? {
+            let mut columns = HashSet::from([left, right]);
+            // merge the sets of the columns already joined on either of them
+            using_columns.retain(|set| {
+                let joined = !set.is_disjoint(&columns);
+                if joined {
+                    columns.extend(set.iter().cloned());
+                }
+                !joined
+            });
+            using_columns.push(columns);
+        }
+        Ok(using_columns)
+    }
+
+    /// returns the pairs of left and right columns of all `Using` joins in a
+    /// logical plan, with the type of their join
+    pub fn using_join_columns(
+        &self,
+    ) -> Result<Vec<(JoinType, Column, Column)>, DataFusionError> {
         struct UsingJoinColumnVisitor {
-            using_columns: Vec<HashSet<Column>>,
+            using_join_columns: Vec<(JoinType, Column, Column)>,
         }
 
         impl PlanVisitor for UsingJoinColumnVisitor {
@@ -328,15 +351,13 @@ impl LogicalPlan {
             fn pre_visit(&mut self, plan: &LogicalPlan) -> Result<bool, Self::Error> {
                 if let LogicalPlan::Join(Join {
                     join_constraint: JoinConstraint::Using,
+                    join_type,
                     on,
                     ..
                 }) = plan
                 {
-                    self.using_columns.push(
-                        on.iter()
-                            .flat_map(|entry| [&entry.0, &entry.1])
-                            .cloned()
-                            .collect::<HashSet<Column>>(),
+                    self.using_join_columns.extend(
+                        on.iter().map(|(l, r)| (*join_type, l.clone(), r.clone())),
                     );
                 }
                 Ok(true)
@@ -344,10 +365,10 @@ impl LogicalPlan {
         }
 
         let mut visitor = UsingJoinColumnVisitor {
-            using_columns: vec![],
+            using_join_columns: vec![],
         };
         self.accept(&mut visitor)?;
-        Ok(visitor.using_columns)
+        Ok(visitor.using_join_columns)
     }
 }
 
@@ -833,10 +854,17 @@ impl LogicalPlan {
                         filter,
                         join_constraint,
                         join_type,
+                        null_equals_null,
                         ..
                     }) => {
                         let join_expr: Vec<String> =
                             keys.iter().map(|(l, r)| format!("{} = {}", l, r)).collect();
+                        // null keys only match each other when marked
+                        let null_equals_null = if *null_equals_null {
+                            " NullEqualsNull"
+                        } else {
+                            ""
+                        };
                         let filter_expr = filter
                             .as_ref()
                             .map(|expr| format!(" Filter: {}", expr))
@@ -845,18 +873,20 @@ impl LogicalPlan {
                             JoinConstraint::On => {
                                 write!(
                                     f,
-                                    "{} Join: {}{}",
+                                    "{} Join: {}{}{}",
                                     join_type,
                                     join_expr.join(", "),
+                                    null_equals_null,
                                     filter_expr
                                 )
                             }
                             JoinConstraint::Using => {
                                 write!(
                                     f,
-                                    "{} Join: Using {}{}",
+                                    "{} Join: Using {}{}{}",
                                     join_type,
                                     join_expr.join(", "),
+                                    null_equals_null,
                                     filter_expr,
                                 )
                             }
@@ -1300,6 +1330,9 @@ pub struct CreateMemoryTable {
     pub if_not_exists: bool,
     /// Option to replace table content if table already exists
     pub or_replace: bool,
+    /// Whether the table is a temporary table, only visible to the session
+    /// creating it
+    pub temporary: bool,
 }
 
 /// Creates a view.
@@ -1418,10 +1451,16 @@ impl Aggregate {
         let grouping_expr: Vec<Expr> = grouping_set_to_exprlist(group_expr.as_slice())?;
         let all_expr = grouping_expr.iter().chain(aggr_expr.iter());
         validate_unique_names("Aggregations", all_expr.clone())?;
-        let schema = DFSchema::new_with_metadata(
-            exprlist_to_fields(all_expr, &input)?,
-            input.schema().metadata().clone(),
-        )?;
+        let mut fields = exprlist_to_fields(all_expr, &input)?;
+        if matches!(group_expr.as_slice(), [Expr::GroupingSet(_)]) {
+            // the grouping sets that leave out an expression are padded
+            // with NULLs in its place
+            for field in &mut fields[0..grouping_expr.len()] {
+                *field = field.clone().with_nullable(true);
+            }
+        }
+        let schema =
+            DFSchema::new_with_metadata(fields, input.schema().metadata().clone())?;
         Self::try_new_with_schema(input, group_expr, aggr_expr, Arc::new(schema))
     }
 
@@ -20,6 +20,10 @@
 
 use arrow::datatypes::DataType;
 
+/// The time zone of a `Timestamp` type of a [`TypeSignature`] matching the
+/// timestamps of any time zone, which keep theirs
+pub const TIMEZONE_WILDCARD: &str = "+TZ";
+
 ///A function's volatility, which defines the functions eligibility for certain optimizations
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
 pub enum Volatility {
@@ -56,6 +60,19 @@ pub enum TypeSignature {
     Any(usize),
     /// One of a list of signatures
     OneOf(Vec<TypeSignature>),
+    /// at least one argument, each of its own type out of a list of valid
+    /// types, where decimals of any precision and scale, and timestamps of
+    /// any time zone, are valid if one of the list is
+    // A function such as `xxhash64` is `VariadicAnyOf(vec![DataType::Utf8, DataType::Int64, ...])`
+    VariadicAnyOf(Vec<DataType>),
+    /// at least one argument of types that have a common type, which all of
+    /// them are coerced to
+    // A function such as `coalesce` is `VariadicCommon`
+    VariadicCommon,
+    /// fixed number of arguments of types that have a common type, which all
+    /// of them are coerced to
+    // A function such as `nullif` is `UniformCommon(2)`
+    UniformCommon(usize),
 }
 
 ///The Signature of a function defines its supported input types as well as its volatility.
@@ -89,6 +106,27 @@ impl Signature {
             volatility,
         }
     }
+    /// variadic_any_of - Creates a variadic signature that represents at least one argument, each of its own type out of valid_types.
+    pub fn variadic_any_of(valid_types: Vec<DataType>, volatility: Volatility) -> Self {
+        Self {
+            type_signature: TypeSignature::VariadicAnyOf(valid_types),
+            volatility,
+        }
+    }
+    /// variadic_common - Creates a variadic signature that represents at least one argument, all coerced to their common type.
+    pub fn variadic_common(volatility: Volatility) -> Self {
+        Self {
+            type_signature: TypeSignature::VariadicCommon,
+            volatility,
+        }
+    }
+    /// uniform_common - Creates a function with a fixed number of arguments, all coerced to their common type.
+    pub fn uniform_common(arg_count: usize, volatility: Volatility) -> Self {
+        Self {
+            type_signature: TypeSignature::UniformCommon(arg_count),
+            volatility,
+        }
+    }
     /// uniform - Creates a function with a fixed number of arguments of the same type, which must be from valid_types.
     pub fn uniform(
         arg_count: usize,
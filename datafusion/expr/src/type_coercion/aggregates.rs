@@ -38,6 +38,17 @@ pub static NUMERICS: &[DataType] = &[
     DataType::Float64,
 ];
 
+pub static INTEGERS: &[DataType] = &[
+    DataType::Int8,
+    DataType::Int16,
+    DataType::Int32,
+    DataType::Int64,
+    DataType::UInt8,
+    DataType::UInt16,
+    DataType::UInt32,
+    DataType::UInt64,
+];
+
 pub static TIMESTAMPS: &[DataType] = &[
     DataType::Timestamp(TimeUnit::Second, None),
     DataType::Timestamp(TimeUnit::Millisecond, None),
@@ -68,7 +79,29 @@ pub fn coerce_types(
         AggregateFunction::Count | AggregateFunction::ApproxDistinct => {
             Ok(input_types.to_vec())
         }
-        AggregateFunction::ArrayAgg => Ok(input_types.to_vec()),
+        AggregateFunction::ArrayAgg
+        | AggregateFunction::FirstValue
+        | AggregateFunction::LastValue => {
+            if let Some(data_type) = input_types
+                .iter()
+                .skip(2)
+                .find(|data_type| **data_type != DataType::Boolean)
+            {
+                return Err(DataFusionError::Plan(format!(
+                    "The ordering arguments for {:?} must be Boolean, not {:?}.",
+                    agg_fun, data_type
+                )));
+            }
+            Ok(input_types.to_vec())
+        }
+        AggregateFunction::StringAgg => {
+            // the values are concatenated as strings
+            let value_type = match &input_types[0] {
+                DataType::LargeUtf8 => DataType::LargeUtf8,
+                _ => DataType::Utf8,
+            };
+            Ok(vec![value_type, DataType::Utf8])
+        }
         AggregateFunction::Min | AggregateFunction::Max => {
             // min and max support the dictionary data type
             // unpack the dictionary to get the value
@@ -210,7 +243,35 @@ pub fn coerce_types(
             }
             Ok(input_types.to_vec())
         }
-        AggregateFunction::Median => Ok(input_types.to_vec()),
+        AggregateFunction::Median | AggregateFunction::Mode => {
+            if !is_median_support_arg_type(&input_types[0]) {
+                return Err(DataFusionError::Plan(format!(
+                    "The function {:?} does not support inputs of type {:?}.",
+                    agg_fun, input_types[0]
+                )));
+            }
+            Ok(input_types.to_vec())
+        }
+        AggregateFunction::BitAnd
+        | AggregateFunction::BitOr
+        | AggregateFunction::BitXor => {
+            if !is_integer_arg_type(&input_types[0]) {
+                return Err(DataFusionError::Plan(format!(
+                    "The function {:?} does not support inputs of type {:?}.",
+                    agg_fun, input_types[0]
+                )));
+            }
+            Ok(input_types.to_vec())
+        }
+        AggregateFunction::BoolAnd | AggregateFunction::BoolOr => {
+            if input_types[0] != DataType::Boolean {
+                return Err(DataFusionError::Plan(format!(
+                    "The function {:?} does not support inputs of type {:?}.",
+                    agg_fun, input_types[0]
+                )));
+            }
+            Ok(input_types.to_vec())
+        }
         AggregateFunction::Grouping => Ok(vec![input_types[0].clone()]),
     }
 }
@@ -433,6 +494,14 @@ pub fn is_sum_support_arg_type(arg_type: &DataType) -> bool {
     )
 }
 
+/// Whether MEDIAN and MODE support values of type `arg_type`, numbers, dates
+/// and timestamps
+pub fn is_median_support_arg_type(arg_type: &DataType) -> bool {
+    NUMERICS.contains(arg_type)
+        || DATES.contains(arg_type)
+        || matches!(arg_type, DataType::Timestamp(_, _))
+}
+
 pub fn is_avg_support_arg_type(arg_type: &DataType) -> bool {
     matches!(
         arg_type,
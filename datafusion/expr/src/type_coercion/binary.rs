@@ -132,7 +132,7 @@ pub fn coerce_types(
         Operator::RegexMatch
         | Operator::RegexIMatch
         | Operator::RegexNotMatch
-        | Operator::RegexNotIMatch => string_coercion(lhs_type, rhs_type),
+        | Operator::RegexNotIMatch => like_coercion(lhs_type, rhs_type),
         // "||" operator has its own rules, and always return a string type
         Operator::StringConcat => string_concat_coercion(lhs_type, rhs_type),
         Operator::IsDistinctFrom | Operator::IsNotDistinctFrom => {
@@ -191,6 +191,21 @@ pub fn comparison_coercion(lhs_type: &DataType, rhs_type: &DataType) -> Option<D
         .or_else(|| string_numeric_coercion(lhs_type, rhs_type))
 }
 
+/// Returns the type that values of both `lhs_type` and `rhs_type` are cast
+/// to when they are combined into a single value, as the arguments of
+/// `COALESCE` are. Unlike [`comparison_coercion`], numbers and strings have
+/// no common type.
+pub fn common_coercion(lhs_type: &DataType, rhs_type: &DataType) -> Option<DataType> {
+    if lhs_type == rhs_type {
+        return Some(lhs_type.clone());
+    }
+    comparison_binary_numeric_coercion(lhs_type, rhs_type)
+        .or_else(|| dictionary_coercion(lhs_type, rhs_type, true))
+        .or_else(|| temporal_coercion(lhs_type, rhs_type))
+        .or_else(|| string_coercion(lhs_type, rhs_type))
+        .or_else(|| null_coercion(lhs_type, rhs_type))
+}
+
 /// Returns the output type of applying numeric operations such as `=`
 /// to arguments `lhs_type` and `rhs_type` if one is numeric and one
 /// is `Utf8`/`LargeUtf8`.
@@ -505,11 +520,12 @@ fn string_coercion(lhs_type: &DataType, rhs_type: &DataType) -> Option<DataType>
     }
 }
 
-/// coercion rules for like operations.
-/// This is a union of string coercion rules and dictionary coercion rules
+/// coercion rules for like and regular expression match operations.
+/// This is a union of string coercion rules and dictionary coercion rules,
+/// string dictionaries being preserved so that only their values are matched
 fn like_coercion(lhs_type: &DataType, rhs_type: &DataType) -> Option<DataType> {
     string_coercion(lhs_type, rhs_type)
-        .or_else(|| dictionary_coercion(lhs_type, rhs_type, false))
+        .or_else(|| dictionary_coercion(lhs_type, rhs_type, true))
         .or_else(|| null_coercion(lhs_type, rhs_type))
 }
 
@@ -629,7 +645,7 @@ fn null_coercion(lhs_type: &DataType, rhs_type: &DataType) -> Option<DataType> {
 mod tests {
     use super::*;
     use crate::Operator;
-    use arrow::datatypes::DataType;
+    use arrow::datatypes::{DataType, TimeUnit};
     use datafusion_common::DataFusionError;
     use datafusion_common::Result;
 
@@ -648,6 +664,20 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_common_coercion() {
+        use DataType::*;
+        assert_eq!(common_coercion(&Int32, &Int64), Some(Int64));
+        assert_eq!(common_coercion(&Int8, &Float32), Some(Float32));
+        assert_eq!(common_coercion(&Utf8, &LargeUtf8), Some(LargeUtf8));
+        assert_eq!(common_coercion(&Null, &Utf8), Some(Utf8));
+        assert_eq!(common_coercion(&Date32, &Date64), Some(Date64));
+        // unlike comparisons, numbers and strings are not unified
+        assert_eq!(common_coercion(&Int64, &Utf8), None);
+        assert_eq!(common_coercion(&Utf8, &Float64), None);
+        assert_eq!(common_coercion(&Boolean, &Int32), None);
+    }
+
     #[test]
     fn test_decimal_binary_comparison_coercion() -> Result<()> {
         let input_decimal = DataType::Decimal128(20, 3);
@@ -826,6 +856,28 @@ mod tests {
             Operator::Lt,
             DataType::Date64
         );
+        // only string literals, parsed while planning, are compared to timestamps
+        assert_eq!(
+            comparison_coercion(
+                &DataType::Timestamp(TimeUnit::Nanosecond, None),
+                &DataType::Utf8
+            ),
+            None
+        );
+        let dictionary =
+            DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8));
+        test_coercion_binary_rule!(
+            dictionary,
+            DataType::Utf8,
+            Operator::Like,
+            dictionary.clone()
+        );
+        test_coercion_binary_rule!(
+            DataType::Utf8,
+            dictionary,
+            Operator::RegexIMatch,
+            dictionary.clone()
+        );
         test_coercion_binary_rule!(
             DataType::Utf8,
             DataType::Utf8,
@@ -15,7 +15,8 @@
 // specific language governing permissions and limitations
 // under the License.
 
-use crate::{Signature, TypeSignature};
+use crate::type_coercion::binary::common_coercion;
+use crate::{Signature, TypeSignature, TIMEZONE_WILDCARD};
 use arrow::{
     compute::can_cast_types,
     datatypes::{DataType, TimeUnit},
@@ -31,6 +32,13 @@ pub fn data_types(
     current_types: &[DataType],
     signature: &Signature,
 ) -> Result<Vec<DataType>> {
+    if let TypeSignature::VariadicCommon | TypeSignature::UniformCommon(_) =
+        signature.type_signature
+    {
+        // the common type need not be one `can_coerce_from` allows, such as
+        // the decimal of an integer and a decimal
+        return common_types(&signature.type_signature, current_types);
+    }
     if current_types.is_empty() {
         return Ok(vec![]);
     }
@@ -76,6 +84,41 @@ fn get_valid_types(
                 .map(|_| current_types[0].clone())
                 .collect()]
         }
+        TypeSignature::VariadicAnyOf(valid_types) => {
+            if current_types.is_empty() {
+                return Err(DataFusionError::Plan(
+                    "The function expected at least one argument but received none"
+                        .to_string(),
+                ));
+            }
+            // every argument keeps its type if it is valid, or is coerced to
+            // the first valid type it can be
+            let types = current_types
+                .iter()
+                .map(|current_type| {
+                    if valid_types
+                        .iter()
+                        .any(|valid_type| is_variant_of(current_type, valid_type))
+                    {
+                        return Ok(current_type.clone());
+                    }
+                    valid_types
+                        .iter()
+                        .find(|valid_type| can_coerce_from(valid_type, current_type))
+                        .cloned()
+                        .ok_or_else(|| {
+                            DataFusionError::Plan(format!(
+                                "The function does not support arguments of type {:?}",
+                                current_type
+                            ))
+                        })
+                })
+                .collect::<Result<_>>()?;
+            vec![types]
+        }
+        TypeSignature::VariadicCommon | TypeSignature::UniformCommon(_) => {
+            vec![common_types(signature, current_types)?]
+        }
         TypeSignature::Exact(valid_types) => vec![valid_types.clone()],
         TypeSignature::Any(number) => {
             if current_types.len() != *number {
@@ -97,6 +140,51 @@ fn get_valid_types(
     Ok(valid_types)
 }
 
+/// Returns the common type of `current_types` for each of them, for the
+/// signatures whose arguments are coerced to their common type
+fn common_types(
+    signature: &TypeSignature,
+    current_types: &[DataType],
+) -> Result<Vec<DataType>> {
+    if let TypeSignature::UniformCommon(number) = signature {
+        if current_types.len() != *number {
+            return Err(DataFusionError::Plan(format!(
+                "The function expected {} arguments but received {}",
+                number,
+                current_types.len()
+            )));
+        }
+    }
+    let (first, rest) = current_types.split_first().ok_or_else(|| {
+        DataFusionError::Plan(
+            "The function expected at least one argument but received none".to_string(),
+        )
+    })?;
+    let common_type = rest
+        .iter()
+        .try_fold(first.clone(), |common_type, data_type| {
+            common_coercion(&common_type, data_type).ok_or_else(|| {
+                DataFusionError::Plan(format!(
+                    "There is no common type for the arguments of types {:?}",
+                    current_types
+                ))
+            })
+        })?;
+    Ok(vec![common_type; current_types.len()])
+}
+
+/// Whether `current_type` is `valid_type`, whatever the precision and scale
+/// of decimals and the time zone of timestamps
+fn is_variant_of(current_type: &DataType, valid_type: &DataType) -> bool {
+    match (current_type, valid_type) {
+        (DataType::Decimal128(_, _), DataType::Decimal128(_, _)) => true,
+        (DataType::Timestamp(unit, _), DataType::Timestamp(valid_unit, _)) => {
+            unit == valid_unit
+        }
+        _ => current_type == valid_type,
+    }
+}
+
 /// Try to coerce current_types into valid_types.
 fn maybe_data_types(
     valid_types: &[DataType],
@@ -110,7 +198,9 @@ fn maybe_data_types(
     for (i, valid_type) in valid_types.iter().enumerate() {
         let current_type = &current_types[i];
 
-        if current_type == valid_type {
+        if current_type == valid_type
+            || matches_timezone_wildcard(current_type, valid_type)
+        {
             new_type.push(current_type.clone())
         } else {
             // attempt to coerce
@@ -125,6 +215,18 @@ fn maybe_data_types(
     Some(new_type)
 }
 
+/// Whether `current_type` is a timestamp with a time zone matching the
+/// [`TIMEZONE_WILDCARD`] of `valid_type`
+fn matches_timezone_wildcard(current_type: &DataType, valid_type: &DataType) -> bool {
+    match (current_type, valid_type) {
+        (
+            DataType::Timestamp(unit, Some(_)),
+            DataType::Timestamp(valid_unit, Some(valid_tz)),
+        ) => unit == valid_unit && valid_tz == TIMEZONE_WILDCARD,
+        _ => false,
+    }
+}
+
 /// Return true if a value of type `type_from` can be coerced
 /// (losslessly converted) into a value of `type_to`
 ///
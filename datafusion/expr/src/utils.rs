@@ -17,13 +17,14 @@
 
 //! Expression utilities
 
+use crate::expr_fn::coalesce;
 use crate::expr_rewriter::{ExprRewritable, ExprRewriter, RewriteRecursion};
 use crate::expr_visitor::{ExprVisitable, ExpressionVisitor, Recursion};
 use crate::logical_plan::builder::build_join_schema;
 use crate::logical_plan::{
     Aggregate, Analyze, CreateMemoryTable, CreateView, Distinct, Extension, Filter, Join,
-    Limit, Partitioning, Projection, Repartition, Sort, Subquery, SubqueryAlias, Union,
-    Values, Window,
+    JoinType, Limit, Partitioning, Projection, Repartition, Sort, Subquery,
+    SubqueryAlias, Union, Values, Window,
 };
 use crate::{Cast, Expr, ExprSchemable, LogicalPlan, LogicalPlanBuilder};
 use arrow::datatypes::{DataType, TimeUnit};
@@ -140,40 +141,172 @@ pub fn expr_to_columns(expr: &Expr, accum: &mut HashSet<Column>) -> Result<()> {
 }
 
 /// Resolves an `Expr::Wildcard` to a collection of `Expr::Column`'s.
+///
+/// The columns the inputs of `Using` joins are joined on are only expanded
+/// once, in place of the first of them, to their value, see
+/// [`using_join_column_expr`]
 pub fn expand_wildcard(schema: &DFSchema, plan: &LogicalPlan) -> Result<Vec<Expr>> {
-    let using_columns = plan.using_columns()?;
-    let columns_to_skip = using_columns
+    // a qualified wildcard may only expand the columns of one side
+    let using_join_columns = schema_using_join_columns(plan, schema)?;
+    let mut expanded: HashSet<Column> = HashSet::new();
+    let mut exprs = vec![];
+    for f in schema.fields() {
+        let col = f.qualified_column();
+        if expanded.contains(&col) {
+            continue;
+        }
+        match using_join_column_value(&using_join_columns, &col) {
+            Some((expr, columns)) => {
+                expanded.extend(columns);
+                exprs.push(match expr {
+                    expr @ Expr::Column(_) => expr,
+                    expr => expr.alias(&col.name),
+                });
+            }
+            None => exprs.push(Expr::Column(col)),
+        }
+    }
+    Ok(exprs)
+}
+
+/// Returns the value of the column a `Using` join of `join_type` joins on
+/// the `left` and `right` values, as in the SQL standard: the value of the
+/// input whose rows are all kept, or both coalesced for a full join.
+pub fn using_join_column_expr(join_type: JoinType, left: Expr, right: Expr) -> Expr {
+    match join_type {
+        JoinType::Right => right,
+        JoinType::Full => coalesce(vec![left, right]),
+        _ => left,
+    }
+}
+
+/// Returns the `using_join_columns` of `plan` whose columns are both in
+/// `schema`
+fn schema_using_join_columns(
+    plan: &LogicalPlan,
+    schema: &DFSchema,
+) -> Result<Vec<(JoinType, Column, Column)>> {
+    Ok(plan
+        .using_join_columns()?
         .into_iter()
-        // For each USING JOIN condition, only expand to one column in projection
-        .flat_map(|cols| {
-            let mut cols = cols.into_iter().collect::<Vec<_>>();
-            // sort join columns to make sure we consistently keep the same
-            // qualified column
-            cols.sort();
-            cols.into_iter().skip(1)
+        .filter(|(_, left, right)| {
+            schema.field_from_column(left).is_ok()
+                && schema.field_from_column(right).is_ok()
         })
-        .collect::<HashSet<_>>();
+        .collect())
+}
 
-    if columns_to_skip.is_empty() {
-        Ok(schema
-            .fields()
-            .iter()
-            .map(|f| Expr::Column(f.qualified_column()))
-            .collect::<Vec<Expr>>())
-    } else {
-        Ok(schema
-            .fields()
+/// Returns the value of the column `col` is joined on by the outermost of
+/// the `using_join_columns` joining it, with all the columns joined with it.
+///
+/// The inputs of a join may themselves be joined on the column, such as the
+/// left input of `t1 LEFT JOIN t2 USING (id) FULL JOIN t3 USING (id)`, whose
+/// value is then the one of the inner join, see [`using_join_column_expr`]
+fn using_join_column_value(
+    using_join_columns: &[(JoinType, Column, Column)],
+    col: &Column,
+) -> Option<(Expr, HashSet<Column>)> {
+    // the pairs are listed from the outermost join in
+    (0..using_join_columns.len()).find_map(|idx| {
+        let mut columns = HashSet::new();
+        let value = joined_column_value(using_join_columns, idx, &mut columns);
+        columns.contains(col).then_some((value, columns))
+    })
+}
+
+/// Returns the value of the column joined on by the `idx`th of the
+/// `using_join_columns`, collecting the columns it is made of
+fn joined_column_value(
+    using_join_columns: &[(JoinType, Column, Column)],
+    idx: usize,
+    columns: &mut HashSet<Column>,
+) -> Expr {
+    let (join_type, left, right) = &using_join_columns[idx];
+    let mut input_value = |col: &Column| {
+        columns.insert(col.clone());
+        // the joins of an input are listed after the join of the inputs
+        match using_join_columns[idx + 1..]
             .iter()
-            .filter_map(|f| {
-                let col = f.qualified_column();
-                if !columns_to_skip.contains(&col) {
-                    Some(Expr::Column(col))
-                } else {
-                    None
+            .position(|(_, inner_left, _)| inner_left == col)
+        {
+            Some(inner) => {
+                joined_column_value(using_join_columns, idx + 1 + inner, columns)
+            }
+            None => Expr::Column(col.clone()),
+        }
+    };
+    let left = input_value(left);
+    let right = input_value(right);
+    using_join_column_expr(*join_type, left, right)
+}
+
+/// Returns the value of the column named `name` that the `Using` joins of
+/// `plan` join on, if all the columns of the schema of `plan` with that name
+/// are joined on, see [`using_join_column_expr`]
+pub fn find_using_join_column(plan: &LogicalPlan, name: &str) -> Result<Option<Expr>> {
+    let schema = plan.schema();
+    let using_join_columns = schema_using_join_columns(plan, schema)?;
+    Ok(find_schema_using_join_column(
+        &using_join_columns,
+        schema,
+        name,
+    ))
+}
+
+fn find_schema_using_join_column(
+    using_join_columns: &[(JoinType, Column, Column)],
+    schema: &DFSchema,
+    name: &str,
+) -> Option<Expr> {
+    let fields = schema.fields_with_unqualified_name(name);
+    if fields.len() < 2 {
+        return None;
+    }
+    let (value, columns) =
+        using_join_column_value(using_join_columns, &fields[0].qualified_column())?;
+    fields
+        .iter()
+        .all(|f| columns.contains(&f.qualified_column()))
+        .then_some(value)
+}
+
+/// Recursively replaces the unqualified columns in `expr` that name the
+/// columns the `Using` joins of `plan` join on by their value, see
+/// [`find_using_join_column`]
+pub fn resolve_using_join_columns(expr: Expr, plan: &LogicalPlan) -> Result<Expr> {
+    struct UsingJoinColumnResolver<'a> {
+        using_join_columns: &'a [(JoinType, Column, Column)],
+        schema: &'a DFSchema,
+    }
+
+    impl<'a> ExprRewriter for UsingJoinColumnResolver<'a> {
+        fn mutate(&mut self, expr: Expr) -> Result<Expr> {
+            if let Expr::Column(Column {
+                relation: None,
+                name,
+            }) = &expr
+            {
+                if let Some(value) = find_schema_using_join_column(
+                    self.using_join_columns,
+                    self.schema,
+                    name,
+                ) {
+                    return Ok(value);
                 }
-            })
-            .collect::<Vec<Expr>>())
+            }
+            Ok(expr)
+        }
     }
+
+    let schema = plan.schema();
+    let using_join_columns = schema_using_join_columns(plan, schema)?;
+    if using_join_columns.is_empty() {
+        return Ok(expr);
+    }
+    expr.rewrite(&mut UsingJoinColumnResolver {
+        using_join_columns: &using_join_columns,
+        schema,
+    })
 }
 
 /// Resolves an `Expr::Wildcard` to a collection of qualified `Expr::Column`'s.
@@ -525,12 +658,14 @@ pub fn from_plan(
             name,
             if_not_exists,
             or_replace,
+            temporary,
             ..
         }) => Ok(LogicalPlan::CreateMemoryTable(CreateMemoryTable {
             input: Arc::new(inputs[0].clone()),
             name: name.clone(),
             if_not_exists: *if_not_exists,
             or_replace: *or_replace,
+            temporary: *temporary,
         })),
         LogicalPlan::CreateView(CreateView {
             name,
@@ -770,6 +905,8 @@ pub fn can_hash(data_type: &DataType) -> bool {
         {
             DataType::is_dictionary_key_type(key_type)
         }
+        DataType::List(field) => can_hash(field.data_type()),
+        DataType::Struct(fields) => fields.iter().all(|f| can_hash(f.data_type())),
         _ => false,
     }
 }
@@ -41,12 +41,12 @@ impl FromStr for WindowFunction {
     type Err = DataFusionError;
     fn from_str(name: &str) -> Result<WindowFunction> {
         let name = name.to_lowercase();
-        if let Ok(aggregate) = AggregateFunction::from_str(name.as_str()) {
-            Ok(WindowFunction::AggregateFunction(aggregate))
-        } else if let Ok(built_in_function) =
-            BuiltInWindowFunction::from_str(name.as_str())
-        {
+        // built-in window functions take precedence over the aggregates of
+        // the same name, such as `first_value`
+        if let Ok(built_in_function) = BuiltInWindowFunction::from_str(name.as_str()) {
             Ok(WindowFunction::BuiltInWindowFunction(built_in_function))
+        } else if let Ok(aggregate) = AggregateFunction::from_str(name.as_str()) {
+            Ok(WindowFunction::AggregateFunction(aggregate))
         } else {
             Err(DataFusionError::Plan(format!(
                 "There is no window function named {}",
@@ -41,7 +41,10 @@ impl OptimizerRule for FilterNullJoinKeys {
         optimizer_config: &mut OptimizerConfig,
     ) -> datafusion_common::Result<LogicalPlan> {
         match plan {
-            LogicalPlan::Join(join) if join.join_type == JoinType::Inner => {
+            // null keys match each other if nulls are equal
+            LogicalPlan::Join(join)
+                if join.join_type == JoinType::Inner && !join.null_equals_null =>
+            {
                 // recurse down first and optimize inputs
                 let mut join = join.clone();
                 join.left = Arc::new(self.optimize(&join.left, optimizer_config)?);
@@ -217,6 +220,28 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn null_equals_null_not_filtered() -> Result<()> {
+        let (t1, t2) = test_tables()?;
+        let plan = LogicalPlanBuilder::from(t1)
+            .join_detailed(
+                &t2,
+                JoinType::Inner,
+                (
+                    vec![Column::from_qualified_name("t1.optional_id")],
+                    vec![Column::from_qualified_name("t2.optional_id")],
+                ),
+                None,
+                true,
+            )?
+            .build()?;
+        let expected = "Inner Join: t1.optional_id = t2.optional_id NullEqualsNull\
+        \n  TableScan: t1\
+        \n  TableScan: t2";
+        assert_optimized_plan_eq(&plan, expected);
+        Ok(())
+    }
+
     fn build_plan(
         left_table: LogicalPlan,
         right_table: LogicalPlan,
@@ -742,10 +742,10 @@ fn optimize(plan: &LogicalPlan, mut state: State) -> Result<LogicalPlan> {
             table_name,
             fetch,
         }) => {
-            let mut used_columns = HashSet::new();
+            let mut preserved_filters = vec![];
             let mut new_filters = filters.clone();
 
-            for (filter_expr, cols) in &state.filters {
+            for (filter_expr, _) in &state.filters {
                 let (preserve_filter_node, add_to_provider) =
                     match source.supports_filter_pushdown(filter_expr)? {
                         TableProviderFilterPushDown::Unsupported => (true, false),
@@ -753,8 +753,11 @@ fn optimize(plan: &LogicalPlan, mut state: State) -> Result<LogicalPlan> {
                         TableProviderFilterPushDown::Exact => (false, true),
                     };
 
+                // decide per filter rather than per column, so that an exact
+                // filter is not re-applied because an inexact or unsupported
+                // one happens to reference the same columns
                 if preserve_filter_node {
-                    used_columns.extend(cols.clone());
+                    preserved_filters.push(filter_expr);
                 }
 
                 if add_to_provider {
@@ -767,18 +770,21 @@ fn optimize(plan: &LogicalPlan, mut state: State) -> Result<LogicalPlan> {
                 }
             }
 
-            issue_filters(
-                state,
-                used_columns,
-                &LogicalPlan::TableScan(TableScan {
-                    source: source.clone(),
-                    projection: projection.clone(),
-                    projected_schema: projected_schema.clone(),
-                    table_name: table_name.clone(),
-                    filters: new_filters,
-                    fetch: *fetch,
-                }),
-            )
+            let plan = LogicalPlan::TableScan(TableScan {
+                source: source.clone(),
+                projection: projection.clone(),
+                projected_schema: projected_schema.clone(),
+                table_name: table_name.clone(),
+                filters: new_filters,
+                fetch: *fetch,
+            });
+
+            // a table scan has no inputs to push the remaining filters to
+            if preserved_filters.is_empty() {
+                Ok(plan)
+            } else {
+                utils::add_filter(plan, &preserved_filters)
+            }
         }
         _ => {
             // all other plans are _not_ filter-commutable
@@ -2070,6 +2076,66 @@ mod tests {
         Ok(())
     }
 
+    /// Handles equality filters exactly and all other filters inexactly
+    struct ExactEqualityProvider;
+
+    #[async_trait]
+    impl TableSource for ExactEqualityProvider {
+        fn schema(&self) -> SchemaRef {
+            PushDownProvider {
+                filter_support: TableProviderFilterPushDown::Exact,
+            }
+            .schema()
+        }
+
+        fn table_type(&self) -> TableType {
+            TableType::Base
+        }
+
+        fn supports_filter_pushdown(
+            &self,
+            filter: &Expr,
+        ) -> Result<TableProviderFilterPushDown> {
+            Ok(match filter {
+                Expr::BinaryExpr(BinaryExpr {
+                    op: Operator::Eq, ..
+                }) => TableProviderFilterPushDown::Exact,
+                _ => TableProviderFilterPushDown::Inexact,
+            })
+        }
+
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+    }
+
+    #[test]
+    fn filter_with_table_provider_mixed_support_on_same_column() -> Result<()> {
+        let test_provider = ExactEqualityProvider;
+        let table_scan = LogicalPlan::TableScan(TableScan {
+            table_name: "test".to_string(),
+            filters: vec![],
+            projected_schema: Arc::new(DFSchema::try_from(
+                (*test_provider.schema()).clone(),
+            )?),
+            projection: None,
+            source: Arc::new(test_provider),
+            fetch: None,
+        });
+
+        let plan = LogicalPlanBuilder::from(table_scan)
+            .filter(and(col("a").eq(lit(1i64)), col("a").gt(lit(0i64))))?
+            .build()?;
+
+        // the exact filter is not re-applied, even though the inexact one
+        // references the same column
+        let expected = "\
+        Filter: a > Int64(0)\
+        \n  TableScan: test, full_filters=[a = Int64(1)], partial_filters=[a > Int64(0)]";
+        assert_optimized_plan_eq(&plan, expected);
+        Ok(())
+    }
+
     #[test]
     fn test_filter_with_alias() -> Result<()> {
         // in table scan the true col name is 'test.a',
@@ -75,6 +75,19 @@ pub struct OptimizerConfig {
     filter_null_keys: bool,
     /// Maximum number of times to run optimizer against a plan
     max_passes: u8,
+    /// Whether arithmetic that overflows or divides by zero yields null
+    /// rather than an error when folding constants
+    lenient_arithmetic: bool,
+    /// Whether integer arithmetic wraps around on overflow rather than
+    /// failing when folding constants
+    wrapping_arithmetic: bool,
+    /// Whether dividing by zero fails rather than yields null when folding
+    /// constants
+    checked_division: bool,
+    /// The names of the default catalog and schema of the session, used as
+    /// the values of `current_catalog()` and `current_schema()`
+    current_catalog: String,
+    current_schema: String,
 }
 
 impl OptimizerConfig {
@@ -86,6 +99,11 @@ impl OptimizerConfig {
             skip_failing_rules: true,
             filter_null_keys: true,
             max_passes: 3,
+            lenient_arithmetic: false,
+            wrapping_arithmetic: false,
+            checked_division: false,
+            current_catalog: "datafusion".to_owned(),
+            current_schema: "public".to_owned(),
         }
     }
 
@@ -118,6 +136,37 @@ impl OptimizerConfig {
         self
     }
 
+    /// Specify whether arithmetic that overflows or divides by zero yields
+    /// null rather than an error
+    pub fn with_lenient_arithmetic(mut self, lenient_arithmetic: bool) -> Self {
+        self.lenient_arithmetic = lenient_arithmetic;
+        self
+    }
+
+    /// Specify whether integer arithmetic wraps around on overflow rather
+    /// than failing
+    pub fn with_wrapping_arithmetic(mut self, wrapping_arithmetic: bool) -> Self {
+        self.wrapping_arithmetic = wrapping_arithmetic;
+        self
+    }
+
+    /// Specify whether dividing by zero fails rather than yields null
+    pub fn with_checked_division(mut self, checked_division: bool) -> Self {
+        self.checked_division = checked_division;
+        self
+    }
+
+    /// Specify the names of the default catalog and schema of the session
+    pub fn with_current_catalog_and_schema(
+        mut self,
+        catalog: impl Into<String>,
+        schema: impl Into<String>,
+    ) -> Self {
+        self.current_catalog = catalog.into();
+        self.current_schema = schema.into();
+        self
+    }
+
     /// Generate the next ID needed
     pub fn next_id(&mut self) -> usize {
         self.next_id += 1;
@@ -129,6 +178,32 @@ impl OptimizerConfig {
     pub fn query_execution_start_time(&self) -> DateTime<Utc> {
         self.query_execution_start_time
     }
+
+    /// Does arithmetic that overflows or divides by zero yield null rather
+    /// than an error
+    pub fn lenient_arithmetic(&self) -> bool {
+        self.lenient_arithmetic
+    }
+
+    /// Does integer arithmetic wrap around on overflow rather than fail
+    pub fn wrapping_arithmetic(&self) -> bool {
+        self.wrapping_arithmetic
+    }
+
+    /// Does dividing by zero fail rather than yield null
+    pub fn checked_division(&self) -> bool {
+        self.checked_division
+    }
+
+    /// Return the name of the default catalog of the session
+    pub fn current_catalog(&self) -> &str {
+        &self.current_catalog
+    }
+
+    /// Return the name of the default schema of the session
+    pub fn current_schema(&self) -> &str {
+        &self.current_schema
+    }
 }
 
 impl Default for OptimizerConfig {
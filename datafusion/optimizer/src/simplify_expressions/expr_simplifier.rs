@@ -22,7 +22,6 @@ use crate::type_coercion::TypeCoercionRewriter;
 use arrow::{
     array::new_null_array,
     datatypes::{DataType, Field, Schema},
-    error::ArrowError,
     record_batch::RecordBatch,
 };
 use datafusion_common::{DFSchema, DFSchemaRef, DataFusionError, Result, ScalarValue};
@@ -190,7 +189,12 @@ impl<'a> ExprRewriter for ConstEvaluator<'a> {
 
     fn mutate(&mut self, expr: Expr) -> Result<Expr> {
         match self.can_evaluate.pop() {
-            Some(true) => Ok(Expr::Literal(self.evaluate_to_scalar(expr)?)),
+            // An expression that fails to evaluate is left in place, as it
+            // might never be evaluated at runtime, e.g. `COALESCE(a, 1 / 0)`
+            Some(true) => match self.evaluate_to_scalar(expr.clone()) {
+                Ok(scalar) => Ok(Expr::Literal(scalar)),
+                Err(_) => Ok(expr),
+            },
             Some(false) => Ok(expr),
             _ => Err(DataFusionError::Internal(
                 "Failed to pop can_evaluate".to_string(),
@@ -620,14 +624,6 @@ impl<'a, S: SimplifyInfo> ExprRewriter for Simplifier<'a, S> {
             }) if is_zero(&left) && is_zero(&right) => {
                 Expr::Literal(ScalarValue::Int32(None))
             }
-            // A / 0 -> DivideByZero Error
-            Expr::BinaryExpr(BinaryExpr {
-                left,
-                op: Divide,
-                right,
-            }) if !info.nullable(&left)? && is_zero(&right) => {
-                return Err(DataFusionError::ArrowError(ArrowError::DivideByZero));
-            }
 
             //
             // Rules for Modulo
@@ -651,14 +647,6 @@ impl<'a, S: SimplifyInfo> ExprRewriter for Simplifier<'a, S> {
                 op: Modulo,
                 right,
             }) if !info.nullable(&left)? && is_one(&right) => lit(0),
-            // A % 0 --> DivideByZero Error
-            Expr::BinaryExpr(BinaryExpr {
-                left,
-                op: Modulo,
-                right,
-            }) if !info.nullable(&left)? && is_zero(&right) => {
-                return Err(DataFusionError::ArrowError(ArrowError::DivideByZero));
-            }
 
             //
             // Rules for Not
@@ -865,6 +853,12 @@ mod tests {
     ) {
         let execution_props = ExecutionProps {
             query_execution_start_time: *date_time,
+            fixed_query_execution_start_time: None,
+            lenient_arithmetic: false,
+            wrapping_arithmetic: false,
+            checked_division: false,
+            current_catalog: "datafusion".to_owned(),
+            current_schema: "public".to_owned(),
             var_providers: None,
         };
 
@@ -946,6 +940,16 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_const_evaluator_keeps_failing_exprs() {
+        // coalesce(c, 1 / 0) --> coalesce(c, 1 / 0), the division may never run
+        let expr = coalesce(vec![col("c"), lit(1) / lit(0)]);
+        test_evaluate(expr.clone(), expr);
+        // coalesce(c, 1 / 0 + 2) --> coalesce(c, 1 / 0 + 2)
+        let expr = coalesce(vec![col("c"), lit(1) / lit(0) + lit(2)]);
+        test_evaluate(expr.clone(), expr);
+    }
+
     #[test]
     fn test_const_evaluator_scalar_functions() {
         // concat("foo", "bar") --> "foobar"
@@ -1233,14 +1237,12 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(
-        expected = "called `Result::unwrap()` on an `Err` value: ArrowError(DivideByZero)"
-    )]
     fn test_simplify_divide_by_zero() {
-        // A / 0 -> DivideByZeroError
+        // A / 0 is left for the execution to report, as it may never run
         let expr = binary_expr(col("c2_non_null"), Operator::Divide, lit(0));
+        let expected = expr.clone();
 
-        simplify(expr);
+        assert_eq!(simplify(expr), expected);
     }
 
     #[test]
@@ -1281,12 +1283,11 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(
-        expected = "called `Result::unwrap()` on an `Err` value: ArrowError(DivideByZero)"
-    )]
     fn test_simplify_modulo_by_zero_non_null() {
         let expr = binary_expr(col("c2_non_null"), Operator::Modulo, lit(0));
-        simplify(expr);
+        let expected = expr.clone();
+
+        assert_eq!(simplify(expr), expected);
     }
 
     #[test]
@@ -51,6 +51,11 @@ impl OptimizerRule for SimplifyExpressions {
         let mut execution_props = ExecutionProps::new();
         execution_props.query_execution_start_time =
             optimizer_config.query_execution_start_time();
+        execution_props.lenient_arithmetic = optimizer_config.lenient_arithmetic();
+        execution_props.wrapping_arithmetic = optimizer_config.wrapping_arithmetic();
+        execution_props.checked_division = optimizer_config.checked_division();
+        execution_props.current_catalog = optimizer_config.current_catalog().to_owned();
+        execution_props.current_schema = optimizer_config.current_schema().to_owned();
         Self::optimize_internal(plan, &execution_props)
     }
 }
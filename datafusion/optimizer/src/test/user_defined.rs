@@ -25,6 +25,7 @@ use datafusion_expr::{
 use std::{
     any::Any,
     fmt::{self, Debug},
+    hash::Hasher,
     sync::Arc,
 };
 
@@ -76,4 +77,10 @@ impl UserDefinedLogicalNode for TestUserDefinedPlanNode {
             input: inputs[0].clone(),
         })
     }
+
+    fn dyn_hash(&self, _state: &mut dyn Hasher) {}
+
+    fn dyn_eq(&self, other: &dyn UserDefinedLogicalNode) -> bool {
+        other.as_any().is::<Self>()
+    }
 }
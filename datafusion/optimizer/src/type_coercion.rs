@@ -19,7 +19,7 @@
 
 use crate::utils::rewrite_preserving_name;
 use crate::{OptimizerConfig, OptimizerRule};
-use arrow::datatypes::{DataType, IntervalUnit};
+use arrow::datatypes::{DataType, IntervalUnit, TimeUnit, DECIMAL128_MAX_PRECISION};
 use datafusion_common::{
     parse_interval, DFSchema, DFSchemaRef, DataFusionError, Result, ScalarValue,
 };
@@ -41,6 +41,8 @@ use datafusion_expr::{
 use datafusion_expr::{ExprSchemable, Signature};
 use std::sync::Arc;
 
+const NANOSECONDS_PER_DAY: i64 = 24 * 60 * 60 * 1_000_000_000;
+
 #[derive(Default)]
 pub struct TypeCoercion {}
 
@@ -188,6 +190,22 @@ impl ExprRewriter for TypeCoercionRewriter {
                 let expr = Expr::ILike(Like::new(negated, expr, pattern, escape_char));
                 Ok(expr)
             }
+            Expr::SimilarTo(Like {
+                negated,
+                expr,
+                pattern,
+                escape_char,
+            }) => {
+                let left_type = expr.get_type(&self.schema)?;
+                let right_type = pattern.get_type(&self.schema)?;
+                let coerced_type =
+                    coerce_types(&left_type, &Operator::Like, &right_type)?;
+                let expr = Box::new(expr.cast_to(&coerced_type, &self.schema)?);
+                let pattern = Box::new(pattern.cast_to(&coerced_type, &self.schema)?);
+                let expr =
+                    Expr::SimilarTo(Like::new(negated, expr, pattern, escape_char));
+                Ok(expr)
+            }
             Expr::IsUnknown(expr) => {
                 // will convert the binary(expr,IsNotDistinctFrom,lit(Boolean(None));
                 let left_type = expr.get_type(&self.schema)?;
@@ -213,6 +231,26 @@ impl ExprRewriter for TypeCoercionRewriter {
             }) => {
                 let left_type = left.get_type(&self.schema)?;
                 let right_type = right.get_type(&self.schema)?;
+                if matches!(
+                    op,
+                    Operator::Eq
+                        | Operator::NotEq
+                        | Operator::Lt
+                        | Operator::LtEq
+                        | Operator::Gt
+                        | Operator::GtEq
+                        | Operator::IsDistinctFrom
+                        | Operator::IsNotDistinctFrom
+                ) {
+                    if let Some(right) = parse_compared_string(right, &left_type)? {
+                        let expr = BinaryExpr::new(left.clone(), op, Box::new(right));
+                        return self.mutate(Expr::BinaryExpr(expr));
+                    }
+                    if let Some(left) = parse_compared_string(left, &right_type)? {
+                        let expr = BinaryExpr::new(Box::new(left), op, right.clone());
+                        return self.mutate(Expr::BinaryExpr(expr));
+                    }
+                }
                 match (&left_type, &right_type) {
                     (
                         DataType::Date32 | DataType::Date64 | DataType::Timestamp(_, _),
@@ -239,6 +277,12 @@ impl ExprRewriter for TypeCoercionRewriter {
                 high,
             }) => {
                 let expr_type = expr.get_type(&self.schema)?;
+                let low = parse_compared_string(&low, &expr_type)?
+                    .map(Box::new)
+                    .unwrap_or(low);
+                let high = parse_compared_string(&high, &expr_type)?
+                    .map(Box::new)
+                    .unwrap_or(high);
                 let low_type = low.get_type(&self.schema)?;
                 let low_coerced_type = comparison_coercion(&expr_type, &low_type)
                     .ok_or_else(|| {
@@ -248,7 +292,7 @@ impl ExprRewriter for TypeCoercionRewriter {
                         ))
                     })?;
                 let high_type = high.get_type(&self.schema)?;
-                let high_coerced_type = comparison_coercion(&expr_type, &low_type)
+                let high_coerced_type = comparison_coercion(&expr_type, &high_type)
                     .ok_or_else(|| {
                         DataFusionError::Internal(format!(
                             "Failed to coerce types {} and {} in BETWEEN expression",
@@ -423,6 +467,68 @@ impl ExprRewriter for TypeCoercionRewriter {
     }
 }
 
+/// Parses `expr`, if it is a string literal compared to an expression of the
+/// temporal or numeric `other_type`, as a literal of `other_type`, so that a
+/// string which is not a valid value fails the planning instead of every row.
+///
+/// The strings compared to timestamps are parsed in nanoseconds, and those
+/// compared to decimals keep all their fractional digits: the comparison
+/// then casts them like any other literal of the same kind.
+fn parse_compared_string(expr: &Expr, other_type: &DataType) -> Result<Option<Expr>> {
+    let value = match expr {
+        Expr::Literal(
+            ScalarValue::Utf8(Some(value)) | ScalarValue::LargeUtf8(Some(value)),
+        ) => value,
+        _ => return Ok(None),
+    };
+
+    let parsed = match other_type {
+        DataType::Date32 | DataType::Date64 => {
+            ScalarValue::try_from_string(value.clone(), other_type)
+        }
+        DataType::Timestamp(_, _) => {
+            let timestamp_type = DataType::Timestamp(TimeUnit::Nanosecond, None);
+            ScalarValue::try_from_string(value.clone(), &timestamp_type).or_else(|e| {
+                // a date is the timestamp of its midnight
+                match ScalarValue::try_from_string(value.clone(), &DataType::Date32) {
+                    Ok(ScalarValue::Date32(Some(days))) => {
+                        Ok(ScalarValue::TimestampNanosecond(
+                            Some(days as i64 * NANOSECONDS_PER_DAY),
+                            None,
+                        ))
+                    }
+                    _ => Err(e),
+                }
+            })
+        }
+        DataType::Decimal128(precision, scale) => {
+            let fraction_digits = value
+                .trim()
+                .split_once('.')
+                .map(|(_, fraction)| fraction.len())
+                .unwrap_or(0)
+                .min(DECIMAL128_MAX_PRECISION as usize)
+                as u8;
+            let literal_scale = fraction_digits.max(*scale);
+            let literal_precision =
+                (precision + literal_scale - scale).min(DECIMAL128_MAX_PRECISION);
+            let literal_type = DataType::Decimal128(literal_precision, literal_scale);
+            ScalarValue::try_from_string(value.clone(), &literal_type)
+        }
+        _ if is_numeric(other_type) => {
+            ScalarValue::try_from_string(value.clone(), other_type)
+        }
+        _ => return Ok(None),
+    };
+
+    parsed.map(|value| Some(Expr::Literal(value))).map_err(|e| {
+        DataFusionError::Plan(format!(
+            "Cannot compare {} with '{}', which is not a valid {}: {}",
+            other_type, value, other_type, e
+        ))
+    })
+}
+
 /// Casts the ScalarValue `value` to coerced type.
 // When coerced type is `Interval` we use `parse_interval` since `try_from_string` not
 // supports conversion from string to Interval
@@ -582,9 +688,9 @@ fn coerce_agg_exprs_for_signature(
 mod test {
     use crate::type_coercion::{TypeCoercion, TypeCoercionRewriter};
     use crate::{OptimizerConfig, OptimizerRule};
-    use arrow::datatypes::DataType;
+    use arrow::datatypes::{DataType, TimeUnit};
     use datafusion_common::{DFField, DFSchema, Result, ScalarValue};
-    use datafusion_expr::expr::Like;
+    use datafusion_expr::expr::{Between, Like};
     use datafusion_expr::expr_rewriter::ExprRewritable;
     use datafusion_expr::{
         cast, col, concat, concat_ws, create_udaf, is_true,
@@ -1013,6 +1119,103 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn between_for_type_coercion() -> Result<()> {
+        // the high bound takes part in finding the common type
+        let expr = Expr::Between(Between::new(
+            Box::new(col("a")),
+            false,
+            Box::new(lit(1i64)),
+            Box::new(lit(2.5f64)),
+        ));
+        let empty = empty_with_type(DataType::Int32);
+        let plan = LogicalPlan::Projection(Projection::try_new(vec![expr], empty, None)?);
+        let rule = TypeCoercion::new();
+        let mut config = OptimizerConfig::default();
+        let plan = rule.optimize(&plan, &mut config)?;
+        assert_eq!(
+            "Projection: CAST(a AS Float64) BETWEEN CAST(Int64(1) AS Float64) AND Float64(2.5) AS a BETWEEN Int64(1) AND Float64(2.5)\
+             \n  EmptyRelation",
+            &format!("{:?}", plan)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn compared_string_for_type_coercion() -> Result<()> {
+        let coerce = |data_type: DataType, expr: Expr| {
+            let empty = empty_with_type(data_type);
+            let plan =
+                LogicalPlan::Projection(Projection::try_new(vec![expr], empty, None)?);
+            let rule = TypeCoercion::new();
+            let mut config = OptimizerConfig::default();
+            rule.optimize(&plan, &mut config)
+                .map(|plan| format!("{:?}", plan))
+        };
+
+        // date
+        let plan = coerce(DataType::Date32, col("a").gt(lit("2022-05-01")))?;
+        assert_eq!(
+            "Projection: a > Date32(\"19113\") AS a > Utf8(\"2022-05-01\")\
+             \n  EmptyRelation",
+            plan
+        );
+
+        // timestamp with fractional seconds, parsed in nanoseconds
+        let expr = lit("2022-05-01 10:20:30.123456").lt_eq(col("a"));
+        let plan = coerce(
+            DataType::Timestamp(TimeUnit::Millisecond, None),
+            expr.clone(),
+        )?;
+        assert_eq!(
+            "Projection: CAST(TimestampNanosecond(1651400430123456000, None) AS Timestamp(Millisecond, None)) <= a \
+             AS Utf8(\"2022-05-01 10:20:30.123456\") <= a\
+             \n  EmptyRelation",
+            plan
+        );
+        let plan = coerce(DataType::Timestamp(TimeUnit::Nanosecond, None), expr)?;
+        assert_eq!(
+            "Projection: TimestampNanosecond(1651400430123456000, None) <= a \
+             AS Utf8(\"2022-05-01 10:20:30.123456\") <= a\
+             \n  EmptyRelation",
+            plan
+        );
+
+        // decimal, keeping the digits beyond the scale of the column
+        let plan = coerce(DataType::Decimal128(10, 2), col("a").eq(lit("12.345")))?;
+        assert_eq!(
+            "Projection: CAST(a AS Decimal128(11, 3)) = Decimal128(Some(12345),11,3) \
+             AS a = Utf8(\"12.345\")\
+             \n  EmptyRelation",
+            plan
+        );
+
+        // integer
+        let plan = coerce(DataType::Int32, col("a").eq(lit("42")))?;
+        assert_eq!(
+            "Projection: a = Int32(42) AS a = Utf8(\"42\")\n  EmptyRelation",
+            plan
+        );
+
+        // strings that are not values of the compared type fail the planning
+        let err = coerce(DataType::Int32, col("a").eq(lit("forty two"))).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("Error during planning: Cannot compare Int32 with 'forty two'"));
+        let err = coerce(DataType::Date32, col("a").lt(lit("2022-13-01"))).unwrap_err();
+        assert!(err.to_string().starts_with(
+            "Error during planning: Cannot compare Date32 with '2022-13-01'"
+        ));
+
+        // string columns are compared as strings
+        let plan = coerce(DataType::Utf8, col("a").eq(lit(42i32)))?;
+        assert_eq!(
+            "Projection: a = CAST(Int32(42) AS Utf8)\n  EmptyRelation",
+            plan
+        );
+        Ok(())
+    }
+
     #[test]
     fn unknown_for_type_coercion() -> Result<()> {
         // unknown
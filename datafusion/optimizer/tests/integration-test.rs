@@ -163,9 +163,9 @@ fn intersect() -> Result<()> {
     INTERSECT SELECT col_int32, col_utf8 FROM test";
     let plan = test_sql(sql)?;
     let expected =
-        "LeftSemi Join: test.col_int32 = test.col_int32, test.col_utf8 = test.col_utf8\
+        "LeftSemi Join: test.col_int32 = test.col_int32, test.col_utf8 = test.col_utf8 NullEqualsNull\
     \n  Distinct:\
-    \n    LeftSemi Join: test.col_int32 = test.col_int32, test.col_utf8 = test.col_utf8\
+    \n    LeftSemi Join: test.col_int32 = test.col_int32, test.col_utf8 = test.col_utf8 NullEqualsNull\
     \n      Distinct:\
     \n        TableScan: test projection=[col_int32, col_utf8]\
     \n      TableScan: test projection=[col_int32, col_utf8]\
@@ -0,0 +1,82 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use ahash::RandomState;
+use arrow::array::{ArrayRef, Float64Array, Int64Array, StringArray};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use datafusion_physical_expr::hash_utils::create_hashes;
+use rand::distributions::Alphanumeric;
+use rand::prelude::*;
+use std::sync::Arc;
+
+const BATCH_SIZE: usize = 8192;
+
+fn random_columns(rng: &mut StdRng, null_percent: f64) -> Vec<ArrayRef> {
+    let ints: Int64Array = (0..BATCH_SIZE)
+        .map(|_| (!rng.gen_bool(null_percent)).then(|| rng.gen_range(0..1000)))
+        .collect();
+    let strings: StringArray = (0..BATCH_SIZE)
+        .map(|_| {
+            (!rng.gen_bool(null_percent)).then(|| {
+                let value = (&mut *rng).sample_iter(&Alphanumeric).take(10).collect();
+                String::from_utf8(value).unwrap()
+            })
+        })
+        .collect();
+    let floats: Float64Array = (0..BATCH_SIZE)
+        .map(|_| (!rng.gen_bool(null_percent)).then(|| rng.gen::<f64>()))
+        .collect();
+    vec![Arc::new(ints), Arc::new(strings), Arc::new(floats)]
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(42);
+    let random_state = RandomState::with_seeds(0, 0, 0, 0);
+
+    for null_percent in [0.0, 0.1] {
+        let columns = random_columns(&mut rng, null_percent);
+        for num_columns in 1..=columns.len() {
+            let columns = &columns[..num_columns];
+            let name = format!(
+                "create_hashes {} columns, {}% nulls",
+                num_columns,
+                null_percent * 100.0
+            );
+
+            c.bench_function(&format!("{}, new buffer", name), |b| {
+                b.iter(|| {
+                    let mut hashes_buffer = vec![0; BATCH_SIZE];
+                    create_hashes(columns, &random_state, &mut hashes_buffer).unwrap();
+                    black_box(hashes_buffer)
+                })
+            });
+
+            let mut hashes_buffer = vec![];
+            c.bench_function(&format!("{}, reused buffer", name), |b| {
+                b.iter(|| {
+                    hashes_buffer.clear();
+                    hashes_buffer.resize(BATCH_SIZE, 0);
+                    create_hashes(columns, &random_state, &mut hashes_buffer).unwrap();
+                    black_box(&hashes_buffer);
+                })
+            });
+        }
+    }
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,174 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines the ARRAY_AGG aggregate expression ordered by a key
+
+use crate::aggregate::first_last::OrderKey;
+use crate::expressions::format_state_name;
+use crate::{AggregateExpr, PhysicalExpr};
+use arrow::array::ArrayRef;
+use arrow::datatypes::{DataType, Field};
+use datafusion_common::{DataFusionError, Result, ScalarValue};
+use datafusion_expr::{Accumulator, AggregateState};
+use std::any::Any;
+use std::sync::Arc;
+
+/// ARRAY_AGG aggregate expression whose values are ordered by a key
+#[derive(Debug)]
+pub struct OrderedArrayAgg {
+    name: String,
+    input_data_type: DataType,
+    expr: Arc<dyn PhysicalExpr>,
+    order_key: OrderKey,
+}
+
+impl OrderedArrayAgg {
+    /// Create a new OrderedArrayAgg aggregate function
+    pub fn new(
+        expr: Arc<dyn PhysicalExpr>,
+        name: impl Into<String>,
+        data_type: DataType,
+        order_key: OrderKey,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            input_data_type: data_type,
+            expr,
+            order_key,
+        }
+    }
+}
+
+impl AggregateExpr for OrderedArrayAgg {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn field(&self) -> Result<Field> {
+        Ok(Field::new(
+            &self.name,
+            DataType::List(Box::new(Field::new(
+                "item",
+                self.input_data_type.clone(),
+                true,
+            ))),
+            false,
+        ))
+    }
+
+    fn create_accumulator(&self) -> Result<Box<dyn Accumulator>> {
+        Ok(Box::new(OrderedArrayAggAccumulator {
+            values: vec![],
+            keys: vec![],
+            data_type: self.input_data_type.clone(),
+            order_key: self.order_key.clone(),
+        }))
+    }
+
+    fn state_fields(&self) -> Result<Vec<Field>> {
+        // the values and their keys, in the order they were read
+        let list = |data_type: &DataType| {
+            DataType::List(Box::new(Field::new("item", data_type.clone(), true)))
+        };
+        Ok(vec![
+            Field::new(
+                &format_state_name(&self.name, "array_agg"),
+                list(&self.input_data_type),
+                false,
+            ),
+            Field::new(
+                &format_state_name(&self.name, "keys"),
+                list(&self.order_key.data_type),
+                false,
+            ),
+        ])
+    }
+
+    fn expressions(&self) -> Vec<Arc<dyn PhysicalExpr>> {
+        vec![self.expr.clone(), self.order_key.expr.clone()]
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+#[derive(Debug)]
+struct OrderedArrayAggAccumulator {
+    values: Vec<ScalarValue>,
+    keys: Vec<ScalarValue>,
+    data_type: DataType,
+    order_key: OrderKey,
+}
+
+impl Accumulator for OrderedArrayAggAccumulator {
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        let (array, keys) = (&values[0], &values[1]);
+        (0..array.len()).try_for_each(|index| {
+            self.values.push(ScalarValue::try_from_array(array, index)?);
+            self.keys.push(ScalarValue::try_from_array(keys, index)?);
+            Ok(())
+        })
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<()> {
+        let (lists, keys) = (&states[0], &states[1]);
+        (0..lists.len()).try_for_each(|index| {
+            match (
+                ScalarValue::try_from_array(lists, index)?,
+                ScalarValue::try_from_array(keys, index)?,
+            ) {
+                (ScalarValue::List(values, _), ScalarValue::List(keys, _)) => {
+                    self.values.extend(values.unwrap_or_default());
+                    self.keys.extend(keys.unwrap_or_default());
+                    Ok(())
+                }
+                _ => Err(DataFusionError::Internal(
+                    "array_agg states must be lists!".into(),
+                )),
+            }
+        })
+    }
+
+    fn state(&self) -> Result<Vec<AggregateState>> {
+        Ok(vec![
+            AggregateState::Scalar(ScalarValue::new_list(
+                Some(self.values.clone()),
+                self.data_type.clone(),
+            )),
+            AggregateState::Scalar(ScalarValue::new_list(
+                Some(self.keys.clone()),
+                self.order_key.data_type.clone(),
+            )),
+        ])
+    }
+
+    fn evaluate(&self) -> Result<ScalarValue> {
+        // a stable sort keeps values of equal keys in the order they were read
+        let mut indices = (0..self.values.len()).collect::<Vec<_>>();
+        indices.sort_by(|a, b| self.order_key.compare(&self.keys[*a], &self.keys[*b]));
+        Ok(ScalarValue::new_list(
+            Some(
+                indices
+                    .into_iter()
+                    .map(|i| self.values[i].clone())
+                    .collect(),
+            ),
+            self.data_type.clone(),
+        ))
+    }
+}
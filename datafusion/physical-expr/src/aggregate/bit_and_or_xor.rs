@@ -0,0 +1,494 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines the BIT_AND, BIT_OR and BIT_XOR aggregate expressions
+
+use crate::aggregate::median::primitive_array;
+use crate::expressions::format_state_name;
+use crate::{AggregateExpr, PhysicalExpr};
+use arrow::array::{ArrayRef, PrimitiveArray};
+use arrow::datatypes::{
+    ArrowPrimitiveType, DataType, Field, Int16Type, Int32Type, Int64Type, Int8Type,
+    UInt16Type, UInt32Type, UInt64Type, UInt8Type,
+};
+use datafusion_common::{downcast_value, DataFusionError, Result, ScalarValue};
+use datafusion_expr::{Accumulator, AggregateState};
+use std::any::Any;
+use std::sync::Arc;
+
+/// The bitwise operation a [`BitwiseAccumulator`] aggregates values with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BitwiseOperation {
+    /// The bits set in all values
+    And,
+    /// The bits set in any value
+    Or,
+    /// The bits set in an odd number of values
+    Xor,
+}
+
+impl BitwiseOperation {
+    fn name(&self) -> &'static str {
+        match self {
+            BitwiseOperation::And => "bit_and",
+            BitwiseOperation::Or => "bit_or",
+            BitwiseOperation::Xor => "bit_xor",
+        }
+    }
+}
+
+/// BIT_AND aggregate expression
+#[derive(Debug)]
+pub struct BitAnd {
+    name: String,
+    expr: Arc<dyn PhysicalExpr>,
+    data_type: DataType,
+}
+
+impl BitAnd {
+    /// Create a new BIT_AND aggregate function
+    pub fn new(
+        expr: Arc<dyn PhysicalExpr>,
+        name: impl Into<String>,
+        data_type: DataType,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            expr,
+            data_type,
+        }
+    }
+}
+
+impl AggregateExpr for BitAnd {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn field(&self) -> Result<Field> {
+        Ok(Field::new(&self.name, self.data_type.clone(), true))
+    }
+
+    fn create_accumulator(&self) -> Result<Box<dyn Accumulator>> {
+        create_accumulator(&self.data_type, BitwiseOperation::And, false)
+    }
+
+    fn create_sliding_accumulator(&self) -> Result<Box<dyn Accumulator>> {
+        create_accumulator(&self.data_type, BitwiseOperation::And, true)
+    }
+
+    fn state_fields(&self) -> Result<Vec<Field>> {
+        Ok(state_fields(
+            &self.name,
+            &self.data_type,
+            BitwiseOperation::And,
+        ))
+    }
+
+    fn expressions(&self) -> Vec<Arc<dyn PhysicalExpr>> {
+        vec![self.expr.clone()]
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// BIT_OR aggregate expression
+#[derive(Debug)]
+pub struct BitOr {
+    name: String,
+    expr: Arc<dyn PhysicalExpr>,
+    data_type: DataType,
+}
+
+impl BitOr {
+    /// Create a new BIT_OR aggregate function
+    pub fn new(
+        expr: Arc<dyn PhysicalExpr>,
+        name: impl Into<String>,
+        data_type: DataType,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            expr,
+            data_type,
+        }
+    }
+}
+
+impl AggregateExpr for BitOr {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn field(&self) -> Result<Field> {
+        Ok(Field::new(&self.name, self.data_type.clone(), true))
+    }
+
+    fn create_accumulator(&self) -> Result<Box<dyn Accumulator>> {
+        create_accumulator(&self.data_type, BitwiseOperation::Or, false)
+    }
+
+    fn create_sliding_accumulator(&self) -> Result<Box<dyn Accumulator>> {
+        create_accumulator(&self.data_type, BitwiseOperation::Or, true)
+    }
+
+    fn state_fields(&self) -> Result<Vec<Field>> {
+        Ok(state_fields(
+            &self.name,
+            &self.data_type,
+            BitwiseOperation::Or,
+        ))
+    }
+
+    fn expressions(&self) -> Vec<Arc<dyn PhysicalExpr>> {
+        vec![self.expr.clone()]
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// BIT_XOR aggregate expression
+#[derive(Debug)]
+pub struct BitXor {
+    name: String,
+    expr: Arc<dyn PhysicalExpr>,
+    data_type: DataType,
+}
+
+impl BitXor {
+    /// Create a new BIT_XOR aggregate function
+    pub fn new(
+        expr: Arc<dyn PhysicalExpr>,
+        name: impl Into<String>,
+        data_type: DataType,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            expr,
+            data_type,
+        }
+    }
+}
+
+impl AggregateExpr for BitXor {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn field(&self) -> Result<Field> {
+        Ok(Field::new(&self.name, self.data_type.clone(), true))
+    }
+
+    fn create_accumulator(&self) -> Result<Box<dyn Accumulator>> {
+        create_accumulator(&self.data_type, BitwiseOperation::Xor, false)
+    }
+
+    fn create_sliding_accumulator(&self) -> Result<Box<dyn Accumulator>> {
+        create_accumulator(&self.data_type, BitwiseOperation::Xor, true)
+    }
+
+    fn state_fields(&self) -> Result<Vec<Field>> {
+        Ok(state_fields(
+            &self.name,
+            &self.data_type,
+            BitwiseOperation::Xor,
+        ))
+    }
+
+    fn expressions(&self) -> Vec<Arc<dyn PhysicalExpr>> {
+        vec![self.expr.clone()]
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// The state of a bitwise aggregate: the aggregate of the values so far,
+/// which is aggregated again to merge partial states
+fn state_fields(
+    name: &str,
+    data_type: &DataType,
+    operation: BitwiseOperation,
+) -> Vec<Field> {
+    vec![Field::new(
+        &format_state_name(name, operation.name()),
+        data_type.clone(),
+        true,
+    )]
+}
+
+/// The accumulator of the bitwise `operation` of values of type `data_type`,
+/// from which values can be retracted if `sliding`
+fn create_accumulator(
+    data_type: &DataType,
+    operation: BitwiseOperation,
+    sliding: bool,
+) -> Result<Box<dyn Accumulator>> {
+    // values are aggregated sign extended to 64 bits, then truncated back
+    macro_rules! integer {
+        ($TY:ty, $NATIVE:ty) => {
+            Box::new(BitwiseAccumulator::<$TY>::new(
+                data_type,
+                operation,
+                sliding,
+                |v| v as u64,
+                |bits| bits as $NATIVE,
+            ))
+        };
+    }
+
+    let accumulator: Box<dyn Accumulator> = match data_type {
+        DataType::Int8 => integer!(Int8Type, i8),
+        DataType::Int16 => integer!(Int16Type, i16),
+        DataType::Int32 => integer!(Int32Type, i32),
+        DataType::Int64 => integer!(Int64Type, i64),
+        DataType::UInt8 => integer!(UInt8Type, u8),
+        DataType::UInt16 => integer!(UInt16Type, u16),
+        DataType::UInt32 => integer!(UInt32Type, u32),
+        DataType::UInt64 => integer!(UInt64Type, u64),
+        data_type => {
+            return Err(DataFusionError::Execution(format!(
+                "unsupported data type {:?} for {}",
+                data_type,
+                operation.name()
+            )))
+        }
+    };
+    Ok(accumulator)
+}
+
+/// Aggregates the non null values of a group with a bitwise operation. The
+/// accumulator of a sliding window also counts the values each bit is set in,
+/// so that values can be retracted from an AND or an OR.
+#[derive(Debug)]
+struct BitwiseAccumulator<T: ArrowPrimitiveType + std::fmt::Debug> {
+    data_type: DataType,
+    operation: BitwiseOperation,
+    /// The number of values aggregated
+    count: u64,
+    /// The aggregate of the values, which retracting a value XORs with it
+    /// again
+    bits: u64,
+    /// The number of values each of the 64 bits is set in, only counted by
+    /// the AND and the OR of a sliding window
+    ones: Option<Vec<u64>>,
+    to_bits: fn(T::Native) -> u64,
+    from_bits: fn(u64) -> T::Native,
+}
+
+impl<T: ArrowPrimitiveType + std::fmt::Debug> BitwiseAccumulator<T> {
+    fn new(
+        data_type: &DataType,
+        operation: BitwiseOperation,
+        sliding: bool,
+        to_bits: fn(T::Native) -> u64,
+        from_bits: fn(u64) -> T::Native,
+    ) -> Self {
+        let counted = sliding && operation != BitwiseOperation::Xor;
+        Self {
+            data_type: data_type.clone(),
+            operation,
+            count: 0,
+            bits: match operation {
+                BitwiseOperation::And => u64::MAX,
+                BitwiseOperation::Or | BitwiseOperation::Xor => 0,
+            },
+            ones: counted.then(|| vec![0; 64]),
+            to_bits,
+            from_bits,
+        }
+    }
+
+    /// Adds the non null values of `array` to the aggregate, or removes them
+    /// from it if `retract` is set
+    fn aggregate(&mut self, array: &ArrayRef, retract: bool) -> Result<()> {
+        if retract && self.operation != BitwiseOperation::Xor && self.ones.is_none() {
+            return Err(DataFusionError::Internal(format!(
+                "Values can only be retracted from the {} of a sliding window",
+                self.operation.name()
+            )));
+        }
+        let array = downcast_value!(array, PrimitiveArray, T);
+        for value in array.iter().flatten() {
+            let bits = (self.to_bits)(value);
+            match self.operation {
+                BitwiseOperation::And => self.bits &= bits,
+                BitwiseOperation::Or => self.bits |= bits,
+                BitwiseOperation::Xor => self.bits ^= bits,
+            }
+            if let Some(ones) = &mut self.ones {
+                let mut rest = bits;
+                while rest != 0 {
+                    let ones = &mut ones[rest.trailing_zeros() as usize];
+                    if retract {
+                        *ones -= 1;
+                    } else {
+                        *ones += 1;
+                    }
+                    rest &= rest - 1;
+                }
+            }
+            if retract {
+                self.count -= 1;
+            } else {
+                self.count += 1;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T: ArrowPrimitiveType + std::fmt::Debug> Accumulator for BitwiseAccumulator<T> {
+    fn state(&self) -> Result<Vec<AggregateState>> {
+        Ok(vec![AggregateState::Scalar(self.evaluate()?)])
+    }
+
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        self.aggregate(&values[0], false)
+    }
+
+    fn retract_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        self.aggregate(&values[0], true)
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<()> {
+        // the aggregate of the partial aggregates, which are null if they
+        // have no value
+        self.aggregate(&states[0], false)
+    }
+
+    fn evaluate(&self) -> Result<ScalarValue> {
+        // the aggregate of a group without any non null value is null
+        if self.count == 0 {
+            return ScalarValue::try_from(&self.data_type);
+        }
+
+        // the AND or the OR of values some of which were retracted is
+        // recomputed from the counts of the values left
+        let bits = match &self.ones {
+            Some(ones) => (0..64).fold(0, |bits, bit| {
+                let set = match self.operation {
+                    BitwiseOperation::And => ones[bit] == self.count,
+                    _ => ones[bit] > 0,
+                };
+                if set {
+                    bits | (1 << bit)
+                } else {
+                    bits
+                }
+            }),
+            None => self.bits,
+        };
+        ScalarValue::try_from_array(
+            &primitive_array::<T>(&self.data_type, [(self.from_bits)(bits)])?,
+            0,
+        )
+    }
+
+    fn size(&self) -> usize {
+        std::mem::size_of_val(self)
+            + self.ones.as_ref().map_or(0, |ones| ones.capacity())
+                * std::mem::size_of::<u64>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expressions::col;
+    use crate::expressions::tests::aggregate;
+    use crate::generic_test_op;
+    use arrow::array::{Int32Array, Int8Array, UInt8Array};
+    use arrow::datatypes::Schema;
+    use arrow::record_batch::RecordBatch;
+
+    #[test]
+    fn bit_and_i32() -> Result<()> {
+        let a: ArrayRef = Arc::new(Int32Array::from(vec![Some(7), None, Some(13)]));
+        generic_test_op!(a, DataType::Int32, BitAnd, ScalarValue::Int32(Some(5)))
+    }
+
+    #[test]
+    fn bit_or_u8() -> Result<()> {
+        let a: ArrayRef = Arc::new(UInt8Array::from(vec![Some(1), Some(4), None]));
+        generic_test_op!(a, DataType::UInt8, BitOr, ScalarValue::UInt8(Some(5)))
+    }
+
+    #[test]
+    fn bit_xor_negative_i8() -> Result<()> {
+        let a: ArrayRef = Arc::new(Int8Array::from(vec![-1, 1, 2]));
+        generic_test_op!(a, DataType::Int8, BitXor, ScalarValue::Int8(Some(-4)))
+    }
+
+    #[test]
+    fn bitwise_all_nulls() -> Result<()> {
+        let a: ArrayRef = Arc::new(Int32Array::from(vec![None, None]));
+        generic_test_op!(a, DataType::Int32, BitAnd, ScalarValue::Int32(None))
+    }
+
+    #[test]
+    fn bitwise_merge_and_retract() -> Result<()> {
+        for (operation, expected) in [
+            (BitwiseOperation::And, 4),
+            (BitwiseOperation::Or, 14),
+            (BitwiseOperation::Xor, 4),
+        ] {
+            let partial = |values: Vec<Option<i32>>| -> Result<ArrayRef> {
+                let mut accumulator =
+                    create_accumulator(&DataType::Int32, operation, false)?;
+                accumulator.update_batch(&[Arc::new(Int32Array::from(values))])?;
+                Ok(accumulator.state()?[0].as_scalar()?.to_array())
+            };
+            let mut merged = create_accumulator(&DataType::Int32, operation, false)?;
+            merged.merge_batch(&[partial(vec![Some(6), Some(12)])?])?;
+            merged.merge_batch(&[partial(vec![None])?])?;
+            merged.merge_batch(&[partial(vec![Some(14)])?])?;
+            assert_eq!(merged.evaluate()?, ScalarValue::Int32(Some(expected)));
+
+            // retracting values from the accumulator of a sliding window
+            // restores the aggregate of those left
+            let mut accumulator = create_accumulator(&DataType::Int32, operation, true)?;
+            let values: ArrayRef = Arc::new(Int32Array::from(vec![1, 6, 12, 14]));
+            accumulator.update_batch(&[values.clone()])?;
+            accumulator.retract_batch(&[values.slice(0, 1)])?;
+            assert_eq!(accumulator.evaluate()?, ScalarValue::Int32(Some(expected)));
+            accumulator.retract_batch(&[values.slice(1, 3)])?;
+            assert_eq!(accumulator.evaluate()?, ScalarValue::Int32(None));
+
+            // only XOR can retract values without counting the bits set
+            let mut accumulator = create_accumulator(&DataType::Int32, operation, false)?;
+            accumulator.update_batch(&[values.clone()])?;
+            let retracted = accumulator.retract_batch(&[values.slice(0, 1)]);
+            assert_eq!(retracted.is_ok(), operation == BitwiseOperation::Xor);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn bitwise_unsupported_type() -> Result<()> {
+        let schema = Schema::new(vec![Field::new("a", DataType::Float64, true)]);
+        let agg = Arc::new(BitOr::new(col("a", &schema)?, "bla", DataType::Float64));
+        let batch = RecordBatch::new_empty(Arc::new(schema));
+        assert!(aggregate(&batch, agg).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,312 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines the BOOL_AND and BOOL_OR aggregate expressions
+
+use crate::expressions::format_state_name;
+use crate::{AggregateExpr, PhysicalExpr};
+use arrow::array::{Array, ArrayRef, BooleanArray};
+use arrow::datatypes::{DataType, Field};
+use datafusion_common::{downcast_value, DataFusionError, Result, ScalarValue};
+use datafusion_expr::{Accumulator, AggregateState};
+use std::any::Any;
+use std::sync::Arc;
+
+/// Checks that `data_type`, of the values of the aggregate `name`, is boolean
+fn check_boolean(name: &str, data_type: &DataType) -> Result<()> {
+    match data_type {
+        DataType::Boolean => Ok(()),
+        _ => Err(DataFusionError::Plan(format!(
+            "The function {} does not support inputs of type {:?}.",
+            name, data_type
+        ))),
+    }
+}
+
+/// BOOL_AND aggregate expression: whether all non null values of a group
+/// are true
+#[derive(Debug)]
+pub struct BoolAnd {
+    name: String,
+    expr: Arc<dyn PhysicalExpr>,
+}
+
+impl BoolAnd {
+    /// Create a new BOOL_AND aggregate function of boolean values
+    pub fn try_new(
+        expr: Arc<dyn PhysicalExpr>,
+        name: impl Into<String>,
+        data_type: DataType,
+    ) -> Result<Self> {
+        check_boolean("bool_and", &data_type)?;
+        Ok(Self {
+            name: name.into(),
+            expr,
+        })
+    }
+}
+
+impl AggregateExpr for BoolAnd {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn field(&self) -> Result<Field> {
+        Ok(Field::new(&self.name, DataType::Boolean, true))
+    }
+
+    fn create_accumulator(&self) -> Result<Box<dyn Accumulator>> {
+        Ok(Box::new(BoolAccumulator::new(true)))
+    }
+
+    fn state_fields(&self) -> Result<Vec<Field>> {
+        Ok(vec![Field::new(
+            &format_state_name(&self.name, "bool_and"),
+            DataType::Boolean,
+            true,
+        )])
+    }
+
+    fn expressions(&self) -> Vec<Arc<dyn PhysicalExpr>> {
+        vec![self.expr.clone()]
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// BOOL_OR aggregate expression: whether any non null value of a group is
+/// true
+#[derive(Debug)]
+pub struct BoolOr {
+    name: String,
+    expr: Arc<dyn PhysicalExpr>,
+}
+
+impl BoolOr {
+    /// Create a new BOOL_OR aggregate function of boolean values
+    pub fn try_new(
+        expr: Arc<dyn PhysicalExpr>,
+        name: impl Into<String>,
+        data_type: DataType,
+    ) -> Result<Self> {
+        check_boolean("bool_or", &data_type)?;
+        Ok(Self {
+            name: name.into(),
+            expr,
+        })
+    }
+}
+
+impl AggregateExpr for BoolOr {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn field(&self) -> Result<Field> {
+        Ok(Field::new(&self.name, DataType::Boolean, true))
+    }
+
+    fn create_accumulator(&self) -> Result<Box<dyn Accumulator>> {
+        Ok(Box::new(BoolAccumulator::new(false)))
+    }
+
+    fn state_fields(&self) -> Result<Vec<Field>> {
+        Ok(vec![Field::new(
+            &format_state_name(&self.name, "bool_or"),
+            DataType::Boolean,
+            true,
+        )])
+    }
+
+    fn expressions(&self) -> Vec<Arc<dyn PhysicalExpr>> {
+        vec![self.expr.clone()]
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// Counts the non null and the true values of a group, so that values can be
+/// retracted from the aggregate of a sliding window. Partial states, the
+/// aggregates of their values, are merged as single values.
+#[derive(Debug)]
+struct BoolAccumulator {
+    /// Whether all values must be true, or any
+    all: bool,
+    /// The number of non null values aggregated
+    count: u64,
+    /// The number of true values aggregated
+    trues: u64,
+}
+
+impl BoolAccumulator {
+    fn new(all: bool) -> Self {
+        Self {
+            all,
+            count: 0,
+            trues: 0,
+        }
+    }
+
+    /// Adds the non null values of `array` to the aggregate, or removes them
+    /// from it if `retract` is set
+    fn aggregate(&mut self, array: &ArrayRef, retract: bool) -> Result<()> {
+        let array = downcast_value!(array, BooleanArray);
+        let count = (array.len() - array.null_count()) as u64;
+        let trues = array.iter().filter(|value| *value == Some(true)).count() as u64;
+        if retract {
+            self.count -= count;
+            self.trues -= trues;
+        } else {
+            self.count += count;
+            self.trues += trues;
+        }
+        Ok(())
+    }
+}
+
+impl Accumulator for BoolAccumulator {
+    fn state(&self) -> Result<Vec<AggregateState>> {
+        Ok(vec![AggregateState::Scalar(self.evaluate()?)])
+    }
+
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        self.aggregate(&values[0], false)
+    }
+
+    fn retract_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        self.aggregate(&values[0], true)
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<()> {
+        self.aggregate(&states[0], false)
+    }
+
+    fn evaluate(&self) -> Result<ScalarValue> {
+        // the aggregate of a group without any non null value is null
+        Ok(ScalarValue::Boolean(match self.count {
+            0 => None,
+            count if self.all => Some(self.trues == count),
+            _ => Some(self.trues > 0),
+        }))
+    }
+
+    fn size(&self) -> usize {
+        std::mem::size_of_val(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expressions::col;
+    use crate::expressions::tests::aggregate;
+    use arrow::datatypes::Schema;
+    use arrow::record_batch::RecordBatch;
+
+    /// Aggregates the boolean `values` with `agg`
+    fn aggregate_bools(
+        agg: fn(Arc<dyn PhysicalExpr>, &str, DataType) -> Result<Arc<dyn AggregateExpr>>,
+        values: Vec<Option<bool>>,
+    ) -> Result<ScalarValue> {
+        let schema = Schema::new(vec![Field::new("a", DataType::Boolean, true)]);
+        let batch = RecordBatch::try_new(
+            Arc::new(schema.clone()),
+            vec![Arc::new(BooleanArray::from(values))],
+        )?;
+        aggregate(&batch, agg(col("a", &schema)?, "bla", DataType::Boolean)?)
+    }
+
+    fn bool_and(
+        expr: Arc<dyn PhysicalExpr>,
+        name: &str,
+        data_type: DataType,
+    ) -> Result<Arc<dyn AggregateExpr>> {
+        Ok(Arc::new(BoolAnd::try_new(expr, name, data_type)?))
+    }
+
+    fn bool_or(
+        expr: Arc<dyn PhysicalExpr>,
+        name: &str,
+        data_type: DataType,
+    ) -> Result<Arc<dyn AggregateExpr>> {
+        Ok(Arc::new(BoolOr::try_new(expr, name, data_type)?))
+    }
+
+    #[test]
+    fn bool_and_skips_nulls() -> Result<()> {
+        let actual = aggregate_bools(bool_and, vec![Some(true), None, Some(true)])?;
+        assert_eq!(actual, ScalarValue::Boolean(Some(true)));
+        Ok(())
+    }
+
+    #[test]
+    fn bool_or_false() -> Result<()> {
+        let actual = aggregate_bools(bool_or, vec![Some(false), None])?;
+        assert_eq!(actual, ScalarValue::Boolean(Some(false)));
+        Ok(())
+    }
+
+    #[test]
+    fn bool_all_nulls() -> Result<()> {
+        let actual = aggregate_bools(bool_or, vec![None, None])?;
+        assert_eq!(actual, ScalarValue::Boolean(None));
+        Ok(())
+    }
+
+    #[test]
+    fn bool_merge_and_retract() -> Result<()> {
+        for (all, expected) in [(true, false), (false, true)] {
+            let partial = |values: Vec<Option<bool>>| -> Result<ArrayRef> {
+                let mut accumulator = BoolAccumulator::new(all);
+                accumulator.update_batch(&[Arc::new(BooleanArray::from(values))])?;
+                Ok(accumulator.state()?[0].as_scalar()?.to_array())
+            };
+            let mut merged = BoolAccumulator::new(all);
+            merged.merge_batch(&[partial(vec![Some(true), Some(false)])?])?;
+            merged.merge_batch(&[partial(vec![None])?])?;
+            merged.merge_batch(&[partial(vec![Some(true)])?])?;
+            assert_eq!(merged.evaluate()?, ScalarValue::Boolean(Some(expected)));
+
+            // retracting values restores the aggregate of those left
+            let mut accumulator = BoolAccumulator::new(all);
+            let values: ArrayRef =
+                Arc::new(BooleanArray::from(vec![Some(false), Some(true), None]));
+            accumulator.update_batch(&[values.clone()])?;
+            accumulator.retract_batch(&[values.slice(0, 1)])?;
+            assert_eq!(accumulator.evaluate()?, ScalarValue::Boolean(Some(true)));
+            accumulator.retract_batch(&[values.slice(1, 2)])?;
+            assert_eq!(accumulator.evaluate()?, ScalarValue::Boolean(None));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn bool_unsupported_type() -> Result<()> {
+        let schema = Schema::new(vec![Field::new("a", DataType::Int32, true)]);
+        let agg = bool_or(col("a", &schema)?, "bla", DataType::Boolean)?;
+        let batch = RecordBatch::new_empty(Arc::new(schema));
+        assert!(aggregate(&batch, agg).is_err());
+
+        let err = bool_and(col("a", &schema)?, "bla", DataType::Int32).unwrap_err();
+        assert!(err.to_string().contains("does not support"), "{}", err);
+        Ok(())
+    }
+}
@@ -41,6 +41,26 @@ pub fn create_aggregate_expr(
     input_phy_exprs: &[Arc<dyn PhysicalExpr>],
     input_schema: &Schema,
     name: impl Into<String>,
+) -> Result<Arc<dyn AggregateExpr>> {
+    create_aggregate_expr_with_wrapping(
+        fun,
+        distinct,
+        input_phy_exprs,
+        input_schema,
+        name,
+        false,
+    )
+}
+
+/// Create a physical aggregation expression, whose integer sums wrap around
+/// on overflow rather than fail if `wrapping_arithmetic` is true.
+pub fn create_aggregate_expr_with_wrapping(
+    fun: &AggregateFunction,
+    distinct: bool,
+    input_phy_exprs: &[Arc<dyn PhysicalExpr>],
+    input_schema: &Schema,
+    name: impl Into<String>,
+    wrapping_arithmetic: bool,
 ) -> Result<Arc<dyn AggregateExpr>> {
     let name = name.into();
     // get the result data type for this aggregate function
@@ -68,11 +88,10 @@ pub fn create_aggregate_expr(
             name,
             return_type,
         )),
-        (AggregateFunction::Sum, false) => Arc::new(expressions::Sum::new(
-            input_phy_exprs[0].clone(),
-            name,
-            return_type,
-        )),
+        (AggregateFunction::Sum, false) => Arc::new(
+            expressions::Sum::new(input_phy_exprs[0].clone(), name, return_type)
+                .with_wrapping_arithmetic(wrapping_arithmetic),
+        ),
         (AggregateFunction::Sum, true) => Arc::new(expressions::DistinctSum::new(
             vec![input_phy_exprs[0].clone()],
             name,
@@ -85,18 +104,56 @@ pub fn create_aggregate_expr(
                 input_phy_types[0].clone(),
             ))
         }
-        (AggregateFunction::ArrayAgg, false) => Arc::new(expressions::ArrayAgg::new(
-            input_phy_exprs[0].clone(),
-            name,
-            input_phy_types[0].clone(),
-        )),
+        (AggregateFunction::ArrayAgg, false) => {
+            match expressions::OrderKey::try_from_args(
+                &input_phy_exprs,
+                &input_phy_types,
+            )? {
+                Some(order_key) => Arc::new(expressions::OrderedArrayAgg::new(
+                    input_phy_exprs[0].clone(),
+                    name,
+                    input_phy_types[0].clone(),
+                    order_key,
+                )),
+                None => Arc::new(expressions::ArrayAgg::new(
+                    input_phy_exprs[0].clone(),
+                    name,
+                    input_phy_types[0].clone(),
+                )),
+            }
+        }
         (AggregateFunction::ArrayAgg, true) => {
+            if input_phy_exprs.len() > 1 {
+                return Err(DataFusionError::NotImplemented(
+                    "ARRAY_AGG(DISTINCT) ordered by a key is not available".to_string(),
+                ));
+            }
             Arc::new(expressions::DistinctArrayAgg::new(
                 input_phy_exprs[0].clone(),
                 name,
                 input_phy_types[0].clone(),
             ))
         }
+        (AggregateFunction::FirstValue, _) => Arc::new(expressions::FirstValue::new(
+            input_phy_exprs[0].clone(),
+            name,
+            return_type,
+            expressions::OrderKey::try_from_args(&input_phy_exprs, &input_phy_types)?,
+        )),
+        (AggregateFunction::LastValue, _) => Arc::new(expressions::LastValue::new(
+            input_phy_exprs[0].clone(),
+            name,
+            return_type,
+            expressions::OrderKey::try_from_args(&input_phy_exprs, &input_phy_types)?,
+        )),
+        (AggregateFunction::StringAgg, false) => Arc::new(
+            expressions::StringAgg::try_new(&input_phy_exprs, name, return_type)?,
+        ),
+        (AggregateFunction::StringAgg, true) => {
+            return Err(DataFusionError::NotImplemented(
+                "STRING_AGG(DISTINCT) aggregations are not available".to_string(),
+            ));
+        }
         (AggregateFunction::Min, _) => Arc::new(expressions::Min::new(
             input_phy_exprs[0].clone(),
             name,
@@ -251,6 +308,47 @@ pub fn create_aggregate_expr(
                 "MEDIAN(DISTINCT) aggregations are not available".to_string(),
             ));
         }
+        (AggregateFunction::Mode, false) => Arc::new(expressions::Mode::new(
+            input_phy_exprs[0].clone(),
+            name,
+            return_type,
+        )),
+        (AggregateFunction::Mode, true) => {
+            return Err(DataFusionError::NotImplemented(
+                "MODE(DISTINCT) aggregations are not available".to_string(),
+            ));
+        }
+        // AND and OR ignore duplicate values, so they are the same with DISTINCT
+        (AggregateFunction::BitAnd, _) => Arc::new(expressions::BitAnd::new(
+            input_phy_exprs[0].clone(),
+            name,
+            return_type,
+        )),
+        (AggregateFunction::BitOr, _) => Arc::new(expressions::BitOr::new(
+            input_phy_exprs[0].clone(),
+            name,
+            return_type,
+        )),
+        (AggregateFunction::BitXor, false) => Arc::new(expressions::BitXor::new(
+            input_phy_exprs[0].clone(),
+            name,
+            return_type,
+        )),
+        (AggregateFunction::BitXor, true) => {
+            return Err(DataFusionError::NotImplemented(
+                "BIT_XOR(DISTINCT) aggregations are not available".to_string(),
+            ));
+        }
+        (AggregateFunction::BoolAnd, _) => Arc::new(expressions::BoolAnd::try_new(
+            input_phy_exprs[0].clone(),
+            name,
+            return_type,
+        )?),
+        (AggregateFunction::BoolOr, _) => Arc::new(expressions::BoolOr::try_new(
+            input_phy_exprs[0].clone(),
+            name,
+            return_type,
+        )?),
     })
 }
 
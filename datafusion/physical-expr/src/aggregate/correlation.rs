@@ -22,6 +22,7 @@ use crate::aggregate::stats::StatsType;
 use crate::aggregate::stddev::StddevAccumulator;
 use crate::expressions::format_state_name;
 use crate::{AggregateExpr, PhysicalExpr};
+use arrow::compute::{and, filter, is_not_null};
 use arrow::{array::ArrayRef, datatypes::DataType, datatypes::Field};
 use datafusion_common::Result;
 use datafusion_common::ScalarValue;
@@ -132,6 +133,20 @@ impl CorrelationAccumulator {
     }
 }
 
+/// Keeps the rows of the two arrays of `values` whose values are both not
+/// null, so that the standard deviations are computed over the same pairs as
+/// the covariance
+fn non_null_pairs(values: &[ArrayRef]) -> Result<Vec<ArrayRef>> {
+    let predicate = and(
+        &is_not_null(values[0].as_ref())?,
+        &is_not_null(values[1].as_ref())?,
+    )?;
+    Ok(vec![
+        filter(values[0].as_ref(), &predicate)?,
+        filter(values[1].as_ref(), &predicate)?,
+    ])
+}
+
 impl Accumulator for CorrelationAccumulator {
     fn state(&self) -> Result<Vec<AggregateState>> {
         Ok(vec![
@@ -145,6 +160,7 @@ impl Accumulator for CorrelationAccumulator {
     }
 
     fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        let values = &non_null_pairs(values)?;
         self.covar.update_batch(values)?;
         self.stddev1.update_batch(&values[0..1])?;
         self.stddev2.update_batch(&values[1..2])?;
@@ -152,6 +168,7 @@ impl Accumulator for CorrelationAccumulator {
     }
 
     fn retract_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        let values = &non_null_pairs(values)?;
         self.covar.retract_batch(values)?;
         self.stddev1.retract_batch(&values[0..1])?;
         self.stddev2.retract_batch(&values[1..2])?;
@@ -175,6 +192,11 @@ impl Accumulator for CorrelationAccumulator {
     }
 
     fn evaluate(&self) -> Result<ScalarValue> {
+        // the correlation of less than two pairs is null
+        if self.covar.get_count() < 2 {
+            return Ok(ScalarValue::Float64(None));
+        }
+
         let covar = self.covar.evaluate()?;
         let stddev1 = self.stddev1.evaluate()?;
         let stddev2 = self.stddev2.evaluate()?;
@@ -347,8 +369,8 @@ mod tests {
             "bla".to_string(),
             DataType::Float64,
         ));
-        let actual = aggregate(&batch, agg);
-        assert!(actual.is_err());
+        let actual = aggregate(&batch, agg)?;
+        assert_eq!(actual, ScalarValue::Float64(Some(1_f64)));
 
         Ok(())
     }
@@ -370,8 +392,8 @@ mod tests {
             "bla".to_string(),
             DataType::Float64,
         ));
-        let actual = aggregate(&batch, agg);
-        assert!(actual.is_err());
+        let actual = aggregate(&batch, agg)?;
+        assert_eq!(actual, ScalarValue::Float64(None));
 
         Ok(())
     }
@@ -22,6 +22,7 @@ use std::fmt::Debug;
 use std::sync::Arc;
 
 use crate::aggregate::row_accumulator::RowAccumulator;
+use crate::aggregate::utils::non_null_counts;
 use crate::{AggregateExpr, PhysicalExpr};
 use arrow::array::Int64Array;
 use arrow::compute;
@@ -55,7 +56,8 @@ impl Count {
             name: name.into(),
             expr,
             data_type,
-            nullable: true,
+            // the count of no rows is 0 rather than NULL
+            nullable: false,
         }
     }
 }
@@ -104,6 +106,14 @@ impl AggregateExpr for Count {
     ) -> Result<Box<dyn RowAccumulator>> {
         Ok(Box::new(CountRowAccumulator::new(start_index)))
     }
+
+    fn convert_to_state_supported(&self) -> bool {
+        true
+    }
+
+    fn convert_to_state(&self, values: &[ArrayRef]) -> Result<Vec<ArrayRef>> {
+        Ok(vec![non_null_counts(&values[0], &self.data_type)?])
+    }
 }
 
 #[derive(Debug)]
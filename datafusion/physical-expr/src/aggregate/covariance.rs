@@ -28,8 +28,7 @@ use arrow::{
     datatypes::DataType,
     datatypes::Field,
 };
-use datafusion_common::{downcast_value, unwrap_or_internal_err, ScalarValue};
-use datafusion_common::{DataFusionError, Result};
+use datafusion_common::{downcast_value, Result, ScalarValue};
 use datafusion_expr::{Accumulator, AggregateState};
 
 use crate::aggregate::stats::StatsType;
@@ -249,25 +248,12 @@ impl Accumulator for CovarianceAccumulator {
     fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
         let values1 = &cast(&values[0], &DataType::Float64)?;
         let values2 = &cast(&values[1], &DataType::Float64)?;
+        let arr1 = downcast_value!(values1, Float64Array);
+        let arr2 = downcast_value!(values2, Float64Array);
 
-        let mut arr1 = downcast_value!(values1, Float64Array).iter().flatten();
-        let mut arr2 = downcast_value!(values2, Float64Array).iter().flatten();
-
-        for _i in 0..values1.len() {
-            let value1 = arr1.next();
-            let value2 = arr2.next();
-
-            if value1.is_none() || value2.is_none() {
-                if value1.is_none() && value2.is_none() {
-                    continue;
-                } else {
-                    return Err(DataFusionError::Internal(
-                        "The two columns are not aligned".to_string(),
-                    ));
-                }
-            } else {
-                let value1 = unwrap_or_internal_err!(value1);
-                let value2 = unwrap_or_internal_err!(value2);
+        // a pair with a null value is ignored
+        for (value1, value2) in arr1.iter().zip(arr2.iter()) {
+            if let (Some(value1), Some(value2)) = (value1, value2) {
                 let new_count = self.count + 1;
                 let delta1 = value1 - self.mean1;
                 let new_mean1 = delta1 / new_count as f64 + self.mean1;
@@ -288,33 +274,31 @@ impl Accumulator for CovarianceAccumulator {
     fn retract_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
         let values1 = &cast(&values[0], &DataType::Float64)?;
         let values2 = &cast(&values[1], &DataType::Float64)?;
-        let mut arr1 = downcast_value!(values1, Float64Array).iter().flatten();
-        let mut arr2 = downcast_value!(values2, Float64Array).iter().flatten();
-
-        for _i in 0..values1.len() {
-            let value1 = arr1.next();
-            let value2 = arr2.next();
-
-            if value1.is_none() || value2.is_none() {
-                if value1.is_none() && value2.is_none() {
+        let arr1 = downcast_value!(values1, Float64Array);
+        let arr2 = downcast_value!(values2, Float64Array);
+
+        for (value1, value2) in arr1.iter().zip(arr2.iter()) {
+            if let (Some(value1), Some(value2)) = (value1, value2) {
+                let new_count = self.count - 1;
+                if new_count == 0 {
+                    // the means of no pair are undefined, start over
+                    self.count = 0;
+                    self.mean1 = 0_f64;
+                    self.mean2 = 0_f64;
+                    self.algo_const = 0_f64;
                     continue;
-                } else {
-                    return Err(DataFusionError::Internal(
-                        "The two columns are not aligned".to_string(),
-                    ));
                 }
+                let delta1 = self.mean1 - value1;
+                let new_mean1 = delta1 / new_count as f64 + self.mean1;
+                let delta2 = self.mean2 - value2;
+                let new_mean2 = delta2 / new_count as f64 + self.mean2;
+                let new_c = self.algo_const - delta1 * (new_mean2 - value2);
+
+                self.count -= 1;
+                self.mean1 = new_mean1;
+                self.mean2 = new_mean2;
+                self.algo_const = new_c;
             }
-            let new_count = self.count - 1;
-            let delta1 = self.mean1 - value1.unwrap();
-            let new_mean1 = delta1 / new_count as f64 + self.mean1;
-            let delta2 = self.mean2 - value2.unwrap();
-            let new_mean2 = delta2 / new_count as f64 + self.mean2;
-            let new_c = self.algo_const - delta1 * (new_mean2 - value2.unwrap());
-
-            self.count -= 1;
-            self.mean1 = new_mean1;
-            self.mean2 = new_mean2;
-            self.algo_const = new_c;
         }
         Ok(())
     }
@@ -350,24 +334,14 @@ impl Accumulator for CovarianceAccumulator {
     }
 
     fn evaluate(&self) -> Result<ScalarValue> {
+        // the sample covariance of less than two pairs, like the population
+        // covariance of no pair, is null
         let count = match self.stats_type {
             StatsType::Population => self.count,
-            StatsType::Sample => {
-                if self.count > 0 {
-                    self.count - 1
-                } else {
-                    self.count
-                }
-            }
+            StatsType::Sample => self.count.saturating_sub(1),
         };
 
-        if count <= 1 {
-            return Err(DataFusionError::Internal(
-                "At least two values are needed to calculate covariance".to_string(),
-            ));
-        }
-
-        if self.count == 0 {
+        if count == 0 {
             Ok(ScalarValue::Float64(None))
         } else {
             Ok(ScalarValue::Float64(Some(self.algo_const / count as f64)))
@@ -540,8 +514,8 @@ mod tests {
             "bla".to_string(),
             DataType::Float64,
         ));
-        let actual = aggregate(&batch, agg);
-        assert!(actual.is_err());
+        let actual = aggregate(&batch, agg)?;
+        assert_eq!(actual, ScalarValue::Float64(Some(2_f64)));
 
         Ok(())
     }
@@ -563,8 +537,8 @@ mod tests {
             "bla".to_string(),
             DataType::Float64,
         ));
-        let actual = aggregate(&batch, agg);
-        assert!(actual.is_err());
+        let actual = aggregate(&batch, agg)?;
+        assert_eq!(actual, ScalarValue::Float64(None));
 
         Ok(())
     }
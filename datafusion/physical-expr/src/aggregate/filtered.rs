@@ -0,0 +1,203 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines the `FILTER (WHERE ...)` clause of an aggregate expression
+
+use crate::{AggregateExpr, PhysicalExpr};
+use arrow::array::{ArrayRef, BooleanArray};
+use arrow::compute::filter;
+use arrow::datatypes::Field;
+use datafusion_common::{DataFusionError, Result, ScalarValue};
+use datafusion_expr::{Accumulator, AggregateState};
+use std::any::Any;
+use std::sync::Arc;
+
+/// An aggregate expression that only aggregates the input rows for which
+/// its filter is true, as in `SUM(x) FILTER (WHERE y > 0)`
+#[derive(Debug)]
+pub struct FilteredAggregateExpr {
+    expr: Arc<dyn AggregateExpr>,
+    filter: Arc<dyn PhysicalExpr>,
+}
+
+impl FilteredAggregateExpr {
+    /// Create a new aggregate expression aggregating the rows of `expr`
+    /// for which the boolean expression `filter` is true
+    pub fn new(expr: Arc<dyn AggregateExpr>, filter: Arc<dyn PhysicalExpr>) -> Self {
+        Self { expr, filter }
+    }
+
+    /// The filtered aggregate expression
+    pub fn expr(&self) -> &Arc<dyn AggregateExpr> {
+        &self.expr
+    }
+
+    /// The filter of the input rows
+    pub fn filter(&self) -> &Arc<dyn PhysicalExpr> {
+        &self.filter
+    }
+}
+
+impl AggregateExpr for FilteredAggregateExpr {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn field(&self) -> Result<Field> {
+        self.expr.field()
+    }
+
+    fn create_accumulator(&self) -> Result<Box<dyn Accumulator>> {
+        Ok(Box::new(FilteredAccumulator {
+            accumulator: self.expr.create_accumulator()?,
+        }))
+    }
+
+    fn state_fields(&self) -> Result<Vec<Field>> {
+        self.expr.state_fields()
+    }
+
+    /// the expressions of the filtered aggregate, followed by the filter,
+    /// whose values are passed to the accumulator as its last argument
+    fn expressions(&self) -> Vec<Arc<dyn PhysicalExpr>> {
+        let mut expressions = self.expr.expressions();
+        expressions.push(self.filter.clone());
+        expressions
+    }
+
+    fn name(&self) -> &str {
+        self.expr.name()
+    }
+}
+
+/// Accumulator of a [`FilteredAggregateExpr`], which removes the rows whose
+/// filter is not true from its input before updating the accumulator of the
+/// filtered aggregate. States are merged as they are, since they only ever
+/// contain rows which passed the filter.
+#[derive(Debug)]
+struct FilteredAccumulator {
+    accumulator: Box<dyn Accumulator>,
+}
+
+/// Keeps the rows of `values` for which their last array, the values of the
+/// filter, is true
+fn filter_values(values: &[ArrayRef]) -> Result<Vec<ArrayRef>> {
+    let (predicate, values) = match values.split_last() {
+        Some((predicate, values)) => (predicate, values),
+        None => {
+            return Err(DataFusionError::Internal(
+                "Expected the values of an aggregate filter".to_string(),
+            ))
+        }
+    };
+    let predicate = predicate
+        .as_any()
+        .downcast_ref::<BooleanArray>()
+        .ok_or_else(|| {
+            DataFusionError::Plan(format!(
+                "The FILTER of an aggregate must be a boolean expression, got {:?}",
+                predicate.data_type()
+            ))
+        })?;
+    // rows whose filter is null are removed, like those whose filter is false
+    Ok(values
+        .iter()
+        .map(|array| filter(array, predicate))
+        .collect::<arrow::error::Result<Vec<_>>>()?)
+}
+
+impl Accumulator for FilteredAccumulator {
+    fn state(&self) -> Result<Vec<AggregateState>> {
+        self.accumulator.state()
+    }
+
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        self.accumulator.update_batch(&filter_values(values)?)
+    }
+
+    fn retract_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        self.accumulator.retract_batch(&filter_values(values)?)
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<()> {
+        self.accumulator.merge_batch(states)
+    }
+
+    fn evaluate(&self) -> Result<ScalarValue> {
+        self.accumulator.evaluate()
+    }
+
+    fn size(&self) -> usize {
+        std::mem::size_of_val(self) + self.accumulator.size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expressions::{col, lit, BinaryExpr, Sum};
+    use arrow::array::Int64Array;
+    use arrow::datatypes::{DataType, Schema};
+    use arrow::record_batch::RecordBatch;
+    use datafusion_expr::Operator;
+
+    #[test]
+    fn filtered_sum() -> Result<()> {
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int64, true),
+            Field::new("b", DataType::Int64, true),
+        ]);
+        let batch = RecordBatch::try_new(
+            Arc::new(schema.clone()),
+            vec![
+                Arc::new(Int64Array::from(vec![Some(1), Some(2), Some(3), Some(4)])),
+                Arc::new(Int64Array::from(vec![Some(1), None, Some(3), Some(0)])),
+            ],
+        )?;
+        let sum = Arc::new(Sum::new(col("a", &schema)?, "sum", DataType::Int64));
+        let filter = Arc::new(BinaryExpr::new(
+            col("b", &schema)?,
+            Operator::Gt,
+            lit(0_i64),
+        ));
+        let filtered = FilteredAggregateExpr::new(sum, filter);
+        assert_eq!(filtered.expressions().len(), 2);
+
+        let values = filtered
+            .expressions()
+            .iter()
+            .map(|e| e.evaluate(&batch).map(|v| v.into_array(batch.num_rows())))
+            .collect::<Result<Vec<_>>>()?;
+
+        // the rows of a null or false filter are not summed
+        let mut accumulator = filtered.create_accumulator()?;
+        accumulator.update_batch(&values)?;
+        assert_eq!(accumulator.evaluate()?, ScalarValue::Int64(Some(4)));
+
+        // states are merged without filtering them
+        let states = accumulator
+            .state()?
+            .iter()
+            .map(|s| s.to_array())
+            .collect::<Vec<_>>();
+        let mut merged = filtered.create_accumulator()?;
+        merged.merge_batch(&states)?;
+        merged.merge_batch(&states)?;
+        assert_eq!(merged.evaluate()?, ScalarValue::Int64(Some(8)));
+        Ok(())
+    }
+}
@@ -0,0 +1,474 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines the FIRST_VALUE and LAST_VALUE aggregate expressions
+
+use crate::expressions::{format_state_name, Literal};
+use crate::{AggregateExpr, PhysicalExpr};
+use arrow::array::ArrayRef;
+use arrow::datatypes::{DataType, Field};
+use datafusion_common::{DataFusionError, Result, ScalarValue};
+use datafusion_expr::{Accumulator, AggregateState};
+use std::any::Any;
+use std::cmp::Ordering;
+use std::sync::Arc;
+
+/// The key the values of an ordered aggregate, such as
+/// `first_value(value, key, descending, nulls_first)`, are ordered by
+#[derive(Debug, Clone)]
+pub struct OrderKey {
+    /// The expression of the key
+    pub expr: Arc<dyn PhysicalExpr>,
+    /// The type of the key
+    pub data_type: DataType,
+    /// Whether the values are in descending order of their key
+    pub descending: bool,
+    /// Whether the values of null keys are first
+    pub nulls_first: bool,
+}
+
+impl OrderKey {
+    /// The key of the ordered aggregate of arguments `args`, that is the value
+    /// optionally followed by the key, whether the order is descending and
+    /// whether null keys are first, `None` if unordered
+    pub fn try_from_args(
+        args: &[Arc<dyn PhysicalExpr>],
+        types: &[DataType],
+    ) -> Result<Option<Self>> {
+        match (args, types) {
+            ([_], _) => Ok(None),
+            ([_, expr, descending, nulls_first], [_, data_type, ..]) => Ok(Some(Self {
+                expr: expr.clone(),
+                data_type: data_type.clone(),
+                descending: Self::flag(descending)?,
+                nulls_first: Self::flag(nulls_first)?,
+            })),
+            _ => Err(DataFusionError::Internal(format!(
+                "Unexpected {} arguments of an ordered aggregate",
+                args.len()
+            ))),
+        }
+    }
+
+    /// The value of the boolean literal `arg`
+    fn flag(arg: &Arc<dyn PhysicalExpr>) -> Result<bool> {
+        let literal = arg.as_any().downcast_ref::<Literal>();
+        match literal.map(|l| l.value()) {
+            Some(ScalarValue::Boolean(Some(flag))) => Ok(*flag),
+            _ => Err(DataFusionError::Plan(
+                "The ordering arguments of an ordered aggregate must be boolean \
+                 literals"
+                    .to_string(),
+            )),
+        }
+    }
+
+    /// Compares the keys `a` and `b` in this order
+    pub(crate) fn compare(&self, a: &ScalarValue, b: &ScalarValue) -> Ordering {
+        match (a.is_null(), b.is_null()) {
+            (true, true) => Ordering::Equal,
+            (true, false) if self.nulls_first => Ordering::Less,
+            (true, false) => Ordering::Greater,
+            (false, true) if self.nulls_first => Ordering::Greater,
+            (false, true) => Ordering::Less,
+            (false, false) => {
+                let ordering = a.partial_cmp(b).unwrap_or(Ordering::Equal);
+                if self.descending {
+                    ordering.reverse()
+                } else {
+                    ordering
+                }
+            }
+        }
+    }
+}
+
+/// FIRST_VALUE aggregate expression: the value of the first row of a group,
+/// either in the order the rows are read or ordered by a key
+#[derive(Debug)]
+pub struct FirstValue {
+    name: String,
+    data_type: DataType,
+    expr: Arc<dyn PhysicalExpr>,
+    order_key: Option<OrderKey>,
+}
+
+impl FirstValue {
+    /// Create a new FIRST_VALUE aggregate function
+    pub fn new(
+        expr: Arc<dyn PhysicalExpr>,
+        name: impl Into<String>,
+        data_type: DataType,
+        order_key: Option<OrderKey>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            data_type,
+            expr,
+            order_key,
+        }
+    }
+}
+
+/// LAST_VALUE aggregate expression: the value of the last row of a group,
+/// either in the order the rows are read or ordered by a key
+#[derive(Debug)]
+pub struct LastValue {
+    name: String,
+    data_type: DataType,
+    expr: Arc<dyn PhysicalExpr>,
+    order_key: Option<OrderKey>,
+}
+
+impl LastValue {
+    /// Create a new LAST_VALUE aggregate function
+    pub fn new(
+        expr: Arc<dyn PhysicalExpr>,
+        name: impl Into<String>,
+        data_type: DataType,
+        order_key: Option<OrderKey>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            data_type,
+            expr,
+            order_key,
+        }
+    }
+}
+
+macro_rules! first_last_aggregate_expr {
+    ($NAME:ident, $LAST:expr) => {
+        impl AggregateExpr for $NAME {
+            fn as_any(&self) -> &dyn Any {
+                self
+            }
+
+            fn field(&self) -> Result<Field> {
+                Ok(Field::new(&self.name, self.data_type.clone(), true))
+            }
+
+            fn create_accumulator(&self) -> Result<Box<dyn Accumulator>> {
+                Ok(Box::new(FirstLastAccumulator::try_new(
+                    &self.data_type,
+                    self.order_key.clone(),
+                    $LAST,
+                )?))
+            }
+
+            fn state_fields(&self) -> Result<Vec<Field>> {
+                let mut fields = vec![Field::new(
+                    &format_state_name(&self.name, "value"),
+                    self.data_type.clone(),
+                    true,
+                )];
+                if let Some(order_key) = &self.order_key {
+                    fields.push(Field::new(
+                        &format_state_name(&self.name, "key"),
+                        order_key.data_type.clone(),
+                        true,
+                    ));
+                }
+                fields.push(Field::new(
+                    &format_state_name(&self.name, "is_set"),
+                    DataType::Boolean,
+                    true,
+                ));
+                Ok(fields)
+            }
+
+            fn expressions(&self) -> Vec<Arc<dyn PhysicalExpr>> {
+                let mut exprs = vec![self.expr.clone()];
+                exprs.extend(self.order_key.iter().map(|key| key.expr.clone()));
+                exprs
+            }
+
+            fn name(&self) -> &str {
+                &self.name
+            }
+        }
+    };
+}
+
+first_last_aggregate_expr!(FirstValue, false);
+first_last_aggregate_expr!(LastValue, true);
+
+/// The accumulator of both FIRST_VALUE and LAST_VALUE, whose state is the
+/// value, its key if ordered, and whether a value was read
+#[derive(Debug)]
+struct FirstLastAccumulator {
+    value: ScalarValue,
+    key: ScalarValue,
+    is_set: bool,
+    order_key: Option<OrderKey>,
+    last: bool,
+}
+
+impl FirstLastAccumulator {
+    fn try_new(
+        data_type: &DataType,
+        order_key: Option<OrderKey>,
+        last: bool,
+    ) -> Result<Self> {
+        let key = match &order_key {
+            Some(order_key) => ScalarValue::try_from(&order_key.data_type)?,
+            None => ScalarValue::Null,
+        };
+        Ok(Self {
+            value: ScalarValue::try_from(data_type)?,
+            key,
+            is_set: false,
+            order_key,
+            last,
+        })
+    }
+
+    /// Whether the value of key `key` takes the place of the current one
+    fn replaces(&self, key: &ScalarValue) -> bool {
+        if !self.is_set {
+            return true;
+        }
+        match &self.order_key {
+            // values are read in order, so a later one only replaces the
+            // current one for LAST_VALUE
+            None => self.last,
+            // the first of equal keys is kept by FIRST_VALUE, the last by
+            // LAST_VALUE
+            Some(order_key) => match order_key.compare(key, &self.key) {
+                Ordering::Less => !self.last,
+                Ordering::Equal => self.last,
+                Ordering::Greater => self.last,
+            },
+        }
+    }
+
+    /// Reads the value at `index` of `values`, of key at `index` of `keys`
+    /// if ordered, if it takes the place of the current one
+    fn update(
+        &mut self,
+        values: &ArrayRef,
+        keys: Option<&ArrayRef>,
+        index: usize,
+    ) -> Result<()> {
+        let key = match keys {
+            Some(keys) => ScalarValue::try_from_array(keys, index)?,
+            None => ScalarValue::Null,
+        };
+        if self.replaces(&key) {
+            self.value = ScalarValue::try_from_array(values, index)?;
+            self.key = key;
+            self.is_set = true;
+        }
+        Ok(())
+    }
+}
+
+impl Accumulator for FirstLastAccumulator {
+    fn state(&self) -> Result<Vec<AggregateState>> {
+        let mut state = vec![AggregateState::Scalar(self.value.clone())];
+        if self.order_key.is_some() {
+            state.push(AggregateState::Scalar(self.key.clone()));
+        }
+        state.push(AggregateState::Scalar(ScalarValue::Boolean(Some(
+            self.is_set,
+        ))));
+        Ok(state)
+    }
+
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        let keys = self.order_key.as_ref().map(|_| &values[1]);
+        let values = &values[0];
+        match keys {
+            Some(_) => {
+                (0..values.len()).try_for_each(|index| self.update(values, keys, index))
+            }
+            // only the first or last row can be kept when unordered
+            None if values.is_empty() => Ok(()),
+            None if self.last => self.update(values, None, values.len() - 1),
+            None => self.update(values, None, 0),
+        }
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<()> {
+        let (values, keys, is_set) = match states {
+            [values, keys, is_set] => (values, Some(keys), is_set),
+            [values, is_set] => (values, None, is_set),
+            _ => {
+                return Err(DataFusionError::Internal(format!(
+                    "Unexpected {} states of first_value or last_value",
+                    states.len()
+                )))
+            }
+        };
+        // the partial states are merged in the order they are read, states
+        // without any value being skipped
+        (0..values.len()).try_for_each(|index| {
+            match ScalarValue::try_from_array(is_set, index)? {
+                ScalarValue::Boolean(Some(true)) => self.update(values, keys, index),
+                _ => Ok(()),
+            }
+        })
+    }
+
+    fn evaluate(&self) -> Result<ScalarValue> {
+        Ok(self.value.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expressions::{col, lit};
+    use arrow::array::Int64Array;
+    use arrow::datatypes::Schema;
+    use arrow::record_batch::RecordBatch;
+
+    fn ordered(
+        last: bool,
+        descending: bool,
+        nulls_first: bool,
+        schema: &Schema,
+    ) -> Result<Arc<dyn AggregateExpr>> {
+        let order_key = OrderKey::try_from_args(
+            &[
+                col("v", schema)?,
+                col("k", schema)?,
+                lit(descending),
+                lit(nulls_first),
+            ],
+            &[
+                DataType::Int64,
+                DataType::Int64,
+                DataType::Boolean,
+                DataType::Boolean,
+            ],
+        )?;
+        Ok(if last {
+            Arc::new(LastValue::new(
+                col("v", schema)?,
+                "last",
+                DataType::Int64,
+                order_key,
+            ))
+        } else {
+            Arc::new(FirstValue::new(
+                col("v", schema)?,
+                "first",
+                DataType::Int64,
+                order_key,
+            ))
+        })
+    }
+
+    /// Aggregates every batch of `partitions` with its own accumulator, as
+    /// a partial aggregate does, and merges their states
+    fn merged(
+        agg: &Arc<dyn AggregateExpr>,
+        partitions: &[RecordBatch],
+    ) -> Result<ScalarValue> {
+        let mut states: Vec<Vec<ArrayRef>> = vec![];
+        for batch in partitions {
+            let mut accumulator = agg.create_accumulator()?;
+            let values = agg
+                .expressions()
+                .iter()
+                .map(|e| e.evaluate(batch).map(|v| v.into_array(batch.num_rows())))
+                .collect::<Result<Vec<_>>>()?;
+            accumulator.update_batch(&values)?;
+            let state = accumulator.state()?;
+            for (i, state) in state.iter().enumerate() {
+                if states.len() <= i {
+                    states.push(vec![]);
+                }
+                states[i].push(state.to_array());
+            }
+        }
+        let states = states
+            .iter()
+            .map(|arrays| {
+                let arrays = arrays.iter().map(|a| a.as_ref()).collect::<Vec<_>>();
+                Ok(arrow::compute::concat(&arrays)?)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut accumulator = agg.create_accumulator()?;
+        accumulator.merge_batch(&states)?;
+        accumulator.evaluate()
+    }
+
+    #[test]
+    fn ordered_first_last_value_across_partitions() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("v", DataType::Int64, true),
+            Field::new("k", DataType::Int64, true),
+        ]));
+        let batch = |values: Vec<Option<i64>>, keys: Vec<Option<i64>>| {
+            RecordBatch::try_new(
+                schema.clone(),
+                vec![
+                    Arc::new(Int64Array::from(values)),
+                    Arc::new(Int64Array::from(keys)),
+                ],
+            )
+        };
+        // the extreme keys are in different partitions than the first and
+        // last rows read
+        let partitions = vec![
+            batch(vec![Some(1), Some(2)], vec![Some(5), None])?,
+            batch(vec![Some(3), None], vec![Some(1), Some(9)])?,
+            batch(vec![], vec![])?,
+            batch(vec![Some(4)], vec![Some(7)])?,
+        ];
+
+        let expected = [
+            // ascending, null keys last
+            (false, false, false, ScalarValue::Int64(Some(3))),
+            (true, false, false, ScalarValue::Int64(Some(2))),
+            // ascending, null keys first
+            (false, false, true, ScalarValue::Int64(Some(2))),
+            (true, false, true, ScalarValue::Int64(None)),
+            // descending, null keys first
+            (false, true, true, ScalarValue::Int64(Some(2))),
+            (true, true, true, ScalarValue::Int64(Some(3))),
+            // descending, null keys last
+            (false, true, false, ScalarValue::Int64(None)),
+            (true, true, false, ScalarValue::Int64(Some(2))),
+        ];
+        for (last, descending, nulls_first, expected) in expected {
+            let agg = ordered(last, descending, nulls_first, &schema)?;
+            assert_eq!(merged(&agg, &partitions)?, expected);
+        }
+
+        // unordered, the partial states are merged in order
+        let agg: Arc<dyn AggregateExpr> = Arc::new(FirstValue::new(
+            col("v", &schema)?,
+            "first",
+            DataType::Int64,
+            None,
+        ));
+        assert_eq!(merged(&agg, &partitions)?, ScalarValue::Int64(Some(1)));
+        let agg: Arc<dyn AggregateExpr> = Arc::new(LastValue::new(
+            col("v", &schema)?,
+            "last",
+            DataType::Int64,
+            None,
+        ));
+        assert_eq!(merged(&agg, &partitions)?, ScalarValue::Int64(Some(4)));
+
+        Ok(())
+    }
+}
@@ -19,20 +19,25 @@
 
 use crate::expressions::format_state_name;
 use crate::{AggregateExpr, PhysicalExpr};
-use arrow::array::{Array, ArrayRef, PrimitiveArray, PrimitiveBuilder};
-use arrow::compute::sort;
+use arrow::array::{make_array, Array, ArrayRef, ListArray, PrimitiveArray};
 use arrow::datatypes::{
-    ArrowPrimitiveType, DataType, Field, Float32Type, Float64Type, Int16Type, Int32Type,
-    Int64Type, Int8Type, UInt16Type, UInt32Type, UInt64Type, UInt8Type,
+    ArrowPrimitiveType, DataType, Date32Type, Date64Type, Field, Float32Type,
+    Float64Type, Int16Type, Int32Type, Int64Type, Int8Type, TimeUnit,
+    TimestampMicrosecondType, TimestampMillisecondType, TimestampNanosecondType,
+    TimestampSecondType, UInt16Type, UInt32Type, UInt64Type, UInt8Type,
 };
-use datafusion_common::{DataFusionError, Result, ScalarValue};
+use datafusion_common::{downcast_value, DataFusionError, Result, ScalarValue};
 use datafusion_expr::{Accumulator, AggregateState};
 use std::any::Any;
+use std::cmp::Ordering;
 use std::sync::Arc;
 
 /// MEDIAN aggregate expression. This uses a lot of memory because all values need to be
 /// stored in memory before a result can be computed. If an approximation is sufficient
 /// then APPROX_MEDIAN provides a much more efficient solution.
+///
+/// The median of an even number of values is the mean of the middle pair,
+/// truncated for integers, dates and timestamps.
 #[derive(Debug)]
 pub struct Median {
     name: String,
@@ -66,16 +71,64 @@ impl AggregateExpr for Median {
     }
 
     fn create_accumulator(&self) -> Result<Box<dyn Accumulator>> {
-        Ok(Box::new(MedianAccumulator {
-            data_type: self.data_type.clone(),
-            all_values: vec![],
-        }))
+        macro_rules! integer {
+            ($TY:ty, $NATIVE:ty) => {
+                Box::new(MedianAccumulator::<$TY>::new(
+                    &self.data_type,
+                    <$NATIVE as Ord>::cmp,
+                    |a, b| ((a as i128 + b as i128) / 2) as $NATIVE,
+                ))
+            };
+        }
+
+        let accumulator: Box<dyn Accumulator> = match &self.data_type {
+            DataType::Int8 => integer!(Int8Type, i8),
+            DataType::Int16 => integer!(Int16Type, i16),
+            DataType::Int32 => integer!(Int32Type, i32),
+            DataType::Int64 => integer!(Int64Type, i64),
+            DataType::UInt8 => integer!(UInt8Type, u8),
+            DataType::UInt16 => integer!(UInt16Type, u16),
+            DataType::UInt32 => integer!(UInt32Type, u32),
+            DataType::UInt64 => integer!(UInt64Type, u64),
+            DataType::Date32 => integer!(Date32Type, i32),
+            DataType::Date64 => integer!(Date64Type, i64),
+            DataType::Timestamp(TimeUnit::Second, _) => {
+                integer!(TimestampSecondType, i64)
+            }
+            DataType::Timestamp(TimeUnit::Millisecond, _) => {
+                integer!(TimestampMillisecondType, i64)
+            }
+            DataType::Timestamp(TimeUnit::Microsecond, _) => {
+                integer!(TimestampMicrosecondType, i64)
+            }
+            DataType::Timestamp(TimeUnit::Nanosecond, _) => {
+                integer!(TimestampNanosecondType, i64)
+            }
+            DataType::Float32 => Box::new(MedianAccumulator::<Float32Type>::new(
+                &self.data_type,
+                f32::total_cmp,
+                |a, b| (a + b) / 2.0,
+            )),
+            DataType::Float64 => Box::new(MedianAccumulator::<Float64Type>::new(
+                &self.data_type,
+                f64::total_cmp,
+                |a, b| (a + b) / 2.0,
+            )),
+            data_type => {
+                return Err(DataFusionError::Execution(format!(
+                    "unsupported data type {:?} for median",
+                    data_type
+                )))
+            }
+        };
+        Ok(accumulator)
     }
 
     fn state_fields(&self) -> Result<Vec<Field>> {
+        // the values buffered so far
         Ok(vec![Field::new(
             &format_state_name(&self.name, "median"),
-            self.data_type.clone(),
+            DataType::List(Box::new(Field::new("item", self.data_type.clone(), true))),
             true,
         )])
     }
@@ -89,155 +142,181 @@ impl AggregateExpr for Median {
     }
 }
 
+/// Buffers the non null values of a group, merging partial states by
+/// concatenating their values
 #[derive(Debug)]
-struct MedianAccumulator {
+struct MedianAccumulator<T: ArrowPrimitiveType + std::fmt::Debug> {
     data_type: DataType,
-    all_values: Vec<ArrayRef>,
+    all_values: Vec<T::Native>,
+    /// A total order of the values, which orders NaN after all numbers
+    compare: fn(&T::Native, &T::Native) -> Ordering,
+    /// The mean of two values
+    midpoint: fn(T::Native, T::Native) -> T::Native,
 }
 
-macro_rules! median {
-    ($SELF:ident, $TY:ty, $SCALAR_TY:ident, $TWO:expr) => {{
-        let combined = combine_arrays::<$TY>($SELF.all_values.as_slice())?;
-        if combined.is_empty() {
-            return Ok(ScalarValue::Null);
-        }
-        let sorted = sort(&combined, None)?;
-        let array = sorted
-            .as_any()
-            .downcast_ref::<PrimitiveArray<$TY>>()
-            .ok_or(DataFusionError::Internal(
-                "median! macro failed to cast array to expected type".to_string(),
-            ))?;
-        let len = sorted.len();
-        let mid = len / 2;
-        if len % 2 == 0 {
-            Ok(ScalarValue::$SCALAR_TY(Some(
-                (array.value(mid - 1) + array.value(mid)) / $TWO,
-            )))
-        } else {
-            Ok(ScalarValue::$SCALAR_TY(Some(array.value(mid))))
+impl<T: ArrowPrimitiveType + std::fmt::Debug> MedianAccumulator<T> {
+    fn new(
+        data_type: &DataType,
+        compare: fn(&T::Native, &T::Native) -> Ordering,
+        midpoint: fn(T::Native, T::Native) -> T::Native,
+    ) -> Self {
+        Self {
+            data_type: data_type.clone(),
+            all_values: vec![],
+            compare,
+            midpoint,
         }
-    }};
+    }
+
+    /// Appends the non null values of `array` to the buffered values
+    fn append(&mut self, array: &ArrayRef) -> Result<()> {
+        let array = downcast_value!(array, PrimitiveArray, T);
+        self.all_values.extend(array.iter().flatten());
+        Ok(())
+    }
+}
+
+/// An array of `values` of type `data_type`, such as a timestamp type with
+/// a time zone, which the array is built without
+pub(crate) fn primitive_array<T: ArrowPrimitiveType>(
+    data_type: &DataType,
+    values: impl IntoIterator<Item = T::Native>,
+) -> Result<ArrayRef> {
+    let array = PrimitiveArray::<T>::from_iter_values(values);
+    let data = array
+        .data()
+        .clone()
+        .into_builder()
+        .data_type(data_type.clone())
+        .build()?;
+    Ok(make_array(data))
 }
 
-impl Accumulator for MedianAccumulator {
+/// A list of `values` of type `data_type`
+pub(crate) fn primitive_list<T: ArrowPrimitiveType>(
+    data_type: &DataType,
+    values: impl IntoIterator<Item = T::Native>,
+) -> Result<ScalarValue> {
+    let array = primitive_array::<T>(data_type, values)?;
+    let values = (0..array.len())
+        .map(|index| ScalarValue::try_from_array(&array, index))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(ScalarValue::new_list(Some(values), data_type.clone()))
+}
+
+impl<T: ArrowPrimitiveType + std::fmt::Debug> Accumulator for MedianAccumulator<T> {
     fn state(&self) -> Result<Vec<AggregateState>> {
-        let mut vec: Vec<AggregateState> = self
-            .all_values
-            .iter()
-            .map(|v| AggregateState::Array(v.clone()))
-            .collect();
-        if vec.is_empty() {
-            match self.data_type {
-                DataType::UInt8 => vec.push(empty_array::<UInt8Type>()),
-                DataType::UInt16 => vec.push(empty_array::<UInt16Type>()),
-                DataType::UInt32 => vec.push(empty_array::<UInt32Type>()),
-                DataType::UInt64 => vec.push(empty_array::<UInt64Type>()),
-                DataType::Int8 => vec.push(empty_array::<Int8Type>()),
-                DataType::Int16 => vec.push(empty_array::<Int16Type>()),
-                DataType::Int32 => vec.push(empty_array::<Int32Type>()),
-                DataType::Int64 => vec.push(empty_array::<Int64Type>()),
-                DataType::Float32 => vec.push(empty_array::<Float32Type>()),
-                DataType::Float64 => vec.push(empty_array::<Float64Type>()),
-                _ => {
-                    return Err(DataFusionError::Execution(
-                        "unsupported data type for median".to_string(),
-                    ))
-                }
-            }
-        }
-        Ok(vec)
+        Ok(vec![AggregateState::Scalar(primitive_list::<T>(
+            &self.data_type,
+            self.all_values.iter().copied(),
+        )?)])
     }
 
     fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
-        let x = values[0].clone();
-        self.all_values.extend_from_slice(&[x]);
-        Ok(())
+        self.append(&values[0])
     }
 
     fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<()> {
-        for array in states {
-            self.all_values.extend_from_slice(&[array.clone()]);
-        }
-        Ok(())
+        let lists = downcast_value!(states[0], ListArray);
+        lists
+            .iter()
+            .flatten()
+            .try_for_each(|values| self.append(&values))
     }
 
     fn evaluate(&self) -> Result<ScalarValue> {
-        match self.all_values[0].data_type() {
-            DataType::Int8 => median!(self, arrow::datatypes::Int8Type, Int8, 2),
-            DataType::Int16 => median!(self, arrow::datatypes::Int16Type, Int16, 2),
-            DataType::Int32 => median!(self, arrow::datatypes::Int32Type, Int32, 2),
-            DataType::Int64 => median!(self, arrow::datatypes::Int64Type, Int64, 2),
-            DataType::UInt8 => median!(self, arrow::datatypes::UInt8Type, UInt8, 2),
-            DataType::UInt16 => median!(self, arrow::datatypes::UInt16Type, UInt16, 2),
-            DataType::UInt32 => median!(self, arrow::datatypes::UInt32Type, UInt32, 2),
-            DataType::UInt64 => median!(self, arrow::datatypes::UInt64Type, UInt64, 2),
-            DataType::Float32 => {
-                median!(self, arrow::datatypes::Float32Type, Float32, 2_f32)
-            }
-            DataType::Float64 => {
-                median!(self, arrow::datatypes::Float64Type, Float64, 2_f64)
-            }
-            _ => Err(DataFusionError::Execution(
-                "unsupported data type for median".to_string(),
-            )),
+        // the median of a group without any non null value is null
+        if self.all_values.is_empty() {
+            return ScalarValue::try_from(&self.data_type);
         }
-    }
-}
 
-/// Create an empty array
-fn empty_array<T: ArrowPrimitiveType>() -> AggregateState {
-    AggregateState::Array(Arc::new(PrimitiveBuilder::<T>::with_capacity(0).finish()))
-}
+        let mut values = self.all_values.clone();
+        let (mid, even) = (values.len() / 2, values.len() % 2 == 0);
+        let (lower, median, _) = values.select_nth_unstable_by(mid, self.compare);
+        let median = if even {
+            // the mean of the middle pair, whose other value is the largest
+            // of the lower half
+            let other = *lower
+                .iter()
+                .max_by(|a, b| (self.compare)(*a, *b))
+                .expect("the lower half of an even number of values");
+            (self.midpoint)(other, *median)
+        } else {
+            *median
+        };
+        ScalarValue::try_from_array(&primitive_array::<T>(&self.data_type, [median])?, 0)
+    }
 
-/// Combine all non-null values from provided arrays into a single array
-fn combine_arrays<T: ArrowPrimitiveType>(arrays: &[ArrayRef]) -> Result<ArrayRef> {
-    let len = arrays.iter().map(|a| a.len() - a.null_count()).sum();
-    let mut builder: PrimitiveBuilder<T> = PrimitiveBuilder::with_capacity(len);
-    for array in arrays {
-        let array = array
-            .as_any()
-            .downcast_ref::<PrimitiveArray<T>>()
-            .ok_or_else(|| {
-                DataFusionError::Internal(
-                    "combine_arrays failed to cast array to expected type".to_string(),
-                )
-            })?;
-        for i in 0..array.len() {
-            if !array.is_null(i) {
-                builder.append_value(array.value(i));
-            }
-        }
+    fn size(&self) -> usize {
+        std::mem::size_of_val(self)
+            + self.all_values.capacity() * std::mem::size_of::<T::Native>()
     }
-    Ok(Arc::new(builder.finish()))
 }
 
 #[cfg(test)]
-mod test {
-    use crate::aggregate::median::combine_arrays;
-    use arrow::array::{Int32Array, UInt32Array};
-    use arrow::datatypes::{Int32Type, UInt32Type};
-    use datafusion_common::Result;
-    use std::sync::Arc;
+mod tests {
+    use super::*;
+    use crate::expressions::col;
+    use arrow::array::{Int64Array, TimestampSecondArray};
+    use arrow::datatypes::Schema;
+
+    fn accumulator(data_type: DataType) -> Result<Box<dyn Accumulator>> {
+        let schema = Schema::new(vec![Field::new("a", data_type.clone(), true)]);
+        Median::new(col("a", &schema)?, "median", data_type).create_accumulator()
+    }
 
     #[test]
-    fn combine_i32_array() -> Result<()> {
-        let a = Arc::new(Int32Array::from(vec![1, 2, 3]));
-        let b = combine_arrays::<Int32Type>(&[a.clone(), a])?;
-        assert_eq!(
-            "PrimitiveArray<Int32>\n[\n  1,\n  2,\n  3,\n  1,\n  2,\n  3,\n]",
-            format!("{:?}", b)
-        );
+    fn median_even_and_odd() -> Result<()> {
+        let mut median = accumulator(DataType::Int64)?;
+        let values: ArrayRef =
+            Arc::new(Int64Array::from(vec![Some(7), None, Some(1), Some(4)]));
+        median.update_batch(&[values])?;
+        assert_eq!(median.evaluate()?, ScalarValue::Int64(Some(4)));
+
+        // the mean of 4 and 7, truncated
+        let values: ArrayRef = Arc::new(Int64Array::from(vec![10]));
+        median.update_batch(&[values])?;
+        assert_eq!(median.evaluate()?, ScalarValue::Int64(Some(5)));
         Ok(())
     }
 
     #[test]
-    fn combine_u32_array() -> Result<()> {
-        let a = Arc::new(UInt32Array::from(vec![1, 2, 3]));
-        let b = combine_arrays::<UInt32Type>(&[a.clone(), a])?;
+    fn median_merge_states() -> Result<()> {
+        let mut first = accumulator(DataType::Int64)?;
+        first.update_batch(&[Arc::new(Int64Array::from(vec![1, 9])) as ArrayRef])?;
+        let mut second = accumulator(DataType::Int64)?;
+        second.update_batch(&[Arc::new(Int64Array::from(vec![3, 5])) as ArrayRef])?;
+
+        let mut merged = accumulator(DataType::Int64)?;
+        for partial in [first, second] {
+            let states = partial
+                .state()?
+                .iter()
+                .map(|state| state.to_array())
+                .collect::<Vec<_>>();
+            merged.merge_batch(&states)?;
+        }
+        assert_eq!(merged.evaluate()?, ScalarValue::Int64(Some(4)));
+        Ok(())
+    }
+
+    #[test]
+    fn median_all_nulls() -> Result<()> {
+        let data_type = DataType::Timestamp(TimeUnit::Second, Some("UTC".to_string()));
+        let mut median = accumulator(data_type)?;
+        assert_eq!(
+            median.evaluate()?,
+            ScalarValue::TimestampSecond(None, Some("UTC".to_string()))
+        );
+
+        let values: ArrayRef = Arc::new(
+            TimestampSecondArray::from(vec![Some(30), None, Some(10)])
+                .with_timezone("UTC".to_string()),
+        );
+        median.update_batch(&[values])?;
         assert_eq!(
-            "PrimitiveArray<UInt32>\n[\n  1,\n  2,\n  3,\n  1,\n  2,\n  3,\n]",
-            format!("{:?}", b)
+            median.evaluate()?,
+            ScalarValue::TimestampSecond(Some(20), Some("UTC".to_string()))
         );
         Ok(())
     }
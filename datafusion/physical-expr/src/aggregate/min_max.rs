@@ -18,6 +18,7 @@
 //! Defines physical expressions that can evaluated at runtime during query execution
 
 use std::any::Any;
+use std::cmp::Ordering;
 use std::convert::TryFrom;
 use std::sync::Arc;
 
@@ -140,6 +141,14 @@ impl AggregateExpr for Max {
             self.data_type.clone(),
         )))
     }
+
+    fn convert_to_state_supported(&self) -> bool {
+        true
+    }
+
+    fn convert_to_state(&self, values: &[ArrayRef]) -> Result<Vec<ArrayRef>> {
+        Ok(vec![compute::cast(&values[0], &self.data_type)?])
+    }
 }
 
 // Statically-typed version of min/max(array) -> ScalarValue for string types.
@@ -265,6 +274,31 @@ macro_rules! min_max_batch {
     }};
 }
 
+/// Returns `rhs` if `lhs` is null or compares to `rhs` with `ordering`, and
+/// `lhs` otherwise. Nested values have no arrow kernels, so they are
+/// compared as [`ScalarValue`]s.
+fn choose_nested(
+    lhs: &ScalarValue,
+    rhs: &ScalarValue,
+    ordering: Ordering,
+) -> ScalarValue {
+    if !rhs.is_null() && (lhs.is_null() || lhs.partial_cmp(rhs) == Some(ordering)) {
+        rhs.clone()
+    } else {
+        lhs.clone()
+    }
+}
+
+/// min/max(array) -> ScalarValue for lists and structs, comparing row by row
+fn min_max_batch_nested(values: &ArrayRef, ordering: Ordering) -> Result<ScalarValue> {
+    let mut result = ScalarValue::try_from(values.data_type())?;
+    for index in 0..values.len() {
+        let value = ScalarValue::try_from_array(values, index)?;
+        result = choose_nested(&result, &value, ordering);
+    }
+    Ok(result)
+}
+
 /// dynamically-typed min(array) -> ScalarValue
 fn min_batch(values: &ArrayRef) -> Result<ScalarValue> {
     Ok(match values.data_type() {
@@ -274,6 +308,9 @@ fn min_batch(values: &ArrayRef) -> Result<ScalarValue> {
         DataType::LargeUtf8 => {
             typed_min_max_batch_string!(values, LargeStringArray, LargeUtf8, min_string)
         }
+        DataType::List(_) | DataType::Struct(_) => {
+            min_max_batch_nested(values, Ordering::Greater)?
+        }
         _ => min_max_batch!(values, min),
     })
 }
@@ -287,6 +324,9 @@ fn max_batch(values: &ArrayRef) -> Result<ScalarValue> {
         DataType::LargeUtf8 => {
             typed_min_max_batch_string!(values, LargeStringArray, LargeUtf8, max_string)
         }
+        DataType::List(_) | DataType::Struct(_) => {
+            min_max_batch_nested(values, Ordering::Less)?
+        }
         _ => min_max_batch!(values, max),
     })
 }
@@ -330,6 +370,16 @@ macro_rules! typed_min_max_string {
     }};
 }
 
+// min/max of two scalar values of a nested type
+macro_rules! typed_min_max_nested {
+    ($VALUE:expr, $DELTA:expr, min) => {{
+        choose_nested($VALUE, $DELTA, Ordering::Greater)
+    }};
+    ($VALUE:expr, $DELTA:expr, max) => {{
+        choose_nested($VALUE, $DELTA, Ordering::Less)
+    }};
+}
+
 // min/max of two scalar values of the same type
 macro_rules! min_max {
     ($VALUE:expr, $DELTA:expr, $OP:ident) => {{
@@ -422,6 +472,10 @@ macro_rules! min_max {
             ) => {
                 typed_min_max!(lhs, rhs, Time64, $OP)
             }
+            (lhs @ ScalarValue::List(_, _), rhs @ ScalarValue::List(_, _))
+            | (lhs @ ScalarValue::Struct(_, _), rhs @ ScalarValue::Struct(_, _)) => {
+                typed_min_max_nested!(lhs, rhs, $OP)
+            }
             e => {
                 return Err(DataFusionError::Internal(format!(
                     "MIN/MAX is not expected to receive scalars of incompatible types {:?}",
@@ -656,6 +710,14 @@ impl AggregateExpr for Min {
             self.data_type.clone(),
         )))
     }
+
+    fn convert_to_state_supported(&self) -> bool {
+        true
+    }
+
+    fn convert_to_state(&self, values: &[ArrayRef]) -> Result<Vec<ArrayRef>> {
+        Ok(vec![compute::cast(&values[0], &self.data_type)?])
+    }
 }
 
 /// An accumulator to compute the minimum value
@@ -742,6 +804,7 @@ mod tests {
     use crate::expressions::col;
     use crate::expressions::tests::aggregate;
     use crate::generic_test_op;
+    use arrow::array::ListArray;
     use arrow::datatypes::*;
     use arrow::record_batch::RecordBatch;
     use datafusion_common::Result;
@@ -1093,4 +1156,67 @@ mod tests {
             ScalarValue::Time64(Some(5))
         )
     }
+
+    #[test]
+    fn min_max_list() -> Result<()> {
+        let a: ArrayRef =
+            Arc::new(ListArray::from_iter_primitive::<Int64Type, _, _>(vec![
+                Some(vec![Some(2), Some(1)]),
+                None,
+                Some(vec![Some(1), Some(5)]),
+                Some(vec![Some(2)]),
+            ]));
+        let data_type =
+            DataType::List(Box::new(Field::new("item", DataType::Int64, true)));
+        generic_test_op!(
+            a.clone(),
+            data_type.clone(),
+            Min,
+            ScalarValue::new_list(
+                Some(vec![
+                    ScalarValue::Int64(Some(1)),
+                    ScalarValue::Int64(Some(5))
+                ]),
+                DataType::Int64
+            )
+        )?;
+        generic_test_op!(
+            a,
+            data_type,
+            Max,
+            ScalarValue::new_list(
+                Some(vec![
+                    ScalarValue::Int64(Some(2)),
+                    ScalarValue::Int64(Some(1))
+                ]),
+                DataType::Int64
+            )
+        )
+    }
+
+    #[test]
+    fn min_max_struct() -> Result<()> {
+        let fields = vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Utf8, true),
+        ];
+        let struct_value = |a: i32, b: &str| {
+            ScalarValue::Struct(
+                Some(vec![
+                    ScalarValue::Int32(Some(a)),
+                    ScalarValue::Utf8(Some(b.to_string())),
+                ]),
+                Box::new(fields.clone()),
+            )
+        };
+
+        let left = struct_value(1, "y");
+        let right = struct_value(1, "z");
+        let null = ScalarValue::Struct(None, Box::new(fields.clone()));
+        assert_eq!(min(&left, &right)?, left);
+        assert_eq!(max(&left, &right)?, right);
+        assert_eq!(min(&null, &right)?, right);
+        assert_eq!(max(&left, &null)?, left);
+        Ok(())
+    }
 }
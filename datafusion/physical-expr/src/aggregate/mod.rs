@@ -17,6 +17,7 @@
 
 use crate::aggregate::row_accumulator::RowAccumulator;
 use crate::PhysicalExpr;
+use arrow::array::ArrayRef;
 use arrow::datatypes::Field;
 use datafusion_common::{DataFusionError, Result};
 use datafusion_expr::Accumulator;
@@ -30,13 +31,19 @@ pub(crate) mod approx_percentile_cont;
 pub(crate) mod approx_percentile_cont_with_weight;
 pub(crate) mod array_agg;
 pub(crate) mod array_agg_distinct;
+pub(crate) mod array_agg_ordered;
 pub(crate) mod average;
+pub(crate) mod bit_and_or_xor;
+pub(crate) mod bool_and_or;
 pub(crate) mod correlation;
 pub(crate) mod count;
 pub(crate) mod count_distinct;
 pub(crate) mod covariance;
+pub(crate) mod filtered;
+pub(crate) mod first_last;
 pub(crate) mod grouping;
 pub(crate) mod median;
+pub(crate) mod mode;
 #[macro_use]
 pub(crate) mod min_max;
 pub mod build_in;
@@ -44,6 +51,7 @@ mod hyperloglog;
 pub mod row_accumulator;
 pub(crate) mod stats;
 pub(crate) mod stddev;
+pub(crate) mod string_agg;
 pub(crate) mod sum;
 pub(crate) mod sum_distinct;
 mod tdigest;
@@ -68,6 +76,13 @@ pub trait AggregateExpr: Send + Sync + Debug {
     /// return states with the same description as `state_fields`
     fn create_accumulator(&self) -> Result<Box<dyn Accumulator>>;
 
+    /// the accumulator used to aggregate the rows of a sliding window frame,
+    /// from which the rows leaving the frame are retracted. Defaults to the
+    /// accumulator of [`Self::create_accumulator`].
+    fn create_sliding_accumulator(&self) -> Result<Box<dyn Accumulator>> {
+        self.create_accumulator()
+    }
+
     /// the fields that encapsulate the Accumulator's state
     /// the number of fields here equals the number of states that the accumulator contains
     fn state_fields(&self) -> Result<Vec<Field>>;
@@ -101,4 +116,22 @@ pub trait AggregateExpr: Send + Sync + Debug {
             self
         )))
     }
+
+    /// If the aggregate expression supports [`Self::convert_to_state`]
+    fn convert_to_state_supported(&self) -> bool {
+        false
+    }
+
+    /// Converts the values of `expressions` for a batch into the states of
+    /// one group per row, each as if its accumulator was only updated with
+    /// that row. The states are described by `state_fields`.
+    ///
+    /// This lets a partial aggregation pass its input rows on to the final
+    /// aggregation without grouping them.
+    fn convert_to_state(&self, _values: &[ArrayRef]) -> Result<Vec<ArrayRef>> {
+        Err(DataFusionError::NotImplemented(format!(
+            "Converting values to states hasn't been implemented for {:?} yet",
+            self
+        )))
+    }
 }
@@ -0,0 +1,303 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines the MODE aggregate expression
+
+use crate::aggregate::median::{primitive_array, primitive_list};
+use crate::expressions::format_state_name;
+use crate::{AggregateExpr, PhysicalExpr};
+use arrow::array::{Array, ArrayRef, ListArray, PrimitiveArray, UInt64Array};
+use arrow::datatypes::{
+    ArrowPrimitiveType, DataType, Date32Type, Date64Type, Field, Float32Type,
+    Float64Type, Int16Type, Int32Type, Int64Type, Int8Type, TimeUnit,
+    TimestampMicrosecondType, TimestampMillisecondType, TimestampNanosecondType,
+    TimestampSecondType, UInt16Type, UInt32Type, UInt64Type, UInt8Type,
+};
+use datafusion_common::{downcast_value, DataFusionError, Result, ScalarValue};
+use datafusion_expr::{Accumulator, AggregateState};
+use std::any::Any;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// MODE aggregate expression: the most frequent non null value of a group,
+/// the smallest of them if several are. The number of occurrences of every
+/// distinct value is kept in memory until the result is computed.
+#[derive(Debug)]
+pub struct Mode {
+    name: String,
+    expr: Arc<dyn PhysicalExpr>,
+    data_type: DataType,
+}
+
+impl Mode {
+    /// Create a new MODE aggregate function
+    pub fn new(
+        expr: Arc<dyn PhysicalExpr>,
+        name: impl Into<String>,
+        data_type: DataType,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            expr,
+            data_type,
+        }
+    }
+}
+
+impl AggregateExpr for Mode {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn field(&self) -> Result<Field> {
+        Ok(Field::new(&self.name, self.data_type.clone(), true))
+    }
+
+    fn create_accumulator(&self) -> Result<Box<dyn Accumulator>> {
+        macro_rules! integer {
+            ($TY:ty, $NATIVE:ty) => {
+                Box::new(ModeAccumulator::<$TY>::new(
+                    &self.data_type,
+                    <$NATIVE as Ord>::cmp,
+                    |v| v as u64,
+                ))
+            };
+        }
+
+        let accumulator: Box<dyn Accumulator> = match &self.data_type {
+            DataType::Int8 => integer!(Int8Type, i8),
+            DataType::Int16 => integer!(Int16Type, i16),
+            DataType::Int32 => integer!(Int32Type, i32),
+            DataType::Int64 => integer!(Int64Type, i64),
+            DataType::UInt8 => integer!(UInt8Type, u8),
+            DataType::UInt16 => integer!(UInt16Type, u16),
+            DataType::UInt32 => integer!(UInt32Type, u32),
+            DataType::UInt64 => integer!(UInt64Type, u64),
+            DataType::Date32 => integer!(Date32Type, i32),
+            DataType::Date64 => integer!(Date64Type, i64),
+            DataType::Timestamp(TimeUnit::Second, _) => {
+                integer!(TimestampSecondType, i64)
+            }
+            DataType::Timestamp(TimeUnit::Millisecond, _) => {
+                integer!(TimestampMillisecondType, i64)
+            }
+            DataType::Timestamp(TimeUnit::Microsecond, _) => {
+                integer!(TimestampMicrosecondType, i64)
+            }
+            DataType::Timestamp(TimeUnit::Nanosecond, _) => {
+                integer!(TimestampNanosecondType, i64)
+            }
+            DataType::Float32 => Box::new(ModeAccumulator::<Float32Type>::new(
+                &self.data_type,
+                f32::total_cmp,
+                |v| v.to_bits() as u64,
+            )),
+            DataType::Float64 => Box::new(ModeAccumulator::<Float64Type>::new(
+                &self.data_type,
+                f64::total_cmp,
+                f64::to_bits,
+            )),
+            data_type => {
+                return Err(DataFusionError::Execution(format!(
+                    "unsupported data type {:?} for mode",
+                    data_type
+                )))
+            }
+        };
+        Ok(accumulator)
+    }
+
+    fn state_fields(&self) -> Result<Vec<Field>> {
+        // the distinct values and their number of occurrences
+        let list = |data_type: &DataType| {
+            DataType::List(Box::new(Field::new("item", data_type.clone(), true)))
+        };
+        Ok(vec![
+            Field::new(
+                &format_state_name(&self.name, "mode_values"),
+                list(&self.data_type),
+                true,
+            ),
+            Field::new(
+                &format_state_name(&self.name, "mode_counts"),
+                list(&DataType::UInt64),
+                true,
+            ),
+        ])
+    }
+
+    fn expressions(&self) -> Vec<Arc<dyn PhysicalExpr>> {
+        vec![self.expr.clone()]
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// Counts the occurrences of the non null values of a group, merging partial
+/// states by adding up their counts
+#[derive(Debug)]
+struct ModeAccumulator<T: ArrowPrimitiveType + std::fmt::Debug> {
+    data_type: DataType,
+    /// The distinct values and their number of occurrences, keyed by the
+    /// bits of the value
+    counts: HashMap<u64, (T::Native, u64)>,
+    /// A total order of the values, which breaks ties
+    compare: fn(&T::Native, &T::Native) -> Ordering,
+    /// The bits of a value, the same for equal values
+    key: fn(T::Native) -> u64,
+}
+
+impl<T: ArrowPrimitiveType + std::fmt::Debug> ModeAccumulator<T> {
+    fn new(
+        data_type: &DataType,
+        compare: fn(&T::Native, &T::Native) -> Ordering,
+        key: fn(T::Native) -> u64,
+    ) -> Self {
+        Self {
+            data_type: data_type.clone(),
+            counts: HashMap::new(),
+            compare,
+            key,
+        }
+    }
+
+    fn add(&mut self, value: T::Native, count: u64) {
+        self.counts.entry((self.key)(value)).or_insert((value, 0)).1 += count;
+    }
+}
+
+impl<T: ArrowPrimitiveType + std::fmt::Debug> Accumulator for ModeAccumulator<T> {
+    fn state(&self) -> Result<Vec<AggregateState>> {
+        let (values, counts): (Vec<_>, Vec<_>) = self.counts.values().copied().unzip();
+        Ok(vec![
+            AggregateState::Scalar(primitive_list::<T>(&self.data_type, values)?),
+            AggregateState::Scalar(primitive_list::<UInt64Type>(
+                &DataType::UInt64,
+                counts,
+            )?),
+        ])
+    }
+
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        let values = downcast_value!(values[0], PrimitiveArray, T);
+        values.iter().flatten().for_each(|value| self.add(value, 1));
+        Ok(())
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<()> {
+        let values = downcast_value!(states[0], ListArray);
+        let counts = downcast_value!(states[1], ListArray);
+        for (values, counts) in values.iter().zip(counts.iter()) {
+            if let (Some(values), Some(counts)) = (values, counts) {
+                let values = downcast_value!(values, PrimitiveArray, T);
+                let counts = downcast_value!(counts, UInt64Array);
+                for (value, count) in values.iter().zip(counts.iter()) {
+                    if let (Some(value), Some(count)) = (value, count) {
+                        self.add(value, count);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn evaluate(&self) -> Result<ScalarValue> {
+        // the most frequent value, the smallest of them on ties
+        let mode =
+            self.counts.values().copied().reduce(|mode, candidate| {
+                match candidate
+                    .1
+                    .cmp(&mode.1)
+                    .then_with(|| (self.compare)(&mode.0, &candidate.0))
+                {
+                    Ordering::Greater => candidate,
+                    _ => mode,
+                }
+            });
+        match mode {
+            Some((value, _)) => ScalarValue::try_from_array(
+                &primitive_array::<T>(&self.data_type, [value])?,
+                0,
+            ),
+            // the mode of a group without any non null value is null
+            None => ScalarValue::try_from(&self.data_type),
+        }
+    }
+
+    fn size(&self) -> usize {
+        std::mem::size_of_val(self)
+            + self.counts.capacity() * std::mem::size_of::<(u64, (T::Native, u64))>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expressions::col;
+    use arrow::array::{Float64Array, Int32Array};
+    use arrow::datatypes::Schema;
+
+    fn accumulator(data_type: DataType) -> Result<Box<dyn Accumulator>> {
+        let schema = Schema::new(vec![Field::new("a", data_type.clone(), true)]);
+        Mode::new(col("a", &schema)?, "mode", data_type).create_accumulator()
+    }
+
+    #[test]
+    fn mode_ties() -> Result<()> {
+        let mut mode = accumulator(DataType::Int32)?;
+        assert_eq!(mode.evaluate()?, ScalarValue::Int32(None));
+
+        // 3 and -1 occur twice, the smallest of them is the mode
+        let values: ArrayRef = Arc::new(Int32Array::from(vec![
+            Some(3),
+            None,
+            Some(-1),
+            Some(3),
+            None,
+            Some(-1),
+            Some(7),
+        ]));
+        mode.update_batch(&[values])?;
+        assert_eq!(mode.evaluate()?, ScalarValue::Int32(Some(-1)));
+        Ok(())
+    }
+
+    #[test]
+    fn mode_merge_states() -> Result<()> {
+        let mut first = accumulator(DataType::Float64)?;
+        let values: ArrayRef = Arc::new(Float64Array::from(vec![1.5, 1.5, 2.5]));
+        first.update_batch(&[values])?;
+        let mut second = accumulator(DataType::Float64)?;
+        let values: ArrayRef = Arc::new(Float64Array::from(vec![2.5, 2.5, 0.5]));
+        second.update_batch(&[values])?;
+
+        let mut merged = accumulator(DataType::Float64)?;
+        for partial in [first, second] {
+            let states = partial
+                .state()?
+                .iter()
+                .map(|state| state.to_array())
+                .collect::<Vec<_>>();
+            merged.merge_batch(&states)?;
+        }
+        assert_eq!(merged.evaluate()?, ScalarValue::Float64(Some(2.5)));
+        Ok(())
+    }
+}
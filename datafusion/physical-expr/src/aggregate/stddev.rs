@@ -192,6 +192,10 @@ impl Accumulator for StddevAccumulator {
         self.variance.update_batch(values)
     }
 
+    fn retract_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        self.variance.retract_batch(values)
+    }
+
     fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<()> {
         self.variance.merge_batch(states)
     }
@@ -310,8 +314,8 @@ mod tests {
             "bla".to_string(),
             DataType::Float64,
         ));
-        let actual = aggregate(&batch, agg);
-        assert!(actual.is_err());
+        let actual = aggregate(&batch, agg)?;
+        assert_eq!(actual, ScalarValue::Float64(None));
 
         Ok(())
     }
@@ -344,8 +348,8 @@ mod tests {
             "bla".to_string(),
             DataType::Float64,
         ));
-        let actual = aggregate(&batch, agg);
-        assert!(actual.is_err());
+        let actual = aggregate(&batch, agg)?;
+        assert_eq!(actual, ScalarValue::Float64(None));
 
         Ok(())
     }
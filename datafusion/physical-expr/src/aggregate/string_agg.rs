@@ -0,0 +1,151 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines the STRING_AGG aggregate expression
+
+use crate::expressions::{format_state_name, Literal};
+use crate::{AggregateExpr, PhysicalExpr};
+use arrow::array::ArrayRef;
+use arrow::datatypes::{DataType, Field};
+use datafusion_common::{DataFusionError, Result, ScalarValue};
+use datafusion_expr::{Accumulator, AggregateState};
+use std::any::Any;
+use std::sync::Arc;
+
+/// STRING_AGG aggregate expression: the non null values of a group
+/// concatenated, separated by a delimiter
+#[derive(Debug)]
+pub struct StringAgg {
+    name: String,
+    data_type: DataType,
+    expr: Arc<dyn PhysicalExpr>,
+    delimiter: String,
+}
+
+impl StringAgg {
+    /// Create a new STRING_AGG aggregate function of arguments `args`, the
+    /// value and the delimiter, which must be a string literal. A null
+    /// delimiter concatenates the values without separating them.
+    pub fn try_new(
+        args: &[Arc<dyn PhysicalExpr>],
+        name: impl Into<String>,
+        data_type: DataType,
+    ) -> Result<Self> {
+        let delimiter = match args[1]
+            .as_any()
+            .downcast_ref::<Literal>()
+            .map(|l| l.value())
+        {
+            Some(ScalarValue::Utf8(delimiter)) => delimiter.clone().unwrap_or_default(),
+            _ => {
+                return Err(DataFusionError::Plan(
+                    "The delimiter of string_agg must be a string literal".to_string(),
+                ))
+            }
+        };
+        Ok(Self {
+            name: name.into(),
+            data_type,
+            expr: args[0].clone(),
+            delimiter,
+        })
+    }
+}
+
+impl AggregateExpr for StringAgg {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn field(&self) -> Result<Field> {
+        Ok(Field::new(&self.name, self.data_type.clone(), true))
+    }
+
+    fn create_accumulator(&self) -> Result<Box<dyn Accumulator>> {
+        Ok(Box::new(StringAggAccumulator {
+            value: None,
+            data_type: self.data_type.clone(),
+            delimiter: self.delimiter.clone(),
+        }))
+    }
+
+    fn state_fields(&self) -> Result<Vec<Field>> {
+        Ok(vec![Field::new(
+            &format_state_name(&self.name, "string_agg"),
+            self.data_type.clone(),
+            true,
+        )])
+    }
+
+    fn expressions(&self) -> Vec<Arc<dyn PhysicalExpr>> {
+        vec![self.expr.clone()]
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// The state of STRING_AGG is the concatenation of the values read so far,
+/// so that partial states are merged like values
+#[derive(Debug)]
+struct StringAggAccumulator {
+    value: Option<String>,
+    data_type: DataType,
+    delimiter: String,
+}
+
+impl StringAggAccumulator {
+    fn append(&mut self, values: &ArrayRef) -> Result<()> {
+        (0..values.len()).try_for_each(|index| {
+            match ScalarValue::try_from_array(values, index)? {
+                ScalarValue::Utf8(Some(s)) | ScalarValue::LargeUtf8(Some(s)) => {
+                    match &mut self.value {
+                        Some(value) => {
+                            value.push_str(&self.delimiter);
+                            value.push_str(&s);
+                        }
+                        None => self.value = Some(s),
+                    }
+                }
+                _ => {}
+            }
+            Ok(())
+        })
+    }
+}
+
+impl Accumulator for StringAggAccumulator {
+    fn state(&self) -> Result<Vec<AggregateState>> {
+        Ok(vec![AggregateState::Scalar(self.evaluate()?)])
+    }
+
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        self.append(&values[0])
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<()> {
+        self.append(&states[0])
+    }
+
+    fn evaluate(&self) -> Result<ScalarValue> {
+        Ok(match self.data_type {
+            DataType::LargeUtf8 => ScalarValue::LargeUtf8(self.value.clone()),
+            _ => ScalarValue::Utf8(self.value.clone()),
+        })
+    }
+}
@@ -23,11 +23,11 @@ use std::sync::Arc;
 
 use crate::{AggregateExpr, PhysicalExpr};
 use arrow::compute;
-use arrow::datatypes::DataType;
+use arrow::datatypes::{ArrowPrimitiveType, DataType};
 use arrow::{
     array::{
         ArrayRef, Float32Array, Float64Array, Int16Array, Int32Array, Int64Array,
-        Int8Array, UInt16Array, UInt32Array, UInt64Array, UInt8Array,
+        Int8Array, PrimitiveArray, UInt16Array, UInt32Array, UInt64Array, UInt8Array,
     },
     datatypes::Field,
 };
@@ -35,11 +35,13 @@ use datafusion_common::{downcast_value, DataFusionError, Result, ScalarValue};
 use datafusion_expr::{Accumulator, AggregateState};
 
 use crate::aggregate::row_accumulator::RowAccumulator;
+use crate::aggregate::utils::non_null_counts;
 use crate::expressions::format_state_name;
 use arrow::array::Array;
 use arrow::array::Decimal128Array;
 use arrow::compute::cast;
 use datafusion_row::accessor::RowAccessor;
+use num_traits::{CheckedAdd, WrappingAdd};
 
 /// SUM aggregate expression
 #[derive(Debug)]
@@ -48,6 +50,7 @@ pub struct Sum {
     data_type: DataType,
     expr: Arc<dyn PhysicalExpr>,
     nullable: bool,
+    wrapping_arithmetic: bool,
 }
 
 impl Sum {
@@ -62,8 +65,15 @@ impl Sum {
             expr,
             data_type,
             nullable: true,
+            wrapping_arithmetic: false,
         }
     }
+
+    /// Makes integer sums wrap around on overflow rather than fail
+    pub fn with_wrapping_arithmetic(mut self, wrapping_arithmetic: bool) -> Self {
+        self.wrapping_arithmetic = wrapping_arithmetic;
+        self
+    }
 }
 
 impl AggregateExpr for Sum {
@@ -81,7 +91,11 @@ impl AggregateExpr for Sum {
     }
 
     fn create_accumulator(&self) -> Result<Box<dyn Accumulator>> {
-        Ok(Box::new(SumAccumulator::try_new(&self.data_type)?))
+        Ok(Box::new(SumAccumulator::try_new(
+            &self.data_type,
+            &self.name,
+            self.wrapping_arithmetic,
+        )?))
     }
 
     fn state_fields(&self) -> Result<Vec<Field>> {
@@ -130,26 +144,68 @@ impl AggregateExpr for Sum {
         Ok(Box::new(SumRowAccumulator::new(
             start_index,
             self.data_type.clone(),
+            &self.name,
+            self.wrapping_arithmetic,
         )))
     }
+
+    fn convert_to_state_supported(&self) -> bool {
+        true
+    }
+
+    fn convert_to_state(&self, values: &[ArrayRef]) -> Result<Vec<ArrayRef>> {
+        Ok(vec![
+            cast(&values[0], &self.data_type)?,
+            non_null_counts(&values[0], &DataType::UInt64)?,
+        ])
+    }
 }
 
 #[derive(Debug)]
 struct SumAccumulator {
     sum: ScalarValue,
     count: u64,
+    name: String,
+    wrapping: bool,
 }
 
 impl SumAccumulator {
-    /// new sum accumulator
-    pub fn try_new(data_type: &DataType) -> Result<Self> {
+    /// new sum accumulator, whose integer sums wrap around on overflow if
+    /// `wrapping` is true and otherwise fail with an error naming `name`
+    pub fn try_new(data_type: &DataType, name: &str, wrapping: bool) -> Result<Self> {
         Ok(Self {
             sum: ScalarValue::try_from(data_type)?,
             count: 0,
+            name: name.to_string(),
+            wrapping,
         })
     }
 }
 
+// returns the error of an integer sum overflowing in the aggregate `name`
+fn sum_overflow_error(name: &str) -> DataFusionError {
+    DataFusionError::Execution(format!("Integer overflow computing {}", name))
+}
+
+// sums the non null values of the array, wrapping around on overflow if
+// `wrapping` is true and otherwise returning None
+fn sum_integers<T>(array: &PrimitiveArray<T>, wrapping: bool) -> Option<T::Native>
+where
+    T: ArrowPrimitiveType,
+    T::Native: CheckedAdd + WrappingAdd,
+{
+    array
+        .iter()
+        .flatten()
+        .try_fold(T::Native::default(), |sum, value| {
+            if wrapping {
+                Some(sum.wrapping_add(&value))
+            } else {
+                sum.checked_add(&value)
+            }
+        })
+}
+
 // returns the new value after sum with the new values, taking nullability into account
 macro_rules! typed_sum_delta_batch {
     ($VALUES:expr, $ARRAYTYPE:ident, $SCALAR:ident) => {{
@@ -159,6 +215,20 @@ macro_rules! typed_sum_delta_batch {
     }};
 }
 
+// returns the sum of the integer values, taking nullability and overflow into account
+macro_rules! typed_sum_integer_batch {
+    ($VALUES:expr, $ARRAYTYPE:ident, $SCALAR:ident, $NAME:expr, $WRAPPING:expr) => {{
+        let array = downcast_value!($VALUES, $ARRAYTYPE);
+        if array.null_count() == array.len() {
+            ScalarValue::$SCALAR(None)
+        } else {
+            let sum = sum_integers(array, $WRAPPING)
+                .ok_or_else(|| sum_overflow_error($NAME))?;
+            ScalarValue::$SCALAR(Some(sum))
+        }
+    }};
+}
+
 // TODO implement this in arrow-rs with simd
 // https://github.com/apache/arrow-rs/issues/1010
 fn sum_decimal_batch(values: &ArrayRef, precision: u8, scale: u8) -> Result<ScalarValue> {
@@ -178,6 +248,18 @@ fn sum_decimal_batch(values: &ArrayRef, precision: u8, scale: u8) -> Result<Scal
 
 // sums the array and returns a ScalarValue of its corresponding type.
 pub(crate) fn sum_batch(values: &ArrayRef, sum_type: &DataType) -> Result<ScalarValue> {
+    sum_batch_with_overflow(values, sum_type, "SUM", false)
+}
+
+// sums the array and returns a ScalarValue of its corresponding type, integer
+// sums wrapping around on overflow if `wrapping` is true and otherwise failing
+// with an error naming the aggregate `name`.
+pub(crate) fn sum_batch_with_overflow(
+    values: &ArrayRef,
+    sum_type: &DataType,
+    name: &str,
+    wrapping: bool,
+) -> Result<ScalarValue> {
     let values = &cast(values, sum_type)?;
     Ok(match values.data_type() {
         DataType::Decimal128(precision, scale) => {
@@ -185,14 +267,30 @@ pub(crate) fn sum_batch(values: &ArrayRef, sum_type: &DataType) -> Result<Scalar
         }
         DataType::Float64 => typed_sum_delta_batch!(values, Float64Array, Float64),
         DataType::Float32 => typed_sum_delta_batch!(values, Float32Array, Float32),
-        DataType::Int64 => typed_sum_delta_batch!(values, Int64Array, Int64),
-        DataType::Int32 => typed_sum_delta_batch!(values, Int32Array, Int32),
-        DataType::Int16 => typed_sum_delta_batch!(values, Int16Array, Int16),
-        DataType::Int8 => typed_sum_delta_batch!(values, Int8Array, Int8),
-        DataType::UInt64 => typed_sum_delta_batch!(values, UInt64Array, UInt64),
-        DataType::UInt32 => typed_sum_delta_batch!(values, UInt32Array, UInt32),
-        DataType::UInt16 => typed_sum_delta_batch!(values, UInt16Array, UInt16),
-        DataType::UInt8 => typed_sum_delta_batch!(values, UInt8Array, UInt8),
+        DataType::Int64 => {
+            typed_sum_integer_batch!(values, Int64Array, Int64, name, wrapping)
+        }
+        DataType::Int32 => {
+            typed_sum_integer_batch!(values, Int32Array, Int32, name, wrapping)
+        }
+        DataType::Int16 => {
+            typed_sum_integer_batch!(values, Int16Array, Int16, name, wrapping)
+        }
+        DataType::Int8 => {
+            typed_sum_integer_batch!(values, Int8Array, Int8, name, wrapping)
+        }
+        DataType::UInt64 => {
+            typed_sum_integer_batch!(values, UInt64Array, UInt64, name, wrapping)
+        }
+        DataType::UInt32 => {
+            typed_sum_integer_batch!(values, UInt32Array, UInt32, name, wrapping)
+        }
+        DataType::UInt16 => {
+            typed_sum_integer_batch!(values, UInt16Array, UInt16, name, wrapping)
+        }
+        DataType::UInt8 => {
+            typed_sum_integer_batch!(values, UInt8Array, UInt8, name, wrapping)
+        }
         e => {
             return Err(DataFusionError::Internal(format!(
                 "Sum is not expected to receive the type {:?}",
@@ -241,6 +339,87 @@ pub(crate) fn add_to_row(
     Ok(())
 }
 
+// adds the sum `s` of the aggregate `name` to the row, checking integer sums
+// for overflow unless `wrapping` is true
+fn add_sum_to_row(
+    index: usize,
+    accessor: &mut RowAccessor,
+    s: &ScalarValue,
+    name: &str,
+    wrapping: bool,
+) -> Result<()> {
+    let added = match s {
+        ScalarValue::Int64(Some(v)) if wrapping => {
+            accessor.wrapping_add_i64(index, *v);
+            true
+        }
+        ScalarValue::Int64(Some(v)) => accessor.checked_add_i64(index, *v),
+        ScalarValue::UInt64(Some(v)) if wrapping => {
+            accessor.wrapping_add_u64(index, *v);
+            true
+        }
+        ScalarValue::UInt64(Some(v)) => accessor.checked_add_u64(index, *v),
+        _ => return add_to_row(index, accessor, s),
+    };
+    if added {
+        Ok(())
+    } else {
+        Err(sum_overflow_error(name))
+    }
+}
+
+// adds or subtracts two sums of the aggregate `name`, checking integer sums
+// for overflow unless `wrapping` is true
+fn combine_sums(
+    lhs: &ScalarValue,
+    rhs: &ScalarValue,
+    subtract: bool,
+    name: &str,
+    wrapping: bool,
+) -> Result<ScalarValue> {
+    macro_rules! combine_integers {
+        ($LHS:expr, $RHS:expr, $SCALAR:ident) => {{
+            let value = match (subtract, wrapping) {
+                (false, true) => Some((*$LHS).wrapping_add(*$RHS)),
+                (true, true) => Some((*$LHS).wrapping_sub(*$RHS)),
+                (false, false) => (*$LHS).checked_add(*$RHS),
+                (true, false) => (*$LHS).checked_sub(*$RHS),
+            };
+            let value = value.ok_or_else(|| sum_overflow_error(name))?;
+            Ok(ScalarValue::$SCALAR(Some(value)))
+        }};
+    }
+
+    match (lhs, rhs) {
+        (ScalarValue::Int64(Some(l)), ScalarValue::Int64(Some(r))) => {
+            combine_integers!(l, r, Int64)
+        }
+        (ScalarValue::Int32(Some(l)), ScalarValue::Int32(Some(r))) => {
+            combine_integers!(l, r, Int32)
+        }
+        (ScalarValue::Int16(Some(l)), ScalarValue::Int16(Some(r))) => {
+            combine_integers!(l, r, Int16)
+        }
+        (ScalarValue::Int8(Some(l)), ScalarValue::Int8(Some(r))) => {
+            combine_integers!(l, r, Int8)
+        }
+        (ScalarValue::UInt64(Some(l)), ScalarValue::UInt64(Some(r))) => {
+            combine_integers!(l, r, UInt64)
+        }
+        (ScalarValue::UInt32(Some(l)), ScalarValue::UInt32(Some(r))) => {
+            combine_integers!(l, r, UInt32)
+        }
+        (ScalarValue::UInt16(Some(l)), ScalarValue::UInt16(Some(r))) => {
+            combine_integers!(l, r, UInt16)
+        }
+        (ScalarValue::UInt8(Some(l)), ScalarValue::UInt8(Some(r))) => {
+            combine_integers!(l, r, UInt8)
+        }
+        _ if subtract => lhs.sub(rhs),
+        _ => lhs.add(rhs),
+    }
+}
+
 impl Accumulator for SumAccumulator {
     fn state(&self) -> Result<Vec<AggregateState>> {
         Ok(vec![
@@ -252,16 +431,26 @@ impl Accumulator for SumAccumulator {
     fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
         let values = &values[0];
         self.count += (values.len() - values.data().null_count()) as u64;
-        let delta = sum_batch(values, &self.sum.get_datatype())?;
-        self.sum = self.sum.add(&delta)?;
+        let delta = sum_batch_with_overflow(
+            values,
+            &self.sum.get_datatype(),
+            &self.name,
+            self.wrapping,
+        )?;
+        self.sum = combine_sums(&self.sum, &delta, false, &self.name, self.wrapping)?;
         Ok(())
     }
 
     fn retract_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
         let values = &values[0];
         self.count -= (values.len() - values.data().null_count()) as u64;
-        let delta = sum_batch(values, &self.sum.get_datatype())?;
-        self.sum = self.sum.sub(&delta)?;
+        let delta = sum_batch_with_overflow(
+            values,
+            &self.sum.get_datatype(),
+            &self.name,
+            self.wrapping,
+        )?;
+        self.sum = combine_sums(&self.sum, &delta, true, &self.name, self.wrapping)?;
         Ok(())
     }
 
@@ -285,11 +474,18 @@ impl Accumulator for SumAccumulator {
 struct SumRowAccumulator {
     index: usize,
     datatype: DataType,
+    name: String,
+    wrapping: bool,
 }
 
 impl SumRowAccumulator {
-    pub fn new(index: usize, datatype: DataType) -> Self {
-        Self { index, datatype }
+    pub fn new(index: usize, datatype: DataType, name: &str, wrapping: bool) -> Self {
+        Self {
+            index,
+            datatype,
+            name: name.to_string(),
+            wrapping,
+        }
     }
 }
 
@@ -300,9 +496,9 @@ impl RowAccumulator for SumRowAccumulator {
         accessor: &mut RowAccessor,
     ) -> Result<()> {
         let values = &values[0];
-        let delta = sum_batch(values, &self.datatype)?;
-        add_to_row(self.index, accessor, &delta)?;
-        Ok(())
+        let delta =
+            sum_batch_with_overflow(values, &self.datatype, &self.name, self.wrapping)?;
+        add_sum_to_row(self.index, accessor, &delta, &self.name, self.wrapping)
     }
 
     fn merge_batch(
@@ -434,6 +630,44 @@ mod tests {
         generic_test_op!(a, DataType::Int32, Sum, ScalarValue::Int32(None))
     }
 
+    #[test]
+    fn sum_i32_promoted_to_i64() -> Result<()> {
+        let a: ArrayRef = Arc::new(Int32Array::from(vec![i32::MAX, i32::MAX, 2]));
+        generic_test_op!(
+            a,
+            DataType::Int32,
+            Sum,
+            ScalarValue::from(2 * i32::MAX as i64 + 2)
+        )
+    }
+
+    #[test]
+    fn sum_i64_overflow() -> Result<()> {
+        let a: ArrayRef = Arc::new(Int64Array::from(vec![Some(i64::MAX), None, Some(1)]));
+        let err =
+            sum_batch_with_overflow(&a, &DataType::Int64, "SUM(a)", false).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Execution error: Integer overflow computing SUM(a)"
+        );
+        assert_eq!(
+            sum_batch_with_overflow(&a, &DataType::Int64, "SUM(a)", true)?,
+            ScalarValue::Int64(Some(i64::MIN))
+        );
+
+        // overflows across batches are checked as well
+        let mut accumulator = SumAccumulator::try_new(&DataType::Int64, "SUM(a)", false)?;
+        let batch: ArrayRef = Arc::new(Int64Array::from(vec![i64::MAX]));
+        accumulator.update_batch(&[batch.clone()])?;
+        assert!(accumulator.update_batch(&[batch.clone()]).is_err());
+
+        let mut accumulator = SumAccumulator::try_new(&DataType::Int64, "SUM(a)", true)?;
+        accumulator.update_batch(&[batch.clone()])?;
+        accumulator.update_batch(&[batch])?;
+        assert_eq!(accumulator.evaluate()?, ScalarValue::Int64(Some(-2)));
+        Ok(())
+    }
+
     #[test]
     fn sum_u32() -> Result<()> {
         let a: ArrayRef =
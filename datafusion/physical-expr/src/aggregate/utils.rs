@@ -17,9 +17,12 @@
 
 //! Utilities used in aggregates
 
-use arrow::array::ArrayRef;
+use arrow::array::{Array, ArrayRef, UInt64Array};
+use arrow::compute::cast;
+use arrow::datatypes::DataType;
 use datafusion_common::{Result, ScalarValue};
 use datafusion_expr::Accumulator;
+use std::sync::Arc;
 
 /// Extract scalar values from an accumulator. This can return an error if the accumulator
 /// has any non-scalar values.
@@ -46,3 +49,12 @@ pub fn get_accum_scalar_values_as_arrays(
         })
         .collect::<Result<Vec<_>>>()
 }
+
+/// Returns one for every non null value of `values` and zero for every null
+/// value, as an array of `data_type`
+pub fn non_null_counts(values: &ArrayRef, data_type: &DataType) -> Result<ArrayRef> {
+    let counts: ArrayRef = Arc::new(UInt64Array::from_iter_values(
+        (0..values.len()).map(|i| values.is_valid(i) as u64),
+    ));
+    Ok(cast(&counts, data_type)?)
+}
@@ -31,8 +31,8 @@ use arrow::{
     datatypes::Field,
 };
 use datafusion_common::downcast_value;
+use datafusion_common::Result;
 use datafusion_common::ScalarValue;
-use datafusion_common::{DataFusionError, Result};
 use datafusion_expr::{Accumulator, AggregateState};
 
 /// VAR and VAR_SAMP aggregate expression
@@ -244,6 +244,13 @@ impl Accumulator for VarianceAccumulator {
 
         for value in arr {
             let new_count = self.count - 1;
+            if new_count == 0 {
+                // the mean of no value is undefined, start over
+                self.count = 0;
+                self.mean = 0_f64;
+                self.m2 = 0_f64;
+                continue;
+            }
             let delta1 = self.mean - value;
             let new_mean = delta1 / new_count as f64 + self.mean;
             let delta2 = new_mean - value;
@@ -283,24 +290,14 @@ impl Accumulator for VarianceAccumulator {
     }
 
     fn evaluate(&self) -> Result<ScalarValue> {
+        // the sample variance of less than two values, like the population
+        // variance of no value, is null
         let count = match self.stats_type {
             StatsType::Population => self.count,
-            StatsType::Sample => {
-                if self.count > 0 {
-                    self.count - 1
-                } else {
-                    self.count
-                }
-            }
+            StatsType::Sample => self.count.saturating_sub(1),
         };
 
-        if count <= 1 {
-            return Err(DataFusionError::Internal(
-                "At least two values are needed to calculate variance".to_string(),
-            ));
-        }
-
-        if self.count == 0 {
+        if count == 0 {
             Ok(ScalarValue::Float64(None))
         } else {
             Ok(ScalarValue::Float64(Some(self.m2 / count as f64)))
@@ -386,12 +383,33 @@ mod tests {
             "bla".to_string(),
             DataType::Float64,
         ));
-        let actual = aggregate(&batch, agg);
-        assert!(actual.is_err());
+        let actual = aggregate(&batch, agg)?;
+        assert_eq!(actual, ScalarValue::Float64(None));
 
         Ok(())
     }
 
+    #[test]
+    fn variance_pop_1_input() -> Result<()> {
+        let a: ArrayRef = Arc::new(Float64Array::from(vec![1_f64]));
+        generic_test_op!(a, DataType::Float64, VariancePop, ScalarValue::from(0_f64))
+    }
+
+    #[test]
+    fn variance_retract_all() -> Result<()> {
+        let mut accumulator = VarianceAccumulator::try_new(StatsType::Population)?;
+        let a: ArrayRef = Arc::new(Float64Array::from(vec![1_f64, 2_f64, 3_f64]));
+        accumulator.update_batch(&[a.clone()])?;
+        accumulator.retract_batch(&[a])?;
+        assert_eq!(accumulator.evaluate()?, ScalarValue::Float64(None));
+
+        // retracting every value leaves no trace of them
+        let b: ArrayRef = Arc::new(Float64Array::from(vec![5_f64, 7_f64]));
+        accumulator.update_batch(&[b])?;
+        assert_eq!(accumulator.evaluate()?, ScalarValue::from(1_f64));
+        Ok(())
+    }
+
     #[test]
     fn variance_i32_with_nulls() -> Result<()> {
         let a: ArrayRef = Arc::new(Int32Array::from(vec![
@@ -420,8 +438,8 @@ mod tests {
             "bla".to_string(),
             DataType::Float64,
         ));
-        let actual = aggregate(&batch, agg);
-        assert!(actual.is_err());
+        let actual = aggregate(&batch, agg)?;
+        assert_eq!(actual, ScalarValue::Float64(None));
 
         Ok(())
     }
@@ -16,10 +16,13 @@
 // under the License.
 
 use arrow::array::{new_null_array, Array, BooleanArray};
+use arrow::compute::kernels::comparison::{gt_dyn, lt_dyn};
 use arrow::compute::kernels::zip::zip;
-use arrow::compute::{and, is_not_null, is_null};
+use arrow::compute::{and, is_not_null, is_null, or, prep_null_mask_filter};
+use arrow::datatypes::DataType;
+use arrow::error::Result as ArrowResult;
 
-use datafusion_common::{DataFusionError, Result};
+use datafusion_common::{DataFusionError, Result, ScalarValue};
 use datafusion_expr::ColumnarValue;
 
 /// coalesce evaluates to the first value which is not NULL
@@ -78,3 +81,109 @@ pub fn coalesce(args: &[ColumnarValue]) -> Result<ColumnarValue> {
         Ok(result)
     }
 }
+
+/// greatest evaluates to the largest of its arguments. NULL arguments are
+/// ignored, so that the result is only NULL if all of them are NULL.
+pub fn greatest(args: &[ColumnarValue]) -> Result<ColumnarValue> {
+    extremum(args, "greatest", gt_dyn)
+}
+
+/// least evaluates to the smallest of its arguments. NULL arguments are
+/// ignored, so that the result is only NULL if all of them are NULL.
+pub fn least(args: &[ColumnarValue]) -> Result<ColumnarValue> {
+    extremum(args, "least", lt_dyn)
+}
+
+/// Folds `args` into the value that `replaces` the others, skipping NULLs
+fn extremum(
+    args: &[ColumnarValue],
+    name: &str,
+    replaces: fn(&dyn Array, &dyn Array) -> ArrowResult<BooleanArray>,
+) -> Result<ColumnarValue> {
+    if args.is_empty() {
+        return Err(DataFusionError::Internal(format!(
+            "{} was called with 0 arguments. It requires at least 1.",
+            name
+        )));
+    }
+    let return_type = args[0].data_type();
+    if return_type == DataType::Null {
+        return Ok(args[0].clone());
+    }
+
+    let size = args.iter().find_map(|arg| match arg {
+        ColumnarValue::Array(array) => Some(array.len()),
+        ColumnarValue::Scalar(_) => None,
+    });
+    let mut current_value = args[0].clone().into_array(size.unwrap_or(1));
+    for arg in &args[1..] {
+        let value = arg.clone().into_array(current_value.len());
+        // the comparison is NULL, and so false, if either side is NULL
+        let replace =
+            prep_null_mask_filter(&replaces(value.as_ref(), current_value.as_ref())?);
+        let replace = or(
+            &replace,
+            &and(
+                &is_null(current_value.as_ref())?,
+                &is_not_null(value.as_ref())?,
+            )?,
+        )?;
+        current_value = zip(&replace, value.as_ref(), current_value.as_ref())?;
+    }
+
+    match size {
+        Some(_) => Ok(ColumnarValue::Array(current_value)),
+        None => Ok(ColumnarValue::Scalar(ScalarValue::try_from_array(
+            &current_value,
+            0,
+        )?)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{ArrayRef, Int64Array, StringArray};
+    use std::sync::Arc;
+
+    #[test]
+    fn greatest_and_least_skip_nulls() -> Result<()> {
+        let a: ArrayRef = Arc::new(Int64Array::from(vec![Some(1), None, Some(5), None]));
+        let b: ArrayRef = Arc::new(Int64Array::from(vec![Some(3), Some(2), None, None]));
+        let args = [
+            ColumnarValue::Array(a),
+            ColumnarValue::Array(b),
+            ColumnarValue::Scalar(ScalarValue::Int64(Some(4))),
+        ];
+
+        let result = greatest(&args)?.into_array(4);
+        let expected = Int64Array::from(vec![Some(4), Some(4), Some(5), Some(4)]);
+        assert_eq!(result.as_ref(), &expected);
+
+        let result = least(&args[..2])?.into_array(4);
+        let expected = Int64Array::from(vec![Some(1), Some(2), Some(5), None]);
+        assert_eq!(result.as_ref(), &expected);
+        Ok(())
+    }
+
+    #[test]
+    fn greatest_of_scalars() -> Result<()> {
+        let args = [
+            ColumnarValue::Scalar(ScalarValue::Utf8(Some("b".to_string()))),
+            ColumnarValue::Scalar(ScalarValue::Utf8(None)),
+            ColumnarValue::Scalar(ScalarValue::Utf8(Some("a".to_string()))),
+        ];
+        match greatest(&args)? {
+            ColumnarValue::Scalar(value) => {
+                assert_eq!(value, ScalarValue::Utf8(Some("b".to_string())))
+            }
+            other => panic!("expected a scalar, got {:?}", other),
+        }
+
+        let array: ArrayRef = Arc::new(StringArray::from(vec![Some("c"), None]));
+        let result = least(&[ColumnarValue::Array(array), args[0].clone()])?;
+        let expected = StringArray::from(vec!["b", "b"]);
+        assert_eq!(result.into_array(2).as_ref(), &expected);
+        Ok(())
+    }
+}
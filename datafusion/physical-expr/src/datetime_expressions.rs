@@ -17,11 +17,17 @@
 
 //! DateTime expressions
 
+use crate::expressions::Literal;
+use crate::PhysicalExpr;
 use arrow::{
-    array::{Array, ArrayRef, GenericStringArray, OffsetSizeTrait, PrimitiveArray},
+    array::{
+        as_primitive_array, as_string_array, Array, ArrayRef, GenericStringArray,
+        OffsetSizeTrait, PrimitiveArray, StringArray,
+    },
     compute::kernels::cast_utils::string_to_timestamp_nanos,
     datatypes::{
-        ArrowPrimitiveType, DataType, IntervalDayTimeType, TimestampMicrosecondType,
+        ArrowNumericType, ArrowPrimitiveType, ArrowTemporalType, DataType, Date32Type,
+        Date64Type, IntervalDayTimeType, TimestampMicrosecondType,
         TimestampMillisecondType, TimestampNanosecondType, TimestampSecondType,
     },
 };
@@ -34,13 +40,15 @@ use arrow::{
     datatypes::TimeUnit,
     temporal_conversions::timestamp_ns_to_datetime,
 };
+use chrono::format::{Item, ParseResult, Parsed, StrftimeItems};
 use chrono::prelude::*;
 use chrono::Duration;
 use datafusion_common::cast::as_date32_array;
 use datafusion_common::{DataFusionError, Result};
 use datafusion_common::{ScalarType, ScalarValue};
-use datafusion_expr::ColumnarValue;
+use datafusion_expr::{ColumnarValue, ScalarFunctionImplementation};
 use std::borrow::Borrow;
+use std::fmt::Write;
 use std::sync::Arc;
 
 /// given a function `op` that maps a `&str` to a Result of an arrow native type,
@@ -507,11 +515,387 @@ pub fn date_part(args: &[ColumnarValue]) -> Result<ColumnarValue> {
     })
 }
 
+/// The number of days from the common era to the unix epoch
+const EPOCH_DAYS_FROM_CE: i32 = 719_163;
+
+/// The format `to_date` parses strings with when it is given none
+const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// Compiles the `strftime` style `format`, such as `%Y-%m-%d %H:%M:%S`, into
+/// the items chrono formats and parses dates and times with, returning
+/// `None` if it has an unknown specifier such as `%Q`
+fn compile_format(format: &str) -> Option<Vec<Item<'static>>> {
+    StrftimeItems::new(format)
+        .map(|item| match item {
+            Item::Literal(literal) => Some(Item::OwnedLiteral(literal.into())),
+            Item::Space(space) => Some(Item::OwnedSpace(space.into())),
+            Item::OwnedLiteral(literal) => Some(Item::OwnedLiteral(literal)),
+            Item::OwnedSpace(space) => Some(Item::OwnedSpace(space)),
+            Item::Numeric(numeric, pad) => Some(Item::Numeric(numeric, pad)),
+            Item::Fixed(fixed) => Some(Item::Fixed(fixed)),
+            Item::Error => None,
+        })
+        .collect()
+}
+
+fn invalid_format_message(format: &str, name: &str) -> String {
+    format!("Invalid format string '{}' for {}", format, name)
+}
+
+/// Compiles the format of the function `name` up front when it is the
+/// literal argument at `index` of `args`, so that an invalid format is
+/// reported while planning the query
+fn compile_literal_format(
+    name: &str,
+    args: &[Arc<dyn PhysicalExpr>],
+    index: usize,
+) -> Result<Option<Vec<Item<'static>>>> {
+    match args
+        .get(index)
+        .and_then(|arg| arg.as_any().downcast_ref::<Literal>())
+        .map(|literal| literal.value())
+    {
+        Some(ScalarValue::Utf8(Some(format))) => compile_format(format)
+            .map(Some)
+            .ok_or_else(|| DataFusionError::Plan(invalid_format_message(format, name))),
+        _ => Ok(None),
+    }
+}
+
+/// The format argument of a function, a scalar format being compiled once
+/// and the formats of an array whenever they change from one row to the next
+enum FormatArg<'a> {
+    /// The format of every row, `None` if it is NULL
+    Scalar(Option<Vec<Item<'static>>>),
+    Array {
+        formats: &'a StringArray,
+        last: Option<(&'a str, Vec<Item<'static>>)>,
+    },
+}
+
+impl<'a> FormatArg<'a> {
+    /// Creates the format argument `arg` of the function `name`, whose
+    /// format was compiled while planning if `literal` is set
+    fn try_new(
+        arg: &'a ColumnarValue,
+        literal: Option<&[Item<'static>]>,
+        name: &str,
+    ) -> Result<Self> {
+        match arg {
+            ColumnarValue::Scalar(ScalarValue::Utf8(format)) => {
+                let items = match (literal, format) {
+                    (Some(items), _) => Some(items.to_vec()),
+                    (None, Some(format)) => {
+                        Some(compile_format(format).ok_or_else(|| {
+                            DataFusionError::Execution(invalid_format_message(
+                                format, name,
+                            ))
+                        })?)
+                    }
+                    (None, None) => None,
+                };
+                Ok(Self::Scalar(items))
+            }
+            ColumnarValue::Array(array) => match array.data_type() {
+                DataType::Utf8 => Ok(Self::Array {
+                    formats: as_string_array(array),
+                    last: None,
+                }),
+                other => Err(DataFusionError::Internal(format!(
+                    "Unsupported data type {:?} for the format of function {}",
+                    other, name,
+                ))),
+            },
+            other => Err(DataFusionError::Internal(format!(
+                "Unsupported data type {:?} for the format of function {}",
+                other.data_type(),
+                name,
+            ))),
+        }
+    }
+
+    /// Returns the compiled format of row `index`, `None` if it is NULL
+    fn items(&mut self, index: usize, name: &str) -> Result<Option<&[Item<'static>]>> {
+        match self {
+            Self::Scalar(items) => Ok(items.as_deref()),
+            Self::Array { formats, last } => {
+                let formats: &'a StringArray = *formats;
+                if formats.is_null(index) {
+                    return Ok(None);
+                }
+                let format = formats.value(index);
+                if !matches!(last, Some((previous, _)) if *previous == format) {
+                    let items = compile_format(format).ok_or_else(|| {
+                        DataFusionError::Execution(invalid_format_message(format, name))
+                    })?;
+                    *last = Some((format, items));
+                }
+                Ok(last.as_ref().map(|(_, items)| items.as_slice()))
+            }
+        }
+    }
+}
+
+/// Returns the number of rows of the array arguments in `args`, `None` if
+/// they are all scalars
+fn num_rows(args: &[ColumnarValue]) -> Option<usize> {
+    args.iter().find_map(|arg| match arg {
+        ColumnarValue::Array(array) => Some(array.len()),
+        ColumnarValue::Scalar(_) => None,
+    })
+}
+
+/// Returns `array` as the result of a function whose arguments had
+/// `num_rows` rows, a scalar if they were all scalars
+fn columnar_result(array: ArrayRef, num_rows: Option<usize>) -> Result<ColumnarValue> {
+    match num_rows {
+        Some(_) => Ok(ColumnarValue::Array(array)),
+        None => Ok(ColumnarValue::Scalar(ScalarValue::try_from_array(
+            &array, 0,
+        )?)),
+    }
+}
+
+/// Returns the date and time of each row of the temporal array `array`
+fn temporal_values<T>(array: &dyn Array) -> Vec<Option<NaiveDateTime>>
+where
+    T: ArrowTemporalType + ArrowNumericType,
+    i64: From<T::Native>,
+{
+    let array = as_primitive_array::<T>(array);
+    (0..array.len())
+        .map(|index| {
+            array
+                .is_valid(index)
+                .then(|| array.value_as_datetime(index))?
+        })
+        .collect()
+}
+
+/// Formats the dates or timestamps of `args[0]` with the formats of
+/// `args[1]`, see [`create_to_char`]
+fn to_char(
+    args: &[ColumnarValue],
+    literal: Option<&[Item<'static>]>,
+) -> Result<ColumnarValue> {
+    let num_rows = num_rows(args);
+    let values = args[0].clone().into_array(num_rows.unwrap_or(1));
+    // the values of timestamps with a time zone are in UTC
+    let zone = match values.data_type() {
+        DataType::Timestamp(_, Some(tz)) => Some(Zone::parse(tz)?),
+        _ => None,
+    };
+    let values = match values.data_type() {
+        DataType::Date32 => temporal_values::<Date32Type>(&values),
+        DataType::Date64 => temporal_values::<Date64Type>(&values),
+        DataType::Timestamp(TimeUnit::Second, _) => {
+            temporal_values::<TimestampSecondType>(&values)
+        }
+        DataType::Timestamp(TimeUnit::Millisecond, _) => {
+            temporal_values::<TimestampMillisecondType>(&values)
+        }
+        DataType::Timestamp(TimeUnit::Microsecond, _) => {
+            temporal_values::<TimestampMicrosecondType>(&values)
+        }
+        DataType::Timestamp(TimeUnit::Nanosecond, _) => {
+            temporal_values::<TimestampNanosecondType>(&values)
+        }
+        other => {
+            return Err(DataFusionError::Internal(format!(
+                "Unsupported data type {:?} for function to_char",
+                other,
+            )))
+        }
+    };
+
+    let mut formats = FormatArg::try_new(&args[1], literal, "to_char")?;
+    let formatted = values
+        .into_iter()
+        .enumerate()
+        .map(|(index, value)| {
+            let (value, items) = match (value, formats.items(index, "to_char")?) {
+                (Some(value), Some(items)) => (value, items),
+                _ => return Ok(None),
+            };
+            let mut formatted = String::new();
+            let written = match &zone {
+                Some(zone) => write!(
+                    formatted,
+                    "{}",
+                    zone.localize(&value).format_with_items(items.iter())
+                ),
+                None => write!(formatted, "{}", value.format_with_items(items.iter())),
+            };
+            written.map_err(|_| {
+                DataFusionError::Execution(format!(
+                    "Failed to format {} for to_char, whose format may only apply to values with a time zone",
+                    value
+                ))
+            })?;
+            Ok(Some(formatted))
+        })
+        .collect::<Result<StringArray>>()?;
+    columnar_result(Arc::new(formatted), num_rows)
+}
+
+/// The time zone of a timestamp type, either a fixed offset such as
+/// `+08:00` or a name such as `America/New_York`
+enum Zone {
+    Fixed(FixedOffset),
+    Named(chrono_tz::Tz),
+}
+
+impl Zone {
+    fn parse(tz: &str) -> Result<Self> {
+        let mut parsed = Parsed::new();
+        if chrono::format::parse(&mut parsed, tz, StrftimeItems::new("%:z")).is_ok() {
+            if let Ok(offset) = parsed.to_fixed_offset() {
+                return Ok(Self::Fixed(offset));
+            }
+        }
+        tz.parse::<chrono_tz::Tz>().map(Self::Named).map_err(|_| {
+            DataFusionError::Execution(format!("Invalid time zone '{}' for to_char", tz))
+        })
+    }
+
+    /// Returns the date and time of `utc` in this time zone
+    fn localize(&self, utc: &NaiveDateTime) -> DateTime<FixedOffset> {
+        let offset = match self {
+            Self::Fixed(offset) => *offset,
+            Self::Named(tz) => tz.offset_from_utc_datetime(utc).fix(),
+        };
+        DateTime::from_utc(*utc, offset)
+    }
+}
+
+/// Parses `value` with the compiled format `items`. The time is midnight
+/// if the format has none, and a value with an offset, such as one parsed
+/// with `%z`, is converted to UTC
+fn parse_datetime(value: &str, items: &[Item<'static>]) -> ParseResult<NaiveDateTime> {
+    let mut parsed = Parsed::new();
+    chrono::format::parse(&mut parsed, value, items.iter())?;
+    if parsed.offset.is_some() {
+        return parsed.to_datetime().map(|datetime| datetime.naive_utc());
+    }
+    let date = parsed.to_naive_date()?;
+    let time = match (parsed.hour_div_12, parsed.hour_mod_12) {
+        (None, None) => NaiveTime::from_hms(0, 0, 0),
+        _ => parsed.to_naive_time()?,
+    };
+    Ok(date.and_time(time))
+}
+
+/// Parses the strings of `args[0]` with the formats of `args[1]`, or with
+/// [`DEFAULT_DATE_FORMAT`] if there is no format argument, converting the
+/// parsed dates and times with `convert`
+fn parse_with_formats<T: ArrowPrimitiveType>(
+    args: &[ColumnarValue],
+    literal: Option<&[Item<'static>]>,
+    name: &str,
+    convert: impl Fn(NaiveDateTime) -> Result<T::Native>,
+) -> Result<ColumnarValue> {
+    let num_rows = num_rows(args);
+    let values = args[0].clone().into_array(num_rows.unwrap_or(1));
+    let values = match values.data_type() {
+        DataType::Utf8 => as_string_array(&values),
+        other => {
+            return Err(DataFusionError::Internal(format!(
+                "Unsupported data type {:?} for function {}",
+                other, name,
+            )))
+        }
+    };
+
+    let mut formats = match args.get(1) {
+        Some(arg) => FormatArg::try_new(arg, literal, name)?,
+        None => FormatArg::Scalar(compile_format(DEFAULT_DATE_FORMAT)),
+    };
+    let parsed = values
+        .iter()
+        .enumerate()
+        .map(|(index, value)| {
+            let (value, items) = match (value, formats.items(index, name)?) {
+                (Some(value), Some(items)) => (value, items),
+                _ => return Ok(None),
+            };
+            let datetime = parse_datetime(value, items).map_err(|e| {
+                DataFusionError::Execution(format!(
+                    "Error parsing '{}' with the format of {}: {}",
+                    value, name, e
+                ))
+            })?;
+            convert(datetime).map(Some)
+        })
+        .collect::<Result<PrimitiveArray<T>>>()?;
+    columnar_result(Arc::new(parsed), num_rows)
+}
+
+/// Returns the nanoseconds since the epoch of `datetime`
+fn datetime_to_nanos(datetime: NaiveDateTime) -> Result<i64> {
+    datetime
+        .timestamp()
+        .checked_mul(1_000_000_000)
+        .and_then(|nanos| nanos.checked_add(datetime.timestamp_subsec_nanos() as i64))
+        .ok_or_else(|| {
+            DataFusionError::Execution(format!(
+                "{} is out of the range of nanosecond timestamps",
+                datetime
+            ))
+        })
+}
+
+/// Creates the implementation of `to_char(value, format)`, which formats a
+/// date or timestamp with a `strftime` style format string, for the
+/// arguments `args`, which may be empty when they are not known. A literal
+/// format is compiled here, so that an invalid one fails planning
+pub fn create_to_char(
+    args: &[Arc<dyn PhysicalExpr>],
+) -> Result<ScalarFunctionImplementation> {
+    let literal = compile_literal_format("to_char", args, 1)?;
+    Ok(Arc::new(move |args| to_char(args, literal.as_deref())))
+}
+
+/// Creates the implementation of `to_date(string[, format])`, which parses
+/// a date with a `strftime` style format string, `%Y-%m-%d` by default,
+/// see [`create_to_char`]
+pub fn create_to_date(
+    args: &[Arc<dyn PhysicalExpr>],
+) -> Result<ScalarFunctionImplementation> {
+    let literal = compile_literal_format("to_date", args, 1)?;
+    Ok(Arc::new(move |args| {
+        parse_with_formats::<Date32Type>(
+            args,
+            literal.as_deref(),
+            "to_date",
+            |datetime| Ok(datetime.date().num_days_from_ce() - EPOCH_DAYS_FROM_CE),
+        )
+    }))
+}
+
+/// Creates the implementation of `to_timestamp(string, format)`, which
+/// parses a timestamp with a `strftime` style format string, see
+/// [`create_to_char`]
+pub fn create_to_timestamp(
+    args: &[Arc<dyn PhysicalExpr>],
+) -> Result<ScalarFunctionImplementation> {
+    let literal = compile_literal_format("to_timestamp", args, 1)?;
+    Ok(Arc::new(move |args| {
+        parse_with_formats::<TimestampNanosecondType>(
+            args,
+            literal.as_deref(),
+            "to_timestamp",
+            datetime_to_nanos,
+        )
+    }))
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::Arc;
 
-    use arrow::array::{ArrayRef, Int64Array, IntervalDayTimeArray, StringBuilder};
+    use arrow::array::{
+        ArrayRef, Date32Array, Int64Array, IntervalDayTimeArray, StringBuilder,
+    };
 
     use super::*;
 
@@ -809,6 +1193,193 @@ mod tests {
         );
     }
 
+    fn utf8(value: &str) -> ColumnarValue {
+        ColumnarValue::Scalar(ScalarValue::Utf8(Some(value.to_string())))
+    }
+
+    #[test]
+    fn to_char_formats() -> Result<()> {
+        let nanos = datetime_to_nanos(
+            NaiveDate::from_ymd(2021, 3, 4).and_hms_milli(5, 6, 7, 89),
+        )?;
+        let timestamps =
+            ColumnarValue::Array(Arc::new(TimestampNanosecondArray::from(vec![
+                Some(nanos),
+                None,
+                Some(nanos + 12 * 3600 * 1_000_000_000),
+            ])));
+        let to_char = create_to_char(&[])?;
+        let format = |format: &str| -> Result<Vec<Option<String>>> {
+            let formatted = to_char(&[timestamps.clone(), utf8(format)])?.into_array(3);
+            Ok(as_string_array(&formatted)
+                .iter()
+                .map(|value| value.map(String::from))
+                .collect())
+        };
+        let expect = |values: [&str; 2]| {
+            vec![
+                Some(values[0].to_string()),
+                None,
+                Some(values[1].to_string()),
+            ]
+        };
+
+        // zero padded by default, `-` suppressing the padding
+        assert_eq!(
+            format("%Y-%m-%d %H:%M:%S")?,
+            expect(["2021-03-04 05:06:07", "2021-03-04 17:06:07"])
+        );
+        assert_eq!(format("%-m/%-d %-H")?, expect(["3/4 5", "3/4 17"]));
+        // 12 and 24 hour clocks
+        assert_eq!(format("%I:%M %p")?, expect(["05:06 AM", "05:06 PM"]));
+        assert_eq!(format("%H:%M")?, expect(["05:06", "17:06"]));
+        // fractional seconds
+        assert_eq!(format("%S%.3f")?, expect(["07.089", "07.089"]));
+        assert_eq!(format("%S%.6f")?, expect(["07.089000", "07.089000"]));
+
+        // dates, and a format per row
+        let dates = ColumnarValue::Array(Arc::new(Date32Array::from(vec![
+            Some(18690),
+            Some(18690),
+            None,
+        ])));
+        let formats = ColumnarValue::Array(Arc::new(StringArray::from(vec![
+            Some("%d.%m.%Y"),
+            None,
+            Some("%Y"),
+        ])));
+        let formatted = to_char(&[dates, formats])?.into_array(3);
+        let formatted = as_string_array(&formatted);
+        assert_eq!(formatted.value(0), "04.03.2021");
+        assert!(formatted.is_null(1));
+        assert!(formatted.is_null(2));
+
+        let err = to_char(&[timestamps, utf8("%Y %z")]).unwrap_err();
+        assert!(err.to_string().contains("Failed to format"), "{}", err);
+        Ok(())
+    }
+
+    #[test]
+    fn to_char_time_zones() -> Result<()> {
+        let to_char = create_to_char(&[])?;
+        let nanos = datetime_to_nanos(NaiveDate::from_ymd(2021, 7, 4).and_hms(5, 6, 7))?;
+        let format = |tz: &str| -> Result<String> {
+            let timestamp = ColumnarValue::Scalar(ScalarValue::TimestampNanosecond(
+                Some(nanos),
+                Some(tz.to_string()),
+            ));
+            let formatted =
+                to_char(&[timestamp, utf8("%Y-%m-%d %H:%M %z")])?.into_array(1);
+            Ok(as_string_array(&formatted).value(0).to_string())
+        };
+        assert_eq!(format("+00:00")?, "2021-07-04 05:06 +0000");
+        assert_eq!(format("+08:00")?, "2021-07-04 13:06 +0800");
+        // daylight saving time
+        assert_eq!(format("America/New_York")?, "2021-07-04 01:06 -0400");
+        assert_eq!(format("Asia/Tokyo")?, "2021-07-04 14:06 +0900");
+
+        let err = format("Mars/Olympus").unwrap_err();
+        assert!(err.to_string().contains("Invalid time zone"), "{}", err);
+        Ok(())
+    }
+
+    #[test]
+    fn format_parse_round_trip() -> Result<()> {
+        let to_char = create_to_char(&[])?;
+        let to_timestamp = create_to_timestamp(&[])?;
+        let nanos = datetime_to_nanos(NaiveDate::from_ymd(1999, 12, 31).and_hms_nano(
+            23,
+            59,
+            58,
+            123_456_789,
+        ))?;
+        let timestamp =
+            ColumnarValue::Scalar(ScalarValue::TimestampNanosecond(Some(nanos), None));
+        for (format, expected) in [
+            ("%Y-%m-%d %H:%M:%S%.9f", nanos),
+            ("%d/%m/%Y %I:%M:%S%.f %p", nanos),
+            // the parts the format does not have are lost
+            ("%Y-%m-%dT%H:%M:%S", nanos - 123_456_789),
+            (
+                "%Y-%m-%d",
+                nanos - (23 * 3600 + 59 * 60 + 58) * 1_000_000_000 - 123_456_789,
+            ),
+        ] {
+            let formatted = to_char(&[timestamp.clone(), utf8(format)])?;
+            let parsed = to_timestamp(&[formatted, utf8(format)])?;
+            match parsed {
+                ColumnarValue::Scalar(ScalarValue::TimestampNanosecond(
+                    Some(parsed),
+                    None,
+                )) => {
+                    assert_eq!(parsed, expected, "{}", format)
+                }
+                other => panic!("unexpected result {:?}", other),
+            }
+        }
+
+        // an offset is converted to UTC
+        let parsed = to_timestamp(&[
+            utf8("2021-03-04 05:06:07 +0100"),
+            utf8("%Y-%m-%d %H:%M:%S %z"),
+        ])?;
+        let expected =
+            datetime_to_nanos(NaiveDate::from_ymd(2021, 3, 4).and_hms(4, 6, 7))?;
+        assert!(matches!(
+            parsed,
+            ColumnarValue::Scalar(ScalarValue::TimestampNanosecond(Some(parsed), None)) if parsed == expected
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn to_date_formats() -> Result<()> {
+        let to_date = create_to_date(&[])?;
+        let parsed = to_date(&[utf8("2021-03-04")])?;
+        assert!(matches!(
+            parsed,
+            ColumnarValue::Scalar(ScalarValue::Date32(Some(18690)))
+        ));
+        let parsed = to_date(&[utf8("March 4, 2021"), utf8("%B %-d, %Y")])?;
+        assert!(matches!(
+            parsed,
+            ColumnarValue::Scalar(ScalarValue::Date32(Some(18690)))
+        ));
+
+        let err = to_date(&[utf8("2021-13-04")]).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Error parsing '2021-13-04' with the format of to_date"),
+            "{}",
+            err
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_literal_format() {
+        use crate::expressions::lit;
+        let err = create_to_char(&[lit("2021-03-04"), lit("%Y-%Q")]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Error during planning: Invalid format string '%Y-%Q' for to_char"
+        );
+        let err = create_to_date(&[lit("2021-03-04"), lit("%Y-%")]).unwrap_err();
+        assert!(err.to_string().contains("Invalid format string"), "{}", err);
+
+        // a format only known when executing fails then
+        let to_char = create_to_char(&[]).unwrap();
+        let err = to_char(&[
+            ColumnarValue::Scalar(ScalarValue::Date32(Some(0))),
+            utf8("%Q"),
+        ])
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Execution error: Invalid format string '%Q' for to_char"
+        );
+    }
+
     #[test]
     fn to_timestamp_invalid_input_type() -> Result<()> {
         // pass the wrong type of input array to to_timestamp and test
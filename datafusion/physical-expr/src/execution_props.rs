@@ -30,6 +30,25 @@ use std::sync::Arc;
 #[derive(Clone)]
 pub struct ExecutionProps {
     pub query_execution_start_time: DateTime<Utc>,
+    /// If set, the start time [`Self::start_execution`] gives every query
+    /// rather than the current time, such as to make the results of `now()`
+    /// reproducible in tests
+    pub fixed_query_execution_start_time: Option<DateTime<Utc>>,
+    /// If true, the rows of integer and floating point arithmetic that
+    /// overflow or divide by zero are null rather than an error
+    pub lenient_arithmetic: bool,
+    /// If true, integer arithmetic and sums wrap around on overflow rather
+    /// than failing
+    pub wrapping_arithmetic: bool,
+    /// If true, the division of a column by a column with zeros fails rather
+    /// than yielding null for those rows
+    pub checked_division: bool,
+    /// The name of the default catalog of the session, given by
+    /// `current_catalog()`
+    pub current_catalog: String,
+    /// The name of the default schema of the session, given by
+    /// `current_schema()`
+    pub current_schema: String,
     /// providers for scalar variables
     pub var_providers: Option<HashMap<VarType, Arc<dyn VarProvider + Send + Sync>>>,
 }
@@ -45,16 +64,34 @@ impl ExecutionProps {
     pub fn new() -> Self {
         ExecutionProps {
             query_execution_start_time: chrono::Utc::now(),
+            fixed_query_execution_start_time: None,
+            lenient_arithmetic: false,
+            wrapping_arithmetic: false,
+            checked_division: false,
+            current_catalog: "datafusion".to_owned(),
+            current_schema: "public".to_owned(),
             var_providers: None,
         }
     }
 
     /// Marks the execution of query started timestamp
     pub fn start_execution(&mut self) -> &Self {
-        self.query_execution_start_time = chrono::Utc::now();
+        self.query_execution_start_time = self
+            .fixed_query_execution_start_time
+            .unwrap_or_else(chrono::Utc::now);
         &*self
     }
 
+    /// Fixes the start time of the queries started from now on to
+    /// `start_time`, or lets it be the current time again if `None`
+    pub fn fix_query_execution_start_time(
+        &mut self,
+        start_time: Option<DateTime<Utc>>,
+    ) -> &Self {
+        self.fixed_query_execution_start_time = start_time;
+        self.start_execution()
+    }
+
     /// Registers a variable provider, returning the existing
     /// provider, if any
     pub fn add_var_provider(
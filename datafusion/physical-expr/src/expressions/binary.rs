@@ -28,6 +28,7 @@ use arrow::compute::kernels::arithmetic::{
     multiply_scalar, subtract, subtract_scalar,
 };
 use arrow::compute::kernels::boolean::{and_kleene, not, or_kleene};
+use arrow::compute::kernels::cast::cast;
 use arrow::compute::kernels::comparison::{
     eq_dyn_binary_scalar, gt_dyn_binary_scalar, gt_eq_dyn_binary_scalar,
     lt_dyn_binary_scalar, lt_eq_dyn_binary_scalar, neq_dyn_binary_scalar,
@@ -54,10 +55,13 @@ use arrow::compute::kernels::comparison::{
 
 use adapter::{eq_dyn, gt_dyn, gt_eq_dyn, lt_dyn, lt_eq_dyn, neq_dyn};
 use arrow::compute::kernels::concat_elements::concat_elements_utf8;
+use arrow::compute::kernels::take::take;
 use kernels::{
     bitwise_and, bitwise_and_scalar, bitwise_or, bitwise_or_scalar, bitwise_shift_left,
     bitwise_shift_left_scalar, bitwise_shift_right, bitwise_shift_right_scalar,
-    bitwise_xor, bitwise_xor_scalar,
+    bitwise_xor, bitwise_xor_scalar, checked_arithmetic, compare_nested,
+    lenient_arithmetic, overflow_checked_arithmetic, supports_checked_arithmetic,
+    supports_nested_comparison, supports_overflow_checks,
 };
 use kernels_arrow::{
     add_decimal, add_decimal_scalar, divide_decimal_scalar, divide_opt_decimal,
@@ -70,7 +74,10 @@ use kernels_arrow::{
     subtract_decimal_scalar,
 };
 
-use arrow::datatypes::{DataType, Schema, TimeUnit};
+use arrow::datatypes::{
+    DataType, Int16Type, Int32Type, Int64Type, Int8Type, Schema, TimeUnit, UInt16Type,
+    UInt32Type, UInt64Type, UInt8Type,
+};
 use arrow::record_batch::RecordBatch;
 
 use crate::physical_expr::down_cast_any_ref;
@@ -86,6 +93,15 @@ pub struct BinaryExpr {
     left: Arc<dyn PhysicalExpr>,
     op: Operator,
     right: Arc<dyn PhysicalExpr>,
+    /// If true, the rows of integer and floating point arithmetic that
+    /// overflow or divide by zero are null rather than an error
+    lenient_arithmetic: bool,
+    /// If true, integer addition, subtraction and multiplication wrap around
+    /// on overflow rather than failing
+    wrapping_arithmetic: bool,
+    /// If true, the division of an array by an array with zeros fails rather
+    /// than yielding null for those rows
+    checked_division: bool,
 }
 
 impl BinaryExpr {
@@ -95,7 +111,54 @@ impl BinaryExpr {
         op: Operator,
         right: Arc<dyn PhysicalExpr>,
     ) -> Self {
-        Self { left, op, right }
+        Self {
+            left,
+            op,
+            right,
+            lenient_arithmetic: false,
+            wrapping_arithmetic: false,
+            checked_division: false,
+        }
+    }
+
+    /// Create new binary expression, checking that both sides have the
+    /// same type in `input_schema`
+    pub fn try_new(
+        left: Arc<dyn PhysicalExpr>,
+        op: Operator,
+        right: Arc<dyn PhysicalExpr>,
+        input_schema: &Schema,
+    ) -> Result<Self> {
+        let left_type = &left.data_type(input_schema)?;
+        let right_type = &right.data_type(input_schema)?;
+        if !left_type.eq(right_type) {
+            return Err(DataFusionError::Internal(format!(
+                "The type of {} {} {} of binary physical should be same",
+                left_type, op, right_type
+            )));
+        }
+        Ok(Self::new(left, op, right))
+    }
+
+    /// Makes the rows of integer and floating point arithmetic that
+    /// overflow or divide by zero null rather than an error
+    pub fn with_lenient_arithmetic(mut self, lenient_arithmetic: bool) -> Self {
+        self.lenient_arithmetic = lenient_arithmetic;
+        self
+    }
+
+    /// Makes integer addition, subtraction and multiplication wrap around on
+    /// overflow rather than fail
+    pub fn with_wrapping_arithmetic(mut self, wrapping_arithmetic: bool) -> Self {
+        self.wrapping_arithmetic = wrapping_arithmetic;
+        self
+    }
+
+    /// Makes the division of an array by an array with zeros fail rather than
+    /// yield null for those rows
+    pub fn with_checked_division(mut self, checked_division: bool) -> Self {
+        self.checked_division = checked_division;
+        self
     }
 
     /// Get the left side of the binary expression
@@ -112,6 +175,21 @@ impl BinaryExpr {
     pub fn op(&self) -> &Operator {
         &self.op
     }
+
+    /// Are the rows of arithmetic that overflow or divide by zero null
+    pub fn lenient_arithmetic(&self) -> bool {
+        self.lenient_arithmetic
+    }
+
+    /// Does integer arithmetic wrap around on overflow
+    pub fn wrapping_arithmetic(&self) -> bool {
+        self.wrapping_arithmetic
+    }
+
+    /// Does dividing by zero fail rather than yield null
+    pub fn checked_division(&self) -> bool {
+        self.checked_division
+    }
 }
 
 impl std::fmt::Display for BinaryExpr {
@@ -551,7 +629,9 @@ macro_rules! compute_utf8_flag_op_scalar {
             .downcast_ref::<$ARRAYTYPE>()
             .expect("compute_utf8_flag_op_scalar failed to downcast array");
 
-        if let ScalarValue::Utf8(Some(string_value)) = $RIGHT {
+        if let ScalarValue::Utf8(Some(string_value))
+        | ScalarValue::LargeUtf8(Some(string_value)) = $RIGHT
+        {
             let flag = if $FLAG { Some("i") } else { None };
             let mut array =
                 paste::expr! {[<$OP _utf8_scalar>]}(&ll, &string_value, flag)?;
@@ -616,6 +696,49 @@ impl PhysicalExpr for BinaryExpr {
             }
         }
 
+        if self.lenient_arithmetic
+            && supports_checked_arithmetic(&self.op, &left_data_type)
+        {
+            return lenient_arithmetic(&left_value, &self.op, &right_value);
+        }
+
+        // arrow's unchecked kernels wrap around on overflow
+        if !self.wrapping_arithmetic
+            && supports_overflow_checks(&self.op, &left_data_type)
+        {
+            return overflow_checked_arithmetic(&left_value, &self.op, &right_value)
+                .map_err(|e| match e {
+                    DataFusionError::Execution(msg) => {
+                        DataFusionError::Execution(format!("{} in {}", msg, self))
+                    }
+                    e => e,
+                });
+        }
+
+        // String matches of a dictionary only need to evaluate its values
+        if let (true, DataType::Dictionary(_, value_type)) =
+            (is_string_match(&self.op), &left_data_type)
+        {
+            let left = left_value.into_array(batch.num_rows());
+            return match &right_value {
+                ColumnarValue::Scalar(scalar) => {
+                    self.evaluate_dictionary_scalar(&left, scalar)
+                }
+                ColumnarValue::Array(right) => {
+                    let left = cast(&left, value_type)?;
+                    let right = cast(right, value_type)?;
+                    self.evaluate_with_resolved_args(left, value_type, right, value_type)
+                }
+            }
+            .map(ColumnarValue::Array);
+        }
+
+        if supports_nested_comparison(&self.op, &left_data_type) {
+            let left = left_value.into_array(batch.num_rows());
+            let right = right_value.into_array(batch.num_rows());
+            return compare_nested(&left, &self.op, &right).map(ColumnarValue::Array);
+        }
+
         // Attempt to use special kernels if one input is scalar and the other is an array
         let scalar_result = match (&left_value, &right_value) {
             (ColumnarValue::Array(array), ColumnarValue::Scalar(scalar)) => {
@@ -650,11 +773,12 @@ impl PhysicalExpr for BinaryExpr {
         self: Arc<Self>,
         children: Vec<Arc<dyn PhysicalExpr>>,
     ) -> Result<Arc<dyn PhysicalExpr>> {
-        Ok(Arc::new(BinaryExpr::new(
-            children[0].clone(),
-            self.op,
-            children[1].clone(),
-        )))
+        Ok(Arc::new(
+            BinaryExpr::new(children[0].clone(), self.op, children[1].clone())
+                .with_lenient_arithmetic(self.lenient_arithmetic)
+                .with_wrapping_arithmetic(self.wrapping_arithmetic)
+                .with_checked_division(self.checked_division),
+        ))
     }
 
     /// Return the boundaries of this binary expression's result.
@@ -695,7 +819,14 @@ impl PartialEq<dyn Any> for BinaryExpr {
     fn eq(&self, other: &dyn Any) -> bool {
         down_cast_any_ref(other)
             .downcast_ref::<Self>()
-            .map(|x| self.left.eq(&x.left) && self.op == x.op && self.right.eq(&x.right))
+            .map(|x| {
+                self.left.eq(&x.left)
+                    && self.op == x.op
+                    && self.right.eq(&x.right)
+                    && self.lenient_arithmetic == x.lenient_arithmetic
+                    && self.wrapping_arithmetic == x.wrapping_arithmetic
+                    && self.checked_division == x.checked_division
+            })
             .unwrap_or(false)
     }
 }
@@ -840,6 +971,20 @@ pub(crate) fn array_eq_scalar(lhs: &dyn Array, rhs: &ScalarValue) -> Result<Arra
     )?
 }
 
+/// Returns true if `op` matches strings against a `LIKE` pattern or a
+/// regular expression
+fn is_string_match(op: &Operator) -> bool {
+    matches!(
+        op,
+        Operator::Like
+            | Operator::NotLike
+            | Operator::RegexMatch
+            | Operator::RegexIMatch
+            | Operator::RegexNotMatch
+            | Operator::RegexNotIMatch
+    )
+}
+
 impl BinaryExpr {
     /// Evaluate the expression of the left input is an array and
     /// right is literal - use scalar operations
@@ -933,6 +1078,53 @@ impl BinaryExpr {
         Ok(scalar_result)
     }
 
+    /// Evaluate a string match of a dictionary array against a literal on
+    /// the dictionary values only, looking up the result of each key
+    fn evaluate_dictionary_scalar(
+        &self,
+        array: &ArrayRef,
+        scalar: &ScalarValue,
+    ) -> Result<ArrayRef> {
+        let scalar = match scalar {
+            ScalarValue::Dictionary(_, value) => value.as_ref(),
+            scalar => scalar,
+        };
+        macro_rules! take_value_matches {
+            ($KEY_TYPE:ty) => {{
+                let dictionary = as_dictionary_array::<$KEY_TYPE>(array);
+                let matches = self
+                    .evaluate_array_scalar(dictionary.values().as_ref(), scalar)?
+                    .ok_or_else(|| {
+                        DataFusionError::Internal(format!(
+                            "Operator {} is not a string match",
+                            self.op
+                        ))
+                    })??;
+                Ok(take(matches.as_ref(), dictionary.keys(), None)?)
+            }};
+        }
+        match array.data_type() {
+            DataType::Dictionary(key_type, _) => match key_type.as_ref() {
+                DataType::Int8 => take_value_matches!(Int8Type),
+                DataType::Int16 => take_value_matches!(Int16Type),
+                DataType::Int32 => take_value_matches!(Int32Type),
+                DataType::Int64 => take_value_matches!(Int64Type),
+                DataType::UInt8 => take_value_matches!(UInt8Type),
+                DataType::UInt16 => take_value_matches!(UInt16Type),
+                DataType::UInt32 => take_value_matches!(UInt32Type),
+                DataType::UInt64 => take_value_matches!(UInt64Type),
+                other => Err(DataFusionError::Internal(format!(
+                    "Unsupported dictionary key type {:?}",
+                    other
+                ))),
+            },
+            other => Err(DataFusionError::Internal(format!(
+                "Expected a dictionary array, got {:?}",
+                other
+            ))),
+        }
+    }
+
     /// Evaluate the expression if the left input is a literal and the
     /// right is an array - reverse operator and parameters
     fn evaluate_scalar_array(
@@ -998,6 +1190,12 @@ impl BinaryExpr {
             Operator::Plus => binary_primitive_array_op!(left, right, add),
             Operator::Minus => binary_primitive_array_op!(left, right, subtract),
             Operator::Multiply => binary_primitive_array_op!(left, right, multiply),
+            Operator::Divide
+                if self.checked_division
+                    && supports_checked_arithmetic(&self.op, left_data_type) =>
+            {
+                checked_arithmetic(&left, &self.op, &right)
+            }
             Operator::Divide => binary_primitive_array_op!(left, right, divide_opt),
             Operator::Modulo => binary_primitive_array_op!(left, right, modulus),
             Operator::And => {
@@ -1055,15 +1253,7 @@ pub fn binary(
     rhs: Arc<dyn PhysicalExpr>,
     input_schema: &Schema,
 ) -> Result<Arc<dyn PhysicalExpr>> {
-    let lhs_type = &lhs.data_type(input_schema)?;
-    let rhs_type = &rhs.data_type(input_schema)?;
-    if !lhs_type.eq(rhs_type) {
-        return Err(DataFusionError::Internal(format!(
-            "The type of {} {} {} of binary physical should be same",
-            lhs_type, op, rhs_type
-        )));
-    }
-    Ok(Arc::new(BinaryExpr::new(lhs, op, rhs)))
+    Ok(Arc::new(BinaryExpr::try_new(lhs, op, rhs, input_schema)?))
 }
 
 #[cfg(test)]
@@ -1071,7 +1261,7 @@ mod tests {
     use super::*;
     use crate::expressions::try_cast;
     use crate::expressions::{col, lit};
-    use arrow::datatypes::{ArrowNumericType, Field, Int32Type, SchemaRef};
+    use arrow::datatypes::{ArrowNumericType, Field, SchemaRef};
     use datafusion_common::{ColumnStatistics, Result, Statistics};
     use datafusion_expr::type_coercion::binary::coerce_types;
 
@@ -1508,6 +1698,54 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn dictionary_string_match() -> Result<()> {
+        let dict_type =
+            DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8));
+        let schema = Schema::new(vec![Field::new("a", dict_type, true)]);
+        let mut dict_builder = StringDictionaryBuilder::<Int32Type>::new();
+        dict_builder.append("abc")?;
+        dict_builder.append_null();
+        dict_builder.append("x%y")?;
+        dict_builder.append("abc")?;
+        let batch = RecordBatch::try_new(
+            Arc::new(schema.clone()),
+            vec![Arc::new(dict_builder.finish())],
+        )?;
+
+        let cases = vec![
+            (
+                Operator::Like,
+                ScalarValue::Utf8(Some("%y".to_string())),
+                vec![Some(false), None, Some(true), Some(false)],
+            ),
+            (
+                Operator::NotLike,
+                ScalarValue::Utf8(Some("a%".to_string())),
+                vec![Some(false), None, Some(true), Some(false)],
+            ),
+            (
+                Operator::RegexIMatch,
+                ScalarValue::Dictionary(
+                    Box::new(DataType::Int32),
+                    Box::new(ScalarValue::Utf8(Some("^A".to_string()))),
+                ),
+                vec![Some(true), None, Some(false), Some(true)],
+            ),
+        ];
+        for (op, pattern, expected) in cases {
+            let expr = BinaryExpr::new(col("a", &schema)?, op, lit(pattern));
+            let result = expr.evaluate(&batch)?.into_array(batch.num_rows());
+            assert_eq!(
+                as_boolean_array(&result),
+                &BooleanArray::from(expected),
+                "{}",
+                expr
+            );
+        }
+        Ok(())
+    }
+
     #[test]
     fn plus_op() -> Result<()> {
         let schema = Schema::new(vec![
@@ -1611,6 +1849,150 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn divide_by_zero_op() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Int32, true),
+        ]));
+        let a: ArrayRef = Arc::new(Int32Array::from(vec![Some(8), Some(9), None]));
+        let b: ArrayRef = Arc::new(Int32Array::from(vec![Some(2), Some(0), Some(0)]));
+        let batch = RecordBatch::try_new(schema.clone(), vec![a, b])?;
+
+        // division by zero yields null by default
+        let expr =
+            BinaryExpr::new(col("a", &schema)?, Operator::Divide, col("b", &schema)?);
+        let result = expr.evaluate(&batch)?.into_array(batch.num_rows());
+        let expected = Int32Array::from(vec![Some(4), None, None]);
+        assert_eq!(result.as_ref(), &expected);
+
+        let checked =
+            BinaryExpr::new(col("a", &schema)?, Operator::Divide, col("b", &schema)?)
+                .with_checked_division(true);
+        let err = checked.evaluate(&batch).unwrap_err();
+        assert!(err.to_string().contains("Divide by zero"), "{}", err);
+
+        // lenient arithmetic takes precedence over checked division
+        let expr = checked.with_lenient_arithmetic(true);
+        let result = expr.evaluate(&batch)?.into_array(batch.num_rows());
+        let expected = Int32Array::from(vec![Some(4), None, None]);
+        assert_eq!(result.as_ref(), &expected);
+        Ok(())
+    }
+
+    #[test]
+    fn lenient_arithmetic_op() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int8, false),
+            Field::new("b", DataType::Int8, false),
+        ]));
+        let a: ArrayRef = Arc::new(Int8Array::from(vec![100, -128, 7]));
+        let b: ArrayRef = Arc::new(Int8Array::from(vec![100, -1, 0]));
+        let batch = RecordBatch::try_new(schema.clone(), vec![a, b])?;
+
+        for (op, expected) in [
+            (Operator::Plus, vec![None, None, Some(7)]),
+            (Operator::Minus, vec![Some(0), Some(-127), Some(7)]),
+            (Operator::Multiply, vec![None, None, Some(0)]),
+            (Operator::Divide, vec![Some(1), None, None]),
+            (Operator::Modulo, vec![Some(0), None, None]),
+        ] {
+            let expr = BinaryExpr::new(col("a", &schema)?, op, col("b", &schema)?)
+                .with_lenient_arithmetic(true);
+            let result = expr.evaluate(&batch)?.into_array(batch.num_rows());
+            assert_eq!(result.as_ref(), &Int8Array::from(expected), "{}", op);
+        }
+
+        // a scalar divisor of zero nulls every row
+        let expr = BinaryExpr::new(col("a", &schema)?, Operator::Divide, lit(0i8))
+            .with_lenient_arithmetic(true);
+        let result = expr.evaluate(&batch)?.into_array(batch.num_rows());
+        assert_eq!(result.as_ref(), &Int8Array::from(vec![None, None, None]));
+
+        // scalars may be on either side
+        let expr = BinaryExpr::new(lit(100i8), Operator::Plus, col("a", &schema)?)
+            .with_lenient_arithmetic(true);
+        let result = expr.evaluate(&batch)?.into_array(batch.num_rows());
+        let expected = Int8Array::from(vec![None, Some(-28), Some(107)]);
+        assert_eq!(result.as_ref(), &expected);
+
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Float64, false),
+            Field::new("b", DataType::Float64, false),
+        ]));
+        let a: ArrayRef = Arc::new(Float64Array::from(vec![1.0, 1.0]));
+        let b: ArrayRef = Arc::new(Float64Array::from(vec![4.0, 0.0]));
+        let batch = RecordBatch::try_new(schema.clone(), vec![a, b])?;
+        let expr =
+            BinaryExpr::new(col("a", &schema)?, Operator::Divide, col("b", &schema)?)
+                .with_lenient_arithmetic(true);
+        let result = expr.evaluate(&batch)?.into_array(batch.num_rows());
+        assert_eq!(result.as_ref(), &Float64Array::from(vec![Some(0.25), None]));
+        Ok(())
+    }
+
+    #[test]
+    fn overflow_checked_op() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int64, false)]));
+        let a: ArrayRef = Arc::new(Int64Array::from(vec![1, i64::MAX]));
+        let batch = RecordBatch::try_new(schema.clone(), vec![a])?;
+
+        // integer overflows are an error naming the expression by default
+        let expr = BinaryExpr::new(col("a", &schema)?, Operator::Plus, lit(1i64));
+        let err = expr.evaluate(&batch).unwrap_err().to_string();
+        assert!(err.starts_with("Execution error: Overflow"), "{}", err);
+        assert!(err.ends_with(" in a@0 + 1"), "{}", err);
+
+        for (op, right, expected) in [
+            (Operator::Plus, 1i64, vec![2, i64::MIN]),
+            (Operator::Minus, -1i64, vec![2, i64::MIN]),
+            (Operator::Multiply, 2i64, vec![2, -2]),
+        ] {
+            let expr = BinaryExpr::new(col("a", &schema)?, op, lit(right));
+            assert!(expr.evaluate(&batch).is_err(), "{}", op);
+
+            let expr = BinaryExpr::new(col("a", &schema)?, op, lit(right))
+                .with_wrapping_arithmetic(true);
+            let result = expr.evaluate(&batch)?.into_array(batch.num_rows());
+            assert_eq!(result.as_ref(), &Int64Array::from(expected), "{}", op);
+        }
+
+        // scalars on either side are checked too, without being expanded
+        // unless subtracted from
+        for (left, op, expected) in [
+            (1i64, Operator::Plus, None),
+            (1i64, Operator::Multiply, Some(vec![1, i64::MAX])),
+            (-2i64, Operator::Minus, None),
+            (i64::MAX, Operator::Minus, Some(vec![i64::MAX - 1, 0])),
+        ] {
+            let expr = BinaryExpr::new(lit(left), op, col("a", &schema)?);
+            match expected {
+                Some(expected) => {
+                    let result = expr.evaluate(&batch)?.into_array(batch.num_rows());
+                    assert_eq!(result.as_ref(), &Int64Array::from(expected), "{}", op);
+                }
+                None => assert!(expr.evaluate(&batch).is_err(), "{}", op),
+            }
+        }
+        let expr = BinaryExpr::new(lit(2i64), Operator::Multiply, lit(3i64));
+        match expr.evaluate(&batch)? {
+            ColumnarValue::Scalar(scalar) => {
+                assert_eq!(scalar, ScalarValue::Int64(Some(6)))
+            }
+            ColumnarValue::Array(_) => panic!("expected a scalar"),
+        }
+        let expr = BinaryExpr::new(lit(i64::MAX), Operator::Plus, lit(1i64));
+        assert!(expr.evaluate(&batch).is_err());
+        let expr = BinaryExpr::new(
+            col("a", &schema)?,
+            Operator::Plus,
+            lit(ScalarValue::Int64(None)),
+        );
+        let result = expr.evaluate(&batch)?.into_array(batch.num_rows());
+        assert_eq!(result.as_ref(), &Int64Array::from(vec![None, None]));
+        Ok(())
+    }
+
     fn apply_arithmetic<T: ArrowNumericType>(
         schema: SchemaRef,
         data: Vec<ArrayRef>,
@@ -2891,6 +3273,96 @@ mod tests {
         (schema, statistics)
     }
 
+    #[test]
+    fn comparison_nested_test() -> Result<()> {
+        let fields = vec![
+            Field::new("x", DataType::Int32, true),
+            Field::new("y", DataType::Utf8, true),
+        ];
+        let struct_value = |x: i32, y: &str| {
+            ScalarValue::Struct(
+                Some(vec![
+                    ScalarValue::Int32(Some(x)),
+                    ScalarValue::Utf8(Some(y.to_string())),
+                ]),
+                Box::new(fields.clone()),
+            )
+        };
+        let structs = ScalarValue::iter_to_array(vec![
+            struct_value(1, "a"),
+            ScalarValue::Struct(None, Box::new(fields.clone())),
+            struct_value(1, "b"),
+            struct_value(0, "z"),
+        ])?;
+        let schema = Arc::new(Schema::new(vec![Field::new(
+            "a",
+            structs.data_type().clone(),
+            true,
+        )]));
+        let scalar = struct_value(1, "a");
+        apply_logic_op_arr_scalar(
+            &schema,
+            &structs,
+            &scalar,
+            Operator::Eq,
+            &BooleanArray::from(vec![Some(true), None, Some(false), Some(false)]),
+        )?;
+        apply_logic_op_arr_scalar(
+            &schema,
+            &structs,
+            &scalar,
+            Operator::Gt,
+            &BooleanArray::from(vec![Some(false), None, Some(true), Some(false)]),
+        )?;
+        apply_logic_op_scalar_arr(
+            &schema,
+            &scalar,
+            &structs,
+            Operator::LtEq,
+            &BooleanArray::from(vec![Some(true), None, Some(true), Some(false)]),
+        )?;
+
+        let schema = Arc::new(Schema::new(vec![
+            Field::new(
+                "a",
+                DataType::List(Box::new(Field::new("item", DataType::Int64, true))),
+                true,
+            ),
+            Field::new(
+                "b",
+                DataType::List(Box::new(Field::new("item", DataType::Int64, true))),
+                true,
+            ),
+        ]));
+        let a: ArrayRef =
+            Arc::new(ListArray::from_iter_primitive::<Int64Type, _, _>(vec![
+                Some(vec![Some(1), Some(2)]),
+                Some(vec![Some(3)]),
+                None,
+            ]));
+        let b: ArrayRef =
+            Arc::new(ListArray::from_iter_primitive::<Int64Type, _, _>(vec![
+                Some(vec![Some(1), Some(2)]),
+                Some(vec![Some(1), Some(5)]),
+                Some(vec![]),
+            ]));
+        apply_logic_op(
+            &schema,
+            &a,
+            &b,
+            Operator::Eq,
+            BooleanArray::from(vec![Some(true), Some(false), None]),
+        )?;
+        apply_logic_op(
+            &schema,
+            &a,
+            &b,
+            Operator::Lt,
+            BooleanArray::from(vec![Some(false), Some(false), None]),
+        )?;
+        Ok(())
+    }
+
     #[test]
     fn test_analyze_expr_scalar_comparison() -> Result<()> {
         // A table where the column 'a' has a min of 1, a max of 100.
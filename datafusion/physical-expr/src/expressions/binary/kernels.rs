@@ -18,10 +18,19 @@
 //! This module contains computation kernels that are specific to
 //! datafusion and not (yet) targeted to  port upstream to arrow
 use arrow::array::*;
-use arrow::datatypes::DataType;
+use arrow::compute::kernels::arithmetic::{
+    add_checked, add_scalar_checked, divide_checked, modulus, multiply_checked,
+    multiply_scalar_checked, subtract_checked, subtract_scalar_checked,
+};
+use arrow::datatypes::{
+    ArrowNumericType, DataType, Float32Type, Float64Type, Int16Type, Int32Type,
+    Int64Type, Int8Type, UInt16Type, UInt32Type, UInt64Type, UInt8Type,
+};
+use arrow::error::{ArrowError, Result as ArrowResult};
 use datafusion_common::{DataFusionError, Result, ScalarValue};
-use datafusion_expr::Operator;
+use datafusion_expr::{ColumnarValue, Operator};
 
+use std::cmp::Ordering;
 use std::sync::Arc;
 
 /// The binary_bitwise_array_op macro only evaluates for integer types
@@ -400,3 +409,407 @@ pub(crate) fn bitwise_shift_left_scalar(
     };
     Some(result)
 }
+
+/// Returns the value of a primitive scalar, or `None` if it is null
+fn primitive_scalar<T: ArrowNumericType>(scalar: &ScalarValue) -> Option<T::Native> {
+    let array = scalar.to_array_of_size(1);
+    let array = as_primitive_array::<T>(&array);
+    array.is_valid(0).then(|| array.value(0))
+}
+
+/// Evaluates an arithmetic operation on two arrays or scalars of `T` row by
+/// row with `method`, which returns `None` if the operation overflows or
+/// divides by zero, such rows being null. Scalars are not expanded into arrays.
+fn lenient_kernel<T: ArrowNumericType>(
+    left: &ColumnarValue,
+    right: &ColumnarValue,
+    method: impl Fn(T::Native, T::Native) -> Option<T::Native>,
+) -> Result<ColumnarValue> {
+    let op = |left: Option<T::Native>, right: Option<T::Native>| match (left, right) {
+        (Some(left), Some(right)) => method(left, right),
+        _ => None,
+    };
+    let result: PrimitiveArray<T> = match (left, right) {
+        (ColumnarValue::Array(left), ColumnarValue::Array(right)) => {
+            as_primitive_array::<T>(left)
+                .iter()
+                .zip(as_primitive_array::<T>(right).iter())
+                .map(|(left, right)| op(left, right))
+                .collect()
+        }
+        (ColumnarValue::Array(left), ColumnarValue::Scalar(right)) => {
+            let right = primitive_scalar::<T>(right);
+            as_primitive_array::<T>(left)
+                .iter()
+                .map(|left| op(left, right))
+                .collect()
+        }
+        (ColumnarValue::Scalar(left), ColumnarValue::Array(right)) => {
+            let left = primitive_scalar::<T>(left);
+            as_primitive_array::<T>(right)
+                .iter()
+                .map(|right| op(left, right))
+                .collect()
+        }
+        (ColumnarValue::Scalar(left), ColumnarValue::Scalar(right)) => {
+            let result = op(primitive_scalar::<T>(left), primitive_scalar::<T>(right));
+            let result: PrimitiveArray<T> = std::iter::once(result).collect();
+            let result = Arc::new(result) as ArrayRef;
+            return Ok(ColumnarValue::Scalar(ScalarValue::try_from_array(
+                &result, 0,
+            )?));
+        }
+    };
+    Ok(ColumnarValue::Array(Arc::new(result)))
+}
+
+macro_rules! lenient_integer_op {
+    ($LEFT:expr, $OP:expr, $RIGHT:expr, $ARROW_TYPE:ident, $TYPE:ty) => {{
+        match $OP {
+            Operator::Plus => {
+                lenient_kernel::<$ARROW_TYPE>($LEFT, $RIGHT, |a: $TYPE, b: $TYPE| {
+                    a.checked_add(b)
+                })
+            }
+            Operator::Minus => {
+                lenient_kernel::<$ARROW_TYPE>($LEFT, $RIGHT, |a: $TYPE, b: $TYPE| {
+                    a.checked_sub(b)
+                })
+            }
+            Operator::Multiply => {
+                lenient_kernel::<$ARROW_TYPE>($LEFT, $RIGHT, |a: $TYPE, b: $TYPE| {
+                    a.checked_mul(b)
+                })
+            }
+            Operator::Divide => {
+                lenient_kernel::<$ARROW_TYPE>($LEFT, $RIGHT, |a: $TYPE, b: $TYPE| {
+                    a.checked_div(b)
+                })
+            }
+            Operator::Modulo => {
+                lenient_kernel::<$ARROW_TYPE>($LEFT, $RIGHT, |a: $TYPE, b: $TYPE| {
+                    a.checked_rem(b)
+                })
+            }
+            other => Err(DataFusionError::Internal(format!(
+                "{} is not a checked arithmetic operator",
+                other
+            ))),
+        }
+    }};
+}
+
+macro_rules! lenient_float_op {
+    ($LEFT:expr, $OP:expr, $RIGHT:expr, $ARROW_TYPE:ident, $TYPE:ty) => {{
+        match $OP {
+            Operator::Plus => {
+                lenient_kernel::<$ARROW_TYPE>($LEFT, $RIGHT, |a: $TYPE, b: $TYPE| {
+                    Some(a + b)
+                })
+            }
+            Operator::Minus => {
+                lenient_kernel::<$ARROW_TYPE>($LEFT, $RIGHT, |a: $TYPE, b: $TYPE| {
+                    Some(a - b)
+                })
+            }
+            Operator::Multiply => {
+                lenient_kernel::<$ARROW_TYPE>($LEFT, $RIGHT, |a: $TYPE, b: $TYPE| {
+                    Some(a * b)
+                })
+            }
+            Operator::Divide => {
+                lenient_kernel::<$ARROW_TYPE>($LEFT, $RIGHT, |a: $TYPE, b: $TYPE| {
+                    if b == 0.0 {
+                        None
+                    } else {
+                        Some(a / b)
+                    }
+                })
+            }
+            Operator::Modulo => {
+                lenient_kernel::<$ARROW_TYPE>($LEFT, $RIGHT, |a: $TYPE, b: $TYPE| {
+                    if b == 0.0 {
+                        None
+                    } else {
+                        Some(a % b)
+                    }
+                })
+            }
+            other => Err(DataFusionError::Internal(format!(
+                "{} is not a checked arithmetic operator",
+                other
+            ))),
+        }
+    }};
+}
+
+/// Returns true if `op` on arrays of `data_type` can overflow, in which case
+/// it is evaluated with [`checked_arithmetic`] unless wrapping around
+/// on overflow is allowed
+pub(crate) fn supports_overflow_checks(op: &Operator, data_type: &DataType) -> bool {
+    matches!(op, Operator::Plus | Operator::Minus | Operator::Multiply)
+        && supports_checked_arithmetic(op, data_type)
+        && !matches!(data_type, DataType::Float32 | DataType::Float64)
+}
+
+/// Returns true if [`checked_arithmetic`] and [`lenient_arithmetic`] evaluate
+/// `op` on arrays of `data_type`
+pub(crate) fn supports_checked_arithmetic(op: &Operator, data_type: &DataType) -> bool {
+    matches!(
+        op,
+        Operator::Plus
+            | Operator::Minus
+            | Operator::Multiply
+            | Operator::Divide
+            | Operator::Modulo
+    ) && matches!(
+        data_type,
+        DataType::Int8
+            | DataType::Int16
+            | DataType::Int32
+            | DataType::Int64
+            | DataType::UInt8
+            | DataType::UInt16
+            | DataType::UInt32
+            | DataType::UInt64
+            | DataType::Float32
+            | DataType::Float64
+    )
+}
+
+/// Evaluates the checked arrow kernel `op` on two primitive arrays, an
+/// overflow being an execution error
+fn checked_kernel<T: ArrowNumericType>(
+    left: &ArrayRef,
+    right: &ArrayRef,
+    op: fn(&PrimitiveArray<T>, &PrimitiveArray<T>) -> ArrowResult<PrimitiveArray<T>>,
+) -> Result<ArrayRef> {
+    let result =
+        op(as_primitive_array(left), as_primitive_array(right)).map_err(|e| match e {
+            ArrowError::ComputeError(msg) => DataFusionError::Execution(msg),
+            e => DataFusionError::ArrowError(e),
+        })?;
+    Ok(Arc::new(result))
+}
+
+macro_rules! checked_kernel_op {
+    ($LEFT:expr, $RIGHT:expr, $KERNEL:ident) => {{
+        match $LEFT.data_type() {
+            DataType::Int8 => checked_kernel($LEFT, $RIGHT, $KERNEL::<Int8Type>),
+            DataType::Int16 => checked_kernel($LEFT, $RIGHT, $KERNEL::<Int16Type>),
+            DataType::Int32 => checked_kernel($LEFT, $RIGHT, $KERNEL::<Int32Type>),
+            DataType::Int64 => checked_kernel($LEFT, $RIGHT, $KERNEL::<Int64Type>),
+            DataType::UInt8 => checked_kernel($LEFT, $RIGHT, $KERNEL::<UInt8Type>),
+            DataType::UInt16 => checked_kernel($LEFT, $RIGHT, $KERNEL::<UInt16Type>),
+            DataType::UInt32 => checked_kernel($LEFT, $RIGHT, $KERNEL::<UInt32Type>),
+            DataType::UInt64 => checked_kernel($LEFT, $RIGHT, $KERNEL::<UInt64Type>),
+            DataType::Float32 => checked_kernel($LEFT, $RIGHT, $KERNEL::<Float32Type>),
+            DataType::Float64 => checked_kernel($LEFT, $RIGHT, $KERNEL::<Float64Type>),
+            other => Err(DataFusionError::Internal(format!(
+                "Data type {:?} not supported for checked arithmetic operation '{}'",
+                other,
+                stringify!($KERNEL)
+            ))),
+        }
+    }};
+}
+
+/// Evaluates the arithmetic operator `op` on two integer or floating point
+/// arrays of the same type with arrow's checked kernels, failing if any row
+/// overflows or divides by zero
+pub(crate) fn checked_arithmetic(
+    left: &ArrayRef,
+    op: &Operator,
+    right: &ArrayRef,
+) -> Result<ArrayRef> {
+    match op {
+        Operator::Plus => checked_kernel_op!(left, right, add_checked),
+        Operator::Minus => checked_kernel_op!(left, right, subtract_checked),
+        Operator::Multiply => checked_kernel_op!(left, right, multiply_checked),
+        Operator::Divide => checked_kernel_op!(left, right, divide_checked),
+        // arrow's modulus kernel already fails on division by zero
+        Operator::Modulo => checked_kernel_op!(left, right, modulus),
+        other => Err(DataFusionError::Internal(format!(
+            "{} is not a checked arithmetic operator",
+            other
+        ))),
+    }
+}
+
+/// Evaluates the checked arrow kernel `op` on a primitive array and a scalar,
+/// an overflow being an execution error
+fn checked_scalar_kernel<T: ArrowNumericType>(
+    array: &ArrayRef,
+    scalar: &ScalarValue,
+    op: fn(&PrimitiveArray<T>, T::Native) -> ArrowResult<PrimitiveArray<T>>,
+) -> Result<ArrayRef> {
+    let scalar = match primitive_scalar::<T>(scalar) {
+        Some(scalar) => scalar,
+        None => return Ok(new_null_array(array.data_type(), array.len())),
+    };
+    let result = op(as_primitive_array(array), scalar).map_err(|e| match e {
+        ArrowError::ComputeError(msg) => DataFusionError::Execution(msg),
+        e => DataFusionError::ArrowError(e),
+    })?;
+    Ok(Arc::new(result))
+}
+
+macro_rules! checked_scalar_kernel_op {
+    ($ARRAY:expr, $SCALAR:expr, $KERNEL:ident) => {{
+        match $ARRAY.data_type() {
+            DataType::Int8 => checked_scalar_kernel($ARRAY, $SCALAR, $KERNEL::<Int8Type>),
+            DataType::Int16 => {
+                checked_scalar_kernel($ARRAY, $SCALAR, $KERNEL::<Int16Type>)
+            }
+            DataType::Int32 => {
+                checked_scalar_kernel($ARRAY, $SCALAR, $KERNEL::<Int32Type>)
+            }
+            DataType::Int64 => {
+                checked_scalar_kernel($ARRAY, $SCALAR, $KERNEL::<Int64Type>)
+            }
+            DataType::UInt8 => {
+                checked_scalar_kernel($ARRAY, $SCALAR, $KERNEL::<UInt8Type>)
+            }
+            DataType::UInt16 => {
+                checked_scalar_kernel($ARRAY, $SCALAR, $KERNEL::<UInt16Type>)
+            }
+            DataType::UInt32 => {
+                checked_scalar_kernel($ARRAY, $SCALAR, $KERNEL::<UInt32Type>)
+            }
+            DataType::UInt64 => {
+                checked_scalar_kernel($ARRAY, $SCALAR, $KERNEL::<UInt64Type>)
+            }
+            other => Err(DataFusionError::Internal(format!(
+                "Data type {:?} not supported for checked arithmetic operation '{}'",
+                other,
+                stringify!($KERNEL)
+            ))),
+        }
+    }};
+}
+
+/// Evaluates the integer addition, subtraction or multiplication `op` on an
+/// array and a scalar of the same type with arrow's checked scalar kernels,
+/// failing if any row overflows
+fn checked_arithmetic_scalar(
+    array: &ArrayRef,
+    op: &Operator,
+    scalar: &ScalarValue,
+) -> Result<ArrayRef> {
+    match op {
+        Operator::Plus => checked_scalar_kernel_op!(array, scalar, add_scalar_checked),
+        Operator::Minus => {
+            checked_scalar_kernel_op!(array, scalar, subtract_scalar_checked)
+        }
+        Operator::Multiply => {
+            checked_scalar_kernel_op!(array, scalar, multiply_scalar_checked)
+        }
+        other => Err(DataFusionError::Internal(format!(
+            "{} is not an overflow checked operator",
+            other
+        ))),
+    }
+}
+
+/// Evaluates the integer addition, subtraction or multiplication `op` on two
+/// arrays or scalars of the same type, failing if any row overflows. Scalars
+/// are only expanded into arrays on the left of a subtraction, which has no
+/// scalar kernel.
+pub(crate) fn overflow_checked_arithmetic(
+    left: &ColumnarValue,
+    op: &Operator,
+    right: &ColumnarValue,
+) -> Result<ColumnarValue> {
+    let result = match (left, right) {
+        (ColumnarValue::Array(left), ColumnarValue::Array(right)) => {
+            checked_arithmetic(left, op, right)?
+        }
+        (ColumnarValue::Array(left), ColumnarValue::Scalar(right)) => {
+            checked_arithmetic_scalar(left, op, right)?
+        }
+        (ColumnarValue::Scalar(left), ColumnarValue::Array(right))
+            if matches!(op, Operator::Plus | Operator::Multiply) =>
+        {
+            checked_arithmetic_scalar(right, op, left)?
+        }
+        (ColumnarValue::Scalar(left), ColumnarValue::Array(right)) => {
+            checked_arithmetic(&left.to_array_of_size(right.len()), op, right)?
+        }
+        (ColumnarValue::Scalar(left), ColumnarValue::Scalar(right)) => {
+            let result = checked_arithmetic_scalar(&left.to_array_of_size(1), op, right)?;
+            return Ok(ColumnarValue::Scalar(ScalarValue::try_from_array(
+                &result, 0,
+            )?));
+        }
+    };
+    Ok(ColumnarValue::Array(result))
+}
+
+/// Evaluates the arithmetic operator `op` on two integer or floating point
+/// arrays or scalars of the same type row by row, the rows that overflow or
+/// divide by zero being null
+pub(crate) fn lenient_arithmetic(
+    left: &ColumnarValue,
+    op: &Operator,
+    right: &ColumnarValue,
+) -> Result<ColumnarValue> {
+    match left.data_type() {
+        DataType::Int8 => lenient_integer_op!(left, op, right, Int8Type, i8),
+        DataType::Int16 => lenient_integer_op!(left, op, right, Int16Type, i16),
+        DataType::Int32 => lenient_integer_op!(left, op, right, Int32Type, i32),
+        DataType::Int64 => lenient_integer_op!(left, op, right, Int64Type, i64),
+        DataType::UInt8 => lenient_integer_op!(left, op, right, UInt8Type, u8),
+        DataType::UInt16 => lenient_integer_op!(left, op, right, UInt16Type, u16),
+        DataType::UInt32 => lenient_integer_op!(left, op, right, UInt32Type, u32),
+        DataType::UInt64 => lenient_integer_op!(left, op, right, UInt64Type, u64),
+        DataType::Float32 => lenient_float_op!(left, op, right, Float32Type, f32),
+        DataType::Float64 => lenient_float_op!(left, op, right, Float64Type, f64),
+        other => Err(DataFusionError::Internal(format!(
+            "Data type {:?} not supported for lenient arithmetic operation '{}'",
+            other, op
+        ))),
+    }
+}
+
+/// Returns true if `op` compares arrays of `data_type` with
+/// [`compare_nested`], as arrow has no comparison kernels for nested types
+pub(crate) fn supports_nested_comparison(op: &Operator, data_type: &DataType) -> bool {
+    matches!(
+        op,
+        Operator::Eq
+            | Operator::NotEq
+            | Operator::Lt
+            | Operator::LtEq
+            | Operator::Gt
+            | Operator::GtEq
+    ) && matches!(data_type, DataType::List(_) | DataType::Struct(_))
+}
+
+/// Evaluates the comparison operator `op` on two list or struct arrays of
+/// the same type row by row, ordering the rows as [`ScalarValue`]s. A row
+/// is null if either input row is null.
+pub(crate) fn compare_nested(
+    left: &ArrayRef,
+    op: &Operator,
+    right: &ArrayRef,
+) -> Result<ArrayRef> {
+    let result = (0..left.len())
+        .map(|i| {
+            if left.is_null(i) || right.is_null(i) {
+                return Ok(None);
+            }
+            let ordering = ScalarValue::try_from_array(left, i)?
+                .partial_cmp(&ScalarValue::try_from_array(right, i)?);
+            Ok(ordering.map(|ordering| match op {
+                Operator::Eq => ordering == Ordering::Equal,
+                Operator::NotEq => ordering != Ordering::Equal,
+                Operator::Lt => ordering == Ordering::Less,
+                Operator::LtEq => ordering != Ordering::Greater,
+                Operator::Gt => ordering == Ordering::Greater,
+                // GtEq, the only other operator of supports_nested_comparison
+                _ => ordering != Ordering::Less,
+            }))
+        })
+        .collect::<Result<BooleanArray>>()?;
+    Ok(Arc::new(result))
+}
@@ -0,0 +1,213 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! COALESCE expression
+
+use std::{any::Any, sync::Arc};
+
+use crate::physical_expr::down_cast_any_ref;
+use crate::PhysicalExpr;
+use arrow::array::{new_null_array, BooleanArray};
+use arrow::compute::kernels::zip::zip;
+use arrow::compute::{and, is_not_null, is_null};
+use arrow::datatypes::{DataType, Schema};
+use arrow::record_batch::RecordBatch;
+use datafusion_common::{Result, ScalarValue};
+use datafusion_expr::ColumnarValue;
+use itertools::Itertools;
+
+/// COALESCE expression, which evaluates to the first of its arguments that
+/// is not NULL.
+///
+/// Unlike other functions, the arguments are evaluated lazily: an argument
+/// is only evaluated on the rows where all of the arguments before it are
+/// NULL, so that e.g. a division by zero in a fallback argument does not
+/// fail the rows that do not need it.
+#[derive(Debug)]
+pub struct CoalesceExpr {
+    /// The arguments, coerced to `return_type`
+    args: Vec<Arc<dyn PhysicalExpr>>,
+    return_type: DataType,
+}
+
+impl CoalesceExpr {
+    /// Create a new COALESCE expression
+    pub fn new(args: Vec<Arc<dyn PhysicalExpr>>, return_type: DataType) -> Self {
+        Self { args, return_type }
+    }
+
+    /// Get the arguments
+    pub fn args(&self) -> &[Arc<dyn PhysicalExpr>] {
+        &self.args
+    }
+}
+
+impl std::fmt::Display for CoalesceExpr {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "coalesce({})", self.args.iter().join(", "))
+    }
+}
+
+impl PhysicalExpr for CoalesceExpr {
+    /// Return a reference to Any that can be used for downcasting
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn data_type(&self, _input_schema: &Schema) -> Result<DataType> {
+        Ok(self.return_type.clone())
+    }
+
+    fn nullable(&self, input_schema: &Schema) -> Result<bool> {
+        for arg in &self.args {
+            if !arg.nullable(input_schema)? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    fn evaluate(&self, batch: &RecordBatch) -> Result<ColumnarValue> {
+        let num_rows = batch.num_rows();
+        let mut current_value = new_null_array(&self.return_type, num_rows);
+        // the rows whose value is still NULL
+        let mut remainder = BooleanArray::from(vec![true; num_rows]);
+        let mut is_scalar = true;
+
+        for arg in &self.args {
+            let value = if is_scalar {
+                arg.evaluate(batch)?
+            } else {
+                arg.evaluate_selection(batch, &remainder)?
+            };
+            match value {
+                ColumnarValue::Scalar(value) if value.is_null() => continue,
+                // every row so far is NULL, so the result is this value
+                ColumnarValue::Scalar(value) if is_scalar => {
+                    return Ok(ColumnarValue::Scalar(value));
+                }
+                ColumnarValue::Scalar(value) => {
+                    let value = value.to_array_of_size(num_rows);
+                    current_value =
+                        zip(&remainder, value.as_ref(), current_value.as_ref())?;
+                    return Ok(ColumnarValue::Array(current_value));
+                }
+                ColumnarValue::Array(array) => {
+                    is_scalar = false;
+                    let to_apply = and(&remainder, &is_not_null(array.as_ref())?)?;
+                    current_value =
+                        zip(&to_apply, array.as_ref(), current_value.as_ref())?;
+                    remainder = and(&remainder, &is_null(array.as_ref())?)?;
+                }
+            }
+            if remainder.true_count() == 0 {
+                break;
+            }
+        }
+
+        if is_scalar {
+            Ok(ColumnarValue::Scalar(ScalarValue::try_from(
+                &self.return_type,
+            )?))
+        } else {
+            Ok(ColumnarValue::Array(current_value))
+        }
+    }
+
+    fn children(&self) -> Vec<Arc<dyn PhysicalExpr>> {
+        self.args.clone()
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn PhysicalExpr>>,
+    ) -> Result<Arc<dyn PhysicalExpr>> {
+        Ok(Arc::new(CoalesceExpr::new(
+            children,
+            self.return_type.clone(),
+        )))
+    }
+}
+
+impl PartialEq<dyn Any> for CoalesceExpr {
+    fn eq(&self, other: &dyn Any) -> bool {
+        down_cast_any_ref(other)
+            .downcast_ref::<Self>()
+            .map(|x| {
+                self.return_type == x.return_type
+                    && self.args.len() == x.args.len()
+                    && self.args.iter().zip(x.args.iter()).all(|(a, b)| a.eq(b))
+            })
+            .unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expressions::{binary, col, lit};
+    use arrow::array::{ArrayRef, Int64Array};
+    use arrow::datatypes::Field;
+    use datafusion_expr::Operator;
+
+    #[test]
+    fn coalesce_skips_evaluation_of_unused_args() -> Result<()> {
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int64, true),
+            Field::new("b", DataType::Int64, true),
+        ]);
+        let a: ArrayRef = Arc::new(Int64Array::from(vec![Some(1), None, Some(3)]));
+        let b: ArrayRef = Arc::new(Int64Array::from(vec![Some(0), Some(2), Some(0)]));
+        let batch = RecordBatch::try_new(Arc::new(schema.clone()), vec![a, b])?;
+
+        // `10 / b` fails on the first and last rows, where `a` is not NULL
+        let divide = binary(lit(10i64), Operator::Divide, col("b", &schema)?, &schema)?;
+        let expr = CoalesceExpr::new(vec![col("a", &schema)?, divide], DataType::Int64);
+        let result = expr.evaluate(&batch)?.into_array(batch.num_rows());
+        assert_eq!(result.as_ref(), &Int64Array::from(vec![1, 5, 3]));
+
+        // without a NULL `a` it is not evaluated at all
+        let batch = batch.slice(0, 1);
+        let result = expr.evaluate(&batch)?.into_array(batch.num_rows());
+        assert_eq!(result.as_ref(), &Int64Array::from(vec![1]));
+        Ok(())
+    }
+
+    #[test]
+    fn coalesce_of_scalars() -> Result<()> {
+        let schema = Schema::new(vec![Field::new("a", DataType::Int64, true)]);
+        let a: ArrayRef = Arc::new(Int64Array::from(vec![None, None]));
+        let batch = RecordBatch::try_new(Arc::new(schema.clone()), vec![a])?;
+
+        let expr = CoalesceExpr::new(
+            vec![lit(ScalarValue::Int64(None)), lit(7i64), col("a", &schema)?],
+            DataType::Int64,
+        );
+        match expr.evaluate(&batch)? {
+            ColumnarValue::Scalar(value) => {
+                assert_eq!(value, ScalarValue::Int64(Some(7)))
+            }
+            other => panic!("expected a scalar, got {:?}", other),
+        }
+
+        let expr =
+            CoalesceExpr::new(vec![col("a", &schema)?, lit(7i64)], DataType::Int64);
+        let result = expr.evaluate(&batch)?.into_array(batch.num_rows());
+        assert_eq!(result.as_ref(), &Int64Array::from(vec![7, 7]));
+        Ok(())
+    }
+}
@@ -0,0 +1,151 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Translation of `LIKE` and `SIMILAR TO` patterns into regular expressions
+
+use datafusion_common::{DataFusionError, Result};
+
+/// Appends `c` to `regex` so that it only matches itself
+fn push_literal(regex: &mut String, c: char) {
+    if "\\.+*?()|[]{}^$#&-~".contains(c) {
+        regex.push('\\');
+    }
+    regex.push(c);
+}
+
+fn trailing_escape_error(operator: &str, pattern: &str) -> DataFusionError {
+    DataFusionError::Execution(format!(
+        "{} pattern '{}' must not end with the escape character",
+        operator, pattern
+    ))
+}
+
+/// Translates the `LIKE` `pattern` into an anchored regular expression,
+/// where `%` matches any sequence of characters, `_` any single character
+/// and `escape_char` makes the character following it match literally.
+///
+/// Without `escape_char`, a `\` followed by a wildcard makes it match
+/// literally and is otherwise an ordinary character, as in arrow's `LIKE`
+/// kernels.
+pub fn like_to_regex(pattern: &str, escape_char: Option<char>) -> Result<String> {
+    let mut regex = String::with_capacity(pattern.len() + 8);
+    regex.push_str("(?s)^");
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        if Some(c) == escape_char {
+            match chars.next() {
+                Some(escaped) => push_literal(&mut regex, escaped),
+                None => return Err(trailing_escape_error("LIKE", pattern)),
+            }
+        } else if c == '\\'
+            && escape_char.is_none()
+            && chars.as_str().starts_with(|c| c == '%' || c == '_')
+        {
+            // the wildcard following the backslash
+            push_literal(&mut regex, chars.next().unwrap());
+        } else {
+            match c {
+                '%' => regex.push_str(".*"),
+                '_' => regex.push('.'),
+                c => push_literal(&mut regex, c),
+            }
+        }
+    }
+    regex.push('$');
+    Ok(regex)
+}
+
+/// Translates the `SIMILAR TO` `pattern` into an anchored regular expression.
+///
+/// On top of the `LIKE` wildcards, alternation `|`, repetition `*`, `+`, `?`
+/// and `{m,n}`, grouping `()` and bracket expressions `[...]` keep their
+/// regular expression meaning, while `.` only matches itself. The escape
+/// character defaults to `\`.
+pub fn similar_to_regex(pattern: &str, escape_char: Option<char>) -> Result<String> {
+    let escape_char = escape_char.unwrap_or('\\');
+    let mut regex = String::with_capacity(pattern.len() + 8);
+    regex.push_str("(?s)^(?:");
+    let mut in_brackets = false;
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        if c == escape_char {
+            match chars.next() {
+                Some(escaped) => push_literal(&mut regex, escaped),
+                None => return Err(trailing_escape_error("SIMILAR TO", pattern)),
+            }
+        } else if in_brackets {
+            match c {
+                ']' => {
+                    in_brackets = false;
+                    regex.push(c);
+                }
+                '\\' | '[' => push_literal(&mut regex, c),
+                c => regex.push(c),
+            }
+        } else {
+            match c {
+                '%' => regex.push_str(".*"),
+                '_' => regex.push('.'),
+                '[' => {
+                    in_brackets = true;
+                    regex.push(c);
+                }
+                '|' | '*' | '+' | '?' | '{' | '}' | '(' | ')' => regex.push(c),
+                c => push_literal(&mut regex, c),
+            }
+        }
+    }
+    regex.push_str(")$");
+    Ok(regex)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn like_patterns() -> Result<()> {
+        assert_eq!(like_to_regex("a%b_c", None)?, "(?s)^a.*b.c$");
+        assert_eq!(like_to_regex("1.5 (x)", None)?, "(?s)^1\\.5 \\(x\\)$");
+        assert_eq!(like_to_regex("a\\%", Some('\\'))?, "(?s)^a%$");
+        assert_eq!(like_to_regex("a!_!!%", Some('!'))?, "(?s)^a_!.*$");
+        // without an escape character a backslash only escapes wildcards
+        assert_eq!(like_to_regex("a\\%", None)?, "(?s)^a%$");
+        assert_eq!(like_to_regex("a\\b\\", None)?, "(?s)^a\\\\b\\\\$");
+
+        let err = like_to_regex("a!", Some('!')).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Execution error: LIKE pattern 'a!' must not end with the escape character"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn similar_to_patterns() -> Result<()> {
+        assert_eq!(similar_to_regex("(ab|c)+%", None)?, "(?s)^(?:(ab|c)+.*)$");
+        assert_eq!(similar_to_regex("a.b_", None)?, "(?s)^(?:a\\.b.)$");
+        assert_eq!(
+            similar_to_regex("[a-c_%]{2}", None)?,
+            "(?s)^(?:[a-c_%]{2})$"
+        );
+        assert_eq!(similar_to_regex("a\\%", None)?, "(?s)^(?:a%)$");
+        assert_eq!(similar_to_regex("a#|b", Some('#'))?, "(?s)^(?:a\\|b)$");
+        assert!(similar_to_regex("a\\", None).is_err());
+        Ok(())
+    }
+}
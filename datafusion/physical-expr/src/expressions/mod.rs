@@ -21,12 +21,14 @@
 mod binary;
 mod case;
 mod cast;
+mod coalesce;
 mod column;
 mod datetime;
 mod get_indexed_field;
 mod in_list;
 mod is_not_null;
 mod is_null;
+mod like;
 mod literal;
 mod negative;
 mod no_op;
@@ -45,18 +47,27 @@ pub use crate::aggregate::approx_percentile_cont::ApproxPercentileCont;
 pub use crate::aggregate::approx_percentile_cont_with_weight::ApproxPercentileContWithWeight;
 pub use crate::aggregate::array_agg::ArrayAgg;
 pub use crate::aggregate::array_agg_distinct::DistinctArrayAgg;
+pub use crate::aggregate::array_agg_ordered::OrderedArrayAgg;
 pub use crate::aggregate::average::{Avg, AvgAccumulator};
-pub use crate::aggregate::build_in::create_aggregate_expr;
+pub use crate::aggregate::bit_and_or_xor::{BitAnd, BitOr, BitXor};
+pub use crate::aggregate::bool_and_or::{BoolAnd, BoolOr};
+pub use crate::aggregate::build_in::{
+    create_aggregate_expr, create_aggregate_expr_with_wrapping,
+};
 pub use crate::aggregate::correlation::Correlation;
 pub use crate::aggregate::count::Count;
 pub use crate::aggregate::count_distinct::DistinctCount;
 pub use crate::aggregate::covariance::{Covariance, CovariancePop};
+pub use crate::aggregate::filtered::FilteredAggregateExpr;
+pub use crate::aggregate::first_last::{FirstValue, LastValue, OrderKey};
 pub use crate::aggregate::grouping::Grouping;
 pub use crate::aggregate::median::Median;
+pub use crate::aggregate::mode::Mode;
 pub use crate::aggregate::min_max::{Max, Min};
 pub use crate::aggregate::min_max::{MaxAccumulator, MinAccumulator};
 pub use crate::aggregate::stats::StatsType;
 pub use crate::aggregate::stddev::{Stddev, StddevPop};
+pub use crate::aggregate::string_agg::StringAgg;
 pub use crate::aggregate::sum::Sum;
 pub use crate::aggregate::sum_distinct::DistinctSum;
 pub use crate::aggregate::variance::{Variance, VariancePop};
@@ -72,12 +83,14 @@ pub use case::{case, CaseExpr};
 pub use cast::{
     cast, cast_column, cast_with_options, CastExpr, DEFAULT_DATAFUSION_CAST_OPTIONS,
 };
+pub use coalesce::CoalesceExpr;
 pub use column::{col, Column, UnKnownColumn};
 pub use datetime::DateTimeIntervalExpr;
 pub use get_indexed_field::GetIndexedFieldExpr;
 pub use in_list::{in_list, InListExpr};
 pub use is_not_null::{is_not_null, IsNotNullExpr};
 pub use is_null::{is_null, IsNullExpr};
+pub use like::{like_to_regex, similar_to_regex};
 pub use literal::{lit, Literal};
 pub use negative::{negative, NegativeExpr};
 pub use no_op::NoOp;
@@ -17,57 +17,20 @@
 
 use std::sync::Arc;
 
-use arrow::array::Array;
 use arrow::array::*;
-use arrow::compute::eq_dyn;
-use arrow::compute::kernels::boolean::nullif;
+use arrow::compute::kernels::zip::zip;
+use arrow::compute::{eq_dyn, prep_null_mask_filter};
 use arrow::datatypes::DataType;
-use datafusion_common::{DataFusionError, Result};
+use datafusion_common::{DataFusionError, Result, ScalarValue};
 use datafusion_expr::ColumnarValue;
 
 use super::binary::array_eq_scalar;
 
-/// Invoke a compute kernel on a primitive array and a Boolean Array
-macro_rules! compute_bool_array_op {
-    ($LEFT:expr, $RIGHT:expr, $OP:ident, $DT:ident) => {{
-        let ll = $LEFT
-            .as_any()
-            .downcast_ref::<$DT>()
-            .expect("compute_op failed to downcast array");
-        let rr = $RIGHT
-            .as_any()
-            .downcast_ref::<BooleanArray>()
-            .expect("compute_op failed to downcast array");
-        Ok(Arc::new($OP(&ll, &rr)?) as ArrayRef)
-    }};
-}
-
-/// Binary op between primitive and boolean arrays
-macro_rules! primitive_bool_array_op {
-    ($LEFT:expr, $RIGHT:expr, $OP:ident) => {{
-        match $LEFT.data_type() {
-            DataType::Int8 => compute_bool_array_op!($LEFT, $RIGHT, $OP, Int8Array),
-            DataType::Int16 => compute_bool_array_op!($LEFT, $RIGHT, $OP, Int16Array),
-            DataType::Int32 => compute_bool_array_op!($LEFT, $RIGHT, $OP, Int32Array),
-            DataType::Int64 => compute_bool_array_op!($LEFT, $RIGHT, $OP, Int64Array),
-            DataType::UInt8 => compute_bool_array_op!($LEFT, $RIGHT, $OP, UInt8Array),
-            DataType::UInt16 => compute_bool_array_op!($LEFT, $RIGHT, $OP, UInt16Array),
-            DataType::UInt32 => compute_bool_array_op!($LEFT, $RIGHT, $OP, UInt32Array),
-            DataType::UInt64 => compute_bool_array_op!($LEFT, $RIGHT, $OP, UInt64Array),
-            DataType::Float32 => compute_bool_array_op!($LEFT, $RIGHT, $OP, Float32Array),
-            DataType::Float64 => compute_bool_array_op!($LEFT, $RIGHT, $OP, Float64Array),
-            other => Err(DataFusionError::Internal(format!(
-                "Unsupported data type {:?} for NULLIF/primitive/boolean operator",
-                other
-            ))),
-        }
-    }};
-}
-
 /// Implements NULLIF(expr1, expr2)
 /// Args: 0 - left expr is any array
 ///       1 - if the left is equal to this expr2, then the result is NULL, otherwise left value is passed.
 ///
+/// Both arguments are expected to have been coerced to a common type.
 pub fn nullif_func(args: &[ColumnarValue]) -> Result<ColumnarValue> {
     if args.len() != 2 {
         return Err(DataFusionError::Internal(format!(
@@ -77,27 +40,42 @@ pub fn nullif_func(args: &[ColumnarValue]) -> Result<ColumnarValue> {
     }
 
     let (lhs, rhs) = (&args[0], &args[1]);
+    if lhs.data_type() == DataType::Null {
+        return Ok(lhs.clone());
+    }
 
-    match (lhs, rhs) {
+    let (lhs_array, cond_array) = match (lhs, rhs) {
         (ColumnarValue::Array(lhs), ColumnarValue::Scalar(rhs)) => {
-            let cond_array = array_eq_scalar(lhs, rhs)?;
-
-            let array = primitive_bool_array_op!(lhs, *cond_array, nullif)?;
-
-            Ok(ColumnarValue::Array(array))
+            (lhs.clone(), array_eq_scalar(lhs, rhs)?)
         }
         (ColumnarValue::Array(lhs), ColumnarValue::Array(rhs)) => {
             // Get args0 == args1 evaluated and produce a boolean array
-            let cond_array = eq_dyn(lhs, rhs)?;
-
-            // Now, invoke nullif on the result
-            let array = primitive_bool_array_op!(lhs, cond_array, nullif)?;
-            Ok(ColumnarValue::Array(array))
+            (lhs.clone(), Arc::new(eq_dyn(lhs, rhs)?) as ArrayRef)
         }
-        _ => Err(DataFusionError::NotImplemented(
-            "nullif does not support a literal as first argument".to_string(),
-        )),
-    }
+        (ColumnarValue::Scalar(lhs), ColumnarValue::Array(rhs)) => {
+            let lhs = lhs.to_array_of_size(rhs.len());
+            let cond_array = Arc::new(eq_dyn(&lhs, rhs)?) as ArrayRef;
+            (lhs, cond_array)
+        }
+        (ColumnarValue::Scalar(lhs), ColumnarValue::Scalar(rhs)) => {
+            let result = if !rhs.is_null() && lhs == rhs {
+                ScalarValue::try_from(&lhs.get_datatype())?
+            } else {
+                lhs.clone()
+            };
+            return Ok(ColumnarValue::Scalar(result));
+        }
+    };
+
+    // rows where the comparison is null are not equal
+    let cond_array = cond_array
+        .as_any()
+        .downcast_ref::<BooleanArray>()
+        .expect("NULLIF condition is not a BooleanArray");
+    let cond_array = prep_null_mask_filter(cond_array);
+    let nulls = new_null_array(lhs_array.data_type(), lhs_array.len());
+    let array = zip(&cond_array, nulls.as_ref(), lhs_array.as_ref())?;
+    Ok(ColumnarValue::Array(array))
 }
 
 #[cfg(test)]
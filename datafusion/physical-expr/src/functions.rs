@@ -33,9 +33,11 @@
 use crate::execution_props::ExecutionProps;
 use crate::{
     array_expressions, conditional_expressions, datetime_expressions,
-    expressions::{cast_column, nullif_func, DEFAULT_DATAFUSION_CAST_OPTIONS},
-    math_expressions, string_expressions, struct_expressions, PhysicalExpr,
-    ScalarFunctionExpr,
+    expressions::{
+        cast_column, nullif_func, CoalesceExpr, DEFAULT_DATAFUSION_CAST_OPTIONS,
+    },
+    hash_expressions, math_expressions, string_expressions, struct_expressions,
+    PhysicalExpr, ScalarFunctionExpr,
 };
 use arrow::{
     array::ArrayRef,
@@ -64,11 +66,24 @@ pub fn create_physical_expr(
 
     let data_type = function::return_type(fun, &input_expr_types)?;
 
+    // COALESCE only evaluates an argument on the rows where all of the ones
+    // before it are NULL, so that e.g. `coalesce(a, 1 / b)` does not fail on
+    // the rows with a zero `b` but a non-NULL `a`
+    if fun == &BuiltinScalarFunction::Coalesce {
+        return Ok(Arc::new(CoalesceExpr::new(
+            input_phy_exprs.to_vec(),
+            data_type,
+        )));
+    }
+
     let fun_expr: ScalarFunctionImplementation = match fun {
         // These functions need args and input schema to pick an implementation
         // Unlike the string functions, which actually figure out the function to use with each array,
         // here we return either a cast fn or string timestamp translation based on the expression data type
         // so we don't have to pay a per-array/batch cost.
+        BuiltinScalarFunction::ToTimestamp if input_phy_exprs.len() == 2 => {
+            datetime_expressions::create_to_timestamp(input_phy_exprs)?
+        }
         BuiltinScalarFunction::ToTimestamp => {
             Arc::new(match input_phy_exprs[0].data_type(input_schema) {
                 Ok(DataType::Int64) | Ok(DataType::Timestamp(_, None)) => {
@@ -175,6 +190,31 @@ pub fn create_physical_expr(
                 )))))
             })
         }
+        // Literal formats and patterns are compiled here, so that an invalid
+        // one fails planning, and the compiled ones are kept by the expression
+        BuiltinScalarFunction::ToChar => {
+            datetime_expressions::create_to_char(input_phy_exprs)?
+        }
+        BuiltinScalarFunction::ToDate => {
+            datetime_expressions::create_to_date(input_phy_exprs)?
+        }
+        BuiltinScalarFunction::RegexpLike => create_if_regex_expressions_feature_flag!(
+            create_regexp_like,
+            input_phy_exprs,
+            "regexp_like"
+        ),
+        BuiltinScalarFunction::RegexpMatch => create_if_regex_expressions_feature_flag!(
+            create_regexp_match,
+            input_phy_exprs,
+            "regexp_match"
+        ),
+        BuiltinScalarFunction::RegexpReplace => {
+            create_if_regex_expressions_feature_flag!(
+                create_regexp_replace,
+                input_phy_exprs,
+                "regexp_replace"
+            )
+        }
         // These don't need args and input schema
         _ => create_physical_fun(fun, execution_props)?,
     };
@@ -208,22 +248,22 @@ macro_rules! invoke_if_crypto_expressions_feature_flag {
 }
 
 #[cfg(feature = "regex_expressions")]
-macro_rules! invoke_if_regex_expressions_feature_flag {
-    ($FUNC:ident, $T:tt, $NAME:expr) => {{
+macro_rules! create_if_regex_expressions_feature_flag {
+    ($FUNC:ident, $ARGS:expr, $NAME:expr) => {{
         use crate::regex_expressions;
-        regex_expressions::$FUNC::<$T>
+        regex_expressions::$FUNC($ARGS)?
     }};
 }
 
 #[cfg(not(feature = "regex_expressions"))]
-macro_rules! invoke_if_regex_expressions_feature_flag {
-    ($FUNC:ident, $T:tt, $NAME:expr) => {
-        |_: &[ArrayRef]| -> Result<ArrayRef> {
+macro_rules! create_if_regex_expressions_feature_flag {
+    ($FUNC:ident, $ARGS:expr, $NAME:expr) => {
+        Arc::new(|_: &[ColumnarValue]| -> Result<ColumnarValue> {
             Err(DataFusionError::Internal(format!(
                 "function {} requires compilation with feature flag: regex_expressions.",
                 $NAME
             )))
-        }
+        })
     };
 }
 
@@ -329,23 +369,45 @@ pub fn create_physical_fun(
         BuiltinScalarFunction::Cos => Arc::new(math_expressions::cos),
         BuiltinScalarFunction::Exp => Arc::new(math_expressions::exp),
         BuiltinScalarFunction::Floor => Arc::new(math_expressions::floor),
-        BuiltinScalarFunction::Log => Arc::new(math_expressions::log10),
+        BuiltinScalarFunction::Log => Arc::new(|args| match args.len() {
+            1 => math_expressions::log10(args),
+            _ => make_scalar_function(math_expressions::log)(args),
+        }),
         BuiltinScalarFunction::Ln => Arc::new(math_expressions::ln),
         BuiltinScalarFunction::Log10 => Arc::new(math_expressions::log10),
         BuiltinScalarFunction::Log2 => Arc::new(math_expressions::log2),
         BuiltinScalarFunction::Random => Arc::new(math_expressions::random),
-        BuiltinScalarFunction::Round => Arc::new(math_expressions::round),
+        BuiltinScalarFunction::Round => Arc::new(|args| match args.len() {
+            1 => math_expressions::round(args),
+            _ => make_scalar_function(math_expressions::round_to_precision)(args),
+        }),
         BuiltinScalarFunction::Signum => Arc::new(math_expressions::signum),
         BuiltinScalarFunction::Sin => Arc::new(math_expressions::sin),
         BuiltinScalarFunction::Sqrt => Arc::new(math_expressions::sqrt),
         BuiltinScalarFunction::Tan => Arc::new(math_expressions::tan),
-        BuiltinScalarFunction::Trunc => Arc::new(math_expressions::trunc),
+        BuiltinScalarFunction::Trunc => Arc::new(|args| match args.len() {
+            1 => math_expressions::trunc(args),
+            _ => make_scalar_function(math_expressions::trunc_to_precision)(args),
+        }),
         BuiltinScalarFunction::Power => {
             Arc::new(|args| make_scalar_function(math_expressions::power)(args))
         }
         BuiltinScalarFunction::Atan2 => {
             Arc::new(|args| make_scalar_function(math_expressions::atan2)(args))
         }
+        BuiltinScalarFunction::Cbrt => Arc::new(math_expressions::cbrt),
+        BuiltinScalarFunction::Degrees => Arc::new(math_expressions::degrees),
+        BuiltinScalarFunction::Radians => Arc::new(math_expressions::radians),
+        BuiltinScalarFunction::Pi => Arc::new(math_expressions::pi),
+        BuiltinScalarFunction::Factorial => {
+            Arc::new(|args| make_scalar_function(math_expressions::factorial)(args))
+        }
+        BuiltinScalarFunction::Gcd => {
+            Arc::new(|args| make_scalar_function(math_expressions::gcd)(args))
+        }
+        BuiltinScalarFunction::Lcm => {
+            Arc::new(|args| make_scalar_function(math_expressions::lcm)(args))
+        }
 
         // string functions
         BuiltinScalarFunction::MakeArray => Arc::new(array_expressions::array),
@@ -414,6 +476,8 @@ pub fn create_physical_fun(
             Arc::new(|args| make_scalar_function(string_expressions::chr)(args))
         }
         BuiltinScalarFunction::Coalesce => Arc::new(conditional_expressions::coalesce),
+        BuiltinScalarFunction::Greatest => Arc::new(conditional_expressions::greatest),
+        BuiltinScalarFunction::Least => Arc::new(conditional_expressions::least),
         BuiltinScalarFunction::Concat => Arc::new(string_expressions::concat),
         BuiltinScalarFunction::ConcatWithSeparator => {
             Arc::new(|args| make_scalar_function(string_expressions::concat_ws)(args))
@@ -421,6 +485,8 @@ pub fn create_physical_fun(
         BuiltinScalarFunction::DatePart => Arc::new(datetime_expressions::date_part),
         BuiltinScalarFunction::DateTrunc => Arc::new(datetime_expressions::date_trunc),
         BuiltinScalarFunction::DateBin => Arc::new(datetime_expressions::date_bin),
+        BuiltinScalarFunction::ToChar => datetime_expressions::create_to_char(&[])?,
+        BuiltinScalarFunction::ToDate => datetime_expressions::create_to_date(&[])?,
         BuiltinScalarFunction::Now => {
             // bind value for now at plan time
             Arc::new(datetime_expressions::make_now(
@@ -439,6 +505,37 @@ pub fn create_physical_fun(
                 execution_props.query_execution_start_time,
             ))
         }
+        BuiltinScalarFunction::Version => {
+            let version = format!(
+                "Apache Arrow DataFusion {} on {} {}",
+                env!("CARGO_PKG_VERSION"),
+                std::env::consts::ARCH,
+                std::env::consts::OS
+            );
+            Arc::new(move |_| {
+                Ok(ColumnarValue::Scalar(ScalarValue::Utf8(Some(
+                    version.clone(),
+                ))))
+            })
+        }
+        BuiltinScalarFunction::CurrentCatalog => {
+            // bind value for current_catalog at plan time
+            let catalog = execution_props.current_catalog.clone();
+            Arc::new(move |_| {
+                Ok(ColumnarValue::Scalar(ScalarValue::Utf8(Some(
+                    catalog.clone(),
+                ))))
+            })
+        }
+        BuiltinScalarFunction::CurrentSchema => {
+            // bind value for current_schema at plan time
+            let schema = execution_props.current_schema.clone();
+            Arc::new(move |_| {
+                Ok(ColumnarValue::Scalar(ScalarValue::Utf8(Some(
+                    schema.clone(),
+                ))))
+            })
+        }
         BuiltinScalarFunction::InitCap => Arc::new(|args| match args[0].data_type() {
             DataType::Utf8 => {
                 make_scalar_function(string_expressions::initcap::<i32>)(args)
@@ -511,55 +608,26 @@ pub fn create_physical_fun(
                 _ => unreachable!(),
             },
         }),
+        BuiltinScalarFunction::RegexpLike => {
+            create_if_regex_expressions_feature_flag!(
+                create_regexp_like,
+                &[],
+                "regexp_like"
+            )
+        }
         BuiltinScalarFunction::RegexpMatch => {
-            Arc::new(|args| match args[0].data_type() {
-                DataType::Utf8 => {
-                    let func = invoke_if_regex_expressions_feature_flag!(
-                        regexp_match,
-                        i32,
-                        "regexp_match"
-                    );
-                    make_scalar_function(func)(args)
-                }
-                DataType::LargeUtf8 => {
-                    let func = invoke_if_regex_expressions_feature_flag!(
-                        regexp_match,
-                        i64,
-                        "regexp_match"
-                    );
-                    make_scalar_function(func)(args)
-                }
-                other => Err(DataFusionError::Internal(format!(
-                    "Unsupported data type {:?} for function regexp_match",
-                    other
-                ))),
-            })
+            create_if_regex_expressions_feature_flag!(
+                create_regexp_match,
+                &[],
+                "regexp_match"
+            )
         }
         BuiltinScalarFunction::RegexpReplace => {
-            Arc::new(|args| match args[0].data_type() {
-                DataType::Utf8 => {
-                    let specializer_func = invoke_if_regex_expressions_feature_flag!(
-                        specialize_regexp_replace,
-                        i32,
-                        "regexp_replace"
-                    );
-                    let func = specializer_func(args)?;
-                    func(args)
-                }
-                DataType::LargeUtf8 => {
-                    let specializer_func = invoke_if_regex_expressions_feature_flag!(
-                        specialize_regexp_replace,
-                        i64,
-                        "regexp_replace"
-                    );
-                    let func = specializer_func(args)?;
-                    func(args)
-                }
-                other => Err(DataFusionError::Internal(format!(
-                    "Unsupported data type {:?} for function regexp_replace",
-                    other,
-                ))),
-            })
+            create_if_regex_expressions_feature_flag!(
+                create_regexp_replace,
+                &[],
+                "regexp_replace"
+            )
         }
         BuiltinScalarFunction::Repeat => Arc::new(|args| match args[0].data_type() {
             DataType::Utf8 => {
@@ -761,6 +829,9 @@ pub fn create_physical_fun(
         }),
         BuiltinScalarFunction::Upper => Arc::new(string_expressions::upper),
         BuiltinScalarFunction::Uuid => Arc::new(string_expressions::uuid),
+        BuiltinScalarFunction::XxHash64 => {
+            Arc::new(|args| make_scalar_function(hash_expressions::xxhash64)(args))
+        }
         _ => {
             return Err(DataFusionError::Internal(format!(
                 "create_physical_fun: Unsupported scalar function {:?}",
@@ -2765,6 +2836,7 @@ mod tests {
             BuiltinScalarFunction::Now,
             BuiltinScalarFunction::Random,
             BuiltinScalarFunction::Uuid,
+            BuiltinScalarFunction::Pi,
         ];
 
         for fun in funs.iter() {
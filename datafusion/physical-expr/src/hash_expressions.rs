@@ -0,0 +1,320 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Non-cryptographic hash expressions
+
+use arrow::array::*;
+use arrow::datatypes::*;
+use datafusion_common::{DataFusionError, Result};
+use std::sync::Arc;
+
+const PRIME64_1: u64 = 0x9E3779B185EBCA87;
+const PRIME64_2: u64 = 0xC2B2AE3D27D4EB4F;
+const PRIME64_3: u64 = 0x165667B19E3779F9;
+const PRIME64_4: u64 = 0x85EBCA77C2B2AE63;
+const PRIME64_5: u64 = 0x27D4EB2F165667C5;
+
+#[inline]
+fn read_u64(bytes: &[u8]) -> u64 {
+    u64::from_le_bytes(bytes[..8].try_into().unwrap())
+}
+
+#[inline]
+fn read_u32(bytes: &[u8]) -> u32 {
+    u32::from_le_bytes(bytes[..4].try_into().unwrap())
+}
+
+#[inline]
+fn round(acc: u64, input: u64) -> u64 {
+    acc.wrapping_add(input.wrapping_mul(PRIME64_2))
+        .rotate_left(31)
+        .wrapping_mul(PRIME64_1)
+}
+
+#[inline]
+fn merge_round(acc: u64, val: u64) -> u64 {
+    (acc ^ round(0, val))
+        .wrapping_mul(PRIME64_1)
+        .wrapping_add(PRIME64_4)
+}
+
+/// Computes the 64 bit [xxHash](https://github.com/Cyan4973/xxHash) of
+/// `bytes` with the given `seed`
+pub fn xxh64(bytes: &[u8], seed: u64) -> u64 {
+    let len = bytes.len();
+    let mut rest = bytes;
+
+    let mut hash = if len >= 32 {
+        let mut v1 = seed.wrapping_add(PRIME64_1).wrapping_add(PRIME64_2);
+        let mut v2 = seed.wrapping_add(PRIME64_2);
+        let mut v3 = seed;
+        let mut v4 = seed.wrapping_sub(PRIME64_1);
+        while rest.len() >= 32 {
+            v1 = round(v1, read_u64(rest));
+            v2 = round(v2, read_u64(&rest[8..]));
+            v3 = round(v3, read_u64(&rest[16..]));
+            v4 = round(v4, read_u64(&rest[24..]));
+            rest = &rest[32..];
+        }
+        let hash = v1
+            .rotate_left(1)
+            .wrapping_add(v2.rotate_left(7))
+            .wrapping_add(v3.rotate_left(12))
+            .wrapping_add(v4.rotate_left(18));
+        let hash = merge_round(hash, v1);
+        let hash = merge_round(hash, v2);
+        let hash = merge_round(hash, v3);
+        merge_round(hash, v4)
+    } else {
+        seed.wrapping_add(PRIME64_5)
+    };
+    hash = hash.wrapping_add(len as u64);
+
+    while rest.len() >= 8 {
+        hash ^= round(0, read_u64(rest));
+        hash = hash
+            .rotate_left(27)
+            .wrapping_mul(PRIME64_1)
+            .wrapping_add(PRIME64_4);
+        rest = &rest[8..];
+    }
+    if rest.len() >= 4 {
+        hash ^= (read_u32(rest) as u64).wrapping_mul(PRIME64_1);
+        hash = hash
+            .rotate_left(23)
+            .wrapping_mul(PRIME64_2)
+            .wrapping_add(PRIME64_3);
+        rest = &rest[4..];
+    }
+    for byte in rest {
+        hash ^= (*byte as u64).wrapping_mul(PRIME64_5);
+        hash = hash.rotate_left(11).wrapping_mul(PRIME64_1);
+    }
+
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(PRIME64_2);
+    hash ^= hash >> 29;
+    hash = hash.wrapping_mul(PRIME64_3);
+    hash ^ (hash >> 32)
+}
+
+macro_rules! encode_primitive {
+    ($ARRAY:expr, $ROW:expr, $BUF:expr, $TYPE:ty) => {{
+        let value = as_primitive_array::<$TYPE>($ARRAY).value($ROW);
+        $BUF.extend_from_slice(&value.to_le_bytes());
+    }};
+}
+
+macro_rules! encode_widened {
+    ($ARRAY:expr, $ROW:expr, $BUF:expr, $TYPE:ty, $WIDE:ty) => {{
+        let value = as_primitive_array::<$TYPE>($ARRAY).value($ROW) as $WIDE;
+        $BUF.extend_from_slice(&value.to_le_bytes());
+    }};
+}
+
+macro_rules! encode_float {
+    ($ARRAY:expr, $ROW:expr, $BUF:expr, $TYPE:ty) => {{
+        let value = as_primitive_array::<$TYPE>($ARRAY).value($ROW) as f64;
+        // -0.0 equals 0.0 and all NaNs are alike, so they hash the same
+        let value = if value == 0.0 {
+            0.0
+        } else if value.is_nan() {
+            f64::NAN
+        } else {
+            value
+        };
+        $BUF.extend_from_slice(&value.to_bits().to_le_bytes());
+    }};
+}
+
+#[inline]
+fn encode_bytes(bytes: &[u8], buf: &mut Vec<u8>) {
+    buf.extend_from_slice(&(bytes.len() as u64).to_le_bytes());
+    buf.extend_from_slice(bytes);
+}
+
+/// Appends the bytes `xxhash64` hashes for the value at `row` of `array`,
+/// which must not be null.
+///
+/// Fixed width values are encoded as their little endian bytes, and
+/// variable width ones are prefixed by their length so that
+/// `xxhash64('ab', 'c')` and `xxhash64('a', 'bc')` differ. Integers are
+/// widened to 64 bits and floats to `f64` first, so that the hash of a value
+/// does not depend on the width of the column it is read from.
+fn encode_value(array: &ArrayRef, row: usize, buf: &mut Vec<u8>) -> Result<()> {
+    match array.data_type() {
+        DataType::Utf8 => encode_bytes(as_string_array(array).value(row).as_bytes(), buf),
+        DataType::LargeUtf8 => {
+            encode_bytes(as_largestring_array(array).value(row).as_bytes(), buf)
+        }
+        DataType::Binary => {
+            encode_bytes(as_generic_binary_array::<i32>(array).value(row), buf)
+        }
+        DataType::LargeBinary => {
+            encode_bytes(as_generic_binary_array::<i64>(array).value(row), buf)
+        }
+        DataType::Boolean => buf.push(as_boolean_array(array).value(row) as u8),
+        DataType::Int8 => encode_widened!(array, row, buf, Int8Type, i64),
+        DataType::Int16 => encode_widened!(array, row, buf, Int16Type, i64),
+        DataType::Int32 => encode_widened!(array, row, buf, Int32Type, i64),
+        DataType::Int64 => encode_primitive!(array, row, buf, Int64Type),
+        DataType::UInt8 => encode_widened!(array, row, buf, UInt8Type, u64),
+        DataType::UInt16 => encode_widened!(array, row, buf, UInt16Type, u64),
+        DataType::UInt32 => encode_widened!(array, row, buf, UInt32Type, u64),
+        DataType::UInt64 => encode_primitive!(array, row, buf, UInt64Type),
+        DataType::Float32 => encode_float!(array, row, buf, Float32Type),
+        DataType::Float64 => encode_float!(array, row, buf, Float64Type),
+        DataType::Decimal128(_, _) => {
+            encode_primitive!(array, row, buf, Decimal128Type)
+        }
+        DataType::Date32 => encode_primitive!(array, row, buf, Date32Type),
+        DataType::Date64 => encode_primitive!(array, row, buf, Date64Type),
+        DataType::Time32(TimeUnit::Second) => {
+            encode_primitive!(array, row, buf, Time32SecondType)
+        }
+        DataType::Time32(TimeUnit::Millisecond) => {
+            encode_primitive!(array, row, buf, Time32MillisecondType)
+        }
+        DataType::Time64(TimeUnit::Microsecond) => {
+            encode_primitive!(array, row, buf, Time64MicrosecondType)
+        }
+        DataType::Time64(TimeUnit::Nanosecond) => {
+            encode_primitive!(array, row, buf, Time64NanosecondType)
+        }
+        DataType::Timestamp(TimeUnit::Second, _) => {
+            encode_primitive!(array, row, buf, TimestampSecondType)
+        }
+        DataType::Timestamp(TimeUnit::Millisecond, _) => {
+            encode_primitive!(array, row, buf, TimestampMillisecondType)
+        }
+        DataType::Timestamp(TimeUnit::Microsecond, _) => {
+            encode_primitive!(array, row, buf, TimestampMicrosecondType)
+        }
+        DataType::Timestamp(TimeUnit::Nanosecond, _) => {
+            encode_primitive!(array, row, buf, TimestampNanosecondType)
+        }
+        other => {
+            return Err(DataFusionError::Internal(format!(
+                "Unsupported data type {:?} for function xxhash64",
+                other
+            )))
+        }
+    }
+    Ok(())
+}
+
+/// Returns the 64 bit xxHash of the values of each row of `args`, or null
+/// if any of them is null
+pub fn xxhash64(args: &[ArrayRef]) -> Result<ArrayRef> {
+    let num_rows = args.first().map(|array| array.len()).unwrap_or(0);
+    let mut builder = UInt64Builder::with_capacity(num_rows);
+    let mut buf = vec![];
+    for row in 0..num_rows {
+        if args.iter().any(|array| array.is_null(row)) {
+            builder.append_null();
+            continue;
+        }
+        buf.clear();
+        for array in args {
+            encode_value(array, row, &mut buf)?;
+        }
+        builder.append_value(xxh64(&buf, 0));
+    }
+    Ok(Arc::new(builder.finish()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn xxh64_known_values() {
+        assert_eq!(xxh64(b"", 0), 0xEF46DB3751D8E999);
+        assert_eq!(xxh64(b"a", 0), 0xD24EC4F1A98C6E5B);
+        assert_eq!(xxh64(b"abc", 0), 0x44BC2CF5AD770999);
+        assert_eq!(
+            xxh64(b"Nobody inspects the spammish repetition", 0),
+            0xFBCEA83C8A378BF1
+        );
+    }
+
+    #[test]
+    fn xxhash64_rows() -> Result<()> {
+        let strings: ArrayRef = Arc::new(StringArray::from(vec![
+            Some("a"),
+            Some("a"),
+            None,
+            Some("b"),
+        ]));
+        let ints: ArrayRef =
+            Arc::new(Int64Array::from(vec![Some(1), Some(1), Some(1), None]));
+        let result = xxhash64(&[strings.clone(), ints])?;
+        let result = as_primitive_array::<UInt64Type>(&result);
+
+        assert_eq!(result.len(), 4);
+        assert!(!result.is_null(0));
+        assert_eq!(result.value(0), result.value(1));
+        assert!(result.is_null(2));
+        assert!(result.is_null(3));
+
+        // a single string hashes its length prefixed bytes
+        let result = xxhash64(&[strings])?;
+        let result = as_primitive_array::<UInt64Type>(&result);
+        let mut expected = 1u64.to_le_bytes().to_vec();
+        expected.push(b'a');
+        assert_eq!(result.value(0), xxh64(&expected, 0));
+        assert_ne!(result.value(0), result.value(3));
+        Ok(())
+    }
+
+    #[test]
+    fn xxhash64_widens_numbers() -> Result<()> {
+        let hash = |array: ArrayRef| -> Result<u64> {
+            let result = xxhash64(&[array])?;
+            Ok(as_primitive_array::<UInt64Type>(&result).value(0))
+        };
+        let int64 = hash(Arc::new(Int64Array::from(vec![-3])))?;
+        assert_eq!(hash(Arc::new(Int8Array::from(vec![-3])))?, int64);
+        assert_eq!(hash(Arc::new(Int16Array::from(vec![-3])))?, int64);
+        assert_eq!(hash(Arc::new(Int32Array::from(vec![-3])))?, int64);
+
+        let uint64 = hash(Arc::new(UInt64Array::from(vec![200])))?;
+        assert_eq!(hash(Arc::new(UInt8Array::from(vec![200])))?, uint64);
+        assert_eq!(hash(Arc::new(UInt32Array::from(vec![200])))?, uint64);
+        assert_eq!(hash(Arc::new(Int32Array::from(vec![200])))?, uint64);
+
+        let float64 = hash(Arc::new(Float64Array::from(vec![1.5])))?;
+        assert_eq!(hash(Arc::new(Float32Array::from(vec![1.5])))?, float64);
+        assert_eq!(
+            hash(Arc::new(Float64Array::from(vec![-0.0])))?,
+            hash(Arc::new(Float32Array::from(vec![0.0])))?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn xxhash64_length_prefix() -> Result<()> {
+        let left = |s: &str| Arc::new(StringArray::from(vec![s])) as ArrayRef;
+        let ab_c = xxhash64(&[left("ab"), left("c")])?;
+        let a_bc = xxhash64(&[left("a"), left("bc")])?;
+        assert_ne!(
+            as_primitive_array::<UInt64Type>(&ab_c).value(0),
+            as_primitive_array::<UInt64Type>(&a_bc).value(0)
+        );
+        Ok(())
+    }
+}
@@ -25,7 +25,16 @@ use arrow_buffer::i256;
 use datafusion_common::{DataFusionError, Result};
 use std::sync::Arc;
 
-// Combines two hashes into one hash
+/// Combines the hash `l` of a value with the hash `r` of the values of the
+/// preceding columns of its row, as `(17 * 37 + l) * 37 + r` in wrapping
+/// arithmetic.
+///
+/// The hash of a row of several columns is built by combining the hash of
+/// each column into the hash of the previous ones, starting from 0, so that
+/// it depends on the order of the columns. Every operator hashing rows,
+/// such as `HashJoinExec`, the hash aggregations and `RepartitionExec`,
+/// must use [`create_hashes`] with the same `RandomState`, so that the rows
+/// of both sides of a partitioned join are sent to the same partitions.
 #[inline]
 fn combine_hashes(l: u64, r: u64) -> u64 {
     let hash = (17 * 37u64).wrapping_add(l);
@@ -33,14 +42,15 @@ fn combine_hashes(l: u64, r: u64) -> u64 {
 }
 
 fn hash_null(random_state: &RandomState, hashes_buffer: &'_ mut [u64], mul_col: bool) {
+    // stable hash for null value
+    let null_hash = random_state.hash_one(1);
     if mul_col {
         hashes_buffer.iter_mut().for_each(|hash| {
-            // stable hash for null value
-            *hash = combine_hashes(random_state.hash_one(1), *hash);
+            *hash = combine_hashes(null_hash, *hash);
         })
     } else {
         hashes_buffer.iter_mut().for_each(|hash| {
-            *hash = random_state.hash_one(1);
+            *hash = null_hash;
         })
     }
 }
@@ -67,11 +77,20 @@ macro_rules! hash_value {
 hash_value!(i8, i16, i32, i64, i128, i256, u8, u16, u32, u64);
 hash_value!(bool, str, [u8]);
 
+// Floats are hashed by their bits, after mapping -0.0 to 0.0 and every NaN
+// to the same NaN, so that keys that compare equal also hash the same
 macro_rules! hash_float_value {
     ($(($t:ty, $i:ty)),+) => {
         $(impl HashValue for $t {
             fn hash_one(&self, state: &RandomState) -> u64 {
-                state.hash_one(<$i>::from_ne_bytes(self.to_ne_bytes()))
+                let value = if self.is_nan() {
+                    <$t>::NAN
+                } else if *self == <$t>::default() {
+                    <$t>::default()
+                } else {
+                    *self
+                };
+                state.hash_one(<$i>::from_ne_bytes(value.to_ne_bytes()))
             }
         })+
     };
@@ -143,6 +162,62 @@ fn hash_dictionary<K: ArrowDictionaryKeyType>(
     Ok(())
 }
 
+/// Hash the elements of each list in a list array, combining the hashes of
+/// the elements of a list into the hash of its row
+fn hash_list_array<OffsetSize: OffsetSizeTrait>(
+    array: &GenericListArray<OffsetSize>,
+    random_state: &RandomState,
+    hashes_buffer: &mut [u64],
+    multi_col: bool,
+) -> Result<()> {
+    let values = array.values();
+    let offsets = array.value_offsets();
+    let mut values_hashes = vec![0u64; values.len()];
+    create_hashes(&[values], random_state, &mut values_hashes)?;
+
+    for (i, hash) in hashes_buffer.iter_mut().enumerate() {
+        if array.is_valid(i) {
+            let start = offsets[i].as_usize();
+            let end = offsets[i + 1].as_usize();
+            let list_hash = values_hashes[start..end]
+                .iter()
+                .fold(0, |acc, value_hash| combine_hashes(acc, *value_hash));
+            *hash = if multi_col {
+                combine_hashes(list_hash, *hash)
+            } else {
+                list_hash
+            };
+        } // no update for Null, consistent with other hashes
+    }
+    Ok(())
+}
+
+/// Hash the fields of each row of a struct array as if they were separate
+/// columns
+fn hash_struct_array(
+    array: &StructArray,
+    random_state: &RandomState,
+    hashes_buffer: &mut [u64],
+    multi_col: bool,
+) -> Result<()> {
+    let columns = (0..array.num_columns())
+        .map(|i| array.column(i).clone())
+        .collect::<Vec<_>>();
+    let mut values_hashes = vec![0u64; array.len()];
+    create_hashes(&columns, random_state, &mut values_hashes)?;
+
+    for (i, hash) in hashes_buffer.iter_mut().enumerate() {
+        if array.is_valid(i) {
+            *hash = if multi_col {
+                combine_hashes(values_hashes[i], *hash)
+            } else {
+                values_hashes[i]
+            };
+        } // no update for Null, consistent with other hashes
+    }
+    Ok(())
+}
+
 /// Test version of `create_hashes` that produces the same value for
 /// all hashes (to test collisions)
 ///
@@ -182,9 +257,6 @@ pub fn create_row_hashes<'a>(
     random_state: &RandomState,
     hashes_buffer: &'a mut Vec<u64>,
 ) -> Result<&'a mut Vec<u64>> {
-    for hash in hashes_buffer.iter_mut() {
-        *hash = 0
-    }
     for (i, hash) in hashes_buffer.iter_mut().enumerate() {
         *hash = random_state.hash_one(&rows[i]);
     }
@@ -195,7 +267,14 @@ pub fn create_row_hashes<'a>(
 /// columns.
 ///
 /// The number of rows to hash is determined by `hashes_buffer.len()`.
-/// `hashes_buffer` should be pre-sized appropriately
+/// `hashes_buffer` should be pre-sized appropriately and filled with zeros,
+/// as the hashes of null values leave it untouched. Operators hashing many
+/// batches reuse the same buffer, clearing and resizing it for every batch
+/// rather than allocating a new one.
+///
+/// Each column is hashed in a single pass by a kernel specialized for its
+/// type, the hashes of the columns of a row being combined in order with
+/// `combine_hashes`.
 #[cfg(not(feature = "force_hash_collisions"))]
 pub fn create_hashes<'a>(
     arrays: &[ArrayRef],
@@ -232,6 +311,18 @@ pub fn create_hashes<'a>(
                 array => hash_dictionary(array, random_state, hashes_buffer, multi_col)?,
                 _ => unreachable!()
             }
+            DataType::List(_) => {
+                let array = as_list_array(array);
+                hash_list_array(array, random_state, hashes_buffer, multi_col)?;
+            }
+            DataType::LargeList(_) => {
+                let array = as_large_list_array(array);
+                hash_list_array(array, random_state, hashes_buffer, multi_col)?;
+            }
+            DataType::Struct(_) => {
+                let array: &StructArray = array.as_any().downcast_ref().unwrap();
+                hash_struct_array(array, random_state, hashes_buffer, multi_col)?;
+            }
             _ => {
                 // This is internal because we should have caught this before.
                 return Err(DataFusionError::Internal(format!(
@@ -284,6 +375,33 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    // Tests actual values of hashes, which are different if forcing collisions
+    #[cfg(not(feature = "force_hash_collisions"))]
+    fn create_hashes_for_float_keys() -> Result<()> {
+        let other_nan = f64::from_bits(f64::NAN.to_bits() | 1);
+        let f64_arr = Arc::new(Float64Array::from_slice([
+            0.0,
+            -0.0,
+            f64::NAN,
+            other_nan,
+            -f64::NAN,
+            1.0,
+        ]));
+
+        let random_state = RandomState::with_seeds(0, 0, 0, 0);
+        let hashes_buff = &mut vec![0; f64_arr.len()];
+        let hashes = create_hashes(&[f64_arr], &random_state, hashes_buff)?;
+
+        // -0.0 hashes as 0.0, and all NaNs hash the same
+        assert_eq!(hashes[0], hashes[1]);
+        assert_eq!(hashes[2], hashes[3]);
+        assert_eq!(hashes[2], hashes[4]);
+        assert_ne!(hashes[0], hashes[2]);
+        assert_ne!(hashes[0], hashes[5]);
+        Ok(())
+    }
+
     #[test]
     fn create_hashes_binary() -> Result<()> {
         let byte_array = Arc::new(BinaryArray::from_vec(vec![
@@ -392,4 +510,112 @@ mod tests {
 
         assert_ne!(one_col_hashes, two_col_hashes);
     }
+
+    #[test]
+    // Tests actual values of hashes, which are different if forcing collisions
+    #[cfg(not(feature = "force_hash_collisions"))]
+    fn create_hashes_for_list_arrays() {
+        let data = vec![
+            Some(vec![Some(0), Some(1), Some(2)]),
+            None,
+            Some(vec![Some(3), None, Some(5)]),
+            Some(vec![Some(3), None, Some(5)]),
+            None,
+            Some(vec![Some(0), Some(1), Some(2)]),
+        ];
+        let list_array =
+            Arc::new(ListArray::from_iter_primitive::<Int32Type, _, _>(data)) as ArrayRef;
+        let random_state = RandomState::with_seeds(0, 0, 0, 0);
+        let mut hashes = vec![0; list_array.len()];
+        create_hashes(&[list_array], &random_state, &mut hashes).unwrap();
+
+        // same lists should map to same hash values
+        assert_eq!(hashes[0], hashes[5]);
+        assert_eq!(hashes[1], hashes[4]);
+        assert_eq!(hashes[2], hashes[3]);
+        assert_ne!(hashes[0], hashes[2]);
+    }
+
+    #[test]
+    // Tests actual values of hashes, which are different if forcing collisions
+    #[cfg(not(feature = "force_hash_collisions"))]
+    fn create_hashes_for_struct_arrays() {
+        let ints: ArrayRef = Arc::new(Int32Array::from(vec![Some(1), Some(2), Some(1)]));
+        let strings: ArrayRef =
+            Arc::new(StringArray::from(vec![Some("x"), Some("y"), Some("x")]));
+        let struct_array: ArrayRef = Arc::new(StructArray::from(vec![
+            (Field::new("a", DataType::Int32, true), ints.clone()),
+            (Field::new("b", DataType::Utf8, true), strings.clone()),
+        ]));
+        let random_state = RandomState::with_seeds(0, 0, 0, 0);
+
+        let mut struct_hashes = vec![0; 3];
+        create_hashes(&[struct_array], &random_state, &mut struct_hashes).unwrap();
+
+        let mut column_hashes = vec![0; 3];
+        create_hashes(&[ints, strings], &random_state, &mut column_hashes).unwrap();
+
+        // a struct hashes the same as its fields as separate columns
+        assert_eq!(struct_hashes, column_hashes);
+        assert_eq!(struct_hashes[0], struct_hashes[2]);
+        assert_ne!(struct_hashes[0], struct_hashes[1]);
+    }
+
+    #[test]
+    // Tests actual values of hashes, which are different if forcing collisions
+    #[cfg(not(feature = "force_hash_collisions"))]
+    fn create_hashes_reusing_buffer() {
+        let batches: Vec<Vec<ArrayRef>> = vec![
+            vec![
+                Arc::new(Int64Array::from(vec![Some(1), Some(2), None, Some(4)])),
+                Arc::new(StringArray::from(vec!["a", "b", "c", "d"])),
+            ],
+            vec![
+                Arc::new(Int64Array::from(vec![Some(5), Some(1)])),
+                Arc::new(StringArray::from(vec![None, Some("a")])),
+            ],
+        ];
+        let random_state = RandomState::with_seeds(0, 0, 0, 0);
+
+        // the buffer keeps the hashes of the previous batch until cleared
+        let mut hashes_buffer = vec![];
+        let mut reused_hashes = vec![];
+        for columns in &batches {
+            hashes_buffer.clear();
+            hashes_buffer.resize(columns[0].len(), 0);
+            create_hashes(columns, &random_state, &mut hashes_buffer).unwrap();
+            reused_hashes.push(hashes_buffer.clone());
+        }
+
+        for (columns, reused_hashes) in batches.iter().zip(reused_hashes) {
+            let num_rows = columns[0].len();
+            let mut hashes = vec![0; num_rows];
+            create_hashes(columns, &random_state, &mut hashes).unwrap();
+            assert_eq!(hashes, reused_hashes);
+
+            // the hashes of the columns are combined in order
+            let mut column_hashes = vec![];
+            for column in columns {
+                let mut hashes = vec![0; num_rows];
+                create_hashes(&[column.clone()], &random_state, &mut hashes).unwrap();
+                column_hashes.push(hashes);
+            }
+            for row in 0..num_rows {
+                if columns.iter().all(|column| column.is_valid(row)) {
+                    let expected = combine_hashes(
+                        column_hashes[1][row],
+                        combine_hashes(column_hashes[0][row], 0),
+                    );
+                    assert_eq!(hashes[row], expected);
+                }
+            }
+        }
+
+        // the same values hash the same in every batch
+        let mut first = vec![0; 4];
+        create_hashes(&batches[0], &random_state, &mut first).unwrap();
+        let mut second = vec![0; 2];
+        create_hashes(&batches[1], &random_state, &mut second).unwrap();
+        assert_eq!(first[0], second[1]);
+    }
 }
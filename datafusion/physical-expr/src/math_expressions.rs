@@ -81,9 +81,12 @@ macro_rules! unary_primitive_array_op {
 
 macro_rules! math_unary_function {
     ($NAME:expr, $FUNC:ident) => {
+        math_unary_function!($NAME, $FUNC, $FUNC);
+    };
+    ($NAME:expr, $FUNC:ident, $METHOD:ident) => {
         /// mathematical function that accepts f32 or f64 and returns f64
         pub fn $FUNC(args: &[ColumnarValue]) -> Result<ColumnarValue> {
-            unary_primitive_array_op!(&args[0], $NAME, $FUNC)
+            unary_primitive_array_op!(&args[0], $NAME, $METHOD)
         }
     };
 }
@@ -115,6 +118,23 @@ macro_rules! make_function_inputs2 {
     }};
 }
 
+/// Like `make_function_inputs2` for integer functions that return errors,
+/// such as on overflow
+macro_rules! make_checked_function_inputs2 {
+    ($ARG1: expr, $ARG2: expr, $NAME1:expr, $NAME2: expr, $FUNC: ident) => {{
+        let arg1 = downcast_arg!($ARG1, $NAME1, Int64Array);
+        let arg2 = downcast_arg!($ARG2, $NAME2, Int64Array);
+
+        arg1.iter()
+            .zip(arg2.iter())
+            .map(|(a1, a2)| match (a1, a2) {
+                (Some(a1), Some(a2)) => $FUNC(a1, a2).map(Some),
+                _ => Ok(None),
+            })
+            .collect::<Result<Int64Array>>()?
+    }};
+}
+
 math_unary_function!("sqrt", sqrt);
 math_unary_function!("sin", sin);
 math_unary_function!("cos", cos);
@@ -132,6 +152,16 @@ math_unary_function!("exp", exp);
 math_unary_function!("ln", ln);
 math_unary_function!("log2", log2);
 math_unary_function!("log10", log10);
+math_unary_function!("cbrt", cbrt);
+math_unary_function!("degrees", degrees, to_degrees);
+math_unary_function!("radians", radians, to_radians);
+
+/// pi SQL function
+pub fn pi(_args: &[ColumnarValue]) -> Result<ColumnarValue> {
+    Ok(ColumnarValue::Scalar(ScalarValue::Float64(Some(
+        std::f64::consts::PI,
+    ))))
+}
 
 /// random SQL function
 pub fn random(args: &[ColumnarValue]) -> Result<ColumnarValue> {
@@ -160,13 +190,12 @@ pub fn power(args: &[ArrayRef]) -> Result<ArrayRef> {
             { f64::powf }
         )) as ArrayRef),
 
-        DataType::Int64 => Ok(Arc::new(make_function_inputs2!(
+        DataType::Int64 => Ok(Arc::new(make_checked_function_inputs2!(
             &args[0],
             &args[1],
             "base",
             "exponent",
-            Int64Array,
-            { i64::pow }
+            checked_pow
         )) as ArrayRef),
 
         other => Err(DataFusionError::Internal(format!(
@@ -176,6 +205,202 @@ pub fn power(args: &[ArrayRef]) -> Result<ArrayRef> {
     }
 }
 
+/// Raises `base` to the power of `exponent`, returning an error rather than
+/// wrapping on overflow
+fn checked_pow(base: i64, exponent: i64) -> Result<i64> {
+    if exponent < 0 {
+        return Err(DataFusionError::Execution(format!(
+            "Negative exponent {} for function power of integers, cast the base to a floating point number instead",
+            exponent
+        )));
+    }
+    let result = match (base, u32::try_from(exponent)) {
+        (_, Ok(exponent)) => base.checked_pow(exponent),
+        // only these bases do not overflow for exponents this large
+        (0 | 1, Err(_)) => Some(base),
+        (-1, Err(_)) => Some(if exponent % 2 == 0 { 1 } else { -1 }),
+        _ => None,
+    };
+    result.ok_or_else(|| {
+        DataFusionError::Execution(format!(
+            "Integer overflow computing power({}, {})",
+            base, exponent
+        ))
+    })
+}
+
+/// Rounds `value` to `precision` decimal digits with `func`, a negative
+/// precision rounding to the left of the decimal point
+fn to_precision(value: f64, precision: i64, func: fn(f64) -> f64) -> f64 {
+    // 10^308 is the largest power of ten a f64 can represent
+    let factor = 10f64.powi(precision.clamp(-308, 308) as i32);
+    let result = func(value * factor) / factor;
+    if result.is_finite() || !value.is_finite() {
+        result
+    } else {
+        // `value * factor` overflowed, so `value` has no digits to round
+        // beyond `precision`
+        value
+    }
+}
+
+macro_rules! make_precision_function {
+    ($ARGS:expr, $NAME:expr, $FUNC:expr) => {{
+        let precision = downcast_arg!(&$ARGS[1], "precision", Int64Array);
+        match $ARGS[0].data_type() {
+            DataType::Float64 => {
+                let values = downcast_arg!(&$ARGS[0], "value", Float64Array);
+                Ok(Arc::new(
+                    values
+                        .iter()
+                        .zip(precision.iter())
+                        .map(|(value, precision)| {
+                            Some(to_precision(value?, precision?, $FUNC))
+                        })
+                        .collect::<Float64Array>(),
+                ) as ArrayRef)
+            }
+            DataType::Float32 => {
+                let values = downcast_arg!(&$ARGS[0], "value", Float32Array);
+                Ok(Arc::new(
+                    values
+                        .iter()
+                        .zip(precision.iter())
+                        .map(|(value, precision)| {
+                            Some(to_precision(value? as f64, precision?, $FUNC) as f32)
+                        })
+                        .collect::<Float32Array>(),
+                ) as ArrayRef)
+            }
+            other => Err(DataFusionError::Internal(format!(
+                "Unsupported data type {:?} for function {}",
+                other, $NAME
+            ))),
+        }
+    }};
+}
+
+/// round SQL function with a precision, rounding half away from zero
+pub fn round_to_precision(args: &[ArrayRef]) -> Result<ArrayRef> {
+    make_precision_function!(args, "round", f64::round)
+}
+
+/// trunc SQL function with a precision
+pub fn trunc_to_precision(args: &[ArrayRef]) -> Result<ArrayRef> {
+    make_precision_function!(args, "trunc", f64::trunc)
+}
+
+/// log SQL function with a base
+pub fn log(args: &[ArrayRef]) -> Result<ArrayRef> {
+    match args[0].data_type() {
+        DataType::Float64 => Ok(Arc::new(make_function_inputs2!(
+            &args[1],
+            &args[0],
+            "x",
+            "base",
+            Float64Array,
+            { f64::log }
+        )) as ArrayRef),
+
+        DataType::Float32 => Ok(Arc::new(make_function_inputs2!(
+            &args[1],
+            &args[0],
+            "x",
+            "base",
+            Float32Array,
+            { f32::log }
+        )) as ArrayRef),
+
+        other => Err(DataFusionError::Internal(format!(
+            "Unsupported data type {:?} for function log",
+            other
+        ))),
+    }
+}
+
+/// factorial SQL function
+pub fn factorial(args: &[ArrayRef]) -> Result<ArrayRef> {
+    let values = downcast_arg!(&args[0], "value", Int64Array);
+    Ok(Arc::new(
+        values
+            .iter()
+            .map(|value| value.map(checked_factorial).transpose())
+            .collect::<Result<Int64Array>>()?,
+    ) as ArrayRef)
+}
+
+fn checked_factorial(value: i64) -> Result<i64> {
+    if value < 0 {
+        return Err(DataFusionError::Execution(format!(
+            "Factorial of negative number {} is undefined",
+            value
+        )));
+    }
+    (1..=value)
+        .try_fold(1i64, |acc, i| acc.checked_mul(i))
+        .ok_or_else(|| {
+            DataFusionError::Execution(format!(
+                "Integer overflow computing factorial({})",
+                value
+            ))
+        })
+}
+
+/// Greatest common divisor of the absolute values of `a` and `b`
+fn unsigned_gcd(a: i64, b: i64) -> u64 {
+    let (mut a, mut b) = (a.unsigned_abs(), b.unsigned_abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+fn checked_gcd(a: i64, b: i64) -> Result<i64> {
+    i64::try_from(unsigned_gcd(a, b)).map_err(|_| {
+        DataFusionError::Execution(format!(
+            "Integer overflow computing gcd({}, {})",
+            a, b
+        ))
+    })
+}
+
+fn checked_lcm(a: i64, b: i64) -> Result<i64> {
+    if a == 0 || b == 0 {
+        return Ok(0);
+    }
+    (a.unsigned_abs() / unsigned_gcd(a, b))
+        .checked_mul(b.unsigned_abs())
+        .and_then(|lcm| i64::try_from(lcm).ok())
+        .ok_or_else(|| {
+            DataFusionError::Execution(format!(
+                "Integer overflow computing lcm({}, {})",
+                a, b
+            ))
+        })
+}
+
+/// gcd SQL function, the non-negative greatest common divisor of its arguments
+pub fn gcd(args: &[ArrayRef]) -> Result<ArrayRef> {
+    Ok(Arc::new(make_checked_function_inputs2!(
+        &args[0],
+        &args[1],
+        "x",
+        "y",
+        checked_gcd
+    )) as ArrayRef)
+}
+
+/// lcm SQL function, the non-negative least common multiple of its arguments
+pub fn lcm(args: &[ArrayRef]) -> Result<ArrayRef> {
+    Ok(Arc::new(make_checked_function_inputs2!(
+        &args[0],
+        &args[1],
+        "x",
+        "y",
+        checked_lcm
+    )) as ArrayRef)
+}
+
 pub fn atan2(args: &[ArrayRef]) -> Result<ArrayRef> {
     match args[0].data_type() {
         DataType::Float64 => Ok(Arc::new(make_function_inputs2!(
@@ -207,7 +432,7 @@ pub fn atan2(args: &[ArrayRef]) -> Result<ArrayRef> {
 mod tests {
 
     use super::*;
-    use arrow::array::{Array, Float64Array, NullArray};
+    use arrow::array::{Array, Float64Array, Int64Array, NullArray};
 
     #[test]
     fn test_random_expression() {
@@ -219,6 +444,160 @@ mod tests {
         assert!(0.0 <= floats.value(0) && floats.value(0) < 1.0);
     }
 
+    #[test]
+    fn test_round_to_precision() {
+        let args: Vec<ArrayRef> = vec![
+            Arc::new(Float64Array::from(vec![
+                Some(3.14159),
+                Some(1234.5),
+                Some(-0.125),
+                Some(f64::NAN),
+                Some(1e300),
+                None,
+            ])),
+            Arc::new(Int64Array::from(vec![
+                Some(2),
+                Some(-2),
+                Some(2),
+                Some(1),
+                Some(400),
+                Some(1),
+            ])),
+        ];
+
+        let result = round_to_precision(&args).expect("fail");
+        let floats = result
+            .as_any()
+            .downcast_ref::<Float64Array>()
+            .expect("fail");
+
+        assert_eq!(floats.value(0), 3.14);
+        assert_eq!(floats.value(1), 1200.0);
+        // half away from zero
+        assert_eq!(floats.value(2), -0.13);
+        assert!(floats.value(3).is_nan());
+        assert_eq!(floats.value(4), 1e300);
+        assert!(floats.is_null(5));
+
+        let result = trunc_to_precision(&args).expect("fail");
+        let floats = result
+            .as_any()
+            .downcast_ref::<Float64Array>()
+            .expect("fail");
+        assert_eq!(floats.value(0), 3.14);
+        assert_eq!(floats.value(1), 1200.0);
+        assert_eq!(floats.value(2), -0.12);
+    }
+
+    #[test]
+    fn test_cbrt_negative() {
+        let args = vec![ColumnarValue::Array(Arc::new(Float64Array::from(vec![
+            -8.0, 27.0,
+        ])))];
+        let array = cbrt(&args).expect("fail").into_array(2);
+        let floats = array.as_any().downcast_ref::<Float64Array>().expect("fail");
+        assert_eq!(floats.value(0), -2.0);
+        assert_eq!(floats.value(1), 3.0);
+    }
+
+    #[test]
+    fn test_power_int_overflow() {
+        let args: Vec<ArrayRef> = vec![
+            Arc::new(Int64Array::from(vec![Some(2), Some(-1), Some(1), None])),
+            Arc::new(Int64Array::from(vec![
+                Some(62),
+                Some(u32::MAX as i64 + 1),
+                Some(1 << 40),
+                Some(2),
+            ])),
+        ];
+        let result = power(&args).expect("fail");
+        let ints = result.as_any().downcast_ref::<Int64Array>().expect("fail");
+        assert_eq!(ints.value(0), 1 << 62);
+        assert_eq!(ints.value(1), 1);
+        assert_eq!(ints.value(2), 1);
+        assert!(ints.is_null(3));
+
+        let args: Vec<ArrayRef> = vec![
+            Arc::new(Int64Array::from(vec![2])),
+            Arc::new(Int64Array::from(vec![63])),
+        ];
+        let err = power(&args).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Integer overflow computing power(2, 63)"));
+    }
+
+    #[test]
+    fn test_factorial() {
+        let args: Vec<ArrayRef> = vec![Arc::new(Int64Array::from(vec![
+            Some(0),
+            Some(5),
+            Some(20),
+            None,
+        ]))];
+        let result = factorial(&args).expect("fail");
+        let ints = result.as_any().downcast_ref::<Int64Array>().expect("fail");
+        assert_eq!(ints.value(0), 1);
+        assert_eq!(ints.value(1), 120);
+        assert_eq!(ints.value(2), 2432902008176640000);
+        assert!(ints.is_null(3));
+
+        for (value, expected) in [(21, "Integer overflow"), (-1, "negative number")] {
+            let args: Vec<ArrayRef> = vec![Arc::new(Int64Array::from(vec![value]))];
+            let err = factorial(&args).unwrap_err();
+            assert!(err.to_string().contains(expected), "{}", err);
+        }
+    }
+
+    #[test]
+    fn test_gcd_lcm() {
+        let args: Vec<ArrayRef> = vec![
+            Arc::new(Int64Array::from(vec![
+                Some(12),
+                Some(-4),
+                Some(0),
+                Some(7),
+                None,
+            ])),
+            Arc::new(Int64Array::from(vec![
+                Some(-18),
+                Some(6),
+                Some(0),
+                Some(0),
+                Some(1),
+            ])),
+        ];
+
+        let result = gcd(&args).expect("fail");
+        let ints = result.as_any().downcast_ref::<Int64Array>().expect("fail");
+        assert_eq!(ints.value(0), 6);
+        assert_eq!(ints.value(1), 2);
+        assert_eq!(ints.value(2), 0);
+        assert_eq!(ints.value(3), 7);
+        assert!(ints.is_null(4));
+
+        let result = lcm(&args).expect("fail");
+        let ints = result.as_any().downcast_ref::<Int64Array>().expect("fail");
+        assert_eq!(ints.value(0), 36);
+        assert_eq!(ints.value(1), 12);
+        assert_eq!(ints.value(2), 0);
+        assert_eq!(ints.value(3), 0);
+        assert!(ints.is_null(4));
+
+        // the absolute value of i64::MIN does not fit in an i64
+        let args: Vec<ArrayRef> = vec![
+            Arc::new(Int64Array::from(vec![i64::MIN])),
+            Arc::new(Int64Array::from(vec![0])),
+        ];
+        assert!(gcd(&args).is_err());
+        let args: Vec<ArrayRef> = vec![
+            Arc::new(Int64Array::from(vec![i64::MAX])),
+            Arc::new(Int64Array::from(vec![2])),
+        ];
+        assert!(lcm(&args).is_err());
+    }
+
     #[test]
     fn test_atan2_f64() {
         let args: Vec<ArrayRef> = vec![
@@ -200,7 +200,12 @@ pub fn create_physical_expr(
                 _ => {
                     // assume that we can coerce both sides into a common type
                     // and then perform a binary operation
-                    binary(lhs, *op, rhs, input_schema)
+                    Ok(Arc::new(
+                        expressions::BinaryExpr::try_new(lhs, *op, rhs, input_schema)?
+                            .with_lenient_arithmetic(execution_props.lenient_arithmetic)
+                            .with_wrapping_arithmetic(execution_props.wrapping_arithmetic)
+                            .with_checked_division(execution_props.checked_division),
+                    ))
                 }
             }
         }
@@ -210,10 +215,17 @@ pub fn create_physical_expr(
             pattern,
             escape_char,
         }) => {
-            if escape_char.is_some() {
-                return Err(DataFusionError::Execution(
-                    "LIKE does not support escape_char".to_string(),
-                ));
+            if let Some(escape_char) = escape_char {
+                return create_pattern_match(
+                    expr,
+                    pattern,
+                    *negated,
+                    false,
+                    |pattern| expressions::like_to_regex(pattern, Some(*escape_char)),
+                    input_dfschema,
+                    input_schema,
+                    execution_props,
+                );
             }
             let op = if *negated {
                 Operator::NotLike
@@ -224,6 +236,36 @@ pub fn create_physical_expr(
                 binary_expr(expr.as_ref().clone(), op, pattern.as_ref().clone());
             create_physical_expr(&bin_expr, input_dfschema, input_schema, execution_props)
         }
+        Expr::ILike(Like {
+            negated,
+            expr,
+            pattern,
+            escape_char,
+        }) => create_pattern_match(
+            expr,
+            pattern,
+            *negated,
+            true,
+            |pattern| expressions::like_to_regex(pattern, *escape_char),
+            input_dfschema,
+            input_schema,
+            execution_props,
+        ),
+        Expr::SimilarTo(Like {
+            negated,
+            expr,
+            pattern,
+            escape_char,
+        }) => create_pattern_match(
+            expr,
+            pattern,
+            *negated,
+            false,
+            |pattern| expressions::similar_to_regex(pattern, *escape_char),
+            input_dfschema,
+            input_schema,
+            execution_props,
+        ),
         Expr::Case(case) => {
             let expr: Option<Arc<dyn PhysicalExpr>> = if let Some(e) = &case.expr {
                 Some(create_physical_expr(
@@ -420,3 +462,82 @@ pub fn create_physical_expr(
         ))),
     }
 }
+
+/// Plans a match of `expr` against the literal `pattern`, which `to_regex`
+/// translates into a regular expression, as used for `ILIKE`, `SIMILAR TO`
+/// and `LIKE` with an escape character
+#[allow(clippy::too_many_arguments)]
+fn create_pattern_match(
+    expr: &Expr,
+    pattern: &Expr,
+    negated: bool,
+    case_insensitive: bool,
+    to_regex: impl Fn(&str) -> Result<String>,
+    input_dfschema: &DFSchema,
+    input_schema: &Schema,
+    execution_props: &ExecutionProps,
+) -> Result<Arc<dyn PhysicalExpr>> {
+    let regex = match literal_string(pattern) {
+        Some(Some(pattern)) => to_regex(pattern)?,
+        Some(None) => return Ok(expressions::lit(ScalarValue::Boolean(None))),
+        None => {
+            return Err(DataFusionError::NotImplemented(format!(
+                "Pattern {:?} must be a string literal",
+                pattern
+            )))
+        }
+    };
+    let expr = create_physical_expr(expr, input_dfschema, input_schema, execution_props)?;
+    // the regular expression has the type of `expr`, so dictionaries only
+    // match their values
+    let regex = match expr.data_type(input_schema)? {
+        DataType::Dictionary(key_type, value_type) => ScalarValue::Dictionary(
+            key_type,
+            Box::new(string_scalar(&value_type, regex)?),
+        ),
+        data_type => string_scalar(&data_type, regex)?,
+    };
+    let op = match (negated, case_insensitive) {
+        (false, false) => Operator::RegexMatch,
+        (false, true) => Operator::RegexIMatch,
+        (true, false) => Operator::RegexNotMatch,
+        (true, true) => Operator::RegexNotIMatch,
+    };
+    Ok(Arc::new(expressions::BinaryExpr::try_new(
+        expr,
+        op,
+        expressions::lit(regex),
+        input_schema,
+    )?))
+}
+
+/// Returns the string value of a literal, looking through casts, or `None`
+/// if `expr` is not a string literal
+fn literal_string(expr: &Expr) -> Option<Option<&str>> {
+    fn scalar_string(value: &ScalarValue) -> Option<Option<&str>> {
+        match value {
+            ScalarValue::Utf8(v) | ScalarValue::LargeUtf8(v) => Some(v.as_deref()),
+            ScalarValue::Dictionary(_, v) => scalar_string(v),
+            ScalarValue::Null => Some(None),
+            _ => None,
+        }
+    }
+    match expr {
+        Expr::Literal(value) => scalar_string(value),
+        Expr::Cast(Cast { expr, .. }) | Expr::TryCast { expr, .. } => {
+            literal_string(expr)
+        }
+        _ => None,
+    }
+}
+
+fn string_scalar(data_type: &DataType, value: String) -> Result<ScalarValue> {
+    match data_type {
+        DataType::Utf8 => Ok(ScalarValue::Utf8(Some(value))),
+        DataType::LargeUtf8 => Ok(ScalarValue::LargeUtf8(Some(value))),
+        other => Err(DataFusionError::Plan(format!(
+            "Pattern matching is not supported on {}",
+            other
+        ))),
+    }
+}
@@ -22,19 +22,21 @@
 //! Regex expressions
 
 use arrow::array::{
-    new_null_array, Array, ArrayData, ArrayRef, BufferBuilder, GenericStringArray,
-    OffsetSizeTrait,
+    new_null_array, Array, ArrayData, ArrayRef, BooleanArray, BufferBuilder,
+    GenericStringArray, GenericStringBuilder, ListBuilder, OffsetSizeTrait,
 };
-use arrow::compute;
-use datafusion_common::{DataFusionError, Result};
+use arrow::datatypes::DataType;
+use datafusion_common::{DataFusionError, Result, ScalarValue};
 use datafusion_expr::{ColumnarValue, ScalarFunctionImplementation};
 use hashbrown::HashMap;
 use lazy_static::lazy_static;
-use regex::Regex;
+use regex::{Captures, Regex};
 use std::any::type_name;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
+use crate::expressions::Literal;
 use crate::functions::{make_scalar_function, make_scalar_function_with_hints, Hint};
+use crate::PhysicalExpr;
 
 /// Get the first argument from the given string array.
 ///
@@ -54,152 +56,333 @@ macro_rules! fetch_string_arg {
 macro_rules! downcast_string_array_arg {
     ($ARG:expr, $NAME:expr, $T:ident) => {{
         $ARG.as_any()
-            .downcast_ref::<GenericStringArray<T>>()
+            .downcast_ref::<GenericStringArray<$T>>()
             .ok_or_else(|| {
                 DataFusionError::Internal(format!(
                     "could not cast {} to {}",
                     $NAME,
-                    type_name::<GenericStringArray<T>>()
+                    type_name::<GenericStringArray<$T>>()
                 ))
             })?
     }};
 }
 
-/// extract a specific group from a string column, using a regular expression
-pub fn regexp_match<T: OffsetSizeTrait>(args: &[ArrayRef]) -> Result<ArrayRef> {
-    match args.len() {
-        2 => {
-            let values = downcast_string_array_arg!(args[0], "string", T);
-            let regex = downcast_string_array_arg!(args[1], "pattern", T);
-            compute::regexp_match(values, regex, None).map_err(DataFusionError::ArrowError)
+/// The number of compiled regular expressions a [`RegexCache`] keeps before
+/// starting over, bounding its memory when the patterns come from a column
+const MAX_CACHED_REGEXES: usize = 1024;
+
+/// Compiled regular expressions keyed by their pattern and flags.
+///
+/// Each regexp function expression owns one, so that a pattern is compiled
+/// once rather than for every row, and is reused by every batch the
+/// expression evaluates.
+#[derive(Debug, Default)]
+pub struct RegexCache {
+    regexes: Mutex<HashMap<String, Arc<Regex>>>,
+}
+
+impl RegexCache {
+    /// Returns `pattern` compiled with the inline `flags` (such as `i` for a
+    /// case-insensitive match), compiling it on first use
+    pub fn get(&self, pattern: &str, flags: Option<&str>) -> Result<Arc<Regex>> {
+        self.compile(pattern, flags)
+            .map_err(|err| DataFusionError::Execution(err.to_string()))
+    }
+
+    fn compile(
+        &self,
+        pattern: &str,
+        flags: Option<&str>,
+    ) -> std::result::Result<Arc<Regex>, regex::Error> {
+        let pattern = match flags {
+            Some(flags) if !flags.is_empty() => format!("(?{}){}", flags, pattern),
+            _ => pattern.to_string(),
+        };
+        // a panic while holding the lock leaves the map itself intact
+        let mut regexes = self.regexes.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(regex) = regexes.get(&pattern) {
+            return Ok(regex.clone());
         }
-        3 => {
-            let values = downcast_string_array_arg!(args[0], "string", T);
-            let regex = downcast_string_array_arg!(args[1], "pattern", T);
-            let flags = Some(downcast_string_array_arg!(args[2], "flags", T));
-            compute::regexp_match(values, regex,  flags).map_err(DataFusionError::ArrowError)
+        let regex = Arc::new(Regex::new(&pattern)?);
+        if regexes.len() >= MAX_CACHED_REGEXES {
+            regexes.clear();
         }
-        other => Err(DataFusionError::Internal(format!(
-            "regexp_match was called with {} arguments. It requires at least 2 and at most 3.",
-            other
-        ))),
+        regexes.insert(pattern, regex.clone());
+        Ok(regex)
     }
+
+    /// Creates a cache for the function `name`, compiling its pattern up
+    /// front when the pattern and flags in `args` are literals, so that an
+    /// invalid pattern is reported while planning the query
+    fn try_new_with_literals(
+        name: &str,
+        args: &[Arc<dyn PhysicalExpr>],
+        pattern_index: usize,
+        flags_index: usize,
+        allows_global: bool,
+    ) -> Result<Arc<Self>> {
+        let cache = Arc::new(Self::default());
+        let literal = |index: usize| match args
+            .get(index)
+            .and_then(|arg| arg.as_any().downcast_ref::<Literal>())
+            .map(|literal| literal.value())
+        {
+            Some(ScalarValue::Utf8(Some(value)))
+            | Some(ScalarValue::LargeUtf8(Some(value))) => Some(value.as_str()),
+            _ => None,
+        };
+
+        let pattern = match literal(pattern_index) {
+            Some(pattern) => pattern,
+            None => return Ok(cache),
+        };
+        let flags = match (args.get(flags_index), literal(flags_index)) {
+            (None, _) => None,
+            (Some(_), Some(flags)) => Some(flags),
+            (Some(_), None) => return Ok(cache),
+        };
+        let (flags, global) = split_global_flag(flags);
+        if global && !allows_global {
+            return Err(DataFusionError::Plan(global_flag_message(name)));
+        }
+        cache.compile(pattern, flags.as_deref()).map_err(|err| {
+            DataFusionError::Plan(format!(
+                "Invalid regular expression '{}' for {}: {}",
+                pattern, name, err
+            ))
+        })?;
+        Ok(cache)
+    }
+}
+
+/// Removes the `g` flag of a global replacement from `flags`, returning the
+/// remaining flags and whether it was present
+fn split_global_flag(flags: Option<&str>) -> (Option<String>, bool) {
+    match flags {
+        Some(flags) if flags.contains('g') => (Some(flags.replace('g', "")), true),
+        Some(flags) => (Some(flags.to_string()), false),
+        None => (None, false),
+    }
+}
+
+fn global_flag_message(name: &str) -> String {
+    format!("{}() does not support the \"global\" option", name)
 }
 
-/// replace POSIX capture groups (like \1) with Rust Regex group (like ${1})
-/// used by regexp_replace
+/// Returns the value of row `index` of `array`, an array of a single row
+/// applying to every row
+fn string_at<O: OffsetSizeTrait>(
+    array: &GenericStringArray<O>,
+    index: usize,
+) -> Option<&str> {
+    let index = if array.len() == 1 { 0 } else { index };
+    array.is_valid(index).then(|| array.value(index))
+}
+
+/// The pattern and optional flags arguments of a regexp function, looking
+/// up the regex of each row in a [`RegexCache`]
+struct RegexArgs<'a, O: OffsetSizeTrait> {
+    patterns: &'a GenericStringArray<O>,
+    flags: Option<&'a GenericStringArray<O>>,
+    cache: &'a RegexCache,
+    /// The regex of the previous row, which saves a cache lookup for each
+    /// row of a literal pattern
+    last: Option<(&'a str, Option<&'a str>, Arc<Regex>, bool)>,
+}
+
+impl<'a, O: OffsetSizeTrait> RegexArgs<'a, O> {
+    fn try_new(
+        patterns: &'a ArrayRef,
+        flags: Option<&'a ArrayRef>,
+        cache: &'a RegexCache,
+    ) -> Result<Self> {
+        let patterns = downcast_string_array_arg!(patterns, "pattern", O);
+        let flags = match flags {
+            Some(flags) => Some(downcast_string_array_arg!(flags, "flags", O)),
+            None => None,
+        };
+        Ok(Self {
+            patterns,
+            flags,
+            cache,
+            last: None,
+        })
+    }
+
+    /// Returns the regex of row `index` and whether its flags ask for a
+    /// global replacement, or `None` if its pattern or flags are null
+    fn regex(&mut self, index: usize) -> Result<Option<(Arc<Regex>, bool)>> {
+        let pattern = match string_at(self.patterns, index) {
+            Some(pattern) => pattern,
+            None => return Ok(None),
+        };
+        let flags = match self.flags {
+            Some(flags) => match string_at(flags, index) {
+                Some(flags) => Some(flags),
+                None => return Ok(None),
+            },
+            None => None,
+        };
+        if let Some((last_pattern, last_flags, regex, global)) = &self.last {
+            if *last_pattern == pattern && *last_flags == flags {
+                return Ok(Some((regex.clone(), *global)));
+            }
+        }
+
+        let (inline_flags, global) = split_global_flag(flags);
+        let regex = self.cache.get(pattern, inline_flags.as_deref())?;
+        self.last = Some((pattern, flags, regex.clone(), global));
+        Ok(Some((regex, global)))
+    }
+
+    /// Like [`Self::regex`] for functions without a global mode
+    fn regex_for(&mut self, name: &str, index: usize) -> Result<Option<Arc<Regex>>> {
+        match self.regex(index)? {
+            Some((_, true)) => Err(DataFusionError::Execution(global_flag_message(name))),
+            Some((regex, false)) => Ok(Some(regex)),
+            None => Ok(None),
+        }
+    }
+}
+
+fn check_arg_count(name: &str, args: &[ArrayRef], min: usize, max: usize) -> Result<()> {
+    if args.len() < min || args.len() > max {
+        return Err(DataFusionError::Internal(format!(
+            "{} was called with {} arguments. It requires at least {} and at most {}.",
+            name,
+            args.len(),
+            min,
+            max
+        )));
+    }
+    Ok(())
+}
+
+/// Extracts the capture groups of the first match of a regular expression,
+/// or the whole match when the expression has no groups.
+///
+/// example: `regexp_match('aaa-555', '.*-(\d*)') = ['555']`
+pub fn regexp_match<T: OffsetSizeTrait>(
+    args: &[ArrayRef],
+    cache: &RegexCache,
+) -> Result<ArrayRef> {
+    check_arg_count("regexp_match", args, 2, 3)?;
+    let values = downcast_string_array_arg!(args[0], "string", T);
+    let mut regexes = RegexArgs::<i32>::try_new(&args[1], args.get(2), cache)?;
+
+    let mut builder = ListBuilder::new(GenericStringBuilder::<T>::new());
+    for (index, value) in values.iter().enumerate() {
+        let regex = regexes.regex_for("regexp_match", index)?;
+        let captures = match (value, regex) {
+            (Some(value), Some(regex)) => regex.captures(value),
+            _ => None,
+        };
+        match captures {
+            Some(captures) => {
+                // the first group is the whole match
+                let skip = usize::from(captures.len() > 1);
+                for group in captures.iter().skip(skip) {
+                    match group {
+                        Some(group) => builder.values().append_value(group.as_str()),
+                        None => builder.values().append_null(),
+                    }
+                }
+                builder.append(true);
+            }
+            None => builder.append(false),
+        }
+    }
+    Ok(Arc::new(builder.finish()))
+}
+
+/// Whether a string matches a regular expression.
+///
+/// example: `regexp_like('Thomas', '.*[mN]a.*') = true`
+pub fn regexp_like<T: OffsetSizeTrait>(
+    args: &[ArrayRef],
+    cache: &RegexCache,
+) -> Result<ArrayRef> {
+    check_arg_count("regexp_like", args, 2, 3)?;
+    let values = downcast_string_array_arg!(args[0], "string", T);
+    let mut regexes = RegexArgs::<i32>::try_new(&args[1], args.get(2), cache)?;
+
+    let result = values
+        .iter()
+        .enumerate()
+        .map(|(index, value)| {
+            let regex = regexes.regex_for("regexp_like", index)?;
+            Ok(match (value, regex) {
+                (Some(value), Some(regex)) => Some(regex.is_match(value)),
+                _ => None,
+            })
+        })
+        .collect::<Result<BooleanArray>>()?;
+    Ok(Arc::new(result))
+}
+
+/// replace POSIX capture groups (like \1) and `$1` references with Rust
+/// Regex groups (like ${1}), keeping `$$` as an escaped dollar sign. Used
+/// by regexp_replace
 fn regex_replace_posix_groups(replacement: &str) -> String {
     lazy_static! {
-        static ref CAPTURE_GROUPS_RE: Regex = Regex::new(r"(\\)(\d*)").unwrap();
+        static ref CAPTURE_GROUPS_RE: Regex =
+            Regex::new(r"\$\$|\\(\d*)|\$(\d+)").unwrap();
     }
     CAPTURE_GROUPS_RE
-        .replace_all(replacement, "$${$2}")
+        .replace_all(replacement, |captures: &Captures| {
+            match captures.get(1).or_else(|| captures.get(2)) {
+                Some(group) => format!("${{{}}}", group.as_str()),
+                None => "$$".to_string(),
+            }
+        })
         .into_owned()
 }
 
-/// Replaces substring(s) matching a POSIX regular expression.
+/// Replaces substring(s) matching a POSIX regular expression. Only the first
+/// match is replaced unless the flags contain `g`.
 ///
 /// example: `regexp_replace('Thomas', '.[mN]a.', 'M') = 'ThM'`
-pub fn regexp_replace<T: OffsetSizeTrait>(args: &[ArrayRef]) -> Result<ArrayRef> {
+pub fn regexp_replace<T: OffsetSizeTrait>(
+    args: &[ArrayRef],
+    cache: &RegexCache,
+) -> Result<ArrayRef> {
     // Default implementation for regexp_replace, assumes all args are arrays
     // and args is a sequence of 3 or 4 elements.
-
-    // creating Regex is expensive so create hashmap for memoization
-    let mut patterns: HashMap<String, Regex> = HashMap::new();
-
-    match args.len() {
-        3 => {
-            let string_array = downcast_string_array_arg!(args[0], "string", T);
-            let pattern_array = downcast_string_array_arg!(args[1], "pattern", T);
-            let replacement_array = downcast_string_array_arg!(args[2], "replacement", T);
-
-            let result = string_array
-            .iter()
-            .zip(pattern_array.iter())
-            .zip(replacement_array.iter())
-            .map(|((string, pattern), replacement)| match (string, pattern, replacement) {
-                (Some(string), Some(pattern), Some(replacement)) => {
-                    let replacement = regex_replace_posix_groups(replacement);
-
-                    // if patterns hashmap already has regexp then use else else create and return
-                    let re = match patterns.get(pattern) {
-                        Some(re) => Ok(re.clone()),
-                        None => {
-                            match Regex::new(pattern) {
-                                Ok(re) => {
-                                    patterns.insert(pattern.to_string(), re.clone());
-                                    Ok(re)
-                                },
-                                Err(err) => Err(DataFusionError::Execution(err.to_string())),
-                            }
-                        }
-                    };
-
-                    Some(re.map(|re| re.replace(string, replacement.as_str()))).transpose()
-                }
-            _ => Ok(None)
-            })
-            .collect::<Result<GenericStringArray<T>>>()?;
-
-            Ok(Arc::new(result) as ArrayRef)
-        }
-        4 => {
-            let string_array = downcast_string_array_arg!(args[0], "string", T);
-            let pattern_array = downcast_string_array_arg!(args[1], "pattern", T);
-            let replacement_array = downcast_string_array_arg!(args[2], "replacement", T);
-            let flags_array = downcast_string_array_arg!(args[3], "flags", T);
-
-            let result = string_array
-            .iter()
-            .zip(pattern_array.iter())
-            .zip(replacement_array.iter())
-            .zip(flags_array.iter())
-            .map(|(((string, pattern), replacement), flags)| match (string, pattern, replacement, flags) {
-                (Some(string), Some(pattern), Some(replacement), Some(flags)) => {
-                    let replacement = regex_replace_posix_groups(replacement);
-
-                    // format flags into rust pattern
-                    let (pattern, replace_all) = if flags == "g" {
-                        (pattern.to_string(), true)
-                    } else if flags.contains('g') {
-                        (format!("(?{}){}", flags.to_string().replace('g', ""), pattern), true)
-                    } else {
-                        (format!("(?{}){}", flags, pattern), false)
-                    };
-
-                    // if patterns hashmap already has regexp then use else else create and return
-                    let re = match patterns.get(&pattern) {
-                        Some(re) => Ok(re.clone()),
-                        None => {
-                            match Regex::new(pattern.as_str()) {
-                                Ok(re) => {
-                                    patterns.insert(pattern, re.clone());
-                                    Ok(re)
-                                },
-                                Err(err) => Err(DataFusionError::Execution(err.to_string())),
-                            }
-                        }
-                    };
-
-                    Some(re.map(|re| {
-                        if replace_all {
-                            re.replace_all(string, replacement.as_str())
-                        } else {
-                            re.replace(string, replacement.as_str())
-                        }
-                    })).transpose()
+    check_arg_count("regexp_replace", args, 3, 4)?;
+    let string_array = downcast_string_array_arg!(args[0], "string", T);
+    let replacement_array = downcast_string_array_arg!(args[2], "replacement", T);
+    let mut regexes = RegexArgs::<T>::try_new(&args[1], args.get(3), cache)?;
+
+    // the replacement of the previous row, translated to Rust groups
+    let mut last_replacement: Option<(&str, String)> = None;
+
+    let result = string_array
+        .iter()
+        .enumerate()
+        .map(|(index, string)| {
+            let regex = regexes.regex(index)?;
+            let replacement = string_at(replacement_array, index);
+            let (string, (regex, global), replacement) =
+                match (string, regex, replacement) {
+                    (Some(string), Some(regex), Some(replacement)) => {
+                        (string, regex, replacement)
+                    }
+                    _ => return Ok(None),
+                };
+            let replacement = match &mut last_replacement {
+                Some((last, translated)) if *last == replacement => translated,
+                last => {
+                    &last
+                        .insert((replacement, regex_replace_posix_groups(replacement)))
+                        .1
                 }
-            _ => Ok(None)
-            })
-            .collect::<Result<GenericStringArray<T>>>()?;
+            };
+            let limit = if global { 0 } else { 1 };
+            Ok(Some(regex.replacen(string, limit, replacement.as_str())))
+        })
+        .collect::<Result<GenericStringArray<T>>>()?;
 
-            Ok(Arc::new(result) as ArrayRef)
-        }
-        other => Err(DataFusionError::Internal(format!(
-            "regexp_replace was called with {} arguments. It requires at least 3 and at most 4.",
-            other
-        ))),
-    }
+    Ok(Arc::new(result) as ArrayRef)
 }
 
 fn _regexp_replace_early_abort<T: OffsetSizeTrait>(
@@ -214,12 +397,12 @@ fn _regexp_replace_early_abort<T: OffsetSizeTrait>(
 
 /// Special cased regex_replace implementation for the scenerio where
 /// the pattern, replacement and flags are static (arrays that are derived
-/// from scalars). This means we can skip regex caching system and basically
-/// hold a single Regex object for the replace operation. This also speeds
-/// up the pre-processing time of the replacement string, since it only
-/// needs to processed once.
+/// from scalars). This means we hold a single Regex object for the replace
+/// operation. This also speeds up the pre-processing time of the replacement
+/// string, since it only needs to processed once.
 fn _regexp_replace_static_pattern_replace<T: OffsetSizeTrait>(
     args: &[ArrayRef],
+    cache: &RegexCache,
 ) -> Result<ArrayRef> {
     let string_array = downcast_string_array_arg!(args[0], "string", T);
     let pattern = fetch_string_arg!(args[1], "pattern", T, _regexp_replace_early_abort);
@@ -239,17 +422,9 @@ fn _regexp_replace_static_pattern_replace<T: OffsetSizeTrait>(
     // Embed the flag (if it exists) into the pattern. Limit will determine
     // whether this is a global match (as in replace all) or just a single
     // replace operation.
-    let (pattern, limit) = match flags {
-        Some("g") => (pattern.to_string(), 0),
-        Some(flags) => (
-            format!("(?{}){}", flags.to_string().replace('g', ""), pattern),
-            !flags.contains('g') as usize,
-        ),
-        None => (pattern.to_string(), 1),
-    };
-
-    let re = Regex::new(&pattern)
-        .map_err(|err| DataFusionError::Execution(err.to_string()))?;
+    let (flags, global) = split_global_flag(flags);
+    let limit = if global { 0 } else { 1 };
+    let re = cache.get(pattern, flags.as_deref())?;
 
     // Replaces the posix groups in the replacement string
     // with rust ones.
@@ -293,6 +468,7 @@ fn _regexp_replace_static_pattern_replace<T: OffsetSizeTrait>(
 /// on the given set of arguments.
 pub fn specialize_regexp_replace<T: OffsetSizeTrait>(
     args: &[ColumnarValue],
+    cache: &Arc<RegexCache>,
 ) -> Result<ScalarFunctionImplementation> {
     // This will serve as a dispatch table where we can
     // leverage it in order to determine whether the scalarity
@@ -307,6 +483,7 @@ pub fn specialize_regexp_replace<T: OffsetSizeTrait>(
         matches!(args.get(3), Some(ColumnarValue::Scalar(_)) | None),
     );
 
+    let cache = cache.clone();
     match (
         is_source_scalar,
         is_pattern_scalar,
@@ -315,19 +492,18 @@ pub fn specialize_regexp_replace<T: OffsetSizeTrait>(
     ) {
         // This represents a very hot path for the case where the there is
         // a single pattern that is being matched against and a single replacement.
-        // This is extremely important to specialize on since it removes the overhead
-        // of DF's in-house regex pattern cache (since there will be at most a single
-        // pattern) and the pre-processing of the same replacement pattern at each
-        // query.
+        // This is extremely important to specialize on since it removes the
+        // per-row pattern lookups and the pre-processing of the same replacement
+        // pattern at each row.
         //
         // The flags needs to be a scalar as well since each pattern is actually
         // constructed with the flags embedded into the pattern itself. This means
         // even if the pattern itself is scalar, if the flags are an array then
-        // we will create many regexes and it is best to use the implementation
-        // that caches it. If there are no flags, we can simply ignore it here,
+        // we will look up many regexes and it is best to use the generic
+        // implementation. If there are no flags, we can simply ignore it here,
         // and let the specialized function handle it.
         (_, true, true, true) => Ok(make_scalar_function_with_hints(
-            _regexp_replace_static_pattern_replace::<T>,
+            move |args| _regexp_replace_static_pattern_replace::<T>(args, &cache),
             vec![
                 Hint::Pad,
                 Hint::AcceptsSingular,
@@ -338,15 +514,89 @@ pub fn specialize_regexp_replace<T: OffsetSizeTrait>(
 
         // If there are no specialized implementations, we'll fall back to the
         // generic implementation.
-        (_, _, _, _) => Ok(make_scalar_function(regexp_replace::<T>)),
+        (_, _, _, _) => Ok(make_scalar_function(move |args| {
+            regexp_replace::<T>(args, &cache)
+        })),
     }
 }
 
+/// Dispatches on the string type of the first argument of a regexp function
+fn dispatch_on_string_type(
+    name: &'static str,
+    utf8: ScalarFunctionImplementation,
+    large_utf8: ScalarFunctionImplementation,
+) -> ScalarFunctionImplementation {
+    Arc::new(move |args| match args[0].data_type() {
+        DataType::Utf8 => utf8(args),
+        DataType::LargeUtf8 => large_utf8(args),
+        other => Err(DataFusionError::Internal(format!(
+            "Unsupported data type {:?} for function {}",
+            other, name
+        ))),
+    })
+}
+
+/// Creates the implementation of `regexp_match` for the arguments `args`,
+/// which may be empty when they are not known
+pub fn create_regexp_match(
+    args: &[Arc<dyn PhysicalExpr>],
+) -> Result<ScalarFunctionImplementation> {
+    let cache = RegexCache::try_new_with_literals("regexp_match", args, 1, 2, false)?;
+    let large_cache = cache.clone();
+    let hints = vec![Hint::Pad, Hint::AcceptsSingular, Hint::AcceptsSingular];
+    Ok(dispatch_on_string_type(
+        "regexp_match",
+        make_scalar_function_with_hints(
+            move |args| regexp_match::<i32>(args, &cache),
+            hints.clone(),
+        ),
+        make_scalar_function_with_hints(
+            move |args| regexp_match::<i64>(args, &large_cache),
+            hints,
+        ),
+    ))
+}
+
+/// Creates the implementation of `regexp_like` for the arguments `args`,
+/// which may be empty when they are not known
+pub fn create_regexp_like(
+    args: &[Arc<dyn PhysicalExpr>],
+) -> Result<ScalarFunctionImplementation> {
+    let cache = RegexCache::try_new_with_literals("regexp_like", args, 1, 2, false)?;
+    let large_cache = cache.clone();
+    let hints = vec![Hint::Pad, Hint::AcceptsSingular, Hint::AcceptsSingular];
+    Ok(dispatch_on_string_type(
+        "regexp_like",
+        make_scalar_function_with_hints(
+            move |args| regexp_like::<i32>(args, &cache),
+            hints.clone(),
+        ),
+        make_scalar_function_with_hints(
+            move |args| regexp_like::<i64>(args, &large_cache),
+            hints,
+        ),
+    ))
+}
+
+/// Creates the implementation of `regexp_replace` for the arguments `args`,
+/// which may be empty when they are not known
+pub fn create_regexp_replace(
+    args: &[Arc<dyn PhysicalExpr>],
+) -> Result<ScalarFunctionImplementation> {
+    let cache = RegexCache::try_new_with_literals("regexp_replace", args, 1, 3, true)?;
+    let large_cache = cache.clone();
+    Ok(dispatch_on_string_type(
+        "regexp_replace",
+        Arc::new(move |args| specialize_regexp_replace::<i32>(args, &cache)?(args)),
+        Arc::new(move |args| specialize_regexp_replace::<i64>(args, &large_cache)?(args)),
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::expressions::{lit, Column};
     use arrow::array::*;
-    use datafusion_common::ScalarValue;
 
     #[test]
     fn test_case_sensitive_regexp_match() {
@@ -365,7 +615,11 @@ mod tests {
         expected_builder.append(false);
         let expected = expected_builder.finish();
 
-        let re = regexp_match::<i32>(&[Arc::new(values), Arc::new(patterns)]).unwrap();
+        let re = regexp_match::<i32>(
+            &[Arc::new(values), Arc::new(patterns)],
+            &RegexCache::default(),
+        )
+        .unwrap();
 
         assert_eq!(re.as_ref(), &expected);
     }
@@ -390,9 +644,11 @@ mod tests {
         expected_builder.append(false);
         let expected = expected_builder.finish();
 
-        let re =
-            regexp_match::<i32>(&[Arc::new(values), Arc::new(patterns), Arc::new(flags)])
-                .unwrap();
+        let re = regexp_match::<i32>(
+            &[Arc::new(values), Arc::new(patterns), Arc::new(flags)],
+            &RegexCache::default(),
+        )
+        .unwrap();
 
         assert_eq!(re.as_ref(), &expected);
     }
@@ -523,6 +779,7 @@ mod tests {
             };
         }
 
+        let cache = Arc::new(RegexCache::default());
         for source in [make_scalar!(), make_array!()] {
             for pattern in [make_scalar!(), make_array!()] {
                 for replacement in [make_scalar!(), make_array!()] {
@@ -532,7 +789,7 @@ mod tests {
                         if let Some(flags) = flags {
                             args.push(flags.clone());
                         }
-                        let regex_func = specialize_regexp_replace::<i32>(&args);
+                        let regex_func = specialize_regexp_replace::<i32>(&args, &cache);
                         assert!(regex_func.is_ok());
                     }
                 }
@@ -602,4 +859,108 @@ mod tests {
         assert_eq!(re.as_ref(), &expected);
         assert_eq!(re.null_count(), 3);
     }
+
+    #[test]
+    fn test_regexp_replace_global_and_first() {
+        let values = StringArray::from(vec!["aaa", "aAa", "aaa", "aaa"]);
+        let patterns = StringArray::from(vec!["a"]);
+        let replacements = StringArray::from(vec!["<$0>"]);
+        let flags = StringArray::from(vec!["", "gi", "g", "i"]);
+        let expected =
+            StringArray::from(vec!["<a>aa", "<a><A><a>", "<a><a><a>", "<a>aa"]);
+
+        let re = regexp_replace::<i32>(
+            &[
+                Arc::new(values),
+                Arc::new(patterns),
+                Arc::new(replacements),
+                Arc::new(flags),
+            ],
+            &RegexCache::default(),
+        )
+        .unwrap();
+
+        assert_eq!(re.as_ref(), &expected);
+    }
+
+    #[test]
+    fn test_regexp_replace_backreferences() {
+        assert_eq!(regex_replace_posix_groups("X\\1Y"), "X${1}Y");
+        assert_eq!(regex_replace_posix_groups("X$1Y$2"), "X${1}Y${2}");
+        assert_eq!(regex_replace_posix_groups("$$1 $"), "$$1 $");
+    }
+
+    #[test]
+    fn test_regexp_like() {
+        let values = StringArray::from(vec![Some("abc"), Some("ABC"), Some("bc"), None]);
+        let patterns = StringArray::from(vec!["^a"]);
+        let flags = StringArray::from(vec!["i"]);
+        let expected =
+            BooleanArray::from(vec![Some(true), Some(true), Some(false), None]);
+
+        let re = regexp_like::<i32>(
+            &[Arc::new(values), Arc::new(patterns), Arc::new(flags)],
+            &RegexCache::default(),
+        )
+        .unwrap();
+
+        assert_eq!(re.as_ref(), &expected);
+    }
+
+    #[test]
+    fn test_regexp_match_global_flag() {
+        let values = StringArray::from(vec!["abc"]);
+        let patterns = StringArray::from(vec!["b"]);
+        let flags = StringArray::from(vec!["g"]);
+
+        let err = regexp_match::<i32>(
+            &[Arc::new(values), Arc::new(patterns), Arc::new(flags)],
+            &RegexCache::default(),
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Execution error: regexp_match() does not support the \"global\" option"
+        );
+    }
+
+    #[test]
+    fn test_regex_cache() -> Result<()> {
+        let cache = RegexCache::default();
+        let regex = cache.get("a", Some("i"))?;
+        assert!(regex.is_match("A"));
+        assert!(Arc::ptr_eq(&regex, &cache.get("a", Some("i"))?));
+        assert!(!Arc::ptr_eq(&regex, &cache.get("a", None)?));
+        Ok(())
+    }
+
+    #[test]
+    fn test_literal_pattern_compiled_when_planning() {
+        let string: Arc<dyn PhysicalExpr> = Arc::new(Column::new("a", 0));
+
+        let err = create_regexp_match(&[string.clone(), lit("(")])
+            .err()
+            .expect("invalid literal pattern should fail");
+        assert!(
+            err.to_string().starts_with(
+                "Error during planning: Invalid regular expression '(' for regexp_match"
+            ),
+            "{}",
+            err
+        );
+
+        let err = create_regexp_replace(&[string.clone(), lit("["), lit("b")])
+            .err()
+            .expect("invalid literal pattern should fail");
+        assert!(matches!(err, DataFusionError::Plan(_)));
+
+        let err = create_regexp_like(&[string.clone(), lit("a"), lit("g")])
+            .err()
+            .expect("the global flag should fail");
+        assert!(matches!(err, DataFusionError::Plan(_)));
+
+        // patterns from a column are only compiled when evaluated
+        assert!(create_regexp_like(&[string.clone(), string]).is_ok());
+    }
 }
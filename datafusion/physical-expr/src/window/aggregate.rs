@@ -103,7 +103,7 @@ impl WindowExpr for AggregateWindowExpr {
         };
         let mut row_wise_results: Vec<ScalarValue> = vec![];
         for partition_range in &partition_points {
-            let mut accumulator = self.aggregate.create_accumulator()?;
+            let mut accumulator = self.aggregate.create_sliding_accumulator()?;
             let length = partition_range.end - partition_range.start;
             let slice_order_bys = order_bys
                 .iter()
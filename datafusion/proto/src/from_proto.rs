@@ -33,17 +33,18 @@ use datafusion_common::{
 };
 use datafusion_expr::expr::{BinaryExpr, Cast};
 use datafusion_expr::{
-    abs, acos, array, ascii, asin, atan, atan2, bit_length, btrim, ceil,
-    character_length, chr, coalesce, concat_expr, concat_ws_expr, cos, date_bin,
-    date_part, date_trunc, digest, exp, floor, from_unixtime, left, ln, log10, log2,
+    abs, acos, array, ascii, asin, atan, atan2, bit_length, btrim, cbrt, ceil,
+    character_length, chr, coalesce, concat_expr, concat_ws_expr, cos, current_catalog,
+    current_schema, date_bin, date_part, date_trunc, degrees, digest, exp, factorial,
+    floor, from_unixtime, gcd, greatest, lcm, least, left, ln, log10, log2,
     logical_plan::{PlanType, StringifiedPlan},
-    lower, lpad, ltrim, md5, now, nullif, octet_length, power, random, regexp_match,
-    regexp_replace, repeat, replace, reverse, right, round, rpad, rtrim, sha224, sha256,
-    sha384, sha512, signum, sin, split_part, sqrt, starts_with, strpos, substr,
-    substring, tan, to_hex, to_timestamp_micros, to_timestamp_millis,
-    to_timestamp_seconds, translate, trim, trunc, upper, uuid, AggregateFunction,
-    Between, BuiltInWindowFunction, BuiltinScalarFunction, Case, Expr, GetIndexedField,
-    GroupingSet,
+    lower, lpad, ltrim, md5, now, nullif, octet_length, pi, power, radians, random,
+    regexp_like, regexp_match, regexp_replace, repeat, replace, reverse, right, rpad,
+    rtrim, sha224, sha256, sha384, sha512, signum, sin, split_part, sqrt, starts_with,
+    strpos, substr, substring, tan, to_char, to_date, to_hex, to_timestamp_micros,
+    to_timestamp_millis, to_timestamp_seconds, translate, trim, upper, uuid, version,
+    xxhash64, AggregateFunction, Between, BuiltInWindowFunction, BuiltinScalarFunction,
+    Case, Expr, GetIndexedField, GroupingSet,
     GroupingSet::GroupingSets,
     Like, Operator, WindowFrame, WindowFrameBound, WindowFrameUnits,
 };
@@ -440,6 +441,22 @@ impl From<&protobuf::ScalarFunction> for BuiltinScalarFunction {
             ScalarFunction::FromUnixtime => Self::FromUnixtime,
             ScalarFunction::Atan2 => Self::Atan2,
             ScalarFunction::ArrowTypeof => Self::ArrowTypeof,
+            ScalarFunction::XxHash64 => Self::XxHash64,
+            ScalarFunction::Cbrt => Self::Cbrt,
+            ScalarFunction::Degrees => Self::Degrees,
+            ScalarFunction::Radians => Self::Radians,
+            ScalarFunction::Pi => Self::Pi,
+            ScalarFunction::Factorial => Self::Factorial,
+            ScalarFunction::Gcd => Self::Gcd,
+            ScalarFunction::Lcm => Self::Lcm,
+            ScalarFunction::RegexpLike => Self::RegexpLike,
+            ScalarFunction::Greatest => Self::Greatest,
+            ScalarFunction::Least => Self::Least,
+            ScalarFunction::ToChar => Self::ToChar,
+            ScalarFunction::ToDate => Self::ToDate,
+            ScalarFunction::Version => Self::Version,
+            ScalarFunction::CurrentCatalog => Self::CurrentCatalog,
+            ScalarFunction::CurrentSchema => Self::CurrentSchema,
         }
     }
 }
@@ -470,6 +487,15 @@ impl From<protobuf::AggregateFunction> for AggregateFunction {
             protobuf::AggregateFunction::ApproxMedian => Self::ApproxMedian,
             protobuf::AggregateFunction::Grouping => Self::Grouping,
             protobuf::AggregateFunction::Median => Self::Median,
+            protobuf::AggregateFunction::FirstValue => Self::FirstValue,
+            protobuf::AggregateFunction::LastValue => Self::LastValue,
+            protobuf::AggregateFunction::StringAgg => Self::StringAgg,
+            protobuf::AggregateFunction::Mode => Self::Mode,
+            protobuf::AggregateFunction::BitAnd => Self::BitAnd,
+            protobuf::AggregateFunction::BitOr => Self::BitOr,
+            protobuf::AggregateFunction::BitXor => Self::BitXor,
+            protobuf::AggregateFunction::BoolAnd => Self::BoolAnd,
+            protobuf::AggregateFunction::BoolOr => Self::BoolOr,
         }
     }
 }
@@ -937,8 +963,31 @@ pub fn parse_expr(
                 ScalarFunction::Log10 => Ok(log10(parse_expr(&args[0], registry)?)),
                 ScalarFunction::Floor => Ok(floor(parse_expr(&args[0], registry)?)),
                 ScalarFunction::Ceil => Ok(ceil(parse_expr(&args[0], registry)?)),
-                ScalarFunction::Round => Ok(round(parse_expr(&args[0], registry)?)),
-                ScalarFunction::Trunc => Ok(trunc(parse_expr(&args[0], registry)?)),
+                // these take an optional second argument
+                ScalarFunction::Round | ScalarFunction::Trunc | ScalarFunction::Log => {
+                    Ok(Expr::ScalarFunction {
+                        fun: BuiltinScalarFunction::from(&scalar_function),
+                        args: args
+                            .iter()
+                            .map(|expr| parse_expr(expr, registry))
+                            .collect::<Result<Vec<_>, _>>()?,
+                    })
+                }
+                ScalarFunction::Cbrt => Ok(cbrt(parse_expr(&args[0], registry)?)),
+                ScalarFunction::Degrees => Ok(degrees(parse_expr(&args[0], registry)?)),
+                ScalarFunction::Radians => Ok(radians(parse_expr(&args[0], registry)?)),
+                ScalarFunction::Factorial => {
+                    Ok(factorial(parse_expr(&args[0], registry)?))
+                }
+                ScalarFunction::Pi => Ok(pi()),
+                ScalarFunction::Gcd => Ok(gcd(
+                    parse_expr(&args[0], registry)?,
+                    parse_expr(&args[1], registry)?,
+                )),
+                ScalarFunction::Lcm => Ok(lcm(
+                    parse_expr(&args[0], registry)?,
+                    parse_expr(&args[1], registry)?,
+                )),
                 ScalarFunction::Abs => Ok(abs(parse_expr(&args[0], registry)?)),
                 ScalarFunction::Signum => Ok(signum(parse_expr(&args[0], registry)?)),
                 ScalarFunction::OctetLength => {
@@ -1016,6 +1065,12 @@ pub fn parse_expr(
                         .map(|expr| parse_expr(expr, registry))
                         .collect::<Result<Vec<_>, _>>()?,
                 )),
+                ScalarFunction::XxHash64 => Ok(xxhash64(
+                    args.to_owned()
+                        .iter()
+                        .map(|expr| parse_expr(expr, registry))
+                        .collect::<Result<Vec<_>, _>>()?,
+                )),
                 ScalarFunction::Lpad => Ok(lpad(
                     args.to_owned()
                         .iter()
@@ -1040,6 +1095,24 @@ pub fn parse_expr(
                         .map(|expr| parse_expr(expr, registry))
                         .collect::<Result<Vec<_>, _>>()?,
                 )),
+                ScalarFunction::RegexpLike => Ok(regexp_like(
+                    args.to_owned()
+                        .iter()
+                        .map(|expr| parse_expr(expr, registry))
+                        .collect::<Result<Vec<_>, _>>()?,
+                )),
+                ScalarFunction::Greatest => Ok(greatest(
+                    args.to_owned()
+                        .iter()
+                        .map(|expr| parse_expr(expr, registry))
+                        .collect::<Result<Vec<_>, _>>()?,
+                )),
+                ScalarFunction::Least => Ok(least(
+                    args.to_owned()
+                        .iter()
+                        .map(|expr| parse_expr(expr, registry))
+                        .collect::<Result<Vec<_>, _>>()?,
+                )),
                 ScalarFunction::Btrim => Ok(btrim(
                     args.to_owned()
                         .iter()
@@ -1075,6 +1148,16 @@ pub fn parse_expr(
                     }
                 }
                 ScalarFunction::ToHex => Ok(to_hex(parse_expr(&args[0], registry)?)),
+                ScalarFunction::ToChar => Ok(to_char(
+                    parse_expr(&args[0], registry)?,
+                    parse_expr(&args[1], registry)?,
+                )),
+                ScalarFunction::ToDate => Ok(to_date(
+                    args.to_owned()
+                        .iter()
+                        .map(|expr| parse_expr(expr, registry))
+                        .collect::<Result<Vec<_>, _>>()?,
+                )),
                 ScalarFunction::ToTimestampMillis => {
                     Ok(to_timestamp_millis(parse_expr(&args[0], registry)?))
                 }
@@ -1085,6 +1168,9 @@ pub fn parse_expr(
                     Ok(to_timestamp_seconds(parse_expr(&args[0], registry)?))
                 }
                 ScalarFunction::Now => Ok(now()),
+                ScalarFunction::Version => Ok(version()),
+                ScalarFunction::CurrentCatalog => Ok(current_catalog()),
+                ScalarFunction::CurrentSchema => Ok(current_schema()),
                 ScalarFunction::Translate => Ok(translate(
                     parse_expr(&args[0], registry)?,
                     parse_expr(&args[1], registry)?,
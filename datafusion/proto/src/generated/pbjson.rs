@@ -169,6 +169,15 @@ impl serde::Serialize for AggregateFunction {
             Self::ApproxPercentileContWithWeight => "APPROX_PERCENTILE_CONT_WITH_WEIGHT",
             Self::Grouping => "GROUPING",
             Self::Median => "MEDIAN",
+            Self::FirstValue => "FIRST_VALUE",
+            Self::LastValue => "LAST_VALUE",
+            Self::StringAgg => "STRING_AGG",
+            Self::Mode => "MODE",
+            Self::BitAnd => "BIT_AND",
+            Self::BitOr => "BIT_OR",
+            Self::BitXor => "BIT_XOR",
+            Self::BoolAnd => "BOOL_AND",
+            Self::BoolOr => "BOOL_OR",
         };
         serializer.serialize_str(variant)
     }
@@ -199,6 +208,15 @@ impl<'de> serde::Deserialize<'de> for AggregateFunction {
             "APPROX_PERCENTILE_CONT_WITH_WEIGHT",
             "GROUPING",
             "MEDIAN",
+            "FIRST_VALUE",
+            "LAST_VALUE",
+            "STRING_AGG",
+            "MODE",
+            "BIT_AND",
+            "BIT_OR",
+            "BIT_XOR",
+            "BOOL_AND",
+            "BOOL_OR",
         ];
 
         struct GeneratedVisitor;
@@ -260,6 +278,15 @@ impl<'de> serde::Deserialize<'de> for AggregateFunction {
                     "APPROX_PERCENTILE_CONT_WITH_WEIGHT" => Ok(AggregateFunction::ApproxPercentileContWithWeight),
                     "GROUPING" => Ok(AggregateFunction::Grouping),
                     "MEDIAN" => Ok(AggregateFunction::Median),
+                    "FIRST_VALUE" => Ok(AggregateFunction::FirstValue),
+                    "LAST_VALUE" => Ok(AggregateFunction::LastValue),
+                    "STRING_AGG" => Ok(AggregateFunction::StringAgg),
+                    "MODE" => Ok(AggregateFunction::Mode),
+                    "BIT_AND" => Ok(AggregateFunction::BitAnd),
+                    "BIT_OR" => Ok(AggregateFunction::BitOr),
+                    "BIT_XOR" => Ok(AggregateFunction::BitXor),
+                    "BOOL_AND" => Ok(AggregateFunction::BoolAnd),
+                    "BOOL_OR" => Ok(AggregateFunction::BoolOr),
                     _ => Err(serde::de::Error::unknown_variant(value, FIELDS)),
                 }
             }
@@ -3095,6 +3122,9 @@ impl serde::Serialize for CustomTableScanNode {
         if !self.custom_table_data.is_empty() {
             len += 1;
         }
+        if self.by_name {
+            len += 1;
+        }
         let mut struct_ser = serializer.serialize_struct("datafusion.CustomTableScanNode", len)?;
         if !self.table_name.is_empty() {
             struct_ser.serialize_field("tableName", &self.table_name)?;
@@ -3111,6 +3141,9 @@ impl serde::Serialize for CustomTableScanNode {
         if !self.custom_table_data.is_empty() {
             struct_ser.serialize_field("customTableData", pbjson::private::base64::encode(&self.custom_table_data).as_str())?;
         }
+        if self.by_name {
+            struct_ser.serialize_field("byName", &self.by_name)?;
+        }
         struct_ser.end()
     }
 }
@@ -3128,6 +3161,8 @@ impl<'de> serde::Deserialize<'de> for CustomTableScanNode {
             "filters",
             "custom_table_data",
             "customTableData",
+            "by_name",
+            "byName",
         ];
 
         #[allow(clippy::enum_variant_names)]
@@ -3137,6 +3172,7 @@ impl<'de> serde::Deserialize<'de> for CustomTableScanNode {
             Schema,
             Filters,
             CustomTableData,
+            ByName,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -3163,6 +3199,7 @@ impl<'de> serde::Deserialize<'de> for CustomTableScanNode {
                             "schema" => Ok(GeneratedField::Schema),
                             "filters" => Ok(GeneratedField::Filters),
                             "customTableData" | "custom_table_data" => Ok(GeneratedField::CustomTableData),
+                            "byName" | "by_name" => Ok(GeneratedField::ByName),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -3187,6 +3224,7 @@ impl<'de> serde::Deserialize<'de> for CustomTableScanNode {
                 let mut schema__ = None;
                 let mut filters__ = None;
                 let mut custom_table_data__ = None;
+                let mut by_name__ = None;
                 while let Some(k) = map.next_key()? {
                     match k {
                         GeneratedField::TableName => {
@@ -3221,6 +3259,12 @@ impl<'de> serde::Deserialize<'de> for CustomTableScanNode {
                                 Some(map.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
                             ;
                         }
+                        GeneratedField::ByName => {
+                            if by_name__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("byName"));
+                            }
+                            by_name__ = Some(map.next_value()?);
+                        }
                     }
                 }
                 Ok(CustomTableScanNode {
@@ -3229,6 +3273,7 @@ impl<'de> serde::Deserialize<'de> for CustomTableScanNode {
                     schema: schema__,
                     filters: filters__.unwrap_or_default(),
                     custom_table_data: custom_table_data__.unwrap_or_default(),
+                    by_name: by_name__.unwrap_or_default(),
                 })
             }
         }
@@ -6484,6 +6529,98 @@ impl<'de> serde::Deserialize<'de> for JoinType {
         deserializer.deserialize_any(GeneratedVisitor)
     }
 }
+impl serde::Serialize for JsonFormat {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.file_sort_order.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("datafusion.JsonFormat", len)?;
+        if !self.file_sort_order.is_empty() {
+            struct_ser.serialize_field("fileSortOrder", &self.file_sort_order)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for JsonFormat {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "file_sort_order",
+            "fileSortOrder",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            FileSortOrder,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "fileSortOrder" | "file_sort_order" => Ok(GeneratedField::FileSortOrder),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = JsonFormat;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct datafusion.JsonFormat")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<JsonFormat, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut file_sort_order__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::FileSortOrder => {
+                            if file_sort_order__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("fileSortOrder"));
+                            }
+                            file_sort_order__ = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(JsonFormat {
+                    file_sort_order: file_sort_order__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("datafusion.JsonFormat", FIELDS, GeneratedVisitor)
+    }
+}
 impl serde::Serialize for LikeNode {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
@@ -6883,6 +7020,9 @@ impl serde::Serialize for ListingTableScanNode {
         if self.target_partitions != 0 {
             len += 1;
         }
+        if !self.partition_regex.is_empty() {
+            len += 1;
+        }
         if self.file_format_type.is_some() {
             len += 1;
         }
@@ -6914,6 +7054,9 @@ impl serde::Serialize for ListingTableScanNode {
         if self.target_partitions != 0 {
             struct_ser.serialize_field("targetPartitions", &self.target_partitions)?;
         }
+        if !self.partition_regex.is_empty() {
+            struct_ser.serialize_field("partitionRegex", &self.partition_regex)?;
+        }
         if let Some(v) = self.file_format_type.as_ref() {
             match v {
                 listing_table_scan_node::FileFormatType::Csv(v) => {
@@ -6925,6 +7068,9 @@ impl serde::Serialize for ListingTableScanNode {
                 listing_table_scan_node::FileFormatType::Avro(v) => {
                     struct_ser.serialize_field("avro", v)?;
                 }
+                listing_table_scan_node::FileFormatType::Json(v) => {
+                    struct_ser.serialize_field("json", v)?;
+                }
             }
         }
         struct_ser.end()
@@ -6951,9 +7097,12 @@ impl<'de> serde::Deserialize<'de> for ListingTableScanNode {
             "collectStat",
             "target_partitions",
             "targetPartitions",
+            "partition_regex",
+            "partitionRegex",
             "csv",
             "parquet",
             "avro",
+            "json",
         ];
 
         #[allow(clippy::enum_variant_names)]
@@ -6967,9 +7116,11 @@ impl<'de> serde::Deserialize<'de> for ListingTableScanNode {
             TablePartitionCols,
             CollectStat,
             TargetPartitions,
+            PartitionRegex,
             Csv,
             Parquet,
             Avro,
+            Json,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -7000,9 +7151,11 @@ impl<'de> serde::Deserialize<'de> for ListingTableScanNode {
                             "tablePartitionCols" | "table_partition_cols" => Ok(GeneratedField::TablePartitionCols),
                             "collectStat" | "collect_stat" => Ok(GeneratedField::CollectStat),
                             "targetPartitions" | "target_partitions" => Ok(GeneratedField::TargetPartitions),
+                            "partitionRegex" | "partition_regex" => Ok(GeneratedField::PartitionRegex),
                             "csv" => Ok(GeneratedField::Csv),
                             "parquet" => Ok(GeneratedField::Parquet),
                             "avro" => Ok(GeneratedField::Avro),
+                            "json" => Ok(GeneratedField::Json),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -7031,6 +7184,7 @@ impl<'de> serde::Deserialize<'de> for ListingTableScanNode {
                 let mut table_partition_cols__ = None;
                 let mut collect_stat__ = None;
                 let mut target_partitions__ = None;
+                let mut partition_regex__ = None;
                 let mut file_format_type__ = None;
                 while let Some(k) = map.next_key()? {
                     match k {
@@ -7090,6 +7244,12 @@ impl<'de> serde::Deserialize<'de> for ListingTableScanNode {
                                 Some(map.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
                             ;
                         }
+                        GeneratedField::PartitionRegex => {
+                            if partition_regex__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("partitionRegex"));
+                            }
+                            partition_regex__ = Some(map.next_value()?);
+                        }
                         GeneratedField::Csv => {
                             if file_format_type__.is_some() {
                                 return Err(serde::de::Error::duplicate_field("csv"));
@@ -7109,6 +7269,13 @@ impl<'de> serde::Deserialize<'de> for ListingTableScanNode {
                                 return Err(serde::de::Error::duplicate_field("avro"));
                             }
                             file_format_type__ = map.next_value::<::std::option::Option<_>>()?.map(listing_table_scan_node::FileFormatType::Avro)
+;
+                        }
+                        GeneratedField::Json => {
+                            if file_format_type__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("json"));
+                            }
+                            file_format_type__ = map.next_value::<::std::option::Option<_>>()?.map(listing_table_scan_node::FileFormatType::Json)
 ;
                         }
                     }
@@ -7123,6 +7290,7 @@ impl<'de> serde::Deserialize<'de> for ListingTableScanNode {
                     table_partition_cols: table_partition_cols__.unwrap_or_default(),
                     collect_stat: collect_stat__.unwrap_or_default(),
                     target_partitions: target_partitions__.unwrap_or_default(),
+                    partition_regex: partition_regex__.unwrap_or_default(),
                     file_format_type: file_format_type__,
                 })
             }
@@ -9625,6 +9793,22 @@ impl serde::Serialize for ScalarFunction {
             Self::CurrentDate => "CurrentDate",
             Self::CurrentTime => "CurrentTime",
             Self::Uuid => "Uuid",
+            Self::XxHash64 => "XxHash64",
+            Self::Cbrt => "Cbrt",
+            Self::Degrees => "Degrees",
+            Self::Radians => "Radians",
+            Self::Pi => "Pi",
+            Self::Factorial => "Factorial",
+            Self::Gcd => "Gcd",
+            Self::Lcm => "Lcm",
+            Self::RegexpLike => "RegexpLike",
+            Self::Greatest => "Greatest",
+            Self::Least => "Least",
+            Self::ToChar => "ToChar",
+            Self::ToDate => "ToDate",
+            Self::Version => "Version",
+            Self::CurrentCatalog => "CurrentCatalog",
+            Self::CurrentSchema => "CurrentSchema",
         };
         serializer.serialize_str(variant)
     }
@@ -9709,6 +9893,22 @@ impl<'de> serde::Deserialize<'de> for ScalarFunction {
             "CurrentDate",
             "CurrentTime",
             "Uuid",
+            "XxHash64",
+            "Cbrt",
+            "Degrees",
+            "Radians",
+            "Pi",
+            "Factorial",
+            "Gcd",
+            "Lcm",
+            "RegexpLike",
+            "Greatest",
+            "Least",
+            "ToChar",
+            "ToDate",
+            "Version",
+            "CurrentCatalog",
+            "CurrentSchema",
         ];
 
         struct GeneratedVisitor;
@@ -9824,6 +10024,22 @@ impl<'de> serde::Deserialize<'de> for ScalarFunction {
                     "CurrentDate" => Ok(ScalarFunction::CurrentDate),
                     "CurrentTime" => Ok(ScalarFunction::CurrentTime),
                     "Uuid" => Ok(ScalarFunction::Uuid),
+                    "XxHash64" => Ok(ScalarFunction::XxHash64),
+                    "Cbrt" => Ok(ScalarFunction::Cbrt),
+                    "Degrees" => Ok(ScalarFunction::Degrees),
+                    "Radians" => Ok(ScalarFunction::Radians),
+                    "Pi" => Ok(ScalarFunction::Pi),
+                    "Factorial" => Ok(ScalarFunction::Factorial),
+                    "Gcd" => Ok(ScalarFunction::Gcd),
+                    "Lcm" => Ok(ScalarFunction::Lcm),
+                    "RegexpLike" => Ok(ScalarFunction::RegexpLike),
+                    "Greatest" => Ok(ScalarFunction::Greatest),
+                    "Least" => Ok(ScalarFunction::Least),
+                    "ToChar" => Ok(ScalarFunction::ToChar),
+                    "ToDate" => Ok(ScalarFunction::ToDate),
+                    "Version" => Ok(ScalarFunction::Version),
+                    "CurrentCatalog" => Ok(ScalarFunction::CurrentCatalog),
+                    "CurrentSchema" => Ok(ScalarFunction::CurrentSchema),
                     _ => Err(serde::de::Error::unknown_variant(value, FIELDS)),
                 }
             }
@@ -113,6 +113,11 @@ pub struct ParquetFormat {
 pub struct AvroFormat {
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct JsonFormat {
+    #[prost(message, repeated, tag="1")]
+    pub file_sort_order: ::prost::alloc::vec::Vec<LogicalExprNode>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ListingTableScanNode {
     #[prost(string, tag="1")]
     pub table_name: ::prost::alloc::string::String,
@@ -132,7 +137,9 @@ pub struct ListingTableScanNode {
     pub collect_stat: bool,
     #[prost(uint32, tag="9")]
     pub target_partitions: u32,
-    #[prost(oneof="listing_table_scan_node::FileFormatType", tags="10, 11, 12")]
+    #[prost(string, tag="14")]
+    pub partition_regex: ::prost::alloc::string::String,
+    #[prost(oneof="listing_table_scan_node::FileFormatType", tags="10, 11, 12, 13")]
     pub file_format_type: ::core::option::Option<listing_table_scan_node::FileFormatType>,
 }
 /// Nested message and enum types in `ListingTableScanNode`.
@@ -145,6 +152,8 @@ pub mod listing_table_scan_node {
         Parquet(super::ParquetFormat),
         #[prost(message, tag="12")]
         Avro(super::AvroFormat),
+        #[prost(message, tag="13")]
+        Json(super::JsonFormat),
     }
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -173,6 +182,10 @@ pub struct CustomTableScanNode {
     pub filters: ::prost::alloc::vec::Vec<LogicalExprNode>,
     #[prost(bytes="vec", tag="5")]
     pub custom_table_data: ::prost::alloc::vec::Vec<u8>,
+    /// If true, the table is not described by custom_table_data but is
+    /// resolved by table_name in the catalog of the deserializing context
+    #[prost(bool, tag="6")]
+    pub by_name: bool,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ProjectionNode {
@@ -1230,6 +1243,22 @@ pub enum ScalarFunction {
     CurrentDate = 70,
     CurrentTime = 71,
     Uuid = 72,
+    XxHash64 = 73,
+    Cbrt = 74,
+    Degrees = 75,
+    Radians = 76,
+    Pi = 77,
+    Factorial = 78,
+    Gcd = 79,
+    Lcm = 80,
+    RegexpLike = 81,
+    Greatest = 82,
+    Least = 83,
+    ToChar = 84,
+    ToDate = 85,
+    Version = 86,
+    CurrentCatalog = 87,
+    CurrentSchema = 88,
 }
 impl ScalarFunction {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -1311,6 +1340,22 @@ impl ScalarFunction {
             ScalarFunction::CurrentDate => "CurrentDate",
             ScalarFunction::CurrentTime => "CurrentTime",
             ScalarFunction::Uuid => "Uuid",
+            ScalarFunction::XxHash64 => "XxHash64",
+            ScalarFunction::Cbrt => "Cbrt",
+            ScalarFunction::Degrees => "Degrees",
+            ScalarFunction::Radians => "Radians",
+            ScalarFunction::Pi => "Pi",
+            ScalarFunction::Factorial => "Factorial",
+            ScalarFunction::Gcd => "Gcd",
+            ScalarFunction::Lcm => "Lcm",
+            ScalarFunction::RegexpLike => "RegexpLike",
+            ScalarFunction::Greatest => "Greatest",
+            ScalarFunction::Least => "Least",
+            ScalarFunction::ToChar => "ToChar",
+            ScalarFunction::ToDate => "ToDate",
+            ScalarFunction::Version => "Version",
+            ScalarFunction::CurrentCatalog => "CurrentCatalog",
+            ScalarFunction::CurrentSchema => "CurrentSchema",
         }
     }
 }
@@ -1336,6 +1381,15 @@ pub enum AggregateFunction {
     ApproxPercentileContWithWeight = 16,
     Grouping = 17,
     Median = 18,
+    FirstValue = 19,
+    LastValue = 20,
+    StringAgg = 21,
+    Mode = 22,
+    BitAnd = 23,
+    BitOr = 24,
+    BitXor = 25,
+    BoolAnd = 26,
+    BoolOr = 27,
 }
 impl AggregateFunction {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -1363,6 +1417,15 @@ impl AggregateFunction {
             AggregateFunction::ApproxPercentileContWithWeight => "APPROX_PERCENTILE_CONT_WITH_WEIGHT",
             AggregateFunction::Grouping => "GROUPING",
             AggregateFunction::Median => "MEDIAN",
+            AggregateFunction::FirstValue => "FIRST_VALUE",
+            AggregateFunction::LastValue => "LAST_VALUE",
+            AggregateFunction::StringAgg => "STRING_AGG",
+            AggregateFunction::Mode => "MODE",
+            AggregateFunction::BitAnd => "BIT_AND",
+            AggregateFunction::BitOr => "BIT_OR",
+            AggregateFunction::BitXor => "BIT_XOR",
+            AggregateFunction::BoolAnd => "BOOL_AND",
+            AggregateFunction::BoolOr => "BOOL_OR",
         }
     }
 }
@@ -38,7 +38,12 @@ impl From<from_proto::Error> for DataFusionError {
 
 impl From<to_proto::Error> for DataFusionError {
     fn from(e: to_proto::Error) -> Self {
-        DataFusionError::Plan(e.to_string())
+        match e {
+            to_proto::Error::NotImplemented(desc) => {
+                DataFusionError::NotImplemented(desc)
+            }
+            e => DataFusionError::Plan(e.to_string()),
+        }
     }
 }
 
@@ -52,16 +57,24 @@ mod roundtrip_tests {
     };
     use crate::logical_plan::LogicalExtensionCodec;
     use arrow::datatypes::{Schema, SchemaRef};
+    use arrow::record_batch::RecordBatch;
     use arrow::{
-        array::ArrayRef,
+        array::{ArrayRef, Int64Array},
         datatypes::{
             DataType, Field, IntervalDayTimeType, IntervalMonthDayNanoType, IntervalUnit,
             TimeUnit, UnionMode,
         },
     };
     use datafusion::datasource::datasource::TableProviderFactory;
-    use datafusion::datasource::TableProvider;
+    use datafusion::datasource::file_format::json::JsonFormat;
+    use datafusion::datasource::listing::{
+        ListingOptions, ListingTable, ListingTableConfig, ListingTableUrl,
+    };
+    use datafusion::datasource::{
+        provider_as_source, source_as_provider, MemTable, TableProvider,
+    };
     use datafusion::execution::runtime_env::{RuntimeConfig, RuntimeEnv};
+    use datafusion::physical_plan::collect;
     use datafusion::physical_plan::functions::make_scalar_function;
     use datafusion::prelude::{
         create_udf, CsvReadOptions, SessionConfig, SessionContext,
@@ -74,7 +87,7 @@ mod roundtrip_tests {
     use datafusion_expr::{
         col, lit, Accumulator, AggregateFunction, AggregateState,
         BuiltinScalarFunction::{Sqrt, Substr},
-        Expr, LogicalPlan, Operator, Volatility,
+        Expr, LogicalPlan, LogicalPlanBuilder, Operator, Volatility,
     };
     use prost::Message;
     use std::any::Any;
@@ -82,6 +95,7 @@ mod roundtrip_tests {
     use std::fmt;
     use std::fmt::Debug;
     use std::fmt::Formatter;
+    use std::hash::{Hash, Hasher};
     use std::sync::Arc;
 
     #[cfg(feature = "json")]
@@ -309,6 +323,354 @@ mod roundtrip_tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn roundtrip_json_file_sort_order() -> Result<(), DataFusionError> {
+        let ctx = SessionContext::new();
+        let schema = Arc::new(Schema::new(vec![Field::new("id", DataType::Int64, true)]));
+        let format =
+            JsonFormat::default().with_file_sort_order(vec![col("id").sort(false, true)]);
+        let config = ListingTableConfig::new(ListingTableUrl::parse("file:///tmp/t")?)
+            .with_listing_options(ListingOptions::new(Arc::new(format)))
+            .with_schema(schema);
+        let source = provider_as_source(Arc::new(ListingTable::try_new(config)?));
+        let plan = LogicalPlanBuilder::scan("t", source, None)?.build()?;
+
+        let bytes = logical_plan_to_bytes(&plan)?;
+        let logical_round_trip = logical_plan_from_bytes(&bytes, &ctx)?;
+        assert_eq!(format!("{:?}", plan), format!("{:?}", logical_round_trip));
+
+        let scan = match logical_round_trip {
+            LogicalPlan::TableScan(scan) => scan,
+            plan => panic!("expected a table scan, got {:?}", plan),
+        };
+        let provider = source_as_provider(&scan.source)?;
+        let listing_table = provider.as_any().downcast_ref::<ListingTable>().unwrap();
+        let format = listing_table.options().format.as_any();
+        let format = format.downcast_ref::<JsonFormat>().unwrap();
+        assert_eq!(format.file_sort_order(), &[col("id").sort(false, true)]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn roundtrip_listing_partition_regex() -> Result<(), DataFusionError> {
+        let ctx = SessionContext::new();
+        let schema = Arc::new(Schema::new(vec![Field::new("id", DataType::Int64, true)]));
+        let regex = regex::Regex::new(r"events_(?P<day>.*)\.json").unwrap();
+        let options = ListingOptions::new(Arc::new(JsonFormat::default()))
+            .with_table_partition_cols(vec!["day".to_string()])
+            .with_partition_regex(Some(regex));
+        let config = ListingTableConfig::new(ListingTableUrl::parse("file:///tmp/t")?)
+            .with_listing_options(options)
+            .with_schema(schema);
+        let source = provider_as_source(Arc::new(ListingTable::try_new(config)?));
+        let plan = LogicalPlanBuilder::scan("t", source, None)?.build()?;
+
+        let bytes = logical_plan_to_bytes(&plan)?;
+        let logical_round_trip = logical_plan_from_bytes(&bytes, &ctx)?;
+        assert_eq!(format!("{:?}", plan), format!("{:?}", logical_round_trip));
+
+        let scan = match logical_round_trip {
+            LogicalPlan::TableScan(scan) => scan,
+            plan => panic!("expected a table scan, got {:?}", plan),
+        };
+        let provider = source_as_provider(&scan.source)?;
+        let listing_table = provider.as_any().downcast_ref::<ListingTable>().unwrap();
+        let regex = listing_table.options().partition_regex().unwrap();
+        assert_eq!(regex.as_str(), r"events_(?P<day>.*)\.json");
+        Ok(())
+    }
+
+    /// Registers empty in-memory tables with the schemas of the TPC-H tables
+    /// used by [`TPCH_QUERIES`]
+    fn register_tpch_tables(ctx: &SessionContext) -> Result<(), DataFusionError> {
+        let decimal = || DataType::Decimal128(15, 2);
+        let tables = vec![
+            (
+                "lineitem",
+                vec![
+                    Field::new("l_orderkey", DataType::Int64, false),
+                    Field::new("l_partkey", DataType::Int64, false),
+                    Field::new("l_suppkey", DataType::Int64, false),
+                    Field::new("l_linenumber", DataType::Int32, false),
+                    Field::new("l_quantity", decimal(), false),
+                    Field::new("l_extendedprice", decimal(), false),
+                    Field::new("l_discount", decimal(), false),
+                    Field::new("l_tax", decimal(), false),
+                    Field::new("l_returnflag", DataType::Utf8, false),
+                    Field::new("l_linestatus", DataType::Utf8, false),
+                    Field::new("l_shipdate", DataType::Date32, false),
+                    Field::new("l_commitdate", DataType::Date32, false),
+                    Field::new("l_receiptdate", DataType::Date32, false),
+                    Field::new("l_shipinstruct", DataType::Utf8, false),
+                    Field::new("l_shipmode", DataType::Utf8, false),
+                    Field::new("l_comment", DataType::Utf8, false),
+                ],
+            ),
+            (
+                "orders",
+                vec![
+                    Field::new("o_orderkey", DataType::Int64, false),
+                    Field::new("o_custkey", DataType::Int64, false),
+                    Field::new("o_orderstatus", DataType::Utf8, false),
+                    Field::new("o_totalprice", decimal(), false),
+                    Field::new("o_orderdate", DataType::Date32, false),
+                    Field::new("o_orderpriority", DataType::Utf8, false),
+                    Field::new("o_clerk", DataType::Utf8, false),
+                    Field::new("o_shippriority", DataType::Int32, false),
+                    Field::new("o_comment", DataType::Utf8, false),
+                ],
+            ),
+            (
+                "customer",
+                vec![
+                    Field::new("c_custkey", DataType::Int64, false),
+                    Field::new("c_name", DataType::Utf8, false),
+                    Field::new("c_address", DataType::Utf8, false),
+                    Field::new("c_nationkey", DataType::Int64, false),
+                    Field::new("c_phone", DataType::Utf8, false),
+                    Field::new("c_acctbal", decimal(), false),
+                    Field::new("c_mktsegment", DataType::Utf8, false),
+                    Field::new("c_comment", DataType::Utf8, false),
+                ],
+            ),
+            (
+                "part",
+                vec![
+                    Field::new("p_partkey", DataType::Int64, false),
+                    Field::new("p_name", DataType::Utf8, false),
+                    Field::new("p_mfgr", DataType::Utf8, false),
+                    Field::new("p_brand", DataType::Utf8, false),
+                    Field::new("p_type", DataType::Utf8, false),
+                    Field::new("p_size", DataType::Int32, false),
+                    Field::new("p_container", DataType::Utf8, false),
+                    Field::new("p_retailprice", decimal(), false),
+                    Field::new("p_comment", DataType::Utf8, false),
+                ],
+            ),
+            (
+                "supplier",
+                vec![
+                    Field::new("s_suppkey", DataType::Int64, false),
+                    Field::new("s_name", DataType::Utf8, false),
+                    Field::new("s_address", DataType::Utf8, false),
+                    Field::new("s_nationkey", DataType::Int64, false),
+                    Field::new("s_phone", DataType::Utf8, false),
+                    Field::new("s_acctbal", decimal(), false),
+                    Field::new("s_comment", DataType::Utf8, false),
+                ],
+            ),
+            (
+                "nation",
+                vec![
+                    Field::new("n_nationkey", DataType::Int64, false),
+                    Field::new("n_name", DataType::Utf8, false),
+                    Field::new("n_regionkey", DataType::Int64, false),
+                    Field::new("n_comment", DataType::Utf8, false),
+                ],
+            ),
+            (
+                "region",
+                vec![
+                    Field::new("r_regionkey", DataType::Int64, false),
+                    Field::new("r_name", DataType::Utf8, false),
+                    Field::new("r_comment", DataType::Utf8, false),
+                ],
+            ),
+        ];
+
+        for (name, fields) in tables {
+            let table = MemTable::try_new(Arc::new(Schema::new(fields)), vec![vec![]])?;
+            ctx.register_table(name, Arc::new(table))?;
+        }
+        Ok(())
+    }
+
+    /// TPC-H queries (1, 3, 5, 6, 10, 12, 14 and 19) that only use
+    /// operators supported by the serialization
+    const TPCH_QUERIES: &[&str] = &[
+        "select l_returnflag, l_linestatus, sum(l_quantity) as sum_qty,
+            sum(l_extendedprice) as sum_base_price,
+            sum(l_extendedprice * (1 - l_discount)) as sum_disc_price,
+            sum(l_extendedprice * (1 - l_discount) * (1 + l_tax)) as sum_charge,
+            avg(l_quantity) as avg_qty, avg(l_extendedprice) as avg_price,
+            avg(l_discount) as avg_disc, count(*) as count_order
+        from lineitem
+        where l_shipdate <= date '1998-12-01' - interval '90' day
+        group by l_returnflag, l_linestatus
+        order by l_returnflag, l_linestatus",
+        "select l_orderkey, sum(l_extendedprice * (1 - l_discount)) as revenue,
+            o_orderdate, o_shippriority
+        from customer, orders, lineitem
+        where c_mktsegment = 'BUILDING' and c_custkey = o_custkey
+            and l_orderkey = o_orderkey and o_orderdate < date '1995-03-15'
+            and l_shipdate > date '1995-03-15'
+        group by l_orderkey, o_orderdate, o_shippriority
+        order by revenue desc, o_orderdate
+        limit 10",
+        "select n_name, sum(l_extendedprice * (1 - l_discount)) as revenue
+        from customer, orders, lineitem, supplier, nation, region
+        where c_custkey = o_custkey and l_orderkey = o_orderkey
+            and l_suppkey = s_suppkey and c_nationkey = s_nationkey
+            and s_nationkey = n_nationkey and n_regionkey = r_regionkey
+            and r_name = 'ASIA' and o_orderdate >= date '1994-01-01'
+            and o_orderdate < date '1995-01-01'
+        group by n_name
+        order by revenue desc",
+        "select sum(l_extendedprice * l_discount) as revenue
+        from lineitem
+        where l_shipdate >= date '1994-01-01' and l_shipdate < date '1995-01-01'
+            and l_discount between 0.06 - 0.01 and 0.06 + 0.01
+            and l_quantity < 24",
+        "select c_custkey, c_name, sum(l_extendedprice * (1 - l_discount)) as revenue,
+            c_acctbal, n_name, c_address, c_phone, c_comment
+        from customer
+            join orders on c_custkey = o_custkey
+            join lineitem on l_orderkey = o_orderkey
+            join nation on c_nationkey = n_nationkey
+        where o_orderdate >= date '1993-10-01' and o_orderdate < date '1994-01-01'
+            and l_returnflag = 'R'
+        group by c_custkey, c_name, c_acctbal, c_phone, n_name, c_address, c_comment
+        order by revenue desc
+        limit 20",
+        "select l_shipmode,
+            sum(case when o_orderpriority = '1-URGENT' or o_orderpriority = '2-HIGH'
+                then 1 else 0 end) as high_line_count,
+            sum(case when o_orderpriority <> '1-URGENT' and o_orderpriority <> '2-HIGH'
+                then 1 else 0 end) as low_line_count
+        from orders, lineitem
+        where o_orderkey = l_orderkey and l_shipmode in ('MAIL', 'SHIP')
+            and l_commitdate < l_receiptdate and l_shipdate < l_commitdate
+            and l_receiptdate >= date '1994-01-01' and l_receiptdate < date '1995-01-01'
+        group by l_shipmode
+        order by l_shipmode",
+        "select 100.00 * sum(case when p_type like 'PROMO%'
+                then l_extendedprice * (1 - l_discount) else 0 end)
+            / sum(l_extendedprice * (1 - l_discount)) as promo_revenue
+        from lineitem, part
+        where l_partkey = p_partkey and l_shipdate >= date '1995-09-01'
+            and l_shipdate < date '1995-10-01'",
+        "select sum(l_extendedprice * (1 - l_discount)) as revenue
+        from lineitem, part
+        where (p_partkey = l_partkey and p_brand = 'Brand#12'
+                and p_container in ('SM CASE', 'SM BOX', 'SM PACK', 'SM PKG')
+                and l_quantity >= 1 and l_quantity <= 1 + 10
+                and p_size between 1 and 5 and l_shipmode in ('AIR', 'AIR REG')
+                and l_shipinstruct = 'DELIVER IN PERSON')
+            or (p_partkey = l_partkey and p_brand = 'Brand#23'
+                and p_container in ('MED BAG', 'MED BOX', 'MED PKG', 'MED PACK')
+                and l_quantity >= 10 and l_quantity <= 10 + 10
+                and p_size between 1 and 10 and l_shipmode in ('AIR', 'AIR REG')
+                and l_shipinstruct = 'DELIVER IN PERSON')",
+    ];
+
+    #[tokio::test]
+    async fn roundtrip_tpch_queries() -> Result<(), DataFusionError> {
+        let ctx = SessionContext::new();
+        register_tpch_tables(&ctx)?;
+
+        for query in TPCH_QUERIES {
+            let df = ctx.sql(query).await?;
+            for plan in [df.to_unoptimized_plan(), df.to_logical_plan()?] {
+                let bytes = logical_plan_to_bytes(&plan)?;
+                let logical_round_trip = logical_plan_from_bytes(&bytes, &ctx)?;
+                assert_eq!(
+                    format!("{:?}", plan),
+                    format!("{:?}", logical_round_trip),
+                    "round trip of query {}",
+                    query
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    fn context_with_table(schema: SchemaRef, values: Vec<i64>) -> SessionContext {
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![Arc::new(Int64Array::from(values)) as ArrayRef],
+        )
+        .unwrap();
+        let table = MemTable::try_new(schema, vec![vec![batch]]).unwrap();
+
+        let ctx = SessionContext::new();
+        ctx.register_table("t", Arc::new(table)).unwrap();
+        ctx
+    }
+
+    #[tokio::test]
+    async fn roundtrip_plan_across_contexts() -> Result<(), DataFusionError> {
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int64, false)]));
+        // two contexts with matching catalogs, as two processes would have
+        let ctx1 = context_with_table(schema.clone(), vec![1, 2, 3, 4]);
+        let ctx2 = context_with_table(schema, vec![1, 2, 3, 4]);
+
+        let plan = ctx1
+            .sql("SELECT a % 2 AS k, sum(a) AS s FROM t WHERE a > 1 GROUP BY a % 2 ORDER BY k")
+            .await?
+            .to_logical_plan()?;
+        let bytes = logical_plan_to_bytes(&plan)?;
+        let logical_round_trip = logical_plan_from_bytes(&bytes, &ctx2)?;
+        assert_eq!(format!("{:?}", plan), format!("{:?}", logical_round_trip));
+
+        let physical_plan = ctx2.create_physical_plan(&logical_round_trip).await?;
+        let batches = collect(physical_plan, ctx2.task_ctx()).await?;
+        let expected = vec![
+            "+---+---+",
+            "| k | s |",
+            "+---+---+",
+            "| 0 | 6 |",
+            "| 1 | 3 |",
+            "+---+---+",
+        ];
+        datafusion::assert_batches_eq!(expected, &batches);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn roundtrip_plan_table_schema_mismatch() -> Result<(), DataFusionError> {
+        let ctx1 = context_with_table(
+            Arc::new(Schema::new(vec![Field::new("a", DataType::Int64, false)])),
+            vec![1],
+        );
+        let ctx2 = context_with_table(
+            Arc::new(Schema::new(vec![Field::new("b", DataType::Int64, false)])),
+            vec![1],
+        );
+
+        let plan = ctx1.table("t")?.to_logical_plan()?;
+        let bytes = logical_plan_to_bytes(&plan)?;
+        let err = logical_plan_from_bytes(&bytes, &ctx2).unwrap_err();
+        assert!(
+            err.to_string().contains("but the serialized plan expects"),
+            "unexpected error: {}",
+            err
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn unsupported_plan_errors() -> Result<(), DataFusionError> {
+        let ctx = SessionContext::new();
+        register_tpch_tables(&ctx)?;
+
+        let plan = ctx.create_logical_plan("DROP TABLE region")?;
+        let err = logical_plan_to_bytes(&plan).unwrap_err();
+        assert!(matches!(err, DataFusionError::NotImplemented(_)));
+        assert!(err.to_string().contains("DropTable"), "{}", err);
+
+        let plan = ctx.create_logical_plan(
+            "SELECT r_name FROM region WHERE r_regionkey IN (SELECT n_regionkey FROM nation)",
+        )?;
+        let err = logical_plan_to_bytes(&plan).unwrap_err();
+        assert!(matches!(err, DataFusionError::NotImplemented(_)));
+        assert!(err.to_string().contains("InSubquery"), "{}", err);
+
+        Ok(())
+    }
+
     pub mod proto {
         #[derive(Clone, PartialEq, ::prost::Message)]
         pub struct TopKPlanProto {
@@ -382,6 +744,19 @@ mod roundtrip_tests {
                 expr: exprs[0].clone(),
             })
         }
+
+        fn dyn_hash(&self, state: &mut dyn Hasher) {
+            let mut state = state;
+            self.k.hash(&mut state);
+            self.expr.hash(&mut state);
+        }
+
+        fn dyn_eq(&self, other: &dyn UserDefinedLogicalNode) -> bool {
+            match other.as_any().downcast_ref::<Self>() {
+                Some(other) => self.k == other.k && self.expr == other.expr,
+                None => false,
+            }
+        }
     }
 
     #[derive(Debug)]
@@ -1050,6 +1425,17 @@ mod roundtrip_tests {
         roundtrip_expr_test(test_expr, ctx);
     }
 
+    #[test]
+    fn roundtrip_try_cast() {
+        let test_expr = Expr::TryCast {
+            expr: Box::new(lit("not a bool")),
+            data_type: DataType::Boolean,
+        };
+
+        let ctx = SessionContext::new();
+        roundtrip_expr_test(test_expr, ctx);
+    }
+
     #[test]
     fn roundtrip_sort_expr() {
         let test_expr = Expr::Sort {
@@ -29,10 +29,12 @@ use arrow::datatypes::{Schema, SchemaRef};
 use datafusion::datasource::TableProvider;
 use datafusion::execution::FunctionRegistry;
 use datafusion::physical_plan::ExecutionPlan;
+use datafusion::sql::planner::ContextProvider;
 use datafusion::{
     datasource::{
         file_format::{
-            avro::AvroFormat, csv::CsvFormat, parquet::ParquetFormat, FileFormat,
+            avro::AvroFormat, csv::CsvFormat, json::JsonFormat, parquet::ParquetFormat,
+            FileFormat,
         },
         listing::{ListingOptions, ListingTable, ListingTableConfig, ListingTableUrl},
         view::ViewTable,
@@ -51,6 +53,7 @@ use datafusion_expr::{
 };
 use prost::bytes::BufMut;
 use prost::Message;
+use regex::Regex;
 use std::fmt::Debug;
 use std::sync::Arc;
 
@@ -416,28 +419,38 @@ impl AsLogicalPlan for LogicalPlanNode {
                     .map(|expr| parse_expr(expr, ctx))
                     .collect::<Result<Vec<_>, _>>()?;
 
-                let file_format: Arc<dyn FileFormat> =
-                    match scan.file_format_type.as_ref().ok_or_else(|| {
+                let file_format: Arc<dyn FileFormat> = match scan
+                    .file_format_type
+                    .as_ref()
+                    .ok_or_else(|| {
                         proto_error(format!(
                             "logical_plan::from_proto() Unsupported file format '{:?}'",
                             self
                         ))
                     })? {
-                        &FileFormatType::Parquet(protobuf::ParquetFormat {
-                            enable_pruning,
-                        }) => Arc::new(
-                            ParquetFormat::default().with_enable_pruning(enable_pruning),
-                        ),
-                        FileFormatType::Csv(protobuf::CsvFormat {
-                            has_header,
-                            delimiter,
-                        }) => Arc::new(
-                            CsvFormat::default()
-                                .with_has_header(*has_header)
-                                .with_delimiter(str_to_byte(delimiter)?),
-                        ),
-                        FileFormatType::Avro(..) => Arc::new(AvroFormat::default()),
-                    };
+                    &FileFormatType::Parquet(protobuf::ParquetFormat {
+                        enable_pruning,
+                    }) => Arc::new(
+                        ParquetFormat::default().with_enable_pruning(enable_pruning),
+                    ),
+                    FileFormatType::Csv(protobuf::CsvFormat {
+                        has_header,
+                        delimiter,
+                    }) => Arc::new(
+                        CsvFormat::default()
+                            .with_has_header(*has_header)
+                            .with_delimiter(str_to_byte(delimiter)?),
+                    ),
+                    FileFormatType::Avro(..) => Arc::new(AvroFormat::default()),
+                    FileFormatType::Json(json) => {
+                        let sort_order = json
+                            .file_sort_order
+                            .iter()
+                            .map(|expr| parse_expr(expr, ctx))
+                            .collect::<Result<Vec<_>, _>>()?;
+                        Arc::new(JsonFormat::default().with_file_sort_order(sort_order))
+                    }
+                };
 
                 let table_paths = &scan
                     .paths
@@ -445,14 +458,21 @@ impl AsLogicalPlan for LogicalPlanNode {
                     .map(ListingTableUrl::parse)
                     .collect::<Result<Vec<_>, _>>()?;
 
-                let options = ListingOptions {
-                    file_extension: scan.file_extension.clone(),
-                    format: file_format,
-                    table_partition_cols: scan.table_partition_cols.clone(),
-                    collect_stat: scan.collect_stat,
-                    target_partitions: scan.target_partitions as usize,
+                let partition_regex = if scan.partition_regex.is_empty() {
+                    None
+                } else {
+                    Some(Regex::new(&scan.partition_regex).map_err(|e| {
+                        proto_error(format!("Invalid partition regex: {}", e))
+                    })?)
                 };
 
+                let options = ListingOptions::new(file_format)
+                    .with_file_extension(scan.file_extension.clone())
+                    .with_table_partition_cols(scan.table_partition_cols.clone())
+                    .with_collect_stat(scan.collect_stat)
+                    .with_target_partitions(scan.target_partitions as usize)
+                    .with_partition_regex(partition_regex);
+
                 let config =
                     ListingTableConfig::new_with_multi_paths(table_paths.clone())
                         .with_listing_options(options)
@@ -486,15 +506,30 @@ impl AsLogicalPlan for LogicalPlanNode {
                     .iter()
                     .map(|expr| parse_expr(expr, ctx))
                     .collect::<Result<Vec<_>, _>>()?;
-                let provider = extension_codec.try_decode_table_provider(
-                    &scan.custom_table_data,
-                    schema,
-                    ctx,
-                )?;
+                let source = if scan.by_name {
+                    let source = ctx
+                        .state()
+                        .get_table_provider(scan.table_name.as_str().into())?;
+                    if source.schema() != schema {
+                        return Err(DataFusionError::Plan(format!(
+                            "Table {} has schema {:?}, but the serialized plan expects {:?}",
+                            scan.table_name,
+                            source.schema(),
+                            schema
+                        )));
+                    }
+                    source
+                } else {
+                    provider_as_source(extension_codec.try_decode_table_provider(
+                        &scan.custom_table_data,
+                        schema,
+                        ctx,
+                    )?)
+                };
 
                 LogicalPlanBuilder::scan_with_filters(
                     &scan.table_name,
-                    provider_as_source(provider),
+                    source,
                     projection,
                     filters,
                 )?
@@ -865,6 +900,14 @@ impl AsLogicalPlan for LogicalPlanNode {
                         })
                     } else if any.is::<AvroFormat>() {
                         FileFormatType::Avro(protobuf::AvroFormat {})
+                    } else if let Some(json) = any.downcast_ref::<JsonFormat>() {
+                        FileFormatType::Json(protobuf::JsonFormat {
+                            file_sort_order: json
+                                .file_sort_order()
+                                .iter()
+                                .map(|expr| expr.try_into())
+                                .collect::<Result<Vec<_>, _>>()?,
+                        })
                     } else {
                         return Err(proto_error(format!(
                             "Error converting file format, {:?} is invalid as a datafusion foramt.",
@@ -897,6 +940,11 @@ impl AsLogicalPlan for LogicalPlanNode {
                                     .options()
                                     .target_partitions
                                     as u32,
+                                partition_regex: listing_table
+                                    .options()
+                                    .partition_regex()
+                                    .map(|regex| regex.as_str().to_owned())
+                                    .unwrap_or_default(),
                             },
                         )),
                     })
@@ -922,15 +970,28 @@ impl AsLogicalPlan for LogicalPlanNode {
                     })
                 } else {
                     let mut bytes = vec![];
-                    extension_codec
+                    // tables the codec does not know how to encode are
+                    // referenced by name, and must be registered with the
+                    // deserializing context
+                    let by_name = match extension_codec
                         .try_encode_table_provider(provider, &mut bytes)
-                        .map_err(|e| context!("Error serializing custom table", e))?;
+                    {
+                        Ok(()) => false,
+                        Err(DataFusionError::NotImplemented(_)) => {
+                            bytes.clear();
+                            true
+                        }
+                        Err(e) => {
+                            return Err(context!("Error serializing custom table", e))
+                        }
+                    };
                     let scan = CustomScan(CustomTableScanNode {
                         table_name: table_name.clone(),
                         projection,
                         schema: Some(schema),
                         filters,
                         custom_table_data: bytes,
+                        by_name,
                     });
                     let node = LogicalPlanNode {
                         logical_plan_type: Some(scan),
@@ -1345,17 +1406,18 @@ impl AsLogicalPlan for LogicalPlanNode {
                     )),
                 })
             }
-            LogicalPlan::CreateMemoryTable(_) => Err(proto_error(
-                "LogicalPlan serde is not yet implemented for CreateMemoryTable",
+            LogicalPlan::CreateMemoryTable(_) => Err(DataFusionError::NotImplemented(
+                "LogicalPlan serde is not yet implemented for CreateMemoryTable"
+                    .to_string(),
             )),
-            LogicalPlan::DropTable(_) => Err(proto_error(
-                "LogicalPlan serde is not yet implemented for DropTable",
+            LogicalPlan::DropTable(_) => Err(DataFusionError::NotImplemented(
+                "LogicalPlan serde is not yet implemented for DropTable".to_string(),
             )),
-            LogicalPlan::DropView(_) => Err(proto_error(
-                "LogicalPlan serde is not yet implemented for DropView",
+            LogicalPlan::DropView(_) => Err(DataFusionError::NotImplemented(
+                "LogicalPlan serde is not yet implemented for DropView".to_string(),
             )),
-            LogicalPlan::SetVariable(_) => Err(proto_error(
-                "LogicalPlan serde is not yet implemented for DropView",
+            LogicalPlan::SetVariable(_) => Err(DataFusionError::NotImplemented(
+                "LogicalPlan serde is not yet implemented for SetVariable".to_string(),
             )),
         }
     }
@@ -61,6 +61,8 @@ pub enum Error {
     InvalidTimeUnit(TimeUnit),
 
     UnsupportedScalarFunction(BuiltinScalarFunction),
+
+    NotImplemented(String),
 }
 
 impl std::error::Error for Error {}
@@ -99,6 +101,9 @@ impl std::fmt::Display for Error {
             Self::UnsupportedScalarFunction(function) => {
                 write!(f, "Unsupported scalar function {:?}", function)
             }
+            Self::NotImplemented(desc) => {
+                write!(f, "Not implemented: {}", desc)
+            }
         }
     }
 }
@@ -361,6 +366,15 @@ impl From<&AggregateFunction> for protobuf::AggregateFunction {
             AggregateFunction::ApproxMedian => Self::ApproxMedian,
             AggregateFunction::Grouping => Self::Grouping,
             AggregateFunction::Median => Self::Median,
+            AggregateFunction::FirstValue => Self::FirstValue,
+            AggregateFunction::LastValue => Self::LastValue,
+            AggregateFunction::StringAgg => Self::StringAgg,
+            AggregateFunction::Mode => Self::Mode,
+            AggregateFunction::BitAnd => Self::BitAnd,
+            AggregateFunction::BitOr => Self::BitOr,
+            AggregateFunction::BitXor => Self::BitXor,
+            AggregateFunction::BoolAnd => Self::BoolAnd,
+            AggregateFunction::BoolOr => Self::BoolOr,
         }
     }
 }
@@ -623,6 +637,21 @@ impl TryFrom<&Expr> for protobuf::LogicalExprNode {
                     }
                     AggregateFunction::Grouping => protobuf::AggregateFunction::Grouping,
                     AggregateFunction::Median => protobuf::AggregateFunction::Median,
+                    AggregateFunction::FirstValue => {
+                        protobuf::AggregateFunction::FirstValue
+                    }
+                    AggregateFunction::LastValue => {
+                        protobuf::AggregateFunction::LastValue
+                    }
+                    AggregateFunction::StringAgg => {
+                        protobuf::AggregateFunction::StringAgg
+                    }
+                    AggregateFunction::Mode => protobuf::AggregateFunction::Mode,
+                    AggregateFunction::BitAnd => protobuf::AggregateFunction::BitAnd,
+                    AggregateFunction::BitOr => protobuf::AggregateFunction::BitOr,
+                    AggregateFunction::BitXor => protobuf::AggregateFunction::BitXor,
+                    AggregateFunction::BoolAnd => protobuf::AggregateFunction::BoolAnd,
+                    AggregateFunction::BoolOr => protobuf::AggregateFunction::BoolOr,
                 };
 
                 let aggregate_expr = protobuf::AggregateExprNode {
@@ -641,7 +670,7 @@ impl TryFrom<&Expr> for protobuf::LogicalExprNode {
                     expr_type: Some(ExprType::AggregateExpr(Box::new(aggregate_expr))),
                 }
             }
-            Expr::ScalarVariable(_, _) => return Err(Error::General("Proto serialization error: Scalar Variable not supported".to_string())),
+            Expr::ScalarVariable(_, _) => return Err(Error::NotImplemented("Proto serialization of Expr::ScalarVariable".to_string())),
             Expr::ScalarFunction { ref fun, ref args } => {
                 let fun: protobuf::ScalarFunction = fun.try_into()?;
                 let args: Vec<Self> = args
@@ -805,6 +834,15 @@ impl TryFrom<&Expr> for protobuf::LogicalExprNode {
                     expr_type: Some(ExprType::Cast(expr)),
                 }
             }
+            Expr::TryCast { expr, data_type } => {
+                let expr = Box::new(protobuf::TryCastNode {
+                    expr: Some(Box::new(expr.as_ref().try_into()?)),
+                    arrow_type: Some(data_type.try_into()?),
+                });
+                Self {
+                    expr_type: Some(ExprType::TryCast(expr)),
+                }
+            }
             Expr::Sort {
                 expr,
                 asc,
@@ -850,7 +888,15 @@ impl TryFrom<&Expr> for protobuf::LogicalExprNode {
             Expr::ScalarSubquery(_) | Expr::InSubquery { .. } | Expr::Exists { .. } => {
                 // we would need to add logical plan operators to datafusion.proto to support this
                 // see discussion in https://github.com/apache/arrow-datafusion/issues/2565
-                return Err(Error::General("Proto serialization error: Expr::ScalarSubquery(_) | Expr::InSubquery { .. } | Expr::Exists { .. } not supported".to_string()));
+                let variant = match expr {
+                    Expr::ScalarSubquery(_) => "ScalarSubquery",
+                    Expr::InSubquery { .. } => "InSubquery",
+                    _ => "Exists",
+                };
+                return Err(Error::NotImplemented(format!(
+                    "Proto serialization of Expr::{}",
+                    variant
+                )));
             }
             Expr::GetIndexedField(GetIndexedField { key, expr }) =>
                 Self {
@@ -894,8 +940,8 @@ impl TryFrom<&Expr> for protobuf::LogicalExprNode {
                 })),
             },
 
-            Expr::QualifiedWildcard { .. } | Expr::TryCast { .. } =>
-                return Err(Error::General("Proto serialization error: Expr::QualifiedWildcard { .. } | Expr::TryCast { .. } not supported".to_string())),
+            Expr::QualifiedWildcard { .. } =>
+                return Err(Error::NotImplemented("Proto serialization of Expr::QualifiedWildcard".to_string())),
         };
 
         Ok(expr_node)
@@ -1214,6 +1260,22 @@ impl TryFrom<&BuiltinScalarFunction> for protobuf::ScalarFunction {
             BuiltinScalarFunction::FromUnixtime => Self::FromUnixtime,
             BuiltinScalarFunction::Atan2 => Self::Atan2,
             BuiltinScalarFunction::ArrowTypeof => Self::ArrowTypeof,
+            BuiltinScalarFunction::XxHash64 => Self::XxHash64,
+            BuiltinScalarFunction::Cbrt => Self::Cbrt,
+            BuiltinScalarFunction::Degrees => Self::Degrees,
+            BuiltinScalarFunction::Radians => Self::Radians,
+            BuiltinScalarFunction::Pi => Self::Pi,
+            BuiltinScalarFunction::Factorial => Self::Factorial,
+            BuiltinScalarFunction::Gcd => Self::Gcd,
+            BuiltinScalarFunction::Lcm => Self::Lcm,
+            BuiltinScalarFunction::RegexpLike => Self::RegexpLike,
+            BuiltinScalarFunction::Greatest => Self::Greatest,
+            BuiltinScalarFunction::Least => Self::Least,
+            BuiltinScalarFunction::ToChar => Self::ToChar,
+            BuiltinScalarFunction::ToDate => Self::ToDate,
+            BuiltinScalarFunction::Version => Self::Version,
+            BuiltinScalarFunction::CurrentCatalog => Self::CurrentCatalog,
+            BuiltinScalarFunction::CurrentSchema => Self::CurrentSchema,
         };
 
         Ok(scalar_function)
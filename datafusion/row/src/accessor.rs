@@ -83,6 +83,37 @@ macro_rules! fn_add_idx {
     };
 }
 
+macro_rules! fn_checked_add_idx {
+    ($NATIVE: ident) => {
+        paste::item! {
+            /// add field at `idx` with `value`, leaving the field unchanged
+            /// and returning false if the sum overflows
+            pub fn [<checked_add_ $NATIVE>](&mut self, idx: usize, value: $NATIVE) -> bool {
+                if self.is_valid_at(idx) {
+                    match value.checked_add(self.[<get_ $NATIVE>](idx)) {
+                        Some(v) => self.[<set_ $NATIVE>](idx, v),
+                        None => return false,
+                    }
+                } else {
+                    self.set_non_null_at(idx);
+                    self.[<set_ $NATIVE>](idx, value);
+                }
+                true
+            }
+
+            /// add field at `idx` with `value`, wrapping around on overflow
+            pub fn [<wrapping_add_ $NATIVE>](&mut self, idx: usize, value: $NATIVE) {
+                if self.is_valid_at(idx) {
+                    self.[<set_ $NATIVE>](idx, value.wrapping_add(self.[<get_ $NATIVE>](idx)));
+                } else {
+                    self.set_non_null_at(idx);
+                    self.[<set_ $NATIVE>](idx, value);
+                }
+            }
+        }
+    };
+}
+
 macro_rules! fn_max_min_idx {
     ($NATIVE: ident, $OP: ident) => {
         paste::item! {
@@ -286,6 +317,9 @@ impl<'a> RowAccessor<'a> {
     fn_add_idx!(f32);
     fn_add_idx!(f64);
 
+    fn_checked_add_idx!(u64);
+    fn_checked_add_idx!(i64);
+
     fn_max_min_idx!(u8, max);
     fn_max_min_idx!(u16, max);
     fn_max_min_idx!(u32, max);
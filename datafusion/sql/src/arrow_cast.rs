@@ -0,0 +1,535 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! The `arrow_cast` function, casting to any arrow [`DataType`] given by its
+//! textual form, including those SQL `CAST` can not express, such as
+//! `Dictionary(Int32, Utf8)`
+
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use arrow::datatypes::{DataType, Field, IntervalUnit, TimeUnit, UnionMode};
+use datafusion_common::{DataFusionError, Result, ScalarValue};
+use datafusion_expr::expr::Cast;
+use datafusion_expr::Expr;
+
+/// Plans `arrow_cast(expr, 'type')` as a cast of `expr` to the type named
+/// by the string literal, see [`parse_data_type`]
+pub fn create_arrow_cast(mut args: Vec<Expr>) -> Result<Expr> {
+    if args.len() != 2 {
+        return Err(DataFusionError::Plan(format!(
+            "arrow_cast needs 2 arguments, {} provided",
+            args.len()
+        )));
+    }
+    let data_type = match args.pop() {
+        Some(Expr::Literal(ScalarValue::Utf8(Some(s)))) => parse_data_type(&s)?,
+        other => {
+            return Err(DataFusionError::Plan(format!(
+            "arrow_cast requires its second argument to be a constant string, got {:?}",
+            other
+        )))
+        }
+    };
+    let expr = args.pop().expect("checked above");
+    Ok(Expr::Cast(Cast::new(Box::new(expr), data_type)))
+}
+
+/// Parses a [`DataType`] from the form it is displayed in, such as
+/// `Int32`, `Timestamp(Nanosecond, Some("+00:00"))` or
+/// `List(Field { name: "item", data_type: Int64, nullable: true, dict_id: 0, dict_is_ordered: false, metadata: None })`,
+/// so that `parse_data_type(&data_type.to_string())` gives back `data_type`
+pub fn parse_data_type(s: &str) -> Result<DataType> {
+    let mut parser = Parser { input: s, rest: s };
+    let data_type = parser.data_type()?;
+    parser.skip_whitespace();
+    if !parser.rest.is_empty() {
+        return Err(parser.error("expected the end of the type"));
+    }
+    Ok(data_type)
+}
+
+/// A recursive descent parser of the `Debug` form of arrow types
+struct Parser<'a> {
+    input: &'a str,
+    /// The part of `input` not yet parsed
+    rest: &'a str,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, message: &str) -> DataFusionError {
+        DataFusionError::Plan(format!(
+            "Unsupported type '{}': {} at position {}",
+            self.input,
+            message,
+            self.input.len() - self.rest.len()
+        ))
+    }
+
+    fn skip_whitespace(&mut self) {
+        self.rest = self.rest.trim_start();
+    }
+
+    /// Consumes `token` if it comes next, returning whether it did
+    fn consume(&mut self, token: &str) -> bool {
+        self.skip_whitespace();
+        match self.rest.strip_prefix(token) {
+            Some(rest) => {
+                self.rest = rest;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn expect(&mut self, token: &str) -> Result<()> {
+        if self.consume(token) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected '{}'", token)))
+        }
+    }
+
+    /// Parses the next alphanumeric word, such as an identifier or a number
+    fn word(&mut self) -> Result<&'a str> {
+        self.skip_whitespace();
+        let len = self
+            .rest
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '-'))
+            .unwrap_or(self.rest.len());
+        if len == 0 {
+            return Err(self.error("expected a word"));
+        }
+        let (word, rest) = self.rest.split_at(len);
+        self.rest = rest;
+        Ok(word)
+    }
+
+    fn number<T: FromStr>(&mut self) -> Result<T> {
+        let word = self.word()?;
+        word.parse()
+            .map_err(|_| self.error(&format!("invalid number '{}'", word)))
+    }
+
+    fn boolean(&mut self) -> Result<bool> {
+        match self.word()? {
+            "true" => Ok(true),
+            "false" => Ok(false),
+            other => Err(self.error(&format!("invalid boolean '{}'", other))),
+        }
+    }
+
+    /// Parses a double quoted string with the escapes of `Debug`
+    fn string(&mut self) -> Result<String> {
+        self.expect("\"")?;
+        let rest = self.rest;
+        let mut result = String::new();
+        let mut chars = rest.char_indices();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.rest = &rest[i + 1..];
+                    return Ok(result);
+                }
+                '\\' => {
+                    let escaped = match chars.next().map(|(_, c)| c) {
+                        Some('n') => '\n',
+                        Some('r') => '\r',
+                        Some('t') => '\t',
+                        Some('0') => '\0',
+                        Some(c @ ('\\' | '"' | '\'')) => c,
+                        Some('u') => {
+                            let hex: String = chars
+                                .by_ref()
+                                .map(|(_, c)| c)
+                                .skip_while(|c| *c == '{')
+                                .take_while(|c| *c != '}')
+                                .collect();
+                            u32::from_str_radix(&hex, 16)
+                                .ok()
+                                .and_then(char::from_u32)
+                                .ok_or_else(|| self.error("invalid unicode escape"))?
+                        }
+                        _ => return Err(self.error("invalid escape in string")),
+                    };
+                    result.push(escaped);
+                }
+                c => result.push(c),
+            }
+        }
+        Err(self.error("unterminated string"))
+    }
+
+    /// Parses `items` separated by commas between `open` and `close`
+    fn list<T>(
+        &mut self,
+        open: &str,
+        close: &str,
+        mut item: impl FnMut(&mut Self) -> Result<T>,
+    ) -> Result<Vec<T>> {
+        self.expect(open)?;
+        let mut items = vec![];
+        if self.consume(close) {
+            return Ok(items);
+        }
+        loop {
+            items.push(item(self)?);
+            if self.consume(close) {
+                return Ok(items);
+            }
+            self.expect(",")?;
+        }
+    }
+
+    fn data_type(&mut self) -> Result<DataType> {
+        let data_type = match self.word()? {
+            "Null" => DataType::Null,
+            "Boolean" => DataType::Boolean,
+            "Int8" => DataType::Int8,
+            "Int16" => DataType::Int16,
+            "Int32" => DataType::Int32,
+            "Int64" => DataType::Int64,
+            "UInt8" => DataType::UInt8,
+            "UInt16" => DataType::UInt16,
+            "UInt32" => DataType::UInt32,
+            "UInt64" => DataType::UInt64,
+            "Float16" => DataType::Float16,
+            "Float32" => DataType::Float32,
+            "Float64" => DataType::Float64,
+            "Utf8" => DataType::Utf8,
+            "LargeUtf8" => DataType::LargeUtf8,
+            "Binary" => DataType::Binary,
+            "LargeBinary" => DataType::LargeBinary,
+            "Date32" => DataType::Date32,
+            "Date64" => DataType::Date64,
+            "Timestamp" => {
+                self.expect("(")?;
+                let unit = self.time_unit()?;
+                self.expect(",")?;
+                let timezone = match self.word()? {
+                    "None" => None,
+                    "Some" => {
+                        self.expect("(")?;
+                        let timezone = self.string()?;
+                        self.expect(")")?;
+                        Some(timezone)
+                    }
+                    other => {
+                        return Err(self.error(&format!("invalid time zone '{}'", other)))
+                    }
+                };
+                self.expect(")")?;
+                DataType::Timestamp(unit, timezone)
+            }
+            "Time32" => DataType::Time32(self.parenthesized(Self::time_unit)?),
+            "Time64" => DataType::Time64(self.parenthesized(Self::time_unit)?),
+            "Duration" => DataType::Duration(self.parenthesized(Self::time_unit)?),
+            "Interval" => DataType::Interval(self.parenthesized(Self::interval_unit)?),
+            "FixedSizeBinary" => {
+                DataType::FixedSizeBinary(self.parenthesized(Self::number)?)
+            }
+            "Decimal128" => {
+                let (precision, scale) = self.precision_and_scale()?;
+                DataType::Decimal128(precision, scale)
+            }
+            "Decimal256" => {
+                let (precision, scale) = self.precision_and_scale()?;
+                DataType::Decimal256(precision, scale)
+            }
+            "Dictionary" => {
+                self.expect("(")?;
+                let key_type = self.data_type()?;
+                self.expect(",")?;
+                let value_type = self.data_type()?;
+                self.expect(")")?;
+                DataType::Dictionary(Box::new(key_type), Box::new(value_type))
+            }
+            "List" => DataType::List(Box::new(self.parenthesized(Self::field)?)),
+            "LargeList" => {
+                DataType::LargeList(Box::new(self.parenthesized(Self::field)?))
+            }
+            "FixedSizeList" => {
+                self.expect("(")?;
+                let field = self.field()?;
+                self.expect(",")?;
+                let size = self.number()?;
+                self.expect(")")?;
+                DataType::FixedSizeList(Box::new(field), size)
+            }
+            "Struct" => {
+                self.expect("(")?;
+                let fields = self.list("[", "]", Self::field)?;
+                self.expect(")")?;
+                DataType::Struct(fields)
+            }
+            "Union" => {
+                self.expect("(")?;
+                let fields = self.list("[", "]", Self::field)?;
+                self.expect(",")?;
+                let type_ids = self.list("[", "]", Self::number)?;
+                self.expect(",")?;
+                let mode = match self.word()? {
+                    "Sparse" => UnionMode::Sparse,
+                    "Dense" => UnionMode::Dense,
+                    other => {
+                        return Err(self.error(&format!("invalid union mode '{}'", other)))
+                    }
+                };
+                self.expect(")")?;
+                DataType::Union(fields, type_ids, mode)
+            }
+            "Map" => {
+                self.expect("(")?;
+                let field = self.field()?;
+                self.expect(",")?;
+                let keys_sorted = self.boolean()?;
+                self.expect(")")?;
+                DataType::Map(Box::new(field), keys_sorted)
+            }
+            other => return Err(self.error(&format!("unknown data type '{}'", other))),
+        };
+        Ok(data_type)
+    }
+
+    fn parenthesized<T>(
+        &mut self,
+        inner: impl FnOnce(&mut Self) -> Result<T>,
+    ) -> Result<T> {
+        self.expect("(")?;
+        let value = inner(self)?;
+        self.expect(")")?;
+        Ok(value)
+    }
+
+    fn precision_and_scale(&mut self) -> Result<(u8, i8)> {
+        self.expect("(")?;
+        let precision = self.number()?;
+        self.expect(",")?;
+        let scale = self.number()?;
+        self.expect(")")?;
+        Ok((precision, scale))
+    }
+
+    fn time_unit(&mut self) -> Result<TimeUnit> {
+        match self.word()? {
+            "Second" => Ok(TimeUnit::Second),
+            "Millisecond" => Ok(TimeUnit::Millisecond),
+            "Microsecond" => Ok(TimeUnit::Microsecond),
+            "Nanosecond" => Ok(TimeUnit::Nanosecond),
+            other => Err(self.error(&format!("invalid time unit '{}'", other))),
+        }
+    }
+
+    fn interval_unit(&mut self) -> Result<IntervalUnit> {
+        match self.word()? {
+            "YearMonth" => Ok(IntervalUnit::YearMonth),
+            "DayTime" => Ok(IntervalUnit::DayTime),
+            "MonthDayNano" => Ok(IntervalUnit::MonthDayNano),
+            other => Err(self.error(&format!("invalid interval unit '{}'", other))),
+        }
+    }
+
+    /// Parses the key of the next member of a struct, such as `name:`
+    fn key(&mut self, key: &str) -> Result<()> {
+        if self.word()? != key {
+            return Err(self.error(&format!("expected '{}'", key)));
+        }
+        self.expect(":")
+    }
+
+    fn field(&mut self) -> Result<Field> {
+        self.expect("Field")?;
+        self.expect("{")?;
+        self.key("name")?;
+        let name = self.string()?;
+        self.expect(",")?;
+        self.key("data_type")?;
+        let data_type = self.data_type()?;
+        self.expect(",")?;
+        self.key("nullable")?;
+        let nullable = self.boolean()?;
+        self.expect(",")?;
+        self.key("dict_id")?;
+        let dict_id = self.number()?;
+        self.expect(",")?;
+        self.key("dict_is_ordered")?;
+        let dict_is_ordered = self.boolean()?;
+        self.expect(",")?;
+        self.key("metadata")?;
+        let metadata = match self.word()? {
+            "None" => None,
+            "Some" => {
+                self.expect("(")?;
+                let entries = self.list("{", "}", |parser| {
+                    let key = parser.string()?;
+                    parser.expect(":")?;
+                    Ok((key, parser.string()?))
+                })?;
+                self.expect(")")?;
+                Some(entries.into_iter().collect::<BTreeMap<_, _>>())
+            }
+            other => return Err(self.error(&format!("invalid metadata '{}'", other))),
+        };
+        self.expect("}")?;
+        Ok(
+            Field::new_dict(&name, data_type, nullable, dict_id, dict_is_ordered)
+                .with_metadata(metadata),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_data_type_round_trip() {
+        let field = |name: &str, data_type: DataType| Field::new(name, data_type, true);
+        let data_types = vec![
+            DataType::Null,
+            DataType::Boolean,
+            DataType::Int8,
+            DataType::UInt64,
+            DataType::Float16,
+            DataType::Float64,
+            DataType::Utf8,
+            DataType::LargeBinary,
+            DataType::Date32,
+            DataType::Timestamp(TimeUnit::Nanosecond, None),
+            DataType::Timestamp(TimeUnit::Second, Some("+08:00".to_string())),
+            DataType::Timestamp(
+                TimeUnit::Millisecond,
+                Some("America/New_York".to_string()),
+            ),
+            DataType::Time32(TimeUnit::Millisecond),
+            DataType::Time64(TimeUnit::Microsecond),
+            DataType::Duration(TimeUnit::Second),
+            DataType::Interval(IntervalUnit::MonthDayNano),
+            DataType::FixedSizeBinary(16),
+            DataType::Decimal128(38, 10),
+            DataType::Decimal128(5, -2),
+            DataType::Decimal256(76, 0),
+            DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8)),
+            DataType::List(Box::new(field("item", DataType::Int64))),
+            DataType::LargeList(Box::new(
+                field("a \"quoted\" name\n", DataType::Utf8).with_metadata(Some(
+                    vec![("k".to_string(), "v".to_string())]
+                        .into_iter()
+                        .collect(),
+                )),
+            )),
+            DataType::FixedSizeList(Box::new(field("item", DataType::Float32)), 3),
+            DataType::Struct(vec![
+                field("a", DataType::Int32),
+                Field::new_dict(
+                    "b",
+                    DataType::Dictionary(
+                        Box::new(DataType::UInt8),
+                        Box::new(DataType::LargeUtf8),
+                    ),
+                    false,
+                    1,
+                    true,
+                ),
+                field(
+                    "c",
+                    DataType::Struct(vec![field(
+                        "d",
+                        DataType::List(Box::new(field("item", DataType::Boolean))),
+                    )]),
+                ),
+            ]),
+            DataType::Struct(vec![]),
+            DataType::Union(
+                vec![field("a", DataType::Int32), field("b", DataType::Utf8)],
+                vec![0, 1],
+                UnionMode::Dense,
+            ),
+            DataType::Map(
+                Box::new(field(
+                    "entries",
+                    DataType::Struct(vec![
+                        Field::new("key", DataType::Utf8, false),
+                        field("value", DataType::Int64),
+                    ]),
+                )),
+                false,
+            ),
+        ];
+
+        for data_type in data_types {
+            let text = data_type.to_string();
+            let parsed = parse_data_type(&text)
+                .unwrap_or_else(|e| panic!("parsing {}: {}", text, e));
+            assert_eq!(parsed, data_type, "parsing {}", text);
+        }
+    }
+
+    #[test]
+    fn parse_data_type_errors() {
+        for (text, message) in [
+            ("", "expected a word at position 0"),
+            ("Foo", "unknown data type 'Foo' at position 3"),
+            ("Int32 Int32", "expected the end of the type at position 6"),
+            ("Timestamp(Nanosecond", "expected ',' at position 20"),
+            (
+                "Timestamp(Hour, None)",
+                "invalid time unit 'Hour' at position 14",
+            ),
+            ("Dictionary(Int32, )", "expected a word at position 18"),
+            ("Decimal128(300, 2)", "invalid number '300' at position 14"),
+            (
+                "List(Field { name: \"item })",
+                "unterminated string at position 20",
+            ),
+        ] {
+            let err = parse_data_type(text).unwrap_err().to_string();
+            assert_eq!(
+                err,
+                format!(
+                    "Error during planning: Unsupported type '{}': {}",
+                    text, message
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn create_arrow_cast_args() {
+        let cast = create_arrow_cast(vec![
+            Expr::Literal(ScalarValue::Int64(Some(1))),
+            Expr::Literal(ScalarValue::Utf8(Some(
+                "Dictionary(Int8, Utf8)".to_string(),
+            ))),
+        ])
+        .unwrap();
+        assert_eq!(
+            cast,
+            Expr::Cast(Cast::new(
+                Box::new(Expr::Literal(ScalarValue::Int64(Some(1)))),
+                DataType::Dictionary(Box::new(DataType::Int8), Box::new(DataType::Utf8)),
+            ))
+        );
+
+        let err = create_arrow_cast(vec![Expr::Literal(ScalarValue::Int64(Some(1)))])
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Error during planning: arrow_cast needs 2 arguments, 1 provided"
+        );
+    }
+}
@@ -26,6 +26,7 @@ use sqlparser::{
     tokenizer::{Token, Tokenizer},
 };
 use std::collections::HashMap;
+use std::iter::Peekable;
 use std::{collections::VecDeque, fmt};
 
 // Use `Parser::expected` instead, if possible
@@ -62,7 +63,7 @@ pub struct CreateExternalTable {
     pub table_partition_cols: Vec<String>,
     /// Option to not error if table already exists
     pub if_not_exists: bool,
-    /// File compression type (GZIP, BZIP2)
+    /// File compression type (GZIP, BZIP2, XZ, ZSTD)
     pub file_compression_type: String,
     /// Table(provider) specific options
     pub options: HashMap<String, String>,
@@ -100,6 +101,216 @@ pub enum Statement {
     DescribeTable(DescribeTable),
 }
 
+/// Whether `token` is the keyword `keyword`
+fn is_keyword(token: Option<&Token>, keyword: Keyword) -> bool {
+    matches!(token, Some(Token::Word(w)) if w.keyword == keyword)
+}
+
+/// The index of the last token of `tokens[..end]` that is not whitespace
+fn last_significant(tokens: &[Token], end: usize) -> Option<usize> {
+    (0..end)
+        .rev()
+        .find(|i| !matches!(tokens[*i], Token::Whitespace(_)))
+}
+
+/// The next token of `tokens` that is not whitespace, skipping the whitespace
+/// before it
+fn next_significant(tokens: &mut Peekable<impl Iterator<Item = Token>>) -> Option<Token> {
+    skip_whitespace(tokens);
+    tokens.next()
+}
+
+/// Skip the whitespace at the start of `tokens`
+fn skip_whitespace(tokens: &mut Peekable<impl Iterator<Item = Token>>) {
+    while matches!(tokens.peek(), Some(Token::Whitespace(_))) {
+        tokens.next();
+    }
+}
+
+/// The aggregates whose values can be ordered with `ORDER BY` in their call
+const ORDERED_AGGREGATES: &[&str] = &["array_agg", "first_value", "last_value"];
+
+/// Name of the function call the `ORDER BY` of an ordered aggregate is
+/// rewritten into, as `__datafusion_aggregate_order_by(key, descending,
+/// nulls_first)`, since sqlparser does not support ordering the arguments of
+/// a function call.
+pub(crate) const AGGREGATE_ORDER_BY_FUNCTION: &str = "__datafusion_aggregate_order_by";
+
+/// The `ORDER BY` of a pending call of an ordered aggregate
+#[derive(Default)]
+struct AggregateOrderBy {
+    descending: bool,
+    nulls_first: Option<bool>,
+}
+
+/// Rewrite every `agg(value ORDER BY key [ASC | DESC] [NULLS FIRST | LAST])`
+/// call of the [`ORDERED_AGGREGATES`] into
+/// `agg(value, __datafusion_aggregate_order_by(key, descending, nulls_first))`,
+/// which the planner turns back into an aggregate ordered by `key`
+fn rewrite_ordered_aggregates(tokens: Vec<Token>) -> Result<Vec<Token>, ParserError> {
+    let mut rewritten: Vec<Token> = Vec::with_capacity(tokens.len());
+    let mut tokens = tokens.into_iter().peekable();
+    // for each pending call of an ordered aggregate, the depth of the
+    // parentheses of its arguments and its ORDER BY, if read
+    let mut calls: Vec<(usize, Option<AggregateOrderBy>)> = vec![];
+    let mut depth = 0;
+    while let Some(token) = tokens.next() {
+        if let Token::Whitespace(_) = token {
+            rewritten.push(token);
+            continue;
+        }
+        let in_call =
+            matches!(calls.last(), Some((call_depth, _)) if *call_depth == depth);
+        let ordered = in_call && matches!(calls.last(), Some((_, Some(_))));
+        match &token {
+            Token::LParen => {
+                let ordered_aggregate = matches!(
+                    last_significant(&rewritten, rewritten.len()).map(|i| &rewritten[i]),
+                    Some(Token::Word(w)) if w.quote_style.is_none()
+                        && ORDERED_AGGREGATES.contains(&w.value.to_lowercase().as_str())
+                );
+                depth += 1;
+                if ordered_aggregate {
+                    calls.push((depth, None));
+                }
+            }
+            Token::RParen => {
+                if let Some((_, Some(order_by))) = in_call.then(|| calls.pop()).flatten()
+                {
+                    // nulls are first when descending unless stated otherwise
+                    let nulls_first = order_by.nulls_first.unwrap_or(order_by.descending);
+                    for flag in [order_by.descending, nulls_first] {
+                        rewritten.push(Token::Comma);
+                        rewritten.push(Token::make_word(
+                            if flag { "true" } else { "false" },
+                            None,
+                        ));
+                    }
+                    // closes the call of the ORDER BY
+                    rewritten.push(Token::RParen);
+                }
+                depth = depth.saturating_sub(1);
+            }
+            Token::Word(w) if in_call && !ordered && w.keyword == Keyword::ORDER => {
+                if !is_keyword(next_significant(&mut tokens).as_ref(), Keyword::BY) {
+                    return parser_err!("Expected BY after ORDER");
+                }
+                if let Some(call) = calls.last_mut() {
+                    call.1 = Some(AggregateOrderBy::default());
+                }
+                rewritten.push(Token::Comma);
+                rewritten.push(Token::make_word(AGGREGATE_ORDER_BY_FUNCTION, None));
+                rewritten.push(Token::LParen);
+                continue;
+            }
+            Token::Word(w)
+                if ordered && matches!(w.keyword, Keyword::ASC | Keyword::DESC) =>
+            {
+                if let Some((_, Some(order_by))) = calls.last_mut() {
+                    order_by.descending = w.keyword == Keyword::DESC;
+                }
+                continue;
+            }
+            Token::Word(w) if ordered && w.keyword == Keyword::NULLS => {
+                let next = next_significant(&mut tokens);
+                let nulls_first = if is_keyword(next.as_ref(), Keyword::FIRST) {
+                    true
+                } else if is_keyword(next.as_ref(), Keyword::LAST) {
+                    false
+                } else {
+                    return parser_err!("Expected FIRST or LAST after NULLS");
+                };
+                if let Some((_, Some(order_by))) = calls.last_mut() {
+                    order_by.nulls_first = Some(nulls_first);
+                }
+                continue;
+            }
+            Token::Comma if ordered => {
+                return parser_err!("Only one ORDER BY key is supported in an aggregate");
+            }
+            _ => {}
+        }
+        rewritten.push(token);
+    }
+    Ok(rewritten)
+}
+
+/// Name of the function call `agg(args) FILTER (WHERE condition)` is rewritten
+/// into, as `__datafusion_aggregate_filter(agg(args), condition)`, since
+/// sqlparser only supports aggregate filters in some dialects.
+pub(crate) const AGGREGATE_FILTER_FUNCTION: &str = "__datafusion_aggregate_filter";
+
+/// The index of the first token of the possibly qualified function name
+/// `tokens` end with
+fn function_name_start(tokens: &[Token]) -> usize {
+    let mut start = last_significant(tokens, tokens.len()).unwrap_or(0);
+    while let Some(period) = last_significant(tokens, start) {
+        match last_significant(tokens, period) {
+            Some(name)
+                if tokens[period] == Token::Period
+                    && matches!(tokens[name], Token::Word(_)) =>
+            {
+                start = name
+            }
+            _ => break,
+        }
+    }
+    start
+}
+
+/// Rewrite every `agg(args) FILTER (WHERE condition)` into
+/// `__datafusion_aggregate_filter(agg(args), condition)`, which the planner
+/// turns back into an aggregate with a filter
+fn rewrite_aggregate_filters(tokens: Vec<Token>) -> Result<Vec<Token>, ParserError> {
+    let mut rewritten: Vec<Token> = Vec::with_capacity(tokens.len() + 2);
+    let mut tokens = tokens.into_iter().peekable();
+    // for each pending parenthesis, the index in `rewritten` of the name of
+    // the function it calls, if any
+    let mut calls: Vec<Option<usize>> = vec![];
+    // the index of the name of the function whose call was closed by the
+    // last token
+    let mut closed_call = None;
+    while let Some(token) = tokens.next() {
+        if let Token::Whitespace(_) = token {
+            rewritten.push(token);
+            continue;
+        }
+        if let Some(start) = closed_call.take() {
+            let filter = is_keyword(Some(&token), Keyword::FILTER);
+            if filter {
+                skip_whitespace(&mut tokens);
+            }
+            if filter && tokens.peek() == Some(&Token::LParen) {
+                tokens.next();
+                if !is_keyword(next_significant(&mut tokens).as_ref(), Keyword::WHERE) {
+                    return parser_err!("Expected WHERE after FILTER (");
+                }
+                rewritten.insert(start, Token::LParen);
+                rewritten
+                    .insert(start, Token::make_word(AGGREGATE_FILTER_FUNCTION, None));
+                rewritten.push(Token::Comma);
+                // the parenthesis of the filter closes the rewritten call
+                calls.push(None);
+                continue;
+            }
+        }
+        match &token {
+            Token::LParen => {
+                calls.push(match last_significant(&rewritten, rewritten.len()) {
+                    Some(i) if matches!(rewritten[i], Token::Word(_)) => {
+                        Some(function_name_start(&rewritten))
+                    }
+                    _ => None,
+                })
+            }
+            Token::RParen => closed_call = calls.pop().flatten(),
+            _ => {}
+        }
+        rewritten.push(token);
+    }
+    Ok(rewritten)
+}
+
 /// SQL Parser
 pub struct DFParser<'a> {
     parser: Parser<'a>,
@@ -118,7 +329,8 @@ impl<'a> DFParser<'a> {
         dialect: &'a dyn Dialect,
     ) -> Result<Self, ParserError> {
         let mut tokenizer = Tokenizer::new(dialect, sql);
-        let tokens = tokenizer.tokenize()?;
+        let tokens = rewrite_ordered_aggregates(tokenizer.tokenize()?)?;
+        let tokens = rewrite_aggregate_filters(tokens)?;
 
         Ok(DFParser {
             parser: Parser::new(tokens, dialect),
@@ -387,7 +599,7 @@ impl<'a> DFParser<'a> {
     fn parse_file_compression_type(&mut self) -> Result<String, ParserError> {
         match self.parser.next_token() {
             Token::Word(w) => parse_file_compression_type(&w.value),
-            unexpected => self.expected("one of GZIP, BZIP2", unexpected),
+            unexpected => self.expected("one of GZIP, BZIP2, XZ, ZSTD", unexpected),
         }
     }
 
@@ -586,6 +798,8 @@ mod tests {
         let sqls = vec![
             ("CREATE EXTERNAL TABLE t(c1 int) STORED AS CSV COMPRESSION TYPE GZIP LOCATION 'foo.csv'", "GZIP"),
             ("CREATE EXTERNAL TABLE t(c1 int) STORED AS CSV COMPRESSION TYPE BZIP2 LOCATION 'foo.csv'", "BZIP2"),
+            ("CREATE EXTERNAL TABLE t(c1 int) STORED AS CSV COMPRESSION TYPE XZ LOCATION 'foo.csv'", "XZ"),
+            ("CREATE EXTERNAL TABLE t(c1 int) STORED AS CSV COMPRESSION TYPE ZSTD LOCATION 'foo.csv'", "ZSTD"),
         ];
         for (sql, file_compression_type) in sqls {
             let expected = Statement::CreateExternalTable(CreateExternalTable {
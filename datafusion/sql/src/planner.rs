@@ -23,6 +23,7 @@ use datafusion_common::parsers::parse_interval;
 use datafusion_common::{context, ToDFSchema};
 use datafusion_expr::expr_rewriter::normalize_col;
 use datafusion_expr::expr_rewriter::normalize_col_with_schemas;
+use datafusion_expr::expr_rewriter::normalize_projected_col;
 use datafusion_expr::logical_plan::{
     Analyze, CreateCatalog, CreateCatalogSchema,
     CreateExternalTable as PlanCreateExternalTable, CreateMemoryTable, CreateView,
@@ -32,11 +33,11 @@ use datafusion_expr::logical_plan::{
 use datafusion_expr::utils::{
     can_hash, expand_qualified_wildcard, expand_wildcard, expr_as_column_expr,
     expr_to_columns, find_aggregate_exprs, find_column_exprs, find_window_exprs,
-    COUNT_STAR_EXPANSION,
+    resolve_using_join_columns, COUNT_STAR_EXPANSION,
 };
 use datafusion_expr::{
-    and, col, lit, AggregateFunction, AggregateUDF, Expr, ExprSchemable, GetIndexedField,
-    Operator, ScalarUDF, WindowFrame, WindowFrameUnits,
+    and, col, lit, ordered_aggregate_args, AggregateFunction, AggregateUDF, Expr,
+    ExprSchemable, GetIndexedField, Operator, ScalarUDF, WindowFrame, WindowFrameUnits,
 };
 use datafusion_expr::{
     window_function::WindowFunction, BuiltinScalarFunction, TableSource,
@@ -46,6 +47,7 @@ use std::str::FromStr;
 use std::sync::Arc;
 use std::{convert::TryInto, vec};
 
+use crate::arrow_cast::create_arrow_cast;
 use crate::utils::{make_decimal_type, normalize_ident, resolve_columns};
 use datafusion_common::TableReference;
 use datafusion_common::{
@@ -58,8 +60,8 @@ use datafusion_expr::Expr::Alias;
 
 use sqlparser::ast::TimezoneInfo;
 use sqlparser::ast::{
-    BinaryOperator, DataType as SQLDataType, DateTimeField, Expr as SQLExpr, FunctionArg,
-    FunctionArgExpr, Ident, Join, JoinConstraint, JoinOperator, ObjectName,
+    BinaryOperator, DataType as SQLDataType, DateTimeField, Expr as SQLExpr, Function,
+    FunctionArg, FunctionArgExpr, Ident, Join, JoinConstraint, JoinOperator, ObjectName,
     Offset as SQLOffset, Query, Select, SelectItem, SetExpr, SetOperator,
     ShowCreateObject, ShowStatementFilter, TableAlias, TableFactor, TableWithJoins,
     TrimWhereField, UnaryOperator, Value, Values as SQLValues,
@@ -71,13 +73,71 @@ use sqlparser::parser::ParserError::ParserError;
 use sqlparser::ast::ExactNumberInfo;
 
 use super::{
-    parser::DFParser,
+    parser::{DFParser, AGGREGATE_FILTER_FUNCTION, AGGREGATE_ORDER_BY_FUNCTION},
     utils::{
         check_columns_satisfy_exprs, extract_aliases, rebase_expr,
         resolve_aliases_to_exprs, resolve_positions_to_exprs,
     },
 };
 
+/// Whether `function` is a function `ON`, which is how sqlparser reads the
+/// expressions of a `DISTINCT ON`
+fn is_distinct_on(function: &Function) -> bool {
+    function.name.0.len() == 1
+        && function.name.0[0].quote_style.is_none()
+        && function.name.0[0].value.eq_ignore_ascii_case("on")
+}
+
+/// Whether `expr` starts with the expressions of a `DISTINCT ON`
+fn starts_with_distinct_on(expr: &SQLExpr) -> bool {
+    match expr {
+        SQLExpr::Function(function) => is_distinct_on(function),
+        SQLExpr::BinaryOp { left, .. } => starts_with_distinct_on(left),
+        _ => false,
+    }
+}
+
+/// Take the expressions of a `SELECT DISTINCT ON (exprs)` out of `select`,
+/// leaving a plain `SELECT`.
+///
+/// Not supporting `DISTINCT ON`, sqlparser reads
+/// `SELECT DISTINCT ON (exprs) column, ...` as a `SELECT DISTINCT` of the call
+/// of a function `ON` aliased as the first selected column, so that only a
+/// column name can be selected first.
+fn take_distinct_on(select: &mut Select) -> Result<Option<Vec<SQLExpr>>> {
+    if !select.distinct {
+        return Ok(None);
+    }
+    let (args, column) = match select.projection.first() {
+        Some(SelectItem::ExprWithAlias {
+            expr: SQLExpr::Function(function),
+            alias,
+        }) if is_distinct_on(function) => (function.args.clone(), alias.clone()),
+        Some(SelectItem::UnnamedExpr(expr) | SelectItem::ExprWithAlias { expr, .. })
+            if starts_with_distinct_on(expr) =>
+        {
+            return Err(DataFusionError::NotImplemented(
+                "DISTINCT ON is only supported when a column name is selected first"
+                    .to_string(),
+            ));
+        }
+        _ => return Ok(None),
+    };
+    select.projection[0] = SelectItem::UnnamedExpr(SQLExpr::Identifier(column));
+    select.distinct = false;
+    let on_expr = args
+        .into_iter()
+        .map(|arg| match arg {
+            FunctionArg::Unnamed(FunctionArgExpr::Expr(e)) => Ok(e),
+            arg => Err(DataFusionError::Plan(format!(
+                "Unsupported DISTINCT ON expression {}",
+                arg
+            ))),
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(Some(on_expr))
+}
+
 /// The ContextProvider trait allows the query planner to obtain meta-data about tables and
 /// functions referenced in SQL statements
 pub trait ContextProvider {
@@ -98,6 +158,15 @@ pub struct SqlToRel<'a, S: ContextProvider> {
     schema_provider: &'a S,
 }
 
+/// The error of a call of the function DFParser rewrites the `ORDER BY` of an
+/// ordered aggregate into that it did not produce
+fn invalid_order_by() -> DataFusionError {
+    DataFusionError::Internal(format!(
+        "Invalid arguments of {}",
+        AGGREGATE_ORDER_BY_FUNCTION
+    ))
+}
+
 fn plan_key(key: SQLExpr) -> Result<ScalarValue> {
     let scalar = match key {
         SQLExpr::Value(Value::Number(s, _)) => ScalarValue::Int64(Some(
@@ -180,6 +249,7 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                 with_options,
                 if_not_exists,
                 or_replace,
+                temporary,
                 ..
             } if columns.is_empty()
                 && constraints.is_empty()
@@ -193,6 +263,7 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                     input: Arc::new(plan),
                     if_not_exists,
                     or_replace,
+                    temporary,
                 }))
             }
             Statement::CreateView {
@@ -399,7 +470,20 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                 ctes.insert(cte_name, logical_plan);
             }
         }
-        let plan = self.set_expr_to_plan(*set_expr, alias, ctes, outer_query_schema)?;
+        let plan = match *set_expr {
+            // the ORDER BY of a query that is a single SELECT also orders the
+            // rows of its DISTINCT ON groups
+            SetExpr::Select(select) => self.select_to_plan(
+                *select,
+                ctes,
+                alias,
+                outer_query_schema,
+                &query.order_by,
+            )?,
+            set_expr => {
+                self.set_expr_to_plan(set_expr, alias, ctes, outer_query_schema)?
+            }
+        };
         let plan = self.order_by(plan, query.order_by)?;
         self.limit(plan, query.offset, query.limit)
     }
@@ -413,7 +497,7 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
     ) -> Result<LogicalPlan> {
         match set_expr {
             SetExpr::Select(s) => {
-                self.select_to_plan(*s, ctes, alias, outer_query_schema)
+                self.select_to_plan(*s, ctes, alias, outer_query_schema, &[])
             }
             SetExpr::Values(v) => self.sql_values_to_plan(v),
             SetExpr::SetOperation {
@@ -667,6 +751,7 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         match constraint {
             JoinConstraint::On(sql_expr) => {
                 let mut keys: Vec<(Column, Column)> = vec![];
+                let mut null_safe_keys: Vec<(Column, Column)> = vec![];
                 let join_schema = left.schema().join(right.schema())?;
 
                 // parse ON expression
@@ -679,11 +764,28 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                 extract_join_keys(
                     expr,
                     &mut keys,
+                    &mut null_safe_keys,
                     &mut filter,
                     left.schema(),
                     right.schema(),
                 );
 
+                // nulls are equal for all the keys of a join or for none, so
+                // the keys compared with IS NOT DISTINCT FROM are only used
+                // as such without any compared with `=`
+                let null_equals_null = keys.is_empty() && !null_safe_keys.is_empty();
+                if null_equals_null {
+                    keys = null_safe_keys;
+                } else {
+                    filter.extend(null_safe_keys.into_iter().map(|(l, r)| {
+                        Expr::BinaryExpr(BinaryExpr::new(
+                            Box::new(Expr::Column(l)),
+                            Operator::IsNotDistinctFrom,
+                            Box::new(Expr::Column(r)),
+                        ))
+                    }));
+                }
+
                 let (left_keys, right_keys): (Vec<Column>, Vec<Column>) =
                     keys.into_iter().unzip();
 
@@ -703,7 +805,7 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                     .into_iter()
                     .reduce(Expr::and);
 
-                if left_keys.is_empty() {
+                if left_keys.is_empty() && join_type == JoinType::Inner {
                     // When we don't have join keys, use cross join
                     let join = LogicalPlanBuilder::from(left).cross_join(&right)?;
                     join_filter
@@ -712,7 +814,13 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                         .build()
                 } else {
                     LogicalPlanBuilder::from(left)
-                        .join(&right, join_type, (left_keys, right_keys), join_filter)?
+                        .join_detailed(
+                            &right,
+                            join_type,
+                            (left_keys, right_keys),
+                            join_filter,
+                            null_equals_null,
+                        )?
                         .build()
                 }
             }
@@ -726,10 +834,37 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                     .build()
             }
             JoinConstraint::Natural => {
-                // https://issues.apache.org/jira/browse/ARROW-10727
-                Err(DataFusionError::NotImplemented(
-                    "NATURAL JOIN is not supported (https://issues.apache.org/jira/browse/ARROW-10727)".to_string(),
-                ))
+                // join using the unqualified names found on both sides
+                let right_names: HashSet<&String> =
+                    right.schema().fields().iter().map(|f| f.name()).collect();
+                let mut seen = HashSet::new();
+                let keys: Vec<Column> = left
+                    .schema()
+                    .fields()
+                    .iter()
+                    .map(|f| f.name())
+                    .filter(|name| right_names.contains(name) && seen.insert(*name))
+                    .map(|name| Column::from_name(name.as_str()))
+                    .collect();
+                if keys.is_empty() {
+                    let field_names = |plan: &LogicalPlan| {
+                        plan.schema()
+                            .fields()
+                            .iter()
+                            .map(|f| format!("'{}'", f.name()))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    };
+                    return Err(DataFusionError::Plan(format!(
+                        "NATURAL JOIN requires a column name common to both sides, \
+                        but the left side has columns {} and the right side has columns {}",
+                        field_names(&left),
+                        field_names(&right),
+                    )));
+                }
+                LogicalPlanBuilder::from(left)
+                    .join_using(&right, join_type, keys)?
+                    .build()
             }
             JoinConstraint::None => Err(DataFusionError::NotImplemented(
                 "NONE constraint is not supported".to_string(),
@@ -994,6 +1129,7 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                             join_columns.insert(r.clone());
                         }
                         let x: Vec<&DFSchemaRef> = all_schemas.iter().collect();
+                        let filter_expr = resolve_using_join_columns(filter_expr, &left)?;
                         let filter_expr = normalize_col_with_schemas(
                             filter_expr,
                             x.as_slice(),
@@ -1022,13 +1158,15 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         }
     }
 
-    /// Generate a logic plan from an SQL select
+    /// Generate a logical plan from a SELECT, whose DISTINCT ON groups, if any,
+    /// are ordered by `order_by`
     fn select_to_plan(
         &self,
-        select: Select,
+        mut select: Select,
         ctes: &mut HashMap<String, LogicalPlan>,
         alias: Option<String>,
         outer_query_schema: Option<&DFSchema>,
+        order_by: &[OrderByExpr],
     ) -> Result<LogicalPlan> {
         // check for unsupported syntax first
         if !select.cluster_by.is_empty() {
@@ -1043,6 +1181,7 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         if select.top.is_some() {
             return Err(DataFusionError::NotImplemented("TOP".to_string()));
         }
+        let distinct_on = take_distinct_on(&mut select)?;
 
         // process `from` clause
         let plans = self.plan_from_tables(select.from, ctes, outer_query_schema)?;
@@ -1091,13 +1230,56 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                 //   SELECT c1, MAX(c2) AS m FROM t GROUP BY c1 HAVING MAX(c2) > 10;
                 //
                 let having_expr = resolve_aliases_to_exprs(&having_expr, &alias_map)?;
+                let having_expr = resolve_using_join_columns(having_expr, &plan)?;
                 normalize_col(having_expr, &projected_plan)
             })
             .transpose()?;
 
+        // The DISTINCT ON and ORDER BY expressions of a DISTINCT ON are
+        // evaluated over the rows before the projection, following the
+        // selected expressions.
+        let mut select_and_distinct_on_exprs = select_exprs.clone();
+        let distinct_on_len = match distinct_on {
+            Some(on_expr) => {
+                let on_len = on_expr.len();
+                for e in on_expr {
+                    let e = self.distinct_on_expr(
+                        e,
+                        &plan,
+                        &projected_plan,
+                        &select_exprs,
+                        ctes,
+                    )?;
+                    select_and_distinct_on_exprs.push(e);
+                }
+                for e in order_by {
+                    let OrderByExpr {
+                        expr,
+                        asc,
+                        nulls_first,
+                    } = e.clone();
+                    let expr = self.distinct_on_expr(
+                        expr,
+                        &plan,
+                        &projected_plan,
+                        &select_exprs,
+                        ctes,
+                    )?;
+                    let asc = asc.unwrap_or(true);
+                    select_and_distinct_on_exprs.push(Expr::Sort {
+                        expr: Box::new(expr),
+                        asc,
+                        nulls_first: nulls_first.unwrap_or(!asc),
+                    });
+                }
+                Some(on_len)
+            }
+            None => None,
+        };
+
         // The outer expressions we will search through for
         // aggregates. Aggregates may be sourced from the SELECT...
-        let mut aggr_expr_haystack = select_exprs.clone();
+        let mut aggr_expr_haystack = select_and_distinct_on_exprs.clone();
         // ... or from the HAVING.
         if let Some(having_expr) = &having_expr_opt {
             aggr_expr_haystack.push(having_expr.clone());
@@ -1113,6 +1295,8 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             .map(|e| {
                 let group_by_expr =
                     self.sql_expr_to_logical_expr(e, &combined_schema, ctes)?;
+                // the column of a USING join is the value it is selected as
+                let group_by_expr = resolve_using_join_columns(group_by_expr, &plan)?;
                 // aliases from the projection can conflict with same-named expressions in the input
                 let mut alias_map = alias_map.clone();
                 for f in plan.schema().fields() {
@@ -1136,7 +1320,7 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             if !group_by_exprs.is_empty() || !aggr_exprs.is_empty() {
                 self.aggregate(
                     plan,
-                    &select_exprs,
+                    &select_and_distinct_on_exprs,
                     &having_expr_opt,
                     group_by_exprs,
                     aggr_exprs,
@@ -1157,7 +1341,7 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                     )?;
                 }
 
-                (plan, select_exprs, having_expr_opt)
+                (plan, select_and_distinct_on_exprs, having_expr_opt)
             };
 
         let plan = if let Some(having_expr_post_aggr) = having_expr_post_aggr {
@@ -1185,6 +1369,19 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             plan
         };
 
+        // keep the first row of each DISTINCT ON group
+        let plan = match distinct_on_len {
+            Some(on_len) => {
+                let sort_expr =
+                    select_exprs_post_aggr.split_off(select_exprs.len() + on_len);
+                let on_expr = select_exprs_post_aggr.split_off(select_exprs.len());
+                LogicalPlanBuilder::from(plan)
+                    .distinct_on(on_expr, sort_expr)?
+                    .build()?
+            }
+            None => plan,
+        };
+
         // final projection
         let plan = project_with_alias(plan, select_exprs_post_aggr, alias)?;
 
@@ -1210,6 +1407,26 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         }
     }
 
+    /// Generate a `DISTINCT ON` or `ORDER BY` expression of a `SELECT DISTINCT ON`
+    /// over `plan`, the rows before the projection, in which the aliases and
+    /// positions of the selected expressions stand for these expressions
+    fn distinct_on_expr(
+        &self,
+        expr: SQLExpr,
+        plan: &LogicalPlan,
+        projected_plan: &LogicalPlan,
+        select_exprs: &[Expr],
+        ctes: &mut HashMap<String, LogicalPlan>,
+    ) -> Result<Expr> {
+        let mut combined_schema = (**projected_plan.schema()).clone();
+        combined_schema.merge(plan.schema());
+        let expr = self.sql_expr_to_logical_expr(expr, &combined_schema, ctes)?;
+        let expr = resolve_aliases_to_exprs(&expr, &extract_aliases(select_exprs))?;
+        let expr = resolve_positions_to_exprs(&expr, select_exprs).unwrap_or(expr);
+        let expr = resolve_using_join_columns(expr, plan)?;
+        normalize_col(expr, projected_plan)
+    }
+
     /// Returns the `Expr`'s corresponding to a SQL query's SELECT expressions.
     ///
     /// Wildcards are expanded into the concrete list of columns.
@@ -1517,7 +1734,7 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         match sql {
             SelectItem::UnnamedExpr(expr) => {
                 let expr = self.sql_to_rex(expr, &input_schema, ctes)?;
-                Ok(vec![normalize_col(expr, plan)?])
+                Ok(vec![normalize_projected_col(expr, plan)?])
             }
             SelectItem::ExprWithAlias { expr, alias } => {
                 let expr = Alias(
@@ -2133,11 +2350,15 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             }
 
             SQLExpr::AggregateExpressionWithFilter { expr, filter } => {
+                let filter = Some(Box::new(self.sql_expr_to_logical_expr(*filter, schema, ctes)?));
                 match self.sql_expr_to_logical_expr(*expr, schema, ctes)? {
                     Expr::AggregateFunction {
                         fun, args, distinct, ..
-                    } => Ok(Expr::AggregateFunction { fun, args, distinct, filter: Some(Box::new(self.sql_expr_to_logical_expr(*filter, schema, ctes)?)) }),
-                    _ => Err(DataFusionError::Internal("AggregateExpressionWithFilter expression was not an AggregateFunction".to_string()))
+                    } => Ok(Expr::AggregateFunction { fun, args, distinct, filter }),
+                    Expr::AggregateUDF { fun, args, .. } => {
+                        Ok(Expr::AggregateUDF { fun, args, filter })
+                    }
+                    _ => Err(DataFusionError::Plan("FILTER is only supported on aggregate functions".to_string()))
                 }
             }
 
@@ -2150,6 +2371,43 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                     normalize_ident(&function.name.0[0])
                 };
 
+                // `agg(args) FILTER (WHERE condition)`, rewritten by DFParser
+                if name == AGGREGATE_FILTER_FUNCTION {
+                    // window functions do not take a filter
+                    if function.over.is_some() {
+                        return Err(DataFusionError::Plan(
+                            "FILTER is not supported on window functions".to_string(),
+                        ));
+                    }
+                    let (expr, filter) = match <[FunctionArg; 2]>::try_from(function.args) {
+                        Ok([
+                            FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)),
+                            FunctionArg::Unnamed(FunctionArgExpr::Expr(filter)),
+                        ]) => (expr, filter),
+                        _ => {
+                            return Err(DataFusionError::Internal(format!(
+                                "Invalid arguments of {}",
+                                AGGREGATE_FILTER_FUNCTION
+                            )))
+                        }
+                    };
+                    let expr = SQLExpr::AggregateExpressionWithFilter {
+                        expr: Box::new(expr),
+                        filter: Box::new(filter),
+                    };
+                    return self.sql_expr_to_logical_expr(expr, schema, ctes);
+                }
+
+                // `ORDER BY` in the call of a function that is not an ordered
+                // aggregate, such as the `first_value` window function
+                if name == AGGREGATE_ORDER_BY_FUNCTION {
+                    return Err(DataFusionError::Plan(
+                        "ORDER BY in a function call is only supported by the \
+                         array_agg, first_value and last_value aggregates"
+                            .to_string(),
+                    ));
+                }
+
                 // first, check SQL reserved words
                 if name == "rollup" {
                     let args = self.function_args_to_expr(function.args, schema)?;
@@ -2159,6 +2417,13 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                     return Ok(Expr::GroupingSet(GroupingSet::Cube(args)));
                 }
 
+                // next, arrow_cast, whose return type is given by its second
+                // argument rather than by the types of its arguments
+                if name == "arrow_cast" {
+                    let args = self.function_args_to_expr(function.args, schema)?;
+                    return create_arrow_cast(args);
+                }
+
                 // next, scalar built-in
                 if let Ok(fun) = BuiltinScalarFunction::from_str(&name) {
                     let args = self.function_args_to_expr(function.args, schema)?;
@@ -2370,12 +2635,63 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                     _ => self.sql_fn_arg_to_logical_expr(a, schema, &mut HashMap::new()),
                 })
                 .collect::<Result<Vec<Expr>>>()?,
+            AggregateFunction::ArrayAgg
+            | AggregateFunction::FirstValue
+            | AggregateFunction::LastValue => {
+                self.ordered_aggregate_args(&fun, args, schema)?
+            }
             _ => self.function_args_to_expr(args, schema)?,
         };
 
         Ok((fun, args))
     }
 
+    /// The arguments of the aggregate `fun` of a value optionally followed by
+    /// the `__datafusion_aggregate_order_by(key, descending, nulls_first)` call
+    /// DFParser rewrites its `ORDER BY` into
+    fn ordered_aggregate_args(
+        &self,
+        fun: &AggregateFunction,
+        mut args: Vec<FunctionArg>,
+        schema: &DFSchema,
+    ) -> Result<Vec<Expr>> {
+        let order_by = match args.as_slice() {
+            [_] => None,
+            [_, FunctionArg::Unnamed(FunctionArgExpr::Expr(SQLExpr::Function(f)))]
+                if f.name.to_string() == AGGREGATE_ORDER_BY_FUNCTION =>
+            {
+                match args.pop() {
+                    Some(FunctionArg::Unnamed(FunctionArgExpr::Expr(
+                        SQLExpr::Function(f),
+                    ))) => Some(self.function_args_to_expr(f.args, schema)?),
+                    _ => None,
+                }
+            }
+            _ => {
+                return Err(DataFusionError::Plan(format!(
+                    "{} takes a single argument, optionally followed by ORDER BY",
+                    fun
+                )))
+            }
+        };
+        let mut args = self.function_args_to_expr(args, schema)?;
+        let (key, descending, nulls_first) = match order_by.as_deref() {
+            None => return Ok(args),
+            Some([key, descending, nulls_first]) => (key, descending, nulls_first),
+            Some(_) => return Err(invalid_order_by()),
+        };
+        match (descending, nulls_first) {
+            (
+                Expr::Literal(ScalarValue::Boolean(Some(descending))),
+                Expr::Literal(ScalarValue::Boolean(Some(nulls_first))),
+            ) => Ok(ordered_aggregate_args(
+                args.remove(0),
+                key.clone().sort(!descending, *nulls_first),
+            )),
+            _ => Err(invalid_order_by()),
+        }
+    }
+
     fn sql_interval_to_expr(
         &self,
         value: SQLExpr,
@@ -2843,37 +3159,51 @@ fn remove_join_expressions(
 fn extract_join_keys(
     expr: Expr,
     accum: &mut Vec<(Column, Column)>,
+    accum_null_safe: &mut Vec<(Column, Column)>,
     accum_filter: &mut Vec<Expr>,
     left_schema: &Arc<DFSchema>,
     right_schema: &Arc<DFSchema>,
 ) {
     match &expr {
         Expr::BinaryExpr(BinaryExpr { left, op, right }) => match op {
-            Operator::Eq => match (left.as_ref(), right.as_ref()) {
-                (Expr::Column(l), Expr::Column(r)) => {
-                    if left_schema.field_from_column(l).is_ok()
-                        && right_schema.field_from_column(r).is_ok()
-                        && can_hash(left_schema.field_from_column(l).unwrap().data_type())
-                    {
-                        accum.push((l.clone(), r.clone()));
-                    } else if left_schema.field_from_column(r).is_ok()
-                        && right_schema.field_from_column(l).is_ok()
-                        && can_hash(left_schema.field_from_column(r).unwrap().data_type())
-                    {
-                        accum.push((r.clone(), l.clone()));
-                    } else {
+            Operator::Eq | Operator::IsNotDistinctFrom => {
+                match (left.as_ref(), right.as_ref()) {
+                    (Expr::Column(l), Expr::Column(r)) => {
+                        // keys compared with IS NOT DISTINCT FROM match nulls
+                        let accum = if *op == Operator::Eq {
+                            &mut *accum
+                        } else {
+                            &mut *accum_null_safe
+                        };
+                        if left_schema.field_from_column(l).is_ok()
+                            && right_schema.field_from_column(r).is_ok()
+                            && can_hash(
+                                left_schema.field_from_column(l).unwrap().data_type(),
+                            )
+                        {
+                            accum.push((l.clone(), r.clone()));
+                        } else if left_schema.field_from_column(r).is_ok()
+                            && right_schema.field_from_column(l).is_ok()
+                            && can_hash(
+                                left_schema.field_from_column(r).unwrap().data_type(),
+                            )
+                        {
+                            accum.push((r.clone(), l.clone()));
+                        } else {
+                            accum_filter.push(expr);
+                        }
+                    }
+                    _other => {
                         accum_filter.push(expr);
                     }
                 }
-                _other => {
-                    accum_filter.push(expr);
-                }
-            },
+            }
             Operator::And => {
                 if let Expr::BinaryExpr(BinaryExpr { left, op: _, right }) = expr {
                     extract_join_keys(
                         *left,
                         accum,
+                        accum_null_safe,
                         accum_filter,
                         left_schema,
                         right_schema,
@@ -2881,6 +3211,7 @@ fn extract_join_keys(
                     extract_join_keys(
                         *right,
                         accum,
+                        accum_null_safe,
                         accum_filter,
                         left_schema,
                         right_schema,
@@ -4241,6 +4572,84 @@ mod tests {
         quick_test(sql, expected);
     }
 
+    #[test]
+    fn select_distinct_on() {
+        // the groups are ordered over the rows before the projection
+        let sql = "SELECT DISTINCT ON (customer_id) customer_id, qty \
+            FROM orders \
+            ORDER BY customer_id, price DESC";
+        let expected = "Projection: orders.customer_id, orders.qty\
+            \n  Sort: orders.customer_id ASC NULLS LAST, orders.price DESC NULLS FIRST\
+            \n    Projection: orders.customer_id, orders.qty, orders.price\
+            \n      Projection: orders.order_id, orders.customer_id, orders.o_item_id, orders.qty, orders.price, orders.delivered\
+            \n        Filter: ROW_NUMBER() PARTITION BY [orders.customer_id] ORDER BY [orders.customer_id ASC NULLS LAST, orders.price DESC NULLS FIRST] = UInt64(1)\
+            \n          WindowAggr: windowExpr=[[ROW_NUMBER() PARTITION BY [orders.customer_id] ORDER BY [orders.customer_id ASC NULLS LAST, orders.price DESC NULLS FIRST]]]\
+            \n            TableScan: orders";
+        quick_test(sql, expected);
+
+        // the selects of a set operation are not ordered by the query
+        let sql = "SELECT DISTINCT ON (customer_id) customer_id, qty FROM orders \
+            UNION ALL SELECT order_id, qty FROM orders";
+        let expected = "Union\
+            \n  Projection: orders.customer_id, orders.qty\
+            \n    Projection: orders.order_id, orders.customer_id, orders.o_item_id, orders.qty, orders.price, orders.delivered\
+            \n      Filter: ROW_NUMBER() PARTITION BY [orders.customer_id] = UInt64(1)\
+            \n        WindowAggr: windowExpr=[[ROW_NUMBER() PARTITION BY [orders.customer_id]]]\
+            \n          TableScan: orders\
+            \n  Projection: orders.order_id, orders.qty\
+            \n    TableScan: orders";
+        quick_test(sql, expected);
+
+        let sql = "SELECT DISTINCT ON (customer_id) customer_id, qty \
+            FROM orders \
+            ORDER BY qty DESC";
+        let err = logical_plan(sql).expect_err("query should have failed");
+        assert!(err.to_string().contains(
+            "SELECT DISTINCT ON expressions must match initial ORDER BY expressions"
+        ));
+
+        let sql = "SELECT DISTINCT ON (customer_id) * FROM orders";
+        let err = logical_plan(sql).expect_err("query should have failed");
+        assert!(err.to_string().contains(
+            "DISTINCT ON is only supported when a column name is selected first"
+        ));
+    }
+
+    #[test]
+    fn join_is_not_distinct_from_keys() {
+        let null_equals_null = |plan: &LogicalPlan| match plan.inputs()[0] {
+            LogicalPlan::Join(join) => join.null_equals_null,
+            other => panic!("expected a join, got {:?}", other),
+        };
+
+        // null keys match each other
+        let sql = "SELECT id, order_id \
+            FROM person \
+            JOIN orders \
+            ON id IS NOT DISTINCT FROM customer_id";
+        let plan = logical_plan(sql).unwrap();
+        let expected = "Projection: person.id, orders.order_id\
+            \n  Inner Join: person.id = orders.customer_id NullEqualsNull\
+            \n    TableScan: person\
+            \n    TableScan: orders";
+        assert_eq!(format!("{:?}", plan), expected);
+        assert!(null_equals_null(&plan));
+
+        // the keys compared with `=` do not match nulls, the others filter
+        // the matches
+        let sql = "SELECT id, order_id \
+            FROM person \
+            JOIN orders \
+            ON id = customer_id AND age IS NOT DISTINCT FROM order_id";
+        let plan = logical_plan(sql).unwrap();
+        let expected = "Projection: person.id, orders.order_id\
+            \n  Inner Join: person.id = orders.customer_id Filter: person.age IS NOT DISTINCT FROM orders.order_id\
+            \n    TableScan: person\
+            \n    TableScan: orders";
+        assert_eq!(format!("{:?}", plan), expected);
+        assert!(!null_equals_null(&plan));
+    }
+
     #[test]
     fn left_equijoin_with_conditions() {
         let sql = "SELECT id, order_id \
@@ -4280,6 +4689,19 @@ mod tests {
         quick_test(sql, expected);
     }
 
+    #[test]
+    fn left_join_without_equijoin_keys() {
+        let sql = "SELECT id, order_id \
+            FROM person \
+            LEFT JOIN orders \
+            ON id < customer_id";
+        let expected = "Projection: person.id, orders.order_id\
+        \n  Left Join:  Filter: person.id < orders.customer_id\
+        \n    TableScan: person\
+        \n    TableScan: orders";
+        quick_test(sql, expected);
+    }
+
     #[test]
     fn join_with_table_name() {
         let sql = "SELECT id, order_id \
@@ -4321,6 +4743,64 @@ mod tests {
         quick_test(sql, expected);
     }
 
+    #[test]
+    fn natural_join() {
+        let sql = "SELECT * FROM orders NATURAL JOIN lineitem";
+        let expected = "Projection: orders.order_id, orders.customer_id, orders.o_item_id, orders.qty, orders.price, orders.delivered, lineitem.l_item_id, lineitem.l_description\
+        \n  Inner Join: Using orders.price = lineitem.price\
+        \n    TableScan: orders\
+        \n    TableScan: lineitem";
+        quick_test(sql, expected);
+
+        let sql = "SELECT * FROM lineitem NATURAL FULL JOIN lineitem AS lineitem2";
+        let expected = "Projection: coalesce(lineitem.l_item_id, lineitem2.l_item_id) AS l_item_id, coalesce(lineitem.l_description, lineitem2.l_description) AS l_description, coalesce(lineitem.price, lineitem2.price) AS price\
+        \n  Full Join: Using lineitem.l_item_id = lineitem2.l_item_id, lineitem.l_description = lineitem2.l_description, lineitem.price = lineitem2.price\
+        \n    TableScan: lineitem\
+        \n    SubqueryAlias: lineitem2\
+        \n      TableScan: lineitem";
+        quick_test(sql, expected);
+    }
+
+    #[test]
+    fn full_join_using_column_references() {
+        let sql = "SELECT id + 1 \
+            FROM person \
+            FULL JOIN person AS person2 \
+            USING (id) \
+            WHERE id > 10";
+        let expected = "Projection: coalesce(person.id, person2.id) + Int64(1)\
+        \n  Filter: coalesce(person.id, person2.id) > Int64(10)\
+        \n    Full Join: Using person.id = person2.id\
+        \n      TableScan: person\
+        \n      SubqueryAlias: person2\
+        \n        TableScan: person";
+        quick_test(sql, expected);
+    }
+
+    #[test]
+    fn natural_join_without_common_columns() {
+        let sql = "SELECT * FROM j1 NATURAL JOIN j2";
+        let err = logical_plan(sql).expect_err("query should have failed");
+        assert_eq!(
+            "Plan(\"NATURAL JOIN requires a column name common to both sides, \
+            but the left side has columns 'j1_id', 'j1_string' \
+            and the right side has columns 'j2_id', 'j2_string'\")",
+            format!("{:?}", err)
+        );
+    }
+
+    #[test]
+    fn join_using_unknown_column() {
+        let sql = "SELECT * FROM person JOIN orders USING (customer_id)";
+        let err = logical_plan(sql).expect_err("query should have failed");
+        assert_eq!(
+            "Schema error: No field named 'customer_id'. Valid fields are 'person'.'id', \
+            'person'.'first_name', 'person'.'last_name', 'person'.'age', 'person'.'state', \
+            'person'.'salary', 'person'.'birth_date', 'person'.'😀'",
+            err.to_string()
+        );
+    }
+
     #[test]
     fn equijoin_explicit_syntax_3_tables() {
         let sql = "SELECT id, order_id, l_description \
@@ -5418,13 +5898,35 @@ mod tests {
         let dialect = &HiveDialect {};
         let sql = "SELECT SUM(age) FILTER (WHERE age > 4) FROM person";
         let plan = logical_plan_with_dialect(sql, dialect)?;
-        let expected = "Projection: SUM(person.age) FILTER (WHERE age > Int64(4))\
-        \n  Aggregate: groupBy=[[]], aggr=[[SUM(person.age) FILTER (WHERE age > Int64(4))]]\
+        let expected = "Projection: SUM(person.age) FILTER (WHERE person.age > Int64(4))\
+        \n  Aggregate: groupBy=[[]], aggr=[[SUM(person.age) FILTER (WHERE person.age > Int64(4))]]\
         \n    TableScan: person".to_string();
         assert_eq!(expected, format!("{}", plan.display_indent()));
         Ok(())
     }
 
+    #[test]
+    fn aggregate_with_filter() {
+        let sql = "SELECT state, COUNT(*) FILTER (WHERE age > 4), COUNT(*), \
+            MAX(age) FILTER (WHERE first_name = 'a') \
+            FROM person GROUP BY state";
+        let expected = "Projection: person.state, COUNT(UInt8(1)) FILTER (WHERE person.age > Int64(4)), COUNT(UInt8(1)), MAX(person.age) FILTER (WHERE person.first_name = Utf8(\"a\"))\
+        \n  Aggregate: groupBy=[[person.state]], aggr=[[COUNT(UInt8(1)) FILTER (WHERE person.age > Int64(4)), COUNT(UInt8(1)), MAX(person.age) FILTER (WHERE person.first_name = Utf8(\"a\"))]]\
+        \n    TableScan: person";
+        quick_test(sql, expected);
+    }
+
+    #[test]
+    fn aggregate_with_filter_over_window() {
+        let sql = "SELECT COUNT(*) FILTER (WHERE age > 4) OVER (PARTITION BY state) \
+            FROM person";
+        let err = logical_plan(sql).expect_err("query should have failed");
+        assert_eq!(
+            "Plan(\"FILTER is not supported on window functions\")",
+            format!("{:?}", err)
+        );
+    }
+
     #[test]
     fn order_by_unaliased_name() {
         // https://github.com/apache/arrow-datafusion/issues/3160